@@ -1,5 +1,7 @@
 //! 이 예제는 Axum에서 HTML 폼 데이터를 수신하고 처리하는 기본 패턴을 보여줍니다.
 //!
+//! `/wizard`에서는 여러 단계로 나뉜 폼(위저드)을 서명된 쿠키로 처리하는 방법을 보여줍니다.
+//!
 //! 실행 방법:
 //!
 //! ```bash
@@ -13,6 +15,8 @@ use axum::{
     routing::get,   // get: HTTP GET 요청용 라우터 생성 함수
     Router,         // Router: 전체 라우팅 트리 구조를 담당하는 타입
 };
+// 서명된 쿠키에 사용할 키
+use axum_extra::extract::cookie::Key;
 
 // Serde를 이용해 폼 데이터를 구조체로 역직렬화
 use serde::Deserialize;
@@ -23,6 +27,9 @@ use tracing_subscriber::{
     util::SubscriberInitExt, // 서브스크라이버 초기화 기능
 };
 
+// 여러 단계 폼(위저드) 예제
+mod wizard;
+
 /// ✨ 메인 함수: 서버 실행
 #[tokio::main]
 async fn main() {
@@ -59,10 +66,15 @@ async fn main() {
 
 // ✨ 라우터 구성 함수
 fn app() -> Router {
-    Router::new().route(
-        "/",                              // "/" 경로에 대해
-        get(show_form).post(accept_form), // GET과 POST 요청을 각각 처리합니다.
-    )
+    // 쿠키 서명 키 (예제에서는 실행할 때마다 새로 생성 → 재시작하면 진행 중인 위저드는 초기화됨)
+    let wizard = wizard::WizardState::new(Key::generate());
+
+    Router::new()
+        .route(
+            "/",                              // "/" 경로에 대해
+            get(show_form).post(accept_form), // GET과 POST 요청을 각각 처리합니다.
+        )
+        .merge(wizard::router(wizard)) // "/wizard/..." 경로의 위저드 라우터 병합
 }
 
 // ✨ GET 요청 처리 핸들러
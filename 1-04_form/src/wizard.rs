@@ -0,0 +1,589 @@
+//! 여러 페이지로 나뉜 폼(위저드) 예제
+//!
+//! 1. `/wizard/account` : 이름, 이메일
+//! 2. `/wizard/address` : 도시, 우편번호
+//! 3. `/wizard/review`  : 입력 내용 확인 후 최종 제출
+//! 4. `/wizard/done`    : 완료 메시지 (flash)
+//!
+//! - 단계별 입력값은 서명된 쿠키(`SignedCookieJar`)에 JSON으로 저장 → 서버에 세션 저장소가 필요 없음
+//!   (서명이라 위조는 막지만 내용은 사용자가 볼 수 있음. 민감한 값이면 `PrivateCookieJar` 사용)
+//! - 단계마다 검증하고, 실패하면 입력값과 에러 메시지를 담아 같은 폼을 다시 렌더링 (422)
+//! - 성공한 POST는 항상 303 See Other로 다음 페이지에 리다이렉트 (redirect-after-post)
+//!   → 새로고침해도 폼이 다시 제출되지 않음
+//! - 다음 페이지에 한 번만 보여줄 메시지는 flash 쿠키로 전달
+
+use axum::{
+    extract::{FromRef, State},
+    http::StatusCode,
+    response::{Html, IntoResponse, Redirect, Response},
+    routing::get,
+    Form, Router,
+};
+use axum_extra::extract::{
+    cookie::{Cookie, Key, SameSite},
+    SignedCookieJar,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+// 쿠키 이름
+const DATA_COOKIE: &str = "wizard";
+const FLASH_COOKIE: &str = "flash";
+
+// ✨ 위저드 상태: 쿠키 서명 키 + 최종 제출된 데이터 저장소
+#[derive(Clone)]
+pub struct WizardState {
+    key: Key,
+    pub submissions: Submissions,
+}
+
+// 최종 제출된 가입 정보 (예제에서는 메모리에 저장)
+pub type Submissions = Arc<Mutex<Vec<Registration>>>;
+
+// SignedCookieJar는 상태에서 Key를 꺼내 쓰므로 FromRef 구현 필요
+impl FromRef<WizardState> for Key {
+    fn from_ref(state: &WizardState) -> Self {
+        state.key.clone()
+    }
+}
+
+impl WizardState {
+    pub fn new(key: Key) -> Self {
+        Self {
+            key,
+            submissions: Submissions::default(),
+        }
+    }
+}
+
+// ✨ 위저드 라우터
+pub fn router(state: WizardState) -> Router {
+    Router::new()
+        .route("/wizard", get(|| async { Redirect::to("/wizard/account") }))
+        .route("/wizard/account", get(show_account).post(submit_account))
+        .route("/wizard/address", get(show_address).post(submit_address))
+        .route("/wizard/review", get(show_review).post(submit_review))
+        .route("/wizard/done", get(show_done))
+        .with_state(state)
+}
+
+// ✨ 쿠키에 저장되는 단계별 입력값 (아직 입력하지 않은 단계는 None)
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+struct WizardData {
+    account: Option<AccountForm>,
+    address: Option<AddressForm>,
+}
+
+// 1단계 폼 (빈 값도 검증 메시지로 안내하기 위해 누락 필드는 빈 문자열로 받음)
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+struct AccountForm {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    email: String,
+}
+
+// 2단계 폼
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+struct AddressForm {
+    #[serde(default)]
+    city: String,
+    #[serde(default)]
+    zip: String,
+}
+
+// 최종 제출된 데이터
+#[derive(Debug, Clone)]
+pub struct Registration {
+    pub name: String,
+    pub email: String,
+    pub city: String,
+    pub zip: String,
+}
+
+// 필드 이름 → 에러 메시지
+type Errors = BTreeMap<&'static str, &'static str>;
+
+// MARK: - ✨ 검증
+
+impl AccountForm {
+    fn normalized(self) -> Self {
+        Self {
+            name: self.name.trim().to_owned(),
+            email: self.email.trim().to_owned(),
+        }
+    }
+
+    fn validate(&self) -> Errors {
+        let mut errors = Errors::new();
+        if self.name.is_empty() {
+            errors.insert("name", "이름을 입력하세요");
+        } else if self.name.chars().count() > 50 {
+            errors.insert("name", "이름은 50자 이하로 입력하세요");
+        }
+        // 예제이므로 간단한 형식 검사만 수행
+        match self.email.split_once('@') {
+            Some((user, domain)) if !user.is_empty() && domain.contains('.') => {}
+            _ => {
+                errors.insert("email", "올바른 이메일 주소를 입력하세요");
+            }
+        }
+        errors
+    }
+}
+
+impl AddressForm {
+    fn normalized(self) -> Self {
+        Self {
+            city: self.city.trim().to_owned(),
+            zip: self.zip.trim().to_owned(),
+        }
+    }
+
+    fn validate(&self) -> Errors {
+        let mut errors = Errors::new();
+        if self.city.is_empty() {
+            errors.insert("city", "도시를 입력하세요");
+        }
+        if self.zip.len() != 5 || !self.zip.bytes().all(|b| b.is_ascii_digit()) {
+            errors.insert("zip", "우편번호는 숫자 5자리입니다");
+        }
+        errors
+    }
+}
+
+// MARK: - ✨ 핸들러
+
+// 1단계: 계정 정보
+async fn show_account(jar: SignedCookieJar) -> (SignedCookieJar, Html<String>) {
+    let (jar, flash) = take_flash(jar);
+    let form = load(&jar).account.unwrap_or_default();
+    (jar, account_page(&form, &Errors::new(), flash.as_deref()))
+}
+
+async fn submit_account(jar: SignedCookieJar, Form(form): Form<AccountForm>) -> Response {
+    let form = form.normalized();
+    let errors = form.validate();
+    if !errors.is_empty() {
+        // 검증 실패: 입력값을 유지한 채 폼을 다시 보여줌
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            account_page(&form, &errors, None),
+        )
+            .into_response();
+    }
+
+    let mut data = load(&jar);
+    data.account = Some(form);
+    (save(jar, &data), Redirect::to("/wizard/address")).into_response()
+}
+
+// 2단계: 주소
+async fn show_address(jar: SignedCookieJar) -> Response {
+    let data = load(&jar);
+    if data.account.is_none() {
+        return redirect_with_flash(jar, "/wizard/account", "먼저 계정 정보를 입력하세요");
+    }
+
+    let (jar, flash) = take_flash(jar);
+    let form = data.address.unwrap_or_default();
+    (jar, address_page(&form, &Errors::new(), flash.as_deref())).into_response()
+}
+
+async fn submit_address(jar: SignedCookieJar, Form(form): Form<AddressForm>) -> Response {
+    let mut data = load(&jar);
+    if data.account.is_none() {
+        return redirect_with_flash(jar, "/wizard/account", "먼저 계정 정보를 입력하세요");
+    }
+
+    let form = form.normalized();
+    let errors = form.validate();
+    if !errors.is_empty() {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            address_page(&form, &errors, None),
+        )
+            .into_response();
+    }
+
+    data.address = Some(form);
+    (save(jar, &data), Redirect::to("/wizard/review")).into_response()
+}
+
+// 3단계: 확인
+async fn show_review(jar: SignedCookieJar) -> Response {
+    match load(&jar) {
+        WizardData {
+            account: Some(account),
+            address: Some(address),
+        } => review_page(&account, &address).into_response(),
+        data => redirect_to_missing_step(jar, &data),
+    }
+}
+
+// 최종 제출: 저장 → 위저드 쿠키 삭제 → flash와 함께 완료 페이지로
+async fn submit_review(State(state): State<WizardState>, jar: SignedCookieJar) -> Response {
+    let (account, address) = match load(&jar) {
+        WizardData {
+            account: Some(account),
+            address: Some(address),
+        } => (account, address),
+        data => return redirect_to_missing_step(jar, &data),
+    };
+
+    let number = {
+        let mut submissions = state.submissions.lock().unwrap();
+        submissions.push(Registration {
+            name: account.name,
+            email: account.email,
+            city: address.city,
+            zip: address.zip,
+        });
+        submissions.len()
+    };
+    tracing::debug!(number, "registration committed");
+
+    let jar = jar.remove(cookie(DATA_COOKIE, String::new()));
+    redirect_with_flash(
+        jar,
+        "/wizard/done",
+        &format!("가입이 완료되었습니다 (#{number})"),
+    )
+}
+
+// 완료 페이지: flash는 한 번만 보이고, 새로고침하면 사라짐
+async fn show_done(jar: SignedCookieJar) -> (SignedCookieJar, Html<String>) {
+    let (jar, flash) = take_flash(jar);
+    (
+        jar,
+        page(
+            "완료",
+            flash.as_deref(),
+            r#"<p><a href="/wizard/account">처음으로</a></p>"#,
+        ),
+    )
+}
+
+// MARK: - ✨ 쿠키 헬퍼
+
+// 서명 검증에 실패했거나 형식이 맞지 않는 쿠키는 없는 것으로 취급
+fn load(jar: &SignedCookieJar) -> WizardData {
+    jar.get(DATA_COOKIE)
+        .and_then(|cookie| serde_json::from_str(cookie.value()).ok())
+        .unwrap_or_default()
+}
+
+fn save(jar: SignedCookieJar, data: &WizardData) -> SignedCookieJar {
+    let value = serde_json::to_string(data).expect("wizard data is always serializable");
+    jar.add(cookie(DATA_COOKIE, value))
+}
+
+fn take_flash(jar: SignedCookieJar) -> (SignedCookieJar, Option<String>) {
+    match jar.get(FLASH_COOKIE) {
+        Some(flash) => {
+            let message = flash.value().to_owned();
+            (
+                jar.remove(cookie(FLASH_COOKIE, String::new())),
+                Some(message),
+            )
+        }
+        None => (jar, None),
+    }
+}
+
+fn redirect_with_flash(jar: SignedCookieJar, to: &str, message: &str) -> Response {
+    let jar = jar.add(cookie(FLASH_COOKIE, message.to_owned()));
+    (jar, Redirect::to(to)).into_response()
+}
+
+fn redirect_to_missing_step(jar: SignedCookieJar, data: &WizardData) -> Response {
+    let to = if data.account.is_none() {
+        "/wizard/account"
+    } else {
+        "/wizard/address"
+    };
+    redirect_with_flash(jar, to, "입력하지 않은 단계가 있습니다")
+}
+
+// 추가/삭제 모두 같은 path를 써야 브라우저가 같은 쿠키로 인식함
+fn cookie(name: &'static str, value: String) -> Cookie<'static> {
+    Cookie::build((name, value))
+        .path("/wizard")
+        .http_only(true)
+        .same_site(SameSite::Lax)
+        .build()
+}
+
+// MARK: - ✨ HTML 렌더링
+
+fn account_page(form: &AccountForm, errors: &Errors, flash: Option<&str>) -> Html<String> {
+    let body = format!(
+        r#"<h2>1/3 계정 정보</h2>
+        <form action="/wizard/account" method="post">
+            {name}
+            {email}
+            <input type="submit" value="다음">
+        </form>"#,
+        name = field("name", "이름", &form.name, errors),
+        email = field("email", "이메일", &form.email, errors),
+    );
+    page("계정 정보", flash, &body)
+}
+
+fn address_page(form: &AddressForm, errors: &Errors, flash: Option<&str>) -> Html<String> {
+    let body = format!(
+        r#"<h2>2/3 주소</h2>
+        <form action="/wizard/address" method="post">
+            {city}
+            {zip}
+            <a href="/wizard/account">이전</a>
+            <input type="submit" value="다음">
+        </form>"#,
+        city = field("city", "도시", &form.city, errors),
+        zip = field("zip", "우편번호", &form.zip, errors),
+    );
+    page("주소", flash, &body)
+}
+
+fn review_page(account: &AccountForm, address: &AddressForm) -> Html<String> {
+    let body = format!(
+        r#"<h2>3/3 확인</h2>
+        <dl>
+            <dt>이름</dt><dd>{name}</dd>
+            <dt>이메일</dt><dd>{email}</dd>
+            <dt>도시</dt><dd>{city}</dd>
+            <dt>우편번호</dt><dd>{zip}</dd>
+        </dl>
+        <form action="/wizard/review" method="post">
+            <a href="/wizard/address">이전</a>
+            <input type="submit" value="가입하기">
+        </form>"#,
+        name = escape(&account.name),
+        email = escape(&account.email),
+        city = escape(&address.city),
+        zip = escape(&address.zip),
+    );
+    page("확인", None, &body)
+}
+
+fn field(name: &str, label: &str, value: &str, errors: &Errors) -> String {
+    let error = errors
+        .get(name)
+        .map(|message| format!(r#"<span class="error">{message}</span>"#))
+        .unwrap_or_default();
+    format!(
+        r#"<p><label>{label} <input type="text" name="{name}" value="{value}"></label> {error}</p>"#,
+        value = escape(value),
+    )
+}
+
+fn page(title: &str, flash: Option<&str>, body: &str) -> Html<String> {
+    let flash = flash
+        .map(|message| format!(r#"<p class="flash">{}</p>"#, escape(message)))
+        .unwrap_or_default();
+    Html(format!(
+        r#"<!doctype html>
+<html>
+    <head><meta charset="utf-8"><title>{title}</title></head>
+    <body>
+        {flash}
+        {body}
+    </body>
+</html>"#
+    ))
+}
+
+// 사용자 입력을 HTML에 넣기 전에 반드시 escape (XSS 방지)
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#x27;")
+}
+
+// MARK: - ✨ 테스트 모듈
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header, Request},
+    };
+    use http_body_util::BodyExt;
+    use std::collections::HashMap;
+    use tower::ServiceExt;
+
+    // ✨ 쿠키를 기억하는 간단한 테스트 클라이언트 (브라우저 흉내)
+    struct Client {
+        app: Router,
+        state: WizardState,
+        cookies: HashMap<String, String>,
+    }
+
+    struct Page {
+        status: StatusCode,
+        location: Option<String>,
+        body: String,
+    }
+
+    impl Client {
+        fn new() -> Self {
+            let state = WizardState::new(Key::generate());
+            Self {
+                app: router(state.clone()),
+                state,
+                cookies: HashMap::new(),
+            }
+        }
+
+        async fn get(&mut self, uri: &str) -> Page {
+            self.send(Request::get(uri), Body::empty()).await
+        }
+
+        async fn post(&mut self, uri: &str, form: &str) -> Page {
+            let request = Request::post(uri).header(
+                header::CONTENT_TYPE,
+                mime::APPLICATION_WWW_FORM_URLENCODED.as_ref(),
+            );
+            self.send(request, Body::from(form.to_owned())).await
+        }
+
+        async fn send(&mut self, mut request: axum::http::request::Builder, body: Body) -> Page {
+            if !self.cookies.is_empty() {
+                let cookies = self
+                    .cookies
+                    .iter()
+                    .map(|(name, value)| format!("{name}={value}"))
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                request = request.header(header::COOKIE, cookies);
+            }
+
+            let response = self
+                .app
+                .clone()
+                .oneshot(request.body(body).unwrap())
+                .await
+                .unwrap();
+
+            // Set-Cookie 반영 (빈 값 = 삭제)
+            for set_cookie in response.headers().get_all(header::SET_COOKIE) {
+                let cookie = Cookie::parse(set_cookie.to_str().unwrap().to_owned()).unwrap();
+                if cookie.value().is_empty() {
+                    self.cookies.remove(cookie.name());
+                } else {
+                    self.cookies
+                        .insert(cookie.name().to_owned(), cookie.value().to_owned());
+                }
+            }
+
+            let status = response.status();
+            let location = response
+                .headers()
+                .get(header::LOCATION)
+                .map(|value| value.to_str().unwrap().to_owned());
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            Page {
+                status,
+                location,
+                body: String::from_utf8(body.to_vec()).unwrap(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_step_is_rerendered_with_errors() {
+        let mut client = Client::new();
+
+        let page = client
+            .post("/wizard/account", "name=%3Cb%3Ekim%3C%2Fb%3E&email=nope")
+            .await;
+
+        assert_eq!(page.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(page.body.contains("올바른 이메일 주소를 입력하세요"));
+        // 입력값은 유지되고 escape됨
+        assert!(page.body.contains(r#"value="&lt;b&gt;kim&lt;/b&gt;""#));
+        assert!(page.body.contains(r#"value="nope""#));
+        assert!(client.cookies.is_empty());
+    }
+
+    #[tokio::test]
+    async fn skipping_a_step_redirects_back_with_flash() {
+        let mut client = Client::new();
+
+        let page = client.get("/wizard/review").await;
+        assert_eq!(page.status, StatusCode::SEE_OTHER);
+        assert_eq!(page.location.as_deref(), Some("/wizard/account"));
+
+        let page = client.get("/wizard/account").await;
+        assert!(page.body.contains("입력하지 않은 단계가 있습니다"));
+
+        // flash는 한 번만 보임
+        let page = client.get("/wizard/account").await;
+        assert!(!page.body.contains("class=\"flash\""));
+    }
+
+    #[tokio::test]
+    async fn complete_flow_commits_once() {
+        let mut client = Client::new();
+
+        let page = client
+            .post("/wizard/account", "name=+kim+&email=kim%40example.com")
+            .await;
+        assert_eq!(page.status, StatusCode::SEE_OTHER);
+        assert_eq!(page.location.as_deref(), Some("/wizard/address"));
+
+        let page = client.post("/wizard/address", "city=Seoul&zip=123").await;
+        assert_eq!(page.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(page.body.contains("우편번호는 숫자 5자리입니다"));
+
+        let page = client.post("/wizard/address", "city=Seoul&zip=04524").await;
+        assert_eq!(page.location.as_deref(), Some("/wizard/review"));
+
+        // 이전 단계로 돌아가면 저장된 값이 채워져 있음
+        let page = client.get("/wizard/account").await;
+        assert!(page.body.contains(r#"value="kim""#));
+
+        let page = client.get("/wizard/review").await;
+        assert_eq!(page.status, StatusCode::OK);
+        assert!(page.body.contains("<dd>kim@example.com</dd>"));
+        assert!(page.body.contains("<dd>04524</dd>"));
+
+        let page = client.post("/wizard/review", "").await;
+        assert_eq!(page.location.as_deref(), Some("/wizard/done"));
+        assert!(!client.cookies.contains_key(DATA_COOKIE));
+
+        let page = client.get("/wizard/done").await;
+        assert!(page.body.contains("가입이 완료되었습니다 (#1)"));
+
+        // 위저드 데이터가 지워졌으므로 다시 제출해도 저장되지 않음
+        let page = client.post("/wizard/review", "").await;
+        assert_eq!(page.location.as_deref(), Some("/wizard/account"));
+
+        let submissions = client.state.submissions.lock().unwrap();
+        assert_eq!(submissions.len(), 1);
+        assert_eq!(submissions[0].name, "kim");
+        assert_eq!(submissions[0].zip, "04524");
+    }
+
+    #[tokio::test]
+    async fn tampered_cookie_is_ignored() {
+        let mut client = Client::new();
+        client
+            .post("/wizard/account", "name=kim&email=kim%40example.com")
+            .await;
+
+        // 서명 부분은 그대로 두고 값만 바꾸면 검증 실패
+        let value = client.cookies.get_mut(DATA_COOKIE).unwrap();
+        *value = value.replace("kim", "lee");
+
+        let page = client.get("/wizard/address").await;
+        assert_eq!(page.location.as_deref(), Some("/wizard/account"));
+    }
+}
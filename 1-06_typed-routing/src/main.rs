@@ -0,0 +1,236 @@
+//! `axum-extra`의 `TypedPath`로 경로를 타입으로 정의하는 예제입니다.
+//!
+//! - 경로 문자열은 `#[typed_path("...")]` 한 곳에만 존재
+//! - 라우트 등록(`typed_get`), 경로 변수 추출(핸들러 인자), 링크/리다이렉트 URL 생성이
+//!   모두 같은 구조체를 사용하므로, 경로를 바꾸면 컴파일러가 맞지 않는 곳을 알려줌
+//!
+//! ```not_rust
+//! curl http://127.0.0.1:3000/users
+//! curl http://127.0.0.1:3000/users/1
+//! curl http://127.0.0.1:3000/users/1/posts/2
+//! curl -i http://127.0.0.1:3000/u/1     # → 308 /users/1
+//! ```
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Redirect, Response},
+    Json, Router,
+};
+use axum_extra::routing::{RouterExt, TypedPath}; // typed_get 등 확장 메서드
+use serde::{Deserialize, Serialize};
+
+/// 링크를 만들 때 사용하는 서버 주소
+const BASE_URL: &str = "http://127.0.0.1:3000";
+
+#[tokio::main]
+async fn main() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    println!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app()).await.unwrap();
+}
+
+// 🧭 라우터 구성
+// 경로 문자열을 직접 쓰지 않고 핸들러 인자 타입(TypedPath)에서 경로를 가져옴
+fn app() -> Router {
+    Router::new()
+        .typed_get(list_users)
+        .typed_get(get_user)
+        .typed_get(get_post)
+        .typed_get(legacy_user)
+}
+
+// 🧩 경로 정의
+
+#[derive(TypedPath)]
+#[typed_path("/users")]
+struct UsersPath;
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/users/{id}")]
+struct UserPath {
+    id: u32,
+}
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/users/{id}/posts/{post_id}")]
+struct PostPath {
+    id: u32,
+    post_id: u32,
+}
+
+// 예전 주소: 새 주소(UserPath)로 리다이렉트
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/u/{id}")]
+struct LegacyUserPath {
+    id: u32,
+}
+
+// 경로 사이의 관계를 메서드로 표현 → 링크를 만들 때 필드를 잘못 옮겨 적을 일이 없음
+impl UserPath {
+    fn post(&self, post_id: u32) -> PostPath {
+        PostPath {
+            id: self.id,
+            post_id,
+        }
+    }
+}
+
+impl PostPath {
+    fn author(&self) -> UserPath {
+        UserPath { id: self.id }
+    }
+}
+
+/// 🔗 TypedPath로부터 절대 URL 생성
+///
+/// derive된 `Display`/`to_uri`가 경로 변수를 percent-encoding 해 줌
+fn url_for<P: TypedPath>(path: &P) -> String {
+    format!("{BASE_URL}{}", path.to_uri())
+}
+
+// 📦 응답 타입
+
+#[derive(Serialize)]
+struct UserSummary {
+    id: u32,
+    name: String,
+    url: String,
+}
+
+#[derive(Serialize)]
+struct User {
+    id: u32,
+    name: String,
+    posts: Vec<String>, // 각 게시글의 URL
+}
+
+#[derive(Serialize)]
+struct Post {
+    id: u32,
+    title: String,
+    author: String, // 작성자 URL
+}
+
+// 예제용 고정 데이터: 사용자 1~3, 사용자마다 게시글 1~2
+const USERS: [&str; 3] = ["alice", "bob", "carol"];
+const POSTS_PER_USER: u32 = 2;
+
+fn user_name(id: u32) -> Option<&'static str> {
+    USERS.get(id.checked_sub(1)? as usize).copied()
+}
+
+// 🧪 핸들러
+// 첫 번째 인자의 타입이 곧 라우트 경로 (typed_get이 P::PATH로 등록)
+
+async fn list_users(_: UsersPath) -> Json<Vec<UserSummary>> {
+    let users = (1..=USERS.len() as u32)
+        .map(|id| UserSummary {
+            id,
+            name: user_name(id).unwrap().to_owned(),
+            url: url_for(&UserPath { id }),
+        })
+        .collect();
+    Json(users)
+}
+
+async fn get_user(path: UserPath) -> Result<Json<User>, StatusCode> {
+    let name = user_name(path.id).ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(User {
+        id: path.id,
+        name: name.to_owned(),
+        posts: (1..=POSTS_PER_USER)
+            .map(|post_id| url_for(&path.post(post_id)))
+            .collect(),
+    }))
+}
+
+async fn get_post(path: PostPath) -> Response {
+    if user_name(path.id).is_none() || path.post_id > POSTS_PER_USER {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    Json(Post {
+        id: path.post_id,
+        title: format!("post {} of user {}", path.post_id, path.id),
+        author: url_for(&path.author()),
+    })
+    .into_response()
+}
+
+// 리다이렉트 대상도 문자열이 아닌 타입으로 생성
+async fn legacy_user(LegacyUserPath { id }: LegacyUserPath) -> Redirect {
+    Redirect::permanent(&UserPath { id }.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    async fn get(uri: &str) -> Response {
+        app()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn get_json(uri: &str) -> Value {
+        let response = get(uri).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[test]
+    fn paths_render_from_types() {
+        assert_eq!(UserPath { id: 1 }.to_string(), "/users/1");
+        assert_eq!(UserPath { id: 1 }.post(2).to_string(), "/users/1/posts/2");
+        assert_eq!(url_for(&UsersPath), "http://127.0.0.1:3000/users");
+    }
+
+    // 생성된 링크를 그대로 따라가면 라우터가 처리할 수 있어야 함 (링크와 라우트가 어긋나지 않음)
+    #[tokio::test]
+    async fn generated_links_resolve_to_routes() {
+        let users = get_json("/users").await;
+        let user_url = users[0]["url"].as_str().unwrap();
+        assert_eq!(user_url, "http://127.0.0.1:3000/users/1");
+
+        let user = get_json(user_url.strip_prefix(BASE_URL).unwrap()).await;
+        let post_url = user["posts"][1].as_str().unwrap();
+        assert_eq!(post_url, "http://127.0.0.1:3000/users/1/posts/2");
+
+        let post = get_json(post_url.strip_prefix(BASE_URL).unwrap()).await;
+        assert_eq!(
+            post,
+            json!({
+                "id": 2,
+                "title": "post 2 of user 1",
+                "author": "http://127.0.0.1:3000/users/1",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn legacy_path_redirects_to_typed_path() {
+        let response = get("/u/3").await;
+
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()["location"], "/users/3");
+    }
+
+    #[tokio::test]
+    async fn invalid_path_params_are_rejected() {
+        assert_eq!(get("/users/abc").await.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(get("/users/9").await.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            get("/users/1/posts/3").await.status(),
+            StatusCode::NOT_FOUND
+        );
+    }
+}
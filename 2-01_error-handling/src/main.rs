@@ -1,7 +1,9 @@
 //! 이 예제는 요청 처리 중 발생할 수 있는 다양한 에러(JSON 파싱 실패, 외부 라이브러리 오류 등)를
 //! 커스텀 에러 타입으로 처리하고, HTTP 응답에 적절히 변환하는 방법을 보여줍니다.
 //! > POST 시 JSON을 다음과 같이 세팅합니다. {"name":"string value"}
-//! ! 3번 시도 시 한번은 에러로 떨어지도록 설계되었습니다.
+//! > ! 3번 시도 시 한번은 에러로 떨어지도록 설계되었습니다.
+//! > GET /panic 은 핸들러에서 panic이 나도 같은 형태의 500 응답이 나가는 것을 보여줍니다.
+//! > ALERT_WEBHOOK_URL 환경변수를 지정하면 panic 발생 시 해당 URL로 장애 알림을 보냅니다.
 //!
 //! 실행 방법:
 //!
@@ -29,7 +31,7 @@ use axum::{
     },
     http::StatusCode,                   // HTTP 상태 코드(200, 404, 500 등) 상수 정의
     response::{IntoResponse, Response}, // 핸들러 반환 타입을 HTTP 응답으로 변환하는 트레이트와 실제 응답 타입
-    routing::post,                      // POST 메서드용 라우터 빌더
+    routing::{get, post},               // GET/POST 메서드용 라우터 빌더
     Router,                             // 라우트들을 모아서 앱을 구성하는 메인 객체
 };
 
@@ -38,13 +40,19 @@ use serde::{
     Serialize,   // serde를 이용해 JSON ↔ Rust struct 변환을 위한 직렬화
 };
 
+use panic::{AlertSink, LogAlertSink, PanicHandler, WebhookAlertSink}; // panic 응답 + 장애 알림
 use time_library::Timestamp; // 외부 모듈: 시간 관련 데이터 구조체
-use tower_http::trace::TraceLayer; // HTTP 요청/응답 트레이싱 미들웨어
+use tower_http::{
+    catch_panic::CatchPanicLayer, // 핸들러 panic을 500 응답으로 변환하는 미들웨어
+    trace::TraceLayer,            // HTTP 요청/응답 트레이싱 미들웨어
+};
 use tracing_subscriber::{
     layer::SubscriberExt,    // 트레이싱 구독자 설정 도우미
     util::SubscriberInitExt, //
 };
 
+mod panic;
+
 // -- ✨ 메인 함수
 
 #[tokio::main]
@@ -60,12 +68,35 @@ async fn main() {
         .with(tracing_subscriber::fmt::layer()) // 콘솔에 로그 출력
         .init();
 
-    // ✨ 앱 상태 초기화
-    let state = AppState::default();
+    // ✨ panic 위치와 backtrace를 tracing으로 남기도록 panic hook 설치
+    panic::install_panic_hook();
+
+    // ✨ 장애 알림 대상 선택 (웹훅 URL이 없으면 로그로만 남김)
+    let app = match std::env::var("ALERT_WEBHOOK_URL") {
+        Ok(url) => app(AppState::default(), WebhookAlertSink::new(url)),
+        Err(_) => app(AppState::default(), LogAlertSink),
+    };
 
-    // ✨ 라우터 구성
-    let app = Router::new()
+    // ✨ 127.0.0.1:3000 포트에서 TCP 소켓 바인딩
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await // 비동기적으로 대기합니다.
+        .unwrap(); // 에러 발생 시 패닉(panic) 처리합니다.
+
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+
+    // hyper 기반 서버 실행
+    axum::serve(listener, app)
+        .await // 비동기적으로 실행합니다.
+        .unwrap(); // 에러 발생 시 패닉 처리합니다.
+}
+
+// ✨ 라우터 구성 (테스트에서도 같은 라우터를 사용)
+fn app(state: AppState, alerts: impl AlertSink) -> Router {
+    Router::new()
         .route("/users", post(users_create)) // POST /users 요청 → users_create 핸들러 연결
+        .route("/panic", get(panic_demo)) // GET /panic 요청 → 일부러 panic
+        // 핸들러 panic을 500 JSON 응답으로 변환 (TraceLayer 안쪽에 두어 500 응답도 트레이싱됨)
+        .layer(CatchPanicLayer::custom(PanicHandler::new(alerts)))
         .layer(
             TraceLayer::new_for_http()
                 .make_span_with(|req: &Request| {
@@ -81,22 +112,10 @@ async fn main() {
                 })
                 .on_failure(()), // 실패 시 기본 5xx 에러 로깅 비활성화 (커스텀 처리 예정)
         )
-        .with_state(state); // 앱 상태(AppState)를 공유
-
-    // ✨ 127.0.0.1:3000 포트에서 TCP 소켓 바인딩
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
-        .await // 비동기적으로 대기합니다.
-        .unwrap(); // 에러 발생 시 패닉(panic) 처리합니다.
-
-    tracing::debug!("listening on {}", listener.local_addr().unwrap());
-
-    // hyper 기반 서버 실행
-    axum::serve(listener, app)
-        .await // 비동기적으로 실행합니다.
-        .unwrap(); // 에러 발생 시 패닉 처리합니다.
+        .with_state(state) // 앱 상태(AppState)를 공유
 }
 
-/// 📦 상태 및 도메인 모델 정의
+// 📦 상태 및 도메인 모델 정의
 
 // ✨ 앱의 글로벌 상태 구조체
 #[derive(Default, Clone)]
@@ -119,7 +138,7 @@ struct User {
     created_at: Timestamp, // 생성 시각 (외부 라이브러리 타입)
 }
 
-/// 🔄 사용자 생성 핸들러 및 JSON 래퍼 정의
+// 🔄 사용자 생성 핸들러 및 JSON 래퍼 정의
 
 // ✨ POST /users 요청을 처리하는 핸들러
 async fn users_create(
@@ -145,7 +164,15 @@ async fn users_create(
     Ok(AppJson(user))
 }
 
-/// 🧩 커스텀 JSON 추출기 및 응답 타입
+// ✨ GET /panic: 처리하지 못한 버그(unwrap 실패 등)를 흉내 내는 핸들러
+async fn panic_demo() -> AppJson<User> {
+    let users: Vec<User> = Vec::new();
+    // 빈 목록이라 expect에서 panic → CatchPanicLayer가 500 응답으로 변환
+    let first = users.first().cloned();
+    AppJson(first.expect("there should always be at least one user"))
+}
+
+// 🧩 커스텀 JSON 추출기 및 응답 타입
 
 // ✨ AppJson: Json 추출 및 응답 처리를 위한 래퍼 타입
 #[derive(FromRequest)]
@@ -162,7 +189,7 @@ where
     }
 }
 
-/// 🚨 에러 타입 정의 및 처리
+// 🚨 에러 타입 정의 및 처리
 
 // ✨ 앱 전용 에러 타입
 enum AppError {
@@ -170,14 +197,15 @@ enum AppError {
     TimeError(time_library::Error), // 시간 생성 실패
 }
 
+// ✨ 모든 에러 응답의 공통 형태 (panic 응답도 같은 형태 사용)
+#[derive(Serialize)]
+struct ErrorResponse {
+    message: String, // 에러 메시지
+}
+
 // ✨ 에러를 HTTP 응답으로 변환하는 로직
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        #[derive(Serialize)]
-        struct ErrorResponse {
-            message: String, // 에러 메시지
-        }
-
         // 에러에 따른 상태 코드 및 메시지 설정
         let (status, message) = match self {
             AppError::JsonRejection(rejection) => {
@@ -214,7 +242,7 @@ impl From<time_library::Error> for AppError {
     }
 }
 
-/// ⏱️ 외부 라이브러리 시뮬레이션 (time_library)
+// ⏱️ 외부 라이브러리 시뮬레이션 (time_library)
 
 // ✨ 시간 관련 외부 모듈 (모의)
 mod time_library {
@@ -230,7 +258,7 @@ mod time_library {
             static COUNTER: AtomicU64 = AtomicU64::new(0);
 
             // 테스트를 위해 일부러 주기적으로 실패
-            if COUNTER.fetch_add(1, Ordering::SeqCst) % 3 == 0 {
+            if COUNTER.fetch_add(1, Ordering::SeqCst).is_multiple_of(3) {
                 Err(Error::FailedToGetTime)
             } else {
                 Ok(Self(1337)) // 고정된 시간값 반환
@@ -250,3 +278,67 @@ mod time_library {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use http_body_util::BodyExt;
+    use panic::Incident;
+    use tower::ServiceExt;
+
+    // 알림을 기록만 하는 테스트용 AlertSink
+    #[derive(Clone, Default)]
+    struct RecordingSink(Arc<Mutex<Vec<Incident>>>);
+
+    impl AlertSink for RecordingSink {
+        fn alert(&self, incident: &Incident) {
+            self.0.lock().unwrap().push(incident.clone());
+        }
+    }
+
+    async fn send(app: Router, request: Request<Body>) -> (StatusCode, String) {
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn panic_becomes_structured_500_and_alert() {
+        let sink = RecordingSink::default();
+        let app = app(AppState::default(), sink.clone());
+
+        for _ in 0..2 {
+            let request = Request::get("/panic").body(Body::empty()).unwrap();
+            let (status, body) = send(app.clone(), request).await;
+            assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+            assert_eq!(body, r#"{"message":"Something went wrong"}"#);
+        }
+
+        let incidents = sink.0.lock().unwrap();
+        assert_eq!(incidents.len(), 2);
+        assert_eq!(incidents[0].id, 1);
+        assert_eq!(incidents[1].id, 2);
+        assert_eq!(
+            incidents[0].message,
+            "there should always be at least one user"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejection_uses_same_error_shape() {
+        let sink = RecordingSink::default();
+        let request = Request::post("/users")
+            .header("content-type", "application/json")
+            .body(Body::from("{"))
+            .unwrap();
+
+        let (status, body) = send(app(AppState::default(), sink.clone()), request).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.starts_with(r#"{"message":"#), "{body}");
+        // panic이 아니면 알림 없음
+        assert!(sink.0.lock().unwrap().is_empty());
+    }
+}
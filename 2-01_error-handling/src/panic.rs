@@ -0,0 +1,185 @@
+//! 💥 핸들러 panic → 구조화된 500 응답 + 알림
+//!
+//! - `CatchPanicLayer`가 panic을 잡아 다른 에러와 같은 `{"message": ...}` 형태로 응답
+//! - panic hook에서 위치와 backtrace를 잡아 tracing으로 남김
+//!   (panic payload에는 메시지만 있으므로 hook에서 잡아 두지 않으면 backtrace를 알 수 없음)
+//! - 장애 알림은 `AlertSink` 트레이트로 분리 → 로그, 웹훅, 메신저 등으로 바꿔 끼울 수 있음
+
+use std::{
+    any::Any,
+    backtrace::Backtrace,
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use axum::{
+    body::Body,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use tower_http::catch_panic::ResponseForPanic;
+
+use crate::{AppJson, ErrorResponse};
+
+// ✨ 알림으로 보낼 장애 정보
+#[derive(Debug, Clone, Serialize)]
+pub struct Incident {
+    pub id: u64,
+    pub message: String,
+    pub location: Option<String>,
+    pub backtrace: String,
+    pub occurred_at: u64, // UNIX timestamp (초)
+}
+
+// ✨ 장애 알림을 보내는 곳 (운영 환경에 맞게 구현해서 끼움)
+// 응답을 만드는 중에 호출되므로 오래 걸리는 작업은 별도 task로 넘길 것
+pub trait AlertSink: Send + Sync + 'static {
+    fn alert(&self, incident: &Incident);
+}
+
+// ✨ 로그로만 남기는 기본 알림
+pub struct LogAlertSink;
+
+impl AlertSink for LogAlertSink {
+    fn alert(&self, incident: &Incident) {
+        tracing::warn!(
+            incident = incident.id,
+            message = incident.message,
+            "🚨 incident alert"
+        );
+    }
+}
+
+// ✨ 웹훅(Slack incoming webhook 등)으로 장애 정보를 JSON POST
+pub struct WebhookAlertSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookAlertSink {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.into(),
+        }
+    }
+}
+
+impl AlertSink for WebhookAlertSink {
+    fn alert(&self, incident: &Incident) {
+        let request = self.client.post(&self.url).json(incident);
+        let id = incident.id;
+        // 응답을 늦추지 않도록 전송은 백그라운드에서
+        tokio::spawn(async move {
+            match request.send().await.and_then(|res| res.error_for_status()) {
+                Ok(_) => tracing::debug!(incident = id, "alert delivered"),
+                Err(err) => tracing::error!(incident = id, %err, "failed to deliver alert"),
+            }
+        });
+    }
+}
+
+// panic hook이 잡아 둔 정보 (CatchPanicLayer는 panic이 난 스레드에서 바로 응답을 만듦)
+struct PanicReport {
+    message: String,
+    location: Option<String>,
+    backtrace: String,
+}
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<PanicReport>> = const { RefCell::new(None) };
+}
+
+// ✨ 모든 panic의 위치와 backtrace를 tracing으로 남기는 hook 설치 (main에서 한 번 호출)
+pub fn install_panic_hook() {
+    std::panic::set_hook(Box::new(|info| {
+        let report = PanicReport {
+            message: payload_message(info.payload()),
+            location: info.location().map(ToString::to_string),
+            // RUST_BACKTRACE 설정과 관계없이 항상 수집 (panic은 드물게 일어나므로 비용은 괜찮음)
+            backtrace: Backtrace::force_capture().to_string(),
+        };
+        tracing::error!(
+            message = report.message,
+            location = report.location,
+            backtrace = report.backtrace,
+            "panic"
+        );
+        LAST_PANIC.with(|last| *last.borrow_mut() = Some(report));
+    }));
+}
+
+// ✨ CatchPanicLayer::custom에 넘기는 panic 응답 생성기
+#[derive(Clone)]
+pub struct PanicHandler {
+    sink: Arc<dyn AlertSink>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl PanicHandler {
+    pub fn new(sink: impl AlertSink) -> Self {
+        Self {
+            sink: Arc::new(sink),
+            next_id: Default::default(),
+        }
+    }
+}
+
+impl ResponseForPanic for PanicHandler {
+    type ResponseBody = Body;
+
+    fn response_for_panic(&mut self, err: Box<dyn Any + Send + 'static>) -> Response<Body> {
+        let message = payload_message(&*err);
+
+        // 같은 panic에 대한 기록일 때만 사용 (hook이 없거나 다른 panic의 기록이면 메시지만)
+        let report = LAST_PANIC
+            .with(|last| last.borrow_mut().take())
+            .filter(|report| report.message == message);
+        let (location, backtrace) = match report {
+            Some(report) => (report.location, report.backtrace),
+            None => (None, "<not captured>".to_owned()),
+        };
+
+        let incident = Incident {
+            id: self.next_id.fetch_add(1, Ordering::SeqCst) + 1,
+            message,
+            location,
+            backtrace,
+            occurred_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        };
+        tracing::error!(
+            incident = incident.id,
+            message = incident.message,
+            "handler panicked"
+        );
+        self.sink.alert(&incident);
+
+        // 다른 서버 내부 오류와 같은 응답 (panic 메시지는 클라이언트에 노출하지 않음)
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            AppJson(ErrorResponse {
+                message: "Something went wrong".to_owned(),
+            }),
+        )
+            .into_response()
+    }
+}
+
+// panic!("...")은 &str, panic!("{}", x)는 String payload
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "<non-string panic payload>".to_owned()
+    }
+}
@@ -46,8 +46,7 @@ async fn main() {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
-    // ✨ 라우터 구성: 특정 경로에 핸들러 등록
-    let app = Router::new().route("/users/{user_id}/teams/{team_id}", get(handler));
+    let app = app();
 
     // ✨ 서버 리스너 바인딩 및 시작
     let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
@@ -62,7 +61,12 @@ async fn main() {
         .unwrap(); // 에러 발생 시 패닉 처리
 }
 
-/// ✅ 핸들러 및 경로 파라미터 추출 구조체
+/// 🧭 라우터 구성: 특정 경로에 핸들러 등록
+fn app() -> Router {
+    Router::new().route("/users/{user_id}/teams/{team_id}", get(handler))
+}
+
+// ✅ 핸들러 및 경로 파라미터 추출 구조체
 
 // ✨ 커스텀 Path 추출기를 사용하는 핸들러
 async fn handler(Path(params): Path<Params>) -> impl IntoResponse {
@@ -76,7 +80,7 @@ struct Params {
     team_id: u32, // 팀 ID
 }
 
-/// 🧩 커스텀 Path 추출기 정의 및 구현
+// 🧩 커스텀 Path 추출기 정의 및 구현
 
 // ✨ 사용자 정의 Path 추출기
 struct Path<T>(T);
@@ -180,3 +184,109 @@ struct PathError {
     message: String,          // 에러 메시지
     location: Option<String>, // 에러가 발생한 위치(키 또는 인덱스)
 }
+
+/// 🎲 임의의 경로를 넣어 보는 property 테스트
+///
+/// • 패닉 없이 항상 응답하는지
+/// • 거절은 모두 `PathError` 형태의 JSON인지 (상태 코드는 400/404만, 5xx 없음)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::header::CONTENT_TYPE};
+    use http_body_util::BodyExt;
+    use proptest::prelude::*;
+    use tower::ServiceExt;
+
+    // 테스트에서 응답 body를 읽기 위한 구조체 (`PathError`와 같은 모양)
+    #[derive(Debug, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct ErrorBody {
+        message: String,
+        location: Option<String>,
+    }
+
+    /// 응답 (상태 코드, content-type, body)
+    fn send(uri: &str) -> (StatusCode, Option<String>, Vec<u8>) {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let request = axum::http::Request::get(uri).body(Body::empty()).unwrap();
+            let response = app().oneshot(request).await.unwrap();
+            let status = response.status();
+            let content_type = response
+                .headers()
+                .get(CONTENT_TYPE)
+                .map(|value| value.to_str().unwrap().to_owned());
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            (status, content_type, body.to_vec())
+        })
+    }
+
+    // 모든 바이트를 %XX로 → 잘못된 UTF-8도 경로 세그먼트 하나로 전달됨
+    fn percent_encode(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("%{byte:02X}")).collect()
+    }
+
+    fn assert_path_error(status: StatusCode, content_type: Option<&str>, body: &[u8]) -> ErrorBody {
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(content_type, Some("application/json"));
+        let error: ErrorBody = serde_json::from_slice(body).unwrap();
+        assert!(!error.message.is_empty());
+        error
+    }
+
+    proptest! {
+        /// 세그먼트 값이 u32로 파싱되면 200, 아니면 실패한 키를 `location`에 담은 400
+        #[test]
+        fn arbitrary_segments(
+            user_id in prop::collection::vec(any::<u8>(), 1..12),
+            team_id in prop::collection::vec(any::<u8>(), 1..12),
+        ) {
+            let uri = format!(
+                "/users/{}/teams/{}",
+                percent_encode(&user_id),
+                percent_encode(&team_id)
+            );
+            let (status, content_type, body) = send(&uri);
+
+            // UTF-8 검사는 역직렬화 전에 모든 세그먼트에 대해 먼저 일어남
+            let segments = [("user_id", &user_id), ("team_id", &team_id)];
+            let invalid_utf8 = segments
+                .iter()
+                .find(|(_, bytes)| std::str::from_utf8(bytes).is_err());
+            let not_a_number = segments.iter().find(|(_, bytes)| {
+                std::str::from_utf8(bytes).map_or(true, |value| value.parse::<u32>().is_err())
+            });
+
+            match invalid_utf8.or(not_a_number) {
+                None => {
+                    prop_assert_eq!(status, StatusCode::OK);
+                    let params: Params = serde_json::from_slice(&body).unwrap();
+                    let expected = |bytes: &[u8]| std::str::from_utf8(bytes).unwrap().parse::<u32>().unwrap();
+                    prop_assert_eq!(params.user_id, expected(&user_id));
+                    prop_assert_eq!(params.team_id, expected(&team_id));
+                }
+                Some((key, _)) => {
+                    let error = assert_path_error(status, content_type.as_deref(), &body);
+                    prop_assert_eq!(error.location.as_deref(), Some(*key));
+                }
+            }
+        }
+
+        /// 아무 경로나 → 라우트가 없으면 404, 있으면 200/400 (5xx 없음)
+        #[test]
+        fn arbitrary_paths(path in "(/([a-z0-9]{0,6}|users|teams|%[0-9A-Fa-f]{2}|%)){0,5}") {
+            let uri = if path.is_empty() { "/".to_owned() } else { path };
+            prop_assume!(uri.parse::<axum::http::Uri>().is_ok());
+
+            let (status, content_type, body) = send(&uri);
+            match status {
+                StatusCode::OK | StatusCode::NOT_FOUND => {}
+                status => {
+                    assert_path_error(status, content_type.as_deref(), &body);
+                }
+            }
+        }
+    }
+}
@@ -3,26 +3,36 @@
 //! 1. trait object (`Arc<dyn UserRepo>`) 방식
 //! 2. generic 타입 파라미터 (`T: UserRepo`) 방식
 //!
+//! 핸들러와 저장소 사이에는 `UserService`(service.rs)가 있어 검증, 중복 검사,
+//! tracing span과 메트릭을 담당하며, 두 방식 모두 같은 서비스를 사용합니다.
+//!
+//! 저장소 메서드는 `Result`를 반환하므로 저장소 에러가 서비스와 핸들러를 거쳐 500 응답으로 전파되며,
+//! 아래 테스트 모듈은 실제 저장소 대신 `MockUserRepo`를 주입해 핸들러만 단위 테스트합니다.
+//!
+
+mod service;
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     sync::{Arc, Mutex},
 };
 
 use axum::{
     extract::{Path, State}, // Path: 경로 변수 추출, State: 앱 상태 주입
     http::StatusCode,
+    response::{IntoResponse, Response},
     routing::{get, post},
     Json,
     Router,
 };
 
 use serde::{Deserialize, Serialize};
+use service::{OperationStats, UserService};
 use tokio::net::TcpListener;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use uuid::Uuid; // 사용자 식별용 UUID
 
-/// 🧭 메인 함수
+// 🧭 메인 함수
 
 #[tokio::main]
 async fn main() {
@@ -61,15 +71,20 @@ async fn main() {
     let using_dyn = Router::new()
         .route("/users/{id}", get(get_user_dyn)) // GET /dyn/users/{id}
         .route("/users", post(create_user_dyn)) // POST /dyn/users
+        .route("/metrics", get(metrics_dyn)) // GET /dyn/metrics
         .with_state(AppStateDyn {
-            user_repo: Arc::new(user_repo.clone()), // Arc로 감싼 dyn UserRepo
+            // Arc로 감싼 dyn UserRepo를 서비스에 주입
+            users: UserService::new(Arc::new(user_repo.clone())),
         });
 
     // 방식 2. Generic 기반 DI (T: Trait)
     let using_generic = Router::new()
         .route("/users/{id}", get(get_user_generic::<InMemoryUserRepo>))
         .route("/users", post(create_user_generic::<InMemoryUserRepo>))
-        .with_state(AppStateGeneric { user_repo }); // 그대로 주입
+        .route("/metrics", get(metrics_generic::<InMemoryUserRepo>))
+        .with_state(AppStateGeneric {
+            users: UserService::new(user_repo), // 그대로 주입
+        });
 
     // `/dyn`과 `/generic` 경로를 각각 서브라우트로 묶음
     let app = Router::new()
@@ -82,21 +97,21 @@ async fn main() {
     axum::serve(listener, app).await.unwrap();
 }
 
-/// 📦 상태 구조체 정의
+// 📦 상태 구조체 정의
 
 // dyn 방식: trait object를 Arc로 감싸서 보관
 #[derive(Clone)]
 struct AppStateDyn {
-    user_repo: Arc<dyn UserRepo>,
+    users: UserService<Arc<dyn UserRepo>>,
 }
 
 // generic 방식: 타입 파라미터로 유연하게 보관
 #[derive(Clone)]
 struct AppStateGeneric<T> {
-    user_repo: T,
+    users: UserService<T>,
 }
 
-/// 🧍 사용자 모델 및 입력 파라미터
+// 🧍 사용자 모델 및 입력 파라미터
 
 #[derive(Debug, Serialize, Clone)]
 struct User {
@@ -109,76 +124,146 @@ struct UserParams {
     name: String,
 }
 
-/// ✏️ 핸들러 함수 (trait object 기반)
+// ✏️ 핸들러 함수 (trait object 기반)
+// 검증/중복 검사/로깅은 서비스가 처리하므로 핸들러는 요청과 응답 변환만 담당
 
 // POST /dyn/users
 async fn create_user_dyn(
     State(state): State<AppStateDyn>,
     Json(params): Json<UserParams>,
-) -> Json<User> {
-    let user = User {
-        id: Uuid::new_v4(),
-        name: params.name,
-    };
-
-    state.user_repo.save_user(&user);
-    Json(user)
+) -> Result<Json<User>, AppError> {
+    // 저장 실패 시 AppError::Repo로 변환되어 바로 반환
+    let user = state.users.create_user(&params.name)?;
+    Ok(Json(user))
 }
 
 // GET /dyn/users/{id}
 async fn get_user_dyn(
     State(state): State<AppStateDyn>,
     Path(id): Path<Uuid>,
-) -> Result<Json<User>, StatusCode> {
-    match state.user_repo.get_user(id) {
-        Some(user) => Ok(Json(user)),
-        None => Err(StatusCode::NOT_FOUND),
-    }
+) -> Result<Json<User>, AppError> {
+    state.users.get_user(id).map(Json)
 }
 
-/// ✏️ 핸들러 함수 (generic 기반)
+// GET /dyn/metrics
+async fn metrics_dyn(
+    State(state): State<AppStateDyn>,
+) -> Json<BTreeMap<&'static str, OperationStats>> {
+    Json(state.users.metrics())
+}
+
+// ✏️ 핸들러 함수 (generic 기반)
 
 // POST /generic/users
 async fn create_user_generic<T>(
     State(state): State<AppStateGeneric<T>>,
     Json(params): Json<UserParams>,
-) -> Json<User>
+) -> Result<Json<User>, AppError>
 where
     T: UserRepo,
 {
-    let user = User {
-        id: Uuid::new_v4(),
-        name: params.name,
-    };
-
-    state.user_repo.save_user(&user);
-    Json(user)
+    let user = state.users.create_user(&params.name)?;
+    Ok(Json(user))
 }
 
 // GET /generic/users/{id}
 async fn get_user_generic<T>(
     State(state): State<AppStateGeneric<T>>,
     Path(id): Path<Uuid>,
-) -> Result<Json<User>, StatusCode>
+) -> Result<Json<User>, AppError>
 where
     T: UserRepo,
 {
-    match state.user_repo.get_user(id) {
-        Some(user) => Ok(Json(user)),
-        None => Err(StatusCode::NOT_FOUND),
-    }
+    state.users.get_user(id).map(Json)
+}
+
+// GET /generic/metrics
+async fn metrics_generic<T>(
+    State(state): State<AppStateGeneric<T>>,
+) -> Json<BTreeMap<&'static str, OperationStats>>
+where
+    T: UserRepo,
+{
+    Json(state.users.metrics())
 }
 
-/// 🧩 DI 대상이 될 Trait 및 구현체
+// 🧩 DI 대상이 될 Trait 및 구현체
 
 // 저장소 Trait (인터페이스 개념)
+// 실제 DB라면 조회/저장이 실패할 수 있으므로 Result를 반환
 trait UserRepo: Send + Sync {
-    fn get_user(&self, id: Uuid) -> Option<User>;
+    fn get_user(&self, id: Uuid) -> Result<Option<User>, RepoError>;
+
+    fn save_user(&self, user: &User) -> Result<(), RepoError>;
+
+    fn find_user_by_name(&self, name: &str) -> Result<Option<User>, RepoError>;
+}
 
-    fn save_user(&self, user: &User);
+// Arc<dyn UserRepo>도 UserRepo로 쓸 수 있게 해서 UserService<Arc<dyn UserRepo>>를 만들 수 있음
+impl<R> UserRepo for Arc<R>
+where
+    R: UserRepo + ?Sized,
+{
+    fn get_user(&self, id: Uuid) -> Result<Option<User>, RepoError> {
+        (**self).get_user(id)
+    }
+
+    fn save_user(&self, user: &User) -> Result<(), RepoError> {
+        (**self).save_user(user)
+    }
+
+    fn find_user_by_name(&self, name: &str) -> Result<Option<User>, RepoError> {
+        (**self).find_user_by_name(name)
+    }
+}
+
+// 저장소 구현체가 반환하는 에러
+#[derive(Debug)]
+struct RepoError(String);
+
+impl std::fmt::Display for RepoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "repository error: {}", self.0)
+    }
+}
+
+// 🧯 핸들러 에러
+
+#[derive(Debug)]
+enum AppError {
+    NotFound,
+    // 입력값 검증 실패
+    Validation(String),
+    // 이미 같은 이름의 사용자가 있음
+    Conflict(String),
+    Repo(RepoError),
+}
+
+// `?`로 RepoError를 바로 전파할 수 있게 함
+impl From<RepoError> for AppError {
+    fn from(err: RepoError) -> Self {
+        Self::Repo(err)
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        match self {
+            AppError::NotFound => StatusCode::NOT_FOUND.into_response(),
+            AppError::Validation(message) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, message).into_response()
+            }
+            AppError::Conflict(message) => (StatusCode::CONFLICT, message).into_response(),
+            AppError::Repo(err) => {
+                // 내부 에러 내용은 로그로만 남김
+                tracing::error!(%err, "user repository failed");
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+        }
+    }
 }
 
-/// 🧠 메모리 기반 저장소 구현
+// 🧠 메모리 기반 저장소 구현
 
 #[derive(Debug, Clone, Default)]
 struct InMemoryUserRepo {
@@ -186,12 +271,18 @@ struct InMemoryUserRepo {
 }
 
 impl UserRepo for InMemoryUserRepo {
-    fn get_user(&self, id: Uuid) -> Option<User> {
-        self.map.lock().unwrap().get(&id).cloned()
+    fn get_user(&self, id: Uuid) -> Result<Option<User>, RepoError> {
+        Ok(self.map.lock().unwrap().get(&id).cloned())
     }
 
-    fn save_user(&self, user: &User) {
+    fn save_user(&self, user: &User) -> Result<(), RepoError> {
         self.map.lock().unwrap().insert(user.id, user.clone());
+        Ok(())
+    }
+
+    fn find_user_by_name(&self, name: &str) -> Result<Option<User>, RepoError> {
+        let map = self.map.lock().unwrap();
+        Ok(map.values().find(|user| user.name == name).cloned())
     }
 }
 
@@ -203,14 +294,19 @@ impl UserRepo for InMemoryUserRepo {
 // 2. 사용자 조회 (UUID는 위 결과에서 가져오기)
 // curl http://localhost:3000/dyn/users/<uuid>
 // ! 또는 ../generic/users 로 제너릭 DI 엔드포인트 테스트.
+// 3. 같은 이름으로 다시 생성 → 409 Conflict, 빈 이름 → 422 Unprocessable Entity
+// 4. 서비스 호출 통계
+// curl http://localhost:3000/dyn/metrics
 
 // ✅ 엔드포인트 요약
 // dyn
 // - 사용자 생성: POST /dyn/users
 // - 사용자 조회: GET /dyn/users/{id}
+// - 호출 통계: GET /dyn/metrics
 // generic
 // - 사용자 생성: POST /generic/users
 // - 사용자 조회: GET /generic/users/{id}
+// - 호출 통계: GET /generic/metrics
 
 // 🔍 두 DI 방식 비교
 // Trait Object (dyn)
@@ -223,3 +319,162 @@ impl UserRepo for InMemoryUserRepo {
 // - 성능:	고성능 (zero cost abstraction)
 // - 제약:	어떤 트레잇이든 사용 가능
 // - 실무 적용:	성능이 중요한 경우 또는 단일 구현일 경우 좋음
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 🧪 테스트용 저장소: 미리 넣어 둔 사용자를 돌려주고, 저장 요청을 기록하며,
+    // `failing()`으로 만들면 모든 호출이 에러를 반환
+    #[derive(Default)]
+    struct MockUserRepo {
+        users: HashMap<Uuid, User>,
+        saved: Mutex<Vec<User>>,
+        fail: bool,
+    }
+
+    impl MockUserRepo {
+        fn with_user(user: User) -> Self {
+            Self {
+                users: HashMap::from([(user.id, user)]),
+                ..Default::default()
+            }
+        }
+
+        fn failing() -> Self {
+            Self {
+                fail: true,
+                ..Default::default()
+            }
+        }
+    }
+
+    impl UserRepo for MockUserRepo {
+        fn get_user(&self, id: Uuid) -> Result<Option<User>, RepoError> {
+            if self.fail {
+                return Err(RepoError("connection refused".to_owned()));
+            }
+            Ok(self.users.get(&id).cloned())
+        }
+
+        fn save_user(&self, user: &User) -> Result<(), RepoError> {
+            if self.fail {
+                return Err(RepoError("connection refused".to_owned()));
+            }
+            self.saved.lock().unwrap().push(user.clone());
+            Ok(())
+        }
+
+        fn find_user_by_name(&self, name: &str) -> Result<Option<User>, RepoError> {
+            if self.fail {
+                return Err(RepoError("connection refused".to_owned()));
+            }
+            Ok(self.users.values().find(|user| user.name == name).cloned())
+        }
+    }
+
+    fn state(repo: Arc<MockUserRepo>) -> State<AppStateDyn> {
+        State(AppStateDyn {
+            users: UserService::new(repo),
+        })
+    }
+
+    fn status<T: IntoResponse>(res: T) -> StatusCode {
+        res.into_response().status()
+    }
+
+    #[tokio::test]
+    async fn get_user_returns_user_from_repo() {
+        let user = User {
+            id: Uuid::new_v4(),
+            name: "Alice".to_owned(),
+        };
+        let repo = Arc::new(MockUserRepo::with_user(user.clone()));
+
+        let Json(found) = get_user_dyn(state(repo), Path(user.id)).await.unwrap();
+
+        assert_eq!(found.id, user.id);
+        assert_eq!(found.name, "Alice");
+    }
+
+    #[tokio::test]
+    async fn get_user_returns_404_for_unknown_id() {
+        let repo = Arc::new(MockUserRepo::default());
+
+        let res = get_user_dyn(state(repo), Path(Uuid::new_v4())).await;
+
+        assert!(matches!(res, Err(AppError::NotFound)));
+        assert_eq!(status(res), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn get_user_returns_500_when_repo_fails() {
+        let repo = Arc::new(MockUserRepo::failing());
+
+        let res = get_user_dyn(state(repo), Path(Uuid::new_v4())).await;
+
+        assert!(matches!(res, Err(AppError::Repo(_))));
+        assert_eq!(status(res), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn create_user_saves_user_to_repo() {
+        let repo = Arc::new(MockUserRepo::default());
+        let params = UserParams {
+            name: "Bob".to_owned(),
+        };
+
+        let Json(created) = create_user_dyn(state(repo.clone()), Json(params))
+            .await
+            .unwrap();
+
+        // 핸들러가 응답으로 준 사용자와 저장소에 저장한 사용자가 같아야 함
+        let saved = repo.saved.lock().unwrap();
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].id, created.id);
+        assert_eq!(saved[0].name, "Bob");
+    }
+
+    #[tokio::test]
+    async fn create_user_returns_500_when_repo_fails() {
+        let repo = Arc::new(MockUserRepo::failing());
+        let params = UserParams {
+            name: "Bob".to_owned(),
+        };
+
+        let res = create_user_dyn(state(repo.clone()), Json(params)).await;
+
+        assert_eq!(status(res), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(repo.saved.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn create_user_returns_409_for_duplicate_name() {
+        let repo = Arc::new(MockUserRepo::with_user(User {
+            id: Uuid::new_v4(),
+            name: "Bob".to_owned(),
+        }));
+        let params = UserParams {
+            name: "Bob".to_owned(),
+        };
+
+        let res = create_user_dyn(state(repo.clone()), Json(params)).await;
+
+        assert_eq!(status(res), StatusCode::CONFLICT);
+        assert!(repo.saved.lock().unwrap().is_empty());
+    }
+
+    // generic 방식도 같은 mock을 타입 파라미터로 주입할 수 있음
+    #[tokio::test]
+    async fn generic_handler_accepts_mock_repo() {
+        let res = get_user_generic(
+            State(AppStateGeneric {
+                users: UserService::new(MockUserRepo::failing()),
+            }),
+            Path(Uuid::new_v4()),
+        )
+        .await;
+
+        assert_eq!(status(res), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
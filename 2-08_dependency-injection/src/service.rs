@@ -0,0 +1,196 @@
+//! 🧱 서비스 계층
+//!
+//! 핸들러 → `UserService` → `UserRepo` 순서로 호출됩니다.
+//! 핸들러는 HTTP 입출력만, 저장소는 데이터 접근만 담당하고
+//! 그 사이의 비즈니스 규칙(검증, 중복 검사)과 공통 관심사(tracing span, 메트릭)는 서비스가 맡습니다.
+//!
+//! 서비스도 저장소 타입을 타입 파라미터로 받으므로 dyn 방식(`UserService<Arc<dyn UserRepo>>`)과
+//! generic 방식(`UserService<T>`) 모두에 그대로 끼워 넣을 수 있습니다.
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{AppError, User, UserRepo};
+
+const MAX_NAME_LEN: usize = 50;
+
+#[derive(Clone)]
+pub struct UserService<R> {
+    repo: R,
+    metrics: Arc<ServiceMetrics>,
+}
+
+impl<R> UserService<R>
+where
+    R: UserRepo,
+{
+    pub fn new(repo: R) -> Self {
+        Self {
+            repo,
+            metrics: Arc::default(),
+        }
+    }
+
+    pub fn get_user(&self, id: Uuid) -> Result<User, AppError> {
+        self.measure("get_user", || {
+            self.repo.get_user(id)?.ok_or(AppError::NotFound)
+        })
+    }
+
+    pub fn create_user(&self, name: &str) -> Result<User, AppError> {
+        self.measure("create_user", || {
+            let name = validate_name(name)?;
+
+            // 같은 이름의 사용자가 이미 있으면 409
+            // (검사와 저장 사이에 경쟁이 있으므로 실제 DB에서는 unique 제약 조건도 함께 두어야 함)
+            if self.repo.find_user_by_name(name)?.is_some() {
+                return Err(AppError::Conflict(format!("user `{name}` already exists")));
+            }
+
+            let user = User {
+                id: Uuid::new_v4(),
+                name: name.to_owned(),
+            };
+            self.repo.save_user(&user)?;
+            Ok(user)
+        })
+    }
+
+    pub fn metrics(&self) -> BTreeMap<&'static str, OperationStats> {
+        self.metrics.snapshot()
+    }
+
+    // 모든 서비스 호출을 span으로 감싸고, 소요 시간을 로그로 남기며, 호출/실패 횟수를 집계
+    fn measure<T>(
+        &self,
+        operation: &'static str,
+        f: impl FnOnce() -> Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        let span = tracing::info_span!("user_service", operation);
+        let _enter = span.enter();
+
+        let started = Instant::now();
+        let result = f();
+        let elapsed = started.elapsed();
+
+        self.metrics.record(operation, result.is_ok());
+        match &result {
+            Ok(_) => tracing::debug!(?elapsed, "succeeded"),
+            Err(err) => tracing::debug!(?elapsed, ?err, "failed"),
+        }
+        result
+    }
+}
+
+// 앞뒤 공백을 제거한 이름을 반환
+fn validate_name(name: &str) -> Result<&str, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("name must not be empty".to_owned()));
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        return Err(AppError::Validation(format!(
+            "name must be at most {MAX_NAME_LEN} characters"
+        )));
+    }
+    Ok(name)
+}
+
+// 📊 연산별 호출 통계
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub struct OperationStats {
+    pub calls: u64,
+    pub errors: u64,
+}
+
+#[derive(Default)]
+struct ServiceMetrics {
+    operations: Mutex<BTreeMap<&'static str, OperationStats>>,
+}
+
+impl ServiceMetrics {
+    fn record(&self, operation: &'static str, ok: bool) {
+        let mut operations = self.operations.lock().unwrap();
+        let stats = operations.entry(operation).or_default();
+        stats.calls += 1;
+        if !ok {
+            stats.errors += 1;
+        }
+    }
+
+    fn snapshot(&self) -> BTreeMap<&'static str, OperationStats> {
+        self.operations.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InMemoryUserRepo;
+
+    #[test]
+    fn create_user_trims_and_saves_name() {
+        let service = UserService::new(InMemoryUserRepo::default());
+
+        let user = service.create_user("  Alice ").unwrap();
+
+        assert_eq!(user.name, "Alice");
+        assert_eq!(service.get_user(user.id).unwrap().name, "Alice");
+    }
+
+    #[test]
+    fn create_user_rejects_invalid_names() {
+        let service = UserService::new(InMemoryUserRepo::default());
+
+        assert!(matches!(
+            service.create_user("   "),
+            Err(AppError::Validation(_))
+        ));
+        assert!(matches!(
+            service.create_user(&"a".repeat(MAX_NAME_LEN + 1)),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn create_user_rejects_duplicate_names() {
+        let service = UserService::new(InMemoryUserRepo::default());
+        service.create_user("Alice").unwrap();
+
+        assert!(matches!(
+            service.create_user("Alice"),
+            Err(AppError::Conflict(_))
+        ));
+    }
+
+    #[test]
+    fn metrics_count_calls_and_errors_per_operation() {
+        let service = UserService::new(InMemoryUserRepo::default());
+        let user = service.create_user("Alice").unwrap();
+        service.get_user(user.id).unwrap();
+        service.get_user(Uuid::new_v4()).unwrap_err();
+
+        let metrics = service.metrics();
+        assert_eq!(
+            metrics["create_user"],
+            OperationStats {
+                calls: 1,
+                errors: 0
+            }
+        );
+        assert_eq!(
+            metrics["get_user"],
+            OperationStats {
+                calls: 2,
+                errors: 1
+            }
+        );
+    }
+}
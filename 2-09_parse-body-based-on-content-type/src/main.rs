@@ -1,22 +1,22 @@
-//! Content-Type 값에 따라 JSON 또는 Form 데이터를 파싱하는 추출기를 만드는 예제입니다.
+//! Content-Type 값에 따라 요청 body를 파싱하고, Accept 값에 따라 응답 형식을 고르는 추출기 예제입니다.
 //! - application/json → serde_json 기반 파싱
 //! - application/x-www-form-urlencoded → URL-encoded form 파싱
-
-/// 📦 의존 라이브러리와 타입 정의
-use axum::{
-    extract::{FromRequest, Request}, // 커스텀 추출기 구현에 필요한 트레잇
-    http::{header::CONTENT_TYPE, StatusCode},
-    response::{IntoResponse, Response},
-    routing::post,
-    Form,
-    Json,
-    RequestExt,
-    Router, // Form, Json 기본 추출기
-};
+//! - application/msgpack → rmp-serde 기반 파싱
+//! - application/cbor → ciborium 기반 파싱
+//! - multipart/form-data → 텍스트 필드만 form처럼 파싱
+//!
+//! 응답은 Accept 헤더에서 가장 선호되는 형식(JSON, MessagePack, CBOR, Form)으로 직렬화됩니다.
+//! 지원하지 않는 Content-Type은 415, 만들 수 없는 Accept는 406과 함께 지원하는 타입 목록을 반환합니다.
+
+mod negotiated;
+
+// 📦 의존 라이브러리와 타입 정의
+use axum::{routing::post, Router};
+use negotiated::{Negotiated, NegotiatedBody};
 use serde::{Deserialize, Serialize}; // 역직렬화용
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-/// 🚀 서버 실행 & 라우터 구성
+// 🚀 서버 실행 & 라우터 구성
 
 #[tokio::main]
 async fn main() {
@@ -41,58 +41,19 @@ async fn main() {
     axum::serve(listener, app).await.unwrap();
 }
 
-/// 📨 수신 데이터 구조체 정의
+// 📨 수신 데이터 구조체 정의
 
 #[derive(Debug, Serialize, Deserialize)]
 struct Payload {
     foo: String,
 }
 
-/// 🧾 요청 처리 핸들러
-
-async fn handler(JsonOrForm(payload): JsonOrForm<Payload>) {
-    dbg!(payload); // 요청 본문을 디버그 출력
-}
-
-/// 🧠 핵심: 커스텀 추출기 JsonOrForm<T> 구현
-
-// Content-Type 에 따라 Json<T> 또는 Form<T> 중 적절히 추출
-struct JsonOrForm<T>(T);
-
-/// 💡 FromRequest 수동 구현
-
-impl<S, T> FromRequest<S> for JsonOrForm<T>
-where
-    S: Send + Sync,
-    Json<T>: FromRequest<()>, // Json 추출 가능
-    Form<T>: FromRequest<()>, // Form 추출 가능
-    T: 'static,
-{
-    type Rejection = Response;
+// 🧾 요청 처리 핸들러
 
-    async fn from_request(req: Request, _state: &S) -> Result<Self, Self::Rejection> {
-        // Content-Type 헤더 추출
-        let content_type_header = req.headers().get(CONTENT_TYPE);
-        let content_type = content_type_header.and_then(|value| value.to_str().ok());
-
-        // Content-Type 이 존재한다면...
-        if let Some(content_type) = content_type {
-            // application/json 인 경우 Json<T> 추출 시도
-            if content_type.starts_with("application/json") {
-                let Json(payload) = req.extract().await.map_err(IntoResponse::into_response)?;
-                return Ok(Self(payload));
-            }
-
-            // application/x-www-form-urlencoded 인 경우 Form<T> 추출 시도
-            if content_type.starts_with("application/x-www-form-urlencoded") {
-                let Form(payload) = req.extract().await.map_err(IntoResponse::into_response)?;
-                return Ok(Self(payload));
-            }
-        }
-
-        // 지원되지 않는 Content-Type 이면 415 응답
-        Err(StatusCode::UNSUPPORTED_MEDIA_TYPE.into_response())
-    }
+// 받은 payload를 Accept에 맞는 형식으로 그대로 돌려줌
+async fn handler(body: NegotiatedBody<Payload>) -> Negotiated<Payload> {
+    dbg!(&body.value); // 요청 본문을 디버그 출력
+    body.response_format.respond(body.value)
 }
 
 // ✅ 테스트 방법
@@ -110,3 +71,20 @@ where
 //      -d 'foo=hello-form'
 // ➡ 서버 콘솔:
 // [src/main.rs:handler] payload = Payload { foo: "hello-form" }
+//
+// 3. MessagePack 요청 → CBOR 응답 (파일은 {"foo":"hello"}를 직렬화한 것)
+// curl -X POST http://localhost:3000 \
+//      -H "Content-Type: application/msgpack" \
+//      -H "Accept: application/cbor" \
+//      --data-binary @payload.msgpack -o response.cbor
+//
+// 4. multipart 요청 (텍스트 필드만 지원) → JSON 응답
+// curl -X POST http://localhost:3000 -F foo=hello-multipart
+// ➡ {"foo":"hello-multipart"}
+//
+// 5. 지원하지 않는 형식
+// curl -i -X POST http://localhost:3000 -H "Content-Type: text/plain" -d 'foo'
+// ➡ 415 {"message":"unsupported content type","supported":["application/json", ...]}
+// curl -i -X POST http://localhost:3000 -H "Content-Type: application/json" \
+//      -H "Accept: text/html" -d '{"foo": "x"}'
+// ➡ 406 {"message":"none of the accepted types can be produced","supported":[...]}
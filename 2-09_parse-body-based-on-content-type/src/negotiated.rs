@@ -0,0 +1,589 @@
+//! Content-Type / Accept 헤더 기반 content negotiation
+//!
+//! • 요청: `Content-Type`에 따라 JSON, Form, MessagePack, CBOR, multipart 중 하나로 역직렬화
+//!   (지원하지 않는 Content-Type → 415 + 지원하는 타입 목록)
+//! • 응답: `Accept`에서 가장 선호되는(q 값이 높은) 형식으로 직렬화
+//!   (Accept가 없거나 `*/*`이면 요청과 같은 형식, 지원하는 형식이 하나도 없으면 406 + 목록)
+
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, Multipart, Request},
+    http::{
+        header::{ACCEPT, CONTENT_TYPE},
+        HeaderMap, HeaderValue, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Json, RequestExt,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// 지원하는 직렬화 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Form,
+    MsgPack,
+    Cbor,
+    // multipart/form-data는 요청 body로만 지원
+    Multipart,
+}
+
+impl Format {
+    /// 요청 body로 받을 수 있는 형식
+    const REQUEST: [Format; 5] = [
+        Format::Json,
+        Format::Form,
+        Format::MsgPack,
+        Format::Cbor,
+        Format::Multipart,
+    ];
+
+    /// 응답으로 만들 수 있는 형식 (앞에 있을수록 기본값으로 우선)
+    const RESPONSE: [Format; 4] = [Format::Json, Format::MsgPack, Format::Cbor, Format::Form];
+
+    fn mime(self) -> &'static str {
+        match self {
+            Format::Json => "application/json",
+            Format::Form => "application/x-www-form-urlencoded",
+            Format::MsgPack => "application/msgpack",
+            Format::Cbor => "application/cbor",
+            Format::Multipart => "multipart/form-data",
+        }
+    }
+
+    fn from_mime(essence: &str) -> Option<Self> {
+        match essence {
+            "application/json" => Some(Format::Json),
+            "application/x-www-form-urlencoded" => Some(Format::Form),
+            // msgpack은 공식 media type 등록 전부터 x- 접두사가 흔히 쓰였음
+            "application/msgpack" | "application/x-msgpack" => Some(Format::MsgPack),
+            "application/cbor" => Some(Format::Cbor),
+            "multipart/form-data" => Some(Format::Multipart),
+            _ => None,
+        }
+    }
+
+    fn can_respond(self) -> bool {
+        Format::RESPONSE.contains(&self)
+    }
+
+    /// 이 형식으로 직렬화되는 응답
+    pub fn respond<T>(self, value: T) -> Negotiated<T> {
+        Negotiated {
+            format: self,
+            value,
+        }
+    }
+}
+
+/// Content-Type에 맞게 역직렬화된 요청 body
+///
+/// `Accept`로 결정된 응답 형식도 함께 들고 있어서 `response_format.respond(..)`로 응답을 만들 수 있음
+#[derive(Debug)]
+pub struct NegotiatedBody<T> {
+    pub value: T,
+    pub response_format: Format,
+}
+
+impl<S, T> FromRequest<S> for NegotiatedBody<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned + 'static,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, _state: &S) -> Result<Self, Self::Rejection> {
+        let Some(format) = request_format(req.headers()) else {
+            return Err(unsupported(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "unsupported content type",
+                &Format::REQUEST,
+            ));
+        };
+
+        // 응답 형식을 먼저 정해서, 어차피 응답할 수 없는 요청이면 body를 읽기 전에 거절
+        let response_format = response_format(req.headers(), format).ok_or_else(|| {
+            unsupported(
+                StatusCode::NOT_ACCEPTABLE,
+                "none of the accepted types can be produced",
+                &Format::RESPONSE,
+            )
+        })?;
+
+        let value = match format {
+            // `Json`/`Form` 추출기는 Content-Type을 다시 (더 엄격하게) 검사함
+            // → 여기서 이미 고른 형식과 어긋나지 않도록 body만 받아서 직접 파싱
+            Format::Json => {
+                let bytes: Bytes = req.extract().await.map_err(IntoResponse::into_response)?;
+                let Json(value) = Json::from_bytes(&bytes).map_err(IntoResponse::into_response)?;
+                value
+            }
+            Format::Form => {
+                let bytes: Bytes = req.extract().await.map_err(IntoResponse::into_response)?;
+                serde_urlencoded::from_bytes(&bytes).map_err(bad_request)?
+            }
+            Format::MsgPack => {
+                let bytes: Bytes = req.extract().await.map_err(IntoResponse::into_response)?;
+                rmp_serde::from_slice(&bytes).map_err(bad_request)?
+            }
+            Format::Cbor => {
+                let bytes: Bytes = req.extract().await.map_err(IntoResponse::into_response)?;
+                ciborium::from_reader(&bytes[..]).map_err(bad_request)?
+            }
+            Format::Multipart => {
+                let multipart: Multipart =
+                    req.extract().await.map_err(IntoResponse::into_response)?;
+                from_multipart(multipart).await?
+            }
+        };
+
+        Ok(Self {
+            value,
+            response_format,
+        })
+    }
+}
+
+/// 협상된 형식으로 직렬화되는 응답
+#[derive(Debug)]
+pub struct Negotiated<T> {
+    pub format: Format,
+    pub value: T,
+}
+
+impl<T> IntoResponse for Negotiated<T>
+where
+    T: Serialize,
+{
+    fn into_response(self) -> Response {
+        let body = match self.format {
+            Format::Json => serde_json::to_vec(&self.value).map_err(|err| err.to_string()),
+            Format::Form => serde_urlencoded::to_string(&self.value)
+                .map(String::into_bytes)
+                .map_err(|err| err.to_string()),
+            // 필드 이름을 포함(map)해야 다른 언어의 클라이언트도 읽기 쉬움
+            Format::MsgPack => rmp_serde::to_vec_named(&self.value).map_err(|err| err.to_string()),
+            Format::Cbor => {
+                let mut buf = Vec::new();
+                ciborium::into_writer(&self.value, &mut buf)
+                    .map(|()| buf)
+                    .map_err(|err| err.to_string())
+            }
+            Format::Multipart => Err("multipart responses are not supported".to_owned()),
+        };
+
+        match body {
+            Ok(body) => (
+                [(CONTENT_TYPE, HeaderValue::from_static(self.format.mime()))],
+                body,
+            )
+                .into_response(),
+            Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err).into_response(),
+        }
+    }
+}
+
+// "application/json; charset=utf-8" → "application/json"
+fn essence(media_type: &str) -> String {
+    media_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+fn request_format(headers: &HeaderMap) -> Option<Format> {
+    let content_type = headers.get(CONTENT_TYPE)?.to_str().ok()?;
+    Format::from_mime(&essence(content_type))
+}
+
+/// Accept 헤더에서 q 값이 가장 높은, 응답 가능한 형식을 고름
+fn response_format(headers: &HeaderMap, request_format: Format) -> Option<Format> {
+    // 요청과 같은 형식으로 응답할 수 없으면(multipart) JSON을 기본값으로 사용
+    let default = if request_format.can_respond() {
+        request_format
+    } else {
+        Format::Json
+    };
+
+    let Some(accept) = headers.get(ACCEPT).and_then(|value| value.to_str().ok()) else {
+        return Some(default);
+    };
+
+    let mut best: Option<(f32, Format)> = None;
+    for media_range in accept.split(',') {
+        let (media_type, q) = parse_media_range(media_range);
+        if q <= 0.0 {
+            continue; // q=0은 "받지 않음"
+        }
+
+        let format = match media_type.as_str() {
+            "*/*" | "application/*" => Some(default),
+            media_type => Format::from_mime(media_type).filter(|format| format.can_respond()),
+        };
+        // 같은 q 값이면 먼저 나온 것을 유지
+        if let Some(format) = format {
+            if best.is_none_or(|(best_q, _)| q > best_q) {
+                best = Some((q, format));
+            }
+        }
+    }
+
+    best.map(|(_, format)| format)
+}
+
+// "application/cbor;q=0.8" → ("application/cbor", 0.8)
+fn parse_media_range(media_range: &str) -> (String, f32) {
+    let q = media_range
+        .split(';')
+        .skip(1)
+        .filter_map(|param| param.trim().strip_prefix("q="))
+        .find_map(|q| q.trim().parse().ok())
+        .unwrap_or(1.0);
+    (essence(media_range), q)
+}
+
+/// multipart의 텍스트 필드를 form 데이터처럼 역직렬화 (파일 필드는 거절)
+async fn from_multipart<T>(mut multipart: Multipart) -> Result<T, Response>
+where
+    T: DeserializeOwned,
+{
+    let mut fields = Vec::new();
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(IntoResponse::into_response)?
+    {
+        let Some(name) = field.name().map(ToOwned::to_owned) else {
+            continue;
+        };
+        if field.file_name().is_some() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("file field `{name}` is not supported"),
+            )
+                .into_response());
+        }
+        let value = field.text().await.map_err(IntoResponse::into_response)?;
+        fields.push((name, value));
+    }
+
+    // 필드 목록을 urlencoded 문자열로 다시 만들어 Form과 같은 규칙으로 역직렬화
+    let encoded = serde_urlencoded::to_string(&fields).map_err(bad_request)?;
+    serde_urlencoded::from_str(&encoded).map_err(bad_request)
+}
+
+fn bad_request<E>(err: E) -> Response
+where
+    E: std::fmt::Display,
+{
+    (StatusCode::BAD_REQUEST, err.to_string()).into_response()
+}
+
+/// 415/406 응답: 지원하는 media type 목록을 JSON body와 `Accept` 헤더로 알려줌
+fn unsupported(status: StatusCode, message: &str, formats: &[Format]) -> Response {
+    #[derive(Serialize)]
+    struct Unsupported<'a> {
+        message: &'a str,
+        supported: Vec<&'static str>,
+    }
+
+    let supported: Vec<_> = formats.iter().map(|format| format.mime()).collect();
+    let accept = HeaderValue::from_str(&supported.join(", ")).unwrap();
+
+    (
+        status,
+        [(ACCEPT, accept)],
+        Json(Unsupported { message, supported }),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::post, Router};
+    use http_body_util::BodyExt;
+    use serde::Deserialize;
+    use tower::ServiceExt;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Payload {
+        foo: String,
+    }
+
+    fn app() -> Router {
+        Router::new().route(
+            "/",
+            post(|body: NegotiatedBody<Payload>| async move {
+                body.response_format.respond(body.value)
+            }),
+        )
+    }
+
+    async fn send(content_type: &str, accept: Option<&str>, body: Vec<u8>) -> Response {
+        let mut request = axum::http::Request::post("/").header(CONTENT_TYPE, content_type);
+        if let Some(accept) = accept {
+            request = request.header(ACCEPT, accept);
+        }
+        app()
+            .oneshot(request.body(Body::from(body)).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn body_bytes(response: Response) -> Vec<u8> {
+        response
+            .into_body()
+            .collect()
+            .await
+            .unwrap()
+            .to_bytes()
+            .to_vec()
+    }
+
+    fn payload() -> Payload {
+        Payload {
+            foo: "hello".to_owned(),
+        }
+    }
+
+    fn cbor(value: &Payload) -> Vec<u8> {
+        let mut buf = Vec::new();
+        ciborium::into_writer(value, &mut buf).unwrap();
+        buf
+    }
+
+    #[tokio::test]
+    async fn json_roundtrip() {
+        let response = send("application/json", None, br#"{"foo":"hello"}"#.to_vec()).await;
+
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        assert_eq!(body_bytes(response).await, br#"{"foo":"hello"}"#);
+    }
+
+    #[tokio::test]
+    async fn msgpack_roundtrip() {
+        let body = rmp_serde::to_vec_named(&payload()).unwrap();
+        let response = send("application/msgpack", None, body).await;
+
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/msgpack");
+        let decoded: Payload = rmp_serde::from_slice(&body_bytes(response).await).unwrap();
+        assert_eq!(decoded, payload());
+    }
+
+    #[tokio::test]
+    async fn cbor_roundtrip() {
+        let response = send("application/cbor", None, cbor(&payload())).await;
+
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/cbor");
+        let decoded: Payload = ciborium::from_reader(&body_bytes(response).await[..]).unwrap();
+        assert_eq!(decoded, payload());
+    }
+
+    #[tokio::test]
+    async fn multipart_text_fields_respond_as_json() {
+        let body = "--X\r\n\
+            Content-Disposition: form-data; name=\"foo\"\r\n\r\n\
+            hello\r\n\
+            --X--\r\n";
+        let response = send("multipart/form-data; boundary=X", None, body.into()).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        assert_eq!(body_bytes(response).await, br#"{"foo":"hello"}"#);
+    }
+
+    #[tokio::test]
+    async fn multipart_file_field_is_rejected() {
+        let body = "--X\r\n\
+            Content-Disposition: form-data; name=\"foo\"; filename=\"a.txt\"\r\n\r\n\
+            hello\r\n\
+            --X--\r\n";
+        let response = send("multipart/form-data; boundary=X", None, body.into()).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    // 요청은 form, 응답은 Accept에서 q 값이 가장 높은 CBOR
+    #[tokio::test]
+    async fn accept_picks_highest_quality() {
+        let accept = "application/json;q=0.5, application/cbor, text/html;q=0.9";
+        let response = send(
+            "application/x-www-form-urlencoded",
+            Some(accept),
+            b"foo=hello".to_vec(),
+        )
+        .await;
+
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/cbor");
+        assert_eq!(body_bytes(response).await, cbor(&payload()));
+    }
+
+    #[tokio::test]
+    async fn wildcard_accept_uses_request_format() {
+        let body = rmp_serde::to_vec_named(&payload()).unwrap();
+        let response = send("application/x-msgpack", Some("*/*"), body).await;
+
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/msgpack");
+    }
+
+    #[tokio::test]
+    async fn unsupported_content_type_lists_supported_types() {
+        let response = send("text/plain", None, b"foo".to_vec()).await;
+
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert!(response.headers()[ACCEPT]
+            .to_str()
+            .unwrap()
+            .contains("application/cbor"));
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+        assert_eq!(body["supported"].as_array().unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn unacceptable_accept_returns_406() {
+        let accept = "text/html, application/json;q=0";
+        let response = send("application/json", Some(accept), br#"{"foo":"x"}"#.to_vec()).await;
+
+        assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+        assert_eq!(
+            body["supported"],
+            serde_json::json!([
+                "application/json",
+                "application/msgpack",
+                "application/cbor",
+                "application/x-www-form-urlencoded"
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn content_type_case_and_parameters_are_ignored() {
+        let response = send(
+            "APPLICATION/X-WWW-FORM-URLENCODED",
+            None,
+            b"foo=hello".to_vec(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = send("application/json;a=:", None, br#"{"foo":"hello"}"#.to_vec()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn malformed_msgpack_is_bad_request() {
+        let response = send("application/msgpack", None, vec![0xc1]).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    /// 🎲 임의의 Content-Type / Accept / body 조합
+    ///
+    /// • 패닉이나 5xx 없이 항상 응답
+    /// • 415/406은 JSON 목록 + `Accept` 헤더, 나머지 거절도 설명이 담긴 4xx
+    mod fuzz {
+        use super::*;
+        use proptest::prelude::*;
+
+        const MEDIA_TYPES: [&str; 9] = [
+            "application/json",
+            "application/x-www-form-urlencoded",
+            "application/msgpack",
+            "application/x-msgpack",
+            "application/cbor",
+            "multipart/form-data; boundary=X",
+            "multipart/form-data",
+            "*/*",
+            "application/*",
+        ];
+
+        fn media_type() -> impl Strategy<Value = String> {
+            prop_oneof![
+                // 알려진 타입 + 대소문자/파라미터 변형
+                (
+                    prop::sample::select(&MEDIA_TYPES[..]),
+                    any::<bool>(),
+                    prop::option::of("; ?(charset=utf-8|q=[0-9.]{0,4}|[a-z]{1,5}=[ -~]{0,5})"),
+                )
+                    .prop_map(|(media_type, upper, param)| {
+                        let media_type = if upper {
+                            media_type.to_uppercase()
+                        } else {
+                            media_type.to_owned()
+                        };
+                        media_type + param.as_deref().unwrap_or_default()
+                    }),
+                // 헤더 값으로 쓸 수 있는 아무 문자열 (쉼표 목록 포함)
+                "[ -~]{0,40}",
+            ]
+        }
+
+        fn body() -> impl Strategy<Value = Vec<u8>> {
+            let foo = ".{0,12}";
+            prop_oneof![
+                prop::collection::vec(any::<u8>(), 0..128),
+                foo.prop_map(|foo| serde_json::to_vec(&Payload { foo }).unwrap()),
+                foo.prop_map(|foo| serde_urlencoded::to_string(Payload { foo }).unwrap().into_bytes()),
+                foo.prop_map(|foo| rmp_serde::to_vec_named(&Payload { foo }).unwrap()),
+                foo.prop_map(|foo| cbor(&Payload { foo })),
+                (foo, prop::option::of("[a-z.]{1,8}")).prop_map(|(foo, file_name)| {
+                    let file_name = file_name
+                        .map(|name| format!("; filename=\"{name}\""))
+                        .unwrap_or_default();
+                    format!(
+                        "--X\r\ncontent-disposition: form-data; name=\"foo\"{file_name}\r\n\r\n{foo}\r\n--X--\r\n"
+                    )
+                    .into_bytes()
+                }),
+            ]
+        }
+
+        proptest! {
+            #[test]
+            fn arbitrary_requests_never_fail_on_the_server(
+                content_type in media_type(),
+                accept in prop::option::of(media_type()),
+                body in body(),
+            ) {
+                let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+                let (status, headers, body) = runtime.block_on(async {
+                    let response = send(&content_type, accept.as_deref(), body).await;
+                    let status = response.status();
+                    let headers = response.headers().clone();
+                    (status, headers, body_bytes(response).await)
+                });
+
+                match status {
+                    StatusCode::OK => {
+                        let content_type = headers[CONTENT_TYPE].to_str().unwrap();
+                        prop_assert!(
+                            Format::RESPONSE.iter().any(|format| format.mime() == content_type),
+                            "{content_type}"
+                        );
+                    }
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE | StatusCode::NOT_ACCEPTABLE => {
+                        prop_assert_eq!(&headers[CONTENT_TYPE], "application/json");
+                        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                        let supported: Vec<&str> = body["supported"]
+                            .as_array()
+                            .unwrap()
+                            .iter()
+                            .map(|value| value.as_str().unwrap())
+                            .collect();
+                        prop_assert!(body["message"].is_string());
+                        prop_assert_eq!(headers[ACCEPT].to_str().unwrap(), supported.join(", "));
+                    }
+                    status => {
+                        prop_assert!(status.is_client_error(), "{status}");
+                        prop_assert!(headers.contains_key(CONTENT_TYPE));
+                        prop_assert!(!body.is_empty());
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,11 +1,26 @@
 //! URL 경로에 포함된 "버전 정보"를 기반으로 처리 로직을 분기하는 예제.
 //! > /v1/foo, /v2/foo 등에서 "v1", "v2"를 추출하고,
 //! > 이를 Enum으로 변환해 핸들러에서 활용하는 방식.
+//!
 //! API 버전 관리 시 매우 실용적인 패턴이며, 실무에서도 흔히 쓰이는 구조.
+//!
+//! 경로 외에도 헤더로 버전을 지정할 수 있음:
+//! > `Accept: application/vnd.example.v2+json` (content negotiation)
+//! > `X-Api-Version: 2`
+//!
+//! 여러 곳에 버전이 있으면 `VersionConfig::precedence` 순서대로 먼저 찾은 값을 사용.
+//! V1 응답에는 deprecation 미들웨어가 `Deprecation`/`Sunset` 헤더를 추가함.
+//!
+//! 버전마다 핸들러가 다른 경로는 `VersionedRouter`로 등록 (`versioned.rs`)
+//! > `/users/{id}`: V1, V2 핸들러를 등록하면 `/v1/users/{id}`, `/v2/users/{id}`, `/v3/users/{id}`와
+//! > 헤더로 고르는 `/users/{id}`가 함께 생김
+
+mod versioned;
 
 use axum::{
-    extract::{FromRequestParts, Path}, // 커스텀 추출기 + 경로 변수 추출
-    http::{request::Parts, StatusCode},
+    extract::{FromRef, FromRequestParts, Path, Request}, // 커스텀 추출기 + 경로 변수 추출
+    http::{header, request::Parts, HeaderMap, HeaderValue, StatusCode},
+    middleware::{self, Next},
     response::{Html, IntoResponse, Response},
     routing::get,
     RequestPartsExt,
@@ -13,8 +28,9 @@ use axum::{
 };
 use std::collections::HashMap;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use versioned::VersionedRouter;
 
-/// 🧭 main 함수
+// 🧭 main 함수
 
 #[tokio::main]
 async fn main() {
@@ -39,57 +55,250 @@ async fn main() {
     axum::serve(listener, app).await.unwrap();
 }
 
-/// 🧱 라우터 구성
+// 🧱 라우터 구성
 
 fn app() -> Router {
-    // /{version}/foo 경로에 대응
-    Router::new().route("/{version}/foo", get(handler))
-    // 여기서 {version}은 동적 경로 파라미터이며, 이후에 Version 타입으로 변환됨.
+    app_with_config(VersionConfig::default())
+}
+
+fn app_with_config(config: VersionConfig) -> Router {
+    Router::new()
+        // /{version}/foo 경로에 대응
+        // 여기서 {version}은 동적 경로 파라미터이며, 이후에 Version 타입으로 변환됨.
+        .route("/{version}/foo", get(handler))
+        // 경로에 버전이 없으면 헤더에서 버전을 찾음
+        .route("/foo", get(handler))
+        .merge(user_routes())
+        // 라우팅 이후에 실행되어야 경로 변수를 읽을 수 있으므로 route_layer 사용
+        .route_layer(middleware::from_fn_with_state(config.clone(), deprecation))
+        .with_state(config)
 }
 
-/// 📩 핸들러
+// 📩 핸들러
 
 async fn handler(version: Version) -> Html<String> {
     Html(format!("received request with version {version:?}"))
     // version은 자동으로 Version enum으로 파싱된 결과.
 }
 
-/// 🧠 핵심 로직: 커스텀 추출기 구현 (Version enum)
+// 🗂 버전별 핸들러 (VersionedRouter)
+
+fn user_routes() -> Router<VersionConfig> {
+    VersionedRouter::new()
+        .route("/users/{id}", Version::V1, get(get_user_v1))
+        // V2부터 응답 형식이 바뀜 (V3도 이 핸들러를 그대로 사용)
+        .route("/users/{id}", Version::V2, get(get_user_v2))
+        // 버전과 상관없이 같은 API
+        .route("/status", Version::V1, get(status))
+        // 헤더 없이 /users/{id}로 오면 V2
+        .default_version(Version::V2)
+        .build()
+}
+
+async fn get_user_v1(Path(id): Path<u32>) -> String {
+    format!("user {id}: Ferris")
+}
+
+async fn get_user_v2(version: Version, Path(id): Path<u32>) -> String {
+    format!(
+        r#"{{"id":{id},"name":{{"first":"Ferris"}},"version":"{}"}}"#,
+        version.prefix()
+    )
+}
+
+async fn status(version: Version) -> String {
+    format!("ok ({})", version.prefix())
+}
+
+// 🧠 핵심 로직: 커스텀 추출기 구현 (Version enum)
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 enum Version {
     V1,
     V2,
     V3,
 }
 
+impl Version {
+    const ALL: [Version; 3] = [Version::V1, Version::V2, Version::V3];
+
+    fn prefix(self) -> &'static str {
+        match self {
+            Version::V1 => "v1",
+            Version::V2 => "v2",
+            Version::V3 => "v3",
+        }
+    }
+
+    // "v1", "1" 형태 모두 허용
+    fn parse(value: &str) -> Option<Self> {
+        match value.strip_prefix('v').unwrap_or(value) {
+            "1" => Some(Version::V1),
+            "2" => Some(Version::V2),
+            "3" => Some(Version::V3),
+            _ => None,
+        }
+    }
+}
+
+/// 버전을 찾을 위치
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VersionSource {
+    /// `/{version}/foo`
+    Path,
+    /// `X-Api-Version: 2`
+    Header,
+    /// `Accept: application/vnd.example.v2+json`
+    Accept,
+}
+
+#[derive(Debug, Clone)]
+struct VersionConfig {
+    // 앞에 있는 위치일수록 우선순위가 높음
+    precedence: Vec<VersionSource>,
+    // 어디에서도 버전을 찾지 못했을 때 사용할 버전 (None이면 400)
+    default: Option<Version>,
+}
+
+impl Default for VersionConfig {
+    fn default() -> Self {
+        Self {
+            precedence: vec![
+                VersionSource::Path,
+                VersionSource::Header,
+                VersionSource::Accept,
+            ],
+            default: None,
+        }
+    }
+}
+
+// 헤더에서 버전을 읽지 못했을 때의 에러 응답
+type VersionRejection = (StatusCode, &'static str);
+
+const VERSION_HEADER: &str = "x-api-version";
+
+// application/vnd.example.v2+json → "v2"
+const VENDOR_PREFIX: &str = "application/vnd.example.";
+const VENDOR_SUFFIX: &str = "+json";
+
 impl<S> FromRequestParts<S> for Version
 where
+    VersionConfig: FromRef<S>,
     S: Send + Sync,
 {
     type Rejection = Response;
 
-    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
-        // 경로 변수 전체를 HashMap 으로 파싱
-        let params: Path<HashMap<String, String>> =
-            parts.extract().await.map_err(IntoResponse::into_response)?;
-
-        // "version" 파라미터 가져오기
-        let version = params
-            .get("version")
-            .ok_or_else(|| (StatusCode::NOT_FOUND, "version param missing").into_response())?;
-
-        // 문자열을 enum 으로 매핑
-        match version.as_str() {
-            "v1" => Ok(Version::V1),
-            "v2" => Ok(Version::V2),
-            "v3" => Ok(Version::V3),
-            _ => Err((StatusCode::NOT_FOUND, "unknown version").into_response()),
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        // VersionedRouter가 이미 고른 버전
+        if let Some(version) = parts.extensions.get::<Version>() {
+            return Ok(*version);
+        }
+
+        let config = VersionConfig::from_ref(state);
+
+        for source in &config.precedence {
+            let version = match source {
+                VersionSource::Path => version_from_path(parts).await?,
+                VersionSource::Header => {
+                    version_from_header(&parts.headers).map_err(IntoResponse::into_response)?
+                }
+                VersionSource::Accept => {
+                    version_from_accept(&parts.headers).map_err(IntoResponse::into_response)?
+                }
+            };
+            // 해당 위치에 버전 정보가 없으면 다음 위치를 확인
+            if let Some(version) = version {
+                return Ok(version);
+            }
         }
+
+        config
+            .default
+            .ok_or_else(|| (StatusCode::BAD_REQUEST, "api version missing").into_response())
+    }
+}
+
+async fn version_from_path(parts: &mut Parts) -> Result<Option<Version>, Response> {
+    // 경로 변수 전체를 HashMap 으로 파싱
+    let params: Path<HashMap<String, String>> =
+        parts.extract().await.map_err(IntoResponse::into_response)?;
+
+    // "version" 파라미터가 없는 라우트(/foo)라면 다른 위치를 확인
+    let Some(version) = params.get("version") else {
+        return Ok(None);
+    };
+
+    // 문자열을 enum 으로 매핑 (경로는 "v1" 형태만 허용)
+    match version.as_str() {
+        "v1" | "v2" | "v3" => Ok(Version::parse(version)),
+        _ => Err((StatusCode::NOT_FOUND, "unknown version").into_response()),
+    }
+}
+
+fn version_from_header(headers: &HeaderMap) -> Result<Option<Version>, VersionRejection> {
+    let Some(value) = headers.get(VERSION_HEADER) else {
+        return Ok(None);
+    };
+
+    value
+        .to_str()
+        .ok()
+        .and_then(|value| Version::parse(value.trim()))
+        .map(Some)
+        .ok_or((StatusCode::BAD_REQUEST, "unknown version"))
+}
+
+fn version_from_accept(headers: &HeaderMap) -> Result<Option<Version>, VersionRejection> {
+    let Some(accept) = headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return Ok(None);
+    };
+
+    // Accept는 "text/html, application/vnd.example.v2+json;q=0.9" 처럼 여러 값을 가질 수 있음
+    // 이 API의 vendor media type만 보고, 나머지(*/* 등)는 무시
+    for media_type in accept.split(',') {
+        let media_type = media_type.split(';').next().unwrap_or_default().trim();
+        let Some(version) = media_type
+            .strip_prefix(VENDOR_PREFIX)
+            .and_then(|rest| rest.strip_suffix(VENDOR_SUFFIX))
+        else {
+            continue;
+        };
+
+        return Version::parse(version)
+            .map(Some)
+            .ok_or((StatusCode::NOT_ACCEPTABLE, "unsupported media type version"));
     }
+
+    Ok(None)
+}
+
+/// 🕰 deprecation 미들웨어
+///
+/// V1으로 처리된 응답에 폐기 예정임을 알리는 헤더를 추가
+/// > `Deprecation: true` — 폐기 예정(deprecated)인 API
+/// > `Sunset: <HTTP-date>` — 이 시점 이후 제공 중단 (RFC 8594)
+async fn deprecation(version: Result<Version, Response>, req: Request, next: Next) -> Response {
+    let mut res = next.run(req).await;
+
+    // VersionedRouter로 처리된 응답에는 실제로 처리한 버전이 남아 있음 (기본 버전 포함)
+    let version = res.extensions().get::<Version>().copied().or(version.ok());
+
+    // 버전 추출에 실패한 요청은 핸들러가 에러 응답을 만들므로 그대로 반환
+    if version == Some(Version::V1) {
+        let headers = res.headers_mut();
+        headers.insert("deprecation", HeaderValue::from_static("true"));
+        headers.insert("sunset", HeaderValue::from_static(V1_SUNSET));
+    }
+    res
 }
 
-/// 🧪 테스트 코드
+const V1_SUNSET: &str = "Thu, 31 Dec 2026 23:59:59 GMT";
+
+// 🧪 테스트 코드
 
 #[cfg(test)]
 mod tests {
@@ -139,4 +348,131 @@ mod tests {
 
         assert_eq!(html, "unknown version");
     }
+
+    async fn send(app: Router, request: Request<Body>) -> (StatusCode, HeaderMap, String) {
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let headers = response.headers().clone();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, headers, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    fn get(uri: &str, headers: &[(&str, &str)]) -> Request<Body> {
+        let mut request = Request::builder().uri(uri);
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        request.body(Body::empty()).unwrap()
+    }
+
+    // ✅ X-Api-Version 헤더로 버전 지정
+    #[tokio::test]
+    async fn test_version_header() {
+        let (status, _, body) = send(app(), get("/foo", &[("x-api-version", "2")])).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "received request with version V2");
+    }
+
+    // ✅ Accept 헤더의 vendor media type으로 버전 지정
+    #[tokio::test]
+    async fn test_accept_header() {
+        let accept = "text/html, application/vnd.example.v3+json;q=0.9";
+        let (status, _, body) = send(app(), get("/foo", &[("accept", accept)])).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "received request with version V3");
+    }
+
+    // 지원하지 않는 버전의 media type → 406
+    #[tokio::test]
+    async fn test_accept_unknown_version() {
+        let accept = "application/vnd.example.v9+json";
+        let (status, _, _) = send(app(), get("/foo", &[("accept", accept)])).await;
+
+        assert_eq!(status, StatusCode::NOT_ACCEPTABLE);
+    }
+
+    // 버전 정보가 전혀 없으면 400, default가 있으면 그 버전 사용
+    #[tokio::test]
+    async fn test_missing_version() {
+        let (status, _, body) = send(app(), get("/foo", &[("accept", "*/*")])).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body, "api version missing");
+
+        let app = app_with_config(VersionConfig {
+            default: Some(Version::V2),
+            ..VersionConfig::default()
+        });
+        let (status, _, body) = send(app, get("/foo", &[])).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "received request with version V2");
+    }
+
+    // 기본 설정에서는 경로 > X-Api-Version > Accept 순서
+    #[tokio::test]
+    async fn test_default_precedence() {
+        let headers = [
+            ("x-api-version", "3"),
+            ("accept", "application/vnd.example.v1+json"),
+        ];
+
+        let (_, _, body) = send(app(), get("/v2/foo", &headers)).await;
+        assert_eq!(body, "received request with version V2");
+
+        let (_, _, body) = send(app(), get("/foo", &headers)).await;
+        assert_eq!(body, "received request with version V3");
+    }
+
+    // 우선순위를 바꾸면 Accept가 먼저 적용됨
+    #[tokio::test]
+    async fn test_custom_precedence() {
+        let app = app_with_config(VersionConfig {
+            precedence: vec![VersionSource::Accept, VersionSource::Header],
+            default: None,
+        });
+        let headers = [
+            ("x-api-version", "3"),
+            ("accept", "application/vnd.example.v2+json"),
+        ];
+
+        let (_, _, body) = send(app, get("/foo", &headers)).await;
+        assert_eq!(body, "received request with version V2");
+    }
+
+    // V1 응답에만 Deprecation/Sunset 헤더가 붙음
+    #[tokio::test]
+    async fn test_deprecation_headers() {
+        let (_, headers, _) = send(app(), get("/v1/foo", &[])).await;
+        assert_eq!(headers["deprecation"], "true");
+        assert_eq!(headers["sunset"], V1_SUNSET);
+
+        let (_, headers, _) = send(app(), get("/foo", &[("x-api-version", "1")])).await;
+        assert_eq!(headers["deprecation"], "true");
+
+        let (_, headers, _) = send(app(), get("/v2/foo", &[])).await;
+        assert!(!headers.contains_key("deprecation"));
+        assert!(!headers.contains_key("sunset"));
+    }
+
+    // VersionedRouter 경로도 같은 Version 추출기와 deprecation 미들웨어를 사용
+    #[tokio::test]
+    async fn test_versioned_routes() {
+        let (status, headers, body) = send(app(), get("/v1/users/7", &[])).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "user 7: Ferris");
+        assert_eq!(headers["deprecation"], "true");
+
+        let (_, headers, body) = send(app(), get("/users/7", &[("x-api-version", "1")])).await;
+        assert_eq!(body, "user 7: Ferris");
+        assert_eq!(headers["deprecation"], "true");
+
+        // 헤더가 없으면 기본 버전(V2)
+        let (_, headers, body) = send(app(), get("/users/7", &[])).await;
+        assert_eq!(body, r#"{"id":7,"name":{"first":"Ferris"},"version":"v2"}"#);
+        assert!(!headers.contains_key("deprecation"));
+
+        let (_, _, body) = send(app(), get("/v3/status", &[])).await;
+        assert_eq!(body, "ok (v3)");
+    }
 }
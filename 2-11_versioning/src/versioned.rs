@@ -0,0 +1,300 @@
+//! 🗂 `VersionedRouter`: 같은 논리 경로에 버전별 핸들러를 등록하는 헬퍼
+//!
+//! ```rust,ignore
+//! VersionedRouter::new()
+//!     .route("/users/{id}", Version::V1, get(get_user_v1))
+//!     .route("/users/{id}", Version::V2, get(get_user_v2))
+//!     .default_version(Version::V2)
+//!     .build()
+//! ```
+//!
+//! • 핸들러는 "이 버전부터" 사용 → 더 새 변형이 등록될 때까지 이후 버전에서도 그대로 공유
+//!   → 위 예에서 V3는 `get_user_v2`를 사용, 바뀌지 않은 API는 한 번만 등록하면 됨
+//! • 버전마다 `/v1/users/{id}`, `/v2/users/{id}`, `/v3/users/{id}`를 자동으로 등록
+//!   → 경로에 버전이 있으면 헤더보다 항상 우선
+//! • 버전 없는 `/users/{id}`는 헤더(`X-Api-Version`, `Accept`)로 고르고, 없으면 기본 버전
+//!   → 헤더 순서는 `VersionConfig::precedence`를 따름
+//! • 선택된 버전은 요청/응답 extension에 남김
+//!   → 핸들러의 `Version` 추출기와 deprecation 미들웨어가 실제로 처리한 버전을 봄
+
+use crate::{
+    version_from_accept, version_from_header, Version, VersionConfig, VersionRejection,
+    VersionSource,
+};
+use axum::{
+    extract::{FromRef, Request, State},
+    http::{HeaderMap, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{any, MethodRouter},
+    Router,
+};
+use std::{collections::BTreeMap, sync::Arc};
+use tower::ServiceExt;
+
+pub struct VersionedRouter<S = ()> {
+    // 논리 경로 → (이 버전부터 → 핸들러)
+    routes: BTreeMap<String, BTreeMap<Version, MethodRouter<S>>>,
+    default: Option<Version>,
+}
+
+impl<S> VersionedRouter<S>
+where
+    VersionConfig: FromRef<S>,
+    S: Clone + Send + Sync + 'static,
+{
+    pub fn new() -> Self {
+        Self {
+            routes: BTreeMap::new(),
+            default: None,
+        }
+    }
+
+    /// `since` 버전부터 `path`를 `handler`로 처리
+    pub fn route(mut self, path: &str, since: Version, handler: MethodRouter<S>) -> Self {
+        let previous = self
+            .routes
+            .entry(path.to_owned())
+            .or_default()
+            .insert(since, handler);
+        assert!(
+            previous.is_none(),
+            "{path} is already registered for {}",
+            since.prefix()
+        );
+        self
+    }
+
+    /// 버전 없는 경로에 헤더도 없을 때 사용할 버전 (없으면 `VersionConfig::default`)
+    pub fn default_version(mut self, version: Version) -> Self {
+        self.default = Some(version);
+        self
+    }
+
+    pub fn build(self) -> Router<S> {
+        let mut router = Router::new();
+
+        for (path, variants) in self.routes {
+            // 버전마다 그 버전 이하에서 가장 새 변형
+            let mut table = BTreeMap::new();
+            for version in Version::ALL {
+                let Some((_, handler)) = variants.range(..=version).next_back() else {
+                    // 처음 등록된 버전보다 이전 버전 → /vN/... 경로도 없음 (404)
+                    continue;
+                };
+                let handler = pin(version, handler.clone());
+                router = router.route(&format!("/{}{path}", version.prefix()), handler.clone());
+                table.insert(version, handler);
+            }
+
+            let table = Arc::new(table);
+            let default = self.default;
+            router = router.route(
+                &path,
+                any(move |State(state): State<S>, req: Request| {
+                    dispatch(table.clone(), default, state, req)
+                }),
+            );
+        }
+
+        router
+    }
+}
+
+impl<S> Default for VersionedRouter<S>
+where
+    VersionConfig: FromRef<S>,
+    S: Clone + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 처리한 버전을 요청(핸들러용)과 응답(바깥 미들웨어용) extension에 남김
+fn pin<S>(version: Version, handler: MethodRouter<S>) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    handler.layer(middleware::from_fn(
+        move |mut req: Request, next: Next| async move {
+            req.extensions_mut().insert(version);
+            let mut res = next.run(req).await;
+            res.extensions_mut().insert(version);
+            res
+        },
+    ))
+}
+
+// 버전 없는 경로: 헤더 → 기본 버전 순서로 고른 뒤 그 버전의 핸들러를 호출
+async fn dispatch<S>(
+    table: Arc<BTreeMap<Version, MethodRouter<S>>>,
+    default: Option<Version>,
+    state: S,
+    req: Request,
+) -> Response
+where
+    VersionConfig: FromRef<S>,
+    S: Clone + Send + Sync + 'static,
+{
+    let config = VersionConfig::from_ref(&state);
+    let version = match version_from_headers(&config, req.headers()) {
+        Ok(Some(version)) => version,
+        Ok(None) => match default.or(config.default) {
+            Some(version) => version,
+            None => return (StatusCode::BAD_REQUEST, "api version missing").into_response(),
+        },
+        Err(rejection) => return rejection.into_response(),
+    };
+
+    let Some(handler) = table.get(&version) else {
+        return (StatusCode::NOT_FOUND, "not available in this api version").into_response();
+    };
+    match handler.clone().with_state(state).oneshot(req).await {
+        Ok(res) => res,
+        Err(never) => match never {},
+    }
+}
+
+/// 헤더에서만 버전을 찾음 (`precedence`의 `Path`는 건너뜀)
+fn version_from_headers(
+    config: &VersionConfig,
+    headers: &HeaderMap,
+) -> Result<Option<Version>, VersionRejection> {
+    for source in &config.precedence {
+        let version = match source {
+            VersionSource::Path => continue,
+            VersionSource::Header => version_from_header(headers),
+            VersionSource::Accept => version_from_accept(headers),
+        }?;
+        if version.is_some() {
+            return Ok(version);
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, extract::Path, routing::get};
+    use http_body_util::BodyExt;
+
+    async fn send(router: Router, uri: &str, headers: &[(&str, &str)]) -> (StatusCode, String) {
+        let mut request = Request::builder().uri(uri);
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        let response = router
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    // 핸들러가 받은 버전도 함께 응답
+    async fn items_v1(version: Version, Path(id): Path<u32>) -> String {
+        format!("items_v1 {id} as {version:?}")
+    }
+
+    async fn items_v2(version: Version, Path(id): Path<u32>) -> String {
+        format!("items_v2 {id} as {version:?}")
+    }
+
+    fn router(config: VersionConfig) -> Router {
+        VersionedRouter::new()
+            .route("/items/{id}", Version::V1, get(items_v1))
+            .route("/items/{id}", Version::V2, get(items_v2))
+            .route("/reports", Version::V3, get(|| async { "reports" }))
+            .build()
+            .with_state(config)
+    }
+
+    #[tokio::test]
+    async fn each_version_is_mounted_and_unchanged_handlers_are_shared() {
+        let app = router(VersionConfig::default());
+
+        let (status, body) = send(app.clone(), "/v1/items/7", &[]).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "items_v1 7 as V1");
+
+        assert_eq!(
+            send(app.clone(), "/v2/items/7", &[]).await.1,
+            "items_v2 7 as V2"
+        );
+        // V3에는 새 변형이 없으므로 V2 핸들러를 그대로 사용
+        assert_eq!(
+            send(app.clone(), "/v3/items/7", &[]).await.1,
+            "items_v2 7 as V3"
+        );
+
+        // V3부터 생긴 API는 이전 버전 경로가 없음
+        assert_eq!(
+            send(app.clone(), "/v2/reports", &[]).await.0,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            send(app, "/reports", &[("x-api-version", "1")]).await,
+            (
+                StatusCode::NOT_FOUND,
+                "not available in this api version".to_owned()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn path_beats_headers_and_headers_follow_the_configured_order() {
+        let app = router(VersionConfig::default());
+        let headers = [
+            ("x-api-version", "1"),
+            ("accept", "application/vnd.example.v2+json"),
+        ];
+
+        // 경로 > 헤더
+        assert_eq!(
+            send(app.clone(), "/v3/items/1", &headers).await.1,
+            "items_v2 1 as V3"
+        );
+        // 기본 설정: X-Api-Version > Accept
+        assert_eq!(
+            send(app.clone(), "/items/1", &headers).await.1,
+            "items_v1 1 as V1"
+        );
+
+        let accept_first = router(VersionConfig {
+            precedence: vec![VersionSource::Accept, VersionSource::Header],
+            default: None,
+        });
+        assert_eq!(
+            send(accept_first, "/items/1", &headers).await.1,
+            "items_v2 1 as V2"
+        );
+
+        // 잘못된 헤더는 기본 버전으로 넘어가지 않고 에러
+        assert_eq!(
+            send(app, "/items/1", &[("x-api-version", "9")]).await.0,
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[tokio::test]
+    async fn unversioned_requests_fall_back_to_the_default_version() {
+        // 기본 버전이 없으면 400
+        let (status, body) = send(router(VersionConfig::default()), "/items/1", &[]).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body, "api version missing");
+
+        // 라우터의 기본 버전이 VersionConfig::default보다 우선
+        let app = VersionedRouter::new()
+            .route("/items/{id}", Version::V1, get(items_v1))
+            .default_version(Version::V3)
+            .build()
+            .with_state(VersionConfig {
+                default: Some(Version::V1),
+                ..VersionConfig::default()
+            });
+        assert_eq!(send(app, "/items/1", &[]).await.1, "items_v1 1 as V3");
+    }
+}
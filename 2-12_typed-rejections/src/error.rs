@@ -0,0 +1,67 @@
+//! 모든 추출기가 공유하는 에러 응답 형식
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+
+/// 추출 실패 시 반환되는 리젝션 (상태 코드 + JSON body)
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub body: ErrorBody,
+}
+
+/// `{code, message, field}` 형태의 JSON 에러 본문
+#[derive(Debug, Serialize)]
+pub struct ErrorBody {
+    pub code: &'static str,
+    pub message: String,
+    // 값이 없어도 항상 같은 모양이 되도록 null로 직렬화
+    pub field: Option<String>,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: ErrorBody {
+                code,
+                message: message.into(),
+                field: None,
+            },
+        }
+    }
+
+    pub fn with_field(mut self, field: impl Into<String>) -> Self {
+        self.body.field = Some(field.into());
+        self
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, axum::Json(self.body)).into_response()
+    }
+}
+
+/// serde_path_to_error 경로를 `field` 값으로 변환
+///
+/// 필드가 아예 없는 경우(missing field)에는 경로가 상위 구조체(".")를 가리키므로,
+/// serde 표준 메시지 "missing field `name`"에서 필드 이름을 꺼내 경로에 붙임
+pub(crate) fn field_from_path(path: &serde_path_to_error::Path, message: &str) -> Option<String> {
+    let path = path.to_string();
+    let parent = (path != ".").then_some(path);
+
+    match (parent, missing_field(message)) {
+        (Some(parent), Some(field)) => Some(format!("{parent}.{field}")),
+        (None, Some(field)) => Some(field.to_owned()),
+        (parent, None) => parent,
+    }
+}
+
+fn missing_field(message: &str) -> Option<&str> {
+    let rest = message.strip_prefix("missing field `")?;
+    rest.split('`').next()
+}
@@ -0,0 +1,262 @@
+//! JSON body 추출기
+//!
+//! Content-Type 검사와 body 읽기는 axum과 같게 처리하고,
+//! 역직렬화는 serde_path_to_error로 감싸 실패한 필드의 경로(예: `address.city`, `items[0]`)를 얻음
+
+use axum::{
+    body::Bytes,
+    extract::{rejection::BytesRejection, FromRequest, Request},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::error::Category;
+
+use crate::{error::field_from_path, ApiError};
+
+/// 실패 시 [`ApiError`]를 반환하는 `Json`
+///
+/// 응답 타입으로도 사용할 수 있음 (`axum::Json`과 동일하게 직렬화)
+#[derive(Debug)]
+pub struct Json<T>(pub T);
+
+impl<S, T> FromRequest<S> for Json<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !json_content_type(req.headers()) {
+            return Err(ApiError::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "json_missing_content_type",
+                "Expected request with `Content-Type: application/json`",
+            ));
+        }
+
+        let bytes = Bytes::from_request(req, state).await.map_err(body_error)?;
+
+        let mut deserializer = serde_json::Deserializer::from_slice(&bytes);
+        let value = serde_path_to_error::deserialize(&mut deserializer).map_err(json_error)?;
+        // 값 뒤에 다른 문자가 남아 있으면 문법 오류
+        deserializer.end().map_err(|err| {
+            ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "json_syntax_error",
+                err.to_string(),
+            )
+        })?;
+
+        Ok(Self(value))
+    }
+}
+
+impl<T> IntoResponse for Json<T>
+where
+    T: Serialize,
+{
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
+
+// application/json 또는 application/*+json (예: application/problem+json)
+fn json_content_type(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    essence == "application/json"
+        || (essence.starts_with("application/") && essence.ends_with("+json"))
+}
+
+fn body_error(rejection: BytesRejection) -> ApiError {
+    let status = rejection.status();
+    let code = if status == StatusCode::PAYLOAD_TOO_LARGE {
+        "payload_too_large"
+    } else {
+        "body_read_failed"
+    };
+    ApiError::new(status, code, rejection.body_text())
+}
+
+fn json_error(err: serde_path_to_error::Error<serde_json::Error>) -> ApiError {
+    let message = err.inner().to_string();
+
+    match err.inner().classify() {
+        // 문법은 맞지만 타입이 맞지 않음 (필드 누락, 잘못된 타입 등) → 422
+        Category::Data => {
+            let error = ApiError::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "json_invalid_data",
+                message,
+            );
+            match field_from_path(err.path(), &err.inner().to_string()) {
+                Some(field) => error.with_field(field),
+                None => error,
+            }
+        }
+        // 잘못된 JSON 문법 또는 중간에 끝난 body → 400
+        Category::Syntax | Category::Eof | Category::Io => {
+            ApiError::new(StatusCode::BAD_REQUEST, "json_syntax_error", message)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{send, ErrorJson};
+    use axum::{extract::DefaultBodyLimit, routing::post, Router};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct CreateUser {
+        name: String,
+        age: u8,
+        address: Address,
+        tags: Vec<String>,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Address {
+        city: String,
+    }
+
+    fn app() -> Router {
+        Router::new()
+            .route("/users", post(|_: Json<CreateUser>| async {}))
+            .layer(DefaultBodyLimit::max(256))
+    }
+
+    const VALID: &str = r#"{"name":"kim","age":30,"address":{"city":"Seoul"},"tags":["a"]}"#;
+
+    #[tokio::test]
+    async fn valid_body_passes_through() {
+        let (status, _) = send(app(), "POST", "/users", Some(VALID)).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn vendor_json_content_type_is_accepted() {
+        let request = axum::http::Request::post("/users")
+            .header(
+                "content-type",
+                "application/vnd.example+json; charset=utf-8",
+            )
+            .body(axum::body::Body::from(VALID))
+            .unwrap();
+        let response = tower::ServiceExt::oneshot(app(), request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn missing_content_type() {
+        let request = axum::http::Request::post("/users")
+            .header("content-type", "text/plain")
+            .body(axum::body::Body::from(VALID))
+            .unwrap();
+        let response = tower::ServiceExt::oneshot(app(), request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        let body = crate::tests::error_json(response).await;
+        assert_eq!(body.code, "json_missing_content_type");
+        assert_eq!(body.field, None);
+    }
+
+    #[tokio::test]
+    async fn syntax_error() {
+        let (status, body) = send(app(), "POST", "/users", Some(r#"{"name": }"#)).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.code, "json_syntax_error");
+        assert_eq!(body.field, None);
+    }
+
+    #[tokio::test]
+    async fn truncated_body_is_syntax_error() {
+        let (status, body) = send(app(), "POST", "/users", Some(r#"{"name":"kim""#)).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.code, "json_syntax_error");
+    }
+
+    #[tokio::test]
+    async fn trailing_characters_are_syntax_error() {
+        let body = format!("{VALID} x");
+        let (status, body) = send(app(), "POST", "/users", Some(&body)).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.code, "json_syntax_error");
+    }
+
+    #[tokio::test]
+    async fn wrong_type_reports_field() {
+        let body = r#"{"name":"kim","age":"thirty","address":{"city":"Seoul"},"tags":[]}"#;
+        let (status, body) = send(app(), "POST", "/users", Some(body)).await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body.code, "json_invalid_data");
+        assert_eq!(body.field.as_deref(), Some("age"));
+    }
+
+    #[tokio::test]
+    async fn out_of_range_reports_field() {
+        let body = r#"{"name":"kim","age":300,"address":{"city":"Seoul"},"tags":[]}"#;
+        let (status, body) = send(app(), "POST", "/users", Some(body)).await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body.field.as_deref(), Some("age"));
+    }
+
+    #[tokio::test]
+    async fn nested_and_array_fields_report_full_path() {
+        let body = r#"{"name":"kim","age":30,"address":{"city":1},"tags":[]}"#;
+        let (_, body) = send(app(), "POST", "/users", Some(body)).await;
+        assert_eq!(body.field.as_deref(), Some("address.city"));
+
+        let body = r#"{"name":"kim","age":30,"address":{"city":"Seoul"},"tags":["a",2]}"#;
+        let (_, body) = send(app(), "POST", "/users", Some(body)).await;
+        assert_eq!(body.field.as_deref(), Some("tags[1]"));
+    }
+
+    #[tokio::test]
+    async fn missing_field_reports_field() {
+        let body = r#"{"name":"kim","age":30,"address":{},"tags":[]}"#;
+        let (status, body) = send(app(), "POST", "/users", Some(body)).await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            body,
+            ErrorJson {
+                code: "json_invalid_data".to_owned(),
+                message: "missing field `city` at line 1 column 35".to_owned(),
+                field: Some("address.city".to_owned()),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn payload_too_large() {
+        let body = format!(r#"{{"name":"{}"}}"#, "a".repeat(1024));
+        let (status, body) = send(app(), "POST", "/users", Some(&body)).await;
+
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(body.code, "payload_too_large");
+    }
+}
@@ -0,0 +1,87 @@
+//! 2-04_customize-path-rejection의 패턴을 Path, Query, Json 전체로 일반화한 추출기 모음입니다.
+//!
+//! 세 추출기 모두 실패 시 같은 모양의 JSON 에러를 반환하므로,
+//! 클라이언트는 어떤 추출기에서 실패했는지와 상관없이 한 가지 방식으로 에러를 처리할 수 있습니다.
+//!
+//! ```json
+//! { "code": "query_invalid_param", "message": "invalid digit found in string", "field": "page" }
+//! ```
+//!
+//! - `code` : 에러 종류를 나타내는 고정 문자열 (클라이언트 분기용)
+//! - `message` : 사람이 읽을 수 있는 설명
+//! - `field` : 문제가 된 경로 변수/쿼리 파라미터/JSON 필드 (알 수 없으면 null)
+//!
+//! 사용법은 axum 기본 추출기와 같습니다:
+//!
+//! ```rust,ignore
+//! use example_typed_rejections::{Json, Path, Query};
+//!
+//! async fn handler(Path(id): Path<u32>, Query(page): Query<Page>, Json(body): Json<Body>) {}
+//! ```
+
+mod error;
+mod json;
+mod path;
+mod query;
+
+pub use error::{ApiError, ErrorBody};
+pub use json::Json;
+pub use path::Path;
+pub use query::Query;
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        response::Response,
+        Router,
+    };
+    use http_body_util::BodyExt;
+    use serde::Deserialize;
+    use tower::ServiceExt;
+
+    // 테스트에서 응답 body를 비교하기 위한 타입
+    #[derive(Debug, PartialEq, Deserialize)]
+    pub struct ErrorJson {
+        pub code: String,
+        pub message: String,
+        pub field: Option<String>,
+    }
+
+    /// 요청을 보내고 상태 코드와 에러 body를 반환 (성공 응답이면 빈 ErrorJson)
+    pub async fn send(
+        app: Router,
+        method: &str,
+        uri: &str,
+        json: Option<&str>,
+    ) -> (StatusCode, ErrorJson) {
+        let mut request = Request::builder().method(method).uri(uri);
+        if json.is_some() {
+            request = request.header("content-type", "application/json");
+        }
+        let body = json.map(|json| Body::from(json.to_owned()));
+        let response = app
+            .oneshot(request.body(body.unwrap_or_default()).unwrap())
+            .await
+            .unwrap();
+
+        let status = response.status();
+        if status.is_success() {
+            return (
+                status,
+                ErrorJson {
+                    code: String::new(),
+                    message: String::new(),
+                    field: None,
+                },
+            );
+        }
+        (status, error_json(response).await)
+    }
+
+    pub async fn error_json(response: Response) -> ErrorJson {
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+}
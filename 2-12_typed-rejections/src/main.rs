@@ -0,0 +1,77 @@
+//! `example_typed_rejections`의 Path, Query, Json 추출기를 사용하는 예제 서버입니다.
+//!
+//! 어떤 추출기에서 실패하든 `{code, message, field}` 형태의 JSON 에러가 반환됩니다.
+
+use axum::{routing::get, Router};
+use example_typed_rejections::{Json, Path, Query};
+use serde::{Deserialize, Serialize};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[tokio::main]
+async fn main() {
+    // ✨ tracing 설정: 환경 변수 기반 필터와 포맷터를 등록하여 로깅 초기화
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    // ✨ 라우터 구성
+    let app = Router::new().route("/users/{user_id}/posts", get(list_posts).post(create_post));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app).await.unwrap();
+}
+
+#[derive(Debug, Deserialize)]
+struct Pagination {
+    page: u32,
+    per_page: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct NewPost {
+    title: String,
+    tags: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct PostList {
+    user_id: u32,
+    page: u32,
+    per_page: u32,
+}
+
+// GET /users/{user_id}/posts?page=1&per_page=20
+async fn list_posts(
+    Path(user_id): Path<u32>,
+    Query(pagination): Query<Pagination>,
+) -> Json<PostList> {
+    Json(PostList {
+        user_id,
+        page: pagination.page,
+        per_page: pagination.per_page.unwrap_or(20),
+    })
+}
+
+// POST /users/{user_id}/posts
+async fn create_post(Path(_user_id): Path<u32>, Json(post): Json<NewPost>) -> Json<NewPost> {
+    Json(post)
+}
+
+// ✅ 요청 예시
+// curl 'localhost:3000/users/foo/posts?page=1'
+// → 400 {"code":"path_invalid_param","message":"Cannot parse `foo` to a `u32`","field":null}
+// curl 'localhost:3000/users/1/posts'
+// → 400 {"code":"query_missing_param","message":"missing field `page`","field":"page"}
+// curl 'localhost:3000/users/1/posts?page=x'
+// → 400 {"code":"query_invalid_param","message":"invalid digit found in string","field":"page"}
+// curl -X POST localhost:3000/users/1/posts -H 'content-type: application/json' -d '{"title":"hi","tags":[1]}'
+// → 422 {"code":"json_invalid_data","message":"invalid type: integer `1`, expected a string at line 1 column 23","field":"tags[0]"}
+// curl -X POST localhost:3000/users/1/posts -d '{}'
+// → 415 {"code":"json_missing_content_type", ...}
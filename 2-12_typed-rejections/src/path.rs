@@ -0,0 +1,201 @@
+//! 경로 변수 추출기 (`axum::extract::Path` 래퍼)
+
+use axum::{
+    extract::{path::ErrorKind, rejection::PathRejection, FromRequestParts},
+    http::{request::Parts, StatusCode},
+};
+use serde::de::DeserializeOwned;
+
+use crate::ApiError;
+
+/// 실패 시 [`ApiError`]를 반환하는 `Path`
+#[derive(Debug)]
+pub struct Path<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for Path<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Path::<T>::from_request_parts(parts, state).await {
+            Ok(axum::extract::Path(value)) => Ok(Self(value)),
+            Err(rejection) => Err(path_error(rejection)),
+        }
+    }
+}
+
+fn path_error(rejection: PathRejection) -> ApiError {
+    let kind = match rejection {
+        PathRejection::FailedToDeserializePathParams(inner) => inner.into_kind(),
+        // 경로 변수가 없는 라우트에서 Path를 사용한 경우 → 프로그래머 실수
+        PathRejection::MissingPathParams(error) => {
+            return ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "path_missing_params",
+                error.to_string(),
+            );
+        }
+        _ => {
+            return ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "path_rejected",
+                format!("Unhandled path rejection: {rejection}"),
+            );
+        }
+    };
+
+    let message = kind.to_string();
+    match kind {
+        ErrorKind::WrongNumberOfParameters { .. } => {
+            ApiError::new(StatusCode::BAD_REQUEST, "path_param_count", message)
+        }
+        ErrorKind::ParseErrorAtKey { key, .. } | ErrorKind::DeserializeError { key, .. } => {
+            ApiError::new(StatusCode::BAD_REQUEST, "path_invalid_param", message).with_field(key)
+        }
+        ErrorKind::ParseErrorAtIndex { index, .. } => {
+            ApiError::new(StatusCode::BAD_REQUEST, "path_invalid_param", message)
+                .with_field(index.to_string())
+        }
+        ErrorKind::ParseError { .. } => {
+            ApiError::new(StatusCode::BAD_REQUEST, "path_invalid_param", message)
+        }
+        ErrorKind::InvalidUtf8InPathParam { key } => {
+            ApiError::new(StatusCode::BAD_REQUEST, "path_invalid_utf8", message).with_field(key)
+        }
+        // 중첩 맵 등 경로 변수로 표현할 수 없는 타입 → 프로그래머 실수
+        ErrorKind::UnsupportedType { .. } => ApiError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "path_unsupported_type",
+            message,
+        ),
+        _ => ApiError::new(StatusCode::BAD_REQUEST, "path_invalid", message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{send, ErrorJson};
+    use axum::{routing::get, Router};
+    use serde::Deserialize;
+    use std::collections::HashMap;
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Params {
+        user_id: u32,
+        team_id: u32,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    enum Color {
+        Red,
+    }
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/struct/{user_id}/{team_id}",
+                get(|_: Path<Params>| async {}),
+            )
+            .route("/tuple/{a}/{b}", get(|_: Path<(u32, u32)>| async {}))
+            .route("/single/{id}", get(|_: Path<u32>| async {}))
+            .route("/string/{name}", get(|_: Path<String>| async {}))
+            .route("/count/{id}", get(|_: Path<(u32, u32)>| async {}))
+            .route("/enum/{color}", get(|_: Path<Color>| async {}))
+            .route(
+                "/nested/{key}",
+                get(|_: Path<HashMap<String, Vec<u32>>>| async {}),
+            )
+    }
+
+    #[tokio::test]
+    async fn valid_params_pass_through() {
+        let (status, _) = send(app(), "GET", "/struct/1/2", None).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn parse_error_at_key_reports_key() {
+        let (status, body) = send(app(), "GET", "/struct/foo/2", None).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.code, "path_invalid_param");
+        assert_eq!(body.field.as_deref(), Some("user_id"));
+    }
+
+    #[tokio::test]
+    async fn parse_error_at_index_reports_index() {
+        let (status, body) = send(app(), "GET", "/tuple/1/foo", None).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.code, "path_invalid_param");
+        assert_eq!(body.field.as_deref(), Some("1"));
+    }
+
+    #[tokio::test]
+    async fn parse_error_without_location() {
+        let (status, body) = send(app(), "GET", "/single/foo", None).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body,
+            ErrorJson {
+                code: "path_invalid_param".to_owned(),
+                message: "Cannot parse `foo` to a `u32`".to_owned(),
+                field: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn wrong_number_of_parameters() {
+        let (status, body) = send(app(), "GET", "/count/1", None).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.code, "path_param_count");
+        assert_eq!(body.field, None);
+    }
+
+    #[tokio::test]
+    async fn invalid_utf8_reports_key() {
+        let (status, body) = send(app(), "GET", "/string/%FF", None).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.code, "path_invalid_utf8");
+        assert_eq!(body.field.as_deref(), Some("name"));
+    }
+
+    #[tokio::test]
+    async fn custom_deserialize_error() {
+        let (status, body) = send(app(), "GET", "/enum/blue", None).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.code.starts_with("path_invalid"), "{body:?}");
+    }
+
+    #[tokio::test]
+    async fn unsupported_type_is_server_error() {
+        let (status, body) = send(app(), "GET", "/nested/1", None).await;
+
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body.code, "path_unsupported_type");
+    }
+
+    // 라우터를 거치지 않은 요청에는 경로 변수 정보가 없음
+    #[tokio::test]
+    async fn missing_params_is_server_error() {
+        let (mut parts, ()) = axum::http::Request::new(()).into_parts();
+
+        let error = Path::<u32>::from_request_parts(&mut parts, &())
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(error.body.code, "path_missing_params");
+    }
+}
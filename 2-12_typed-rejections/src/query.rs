@@ -0,0 +1,141 @@
+//! 쿼리 문자열 추출기
+//!
+//! `axum::extract::Query`의 리젝션에는 실패한 필드 정보가 남지 않으므로,
+//! 쿼리 문자열을 직접 serde_urlencoded + serde_path_to_error로 역직렬화해 필드 경로를 얻음
+
+use axum::{
+    extract::FromRequestParts,
+    http::{request::Parts, StatusCode},
+};
+use serde::de::DeserializeOwned;
+
+use crate::{error::field_from_path, ApiError};
+
+/// 실패 시 [`ApiError`]를 반환하는 `Query`
+#[derive(Debug)]
+pub struct Query<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let query = parts.uri.query().unwrap_or_default();
+        let deserializer =
+            serde_urlencoded::Deserializer::new(form_urlencoded::parse(query.as_bytes()));
+
+        serde_path_to_error::deserialize(deserializer)
+            .map(Self)
+            .map_err(query_error)
+    }
+}
+
+fn query_error(err: serde_path_to_error::Error<serde_urlencoded::de::Error>) -> ApiError {
+    let message = err.inner().to_string();
+    let field = field_from_path(err.path(), &message);
+
+    // 필드가 빠졌는지, 값의 형식이 틀렸는지를 code로 구분
+    let code = if message.starts_with("missing field") {
+        "query_missing_param"
+    } else {
+        "query_invalid_param"
+    };
+
+    let error = ApiError::new(StatusCode::BAD_REQUEST, code, message);
+    match field {
+        Some(field) => error.with_field(field),
+        None => error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{send, ErrorJson};
+    use axum::{routing::get, Router};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Pagination {
+        page: u32,
+        per_page: Option<u32>,
+        sort: Option<Sort>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Sort {
+        Asc,
+        Desc,
+    }
+
+    fn app() -> Router {
+        Router::new().route(
+            "/items",
+            get(|Query(pagination): Query<Pagination>| async move {
+                match pagination.sort {
+                    Some(Sort::Asc) | None => "asc",
+                    Some(Sort::Desc) => "desc",
+                }
+            }),
+        )
+    }
+
+    #[tokio::test]
+    async fn valid_query_passes_through() {
+        let (status, _) = send(app(), "GET", "/items?page=1&per_page=10&sort=desc", None).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn invalid_value_reports_field() {
+        let (status, body) = send(app(), "GET", "/items?page=abc", None).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body,
+            ErrorJson {
+                code: "query_invalid_param".to_owned(),
+                message: "invalid digit found in string".to_owned(),
+                field: Some("page".to_owned()),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_optional_value_reports_field() {
+        let (status, body) = send(app(), "GET", "/items?page=1&per_page=-1", None).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.code, "query_invalid_param");
+        assert_eq!(body.field.as_deref(), Some("per_page"));
+    }
+
+    #[tokio::test]
+    async fn unknown_variant_reports_field() {
+        let (status, body) = send(app(), "GET", "/items?page=1&sort=random", None).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.code, "query_invalid_param");
+        assert_eq!(body.field.as_deref(), Some("sort"));
+    }
+
+    #[tokio::test]
+    async fn missing_field_reports_field() {
+        let (status, body) = send(app(), "GET", "/items", None).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body,
+            ErrorJson {
+                code: "query_missing_param".to_owned(),
+                message: "missing field `page`".to_owned(),
+                field: Some("page".to_owned()),
+            }
+        );
+    }
+}
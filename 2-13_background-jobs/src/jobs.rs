@@ -0,0 +1,154 @@
+//! 작업(Job) 타입과 메모리 저장소, 큐
+//!
+//! - 핸들러는 [`JobQueue::enqueue`]로 작업을 저장소에 기록하고 ID만 채널로 보냄
+//! - 워커는 채널에서 ID를 받아 저장소의 상태를 갱신하며 처리함
+//! - 모든 `JobQueue`(= Sender)가 drop되면 채널이 닫히고, 워커는 남은 작업을 마저 처리한 뒤 종료
+
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+use tokio::sync::mpsc::{self, error::TrySendError};
+use uuid::Uuid;
+
+/// 📌 작업 내용 (예제용 가짜 작업)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JobPayload {
+    /// 작업 이름 (로그용)
+    pub task: String,
+    /// 한 번 시도할 때 걸리는 시간
+    #[serde(default)]
+    pub duration_ms: u64,
+    /// 처음 N번의 시도는 실패하도록 흉내냄 (재시도 확인용)
+    #[serde(default)]
+    pub fail_attempts: u32,
+}
+
+/// 📌 작업 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    /// 실패 후 백오프 대기 중
+    Retrying,
+    Succeeded,
+    /// 재시도 횟수를 모두 소진 → dead-letter 목록으로 이동
+    Dead,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Job {
+    pub id: Uuid,
+    pub payload: JobPayload,
+    pub status: JobStatus,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+}
+
+/// 🗃️ 작업 저장소
+/// 상태 조회(`GET /jobs/{id}`)와 워커가 함께 사용
+#[derive(Debug, Clone, Default)]
+pub struct JobStore {
+    inner: Arc<RwLock<StoreInner>>,
+}
+
+#[derive(Debug, Default)]
+struct StoreInner {
+    jobs: HashMap<Uuid, Job>,
+    dead_letters: Vec<Uuid>,
+}
+
+impl JobStore {
+    pub fn get(&self, id: Uuid) -> Option<Job> {
+        self.inner.read().unwrap().jobs.get(&id).cloned()
+    }
+
+    /// dead-letter 목록 (들어온 순서대로)
+    pub fn dead_letters(&self) -> Vec<Job> {
+        let inner = self.inner.read().unwrap();
+        inner
+            .dead_letters
+            .iter()
+            .filter_map(|id| inner.jobs.get(id).cloned())
+            .collect()
+    }
+
+    pub(crate) fn update(&self, id: Uuid, f: impl FnOnce(&mut Job)) {
+        if let Some(job) = self.inner.write().unwrap().jobs.get_mut(&id) {
+            f(job);
+        }
+    }
+
+    pub(crate) fn mark_dead(&self, id: Uuid, error: String) {
+        let mut inner = self.inner.write().unwrap();
+        if let Some(job) = inner.jobs.get_mut(&id) {
+            job.status = JobStatus::Dead;
+            job.last_error = Some(error);
+            inner.dead_letters.push(id);
+        }
+    }
+
+    fn insert(&self, job: Job) {
+        self.inner.write().unwrap().jobs.insert(job.id, job);
+    }
+
+    fn remove(&self, id: Uuid) {
+        self.inner.write().unwrap().jobs.remove(&id);
+    }
+}
+
+/// 큐가 가득 찼거나 워커가 종료되어 작업을 받을 수 없음
+#[derive(Debug, PartialEq, Eq)]
+pub enum EnqueueError {
+    Full,
+    Closed,
+}
+
+/// 📮 작업 큐 (핸들러 쪽)
+#[derive(Debug, Clone)]
+pub struct JobQueue {
+    sender: mpsc::Sender<Uuid>,
+    store: JobStore,
+}
+
+/// 큐와 워커가 사용할 Receiver를 함께 생성
+/// `capacity`를 넘는 작업은 기다리지 않고 [`EnqueueError::Full`]로 거절 (backpressure)
+pub fn queue(capacity: usize) -> (JobQueue, mpsc::Receiver<Uuid>) {
+    let (sender, receiver) = mpsc::channel(capacity);
+    let queue = JobQueue {
+        sender,
+        store: JobStore::default(),
+    };
+    (queue, receiver)
+}
+
+impl JobQueue {
+    pub fn store(&self) -> &JobStore {
+        &self.store
+    }
+
+    pub fn enqueue(&self, payload: JobPayload) -> Result<Job, EnqueueError> {
+        let job = Job {
+            id: Uuid::new_v4(),
+            payload,
+            status: JobStatus::Queued,
+            attempts: 0,
+            last_error: None,
+        };
+
+        // 워커가 ID를 받기 전에 상태가 존재하도록 먼저 저장
+        self.store.insert(job.clone());
+
+        self.sender.try_send(job.id).map_err(|err| {
+            self.store.remove(job.id);
+            match err {
+                TrySendError::Full(_) => EnqueueError::Full,
+                TrySendError::Closed(_) => EnqueueError::Closed,
+            }
+        })?;
+
+        Ok(job)
+    }
+}
@@ -0,0 +1,240 @@
+//! 백그라운드 작업 큐 예제
+//!
+//! - `POST /jobs`: 작업을 큐에 넣고 바로 202 Accepted + 작업 ID 반환
+//! - `GET /jobs/{id}`: 작업 상태 조회 (queued → running → retrying → succeeded / dead)
+//! - `GET /dead-letters`: 재시도를 모두 실패한 작업 목록
+//!
+//! 작업은 `tokio::sync::mpsc` 채널로 워커 풀에 전달되고,
+//! 실패하면 지수 백오프로 재시도한 뒤 dead-letter 목록으로 이동합니다.
+//!
+//! 종료 순서 (Ctrl+C / SIGTERM)
+//! 1. 서버가 새 요청을 받지 않고, 진행 중인 요청을 마무리
+//! 2. 라우터가 drop되면서 큐의 Sender가 모두 사라짐 → 채널이 닫힘
+//! 3. 워커는 처리 중인 작업과 채널에 남은 작업을 모두 끝낸 뒤 종료 (최대 30초)
+
+mod jobs;
+mod worker;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use jobs::{EnqueueError, Job, JobPayload, JobQueue};
+use serde::Serialize;
+use std::time::Duration;
+use tokio::signal;
+use tower_http::trace::TraceLayer;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use uuid::Uuid;
+use worker::WorkerConfig;
+
+// 채널에 쌓아둘 수 있는 최대 작업 수
+const QUEUE_CAPACITY: usize = 100;
+// 종료 시 워커를 기다리는 최대 시간
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 🚀 메인 함수
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+                format!("{}=debug,tower_http=debug", env!("CARGO_CRATE_NAME")).into()
+            }),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let (queue, receiver) = jobs::queue(QUEUE_CAPACITY);
+    let workers = worker::spawn(receiver, queue.store().clone(), WorkerConfig::default());
+
+    // queue(Sender)는 라우터로 move → 서버가 끝나면 함께 drop됨
+    let app = app(queue).layer(TraceLayer::new_for_http());
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await
+        .unwrap();
+
+    tracing::info!("server stopped, draining background jobs");
+    if workers.shutdown(DRAIN_TIMEOUT).await {
+        tracing::info!("all background jobs finished");
+    }
+}
+
+fn app(queue: JobQueue) -> Router {
+    Router::new()
+        .route("/jobs", post(create_job))
+        .route("/jobs/{id}", get(get_job))
+        .route("/dead-letters", get(dead_letters))
+        .with_state(queue)
+}
+
+#[derive(Serialize)]
+struct JobAccepted {
+    id: Uuid,
+}
+
+// 1️⃣ POST /jobs
+// 작업은 큐에 넣기만 하고 결과를 기다리지 않음
+async fn create_job(
+    State(queue): State<JobQueue>,
+    Json(payload): Json<JobPayload>,
+) -> Result<(StatusCode, Json<JobAccepted>), Response> {
+    match queue.enqueue(payload) {
+        Ok(job) => Ok((StatusCode::ACCEPTED, Json(JobAccepted { id: job.id }))),
+        Err(EnqueueError::Full) => {
+            Err((StatusCode::SERVICE_UNAVAILABLE, "job queue is full").into_response())
+        }
+        Err(EnqueueError::Closed) => {
+            Err((StatusCode::SERVICE_UNAVAILABLE, "server is shutting down").into_response())
+        }
+    }
+}
+
+// 2️⃣ GET /jobs/{id}
+async fn get_job(
+    State(queue): State<JobQueue>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Job>, StatusCode> {
+    queue.store().get(id).map(Json).ok_or(StatusCode::NOT_FOUND)
+}
+
+// 3️⃣ GET /dead-letters
+async fn dead_letters(State(queue): State<JobQueue>) -> Json<Vec<Job>> {
+    Json(queue.store().dead_letters())
+}
+
+// 🧠 종료 신호 처리 함수 (graceful-shutdown 예제와 동일)
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("failed to install signal handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    async fn send(app: Router, request: Request<Body>) -> (StatusCode, Value) {
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    fn post_job(body: Value) -> Request<Body> {
+        Request::post("/jobs")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn enqueue_and_poll_status() {
+        let (queue, receiver) = jobs::queue(10);
+        let workers = worker::spawn(receiver, queue.store().clone(), WorkerConfig::default());
+        let app = app(queue);
+
+        let (status, body) = send(app.clone(), post_job(json!({ "task": "email" }))).await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        let id = body["id"].as_str().unwrap().to_owned();
+
+        // 작업이 끝날 때까지 상태를 조회
+        let job = loop {
+            let request = Request::get(format!("/jobs/{id}"))
+                .body(Body::empty())
+                .unwrap();
+            let (status, job) = send(app.clone(), request).await;
+            assert_eq!(status, StatusCode::OK);
+            if job["status"] == "succeeded" {
+                break job;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        };
+        assert_eq!(job["attempts"], 1);
+        assert_eq!(job["payload"]["task"], "email");
+
+        drop(app);
+        assert!(workers.shutdown(Duration::from_secs(1)).await);
+    }
+
+    #[tokio::test]
+    async fn unknown_job_is_404() {
+        let (queue, _receiver) = jobs::queue(1);
+
+        let request = Request::get(format!("/jobs/{}", Uuid::nil()))
+            .body(Body::empty())
+            .unwrap();
+        let (status, _) = send(app(queue), request).await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn full_queue_returns_503() {
+        let (queue, _receiver) = jobs::queue(1);
+        let app = app(queue);
+
+        let (status, _) = send(app.clone(), post_job(json!({ "task": "a" }))).await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        let (status, _) = send(app, post_job(json!({ "task": "b" }))).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
+
+// 🧪 테스트 방법
+//
+// ✅ 작업 등록 (200ms 걸리는 작업)
+// curl -X POST http://localhost:3000/jobs -H 'Content-Type: application/json' \
+// -d '{"task": "send-email", "duration_ms": 200}'
+//
+// ✅ 재시도 후 성공 (처음 2번 실패)
+// curl -X POST http://localhost:3000/jobs -H 'Content-Type: application/json' \
+// -d '{"task": "flaky", "fail_attempts": 2}'
+//
+// ✅ 재시도를 모두 실패 → dead-letter
+// curl -X POST http://localhost:3000/jobs -H 'Content-Type: application/json' \
+// -d '{"task": "broken", "fail_attempts": 10}'
+// curl http://localhost:3000/dead-letters
+//
+// ✅ 상태 조회
+// curl http://localhost:3000/jobs/<id>
+//
+// ✅ 종료 시 작업 소진 확인
+// 10초짜리 작업을 몇 개 등록한 뒤 Ctrl+C → 작업이 모두 끝난 뒤 프로세스 종료
+// curl -X POST http://localhost:3000/jobs -H 'Content-Type: application/json' \
+// -d '{"task": "long", "duration_ms": 10000}'
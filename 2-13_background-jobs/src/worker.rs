@@ -0,0 +1,266 @@
+//! 워커 풀: 재시도(지수 백오프)와 dead-letter 처리, 종료 시 남은 작업 소진
+
+use std::{sync::Arc, time::Duration};
+use tokio::{
+    sync::{mpsc, Mutex},
+    task::JoinSet,
+    time::sleep,
+};
+use uuid::Uuid;
+
+use crate::jobs::{JobPayload, JobStatus, JobStore};
+
+/// ⚙️ 워커 설정
+#[derive(Debug, Clone)]
+pub struct WorkerConfig {
+    /// 동시에 작업을 처리하는 워커 수
+    pub workers: usize,
+    /// 최대 시도 횟수 (첫 시도 포함)
+    pub max_attempts: u32,
+    /// 첫 재시도 전 대기 시간, 이후 2배씩 증가
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        Self {
+            workers: 4,
+            max_attempts: 3,
+            base_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+}
+
+impl WorkerConfig {
+    /// attempt번째 시도가 실패한 뒤 기다릴 시간
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+pub struct WorkerPool {
+    tasks: JoinSet<()>,
+}
+
+/// 🏭 워커 실행
+/// 모든 워커가 하나의 Receiver를 나눠 씀 (먼저 lock을 잡은 워커가 다음 작업을 가져감)
+pub fn spawn(receiver: mpsc::Receiver<Uuid>, store: JobStore, config: WorkerConfig) -> WorkerPool {
+    let receiver = Arc::new(Mutex::new(receiver));
+    let config = Arc::new(config);
+    let mut tasks = JoinSet::new();
+
+    for worker in 0..config.workers {
+        tasks.spawn(run(worker, receiver.clone(), store.clone(), config.clone()));
+    }
+
+    WorkerPool { tasks }
+}
+
+impl WorkerPool {
+    /// 🛑 모든 워커가 끝날 때까지 대기
+    ///
+    /// 채널이 닫힌 뒤(모든 `JobQueue` drop) 호출해야 함.
+    /// 워커는 처리 중인 작업과 채널에 남은 작업을 모두 끝낸 뒤 종료하며,
+    /// `timeout`이 지나면 남은 워커를 중단하고 false를 반환
+    pub async fn shutdown(mut self, timeout: Duration) -> bool {
+        let drain = async { while self.tasks.join_next().await.is_some() {} };
+
+        if tokio::time::timeout(timeout, drain).await.is_ok() {
+            true
+        } else {
+            tracing::warn!("workers did not finish within {timeout:?}, aborting");
+            self.tasks.abort_all();
+            false
+        }
+    }
+}
+
+async fn run(
+    worker: usize,
+    receiver: Arc<Mutex<mpsc::Receiver<Uuid>>>,
+    store: JobStore,
+    config: Arc<WorkerConfig>,
+) {
+    loop {
+        // lock은 다음 ID를 받는 동안만 잡고, 작업 처리 전에 풀어줌
+        let next = receiver.lock().await.recv().await;
+        let Some(id) = next else {
+            break; // 채널이 닫혔고 남은 작업도 없음
+        };
+        process(worker, id, &store, &config).await;
+    }
+    tracing::debug!(worker, "worker stopped");
+}
+
+// 🔁 작업 하나를 재시도 포함해서 처리
+async fn process(worker: usize, id: Uuid, store: &JobStore, config: &WorkerConfig) {
+    let Some(job) = store.get(id) else {
+        return;
+    };
+
+    for attempt in 1..=config.max_attempts {
+        store.update(id, |job| {
+            job.status = JobStatus::Running;
+            job.attempts = attempt;
+        });
+
+        match perform(&job.payload, attempt).await {
+            Ok(()) => {
+                tracing::debug!(worker, %id, attempt, task = job.payload.task, "job succeeded");
+                store.update(id, |job| job.status = JobStatus::Succeeded);
+                return;
+            }
+            Err(error) if attempt == config.max_attempts => {
+                tracing::warn!(worker, %id, attempt, error, "job moved to dead letters");
+                store.mark_dead(id, error);
+                return;
+            }
+            Err(error) => {
+                let backoff = config.backoff(attempt);
+                tracing::debug!(worker, %id, attempt, error, ?backoff, "job failed, retrying");
+                store.update(id, |job| {
+                    job.status = JobStatus::Retrying;
+                    job.last_error = Some(error);
+                });
+                sleep(backoff).await;
+            }
+        }
+    }
+}
+
+// 🛠️ 실제 작업 (예제에서는 시간만 보내고, 설정된 횟수만큼 실패)
+async fn perform(payload: &JobPayload, attempt: u32) -> Result<(), String> {
+    sleep(Duration::from_millis(payload.duration_ms)).await;
+
+    if attempt <= payload.fail_attempts {
+        Err(format!("simulated failure on attempt {attempt}"))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jobs::{self, EnqueueError, JobQueue};
+
+    fn config() -> WorkerConfig {
+        WorkerConfig {
+            workers: 2,
+            max_attempts: 3,
+            base_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+        }
+    }
+
+    fn payload(duration_ms: u64, fail_attempts: u32) -> JobPayload {
+        JobPayload {
+            task: "test".to_owned(),
+            duration_ms,
+            fail_attempts,
+        }
+    }
+
+    // 큐를 닫고 워커가 모두 끝날 때까지 기다린 뒤 저장소를 돌려줌
+    async fn run_jobs(payloads: Vec<JobPayload>) -> (JobStore, Vec<Uuid>) {
+        let (queue, receiver) = jobs::queue(16);
+        let store = queue.store().clone();
+        let pool = spawn(receiver, store.clone(), config());
+
+        let ids = payloads
+            .into_iter()
+            .map(|payload| queue.enqueue(payload).unwrap().id)
+            .collect();
+
+        drop(queue);
+        assert!(pool.shutdown(Duration::from_secs(5)).await);
+        (store, ids)
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let config = WorkerConfig::default();
+
+        assert_eq!(config.backoff(1), Duration::from_millis(500));
+        assert_eq!(config.backoff(2), Duration::from_secs(1));
+        assert_eq!(config.backoff(3), Duration::from_secs(2));
+        assert_eq!(config.backoff(10), Duration::from_secs(10));
+        assert_eq!(config.backoff(100), Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn successful_job() {
+        let (store, ids) = run_jobs(vec![payload(0, 0)]).await;
+
+        let job = store.get(ids[0]).unwrap();
+        assert_eq!(job.status, JobStatus::Succeeded);
+        assert_eq!(job.attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn failed_job_is_retried() {
+        let (store, ids) = run_jobs(vec![payload(0, 2)]).await;
+
+        let job = store.get(ids[0]).unwrap();
+        assert_eq!(job.status, JobStatus::Succeeded);
+        assert_eq!(job.attempts, 3);
+        assert_eq!(
+            job.last_error.as_deref(),
+            Some("simulated failure on attempt 2")
+        );
+        assert!(store.dead_letters().is_empty());
+    }
+
+    #[tokio::test]
+    async fn exhausted_job_goes_to_dead_letters() {
+        let (store, ids) = run_jobs(vec![payload(0, 0), payload(0, 3)]).await;
+
+        let dead = store.dead_letters();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].id, ids[1]);
+        assert_eq!(dead[0].status, JobStatus::Dead);
+        assert_eq!(dead[0].attempts, 3);
+    }
+
+    // 종료 시 처리 중인 작업뿐 아니라 채널에 남아 있던 작업까지 모두 끝남
+    #[tokio::test]
+    async fn shutdown_drains_queued_jobs() {
+        let payloads = (0..6).map(|_| payload(20, 0)).collect();
+        let (store, ids) = run_jobs(payloads).await;
+
+        for id in ids {
+            assert_eq!(store.get(id).unwrap().status, JobStatus::Succeeded);
+        }
+    }
+
+    #[tokio::test]
+    async fn shutdown_times_out_on_stuck_jobs() {
+        let (queue, receiver) = jobs::queue(1);
+        let pool = spawn(receiver, queue.store().clone(), config());
+        queue.enqueue(payload(60_000, 0)).unwrap();
+        drop(queue);
+
+        assert!(!pool.shutdown(Duration::from_millis(50)).await);
+    }
+
+    #[tokio::test]
+    async fn full_queue_rejects_jobs() {
+        // 워커 없이 Receiver만 잡고 있으면 채널이 비워지지 않음
+        let (queue, _receiver): (JobQueue, _) = jobs::queue(1);
+
+        let first = queue.enqueue(payload(0, 0)).unwrap();
+        assert_eq!(
+            queue.enqueue(payload(0, 0)).unwrap_err(),
+            EnqueueError::Full
+        );
+        assert_eq!(
+            queue.store().get(first.id).unwrap().status,
+            JobStatus::Queued
+        );
+    }
+}
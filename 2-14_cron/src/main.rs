@@ -0,0 +1,309 @@
+//! 주기 작업(cron) 예제
+//!
+//! 애플리케이션 상태(AppState)를 사용하는 주기 작업을 `Scheduler`에 등록하고,
+//! HTTP로 스케줄 목록 조회 / 수동 실행 / 마지막 실행 결과 확인을 할 수 있습니다.
+//!
+//! 등록된 작업
+//! - `cleanup-sessions` (10초마다): 만료된 세션 삭제
+//! - `warm-cache` (30초마다): 인기 상품 목록을 미리 계산해 캐시에 저장
+//!
+//! API
+//! - `GET /schedules`: 스케줄 목록과 마지막 실행 결과
+//! - `GET /schedules/{name}`: 스케줄 하나 조회
+//! - `POST /schedules/{name}/run`: 수동 실행
+//! - `POST /sessions`: 세션 생성 (만료 시간 15초)
+//! - `GET /popular`: 캐시된 인기 상품 목록
+
+mod scheduler;
+
+use axum::{
+    extract::{FromRef, Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use scheduler::{RunError, RunRecord, ScheduleInfo, Scheduler};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use uuid::Uuid;
+
+const SESSION_TTL: Duration = Duration::from_secs(15);
+
+/// 🧩 AppState
+#[derive(Clone, Default, FromRef)]
+struct AppState {
+    sessions: Sessions,
+    cache: PopularCache,
+    scheduler: Scheduler,
+}
+
+// 세션 토큰 → 만료 시각
+type Sessions = Arc<RwLock<HashMap<Uuid, Instant>>>;
+
+#[derive(Clone, Default)]
+struct PopularCache(Arc<RwLock<Option<Vec<String>>>>);
+
+/// 🚀 메인 함수
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let state = AppState::default();
+    register_tasks(&state, Duration::from_secs(10), Duration::from_secs(30));
+    state.scheduler.start();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(state)).await.unwrap();
+}
+
+// 📅 작업 등록
+// 작업은 필요한 상태만 clone해서 캡처 (Arc라서 같은 데이터를 가리킴)
+fn register_tasks(state: &AppState, cleanup_every: Duration, warmup_every: Duration) {
+    let sessions = state.sessions.clone();
+    state
+        .scheduler
+        .register("cleanup-sessions", cleanup_every, move || {
+            let sessions = sessions.clone();
+            async move { Ok(cleanup_sessions(&sessions)) }
+        })
+        .expect("failed to register cleanup-sessions");
+
+    let cache = state.cache.clone();
+    state
+        .scheduler
+        .register("warm-cache", warmup_every, move || {
+            let cache = cache.clone();
+            async move { warm_cache(&cache).await }
+        })
+        .expect("failed to register warm-cache");
+}
+
+fn app(state: AppState) -> Router {
+    Router::new()
+        .route("/schedules", get(list_schedules))
+        .route("/schedules/{name}", get(get_schedule))
+        .route("/schedules/{name}/run", post(run_schedule))
+        .route("/sessions", post(create_session))
+        .route("/popular", get(popular))
+        .with_state(state)
+}
+
+// 🧹 만료된 세션 삭제
+fn cleanup_sessions(sessions: &Sessions) -> String {
+    let now = Instant::now();
+    let mut sessions = sessions.write().unwrap();
+    let before = sessions.len();
+    sessions.retain(|_, expires_at| *expires_at > now);
+    format!(
+        "removed {} expired sessions, {} active",
+        before - sessions.len(),
+        sessions.len()
+    )
+}
+
+// 🔥 캐시 워밍업 (DB 집계 쿼리를 흉내냄)
+async fn warm_cache(cache: &PopularCache) -> Result<String, String> {
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let items = ["keyboard", "mouse", "monitor"]
+        .into_iter()
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+    let count = items.len();
+    *cache.0.write().unwrap() = Some(items);
+
+    Ok(format!("cached {count} popular items"))
+}
+
+// 📚 핸들러
+
+async fn list_schedules(State(scheduler): State<Scheduler>) -> Json<Vec<ScheduleInfo>> {
+    Json(scheduler.list())
+}
+
+async fn get_schedule(
+    State(scheduler): State<Scheduler>,
+    Path(name): Path<String>,
+) -> Result<Json<ScheduleInfo>, StatusCode> {
+    scheduler.info(&name).map(Json).ok_or(StatusCode::NOT_FOUND)
+}
+
+// 실행이 끝날 때까지 기다린 뒤 결과를 반환
+async fn run_schedule(
+    State(scheduler): State<Scheduler>,
+    Path(name): Path<String>,
+) -> Result<Json<RunRecord>, Response> {
+    scheduler.run_now(&name).await.map(Json).map_err(|err| {
+        match err {
+            RunError::NotFound => (StatusCode::NOT_FOUND, "unknown schedule"),
+            RunError::AlreadyRunning => (StatusCode::CONFLICT, "schedule is already running"),
+        }
+        .into_response()
+    })
+}
+
+#[derive(Serialize)]
+struct SessionCreated {
+    token: Uuid,
+    expires_in_secs: u64,
+}
+
+async fn create_session(State(sessions): State<Sessions>) -> (StatusCode, Json<SessionCreated>) {
+    let token = Uuid::new_v4();
+    sessions
+        .write()
+        .unwrap()
+        .insert(token, Instant::now() + SESSION_TTL);
+
+    (
+        StatusCode::CREATED,
+        Json(SessionCreated {
+            token,
+            expires_in_secs: SESSION_TTL.as_secs(),
+        }),
+    )
+}
+
+// 캐시가 아직 채워지지 않았으면 503
+async fn popular(State(cache): State<PopularCache>) -> Result<Json<Vec<String>>, StatusCode> {
+    cache
+        .0
+        .read()
+        .unwrap()
+        .clone()
+        .map(Json)
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use http_body_util::BodyExt;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    // 테스트에서는 주기 실행을 시작하지 않고 수동 실행만 사용
+    fn test_state() -> AppState {
+        let state = AppState::default();
+        register_tasks(&state, Duration::from_secs(60), Duration::from_secs(60));
+        state
+    }
+
+    async fn send(state: &AppState, method: &str, uri: &str) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+        let response = app(state.clone()).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    #[tokio::test]
+    async fn lists_registered_schedules() {
+        let state = test_state();
+
+        let (status, body) = send(&state, "GET", "/schedules").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let names = body
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|schedule| schedule["name"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["cleanup-sessions", "warm-cache"]);
+        assert_eq!(body[0]["last_run"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn manual_warmup_fills_cache() {
+        let state = test_state();
+
+        let (status, _) = send(&state, "GET", "/popular").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+
+        let (status, record) = send(&state, "POST", "/schedules/warm-cache/run").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(record["trigger"], "manual");
+        assert_eq!(record["message"], "cached 3 popular items");
+
+        let (status, items) = send(&state, "GET", "/popular").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(items[0], "keyboard");
+
+        let (_, schedule) = send(&state, "GET", "/schedules/warm-cache").await;
+        assert_eq!(schedule["runs"], 1);
+        assert_eq!(schedule["last_run"]["success"], true);
+    }
+
+    #[tokio::test]
+    async fn cleanup_removes_only_expired_sessions() {
+        let state = test_state();
+        send(&state, "POST", "/sessions").await;
+        state
+            .sessions
+            .write()
+            .unwrap()
+            .insert(Uuid::new_v4(), Instant::now() - Duration::from_secs(1));
+
+        let (_, record) = send(&state, "POST", "/schedules/cleanup-sessions/run").await;
+
+        assert_eq!(record["message"], "removed 1 expired sessions, 1 active");
+        assert_eq!(state.sessions.read().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn run_errors() {
+        let state = test_state();
+
+        let (status, _) = send(&state, "POST", "/schedules/nope/run").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // warm-cache는 200ms 걸리므로 실행 중에 다시 요청하면 409
+        let running = tokio::spawn({
+            let state = state.clone();
+            async move { send(&state, "POST", "/schedules/warm-cache/run").await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let (status, _) = send(&state, "POST", "/schedules/warm-cache/run").await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(running.await.unwrap().0, StatusCode::OK);
+    }
+}
+
+// 🧪 테스트 방법
+//
+// ✅ 스케줄 목록 (서버 시작 직후 두 작업이 한 번씩 실행됨)
+// curl http://localhost:3000/schedules
+//
+// ✅ 세션 생성 후 15초 이상 지나면 cleanup-sessions 작업이 삭제
+// curl -X POST http://localhost:3000/sessions
+//
+// ✅ 수동 실행
+// curl -X POST http://localhost:3000/schedules/warm-cache/run
+//
+// ✅ 캐시 확인
+// curl http://localhost:3000/popular
@@ -0,0 +1,394 @@
+//! 주기 작업 스케줄러
+//!
+//! - [`Scheduler::register`]로 이름, 주기, 작업(async 클로저)을 등록
+//!   → 주기가 0이거나 `start` 이후에 등록하면 에러 (시작 후 등록한 작업은 루프가 없어 영영 실행되지 않음)
+//! - [`Scheduler::start`]가 작업마다 `tokio::time::interval` 루프를 하나씩 띄움
+//! - [`Scheduler::run_now`]로 수동 실행 (같은 작업이 이미 실행 중이면 거절)
+//! - 마지막 실행 결과와 실행 횟수를 기록해 API로 조회
+//! - 실행은 매번 별도 태스크에서 → 작업이 패닉해도 실패로 기록되고 다음 주기에 다시 실행됨
+
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    sync::Mutex,
+    time::{interval, MissedTickBehavior},
+};
+
+/// 작업 결과: 성공 시 요약 메시지, 실패 시 에러 메시지
+pub type TaskResult = Result<String, String>;
+type TaskFn = Arc<dyn Fn() -> Pin<Box<dyn Future<Output = TaskResult> + Send>> + Send + Sync>;
+
+/// 📅 스케줄러 (AppState에 넣어 핸들러와 공유)
+#[derive(Clone, Default)]
+pub struct Scheduler {
+    tasks: Arc<RwLock<BTreeMap<String, Arc<Task>>>>,
+    started: Arc<AtomicBool>,
+}
+
+struct Task {
+    every: Duration,
+    run: TaskFn,
+    // 같은 작업이 동시에 두 번 실행되지 않도록 막는 lock (실행 태스크가 guard를 가지고 감)
+    running: Arc<Mutex<()>>,
+    history: RwLock<History>,
+}
+
+#[derive(Default)]
+struct History {
+    runs: u64,
+    failures: u64,
+    last_run: Option<RunRecord>,
+}
+
+/// 실행 계기
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Trigger {
+    Schedule,
+    Manual,
+}
+
+/// 📝 한 번의 실행 기록
+#[derive(Debug, Clone, Serialize)]
+pub struct RunRecord {
+    pub trigger: Trigger,
+    /// 실행 시작 시각 (Unix time, 초)
+    pub started_at: u64,
+    pub duration_ms: u128,
+    pub success: bool,
+    pub message: String,
+}
+
+/// 📋 조회용 스케줄 정보
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleInfo {
+    pub name: String,
+    pub every_secs: u64,
+    pub running: bool,
+    pub runs: u64,
+    pub failures: u64,
+    pub last_run: Option<RunRecord>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RunError {
+    NotFound,
+    AlreadyRunning,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RegisterError {
+    /// `tokio::time::interval`은 0을 받으면 패닉
+    ZeroInterval,
+    AlreadyStarted,
+}
+
+impl std::fmt::Display for RegisterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ZeroInterval => f.write_str("schedule interval must be greater than zero"),
+            Self::AlreadyStarted => f.write_str("tasks must be registered before start()"),
+        }
+    }
+}
+
+impl std::error::Error for RegisterError {}
+
+impl Scheduler {
+    /// 작업 등록 (같은 이름이면 덮어씀, `start` 전에만)
+    pub fn register<F, Fut>(
+        &self,
+        name: &str,
+        every: Duration,
+        task: F,
+    ) -> Result<(), RegisterError>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = TaskResult> + Send + 'static,
+    {
+        if every.is_zero() {
+            return Err(RegisterError::ZeroInterval);
+        }
+        let task = Task {
+            every,
+            run: Arc::new(move || Box::pin(task())),
+            running: Arc::default(),
+            history: RwLock::default(),
+        };
+        // start가 목록을 읽는 동안 등록되지 않도록 같은 lock 안에서 확인
+        let mut tasks = self.tasks.write().unwrap();
+        if self.started.load(Ordering::SeqCst) {
+            return Err(RegisterError::AlreadyStarted);
+        }
+        tasks.insert(name.to_owned(), Arc::new(task));
+        Ok(())
+    }
+
+    /// ⏱️ 등록된 모든 작업의 주기 실행 시작
+    ///
+    /// 첫 실행은 바로 일어나고(캐시 워밍업 등), 이후 `every`마다 반복.
+    /// 실행이 주기보다 오래 걸려 밀린 tick은 건너뜀 (한꺼번에 몰아서 실행하지 않음)
+    /// 두 번째 호출부터는 아무것도 하지 않음
+    pub fn start(&self) {
+        let tasks = self.tasks.read().unwrap();
+        if self.started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        for (name, task) in tasks.iter() {
+            let name = name.clone();
+            let task = task.clone();
+
+            tokio::spawn(async move {
+                let mut ticker = interval(task.every);
+                ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+                loop {
+                    ticker.tick().await;
+                    // 수동 실행이 진행 중이면 이번 주기는 건너뜀
+                    if let Err(RunError::AlreadyRunning) = execute(&task, Trigger::Schedule).await {
+                        tracing::debug!(task = name, "previous run still in progress, skipping");
+                    }
+                }
+            });
+        }
+    }
+
+    /// ▶️ 수동 실행 (끝날 때까지 기다린 뒤 결과 반환)
+    pub async fn run_now(&self, name: &str) -> Result<RunRecord, RunError> {
+        let task = self.get(name).ok_or(RunError::NotFound)?;
+        execute(&task, Trigger::Manual).await
+    }
+
+    pub fn list(&self) -> Vec<ScheduleInfo> {
+        self.tasks
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(name, task)| info(name, task))
+            .collect()
+    }
+
+    pub fn info(&self, name: &str) -> Option<ScheduleInfo> {
+        self.get(name).map(|task| info(name, &task))
+    }
+
+    fn get(&self, name: &str) -> Option<Arc<Task>> {
+        self.tasks.read().unwrap().get(name).cloned()
+    }
+}
+
+async fn execute(task: &Task, trigger: Trigger) -> Result<RunRecord, RunError> {
+    let guard = task
+        .running
+        .clone()
+        .try_lock_owned()
+        .map_err(|_| RunError::AlreadyRunning)?;
+
+    let started_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let start = Instant::now();
+    // 별도 태스크에서 실행 → 패닉이 스케줄 루프(또는 수동 실행 요청)까지 번지지 않음
+    // guard도 함께 넘겨서, 호출한 쪽이 취소되어도 실행이 끝날 때까지 중복 실행을 막음
+    let run = (task.run)();
+    let result = match tokio::spawn(async move {
+        let _guard = guard;
+        run.await
+    })
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            tracing::error!(%err, "scheduled task panicked");
+            Err(err.to_string())
+        }
+    };
+
+    let record = RunRecord {
+        trigger,
+        started_at,
+        duration_ms: start.elapsed().as_millis(),
+        success: result.is_ok(),
+        message: result.unwrap_or_else(|err| err),
+    };
+
+    let mut history = task.history.write().unwrap();
+    history.runs += 1;
+    if !record.success {
+        history.failures += 1;
+        tracing::warn!(message = record.message, "scheduled task failed");
+    }
+    history.last_run = Some(record.clone());
+
+    Ok(record)
+}
+
+fn info(name: &str, task: &Task) -> ScheduleInfo {
+    let history = task.history.read().unwrap();
+    ScheduleInfo {
+        name: name.to_owned(),
+        every_secs: task.every.as_secs(),
+        running: task.running.try_lock().is_err(),
+        runs: history.runs,
+        failures: history.failures,
+        last_run: history.last_run.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+
+    fn counting_task(scheduler: &Scheduler, every: Duration) -> Arc<AtomicU32> {
+        let count = Arc::new(AtomicU32::new(0));
+        let counter = count.clone();
+        scheduler
+            .register("count", every, move || {
+                let counter = counter.clone();
+                async move {
+                    let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                    Ok(format!("run {n}"))
+                }
+            })
+            .unwrap();
+        count
+    }
+
+    #[tokio::test]
+    async fn manual_run_records_result() {
+        let scheduler = Scheduler::default();
+        counting_task(&scheduler, Duration::from_secs(60));
+
+        let record = scheduler.run_now("count").await.unwrap();
+        assert_eq!(record.trigger, Trigger::Manual);
+        assert!(record.success);
+        assert_eq!(record.message, "run 1");
+
+        let info = scheduler.info("count").unwrap();
+        assert_eq!(info.runs, 1);
+        assert_eq!(info.every_secs, 60);
+        assert!(!info.running);
+    }
+
+    #[tokio::test]
+    async fn failures_are_counted() {
+        let scheduler = Scheduler::default();
+        scheduler
+            .register("broken", Duration::from_secs(60), || async {
+                Err("boom".to_owned())
+            })
+            .unwrap();
+
+        let record = scheduler.run_now("broken").await.unwrap();
+        assert!(!record.success);
+        assert_eq!(record.message, "boom");
+        assert_eq!(scheduler.info("broken").unwrap().failures, 1);
+    }
+
+    #[tokio::test]
+    async fn unknown_task() {
+        let scheduler = Scheduler::default();
+        assert_eq!(
+            scheduler.run_now("nope").await.unwrap_err(),
+            RunError::NotFound
+        );
+    }
+
+    #[tokio::test]
+    async fn overlapping_runs_are_rejected() {
+        let scheduler = Scheduler::default();
+        scheduler
+            .register("slow", Duration::from_secs(60), || async {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                Ok("done".to_owned())
+            })
+            .unwrap();
+
+        let first = tokio::spawn({
+            let scheduler = scheduler.clone();
+            async move { scheduler.run_now("slow").await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        assert!(scheduler.info("slow").unwrap().running);
+        assert_eq!(
+            scheduler.run_now("slow").await.unwrap_err(),
+            RunError::AlreadyRunning
+        );
+        assert!(first.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn started_tasks_run_periodically() {
+        let scheduler = Scheduler::default();
+        let count = counting_task(&scheduler, Duration::from_millis(10));
+
+        scheduler.start();
+        tokio::time::sleep(Duration::from_millis(55)).await;
+
+        // 시작 직후 1번 + 10ms마다 (타이밍 오차를 감안해 넉넉하게 확인)
+        assert!(count.load(Ordering::SeqCst) >= 3);
+        let last_run = scheduler.info("count").unwrap().last_run.unwrap();
+        assert_eq!(last_run.trigger, Trigger::Schedule);
+    }
+
+    #[test]
+    fn invalid_registrations_are_rejected() {
+        let scheduler = Scheduler::default();
+        assert_eq!(
+            scheduler.register("zero", Duration::ZERO, || async { Ok(String::new()) }),
+            Err(RegisterError::ZeroInterval)
+        );
+        assert!(scheduler.list().is_empty());
+    }
+
+    #[tokio::test]
+    async fn registering_after_start_is_rejected() {
+        let scheduler = Scheduler::default();
+        scheduler.start();
+        assert_eq!(
+            scheduler.register("late", Duration::from_secs(60), || async {
+                Ok(String::new())
+            }),
+            Err(RegisterError::AlreadyStarted)
+        );
+    }
+
+    #[tokio::test]
+    async fn panicking_task_keeps_its_schedule() {
+        let scheduler = Scheduler::default();
+        let count = Arc::new(AtomicU32::new(0));
+        let counter = count.clone();
+        scheduler
+            .register("panics", Duration::from_millis(10), move || {
+                let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                async move {
+                    if n == 1 {
+                        panic!("boom");
+                    }
+                    Ok(format!("run {n}"))
+                }
+            })
+            .unwrap();
+
+        scheduler.start();
+        tokio::time::sleep(Duration::from_millis(55)).await;
+
+        // 첫 실행의 패닉 뒤에도 계속 실행됨
+        assert!(count.load(Ordering::SeqCst) >= 3);
+        let info = scheduler.info("panics").unwrap();
+        assert_eq!(info.failures, 1);
+        assert!(info.last_run.unwrap().success);
+    }
+}
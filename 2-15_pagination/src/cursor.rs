@@ -0,0 +1,170 @@
+//! `?cursor=..&limit=20` 커서 방식 (keyset pagination)
+//!
+//! `OFFSET`은 앞의 행을 모두 읽고 버리므로 뒤 페이지일수록 느리고, 그 사이 행이 추가/삭제되면 항목이 밀림.
+//! 커서 방식은 "마지막으로 본 키보다 큰 것"을 읽어서 둘 다 없음 (대신 임의의 페이지로 이동할 수 없음).
+
+use axum::{
+    extract::FromRequestParts,
+    http::{request::Parts, Uri},
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::Deserialize;
+use std::str::FromStr;
+
+use crate::{request_uri, PaginationRejection};
+
+/// 이전 페이지 마지막 항목의 키 + 페이지 크기
+///
+/// • `cursor`가 없으면 처음부터
+/// • `limit`은 1..=`MAX_LIMIT`로 맞춤
+#[derive(Debug, Clone)]
+pub struct CursorPagination {
+    /// 디코딩한 키 (이 키보다 뒤의 항목부터 읽음)
+    pub after: Option<String>,
+    pub limit: u32,
+    pub(crate) uri: Uri,
+}
+
+#[derive(Deserialize)]
+struct CursorQuery {
+    cursor: Option<String>,
+    limit: Option<u32>,
+}
+
+impl CursorPagination {
+    pub const DEFAULT_LIMIT: u32 = 20;
+    pub const MAX_LIMIT: u32 = 100;
+
+    /// 다음 페이지가 있는지 알기 위해 하나 더 읽을 개수 (`Page::cursor`가 잘라냄)
+    pub fn fetch_limit(&self) -> u32 {
+        self.limit + 1
+    }
+
+    /// `after`를 원래 키 타입으로 변환 (형식이 틀린 커서는 400)
+    pub fn parse_after<T: FromStr>(&self) -> Result<Option<T>, PaginationRejection> {
+        self.after
+            .as_deref()
+            .map(|after| after.parse())
+            .transpose()
+            .map_err(|_| PaginationRejection::new("invalid cursor"))
+    }
+
+    pub(crate) fn encode(key: &str) -> String {
+        URL_SAFE_NO_PAD.encode(key)
+    }
+
+    fn decode(cursor: &str) -> Option<String> {
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+        String::from_utf8(bytes).ok()
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for CursorPagination {
+    type Rejection = PaginationRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let query: CursorQuery = serde_urlencoded::from_str(parts.uri.query().unwrap_or_default())
+            .map_err(|err| PaginationRejection::new(format!("invalid pagination: {err}")))?;
+
+        // 빈 cursor(`?cursor=`)는 처음부터
+        let after = match query.cursor.filter(|cursor| !cursor.is_empty()) {
+            Some(cursor) => Some(
+                Self::decode(&cursor).ok_or_else(|| PaginationRejection::new("invalid cursor"))?,
+            ),
+            None => None,
+        };
+
+        Ok(Self {
+            after,
+            limit: query
+                .limit
+                .unwrap_or(Self::DEFAULT_LIMIT)
+                .clamp(1, Self::MAX_LIMIT),
+            uri: request_uri(parts),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::send, Page};
+    use axum::{routing::get, Router};
+    use serde_json::{json, Value};
+
+    // 키가 "a".."e"인 항목을 키 순서로 반환
+    fn app() -> Router {
+        Router::new().route(
+            "/letters",
+            get(|pagination: CursorPagination| async move {
+                let items = ["a", "b", "c", "d", "e"]
+                    .into_iter()
+                    .filter(|key| pagination.after.as_deref().is_none_or(|after| *key > after))
+                    .take(pagination.fetch_limit() as usize)
+                    .collect();
+                Page::cursor(items, &pagination, |key| key.to_string())
+            }),
+        )
+    }
+
+    fn next_link(link: &str) -> Option<&str> {
+        link.split(", ")
+            .find_map(|link| link.strip_suffix("; rel=\"next\""))
+            .map(|url| url.trim_matches(['<', '>']))
+    }
+
+    #[tokio::test]
+    async fn following_next_links_visits_every_item_once() {
+        let mut uri = "/letters?limit=2".to_owned();
+        let mut seen = Vec::new();
+        loop {
+            let (status, link, body) = send(app(), &uri).await;
+            assert_eq!(status, 200);
+            seen.extend(body["items"].as_array().unwrap().iter().cloned());
+
+            let link = link.unwrap();
+            assert!(
+                link.starts_with("</letters?limit=2>; rel=\"first\""),
+                "{link}"
+            );
+            match next_link(&link) {
+                Some(next) => {
+                    assert_eq!(
+                        body["next_cursor"],
+                        Value::from(next.split("cursor=").nth(1))
+                    );
+                    uri = next.to_owned();
+                }
+                None => {
+                    assert_eq!(body["next_cursor"], Value::Null);
+                    break;
+                }
+            }
+        }
+        assert_eq!(Value::from(seen), json!(["a", "b", "c", "d", "e"]));
+    }
+
+    #[tokio::test]
+    async fn invalid_cursor_is_rejected() {
+        let (status, _, body) = send(app(), "/letters?cursor=!!!").await;
+        assert_eq!(status, 400);
+        assert_eq!(body["message"], "invalid cursor");
+
+        // base64지만 UTF-8이 아님
+        let (status, _, _) = send(app(), "/letters?cursor=_w").await;
+        assert_eq!(status, 400);
+
+        // 디코딩은 되지만 키 타입으로 변환할 수 없음
+        let numbers = CursorPagination {
+            after: Some("a".to_owned()),
+            limit: 1,
+            uri: Uri::from_static("/numbers"),
+        };
+        assert!(numbers.parse_after::<u32>().is_err());
+
+        let (status, _, body) = send(app(), "/letters?cursor=&limit=0").await;
+        assert_eq!(status, 200);
+        assert_eq!(body["limit"], 1);
+        assert_eq!(body["items"], json!(["a"]));
+    }
+}
@@ -0,0 +1,103 @@
+//! 목록 API에서 함께 쓰는 페이지네이션 도구 모음입니다.
+//!
+//! ```toml
+//! example-pagination = { path = "../2-15_pagination" }
+//! ```
+//!
+//! • `OffsetPagination`: `?page=2&per_page=20` (전체 개수를 알 때, 임의의 페이지로 이동 가능)
+//! • `CursorPagination`: `?cursor=..&limit=20` (정렬 키 기준으로 이어서 읽기, 중간에 데이터가 추가돼도 밀리지 않음)
+//!   → 커서는 마지막 항목의 키를 base64url로 감싼 불투명한 문자열 (클라이언트는 해석하지 않고 그대로 돌려줌)
+//! • `Page<T>`: 두 방식 모두 같은 응답 형태 + RFC 5988 `Link` 헤더 (first/prev/next/last)
+//!   → 링크 URL은 요청 경로와 다른 쿼리 파라미터(필터 등)를 유지하고 페이지 파라미터만 바꿈
+//!
+//! ```json
+//! { "items": [..], "page": 2, "per_page": 20, "total": 42 }
+//! { "items": [..], "limit": 20, "next_cursor": "MTA" }
+//! ```
+//!
+//! ```rust,ignore
+//! use example_pagination::{OffsetPagination, Page};
+//!
+//! async fn list(pagination: OffsetPagination) -> Page<User> {
+//!     let users = db.list(pagination.offset(), pagination.per_page).await;
+//!     Page::offset(users, &pagination, db.count().await)
+//! }
+//! ```
+//!
+//! 쿼리 파라미터가 잘못되면 400 `{"message": ...}` (common-errors와 같은 형태)
+
+mod cursor;
+mod offset;
+mod page;
+
+pub use cursor::CursorPagination;
+pub use offset::OffsetPagination;
+pub use page::{Page, PageMeta};
+
+use axum::{
+    extract::OriginalUri,
+    http::{request::Parts, StatusCode, Uri},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+/// 페이지 쿼리 파라미터가 잘못됐을 때의 리젝션 (400)
+#[derive(Debug)]
+pub struct PaginationRejection {
+    message: String,
+}
+
+impl PaginationRejection {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl IntoResponse for PaginationRejection {
+    fn into_response(self) -> Response {
+        #[derive(Serialize)]
+        struct ErrorResponse {
+            message: String,
+        }
+
+        let body = ErrorResponse {
+            message: self.message,
+        };
+        (StatusCode::BAD_REQUEST, Json(body)).into_response()
+    }
+}
+
+// 중첩 라우터에서는 parts.uri의 앞부분이 잘려 있으므로 링크에는 원래 URI를 사용
+fn request_uri(parts: &Parts) -> Uri {
+    parts
+        .extensions
+        .get::<OriginalUri>()
+        .map(|OriginalUri(uri)| uri.clone())
+        .unwrap_or_else(|| parts.uri.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, http::Request, Router};
+    use http_body_util::BodyExt;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    /// (상태 코드, Link 헤더, JSON body)
+    pub(crate) async fn send(app: Router, uri: &str) -> (u16, Option<String>, Value) {
+        let response = app
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status().as_u16();
+        let link = response
+            .headers()
+            .get("link")
+            .map(|value| value.to_str().unwrap().to_owned());
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, link, serde_json::from_slice(&body).unwrap())
+    }
+}
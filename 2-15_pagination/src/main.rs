@@ -0,0 +1,78 @@
+//! `example_pagination`의 두 추출기를 사용하는 예제 서버입니다.
+//!
+//! 같은 상품 목록을 페이지 번호 방식(`/products`)과 커서 방식(`/products/feed`)으로 제공합니다.
+
+use axum::{routing::get, Router};
+use example_pagination::{CursorPagination, OffsetPagination, Page, PaginationRejection};
+use serde::Serialize;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[tokio::main]
+async fn main() {
+    // ✨ tracing 설정: 환경 변수 기반 필터와 포맷터를 등록하여 로깅 초기화
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    // ✨ 라우터 구성
+    let app = Router::new()
+        .route("/products", get(list_products))
+        .route("/products/feed", get(product_feed));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app).await.unwrap();
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Product {
+    id: u32,
+    name: String,
+}
+
+// id 순으로 정렬된 가짜 데이터 (실제로는 DB)
+fn products() -> impl Iterator<Item = Product> {
+    (1..=95).map(|id| Product {
+        id,
+        name: format!("product #{id}"),
+    })
+}
+
+// GET /products?page=2&per_page=20
+async fn list_products(pagination: OffsetPagination) -> Page<Product> {
+    let items = products()
+        .skip(pagination.offset() as usize)
+        .take(pagination.per_page as usize)
+        .collect();
+    Page::offset(items, &pagination, products().count() as u64)
+}
+
+// GET /products/feed?limit=20&cursor=..
+async fn product_feed(pagination: CursorPagination) -> Result<Page<Product>, PaginationRejection> {
+    // 커서에는 마지막으로 받은 id가 들어 있음 (숫자가 아니면 400)
+    let after: u32 = pagination.parse_after()?.unwrap_or(0);
+    let items = products()
+        .filter(|product| product.id > after)
+        .take(pagination.fetch_limit() as usize)
+        .collect();
+    Ok(Page::cursor(items, &pagination, |product| {
+        product.id.to_string()
+    }))
+}
+
+// ✅ 요청 예시
+// curl -i 'localhost:3000/products?page=2&per_page=20'
+// → link: </products?page=1&per_page=20>; rel="first", </products?page=1&per_page=20>; rel="prev",
+//         </products?page=3&per_page=20>; rel="next", </products?page=5&per_page=20>; rel="last"
+// → {"items":[{"id":21,...},...],"page":2,"per_page":20,"total":95}
+// curl -i 'localhost:3000/products/feed?limit=2'
+// → link: </products/feed?limit=2>; rel="first", </products/feed?limit=2&cursor=Mg>; rel="next"
+// → {"items":[{"id":1,...},{"id":2,...}],"limit":2,"next_cursor":"Mg"}
+// curl 'localhost:3000/products?page=x'
+// → 400 {"message":"invalid pagination: invalid digit found in string"}
@@ -0,0 +1,124 @@
+//! `?page=2&per_page=20` 페이지 번호 방식
+
+use axum::{
+    extract::FromRequestParts,
+    http::{request::Parts, Uri},
+};
+use serde::Deserialize;
+
+use crate::{request_uri, PaginationRejection};
+
+/// 1부터 시작하는 페이지 번호 + 페이지 크기
+///
+/// • 값이 없으면 `page=1`, `per_page=DEFAULT_PER_PAGE`
+/// • `page=0`은 1로, `per_page`는 1..=`MAX_PER_PAGE`로 맞춤 (너무 큰 페이지 요청 방지)
+#[derive(Debug, Clone)]
+pub struct OffsetPagination {
+    pub page: u32,
+    pub per_page: u32,
+    pub(crate) uri: Uri,
+}
+
+#[derive(Deserialize)]
+struct OffsetQuery {
+    page: Option<u32>,
+    per_page: Option<u32>,
+}
+
+impl OffsetPagination {
+    pub const DEFAULT_PER_PAGE: u32 = 20;
+    pub const MAX_PER_PAGE: u32 = 100;
+
+    /// 건너뛸 항목 수 (SQL의 `OFFSET`)
+    pub fn offset(&self) -> u64 {
+        u64::from(self.page - 1) * u64::from(self.per_page)
+    }
+
+    /// 전체 개수가 `total`일 때 마지막 페이지 번호 (비어 있어도 1)
+    pub fn last_page(&self, total: u64) -> u32 {
+        let pages = total.div_ceil(u64::from(self.per_page)).max(1);
+        u32::try_from(pages).unwrap_or(u32::MAX)
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for OffsetPagination {
+    type Rejection = PaginationRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let query: OffsetQuery = serde_urlencoded::from_str(parts.uri.query().unwrap_or_default())
+            .map_err(|err| PaginationRejection::new(format!("invalid pagination: {err}")))?;
+
+        Ok(Self {
+            page: query.page.unwrap_or(1).max(1),
+            per_page: query
+                .per_page
+                .unwrap_or(Self::DEFAULT_PER_PAGE)
+                .clamp(1, Self::MAX_PER_PAGE),
+            uri: request_uri(parts),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::send, Page};
+    use axum::{routing::get, Router};
+
+    // 1..=total 숫자를 페이지로 나눠 반환
+    fn app(total: u64) -> Router {
+        Router::new().route(
+            "/numbers",
+            get(move |pagination: OffsetPagination| async move {
+                let items = (1..=total)
+                    .skip(pagination.offset() as usize)
+                    .take(pagination.per_page as usize)
+                    .collect();
+                Page::offset(items, &pagination, total)
+            }),
+        )
+    }
+
+    #[tokio::test]
+    async fn defaults_and_clamping() {
+        let (_, _, body) = send(app(3), "/numbers").await;
+        assert_eq!(body["page"], 1);
+        assert_eq!(body["per_page"], OffsetPagination::DEFAULT_PER_PAGE);
+
+        let (_, _, body) = send(app(3), "/numbers?page=0&per_page=1000").await;
+        assert_eq!(body["page"], 1);
+        assert_eq!(body["per_page"], OffsetPagination::MAX_PER_PAGE);
+
+        let (status, _, body) = send(app(3), "/numbers?page=abc").await;
+        assert_eq!(status, 400);
+        assert!(body["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("invalid pagination"));
+    }
+
+    #[tokio::test]
+    async fn links_keep_other_query_parameters() {
+        let (status, link, body) = send(app(45), "/numbers?q=x&page=2&per_page=20").await;
+        assert_eq!(status, 200);
+        assert_eq!(body["items"][0], 21);
+        assert_eq!(body["total"], 45);
+        assert_eq!(
+            link.unwrap(),
+            "</numbers?q=x&page=1&per_page=20>; rel=\"first\", \
+             </numbers?q=x&page=1&per_page=20>; rel=\"prev\", \
+             </numbers?q=x&page=3&per_page=20>; rel=\"next\", \
+             </numbers?q=x&page=3&per_page=20>; rel=\"last\""
+        );
+
+        // 마지막 페이지를 넘어가면 prev는 마지막 페이지, next는 없음
+        let (_, link, body) = send(app(45), "/numbers?page=9").await;
+        assert_eq!(body["items"], serde_json::json!([]));
+        assert_eq!(
+            link.unwrap(),
+            "</numbers?page=1&per_page=20>; rel=\"first\", \
+             </numbers?page=3&per_page=20>; rel=\"prev\", \
+             </numbers?page=3&per_page=20>; rel=\"last\""
+        );
+    }
+}
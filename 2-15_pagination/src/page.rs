@@ -0,0 +1,162 @@
+//! 페이지 응답 (`Page<T>`) + `Link` 헤더
+
+use axum::{
+    http::{header, HeaderValue, Uri},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::{CursorPagination, OffsetPagination};
+
+/// 목록 응답 envelope
+///
+/// JSON body에는 항목과 `PageMeta`, `Link` 헤더에는 이동할 페이지 URL이 들어감
+#[derive(Debug, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    #[serde(flatten)]
+    pub meta: PageMeta,
+    #[serde(skip)]
+    links: Vec<(&'static str, String)>,
+}
+
+/// 페이지 방식별 메타데이터 (`items`와 같은 단계에 펼쳐짐)
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum PageMeta {
+    Offset {
+        page: u32,
+        per_page: u32,
+        total: u64,
+    },
+    Cursor {
+        limit: u32,
+        /// 마지막 페이지면 null
+        next_cursor: Option<String>,
+    },
+}
+
+impl<T> Page<T> {
+    /// 페이지 번호 방식: first / prev / next / last 링크
+    pub fn offset(items: Vec<T>, pagination: &OffsetPagination, total: u64) -> Self {
+        let (page, per_page) = (pagination.page, pagination.per_page);
+        let last = pagination.last_page(total);
+        let link = |page: u32| {
+            let page = page.to_string();
+            let per_page = per_page.to_string();
+            link_url(
+                &pagination.uri,
+                &[("page", Some(&page)), ("per_page", Some(&per_page))],
+            )
+        };
+
+        let mut links = vec![("first", link(1))];
+        if page > 1 {
+            // 범위를 벗어난 페이지에서도 prev는 실제로 있는 페이지를 가리키도록
+            links.push(("prev", link((page - 1).min(last))));
+        }
+        if page < last {
+            links.push(("next", link(page + 1)));
+        }
+        links.push(("last", link(last)));
+
+        Self {
+            items,
+            meta: PageMeta::Offset {
+                page,
+                per_page,
+                total,
+            },
+            links,
+        }
+    }
+
+    /// 커서 방식: `fetch_limit()`개까지 읽은 `items`를 받아 `limit`개로 자르고, 남는 게 있으면 next 링크
+    ///
+    /// `key`는 정렬 키 (다음 요청의 `after`로 돌아옴), 앞으로만 이동하므로 prev/last 링크는 없음
+    pub fn cursor(
+        mut items: Vec<T>,
+        pagination: &CursorPagination,
+        key: impl Fn(&T) -> String,
+    ) -> Self {
+        let has_more = items.len() > pagination.limit as usize;
+        items.truncate(pagination.limit as usize);
+        let next_cursor = items
+            .last()
+            .filter(|_| has_more)
+            .map(|last| CursorPagination::encode(&key(last)));
+
+        let limit = pagination.limit.to_string();
+        let mut links = vec![(
+            "first",
+            link_url(
+                &pagination.uri,
+                &[("limit", Some(&limit)), ("cursor", None)],
+            ),
+        )];
+        if let Some(cursor) = &next_cursor {
+            links.push((
+                "next",
+                link_url(
+                    &pagination.uri,
+                    &[("limit", Some(&limit)), ("cursor", Some(cursor))],
+                ),
+            ));
+        }
+
+        Self {
+            items,
+            meta: PageMeta::Cursor {
+                limit: pagination.limit,
+                next_cursor,
+            },
+            links,
+        }
+    }
+
+    /// `</users?page=2&per_page=20>; rel="next", ...` (RFC 5988)
+    pub fn link_header(&self) -> Option<HeaderValue> {
+        let value = self
+            .links
+            .iter()
+            .map(|(rel, url)| format!("<{url}>; rel=\"{rel}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+        // URL은 파싱된 Uri + form_urlencoded 인코딩이라 항상 유효한 헤더 값
+        HeaderValue::from_str(&value).ok()
+    }
+}
+
+impl<T: Serialize> IntoResponse for Page<T> {
+    fn into_response(self) -> Response {
+        match self.link_header() {
+            Some(link) => ([(header::LINK, link)], Json(self)).into_response(),
+            None => Json(self).into_response(),
+        }
+    }
+}
+
+// 요청 URL에서 `params`의 키만 바꾼 경로 + 쿼리 (None이면 제거)
+// → 필터/정렬 같은 다른 쿼리 파라미터는 그대로 유지
+fn link_url(uri: &Uri, params: &[(&str, Option<&str>)]) -> String {
+    let query = uri.query().unwrap_or_default();
+    let mut serializer = form_urlencoded::Serializer::new(String::new());
+    for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+        if params.iter().all(|(name, _)| *name != key) {
+            serializer.append_pair(&key, &value);
+        }
+    }
+    for (key, value) in params {
+        if let Some(value) = value {
+            serializer.append_pair(key, value);
+        }
+    }
+
+    let query = serializer.finish();
+    if query.is_empty() {
+        uri.path().to_owned()
+    } else {
+        format!("{}?{query}", uri.path())
+    }
+}
@@ -3,6 +3,26 @@
 //! ```bash
 //! cargo run -p example-key-value-store
 //! ```
+//!
+//! `DATA_DIR`를 지정하면 변경 사항을 WAL에 기록하고 주기적으로 스냅샷을 남겨
+//! 재시작해도 데이터가 유지됩니다. (자세한 내용은 `persistence.rs`)
+//!
+//! ```bash
+//! DATA_DIR=data SNAPSHOT_INTERVAL_SECS=30 cargo run -p example-key-value-store
+//! ```
+//!
+//! `REPLICATE_FROM`을 지정하면 다른 인스턴스의 변경을 받아오는 읽기 전용 팔로워로 실행됩니다.
+//! (자세한 내용은 `replication.rs`)
+//!
+//! ```bash
+//! PORT=3001 REPLICATE_FROM=ws://127.0.0.1:3000/replication/stream cargo run -p example-key-value-store
+//! ```
+//!
+//! 타임아웃/과부하 에러는 `{"message": ..., "request_id": ...}` JSON으로 응답합니다.
+//! (common-errors의 `handle_middleware_error`)
+
+mod persistence;
+mod replication;
 
 use axum::{
     body::Bytes,                      // 요청/응답 바디의 바이너리
@@ -10,26 +30,33 @@ use axum::{
     extract::{DefaultBodyLimit, Path, State},
     handler::Handler, // .post_service() 사용을 위한 트레잇
     http::StatusCode,
-    response::IntoResponse,
-    routing::{delete, get},
+    routing::{delete, get, post},
     Router,
 };
+use example_common_errors::handle_middleware_error;
+use persistence::{Ack, Db, Mutation, Wal};
+use replication::{Follower, Replication};
 
 use std::{
-    borrow::Cow,
-    collections::HashMap,
+    net::SocketAddr,
     sync::{Arc, RwLock}, // 공유 상태를 위한 RwLock
     time::Duration,
 };
 
-use tower::{BoxError, ServiceBuilder};
+use tower::ServiceBuilder;
 use tower_http::{
-    compression::CompressionLayer, limit::RequestBodyLimitLayer, trace::TraceLayer,
+    compression::CompressionLayer,
+    limit::RequestBodyLimitLayer,
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::TraceLayer,
     validate_request::ValidateRequestHeaderLayer,
 };
 
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+// 관리자 API와 복제 스트림에 사용하는 Bearer 토큰
+const ADMIN_TOKEN: &str = "secret-token";
+
 #[tokio::main]
 async fn main() {
     // 🧭 main 함수: 서버 설정
@@ -42,10 +69,59 @@ async fn main() {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
-    let shared_state = SharedState::default();
+    // DATA_DIR가 있으면 디스크에서 복구, 없으면 기존처럼 메모리에만 저장
+    let shared_state = match std::env::var_os("DATA_DIR") {
+        Some(dir) => {
+            let (wal, db) = persistence::open(dir)
+                .await
+                .expect("failed to restore key-value store");
+            let state = Arc::new(RwLock::new(AppState {
+                db,
+                wal: Some(wal),
+                ..Default::default()
+            }));
+
+            let interval = std::env::var("SNAPSHOT_INTERVAL_SECS")
+                .ok()
+                .and_then(|secs| secs.parse().ok())
+                .map_or(Duration::from_secs(60), Duration::from_secs);
+            tokio::spawn(snapshot_periodically(Arc::clone(&state), interval));
+            state
+        }
+        None => SharedState::default(),
+    };
+
+    // REPLICATE_FROM이 있으면 팔로워로 실행 (리더의 변경을 받아 적용)
+    if let Ok(leader) = std::env::var("REPLICATE_FROM") {
+        shared_state.write().unwrap().replication =
+            Replication::Follower(Follower::new(leader.clone()));
+        tokio::spawn(replication::follow(Arc::clone(&shared_state), leader));
+    }
+
+    let port = std::env::var("PORT")
+        .ok()
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(3000);
+
+    // 🌐 hyper 로 서버 실행
+    let listener = tokio::net::TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], port)))
+        .await
+        .unwrap();
+
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
 
-    // 🧱 라우터 구성
-    let app = Router::new()
+    // 복제 스트림에서 팔로워 주소를 알 수 있도록 ConnectInfo 제공
+    axum::serve(
+        listener,
+        app(shared_state).into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
+}
+
+// 🧱 라우터 구성
+fn app(shared_state: SharedState) -> Router {
+    Router::new()
         // 키 경로에 대해 GET/POST 제공
         .route(
             "/{key}",
@@ -67,25 +143,20 @@ async fn main() {
         .route("/keys", get(list_keys))
         // 관리자 전용 경로 `/admin` 하위에 포함
         .nest("/admin", admin_routes())
+        // 복제 상태 조회 + 팔로워용 스트림
+        .merge(replication::router())
         // 전역 미들웨어
         .layer(
             ServiceBuilder::new()
-                .layer(HandleErrorLayer::new(handle_error)) // 미들웨어 에러 핸들링
+                .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid)) // 요청마다 x-request-id
+                .layer(PropagateRequestIdLayer::x_request_id()) // 에러 응답에도 같은 헤더
+                .layer(HandleErrorLayer::new(handle_middleware_error)) // 미들웨어 에러 핸들링 (JSON)
                 .load_shed() // 과부하 처리
                 .concurrency_limit(1024) // 동시 처리 제한
                 .timeout(Duration::from_secs(10)) // 요청당 10초 제한
                 .layer(TraceLayer::new_for_http()), // 요청 추적 로그
         )
-        .with_state(Arc::clone(&shared_state));
-
-    // 🌐 hyper 로 서버 실행
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
-        .await
-        .unwrap();
-
-    tracing::debug!("listening on {}", listener.local_addr().unwrap());
-
-    axum::serve(listener, app).await.unwrap();
+        .with_state(shared_state)
 }
 
 // 📦 상태 정의
@@ -93,10 +164,68 @@ type SharedState = Arc<RwLock<AppState>>;
 
 #[derive(Default)]
 struct AppState {
-    db: HashMap<String, Bytes>,
+    db: Db,
+    // 영속화를 켰을 때만 있음
+    wal: Option<Wal>,
+    replication: Replication,
 }
 
-/// 📩 핸들러 함수들
+impl AppState {
+    // 메모리에 적용하고 같은 락 안에서 WAL과 팔로워에게 넘김 (기록 순서 = 메모리 순서)
+    // 팔로워는 읽기 전용 (리더에게서 받은 변경만 적용)
+    fn apply(&mut self, mutation: Mutation) -> Result<Option<Ack>, StatusCode> {
+        let Replication::Leader(leader) = &mut self.replication else {
+            return Err(StatusCode::FORBIDDEN);
+        };
+        leader.record(&mutation);
+        mutation.apply(&mut self.db);
+        Ok(self.wal.as_ref().map(|wal| wal.append(mutation)))
+    }
+
+    // 상태를 통째로 교체 (팔로워는 스냅샷부터 다시 받음)
+    fn replace(&mut self, db: Db) -> Result<(), StatusCode> {
+        let Replication::Leader(leader) = &mut self.replication else {
+            return Err(StatusCode::FORBIDDEN);
+        };
+        leader.reset();
+        self.db = db;
+        Ok(())
+    }
+
+    // 락 안에서 현재 상태를 복사해 스냅샷 요청
+    fn snapshot(&self) -> Option<(usize, Ack)> {
+        let wal = self.wal.as_ref()?;
+        Some((self.db.len(), wal.snapshot(self.db.clone())))
+    }
+}
+
+// 디스크 기록이 끝날 때까지 기다림 (락을 놓은 뒤에 호출)
+async fn persisted(ack: Option<Ack>) -> Result<(), StatusCode> {
+    match ack {
+        Some(ack) => ack.wait().await.map_err(|err| {
+            tracing::error!(%err, "failed to persist mutation");
+            StatusCode::INTERNAL_SERVER_ERROR
+        }),
+        None => Ok(()),
+    }
+}
+
+// ⏱️ 주기적인 스냅샷 (WAL이 끝없이 길어지지 않도록)
+async fn snapshot_periodically(state: SharedState, period: Duration) {
+    let mut interval = tokio::time::interval(period);
+    interval.tick().await; // 첫 tick은 바로 끝나므로 건너뜀
+    loop {
+        interval.tick().await;
+        let Some((_, ack)) = state.read().unwrap().snapshot() else {
+            return;
+        };
+        if let Err(err) = ack.wait().await {
+            tracing::error!(%err, "periodic snapshot failed");
+        }
+    }
+}
+
+// 📩 핸들러 함수들
 
 // 🔍 GET /{key}
 async fn kv_get(
@@ -113,8 +242,13 @@ async fn kv_get(
 }
 
 // ✏️ POST /{key}
-async fn kv_set(Path(key): Path<String>, State(state): State<SharedState>, bytes: Bytes) {
-    state.write().unwrap().db.insert(key, bytes);
+async fn kv_set(
+    Path(key): Path<String>,
+    State(state): State<SharedState>,
+    bytes: Bytes,
+) -> Result<(), StatusCode> {
+    let ack = state.write().unwrap().apply(Mutation::Set(key, bytes))?;
+    persisted(ack).await
 }
 
 // 🗂️ GET /keys
@@ -129,37 +263,66 @@ async fn list_keys(State(state): State<SharedState>) -> String {
 
 // 🔐 관리자 API (/admin 하위)
 fn admin_routes() -> Router<SharedState> {
-    async fn delete_all_keys(State(state): State<SharedState>) {
-        state.write().unwrap().db.clear();
+    async fn delete_all_keys(State(state): State<SharedState>) -> Result<(), StatusCode> {
+        let ack = state.write().unwrap().apply(Mutation::Clear)?;
+        persisted(ack).await
     }
 
-    async fn remove_key(Path(key): Path<String>, State(state): State<SharedState>) {
-        state.write().unwrap().db.remove(&key);
+    async fn remove_key(
+        Path(key): Path<String>,
+        State(state): State<SharedState>,
+    ) -> Result<(), StatusCode> {
+        let ack = state.write().unwrap().apply(Mutation::Remove(key))?;
+        persisted(ack).await
     }
 
-    Router::new()
-        .route("/keys", delete(delete_all_keys)) // DELETE /admin/keys
-        .route("/key/{key}", delete(remove_key)) // DELETE /admin/key/{key}
-        .layer(ValidateRequestHeaderLayer::bearer("secret-token")) // Bearer 인증 적용
-}
+    // 지금 상태를 스냅샷으로 저장하고 WAL 비우기
+    async fn snapshot(State(state): State<SharedState>) -> Result<String, (StatusCode, String)> {
+        let (count, ack) = state.read().unwrap().snapshot().ok_or_else(disabled)?;
+        persisted(Some(ack))
+            .await
+            .map_err(|status| (status, "snapshot failed".to_owned()))?;
+        Ok(format!("snapshot saved ({count} keys)\n"))
+    }
+
+    // 마지막 스냅샷으로 되돌리기 (그 이후의 변경은 버림)
+    async fn restore(State(state): State<SharedState>) -> Result<String, (StatusCode, String)> {
+        let wal = state.read().unwrap().wal.clone().ok_or_else(disabled)?;
+        let db = wal.load_snapshot().await.map_err(|err| {
+            tracing::error!(%err, "failed to read snapshot");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to read snapshot".to_owned(),
+            )
+        })?;
 
-// 🚨 에러 핸들링
-async fn handle_error(error: BoxError) -> impl IntoResponse {
-    if error.is::<tower::timeout::error::Elapsed>() {
-        return (StatusCode::REQUEST_TIMEOUT, Cow::from("request timed out"));
+        // 되돌린 상태를 다시 스냅샷으로 남겨 WAL을 비움 (재시작해도 같은 상태)
+        let (count, ack) = {
+            let mut state = state.write().unwrap();
+            state
+                .replace(db)
+                .map_err(|status| (status, "followers are read-only\n".to_owned()))?;
+            state.snapshot().ok_or_else(disabled)?
+        };
+        persisted(Some(ack))
+            .await
+            .map_err(|status| (status, "restore failed".to_owned()))?;
+        Ok(format!("restored {count} keys from snapshot\n"))
     }
 
-    if error.is::<tower::load_shed::error::Overloaded>() {
-        return (
-            StatusCode::SERVICE_UNAVAILABLE,
-            Cow::from("service is overloaded, try again later"),
-        );
+    fn disabled() -> (StatusCode, String) {
+        (
+            StatusCode::CONFLICT,
+            "persistence is disabled (set DATA_DIR)\n".to_owned(),
+        )
     }
 
-    (
-        StatusCode::INTERNAL_SERVER_ERROR,
-        Cow::from(format!("Unhandled internal error: {error}")),
-    )
+    Router::new()
+        .route("/keys", delete(delete_all_keys)) // DELETE /admin/keys
+        .route("/key/{key}", delete(remove_key)) // DELETE /admin/key/{key}
+        .route("/snapshot", post(snapshot)) // POST /admin/snapshot
+        .route("/restore", post(restore)) // POST /admin/restore
+        .layer(ValidateRequestHeaderLayer::bearer(ADMIN_TOKEN)) // Bearer 인증 적용
 }
 
 // 🧪 요청 예시
@@ -176,3 +339,24 @@ async fn handle_error(error: BoxError) -> impl IntoResponse {
 // 관리자 - 모든 데이터 삭제
 // > curl -X DELETE http://localhost:3000/admin/keys \
 // >   -H "Authorization: Bearer secret-token"
+//
+// 💽 영속화 (DATA_DIR=data 로 실행했을 때)
+//
+// 스냅샷 저장 (이후 변경은 WAL에 쌓임)
+// > curl -X POST http://localhost:3000/admin/snapshot \
+// >   -H "Authorization: Bearer secret-token"
+//
+// 마지막 스냅샷으로 되돌리기
+// > curl -X POST http://localhost:3000/admin/restore \
+// >   -H "Authorization: Bearer secret-token"
+//
+// 🔁 복제 (리더 3000, 팔로워 3001)
+//
+// > PORT=3001 REPLICATE_FROM=ws://127.0.0.1:3000/replication/stream \
+// >   cargo run -p example-key-value-store
+// > curl -X POST http://localhost:3000/mykey -d 'replicated!'
+// > curl http://localhost:3001/mykey
+//
+// 복제 지연(lag) 확인
+// > curl http://localhost:3000/replication/status
+// > curl http://localhost:3001/replication/status
@@ -0,0 +1,378 @@
+//! 💽 WAL(write-ahead log) + 스냅샷으로 저장소를 디스크에 보존
+//!
+//! ```not_rust
+//! DATA_DIR/
+//! ├── snapshot.bin   (어느 시점의 전체 HashMap)
+//! └── wal.log        (그 이후의 변경 기록)
+//! ```
+//!
+//! - 시작할 때: 스냅샷을 읽고 WAL을 순서대로 다시 적용
+//! - 변경할 때: 메모리에 적용한 뒤 백그라운드 writer 태스크에 기록을 넘기고, fsync가 끝나면 응답
+//!   (동시에 들어온 기록은 한 번에 모아 쓰고 fsync도 한 번만 함 → group commit)
+//! - 스냅샷: 전체를 새 파일에 쓰고 rename한 뒤 WAL을 비움
+//!
+//! 메모리 적용과 writer로 넘기는 일을 **같은 락 안에서** 해야 WAL 순서가 메모리 순서와 같아짐
+//! (`append`/`snapshot`이 async가 아닌 이유)
+
+use axum::body::Bytes;
+use std::{
+    collections::HashMap,
+    io,
+    path::{Path, PathBuf},
+};
+use tokio::{
+    fs::{File, OpenOptions},
+    io::AsyncWriteExt,
+    sync::{mpsc, oneshot},
+};
+
+const SNAPSHOT_FILE: &str = "snapshot.bin";
+const WAL_FILE: &str = "wal.log";
+
+pub type Db = HashMap<String, Bytes>;
+
+/// 저장소 변경 하나 (WAL 레코드 하나)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mutation {
+    Set(String, Bytes),
+    Remove(String),
+    Clear,
+}
+
+impl Mutation {
+    pub fn apply(&self, db: &mut Db) {
+        match self {
+            Mutation::Set(key, value) => {
+                db.insert(key.clone(), value.clone());
+            }
+            Mutation::Remove(key) => {
+                db.remove(key);
+            }
+            Mutation::Clear => db.clear(),
+        }
+    }
+}
+
+enum Command {
+    Append(Mutation, oneshot::Sender<io::Result<()>>),
+    Snapshot(Db, oneshot::Sender<io::Result<()>>),
+}
+
+/// writer 태스크에 기록을 넘기는 핸들
+#[derive(Clone)]
+pub struct Wal {
+    dir: PathBuf,
+    tx: mpsc::UnboundedSender<Command>,
+}
+
+/// 디스크에 기록이 끝나면 완료되는 값
+pub struct Ack(oneshot::Receiver<io::Result<()>>);
+
+impl Ack {
+    pub async fn wait(self) -> io::Result<()> {
+        self.0
+            .await
+            .unwrap_or_else(|_| Err(io::Error::other("WAL writer stopped")))
+    }
+}
+
+/// 디렉토리에서 상태를 복구하고 writer 태스크 시작
+pub async fn open(dir: impl Into<PathBuf>) -> io::Result<(Wal, Db)> {
+    let dir = dir.into();
+    tokio::fs::create_dir_all(&dir).await?;
+
+    let mut db = load_snapshot(&dir).await?;
+
+    let wal_path = dir.join(WAL_FILE);
+    let bytes = match tokio::fs::read(&wal_path).await {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(err) => return Err(err),
+    };
+    let (mutations, valid_len) = decode(&bytes)?;
+    for mutation in &mutations {
+        mutation.apply(&mut db);
+    }
+    tracing::info!(
+        keys = db.len(),
+        replayed = mutations.len(),
+        "restored state from {}",
+        dir.display()
+    );
+
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&wal_path)
+        .await?;
+    // 쓰다가 죽어서 잘린 마지막 레코드는 버림 (뒤에 새 기록을 이어 붙여야 하므로)
+    if valid_len < bytes.len() {
+        tracing::warn!(
+            discarded = bytes.len() - valid_len,
+            "discarding torn record at the end of the WAL"
+        );
+        file.set_len(valid_len as u64).await?;
+    }
+
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(run_writer(dir.clone(), file, rx));
+
+    Ok((Wal { dir, tx }, db))
+}
+
+impl Wal {
+    /// 변경 기록 (메모리에 적용할 때 잡은 락 안에서 호출)
+    pub fn append(&self, mutation: Mutation) -> Ack {
+        let (ack, rx) = oneshot::channel();
+        let _ = self.tx.send(Command::Append(mutation, ack));
+        Ack(rx)
+    }
+
+    /// 전체 상태를 스냅샷으로 저장하고 WAL 비우기 (락 안에서 복사한 상태를 넘김)
+    pub fn snapshot(&self, db: Db) -> Ack {
+        let (ack, rx) = oneshot::channel();
+        let _ = self.tx.send(Command::Snapshot(db, ack));
+        Ack(rx)
+    }
+
+    /// 마지막 스냅샷 읽기 (WAL은 적용하지 않음)
+    pub async fn load_snapshot(&self) -> io::Result<Db> {
+        load_snapshot(&self.dir).await
+    }
+}
+
+async fn load_snapshot(dir: &Path) -> io::Result<Db> {
+    let bytes = match tokio::fs::read(dir.join(SNAPSHOT_FILE)).await {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Db::new()),
+        Err(err) => return Err(err),
+    };
+    // 스냅샷은 rename으로 한 번에 바뀌므로 잘린 파일이면 손상된 것
+    let (mutations, valid_len) = decode(&bytes)?;
+    if valid_len < bytes.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "snapshot is truncated",
+        ));
+    }
+
+    let mut db = Db::new();
+    for mutation in &mutations {
+        mutation.apply(&mut db);
+    }
+    Ok(db)
+}
+
+// ✍️ writer 태스크: 채널에 쌓인 기록을 모아서 쓰고 fsync 한 번
+async fn run_writer(dir: PathBuf, mut wal: File, mut rx: mpsc::UnboundedReceiver<Command>) {
+    let mut buf = Vec::new();
+    let mut pending = Vec::new();
+
+    while let Some(first) = rx.recv().await {
+        let mut command = Some(first);
+        while let Some(current) = command.take().or_else(|| rx.try_recv().ok()) {
+            match current {
+                Command::Append(mutation, ack) => {
+                    encode(&mutation, &mut buf);
+                    pending.push(ack);
+                }
+                Command::Snapshot(db, ack) => {
+                    // 스냅샷 전에 들어온 기록부터 먼저 마무리
+                    let result = flush(&mut wal, &mut buf).await;
+                    acknowledge(&mut pending, &result);
+                    let _ = ack.send(write_snapshot(&dir, &db, &wal).await);
+                }
+            }
+        }
+
+        let result = flush(&mut wal, &mut buf).await;
+        if let Err(err) = &result {
+            tracing::error!(%err, "failed to write WAL");
+        }
+        acknowledge(&mut pending, &result);
+    }
+}
+
+async fn flush(wal: &mut File, buf: &mut Vec<u8>) -> io::Result<()> {
+    if buf.is_empty() {
+        return Ok(());
+    }
+    let result = async {
+        wal.write_all(buf).await?;
+        wal.sync_data().await
+    }
+    .await;
+    buf.clear();
+    result
+}
+
+fn acknowledge(pending: &mut Vec<oneshot::Sender<io::Result<()>>>, result: &io::Result<()>) {
+    for ack in pending.drain(..) {
+        let _ = ack.send(match result {
+            Ok(()) => Ok(()),
+            Err(err) => Err(io::Error::new(err.kind(), err.to_string())),
+        });
+    }
+}
+
+// 임시 파일에 쓰고 rename → WAL 비우기
+// rename 직후 WAL을 비우기 전에 죽어도 괜찮음: WAL의 변경은 모두 덮어쓰기라 스냅샷 위에 다시 적용해도 결과가 같음
+async fn write_snapshot(dir: &Path, db: &Db, wal: &File) -> io::Result<()> {
+    let mut buf = Vec::new();
+    for (key, value) in db {
+        encode(&Mutation::Set(key.clone(), value.clone()), &mut buf);
+    }
+
+    let tmp = dir.join(format!("{SNAPSHOT_FILE}.tmp"));
+    let mut file = File::create(&tmp).await?;
+    file.write_all(&buf).await?;
+    file.sync_all().await?;
+    tokio::fs::rename(&tmp, dir.join(SNAPSHOT_FILE)).await?;
+
+    wal.set_len(0).await?;
+    wal.sync_all().await?;
+    tracing::info!(keys = db.len(), "snapshot written");
+    Ok(())
+}
+
+// 📄 레코드 형식 (길이는 u32 little endian)
+// Set:    0 | key 길이 | key | value 길이 | value
+// Remove: 1 | key 길이 | key
+// Clear:  2
+const SET: u8 = 0;
+const REMOVE: u8 = 1;
+const CLEAR: u8 = 2;
+
+pub fn encode(mutation: &Mutation, buf: &mut Vec<u8>) {
+    fn put(buf: &mut Vec<u8>, bytes: &[u8]) {
+        buf.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        buf.extend_from_slice(bytes);
+    }
+
+    match mutation {
+        Mutation::Set(key, value) => {
+            buf.push(SET);
+            put(buf, key.as_bytes());
+            put(buf, value);
+        }
+        Mutation::Remove(key) => {
+            buf.push(REMOVE);
+            put(buf, key.as_bytes());
+        }
+        Mutation::Clear => buf.push(CLEAR),
+    }
+}
+
+/// 레코드 목록과 온전한 레코드가 끝나는 위치 반환 (마지막 레코드가 잘렸으면 그 앞까지)
+pub fn decode(bytes: &[u8]) -> io::Result<(Vec<Mutation>, usize)> {
+    fn take<'a>(bytes: &'a [u8], pos: &mut usize) -> Option<&'a [u8]> {
+        let len = bytes.get(*pos..*pos + 4)?;
+        let len = u32::from_le_bytes(len.try_into().unwrap()) as usize;
+        let value = bytes.get(*pos + 4..*pos + 4 + len)?;
+        *pos += 4 + len;
+        Some(value)
+    }
+
+    fn key(bytes: &[u8]) -> io::Result<String> {
+        String::from_utf8(bytes.to_vec())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    let mut mutations = Vec::new();
+    let mut valid = 0;
+    while valid < bytes.len() {
+        let mut pos = valid + 1;
+        let mutation = match bytes[valid] {
+            SET => {
+                let (Some(k), Some(v)) = (take(bytes, &mut pos), take(bytes, &mut pos)) else {
+                    break;
+                };
+                Mutation::Set(key(k)?, Bytes::copy_from_slice(v))
+            }
+            REMOVE => {
+                let Some(k) = take(bytes, &mut pos) else {
+                    break;
+                };
+                Mutation::Remove(key(k)?)
+            }
+            CLEAR => Mutation::Clear,
+            tag => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("unknown WAL record type {tag} at offset {valid}"),
+                ))
+            }
+        };
+        mutations.push(mutation);
+        valid = pos;
+    }
+    Ok((mutations, valid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(key: &str, value: &str) -> Mutation {
+        Mutation::Set(key.to_owned(), Bytes::copy_from_slice(value.as_bytes()))
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("example-kv-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn records_round_trip_and_ignore_torn_tail() {
+        let mutations = vec![
+            set("a", "1"),
+            Mutation::Remove("a".to_owned()),
+            Mutation::Clear,
+            set("b", ""),
+        ];
+        let mut buf = Vec::new();
+        for mutation in &mutations {
+            encode(mutation, &mut buf);
+        }
+        assert_eq!(decode(&buf).unwrap(), (mutations.clone(), buf.len()));
+
+        // 마지막 레코드를 쓰다 만 상태
+        let complete = buf.len();
+        encode(&set("c", "hello"), &mut buf);
+        buf.truncate(buf.len() - 2);
+        assert_eq!(decode(&buf).unwrap(), (mutations, complete));
+
+        assert!(decode(&[9]).is_err());
+    }
+
+    #[tokio::test]
+    async fn state_survives_reopen_and_snapshot() {
+        let dir = temp_dir("reopen");
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+
+        let (wal, mut db) = open(&dir).await.unwrap();
+        assert!(db.is_empty());
+        for mutation in [set("a", "1"), set("b", "2")] {
+            mutation.apply(&mut db);
+            wal.append(mutation).wait().await.unwrap();
+        }
+        wal.snapshot(db.clone()).wait().await.unwrap();
+        assert_eq!(
+            tokio::fs::metadata(dir.join(WAL_FILE)).await.unwrap().len(),
+            0
+        );
+
+        // 스냅샷 이후의 변경은 WAL에만 있음
+        for mutation in [Mutation::Remove("a".to_owned()), set("c", "3")] {
+            mutation.apply(&mut db);
+            wal.append(mutation).wait().await.unwrap();
+        }
+        assert_eq!(wal.load_snapshot().await.unwrap().len(), 2);
+
+        let (_, reopened) = open(&dir).await.unwrap();
+        assert_eq!(reopened, db);
+        assert_eq!(reopened.get("c").unwrap().as_ref(), b"3");
+        assert!(!reopened.contains_key("a"));
+
+        tokio::fs::remove_dir_all(dir).await.unwrap();
+    }
+}
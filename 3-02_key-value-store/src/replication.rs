@@ -0,0 +1,716 @@
+//! 🔁 리더 → 팔로워 복제 (WebSocket)
+//!
+//! ```bash
+//! # 리더 (기본)
+//! cargo run -p example-key-value-store
+//! # 팔로워 (읽기 전용)
+//! PORT=3001 REPLICATE_FROM=ws://127.0.0.1:3000/replication/stream cargo run -p example-key-value-store
+//! ```
+//!
+//! - 리더는 모든 변경에 순서 번호(seq)를 붙이고 최근 변경을 backlog에 보관
+//! - 팔로워는 `?epoch=..&from=..`(마지막으로 적용한 위치)로 접속
+//!   - backlog에 그 이후 변경이 모두 남아 있으면 → 빠진 변경만 보냄
+//!   - 리더가 재시작했거나(epoch 다름) backlog가 모자라면 → 전체 스냅샷부터 보냄
+//! - 이후에는 변경을 실시간으로 보내고, 1초마다 heartbeat로 리더의 현재 seq를 알림
+//! - 팔로워는 적용한 seq를 ack로 돌려줌 → 리더/팔로워 모두 `GET /replication/status`에서 지연(lag) 확인
+//!
+//! 연결이 끊기면 팔로워가 1초 뒤 다시 접속해 끊긴 위치부터 이어받음
+
+use axum::{
+    body::Bytes,
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        ConnectInfo, Query, State,
+    },
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    BoxError, Json, Router,
+};
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::{self, client::IntoClientRequest};
+use tower_http::validate_request::ValidateRequestHeaderLayer;
+
+use crate::{
+    persistence::{self, Db, Mutation},
+    AppState, SharedState, ADMIN_TOKEN,
+};
+
+// 재접속한 팔로워에게 스냅샷 없이 보내줄 수 있는 최근 변경 수
+const BACKLOG_CAPACITY: usize = 10_000;
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+pub enum Replication {
+    Leader(Leader),
+    Follower(Follower),
+}
+
+impl Default for Replication {
+    fn default() -> Self {
+        Replication::Leader(Leader::new())
+    }
+}
+
+// 📣 리더 쪽 상태 (AppState 락 안에서만 변경)
+pub struct Leader {
+    // 프로세스마다 다른 값 → 팔로워가 예전 리더의 seq로 이어받지 않도록
+    epoch: u64,
+    seq: u64,
+    backlog: VecDeque<(u64, Mutation)>,
+    events: broadcast::Sender<Event>,
+    followers: Followers,
+}
+
+#[derive(Debug, Clone)]
+enum Event {
+    Mutation(u64, Mutation),
+    // 상태를 통째로 바꿈 (restore) → 팔로워는 스냅샷부터 다시
+    Reset(u64),
+}
+
+impl Leader {
+    fn new() -> Self {
+        let epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(1, |duration| duration.as_nanos() as u64);
+        Self {
+            epoch,
+            seq: 0,
+            backlog: VecDeque::new(),
+            events: broadcast::channel(1024).0,
+            followers: Followers::default(),
+        }
+    }
+
+    pub fn record(&mut self, mutation: &Mutation) {
+        self.seq += 1;
+        if self.backlog.len() == BACKLOG_CAPACITY {
+            self.backlog.pop_front();
+        }
+        self.backlog.push_back((self.seq, mutation.clone()));
+        let _ = self
+            .events
+            .send(Event::Mutation(self.seq, mutation.clone()));
+    }
+
+    pub fn reset(&mut self) {
+        self.seq += 1;
+        self.backlog.clear();
+        let _ = self.events.send(Event::Reset(self.seq));
+    }
+
+    // `from` 이후의 변경이 backlog에 모두 남아 있을 때만 Some
+    fn backlog_since(&self, from: u64) -> Option<Vec<(u64, Mutation)>> {
+        if from == self.seq {
+            return Some(Vec::new());
+        }
+        let (first, _) = self.backlog.front()?;
+        if from > self.seq || *first > from + 1 {
+            return None;
+        }
+        Some(
+            self.backlog
+                .iter()
+                .filter(|(seq, _)| *seq > from)
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+// 📥 팔로워 쪽 상태
+pub struct Follower {
+    leader: String,
+    connected: bool,
+    epoch: u64,
+    applied_seq: u64,
+    leader_seq: u64,
+    full_syncs: u64,
+    last_contact: Option<Instant>,
+}
+
+impl Follower {
+    pub fn new(leader: String) -> Self {
+        Self {
+            leader,
+            connected: false,
+            epoch: 0,
+            applied_seq: 0,
+            leader_seq: 0,
+            full_syncs: 0,
+            last_contact: None,
+        }
+    }
+}
+
+// 접속 중인 팔로워 목록 (리더의 스트림 태스크가 ack를 받을 때마다 갱신)
+#[derive(Clone, Default)]
+struct Followers(Arc<Mutex<HashMap<u64, FollowerInfo>>>);
+
+struct FollowerInfo {
+    addr: SocketAddr,
+    acked_seq: u64,
+    connected_at: Instant,
+}
+
+impl Followers {
+    fn register(&self, addr: SocketAddr) -> u64 {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        self.0.lock().unwrap().insert(
+            id,
+            FollowerInfo {
+                addr,
+                acked_seq: 0,
+                connected_at: Instant::now(),
+            },
+        );
+        id
+    }
+
+    fn ack(&self, id: u64, seq: u64) {
+        if let Some(info) = self.0.lock().unwrap().get_mut(&id) {
+            info.acked_seq = seq;
+        }
+    }
+
+    fn remove(&self, id: u64) {
+        self.0.lock().unwrap().remove(&id);
+    }
+}
+
+pub fn router() -> Router<SharedState> {
+    Router::new()
+        .route("/replication/status", get(status))
+        .route(
+            "/replication/stream",
+            get(stream).layer(ValidateRequestHeaderLayer::bearer(ADMIN_TOKEN)),
+        )
+}
+
+// 📊 GET /replication/status
+#[derive(Debug, Serialize)]
+#[serde(tag = "role", rename_all = "snake_case")]
+enum Status {
+    Leader {
+        epoch: u64,
+        seq: u64,
+        followers: Vec<FollowerStatus>,
+    },
+    Follower {
+        leader: String,
+        connected: bool,
+        epoch: u64,
+        applied_seq: u64,
+        leader_seq: u64,
+        lag: u64,
+        full_syncs: u64,
+        last_contact_ms: Option<u128>,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct FollowerStatus {
+    id: u64,
+    addr: SocketAddr,
+    acked_seq: u64,
+    lag: u64,
+    connected_secs: u64,
+}
+
+async fn status(State(state): State<SharedState>) -> Json<Status> {
+    let state = state.read().unwrap();
+    Json(match &state.replication {
+        Replication::Leader(leader) => {
+            let mut followers: Vec<_> = leader
+                .followers
+                .0
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(id, info)| FollowerStatus {
+                    id: *id,
+                    addr: info.addr,
+                    acked_seq: info.acked_seq,
+                    lag: leader.seq.saturating_sub(info.acked_seq),
+                    connected_secs: info.connected_at.elapsed().as_secs(),
+                })
+                .collect();
+            followers.sort_by_key(|follower| follower.id);
+            Status::Leader {
+                epoch: leader.epoch,
+                seq: leader.seq,
+                followers,
+            }
+        }
+        Replication::Follower(follower) => Status::Follower {
+            leader: follower.leader.clone(),
+            connected: follower.connected,
+            epoch: follower.epoch,
+            applied_seq: follower.applied_seq,
+            leader_seq: follower.leader_seq,
+            lag: follower.leader_seq.saturating_sub(follower.applied_seq),
+            full_syncs: follower.full_syncs,
+            last_contact_ms: follower.last_contact.map(|at| at.elapsed().as_millis()),
+        },
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamParams {
+    #[serde(default)]
+    epoch: u64,
+    #[serde(default)]
+    from: u64,
+}
+
+// 📤 GET /replication/stream (리더)
+async fn stream(
+    ws: WebSocketUpgrade,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Query(params): Query<StreamParams>,
+    State(state): State<SharedState>,
+) -> Response {
+    // 따라잡기용 상태를 읽기 전에 먼저 구독 → 그 사이에 생긴 변경도 놓치지 않음
+    let (events, followers) = match &state.read().unwrap().replication {
+        Replication::Leader(leader) => (leader.events.subscribe(), leader.followers.clone()),
+        Replication::Follower(_) => {
+            return (StatusCode::CONFLICT, "not a leader").into_response();
+        }
+    };
+
+    ws.on_upgrade(move |socket| async move {
+        let id = followers.register(addr);
+        tracing::info!(%addr, params.epoch, params.from, "follower connected");
+        if let Err(err) = serve_follower(socket, params, &state, events, &followers, id).await {
+            tracing::debug!(%addr, %err, "replication stream error");
+        }
+        followers.remove(id);
+        tracing::info!(%addr, "follower disconnected");
+    })
+}
+
+async fn serve_follower(
+    mut socket: WebSocket,
+    params: StreamParams,
+    state: &SharedState,
+    mut events: broadcast::Receiver<Event>,
+    followers: &Followers,
+    id: u64,
+) -> Result<(), axum::Error> {
+    // 1. 따라잡기: backlog 또는 전체 스냅샷
+    let (mut last_sent, frames) = catch_up(state, &params);
+    for frame in frames {
+        socket.send(frame.into_message()).await?;
+    }
+
+    // 2. 실시간 전송
+    let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                let frame = match event {
+                    // 따라잡기에서 이미 보낸 변경은 건너뜀
+                    Ok(Event::Mutation(seq, _) | Event::Reset(seq)) if seq <= last_sent => continue,
+                    Ok(Event::Mutation(seq, mutation)) => {
+                        last_sent = seq;
+                        Frame::Mutation(seq, mutation)
+                    }
+                    // 팔로워가 너무 느려 채널에서 밀려난 경우도 스냅샷부터 다시
+                    Ok(Event::Reset(_)) | Err(broadcast::error::RecvError::Lagged(_)) => {
+                        let (seq, frame) = snapshot_frame(&state.read().unwrap());
+                        last_sent = seq;
+                        frame
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return Ok(()),
+                };
+                socket.send(frame.into_message()).await?;
+            }
+            _ = heartbeat.tick() => {
+                let seq = match &state.read().unwrap().replication {
+                    Replication::Leader(leader) => leader.seq,
+                    Replication::Follower(_) => return Ok(()),
+                };
+                socket.send(Frame::Heartbeat(seq).into_message()).await?;
+            }
+            message = socket.recv() => match message {
+                Some(Ok(Message::Binary(bytes))) => {
+                    if let Ok(Frame::Ack(seq)) = Frame::decode(&bytes) {
+                        followers.ack(id, seq);
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None => return Ok(()),
+                Some(Ok(_)) => {}
+                Some(Err(err)) => return Err(err),
+            },
+        }
+    }
+}
+
+fn catch_up(state: &SharedState, params: &StreamParams) -> (u64, Vec<Frame>) {
+    let state = state.read().unwrap();
+    if let Replication::Leader(leader) = &state.replication {
+        if params.epoch == leader.epoch {
+            if let Some(entries) = leader.backlog_since(params.from) {
+                let frames = entries
+                    .into_iter()
+                    .map(|(seq, mutation)| Frame::Mutation(seq, mutation))
+                    .collect();
+                return (leader.seq, frames);
+            }
+        }
+    }
+    let (seq, frame) = snapshot_frame(&state);
+    (seq, vec![frame])
+}
+
+// 락 안에서 seq와 데이터를 함께 읽음 → 스냅샷에는 정확히 seq까지의 변경이 들어 있음
+fn snapshot_frame(state: &AppState) -> (u64, Frame) {
+    let (epoch, seq) = match &state.replication {
+        Replication::Leader(leader) => (leader.epoch, leader.seq),
+        Replication::Follower(_) => (0, 0),
+    };
+    let frame = Frame::Snapshot {
+        epoch,
+        seq,
+        db: state.db.clone(),
+    };
+    (seq, frame)
+}
+
+/// 📥 팔로워: 리더에 접속해 변경을 받아 적용 (끊기면 다시 접속)
+pub async fn follow(state: SharedState, url: String) {
+    loop {
+        match follow_once(&state, &url).await {
+            Ok(()) => tracing::warn!("replication stream closed by leader"),
+            Err(err) => tracing::warn!(%err, "replication stream failed"),
+        }
+        if let Replication::Follower(follower) = &mut state.write().unwrap().replication {
+            follower.connected = false;
+        }
+        tokio::time::sleep(RETRY_DELAY).await;
+    }
+}
+
+async fn follow_once(state: &SharedState, url: &str) -> Result<(), BoxError> {
+    let (epoch, from) = match &state.read().unwrap().replication {
+        Replication::Follower(follower) => (follower.epoch, follower.applied_seq),
+        Replication::Leader(_) => return Err("not a follower".into()),
+    };
+
+    let mut request = format!("{url}?epoch={epoch}&from={from}").into_client_request()?;
+    request
+        .headers_mut()
+        .insert("authorization", format!("Bearer {ADMIN_TOKEN}").parse()?);
+    let (mut socket, _) = tokio_tungstenite::connect_async(request).await?;
+    tracing::info!(url, epoch, from, "connected to leader");
+
+    while let Some(message) = socket.next().await {
+        let tungstenite::Message::Binary(bytes) = message? else {
+            continue;
+        };
+        let frame = Frame::decode(&bytes)?;
+
+        let (applied, ack) = {
+            let mut state = state.write().unwrap();
+            apply_frame(&mut state, frame)?
+        };
+        // 팔로워 디스크에 기록된 뒤에 ack (DATA_DIR를 쓰는 경우)
+        if let Some(seq) = applied {
+            if let Some(ack) = ack {
+                ack.wait().await?;
+            }
+            socket.send(Frame::Ack(seq).into_ws_message()).await?;
+        }
+    }
+    Ok(())
+}
+
+// 받은 프레임을 팔로워 상태에 적용 → (ack로 보낼 seq, WAL 기록 완료 신호)
+fn apply_frame(
+    state: &mut AppState,
+    frame: Frame,
+) -> io::Result<(Option<u64>, Option<persistence::Ack>)> {
+    let AppState {
+        db,
+        wal,
+        replication,
+    } = state;
+    let Replication::Follower(follower) = replication else {
+        return Err(io::Error::other("not a follower"));
+    };
+    follower.connected = true;
+    follower.last_contact = Some(Instant::now());
+
+    match frame {
+        Frame::Snapshot {
+            epoch,
+            seq,
+            db: snapshot,
+        } => {
+            *db = snapshot;
+            follower.epoch = epoch;
+            follower.applied_seq = seq;
+            follower.leader_seq = follower.leader_seq.max(seq);
+            follower.full_syncs += 1;
+            Ok((Some(seq), wal.as_ref().map(|wal| wal.snapshot(db.clone()))))
+        }
+        Frame::Mutation(seq, mutation) => {
+            // 순서가 어긋나면 연결을 끊고 다시 따라잡기
+            if seq != follower.applied_seq + 1 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("expected seq {}, got {seq}", follower.applied_seq + 1),
+                ));
+            }
+            mutation.apply(db);
+            follower.applied_seq = seq;
+            follower.leader_seq = follower.leader_seq.max(seq);
+            Ok((Some(seq), wal.as_ref().map(|wal| wal.append(mutation))))
+        }
+        Frame::Heartbeat(seq) => {
+            follower.leader_seq = seq;
+            Ok((None, None))
+        }
+        Frame::Ack(_) => Ok((None, None)),
+    }
+}
+
+// 📦 프레임 형식 (바이너리 WebSocket 메시지, 숫자는 u64 little endian)
+// Mutation:  0 | seq | WAL 레코드
+// Snapshot:  1 | epoch | seq | WAL 레코드(Set)...
+// Heartbeat: 2 | seq
+// Ack:       3 | seq           (팔로워 → 리더)
+#[derive(Debug, PartialEq, Eq)]
+enum Frame {
+    Mutation(u64, Mutation),
+    Snapshot { epoch: u64, seq: u64, db: Db },
+    Heartbeat(u64),
+    Ack(u64),
+}
+
+impl Frame {
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        match self {
+            Frame::Mutation(seq, mutation) => {
+                buf.push(0);
+                buf.extend_from_slice(&seq.to_le_bytes());
+                persistence::encode(mutation, &mut buf);
+            }
+            Frame::Snapshot { epoch, seq, db } => {
+                buf.push(1);
+                buf.extend_from_slice(&epoch.to_le_bytes());
+                buf.extend_from_slice(&seq.to_le_bytes());
+                for (key, value) in db {
+                    persistence::encode(&Mutation::Set(key.clone(), value.clone()), &mut buf);
+                }
+            }
+            Frame::Heartbeat(seq) => {
+                buf.push(2);
+                buf.extend_from_slice(&seq.to_le_bytes());
+            }
+            Frame::Ack(seq) => {
+                buf.push(3);
+                buf.extend_from_slice(&seq.to_le_bytes());
+            }
+        }
+        buf
+    }
+
+    fn decode(bytes: &[u8]) -> io::Result<Self> {
+        fn invalid() -> io::Error {
+            io::Error::new(io::ErrorKind::InvalidData, "invalid replication frame")
+        }
+
+        fn u64_at(bytes: &[u8], pos: usize) -> io::Result<u64> {
+            let bytes = bytes.get(pos..pos + 8).ok_or_else(invalid)?;
+            Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+        }
+
+        // 프레임 안의 레코드는 잘려 있으면 안 됨
+        fn records(bytes: &[u8]) -> io::Result<Vec<Mutation>> {
+            let (mutations, valid) = persistence::decode(bytes)?;
+            if valid == bytes.len() {
+                Ok(mutations)
+            } else {
+                Err(invalid())
+            }
+        }
+
+        match bytes.first().ok_or_else(invalid)? {
+            0 => {
+                let seq = u64_at(bytes, 1)?;
+                let mut mutations = records(&bytes[9..])?;
+                if mutations.len() != 1 {
+                    return Err(invalid());
+                }
+                Ok(Frame::Mutation(seq, mutations.remove(0)))
+            }
+            1 => {
+                let epoch = u64_at(bytes, 1)?;
+                let seq = u64_at(bytes, 9)?;
+                let mut db = Db::new();
+                for mutation in records(&bytes[17..])? {
+                    mutation.apply(&mut db);
+                }
+                Ok(Frame::Snapshot { epoch, seq, db })
+            }
+            2 => Ok(Frame::Heartbeat(u64_at(bytes, 1)?)),
+            3 => Ok(Frame::Ack(u64_at(bytes, 1)?)),
+            _ => Err(invalid()),
+        }
+    }
+
+    fn into_message(self) -> Message {
+        Message::Binary(Bytes::from(self.encode()))
+    }
+
+    fn into_ws_message(self) -> tungstenite::Message {
+        tungstenite::Message::Binary(Bytes::from(self.encode()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::RwLock;
+
+    fn set(key: &str, value: &str) -> Mutation {
+        Mutation::Set(key.to_owned(), Bytes::copy_from_slice(value.as_bytes()))
+    }
+
+    #[test]
+    fn frames_round_trip() {
+        let db = Db::from([
+            ("a".to_owned(), Bytes::from_static(b"1")),
+            ("b".to_owned(), Bytes::from_static(b"\0\xff")),
+        ]);
+        for frame in [
+            Frame::Mutation(7, set("a", "1")),
+            Frame::Mutation(8, Mutation::Clear),
+            Frame::Snapshot {
+                epoch: 42,
+                seq: 8,
+                db,
+            },
+            Frame::Heartbeat(9),
+            Frame::Ack(9),
+        ] {
+            assert_eq!(Frame::decode(&frame.encode()).unwrap(), frame);
+        }
+
+        assert!(Frame::decode(&[]).is_err());
+        assert!(Frame::decode(&[0, 1, 2]).is_err());
+        assert!(Frame::decode(&[9; 9]).is_err());
+    }
+
+    #[test]
+    fn backlog_catch_up_window() {
+        let mut leader = Leader::new();
+        for i in 0..5 {
+            leader.record(&set("key", &i.to_string()));
+        }
+        assert_eq!(leader.backlog_since(5).unwrap().len(), 0);
+        assert_eq!(
+            leader
+                .backlog_since(2)
+                .unwrap()
+                .iter()
+                .map(|(seq, _)| *seq)
+                .collect::<Vec<_>>(),
+            [3, 4, 5]
+        );
+        // 리더보다 앞선 위치는 있을 수 없음 → 스냅샷
+        assert!(leader.backlog_since(6).is_none());
+
+        // restore 이후에는 스냅샷부터
+        leader.reset();
+        assert!(leader.backlog_since(5).is_none());
+    }
+
+    async fn spawn_leader(state: SharedState) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(
+                listener,
+                crate::app(state).into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+            .unwrap();
+        });
+        format!("ws://{addr}/replication/stream")
+    }
+
+    fn write(leader: &SharedState, mutation: Mutation) {
+        assert!(leader.write().unwrap().apply(mutation).unwrap().is_none());
+    }
+
+    // 팔로워가 리더를 따라잡을 때까지 기다리고 (적용한 seq, 전체 동기화 횟수) 반환
+    async fn caught_up(leader: &SharedState, follower: &SharedState) -> (u64, u64) {
+        for _ in 0..500 {
+            {
+                let leader = leader.read().unwrap();
+                let follower = follower.read().unwrap();
+                let (Replication::Leader(l), Replication::Follower(f)) =
+                    (&leader.replication, &follower.replication)
+                else {
+                    unreachable!()
+                };
+                if f.applied_seq == l.seq && f.epoch == l.epoch {
+                    assert_eq!(follower.db, leader.db);
+                    return (f.applied_seq, f.full_syncs);
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("follower did not catch up");
+    }
+
+    #[tokio::test]
+    async fn follower_syncs_streams_and_catches_up_after_reconnect() {
+        let leader = SharedState::default();
+        let url = spawn_leader(leader.clone()).await;
+
+        // 접속 전에 있던 데이터는 스냅샷으로 받음
+        write(&leader, set("a", "1"));
+        let follower: SharedState = Arc::new(RwLock::new(AppState {
+            replication: Replication::Follower(Follower::new(url.clone())),
+            ..Default::default()
+        }));
+        let task = tokio::spawn(follow(follower.clone(), url.clone()));
+        assert_eq!(caught_up(&leader, &follower).await, (1, 1));
+
+        // 실시간 전송
+        write(&leader, set("b", "2"));
+        assert_eq!(caught_up(&leader, &follower).await, (2, 1));
+
+        // 끊긴 동안의 변경은 재접속 후 backlog로 받음 (스냅샷 없이)
+        task.abort();
+        write(&leader, set("c", "3"));
+        write(&leader, Mutation::Remove("a".to_owned()));
+        let task = tokio::spawn(follow(follower.clone(), url));
+        assert_eq!(caught_up(&leader, &follower).await, (4, 1));
+
+        // 팔로워는 읽기 전용
+        assert_eq!(
+            follower.write().unwrap().apply(set("x", "1")).err(),
+            Some(StatusCode::FORBIDDEN)
+        );
+        task.abort();
+    }
+}
@@ -4,21 +4,30 @@
 //! GET  /read/{id} → 회원 조회
 //! PUT  /update   → 회원 수정
 //! DELETE /delete/{id} → 회원 삭제
+//! GET  /members/search?q=... → 이름 텍스트 검색 (text index)
+//! GET  /members/stats → 활성/비활성 회원 수 (aggregation pipeline)
+//! GET  /members/watch → 회원 변경 사항(insert/update/delete)을 SSE로 전달 (change stream)
+//!
+//! change stream은 replica set(또는 sharded cluster)에서만 동작함
 
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
     routing::{delete, get, post, put},
     Json, Router,
 };
 
+use futures::{Stream, StreamExt, TryStreamExt};
 use mongodb::{
-    bson::doc,
-    results::{DeleteResult, InsertOneResult, UpdateResult},
-    Client, Collection,
+    bson::{doc, Bson, Document},
+    change_stream::event::{ChangeStreamEvent, OperationType, ResumeToken},
+    options::{FullDocumentType, IndexOptions},
+    Client, Collection, IndexModel,
 };
 
 use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
 use tower_http::trace::TraceLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -43,6 +52,9 @@ async fn main() {
 
     println!("Pinged your database. Successfully connected to MongoDB!");
 
+    // 검색에 필요한 인덱스 생성 (이미 있으면 아무 일도 일어나지 않음)
+    create_indexes(&members_collection(&client)).await.unwrap();
+
     // 📋 로깅 미들웨어 설정
     tracing_subscriber::registry()
         .with(
@@ -63,69 +75,246 @@ async fn main() {
     axum::serve(listener, app(client)).await.unwrap();
 }
 
+// members 컬렉션 선택
+fn members_collection(client: &Client) -> Collection<Member> {
+    client.database("axum-mongo").collection("members")
+}
+
+// 🗂️ 시작 시 인덱스 생성
+// `$text` 검색은 text index가 있어야만 동작함 (컬렉션당 text index는 1개)
+async fn create_indexes(collection: &Collection<Member>) -> mongodb::error::Result<()> {
+    let name_text_index = IndexModel::builder()
+        .keys(doc! { "name": "text" })
+        .options(
+            IndexOptions::builder()
+                .name("members_name_text".to_owned())
+                .build(),
+        )
+        .build();
+
+    collection.create_index(name_text_index).await?;
+    Ok(())
+}
+
 // 🔧 라우터 정의 함수
 fn app(client: Client) -> Router {
-    // members 컬렉션 선택
-    let collection: Collection<Member> = client.database("axum-mongo").collection("members");
+    let collection = members_collection(&client);
 
     Router::new()
         .route("/create", post(create_member))
         .route("/read/{id}", get(read_member))
         .route("/update", put(update_member))
         .route("/delete/{id}", delete(delete_member))
+        .route("/members/search", get(search_members))
+        .route("/members/stats", get(member_stats))
+        .route("/members/watch", get(watch_members))
         .layer(TraceLayer::new_for_http()) // 로그 추적 미들웨어
         .with_state(collection) // 콜렉션을 핸들러에 주입
 }
 
-/// ✅ 핸들러 함수들
+// ✅ 핸들러 함수들
 
 // POST /create – 신규 회원 생성
 async fn create_member(
     State(db): State<Collection<Member>>,
     Json(input): Json<Member>,
-) -> Result<Json<InsertOneResult>, (StatusCode, String)> {
-    let result = db.insert_one(input).await.map_err(internal_error)?;
+) -> Result<Json<CreatedResponse>, (StatusCode, String)> {
+    // inserted_id는 Bson 값이므로 그대로 노출하지 않고, 요청에 담긴 id를 돌려줌
+    let id = input.id;
+    db.insert_one(input).await.map_err(internal_error)?;
 
-    Ok(Json(result))
+    Ok(Json(CreatedResponse { id }))
 }
 
 // GET /read/{id} – 특정 ID 조회
 async fn read_member(
     State(db): State<Collection<Member>>,
     Path(id): Path<u32>,
-) -> Result<Json<Option<Member>>, (StatusCode, String)> {
+) -> Result<Json<MemberResponse>, (StatusCode, String)> {
     let result = db
         .find_one(doc! { "_id": id })
         .await
-        .map_err(internal_error)?;
+        .map_err(internal_error)?
+        .ok_or((StatusCode::NOT_FOUND, format!("member {id} not found")))?;
 
-    Ok(Json(result))
+    Ok(Json(result.into()))
 }
 
 // PUT /update – 기존 회원 수정 (전체 덮어쓰기 방식)
 async fn update_member(
     State(db): State<Collection<Member>>,
     Json(input): Json<Member>,
-) -> Result<Json<UpdateResult>, (StatusCode, String)> {
+) -> Result<Json<UpdatedResponse>, (StatusCode, String)> {
     let result = db
         .replace_one(doc! { "_id": input.id }, input)
         .await
         .map_err(internal_error)?;
 
-    Ok(Json(result))
+    Ok(Json(UpdatedResponse {
+        matched: result.matched_count,
+        modified: result.modified_count,
+    }))
 }
 
 // DELETE /delete/{id} – 기존 회원 삭제
 async fn delete_member(
     State(db): State<Collection<Member>>,
     Path(id): Path<u32>,
-) -> Result<Json<DeleteResult>, (StatusCode, String)> {
+) -> Result<Json<DeletedResponse>, (StatusCode, String)> {
     let result = db
         .delete_one(doc! { "_id": id })
         .await
         .map_err(internal_error)?;
 
-    Ok(Json(result))
+    Ok(Json(DeletedResponse {
+        deleted: result.deleted_count,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchParams {
+    q: String,
+}
+
+// GET /members/search?q=... – 이름 텍스트 검색
+async fn search_members(
+    State(db): State<Collection<Member>>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<Vec<MemberResponse>>, (StatusCode, String)> {
+    // $text는 text index를 사용하며, textScore(관련도) 순으로 정렬
+    let members: Vec<Member> = db
+        .find(doc! { "$text": { "$search": params.q } })
+        .sort(doc! { "score": { "$meta": "textScore" } })
+        .limit(20)
+        .await
+        .map_err(internal_error)?
+        .try_collect()
+        .await
+        .map_err(internal_error)?;
+
+    Ok(Json(members.into_iter().map(Into::into).collect()))
+}
+
+// GET /members/stats – active 값으로 그룹화하여 회원 수 집계
+async fn member_stats(
+    State(db): State<Collection<Member>>,
+) -> Result<Json<MemberStats>, (StatusCode, String)> {
+    // [{ "_id": true, "count": 3 }, { "_id": false, "count": 1 }] 형태의 결과
+    #[derive(Debug, Deserialize)]
+    struct ActiveGroup {
+        #[serde(rename = "_id")]
+        active: bool,
+        count: u64,
+    }
+
+    let pipeline = [doc! { "$group": { "_id": "$active", "count": { "$sum": 1 } } }];
+    let groups: Vec<ActiveGroup> = db
+        .aggregate(pipeline)
+        .with_type::<ActiveGroup>()
+        .await
+        .map_err(internal_error)?
+        .try_collect()
+        .await
+        .map_err(internal_error)?;
+
+    let mut stats = MemberStats::default();
+    for group in groups {
+        if group.active {
+            stats.active += group.count;
+        } else {
+            stats.inactive += group.count;
+        }
+        stats.total += group.count;
+    }
+
+    Ok(Json(stats))
+}
+
+// GET /members/watch – 변경 사항을 Server-Sent Events로 전달
+//
+// 각 SSE 이벤트의 id에 change stream의 resume token을 담아 보냄.
+// 연결이 끊기면 브라우저(EventSource)가 마지막 id를 `Last-Event-ID` 헤더로 보내며 재연결하므로,
+// 그 token부터 다시 구독하면 끊긴 동안의 변경 사항도 놓치지 않음
+async fn watch_members(
+    State(db): State<Collection<Member>>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, String)> {
+    let resume_token = match headers.get("last-event-id") {
+        Some(value) => {
+            let token = value
+                .to_str()
+                .ok()
+                .and_then(|value| serde_json::from_str::<ResumeToken>(value).ok())
+                .ok_or((
+                    StatusCode::BAD_REQUEST,
+                    "invalid Last-Event-ID header".to_owned(),
+                ))?;
+            Some(token)
+        }
+        None => None,
+    };
+
+    tracing::debug!(resuming = resume_token.is_some(), "opening change stream");
+
+    let changes = db
+        .watch()
+        // CRUD 이벤트만 구독 (drop, rename 등은 제외)
+        .pipeline([doc! {
+            "$match": { "operationType": { "$in": ["insert", "update", "replace", "delete"] } }
+        }])
+        // update 이벤트에도 변경 후 전체 문서를 포함
+        .full_document(FullDocumentType::UpdateLookup)
+        .resume_after(resume_token)
+        .await
+        .map_err(internal_error)?;
+
+    let stream = changes.map(|change| {
+        let event = match change {
+            Ok(change) => change_event(change),
+            // 스트림 에러는 error 이벤트로 알림 (클라이언트는 마지막 id로 재연결)
+            Err(err) => Event::default().event("error").data(err.to_string()),
+        };
+        Ok(event)
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+// ChangeStreamEvent → SSE 이벤트 변환
+fn change_event(change: ChangeStreamEvent<Member>) -> Event {
+    let operation = match change.operation_type {
+        OperationType::Insert => "insert",
+        OperationType::Update => "update",
+        OperationType::Replace => "replace",
+        OperationType::Delete => "delete",
+        _ => "other",
+    };
+
+    let data = MemberChange {
+        operation,
+        id: change.document_key.as_ref().and_then(member_id),
+        member: change.full_document.map(Into::into),
+    };
+
+    let event = Event::default().event("member");
+    let event = match serde_json::to_string(&change.id) {
+        Ok(resume_token) => event.id(resume_token),
+        Err(err) => {
+            tracing::warn!(%err, "failed to serialize resume token");
+            event
+        }
+    };
+
+    event.json_data(data).unwrap()
+}
+
+// document_key는 `{ "_id": ... }` 형태 (u32는 Int64로 저장됨)
+fn member_id(document_key: &Document) -> Option<u32> {
+    match document_key.get("_id")? {
+        Bson::Int32(id) => u32::try_from(*id).ok(),
+        Bson::Int64(id) => u32::try_from(*id).ok(),
+        _ => None,
+    }
 }
 
 /// 🧠 에러 핸들러 함수
@@ -145,6 +334,57 @@ struct Member {
     active: bool,
 }
 
+/// 📤 응답 DTO
+// 드라이버 결과 타입(InsertOneResult 등)이나 `_id` 같은 저장 형식을 API에 노출하지 않음
+
+#[derive(Debug, Serialize)]
+struct MemberResponse {
+    id: u32,
+    name: String,
+    active: bool,
+}
+
+impl From<Member> for MemberResponse {
+    fn from(member: Member) -> Self {
+        Self {
+            id: member.id,
+            name: member.name,
+            active: member.active,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CreatedResponse {
+    id: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct UpdatedResponse {
+    matched: u64,
+    modified: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct DeletedResponse {
+    deleted: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct MemberChange {
+    operation: &'static str,
+    id: Option<u32>,
+    // delete 이벤트에는 문서가 없음
+    member: Option<MemberResponse>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct MemberStats {
+    total: u64,
+    active: u64,
+    inactive: u64,
+}
+
 // 🧪 테스트 예시 (Postman or curl)
 //
 // 회원 생성 요청
@@ -162,6 +402,22 @@ struct Member {
 //
 // ❌ 회원 삭제
 // > curl -X DELETE http://localhost:3000/delete/1
+//
+// 🔎 이름 검색
+// > curl 'http://localhost:3000/members/search?q=alice'
+//
+// 📊 통계
+// > curl http://localhost:3000/members/stats
+// {"total":2,"active":1,"inactive":1}
+//
+// 👀 변경 사항 구독 (다른 터미널에서 생성/수정/삭제 요청을 보내 확인)
+// > curl -N http://localhost:3000/members/watch
+// event: member
+// id: {"_data":"8266..."}
+// data: {"operation":"insert","id":1,"member":{"id":1,"name":"Alice","active":true}}
+//
+// 재연결 시 마지막으로 받은 id를 Last-Event-ID로 넘기면 그 이후부터 이어서 받음
+// > curl -N http://localhost:3000/members/watch -H 'Last-Event-ID: {"_data":"8266..."}'
 
 // 서버 실행 전 MongoDB 설치 필수! (2025.04.15 기준)
 // $ brew tap mongodb/brew
@@ -178,3 +434,7 @@ struct Member {
 //
 // 실행 확인
 // $ brew services list
+//
+// change stream을 쓰려면 replica set으로 실행해야 함 (단일 노드도 가능)
+// $ mongod --replSet rs0 --dbpath <data dir>
+// $ mongosh --eval 'rs.initiate()'
@@ -0,0 +1,140 @@
+//! 🩺 커넥션 풀 메트릭과 느린 쿼리 로그
+//!
+//! • `Db::interact`: `conn.interact(...)`를 감싸서 실행 시간을 재는 헬퍼
+//!   → `db_query_duration_seconds` 히스토그램에 기록
+//!   → `SLOW_QUERY_MS`(기본 200ms)를 넘으면 SQL 문과 함께 `warn!` (핸들러 span 안에서 찍힘)
+//! • `spawn_pool_metrics`: `pool.status()`를 주기적으로 읽어 게이지로 내보냄
+//!   → `db_pool_size` / `db_pool_available` / `db_pool_waiting` / `db_pool_max_size`
+//!   → `waiting`이 계속 0보다 크면 풀이 작거나 오래 잡고 있는 쿼리가 있다는 뜻
+
+use deadpool_diesel::{postgres::Pool, Status};
+use diesel::{pg::Pg, query_builder::QueryFragment, PgConnection, QueryResult};
+use example_common_errors::ApiError;
+use std::time::{Duration, Instant};
+use tracing::Instrument;
+
+const DEFAULT_SLOW_QUERY_MS: u64 = 200;
+
+#[derive(Clone)]
+pub struct Db {
+    pool: Pool,
+    slow_query: Duration,
+}
+
+impl Db {
+    pub fn new(pool: Pool, slow_query: Duration) -> Self {
+        Self { pool, slow_query }
+    }
+
+    /// 쿼리 빌더를 받아 SQL 문을 남겨 두고, 커넥션 스레드에서 `f(query, conn)` 실행
+    ///
+    /// ```ignore
+    /// db.interact(users::table.select(User::as_select()), |query, conn| query.load(conn))
+    /// ```
+    pub async fn interact<Q, F, T>(&self, query: Q, f: F) -> Result<T, ApiError>
+    where
+        Q: QueryFragment<Pg> + Send + 'static,
+        F: FnOnce(Q, &mut PgConnection) -> QueryResult<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        // 바인딩 값까지 포함한 SQL (예: `SELECT ... WHERE id = $1 -- binds: [1]`)
+        let sql = diesel::debug_query::<Pg, _>(&query).to_string();
+        let span = tracing::debug_span!("db.query", db.statement = %sql);
+
+        async {
+            let conn = self.pool.get().await.map_err(ApiError::internal)?;
+
+            let start = Instant::now();
+            let result = conn
+                .interact(move |conn| f(query, conn))
+                .await
+                // InteractError는 Sync가 아니라서 문자열로 바꿔 담음
+                .map_err(|err| ApiError::internal(err.to_string()))?;
+            let elapsed = start.elapsed();
+
+            metrics::histogram!("db_query_duration_seconds").record(elapsed.as_secs_f64());
+            if elapsed >= self.slow_query {
+                tracing::warn!(
+                    sql = %sql,
+                    elapsed_ms = elapsed.as_millis() as u64,
+                    threshold_ms = self.slow_query.as_millis() as u64,
+                    "slow query"
+                );
+            }
+
+            Ok(result?)
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+/// `SLOW_QUERY_MS` (밀리초), 없거나 숫자가 아니면 기본값
+pub fn slow_query_threshold(value: Option<String>) -> Duration {
+    let millis = value
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(DEFAULT_SLOW_QUERY_MS);
+    Duration::from_millis(millis)
+}
+
+pub fn record_pool_status(status: Status) {
+    metrics::gauge!("db_pool_max_size").set(status.max_size as f64);
+    metrics::gauge!("db_pool_size").set(status.size as f64);
+    metrics::gauge!("db_pool_available").set(status.available as f64);
+    metrics::gauge!("db_pool_waiting").set(status.waiting as f64);
+}
+
+/// scrape 간격보다 짧게 잡으면 충분 (status()는 락 없이 읽어서 가벼움)
+pub fn spawn_pool_metrics(pool: Pool, every: Duration) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        loop {
+            interval.tick().await;
+            record_pool_status(pool.status());
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metrics_exporter_prometheus::PrometheusBuilder;
+
+    #[test]
+    fn slow_query_threshold_falls_back_to_the_default() {
+        assert_eq!(
+            slow_query_threshold(Some(" 50 ".to_owned())),
+            Duration::from_millis(50)
+        );
+        assert_eq!(slow_query_threshold(None), Duration::from_millis(200));
+        assert_eq!(
+            slow_query_threshold(Some("fast".to_owned())),
+            Duration::from_millis(200)
+        );
+    }
+
+    #[test]
+    fn pool_status_is_exported_as_gauges() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+
+        metrics::with_local_recorder(&recorder, || {
+            record_pool_status(Status {
+                max_size: 16,
+                size: 4,
+                available: 1,
+                waiting: 2,
+            })
+        });
+
+        let rendered = handle.render();
+        for line in [
+            "db_pool_max_size 16",
+            "db_pool_size 4",
+            "db_pool_available 1",
+            "db_pool_waiting 2",
+        ] {
+            assert!(rendered.contains(line), "missing {line:?} in\n{rendered}");
+        }
+    }
+}
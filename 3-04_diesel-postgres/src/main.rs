@@ -9,19 +9,30 @@
 //!
 //! Checkout the [crates.io source code](https://github.com/rust-lang/crates.io/)
 //! for a real world application using axum and diesel
+//!
+//! 풀 상태와 쿼리 시간은 `GET /metrics`로, 느린 쿼리는 로그로 확인 (`db.rs`)
+//!
+//! ```not_rust
+//! SLOW_QUERY_MS=50 cargo run -p example-diesel-postgres
+//! ```
+
+mod db;
 
 use axum::{
     extract::State,
-    http::StatusCode,
     response::Json,
     routing::{get, post},
     Router,
 };
+use db::Db;
 use diesel::prelude::*;
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 use dotenv::dotenv;
+use example_common_errors::ApiError;
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
 use std::env;
 use std::net::SocketAddr;
+use std::time::Duration;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 // 디젤 마이그레이션을 바이너리에 포함시키는 매크로
@@ -87,11 +98,18 @@ async fn main() {
             .unwrap();
     }
 
+    // 📊 풀 상태 게이지 (5초마다 갱신) + 느린 쿼리 기준
+    let metrics = setup_metrics_recorder();
+    db::spawn_pool_metrics(pool.clone(), Duration::from_secs(5));
+    let slow_query = db::slow_query_threshold(env::var("SLOW_QUERY_MS").ok());
+    tracing::debug!(?slow_query, "logging slow queries");
+
     // 🧪 라우팅 및 핸들러
     let app = Router::new()
         .route("/user/list", get(list_users))
         .route("/user/create", post(create_user))
-        .with_state(pool);
+        .route("/metrics", get(move || async move { metrics.render() }))
+        .with_state(Db::new(pool, slow_query));
 
     // run it with hyper
     let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
@@ -100,46 +118,44 @@ async fn main() {
     axum::serve(listener, app).await.unwrap();
 }
 
+// 쿼리 시간 히스토그램 버킷 (초 단위, 5-13_prometheus-metrics와 같은 방식)
+fn setup_metrics_recorder() -> PrometheusHandle {
+    const QUERY_SECONDS: &[f64] = &[0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5];
+
+    PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            Matcher::Full("db_query_duration_seconds".to_string()),
+            QUERY_SECONDS,
+        )
+        .unwrap()
+        .install_recorder()
+        .unwrap()
+}
+
 /// ✏️ POST /user/create
+// 느린 쿼리 로그가 어느 요청에서 나왔는지 span으로 남김
+#[tracing::instrument(skip_all)]
 async fn create_user(
-    State(pool): State<deadpool_diesel::postgres::Pool>,
+    State(db): State<Db>,
     Json(new_user): Json<NewUser>,
-) -> Result<Json<User>, (StatusCode, String)> {
-    let conn = pool.get().await.map_err(internal_error)?;
-    let res = conn
-        .interact(|conn| {
-            diesel::insert_into(users::table)
-                .values(new_user)
-                .returning(User::as_returning()) // PostgreSQL 전용 반환
-                .get_result(conn)
-        })
-        .await
-        .map_err(internal_error)?
-        .map_err(internal_error)?;
+) -> Result<Json<User>, ApiError> {
+    let query = diesel::insert_into(users::table)
+        .values(new_user)
+        .returning(User::as_returning()); // PostgreSQL 전용 반환
+    let res = db
+        .interact(query, |query, conn| query.get_result(conn))
+        .await?;
     Ok(Json(res))
 }
 
 /// 🔍 GET /user/list
-async fn list_users(
-    State(pool): State<deadpool_diesel::postgres::Pool>,
-) -> Result<Json<Vec<User>>, (StatusCode, String)> {
-    let conn = pool.get().await.map_err(internal_error)?;
-    let res = conn
-        .interact(|conn| users::table.select(User::as_select()).load(conn))
-        .await
-        .map_err(internal_error)?
-        .map_err(internal_error)?;
+#[tracing::instrument(skip_all)]
+async fn list_users(State(db): State<Db>) -> Result<Json<Vec<User>>, ApiError> {
+    let query = users::table.select(User::as_select());
+    let res = db.interact(query, |query, conn| query.load(conn)).await?;
     Ok(Json(res))
 }
 
-/// 🔥 에러 헬퍼: 어떤 에러든 500 Internal Server Error로 매핑
-fn internal_error<E>(err: E) -> (StatusCode, String)
-where
-    E: std::error::Error,
-{
-    (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
-}
-
 // 🧪 예시 요청 (Postman)
 //
 // POST /user/create
@@ -147,6 +163,16 @@ where
 //
 // GET /user/list
 //[ { "id": 1, "name": "Alice", "hair_color": "black" } ]
+//
+// GET /metrics
+// db_pool_size 1
+// db_pool_available 1
+// db_pool_waiting 0
+// db_query_duration_seconds_bucket{le="0.005"} 2
+//
+// 느린 쿼리 로그 확인 (모든 쿼리를 느린 쿼리로 취급)
+// $ SLOW_QUERY_MS=0 cargo run -p example-diesel-postgres
+// WARN list_users:db.query{db.statement=SELECT "users"."id", ... -- binds: []}: slow query sql=... elapsed_ms=1 threshold_ms=0
 
 // PostgreSQL 설치
 // $ brew install postgresql
@@ -0,0 +1,2 @@
+-- down.sql: 마이그레이션 롤백 시 테이블 제거
+DROP TABLE "posts";
@@ -0,0 +1,7 @@
+-- up.sql: users를 참조하는 posts 테이블 생성 (외래 키)
+CREATE TABLE "posts" (
+    "id" SERIAL PRIMARY KEY,
+    "user_id" INTEGER NOT NULL REFERENCES "users" ("id"),
+    "title" TEXT NOT NULL,
+    "body" TEXT NOT NULL
+);
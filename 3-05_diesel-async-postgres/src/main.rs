@@ -6,6 +6,9 @@
 //! cargo run -p example-diesel-async-postgres
 //! ```
 //!
+//! `POST /user/transfer` shows how to run several statements in one transaction
+//! (`conn.transaction()`), and how a failure in the middle rolls everything back.
+//!
 //! Checkout the [diesel webpage](https://diesel.rs) for
 //! longer guides about diesel
 //!
@@ -14,16 +17,18 @@
 
 use axum::{
     extract::{FromRef, FromRequestParts, State},
-    http::{request::Parts, StatusCode},
+    http::request::Parts,
     response::Json,
     routing::{get, post},
     Router,
 };
 use diesel::prelude::*;
 use diesel_async::{
-    pooled_connection::AsyncDieselConnectionManager, AsyncPgConnection, RunQueryDsl,
+    pooled_connection::AsyncDieselConnectionManager, scoped_futures::ScopedFutureExt,
+    AsyncConnection, AsyncPgConnection, RunQueryDsl,
 };
 use dotenv::dotenv;
+use example_common_errors::ApiError;
 use std::env;
 use std::net::SocketAddr;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -40,7 +45,20 @@ table! {
     }
 }
 
-/// ✅ 모델 정의
+table! {
+    posts (id) {
+        id -> Integer,
+        user_id -> Integer,
+        title -> Text,
+        body -> Text,
+    }
+}
+
+// posts.user_id → users.id 외래 키 관계 (inner_join에 필요)
+joinable!(posts -> users (user_id));
+allow_tables_to_appear_in_same_query!(users, posts);
+
+// ✅ 모델 정의
 
 // DB에서 읽은 데이터를 응답으로 직렬화
 #[derive(serde::Serialize, Selectable, Queryable)]
@@ -58,6 +76,30 @@ struct NewUser {
     hair_color: Option<String>,
 }
 
+#[derive(serde::Serialize, Selectable, Queryable)]
+struct Post {
+    id: i32,
+    user_id: i32,
+    title: String,
+    body: String,
+}
+
+#[derive(serde::Deserialize, Insertable)]
+#[diesel(table_name = posts)]
+struct NewPost {
+    user_id: i32,
+    title: String,
+    body: String,
+}
+
+// 조인 결과: 게시글 + 작성자 이름
+#[derive(serde::Serialize)]
+struct PostWithAuthor {
+    #[serde(flatten)]
+    post: Post,
+    author: String,
+}
+
 type Pool = bb8::Pool<AsyncDieselConnectionManager<AsyncPgConnection>>;
 
 #[tokio::main]
@@ -82,6 +124,9 @@ async fn main() {
     let app = Router::new()
         .route("/user/list", get(list_users))
         .route("/user/create", post(create_user))
+        .route("/user/transfer", post(transfer_posts))
+        .route("/post/list", get(list_posts))
+        .route("/post/create", post(create_post))
         .with_state(pool);
 
     // run it with hyper
@@ -95,16 +140,15 @@ async fn main() {
 async fn create_user(
     State(pool): State<Pool>,
     Json(new_user): Json<NewUser>,
-) -> Result<Json<User>, (StatusCode, String)> {
-    let mut conn = pool.get().await.map_err(internal_error)?;
+) -> Result<Json<User>, ApiError> {
+    let mut conn = pool.get().await.map_err(ApiError::internal)?;
 
     // Diesel + 비동기 연결을 이용한 삽입
     let res = diesel::insert_into(users::table)
         .values(new_user)
         .returning(User::as_returning())
         .get_result(&mut conn)
-        .await
-        .map_err(internal_error)?;
+        .await?;
     Ok(Json(res))
 }
 
@@ -120,12 +164,12 @@ where
     S: Send + Sync,
     Pool: FromRef<S>,
 {
-    type Rejection = (StatusCode, String);
+    type Rejection = ApiError;
 
     async fn from_request_parts(_parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
         let pool = Pool::from_ref(state);
 
-        let conn = pool.get_owned().await.map_err(internal_error)?;
+        let conn = pool.get_owned().await.map_err(ApiError::internal)?;
 
         Ok(Self(conn))
     }
@@ -134,21 +178,176 @@ where
 /// 🔍 GET /user/list
 async fn list_users(
     DatabaseConnection(mut conn): DatabaseConnection,
-) -> Result<Json<Vec<User>>, (StatusCode, String)> {
+) -> Result<Json<Vec<User>>, ApiError> {
     let res = users::table
         .select(User::as_select())
         .load(&mut conn)
-        .await
-        .map_err(internal_error)?;
+        .await?;
     Ok(Json(res))
 }
 
-/// 🔥 에러 헬퍼: 어떤 에러든 500 Internal Server Error로 매핑
-fn internal_error<E>(err: E) -> (StatusCode, String)
-where
-    E: std::error::Error,
-{
-    (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+/// ✏️ POST /post/create
+async fn create_post(
+    DatabaseConnection(mut conn): DatabaseConnection,
+    Json(new_post): Json<NewPost>,
+) -> Result<Json<Post>, ApiError> {
+    // user_id가 존재하지 않으면 외래 키 제약 조건 위반 → ApiError::Validation (422)
+    let res = diesel::insert_into(posts::table)
+        .values(new_post)
+        .returning(Post::as_returning())
+        .get_result(&mut conn)
+        .await?;
+    Ok(Json(res))
+}
+
+/// 🔍 GET /post/list
+async fn list_posts(
+    DatabaseConnection(mut conn): DatabaseConnection,
+) -> Result<Json<Vec<PostWithAuthor>>, ApiError> {
+    // SELECT posts.*, users.name FROM posts INNER JOIN users ON posts.user_id = users.id
+    let res = posts::table
+        .inner_join(users::table)
+        .select((Post::as_select(), users::name))
+        .order(posts::id)
+        .load::<(Post, String)>(&mut conn)
+        .await?;
+
+    Ok(Json(
+        res.into_iter()
+            .map(|(post, author)| PostWithAuthor { post, author })
+            .collect(),
+    ))
+}
+
+// 🔁 트랜잭션 예제
+
+#[derive(serde::Deserialize)]
+struct TransferRequest {
+    from: i32,
+    to: i32,
+    // true면 게시글을 옮긴 직후 일부러 실패시켜 롤백을 확인할 수 있음
+    #[serde(default)]
+    simulate_failure: bool,
+}
+
+impl TransferRequest {
+    // 자기 자신에게 옮기면 UPDATE는 아무것도 바꾸지 않고 사용자만 삭제됨 (게시글이 있으면 FK 에러로 500)
+    fn validate(&self) -> Result<(), ApiError> {
+        if self.from == self.to {
+            return Err(ApiError::Validation(
+                "`from` and `to` must be different users".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(serde::Serialize)]
+struct TransferResponse {
+    moved_posts: usize,
+    deleted_user: i32,
+}
+
+// Errors that can abort the transaction. `transaction()` requires the error type to be
+// constructible from `diesel::result::Error`.
+enum TransferError {
+    UserNotFound(i32),
+    SimulatedFailure,
+    Database(diesel::result::Error),
+}
+
+impl From<diesel::result::Error> for TransferError {
+    fn from(err: diesel::result::Error) -> Self {
+        Self::Database(err)
+    }
+}
+
+impl From<TransferError> for ApiError {
+    fn from(err: TransferError) -> Self {
+        match err {
+            TransferError::UserNotFound(id) => Self::NotFound(format!("user {id} not found")),
+            TransferError::SimulatedFailure => {
+                Self::internal("simulated failure, transaction rolled back")
+            }
+            TransferError::Database(err) => err.into(),
+        }
+    }
+}
+
+/// ✏️ POST /user/transfer
+// Move every post of `from` to `to` and then delete `from`, as a single unit of work.
+// 클로저 안에서 Err를 반환하면 그때까지 실행된 모든 쿼리가 ROLLBACK 됨
+async fn transfer_posts(
+    DatabaseConnection(mut conn): DatabaseConnection,
+    Json(req): Json<TransferRequest>,
+) -> Result<Json<TransferResponse>, ApiError> {
+    // 트랜잭션을 열기 전에 거절 (422)
+    req.validate()?;
+
+    let res = conn
+        .transaction::<_, TransferError, _>(|conn| {
+            async move {
+                // 1. 두 사용자가 모두 존재하는지 확인
+                for id in [req.from, req.to] {
+                    users::table
+                        .find(id)
+                        .select(users::id)
+                        .first::<i32>(conn)
+                        .await
+                        .optional()?
+                        .ok_or(TransferError::UserNotFound(id))?;
+                }
+
+                // 2. 게시글 소유자 변경
+                let moved_posts = diesel::update(posts::table.filter(posts::user_id.eq(req.from)))
+                    .set(posts::user_id.eq(req.to))
+                    .execute(conn)
+                    .await?;
+
+                // 💥 주입 가능한 실패 지점: 여기서 실패하면 2번의 UPDATE도 취소됨
+                if req.simulate_failure {
+                    return Err(TransferError::SimulatedFailure);
+                }
+
+                // 3. 게시글이 없어진 사용자 삭제 (외래 키 때문에 2번 이후에만 가능)
+                diesel::delete(users::table.find(req.from))
+                    .execute(conn)
+                    .await?;
+
+                Ok(TransferResponse {
+                    moved_posts,
+                    deleted_user: req.from,
+                })
+            }
+            .scope_boxed()
+        })
+        .await?; // TransferError → ApiError (UserNotFound는 404)
+    Ok(Json(res))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::StatusCode, response::IntoResponse};
+
+    fn transfer(from: i32, to: i32) -> TransferRequest {
+        TransferRequest {
+            from,
+            to,
+            simulate_failure: false,
+        }
+    }
+
+    #[test]
+    fn transfer_to_the_same_user_is_rejected() {
+        assert!(transfer(1, 2).validate().is_ok());
+
+        let err = transfer(1, 1).validate().unwrap_err();
+        assert_eq!(
+            err.into_response().status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
 }
 
 // 🧪 예시 요청 (Postman)
@@ -158,6 +357,22 @@ where
 //
 // GET /user/list
 //[ { "id": 1, "name": "Alice", "hair_color": "black" } ]
+//
+// POST /post/create
+// { "user_id": 1, "title": "hello", "body": "first post" }
+//
+// GET /post/list
+// [ { "id": 1, "user_id": 1, "title": "hello", "body": "first post", "author": "Alice" } ]
+//
+// POST /user/transfer  (Alice의 게시글을 Bob에게 넘기고 Alice 삭제)
+// { "from": 1, "to": 2 }
+// { "moved_posts": 1, "deleted_user": 1 }
+//
+// POST /user/transfer  (롤백 확인: 에러 응답 후 /post/list, /user/list 결과가 그대로)
+// { "from": 1, "to": 2, "simulate_failure": true }
+//
+// POST /user/transfer  (같은 사용자 → 422)
+// { "from": 1, "to": 1 }
 
 // PostgreSQL 설치
 // $ brew install postgresql
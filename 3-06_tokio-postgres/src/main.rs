@@ -3,19 +3,29 @@
 //! PostgreSQL은 인증 방법을 설정 파일에서 제어하므로 현재는 비밀번호 없이도 로컬에서 접속이 허용된 상태
 //! 이 예제는 단순히 PostgreSQL 쿼리 연동이 되는지만 확인하는 헬로 월드 스타일의 테스트
 //!
+//! 추가로 LISTEN/NOTIFY → WebSocket 브리지를 포함함:
+//! 전용 커넥션에서 `LISTEN updates` 중 받은 알림을 `/ws`에 접속한 모든 클라이언트에게 전달
+//!
+
+mod notifications;
 
 use axum::{
-    extract::{FromRef, FromRequestParts, State},
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        FromRef, FromRequestParts, State,
+    },
     http::{request::Parts, StatusCode},
-    routing::get,
+    response::Response,
+    routing::{get, post},
     Router,
 };
 use bb8::{Pool, PooledConnection}; // 커넥션 풀과 개별 커넥션 타입
 use bb8_postgres::PostgresConnectionManager; // bb8은 tokio-postgres용 풀을 지원.
+use tokio::sync::broadcast;
 use tokio_postgres::NoTls; // SSL 없는 접속을 위해 NoTls 사용.
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-/// 🔧 main() 함수
+// 🔧 main() 함수
 
 #[tokio::main]
 async fn main() {
@@ -29,14 +39,19 @@ async fn main() {
         .init();
 
     // PostgreSQL 비동기 연결 매니저 구성
-    let manager =
-        PostgresConnectionManager::new_from_stringlike("host=localhost user=postgres", NoTls)
-            .unwrap();
+    let manager = PostgresConnectionManager::new_from_stringlike(DATABASE_CONFIG, NoTls).unwrap();
     // user=postgres는 유저명, 패스워드가 없으면 trust 인증 설정이 필요할 수 있음
 
     // bb8 풀 빌더로 커넥션 풀 생성
     let pool = Pool::builder().build(manager).await.unwrap();
 
+    // LISTEN 전용 커넥션 → broadcast 채널 (끊기면 알아서 재접속)
+    let (updates, _) = broadcast::channel(100);
+    tokio::spawn(notifications::listen(
+        DATABASE_CONFIG.to_owned(),
+        updates.clone(),
+    ));
+
     // 🌐 Axum 라우터 설정
     let app = Router::new()
         .route(
@@ -45,7 +60,9 @@ async fn main() {
             // GET  / → 상태 기반 풀 사용
             // POST / → 커스텀 추출기 사용
         )
-        .with_state(pool);
+        .route("/ws", get(ws_handler)) // NOTIFY payload를 WebSocket으로 전달
+        .route("/notify", post(notify)) // 테스트용: body를 NOTIFY로 발행
+        .with_state(AppState { pool, updates });
 
     // run it
     let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
@@ -55,8 +72,24 @@ async fn main() {
     axum::serve(listener, app).await.unwrap();
 }
 
+const DATABASE_CONFIG: &str = "host=localhost user=postgres";
+
 type ConnectionPool = Pool<PostgresConnectionManager<NoTls>>;
 
+#[derive(Clone)]
+struct AppState {
+    pool: ConnectionPool,
+    // notifications::listen이 받은 NOTIFY payload
+    updates: broadcast::Sender<String>,
+}
+
+// 기존 핸들러와 추출기는 계속 State<ConnectionPool>로 풀을 꺼낼 수 있음
+impl FromRef<AppState> for ConnectionPool {
+    fn from_ref(state: &AppState) -> Self {
+        state.pool.clone()
+    }
+}
+
 /// 🧪 GET 핸들러 - 커넥션 풀 직접 사용
 async fn using_connection_pool_extractor(
     State(pool): State<ConnectionPool>,
@@ -111,6 +144,53 @@ async fn using_connection_extractor(
     Ok(two.to_string())
 }
 
+/// 📡 WebSocket 핸들러 - NOTIFY 구독
+async fn ws_handler(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
+    // 업그레이드 전에 구독해야 그 사이 도착한 알림을 놓치지 않음
+    let updates = state.updates.subscribe();
+    ws.on_upgrade(|socket| forward_updates(socket, updates))
+}
+
+async fn forward_updates(mut socket: WebSocket, mut updates: broadcast::Receiver<String>) {
+    loop {
+        tokio::select! {
+            update = updates.recv() => match update {
+                Ok(payload) => {
+                    if socket.send(Message::Text(payload.into())).await.is_err() {
+                        break; // 클라이언트 연결 끊김
+                    }
+                }
+                // 느린 클라이언트는 밀린 알림을 건너뛰고 계속 받음
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!(skipped, "websocket client lagged behind");
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            // 클라이언트가 보낸 메시지는 무시하고, 닫힘만 감지
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+/// 🧪 POST 핸들러 - body를 `updates` 채널로 NOTIFY
+async fn notify(
+    DatabaseConnection(conn): DatabaseConnection,
+    payload: String,
+) -> Result<StatusCode, (StatusCode, String)> {
+    // NOTIFY 문은 파라미터를 받을 수 없으므로 pg_notify 함수 사용
+    conn.execute(
+        "SELECT pg_notify($1, $2)",
+        &[&notifications::CHANNEL, &payload],
+    )
+    .await
+    .map_err(internal_error)?;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
 /// 💥 공통 에러 처리기
 /// 모든 에러를 500 상태 코드로 포장하여 클라이언트에 전달
 fn internal_error<E>(err: E) -> (StatusCode, String)
@@ -123,6 +203,14 @@ where
 // 🧪 예시 요청 (브라우저 / Postman)
 // > GET http://localhost:3000/ → 2
 // > POST http://localhost:3000/ → 2
+//
+// LISTEN/NOTIFY → WebSocket
+// $ websocat ws://localhost:3000/ws
+// 다른 터미널에서 알림 발행 (둘 중 하나)
+// $ curl -X POST localhost:3000/notify -d 'hello'
+// $ psql -h localhost -U postgres -c "NOTIFY updates, 'hello'"
+// → websocat 화면에 hello 출력
+// PostgreSQL을 재시작해도 listen 커넥션이 재접속되어 다시 알림을 받음
 
 // PostgreSQL 설치
 // $ brew install postgresql
@@ -0,0 +1,74 @@
+//! PostgreSQL `LISTEN/NOTIFY` → broadcast 채널 브리지
+//!
+//! • 풀과 별개인 전용 커넥션에서 `LISTEN updates` 실행
+//!   (풀 커넥션은 요청이 끝나면 다른 곳에서 재사용되므로 LISTEN 상태를 유지할 수 없음)
+//! • 받은 NOTIFY payload를 `broadcast` 채널로 전달 → WebSocket 클라이언트들이 구독
+//! • 커넥션이 끊기면 지수 백오프로 재접속 후 다시 LISTEN
+//!   (끊겨 있는 동안 발생한 NOTIFY는 PostgreSQL이 보관하지 않으므로 유실됨)
+
+use futures_util::stream::{self, StreamExt};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio_postgres::{AsyncMessage, NoTls};
+
+/// 구독할 채널 이름
+pub const CHANNEL: &str = "updates";
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// 서버가 살아 있는 동안 계속 실행되는 LISTEN 루프 (`tokio::spawn`으로 실행)
+pub async fn listen(config: String, updates: broadcast::Sender<String>) {
+    let mut backoff = INITIAL_BACKOFF;
+
+    loop {
+        match listen_once(&config, &updates, &mut backoff).await {
+            Ok(()) => tracing::warn!("listen connection closed"),
+            Err(err) => tracing::warn!(%err, "listen connection failed"),
+        }
+
+        tracing::debug!(?backoff, "reconnecting listen connection");
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+/// 접속 → LISTEN → 커넥션이 끊길 때까지 알림 전달
+async fn listen_once(
+    config: &str,
+    updates: &broadcast::Sender<String>,
+    backoff: &mut Duration,
+) -> Result<(), tokio_postgres::Error> {
+    let (client, mut connection) = tokio_postgres::connect(config, NoTls).await?;
+
+    // NOTIFY는 쿼리 응답이 아닌 비동기 메시지로 도착하므로 Connection을 직접 poll 해야 함
+    let mut messages = stream::poll_fn(move |cx| connection.poll_message(cx));
+    let updates_tx = updates.clone();
+    let driver = tokio::spawn(async move {
+        while let Some(message) = messages.next().await {
+            match message? {
+                AsyncMessage::Notification(notification) => {
+                    tracing::debug!(payload = notification.payload(), "received notification");
+                    // 구독 중인 WebSocket이 없으면 에러지만 무시해도 됨
+                    let _ = updates_tx.send(notification.payload().to_owned());
+                }
+                AsyncMessage::Notice(notice) => tracing::debug!(%notice, "postgres notice"),
+                _ => {}
+            }
+        }
+        Ok(())
+    });
+
+    // driver가 커넥션을 poll 하고 있어야 LISTEN 쿼리도 완료됨
+    if let Err(err) = client.batch_execute(&format!("LISTEN {CHANNEL}")).await {
+        driver.abort();
+        return Err(err);
+    }
+    tracing::debug!("listening on channel `{CHANNEL}`");
+    *backoff = INITIAL_BACKOFF; // 접속에 성공했으므로 백오프 초기화
+
+    // client를 drop 하면 커넥션이 닫히므로 driver가 끝날 때까지 들고 있음
+    let result = driver.await.expect("listen connection task panicked");
+    drop(client);
+    result
+}
@@ -0,0 +1,6 @@
+-- users 테이블 생성 (email은 중복 불가 → 중복 시 409 Conflict)
+CREATE TABLE users (
+    id BIGSERIAL PRIMARY KEY,
+    name TEXT NOT NULL,
+    email TEXT NOT NULL UNIQUE
+);
@@ -0,0 +1,63 @@
+//! 🔀 읽기/쓰기 커넥션 분리 (primary + read replica)
+//!
+//! • `WriteConnection`: 항상 primary에서 커넥션을 가져옴
+//! • `ReadConnection`: replica에서 가져오고, replica에 연결할 수 없으면 primary로 대신 읽음
+//!   → replica가 내려가도 읽기 요청은 실패하지 않음 (primary 부하만 늘어남)
+//!   → replica 풀의 `acquire_timeout`을 짧게 잡아야 장애 시 요청마다 오래 기다리지 않음
+//! • replica를 설정하지 않으면(`replica: None`) 읽기도 primary에서
+//!
+//! ⚠️ replica는 primary보다 조금 늦게 반영됨 (replication lag)
+//!   → 방금 쓴 데이터를 곧바로 다시 읽어야 하는 곳에서는 `WriteConnection`으로 읽기
+
+use axum::{
+    extract::{FromRef, FromRequestParts},
+    http::request::Parts,
+};
+use example_common_errors::ApiError;
+use sqlx::{pool::PoolConnection, PgPool, Postgres};
+
+#[derive(Clone)]
+pub struct DbPools {
+    pub primary: PgPool,
+    pub replica: Option<PgPool>,
+}
+
+/// primary에 연결된 커넥션 (INSERT / UPDATE / DELETE)
+pub struct WriteConnection(pub PoolConnection<Postgres>);
+
+/// replica (또는 대신 primary)에 연결된 커넥션 (SELECT)
+pub struct ReadConnection(pub PoolConnection<Postgres>);
+
+impl<S> FromRequestParts<S> for WriteConnection
+where
+    DbPools: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(_parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let pools = DbPools::from_ref(state);
+        Ok(Self(pools.primary.acquire().await?))
+    }
+}
+
+impl<S> FromRequestParts<S> for ReadConnection
+where
+    DbPools: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(_parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let pools = DbPools::from_ref(state);
+
+        if let Some(replica) = &pools.replica {
+            match replica.acquire().await {
+                Ok(conn) => return Ok(Self(conn)),
+                Err(err) => tracing::warn!(%err, "replica unavailable, reading from primary"),
+            }
+        }
+
+        Ok(Self(pools.primary.acquire().await?))
+    }
+}
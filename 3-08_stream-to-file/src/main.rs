@@ -3,26 +3,31 @@
 //! ```not_rust
 //! cargo run -p example-stream-to-file
 //! ```
+//!
+//! 저장소는 `STORAGE` 환경 변수로 선택 (`local` 기본값, `s3`는 아래 테스트 방법 참고)
+
+mod s3;
+mod storage;
 
 use axum::{
-    body::Bytes,
-    extract::{Multipart, Path, Request},
-    http::StatusCode,
-    response::{Html, Redirect},
-    routing::{get, post},
+    body::{Body, Bytes},
+    extract::{Multipart, Path, Request, State},
+    http::{header, StatusCode},
+    response::{Html, IntoResponse, Redirect, Response},
+    routing::get,
     BoxError, Router,
 };
-use futures::{Stream, TryStreamExt};
-use std::io;
-use tokio::{fs::File, io::BufWriter};
-use tokio_util::io::StreamReader;
+use futures::Stream;
+use std::time::Duration;
+use storage::Storage;
+use tokio::fs::File;
+use tokio_util::io::ReaderStream;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-// 업로드된 파일을 저장할 디렉토리 이름 정의
-const UPLOADS_DIRECTORY: &str = "uploads";
-
-/// 🏁 main 함수
+// S3 다운로드 링크(presigned URL)의 유효 시간
+const PRESIGNED_URL_TTL: Duration = Duration::from_secs(15 * 60);
 
+// 🏁 main 함수
 #[tokio::main]
 async fn main() {
     // 로그 출력 설정
@@ -35,30 +40,67 @@ async fn main() {
         .init();
 
     // save files to a separate directory to not override files in the current directory
-    // 최초 실행 시 uploads/ 폴더가 없다면 생성 (없으면 저장 시 에러 발생)
-    tokio::fs::create_dir(UPLOADS_DIRECTORY)
+    let storage = Storage::from_env()
         .await
-        .expect("failed to create `uploads` directory");
-
-    let app = Router::new()
-        .route("/", get(show_form).post(accept_form)) // HTML form
-        .route("/file/{file_name}", post(save_request_body)); // raw body 업로드
+        .expect("failed to configure storage");
 
     let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
         .await
         .unwrap();
     tracing::debug!("listening on {}", listener.local_addr().unwrap());
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(listener, app(storage)).await.unwrap();
+}
+
+fn app(storage: Storage) -> Router {
+    Router::new()
+        .route("/", get(show_form).post(accept_form)) // HTML form
+        .route(
+            "/file/{file_name}",
+            get(download).post(save_request_body), // 다운로드 / raw body 업로드
+        )
+        .with_state(storage)
 }
 
 // 폼이 아닌 단순 스트림 형태의 POST 요청을 저장하는 Handler
 // POST'ing to `/file/foo.txt` will create a file called `foo.txt`.
 async fn save_request_body(
+    State(storage): State<Storage>,
     Path(file_name): Path<String>,
     request: Request,
 ) -> Result<(), (StatusCode, String)> {
     // 바디는 .into_body().into_data_stream()을 통해 스트림으로 변환하여 저장
-    stream_to_file(&file_name, request.into_body().into_data_stream()).await
+    stream_to_file(&storage, &file_name, request.into_body().into_data_stream()).await
+}
+
+// 📥 다운로드 Handler
+// • local: 파일을 스트림으로 읽어 그대로 응답
+// • s3: 버킷에서 직접 받도록 presigned URL로 리다이렉트 (서버가 파일을 중계하지 않음)
+async fn download(
+    State(storage): State<Storage>,
+    Path(file_name): Path<String>,
+) -> Result<Response, (StatusCode, String)> {
+    if !path_is_valid(&file_name) {
+        return Err((StatusCode::BAD_REQUEST, "Invalid path".to_owned()));
+    }
+
+    match storage {
+        Storage::Local(dir) => {
+            let file = File::open(dir.join(&file_name)).await.map_err(|err| {
+                if err.kind() == std::io::ErrorKind::NotFound {
+                    (StatusCode::NOT_FOUND, format!("{file_name} not found"))
+                } else {
+                    (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+                }
+            })?;
+            let headers = [(header::CONTENT_TYPE, "application/octet-stream")];
+            Ok((headers, Body::from_stream(ReaderStream::new(file))).into_response())
+        }
+        // 없는 파일이면 S3가 404를 응답함
+        Storage::S3(client) => {
+            let url = client.presign_get(&file_name, PRESIGNED_URL_TTL);
+            Ok(Redirect::temporary(&url).into_response())
+        }
+    }
 }
 
 // GET 요청 → 업로드 폼 출력 Handler
@@ -92,7 +134,10 @@ async fn show_form() -> Html<&'static str> {
 // Handler that accepts a multipart form upload and streams each field to a file.
 // POST 요청 (Multipart)
 // 업로드된 multipart/form-data의 각 파일 필드를 하나씩 읽어 저장
-async fn accept_form(mut multipart: Multipart) -> Result<Redirect, (StatusCode, String)> {
+async fn accept_form(
+    State(storage): State<Storage>,
+    mut multipart: Multipart,
+) -> Result<Redirect, (StatusCode, String)> {
     while let Ok(Some(field)) = multipart.next_field().await {
         // field.file_name()이 존재할 경우만 저장
         let file_name = if let Some(file_name) = field.file_name() {
@@ -102,15 +147,19 @@ async fn accept_form(mut multipart: Multipart) -> Result<Redirect, (StatusCode,
         };
 
         // 저장
-        stream_to_file(&file_name, field).await?;
+        stream_to_file(&storage, &file_name, field).await?;
     }
 
     Ok(Redirect::to("/"))
 }
 
 // 💾 파일 저장 함수
-// S: Stream<Item = Result<Bytes, E>> 형식의 스트림을 받아 파일로 저장
-async fn stream_to_file<S, E>(path: &str, stream: S) -> Result<(), (StatusCode, String)>
+// S: Stream<Item = Result<Bytes, E>> 형식의 스트림을 받아 선택된 저장소에 저장
+async fn stream_to_file<S, E>(
+    storage: &Storage,
+    path: &str,
+    stream: S,
+) -> Result<(), (StatusCode, String)>
 where
     S: Stream<Item = Result<Bytes, E>>,
     E: Into<BoxError>,
@@ -119,26 +168,12 @@ where
         return Err((StatusCode::BAD_REQUEST, "Invalid path".to_owned()));
     }
 
-    async {
-        // Convert the stream into an `AsyncRead`.
-        let body_with_io_error = stream.map_err(|err| io::Error::new(io::ErrorKind::Other, err));
-
-        // 내부에서 StreamReader로 AsyncRead처럼 다루고 tokio::io::copy()로 직접 디스크에 기록
-        let body_reader = StreamReader::new(body_with_io_error);
-
-        futures::pin_mut!(body_reader);
-
-        // Create the file. `File` implements `AsyncWrite`.
-        let path = std::path::Path::new(UPLOADS_DIRECTORY).join(path);
-        let mut file = BufWriter::new(File::create(path).await?);
-
-        // Copy the body into the file.
-        tokio::io::copy(&mut body_reader, &mut file).await?;
-
-        Ok::<_, io::Error>(())
-    }
-    .await
-    .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))
+    let size = storage
+        .save(path, stream)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    tracing::debug!(path, size, "saved upload");
+    Ok(())
 }
 
 // to prevent directory traversal attacks we ensure the path consists of exactly one normal
@@ -160,3 +195,90 @@ fn path_is_valid(path: &str) -> bool {
 
     components.count() == 1
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::s3::{S3Client, S3Config};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn uploads_can_be_downloaded_from_local_storage() {
+        let dir = std::env::temp_dir().join(format!("stream-to-file-{}", std::process::id()));
+        let app = app(Storage::local(&dir).await.unwrap());
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::post("/file/hello.txt")
+                    .body(Body::from("hello, world"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .clone()
+            .oneshot(Request::get("/file/hello.txt").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, "hello, world");
+
+        let response = app
+            .oneshot(
+                Request::get("/file/missing.txt")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        tokio::fs::remove_dir_all(dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn s3_downloads_redirect_to_a_presigned_url() {
+        let client = S3Client::new(S3Config {
+            endpoint: "http://localhost:9000".to_owned(),
+            region: "us-east-1".to_owned(),
+            bucket: "uploads".to_owned(),
+            access_key_id: "minioadmin".to_owned(),
+            secret_access_key: "minioadmin".to_owned(),
+            path_style: true,
+        })
+        .unwrap();
+
+        let response = app(Storage::S3(client))
+            .oneshot(
+                Request::get("/file/report.pdf")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::TEMPORARY_REDIRECT);
+        let location = response.headers()[header::LOCATION].to_str().unwrap();
+        assert!(location.starts_with("http://localhost:9000/uploads/report.pdf?"));
+        assert!(location.contains("X-Amz-Expires=900"));
+        assert!(location.contains("X-Amz-Signature="));
+    }
+}
+
+// 🧪 테스트 방법
+// 1. 로컬 디스크 (기본값)
+//    cargo run -p example-stream-to-file
+//    curl --data-binary @Cargo.toml http://127.0.0.1:3000/file/Cargo.toml
+//    curl http://127.0.0.1:3000/file/Cargo.toml
+//
+// 2. S3 호환 스토리지 (MinIO)
+//    docker run -p 9000:9000 minio/minio server /data   # 버킷 `uploads`를 미리 만들어 둠
+//    STORAGE=s3 S3_ENDPOINT=http://localhost:9000 S3_PATH_STYLE=1 S3_BUCKET=uploads \
+//      AWS_ACCESS_KEY_ID=minioadmin AWS_SECRET_ACCESS_KEY=minioadmin cargo run -p example-stream-to-file
+//    curl --data-binary @big.iso http://127.0.0.1:3000/file/big.iso   # 8 MiB 단위 multipart upload
+//    curl -L http://127.0.0.1:3000/file/big.iso -o big.iso            # presigned URL로 리다이렉트
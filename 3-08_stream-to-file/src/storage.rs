@@ -0,0 +1,69 @@
+//! 🗄️ 업로드 파일 저장소 (환경 변수로 선택)
+//!
+//! • `STORAGE=local` (기본값): `uploads/` 디렉토리에 저장
+//! • `STORAGE=s3`: S3 호환 버킷에 저장 (설정은 [`S3Config::from_env`] 참고)
+//!
+//! 어느 쪽이든 요청 바디를 스트림으로 흘려보내므로 파일 전체를 메모리에 올리지 않음
+
+use crate::s3::{S3Client, S3Config};
+use axum::{body::Bytes, BoxError};
+use futures::{Stream, TryStreamExt};
+use std::{io, path::PathBuf};
+use tokio::{fs::File, io::AsyncWriteExt, io::BufWriter};
+use tokio_util::io::StreamReader;
+
+// 업로드된 파일을 저장할 디렉토리 이름 정의
+const UPLOADS_DIRECTORY: &str = "uploads";
+
+#[derive(Clone)]
+pub enum Storage {
+    Local(PathBuf),
+    S3(S3Client),
+}
+
+impl Storage {
+    pub async fn from_env() -> Result<Self, BoxError> {
+        match std::env::var("STORAGE").as_deref() {
+            Ok("s3") => Ok(Self::S3(S3Client::new(S3Config::from_env()?)?)),
+            Ok("local") | Err(_) => Self::local(UPLOADS_DIRECTORY).await,
+            Ok(other) => {
+                Err(format!("unknown STORAGE `{other}` (expected `local` or `s3`)").into())
+            }
+        }
+    }
+
+    /// 디렉토리가 없으면 생성 (없으면 저장 시 에러 발생)
+    pub async fn local(dir: impl Into<PathBuf>) -> Result<Self, BoxError> {
+        let dir = dir.into();
+        tokio::fs::create_dir_all(&dir).await?;
+        Ok(Self::Local(dir))
+    }
+
+    /// `name`으로 저장하고 저장한 바이트 수를 반환 (`name`은 호출하는 쪽에서 검증)
+    pub async fn save<S, E>(&self, name: &str, stream: S) -> Result<u64, BoxError>
+    where
+        S: Stream<Item = Result<Bytes, E>>,
+        E: Into<BoxError>,
+    {
+        match self {
+            Self::Local(dir) => {
+                // Convert the stream into an `AsyncRead`.
+                let body_with_io_error = stream.map_err(io::Error::other);
+
+                // 내부에서 StreamReader로 AsyncRead처럼 다루고 tokio::io::copy()로 직접 디스크에 기록
+                let body_reader = StreamReader::new(body_with_io_error);
+
+                futures::pin_mut!(body_reader);
+
+                // Create the file. `File` implements `AsyncWrite`.
+                let mut file = BufWriter::new(File::create(dir.join(name)).await?);
+
+                // Copy the body into the file.
+                let size = tokio::io::copy(&mut body_reader, &mut file).await?;
+                file.flush().await?;
+                Ok(size)
+            }
+            Self::S3(client) => client.put_stream(name, stream).await,
+        }
+    }
+}
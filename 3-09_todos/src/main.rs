@@ -2,31 +2,49 @@
 //!
 //! API will be:
 //!
-//! - `GET /todos`: return a JSON list of Todos.
+//! - `GET /todos`: return a page of Todos (`?limit=20&cursor=..`, see `example-pagination`).
 //! - `POST /todos`: create a new Todo.
 //! - `PATCH /todos/{id}`: update a specific Todo.
 //! - `DELETE /todos/{id}`: delete a specific Todo.
+//! - `GET /metrics`: Prometheus metrics (`server_timing_seconds`).
+//!
+//! 모든 응답에 `Server-Timing` 헤더가 붙음 (`server_timing` 모듈)
+//! • 핸들러가 `Timings`로 extract → db → render 단계를 기록
+//! • 같은 값이 `server_timing_seconds{path, phase}` 히스토그램으로도 쌓임
+//!
+//! 타임아웃 같은 미들웨어 에러도 `{"message": ..., "request_id": ...}` JSON (common-errors의 `handle_middleware_error`)
+
+mod server_timing;
 
 use axum::{
     error_handling::HandleErrorLayer,
-    extract::{Path, Query, State},
+    extract::{Path, State},
     http::StatusCode,
+    middleware,
     response::IntoResponse,
     routing::{get, patch},
     Json, Router,
 };
+use example_common_errors::handle_middleware_error;
+use example_pagination::{CursorPagination, Page};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
 use serde::{Deserialize, Serialize};
+use server_timing::{server_timing, Timings};
 use std::{
-    collections::HashMap,
+    collections::BTreeMap,
+    ops::Bound,
     sync::{Arc, RwLock},
     time::Duration,
 };
-use tower::{BoxError, ServiceBuilder};
-use tower_http::trace::TraceLayer;
+use tower::ServiceBuilder;
+use tower_http::{
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::TraceLayer,
+};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use uuid::Uuid;
 
-/// 🏁 main()
+// 🏁 main()
 
 #[tokio::main]
 async fn main() {
@@ -41,59 +59,80 @@ async fn main() {
 
     // 빈 Todo 저장소 생성
     let db = Db::default();
+    let metrics = setup_metrics_recorder();
 
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(db, metrics)).await.unwrap();
+}
+
+fn app(db: Db, metrics: PrometheusHandle) -> Router {
     // Compose the routes
-    let app = Router::new()
+    Router::new()
         .route("/todos", get(todos_index).post(todos_create))
         .route("/todos/{id}", patch(todos_update).delete(todos_delete))
+        // 라우트 패턴(`/todos/{id}`)을 메트릭 라벨로 쓰기 위해 route_layer
+        .route_layer(middleware::from_fn(server_timing))
+        .route("/metrics", get(move || async move { metrics.render() }))
         // Add middleware to all routes
         .layer(
             ServiceBuilder::new()
-                .layer(HandleErrorLayer::new(|error: BoxError| async move {
-                    if error.is::<tower::timeout::error::Elapsed>() {
-                        Ok(StatusCode::REQUEST_TIMEOUT)
-                    } else {
-                        Err((
-                            StatusCode::INTERNAL_SERVER_ERROR,
-                            format!("Unhandled internal error: {error}"),
-                        ))
-                    }
-                })) // 에러 핸들링 미들웨어
+                .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid)) // 요청마다 x-request-id
+                .layer(PropagateRequestIdLayer::x_request_id()) // 에러 응답에도 같은 헤더
+                .layer(HandleErrorLayer::new(handle_middleware_error)) // 에러 핸들링 미들웨어 (JSON)
                 .timeout(Duration::from_secs(10)) // 요청 타임아웃 설정
                 .layer(TraceLayer::new_for_http()) // 요청/응답 로그 추적
                 .into_inner(),
         )
-        .with_state(db); // 공유 상태 등록
-
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
-        .await
-        .unwrap();
-    tracing::debug!("listening on {}", listener.local_addr().unwrap());
-    axum::serve(listener, app).await.unwrap();
+        .with_state(db) // 공유 상태 등록
 }
 
-// The query parameters for todos index
-#[derive(Debug, Deserialize, Default)]
-pub struct Pagination {
-    pub offset: Option<usize>,
-    pub limit: Option<usize>,
+// `server_timing_seconds` 히스토그램 버킷 (초, 단계별 시간은 대부분 아주 짧음)
+fn setup_metrics_recorder() -> PrometheusHandle {
+    const BUCKETS: &[f64] = &[
+        0.0001, 0.0005, 0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0,
+    ];
+
+    PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Full("server_timing_seconds".to_owned()), BUCKETS)
+        .unwrap()
+        .install_recorder()
+        .unwrap()
 }
 
-/// 📚 라우트별 핸들러
+// 📚 라우트별 핸들러
 
 // 1️⃣ GET /todos
-// Query<Pagination>으로 페이징 지원 (offset, limit)
-async fn todos_index(pagination: Query<Pagination>, State(db): State<Db>) -> impl IntoResponse {
-    let todos = db.read().unwrap();
-
-    let todos = todos
-        .values()
-        .skip(pagination.offset.unwrap_or(0)) // 전체 리스트에서 skip().take()로 범위 제한
-        .take(pagination.limit.unwrap_or(usize::MAX))
-        .cloned()
-        .collect::<Vec<_>>();
-
-    Json(todos) // JSON 형식으로 반환
+// CursorPagination으로 페이징 지원 (limit, cursor = 이전 페이지 마지막 Todo의 id)
+async fn todos_index(
+    timings: Timings,
+    pagination: CursorPagination,
+    State(db): State<Db>,
+) -> impl IntoResponse {
+    timings.mark("extract"); // 여기까지가 추출기 실행 시간
+
+    let after = match pagination.parse_after::<Uuid>() {
+        Ok(after) => after,
+        Err(rejection) => return rejection.into_response(),
+    };
+
+    let todos = timings.time("db", || {
+        // BTreeMap은 id 순서로 정렬되어 있으므로 커서 다음 id부터 바로 읽을 수 있음
+        let start = after.map_or(Bound::Unbounded, Bound::Excluded);
+        db.read()
+            .unwrap()
+            .range((start, Bound::Unbounded))
+            .map(|(_, todo)| todo.clone())
+            .take(pagination.fetch_limit() as usize)
+            .collect::<Vec<_>>()
+    });
+
+    // JSON 직렬화는 into_response에서 일어나므로 그 시간을 render로 기록
+    timings.time("render", || {
+        Page::cursor(todos, &pagination, |todo| todo.id.to_string()).into_response()
+    })
 }
 
 #[derive(Debug, Deserialize)]
@@ -102,17 +141,27 @@ struct CreateTodo {
 }
 
 // 2️⃣ POST /todos
-async fn todos_create(State(db): State<Db>, Json(input): Json<CreateTodo>) -> impl IntoResponse {
+async fn todos_create(
+    timings: Timings,
+    State(db): State<Db>,
+    Json(input): Json<CreateTodo>,
+) -> impl IntoResponse {
+    timings.mark("extract");
+
     let todo = Todo {
-        id: Uuid::new_v4(), // 고유 ID 부여
-        text: input.text,   // 클라이언트에서 받은 text 값으로 새로운 Todo 생성
+        // 시간 순으로 정렬되는 v7 → 새 Todo는 항상 맨 뒤에 붙음
+        // (랜덤 v4면 페이지를 넘기는 도중 생긴 Todo가 이미 지나간 커서 앞에 끼어 누락될 수 있음)
+        id: Uuid::now_v7(),
+        text: input.text, // 클라이언트에서 받은 text 값으로 새로운 Todo 생성
         completed: false,
     };
 
-    db.write().unwrap().insert(todo.id, todo.clone());
+    timings.time("db", || db.write().unwrap().insert(todo.id, todo.clone()));
 
     // 반환 시 StatusCode::CREATED (201)과 JSON 함께 응답
-    (StatusCode::CREATED, Json(todo))
+    timings.time("render", || {
+        (StatusCode::CREATED, Json(todo)).into_response()
+    })
 }
 
 #[derive(Debug, Deserialize)]
@@ -123,36 +172,48 @@ struct UpdateTodo {
 
 // 3️⃣ PATCH /todos/{id}
 async fn todos_update(
+    timings: Timings,
     Path(id): Path<Uuid>,
     State(db): State<Db>,
     Json(input): Json<UpdateTodo>,
 ) -> Result<impl IntoResponse, StatusCode> {
-    // 기존 Todo를 읽고 일부 필드를 수정
-    let mut todo = db
-        .read()
-        .unwrap()
-        .get(&id)
-        .cloned()
-        .ok_or(StatusCode::NOT_FOUND)?; // 존재하지 않으면 404 Not Found
+    timings.mark("extract");
 
-    if let Some(text) = input.text {
-        todo.text = text;
-    }
+    let todo = timings.time("db", || {
+        // 기존 Todo를 읽고 일부 필드를 수정
+        let mut todo = db
+            .read()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .ok_or(StatusCode::NOT_FOUND)?; // 존재하지 않으면 404 Not Found
 
-    if let Some(completed) = input.completed {
-        todo.completed = completed;
-    }
+        if let Some(text) = input.text {
+            todo.text = text;
+        }
+
+        if let Some(completed) = input.completed {
+            todo.completed = completed;
+        }
 
-    db.write().unwrap().insert(todo.id, todo.clone());
+        db.write().unwrap().insert(todo.id, todo.clone());
+        Ok::<_, StatusCode>(todo)
+    })?;
 
     // 수정 후 다시 저장하고 JSON 반환
-    Ok(Json(todo))
+    Ok(timings.time("render", || Json(todo).into_response()))
 }
 
 // 4️⃣ DELETE /todos/{id}
-async fn todos_delete(Path(id): Path<Uuid>, State(db): State<Db>) -> impl IntoResponse {
+async fn todos_delete(
+    timings: Timings,
+    Path(id): Path<Uuid>,
+    State(db): State<Db>,
+) -> impl IntoResponse {
+    timings.mark("extract");
+
     // ID 기반으로 삭제
-    if db.write().unwrap().remove(&id).is_some() {
+    if timings.time("db", || db.write().unwrap().remove(&id).is_some()) {
         StatusCode::NO_CONTENT // 성공 시 204 No Content
     } else {
         StatusCode::NOT_FOUND // 없으면 404 Not Found
@@ -161,9 +222,9 @@ async fn todos_delete(Path(id): Path<Uuid>, State(db): State<Db>) -> impl IntoRe
 
 /// 📌 Db 타입 정의
 /// rc<RwLock<...>> → 멀티 스레드 안전한 공유 상태
-/// HashMap<Uuid, Todo> → ID별 Todo 저장소
+/// BTreeMap<Uuid, Todo> → ID별 Todo 저장소 (v7 id 순서 = 생성 순서, 커서 페이지네이션에 사용)
 /// 실무에서는 보통 DB 대체 용도로 쓰는 메모리 캐시 구조입니다.
-type Db = Arc<RwLock<HashMap<Uuid, Todo>>>;
+type Db = Arc<RwLock<BTreeMap<Uuid, Todo>>>;
 
 /// 📌 Todo 구조체
 /// > Serialize → JSON 응답용
@@ -175,14 +236,76 @@ struct Todo {
     completed: bool,
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    fn test_app() -> Router {
+        // 전역 recorder를 설치하지 않는 handle (테스트마다 새로 만들 수 있음)
+        let metrics = PrometheusBuilder::new().build_recorder().handle();
+        app(Db::default(), metrics)
+    }
+
+    async fn send(app: &Router, request: Request<Body>) -> Value {
+        let response = app.clone().oneshot(request).await.unwrap();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    async fn create(app: &Router, text: &str) {
+        let request = Request::post("/todos")
+            .header("content-type", "application/json")
+            .body(Body::from(json!({ "text": text }).to_string()))
+            .unwrap();
+        send(app, request).await;
+    }
+
+    async fn page(app: &Router, uri: &str) -> Value {
+        send(app, Request::get(uri).body(Body::empty()).unwrap()).await
+    }
+
+    fn texts(page: &Value) -> Vec<&str> {
+        page["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|todo| todo["text"].as_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn todo_created_while_paging_is_not_skipped() {
+        let app = test_app();
+        for text in ["a", "b", "c"] {
+            create(&app, text).await;
+        }
+
+        let first = page(&app, "/todos?limit=2").await;
+        assert_eq!(texts(&first), ["a", "b"]);
+
+        // 첫 페이지를 받은 뒤에 생긴 Todo도 다음 페이지에 나와야 함
+        create(&app, "d").await;
+
+        let cursor = first["next_cursor"].as_str().unwrap();
+        let second = page(&app, &format!("/todos?limit=2&cursor={cursor}")).await;
+        assert_eq!(texts(&second), ["c", "d"]);
+    }
+}
+
 // 🧪 테스트 예시 (Postman 또는 curl)
 //
 // ✅ 새 Todo 추가
 // curl -X POST http://localhost:3000/todos -H 'Content-Type: application/json' \
 // -d '{"text": "Buy milk"}'
 //
-// ✅ 전체 Todo 리스트 조회
-// curl http://localhost:3000/todos
+// ✅ Todo 리스트 조회 (Link 헤더의 rel="next" URL로 다음 페이지)
+// curl -i 'http://localhost:3000/todos?limit=2'
+// → link: </todos?limit=2>; rel="first", </todos?limit=2&cursor=...>; rel="next"
+// → {"items":[...],"limit":2,"next_cursor":"..."}
 //
 // ✅ Todo 업데이트
 // curl -X PATCH http://localhost:3000/todos/<id> -H 'Content-Type: application/json' \
@@ -191,13 +314,19 @@ struct Todo {
 // ✅ Todo 삭제
 // curl -X DELETE http://localhost:3000/todos/<id>
 //
+// ⏱️ Server-Timing 확인
+// curl -i http://localhost:3000/todos
+// → server-timing: extract;dur=0.012, db;dur=0.004, render;dur=0.009, total;dur=0.101
+// 브라우저 개발자 도구 Network → Timing 탭에도 같은 단계가 표시됨
+// curl http://localhost:3000/metrics | grep server_timing_seconds
+//
 // 🔒 참고: 실무 적용 시 고려사항
-//  - 데이터 저장소: PostgreSQL, MongoDB 등 (예제는 메모리(HashMap))
+//  - 데이터 저장소: PostgreSQL, MongoDB 등 (예제는 메모리(BTreeMap))
 //  - 인증 처리: JWT, OAuth (예제는 없음)
 //  - 데이터 영속성: DB연동 필요 (예제는 없음)
 //  - 동시성 충돌: 트랜잭션/락 관리 필요 (예제는 단순 RwLock)
 //
 // ✅ 요약
 // 	 - Axum의 RESTful 구조 이해에 이상적인 예제
-// 	 - 상태는 Arc<RwLock<HashMap<...>>>으로 관리
+// 	 - 상태는 Arc<RwLock<BTreeMap<...>>>으로 관리
 // 	 - 실무로 확장하려면 DB, 인증, 트랜잭션 처리 필요
@@ -0,0 +1,187 @@
+//! ⏱️ `Server-Timing` 미들웨어
+//!
+//! 핸들러가 단계별 소요 시간을 `Timings`에 기록하면, 응답에 `Server-Timing` 헤더로 붙이고
+//! 같은 값을 `server_timing_seconds{path, phase}` 히스토그램으로도 남김
+//!
+//! ```text
+//! Server-Timing: extract;dur=0.041, db;dur=0.012, render;dur=0.020, total;dur=0.160
+//! ```
+//!
+//! • 브라우저 개발자 도구(Network → Timing)에 그대로 표시됨
+//! • 단계 이름은 핸들러가 정함 (`extract`, `db`, `render`는 관례일 뿐)
+//! • 내부 구조가 드러나므로 운영 환경에서는 신뢰할 수 있는 클라이언트에게만 보내는 편이 좋음
+
+use axum::{
+    extract::{FromRequestParts, MatchedPath, Request},
+    http::{request::Parts, HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use std::{
+    convert::Infallible,
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+pub static SERVER_TIMING: HeaderName = HeaderName::from_static("server-timing");
+
+/// 요청 하나의 단계별 소요 시간 (핸들러 인자로 받음)
+#[derive(Clone)]
+pub struct Timings(Arc<Mutex<Inner>>);
+
+struct Inner {
+    // 마지막 `mark` 시각 (처음에는 미들웨어에 들어온 시각)
+    last: Instant,
+    phases: Vec<(&'static str, Duration)>,
+}
+
+impl Timings {
+    fn new() -> Self {
+        Self(Arc::new(Mutex::new(Inner {
+            last: Instant::now(),
+            phases: Vec::new(),
+        })))
+    }
+
+    /// 직전 `mark`(처음이면 요청 시작)부터 지금까지를 한 단계로 기록
+    ///
+    /// 핸들러 첫 줄에서 `mark("extract")` → 추출기들이 걸린 시간
+    pub fn mark(&self, phase: &'static str) {
+        let mut inner = self.0.lock().unwrap();
+        let now = Instant::now();
+        let elapsed = now - inner.last;
+        inner.last = now;
+        inner.phases.push((phase, elapsed));
+    }
+
+    /// `f`를 실행하는 데 걸린 시간을 기록
+    pub fn time<T>(&self, phase: &'static str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let value = f();
+        self.record(phase, start.elapsed());
+        value
+    }
+
+    /// 직접 잰 시간을 기록 (`.await`가 끼어 있는 구간 등)
+    pub fn record(&self, phase: &'static str, elapsed: Duration) {
+        let mut inner = self.0.lock().unwrap();
+        inner.phases.push((phase, elapsed));
+        inner.last = Instant::now();
+    }
+
+    fn phases(&self) -> Vec<(&'static str, Duration)> {
+        self.0.lock().unwrap().phases.clone()
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Timings {
+    type Rejection = Infallible;
+
+    /// 미들웨어가 없으면 어디에도 보고되지 않는 빈 `Timings` (핸들러는 그대로 동작)
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<Timings>()
+            .cloned()
+            .unwrap_or_else(Timings::new))
+    }
+}
+
+/// 요청마다 `Timings`를 만들어 넘기고, 응답에 `Server-Timing` 헤더와 메트릭을 남김
+///
+/// `route_layer`로 붙여야 메트릭의 `path` 라벨에 `/todos/{id}` 같은 라우트 패턴이 들어감
+pub async fn server_timing(mut request: Request, next: Next) -> Response {
+    let start = Instant::now();
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_owned())
+        .unwrap_or_else(|| request.uri().path().to_owned());
+
+    let timings = Timings::new();
+    request.extensions_mut().insert(timings.clone());
+
+    let mut response = next.run(request).await;
+
+    let mut phases = timings.phases();
+    phases.push(("total", start.elapsed()));
+
+    for (phase, elapsed) in &phases {
+        metrics::histogram!(
+            "server_timing_seconds",
+            "path" => path.clone(),
+            "phase" => *phase,
+        )
+        .record(elapsed.as_secs_f64());
+    }
+
+    // 헤더 값의 dur 단위는 밀리초
+    let mut value = String::new();
+    for (phase, elapsed) in &phases {
+        if !value.is_empty() {
+            value.push_str(", ");
+        }
+        let _ = write!(value, "{phase};dur={:.3}", elapsed.as_secs_f64() * 1000.0);
+    }
+    if let Ok(value) = HeaderValue::from_str(&value) {
+        response.headers_mut().append(SERVER_TIMING.clone(), value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn handler_phases_are_reported_in_order() {
+        let app = Router::new()
+            .route(
+                "/slow",
+                get(|timings: Timings| async move {
+                    timings.mark("extract");
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    timings.mark("db");
+                    timings.time("render", || "done")
+                }),
+            )
+            .route_layer(middleware::from_fn(server_timing));
+
+        let response = app
+            .oneshot(Request::get("/slow").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let header = response.headers()[&SERVER_TIMING].to_str().unwrap();
+        let phases: Vec<(&str, f64)> = header
+            .split(", ")
+            .map(|entry| {
+                let (name, dur) = entry.split_once(";dur=").unwrap();
+                (name, dur.parse().unwrap())
+            })
+            .collect();
+
+        let names: Vec<_> = phases.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["extract", "db", "render", "total"]);
+        assert!(phases[1].1 >= 20.0, "db phase should include the sleep");
+        assert!(phases[3].1 >= phases[1].1);
+    }
+
+    #[tokio::test]
+    async fn extractor_works_without_the_middleware() {
+        let app = Router::new().route(
+            "/",
+            get(|timings: Timings| async move { timings.time("render", || "ok") }),
+        );
+
+        let response = app
+            .oneshot(Request::get("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        assert!(!response.headers().contains_key(&SERVER_TIMING));
+    }
+}
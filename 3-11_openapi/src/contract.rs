@@ -0,0 +1,305 @@
+//! 📜 OpenAPI 계약(contract) 테스트 도구
+//!
+//! 기록해 둔 요청(`contract/recorded.json`)을 앱에 다시 보내고,
+//! 요청과 응답이 생성된 OpenAPI 문서와 맞는지 JSON Schema로 검증함
+//!
+//! - 요청: 문서에 있는 경로/메서드인지, 경로·쿼리 파라미터와 본문이 스키마에 맞는지
+//! - 응답: 문서에 있는 상태 코드인지, 본문이 그 상태 코드의 스키마에 맞는지 (본문이 없어야 하면 비어 있는지)
+//! - 문서에 없는 필드도 실패로 봄 → 객체 스키마에 `additionalProperties: false`를 넣어서 검증
+//!   → 핸들러가 필드를 추가/삭제/타입 변경하고 `ToSchema`를 고치지 않으면 테스트가 실패
+//!
+//! 기록 형식
+//!
+//! ```json
+//! { "name": "create", "method": "POST", "uri": "/todos", "body": { "text": "x" },
+//!   "status": 201, "capture": { "todo_id": "id" } }
+//! ```
+//!
+//! `capture`는 응답 JSON의 필드를 변수로 저장 → 뒤의 `uri`에서 `{{todo_id}}`로 사용
+
+use axum::{body::Body, http::Request, Router};
+use http_body_util::BodyExt;
+use jsonschema::Draft;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use tower::ServiceExt;
+
+#[derive(Debug, Deserialize)]
+pub struct Interaction {
+    pub name: String,
+    pub method: String,
+    pub uri: String,
+    #[serde(default)]
+    pub body: Option<Value>,
+    /// 기록 당시의 상태 코드
+    pub status: u16,
+    #[serde(default)]
+    pub capture: HashMap<String, String>,
+}
+
+pub struct Contract {
+    spec: Value,
+}
+
+impl Contract {
+    pub fn new(mut spec: Value) -> Self {
+        disallow_additional_properties(&mut spec["components"]);
+        Self { spec }
+    }
+
+    /// 기록을 순서대로 보내고 계약 위반을 모두 모아서 반환 (비어 있으면 통과)
+    pub async fn replay(&self, app: Router, interactions: &[Interaction]) -> Vec<String> {
+        let mut variables = HashMap::new();
+        let mut failures = Vec::new();
+
+        for interaction in interactions {
+            let uri = substitute(&interaction.uri, &variables);
+            let label = format!("{} ({} {uri})", interaction.name, interaction.method);
+
+            let errors = self.check_request(&interaction.method, &uri, interaction.body.as_ref());
+            failures.extend(
+                errors
+                    .into_iter()
+                    .map(|err| format!("{label}: request {err}")),
+            );
+
+            let request = Request::builder()
+                .method(interaction.method.as_str())
+                .uri(&uri);
+            let request = match &interaction.body {
+                Some(body) => request
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string())),
+                None => request.body(Body::empty()),
+            }
+            .unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+
+            let status = response.status().as_u16();
+            let content_type = response
+                .headers()
+                .get("content-type")
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+
+            if status != interaction.status {
+                failures.push(format!(
+                    "{label}: expected status {}, got {status}",
+                    interaction.status
+                ));
+            }
+            let errors = self.check_response(
+                &interaction.method,
+                &uri,
+                status,
+                content_type.as_deref(),
+                &body,
+            );
+            failures.extend(
+                errors
+                    .into_iter()
+                    .map(|err| format!("{label}: response {err}")),
+            );
+
+            for (variable, field) in &interaction.capture {
+                let value = serde_json::from_slice::<Value>(&body)
+                    .ok()
+                    .and_then(|body| body.get(field).and_then(Value::as_str).map(str::to_owned));
+                match value {
+                    Some(value) => {
+                        variables.insert(variable.clone(), value);
+                    }
+                    None => failures.push(format!("{label}: no `{field}` to capture")),
+                }
+            }
+        }
+        failures
+    }
+
+    pub fn check_request(&self, method: &str, uri: &str, body: Option<&Value>) -> Vec<String> {
+        let (path, query) = uri.split_once('?').unwrap_or((uri, ""));
+        let (operation, path_params) = match self.operation(method, path) {
+            Ok(found) => found,
+            Err(err) => return vec![err],
+        };
+        let mut query: HashMap<&str, &str> = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+            .collect();
+
+        let mut errors = Vec::new();
+        for param in operation["parameters"].as_array().into_iter().flatten() {
+            let name = param["name"].as_str().unwrap_or_default();
+            let value = match param["in"].as_str() {
+                Some("path") => path_params.get(name).map(String::as_str),
+                Some("query") => query.remove(name),
+                _ => continue,
+            };
+            match value {
+                Some(value) => {
+                    let schema = &param["schema"];
+                    errors.extend(self.validate(schema, &coerce(value, schema), name));
+                }
+                None if param["required"] == true => {
+                    errors.push(format!("missing required parameter `{name}`"));
+                }
+                None => {}
+            }
+        }
+        errors.extend(
+            query
+                .keys()
+                .map(|name| format!("undocumented query parameter `{name}`")),
+        );
+
+        let request_body = operation.get("requestBody");
+        match (body, request_body) {
+            (Some(body), Some(documented)) => {
+                let schema = &documented["content"]["application/json"]["schema"];
+                errors.extend(self.validate(schema, body, "body"));
+            }
+            (Some(_), None) => errors.push("body is not documented".to_owned()),
+            (None, Some(documented)) if documented["required"] == true => {
+                errors.push("missing required body".to_owned());
+            }
+            (None, _) => {}
+        }
+        errors
+    }
+
+    pub fn check_response(
+        &self,
+        method: &str,
+        uri: &str,
+        status: u16,
+        content_type: Option<&str>,
+        body: &[u8],
+    ) -> Vec<String> {
+        let path = uri.split_once('?').map_or(uri, |(path, _)| path);
+        let operation = match self.operation(method, path) {
+            Ok((operation, _)) => operation,
+            Err(err) => return vec![err],
+        };
+        let responses = &operation["responses"];
+        let Some(documented) = responses
+            .get(status.to_string())
+            .or_else(|| responses.get("default"))
+        else {
+            return vec![format!("status {status} is not documented")];
+        };
+
+        let schema = documented["content"]
+            .get("application/json")
+            .map(|media| &media["schema"]);
+        match (body.is_empty(), schema) {
+            (true, None) => Vec::new(),
+            (true, Some(_)) => vec![format!("status {status} should have a JSON body")],
+            (false, None) => vec![format!("status {status} should not have a body")],
+            (false, Some(schema)) => {
+                if !content_type.is_some_and(|value| value.starts_with("application/json")) {
+                    return vec![format!("content-type {content_type:?} is not JSON")];
+                }
+                match serde_json::from_slice::<Value>(body) {
+                    Ok(body) => self.validate(schema, &body, "body"),
+                    Err(err) => vec![format!("body is not JSON: {err}")],
+                }
+            }
+        }
+    }
+
+    /// (operation, 경로 파라미터)
+    fn operation(
+        &self,
+        method: &str,
+        path: &str,
+    ) -> Result<(&Value, HashMap<String, String>), String> {
+        let paths = self.spec["paths"].as_object().into_iter().flatten();
+        for (template, item) in paths {
+            if let Some(params) = match_path(template, path) {
+                return item
+                    .get(method.to_lowercase())
+                    .map(|operation| (operation, params))
+                    .ok_or_else(|| format!("{method} {template} is not documented"));
+            }
+        }
+        Err(format!("path {path} is not documented"))
+    }
+
+    fn validate(&self, schema: &Value, instance: &Value, at: &str) -> Vec<String> {
+        // `$ref: "#/components/schemas/..."`를 풀 수 있도록 같은 문서에 components를 넣음
+        let mut root = schema.clone();
+        root["components"] = self.spec["components"].clone();
+        let validator = jsonschema::options()
+            .with_draft(Draft::Draft202012)
+            .should_validate_formats(true)
+            .build(&root)
+            .unwrap_or_else(|err| panic!("invalid schema for {at}: {err}"));
+        validator
+            .iter_errors(instance)
+            .map(|err| format!("{at}{}: {err}", err.instance_path))
+            .collect()
+    }
+}
+
+/// `/todos/{id}`와 `/todos/abc` → `{"id": "abc"}`
+fn match_path(template: &str, path: &str) -> Option<HashMap<String, String>> {
+    let template: Vec<&str> = template.split('/').collect();
+    let path: Vec<&str> = path.split('/').collect();
+    if template.len() != path.len() {
+        return None;
+    }
+    let mut params = HashMap::new();
+    for (expected, actual) in template.iter().zip(path) {
+        match expected
+            .strip_prefix('{')
+            .and_then(|name| name.strip_suffix('}'))
+        {
+            Some(name) => {
+                params.insert(name.to_owned(), actual.to_owned());
+            }
+            None if *expected == actual => {}
+            None => return None,
+        }
+    }
+    Some(params)
+}
+
+/// 파라미터 문자열을 스키마 타입에 맞는 JSON 값으로 (`"10"` → `10`)
+fn coerce(value: &str, schema: &Value) -> Value {
+    let types = match &schema["type"] {
+        Value::String(ty) => vec![ty.as_str()],
+        Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    if types
+        .iter()
+        .any(|ty| matches!(*ty, "integer" | "number" | "boolean"))
+    {
+        if let Ok(value) = serde_json::from_str::<Value>(value) {
+            return value;
+        }
+    }
+    Value::String(value.to_owned())
+}
+
+fn substitute(uri: &str, variables: &HashMap<String, String>) -> String {
+    variables.iter().fold(uri.to_owned(), |uri, (name, value)| {
+        uri.replace(&format!("{{{{{name}}}}}"), value)
+    })
+}
+
+fn disallow_additional_properties(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            if object.contains_key("properties") && !object.contains_key("additionalProperties") {
+                object.insert("additionalProperties".to_owned(), Value::Bool(false));
+            }
+            object.values_mut().for_each(disallow_additional_properties);
+        }
+        Value::Array(items) => items.iter_mut().for_each(disallow_additional_properties),
+        _ => {}
+    }
+}
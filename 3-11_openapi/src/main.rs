@@ -0,0 +1,457 @@
+//! utoipa로 OpenAPI 문서를 생성하고 Swagger UI로 제공하는 예제입니다.
+//!
+//! todos 예제와 같은 CRUD API에 `#[utoipa::path]`를 붙이고,
+//! `utoipa_axum::OpenApiRouter`로 라우트 등록과 문서 등록을 한 번에 처리합니다.
+//!
+//! - `GET /todos`: Todo 목록 (offset, limit 페이징)
+//! - `POST /todos`: Todo 생성
+//! - `GET /todos/{id}`: Todo 조회
+//! - `PATCH /todos/{id}`: Todo 수정
+//! - `DELETE /todos/{id}`: Todo 삭제
+//! - `GET /api-docs/openapi.json`: OpenAPI 3.1 문서
+//! - `GET /swagger`: Swagger UI
+//!
+//! ```not_rust
+//! cargo run -p example-openapi
+//! ```
+//!
+//! 브라우저에서 <http://127.0.0.1:3000/swagger> 접속
+//!
+//! 테스트의 계약 테스트(`contract.rs`)는 `contract/recorded.json`의 요청을 다시 보내고
+//! 요청/응답이 생성된 문서의 스키마와 맞는지 검사 → 핸들러와 문서가 어긋나면 실패
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+use tower_http::trace::TraceLayer;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use utoipa::{IntoParams, OpenApi, ToSchema};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_swagger_ui::SwaggerUi;
+use uuid::Uuid;
+
+// 계약 테스트 도구 (기록된 요청 재생 + 스키마 검증)
+#[cfg(test)]
+mod contract;
+
+const OPENAPI_JSON: &str = "/api-docs/openapi.json";
+const TODO_TAG: &str = "todo";
+
+/// 🏁 main()
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+                format!("{}=debug,tower_http=debug", env!("CARGO_CRATE_NAME")).into()
+            }),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app().layer(TraceLayer::new_for_http()))
+        .await
+        .unwrap();
+}
+
+/// 📘 문서 전체 정보 (제목, 버전, 태그 등)
+/// 경로(paths)는 아래 `api_router()`에서 라우트와 함께 등록됨
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Todo API", description = "utoipa로 문서화한 Todo CRUD API"),
+    tags((name = TODO_TAG, description = "Todo 관리"))
+)]
+struct ApiDoc;
+
+// 🧭 API 라우터 + OpenAPI 문서
+// routes!()에 넘긴 핸들러는 라우트와 문서에 동시에 등록되므로 둘이 어긋날 수 없음
+// (같은 경로의 핸들러들은 하나의 routes!()에 모아야 함)
+fn api_router() -> OpenApiRouter<Db> {
+    OpenApiRouter::with_openapi(ApiDoc::openapi())
+        .routes(routes!(todos_index, todos_create))
+        .routes(routes!(todos_get, todos_update, todos_delete))
+}
+
+fn app() -> Router {
+    let (router, api) = api_router().with_state(Db::default()).split_for_parts();
+
+    // SwaggerUi는 UI 정적 파일과 OpenAPI JSON 라우트를 함께 제공
+    router.merge(SwaggerUi::new("/swagger").url(OPENAPI_JSON, api))
+}
+
+/// 📌 목록 조회용 쿼리 파라미터
+#[derive(Debug, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct Pagination {
+    /// 건너뛸 Todo 개수
+    pub offset: Option<usize>,
+    /// 최대 반환 개수
+    pub limit: Option<usize>,
+}
+
+/// 📌 Todo 생성 요청
+#[derive(Debug, Deserialize, ToSchema)]
+struct CreateTodo {
+    #[schema(example = "Buy milk")]
+    text: String,
+}
+
+/// 📌 Todo 수정 요청 (보낸 필드만 변경)
+#[derive(Debug, Deserialize, ToSchema)]
+struct UpdateTodo {
+    text: Option<String>,
+    completed: Option<bool>,
+}
+
+/// 📌 Todo
+#[derive(Debug, Serialize, Clone, ToSchema)]
+struct Todo {
+    id: Uuid,
+    #[schema(example = "Buy milk")]
+    text: String,
+    completed: bool,
+}
+
+/// 📌 Db 타입 정의 (todos 예제와 동일한 메모리 저장소)
+type Db = Arc<RwLock<HashMap<Uuid, Todo>>>;
+
+// 📚 라우트별 핸들러
+
+// 1️⃣ GET /todos
+/// Todo 목록 조회
+#[utoipa::path(
+    get,
+    path = "/todos",
+    tag = TODO_TAG,
+    params(Pagination),
+    responses((status = OK, description = "Todo 목록", body = [Todo]))
+)]
+async fn todos_index(pagination: Query<Pagination>, State(db): State<Db>) -> Json<Vec<Todo>> {
+    let todos = db.read().unwrap();
+
+    let todos = todos
+        .values()
+        .skip(pagination.offset.unwrap_or(0))
+        .take(pagination.limit.unwrap_or(usize::MAX))
+        .cloned()
+        .collect();
+
+    Json(todos)
+}
+
+// 2️⃣ POST /todos
+/// Todo 생성
+#[utoipa::path(
+    post,
+    path = "/todos",
+    tag = TODO_TAG,
+    request_body = CreateTodo,
+    responses((status = CREATED, description = "생성된 Todo", body = Todo))
+)]
+async fn todos_create(
+    State(db): State<Db>,
+    Json(input): Json<CreateTodo>,
+) -> (StatusCode, Json<Todo>) {
+    let todo = Todo {
+        id: Uuid::new_v4(),
+        text: input.text,
+        completed: false,
+    };
+
+    db.write().unwrap().insert(todo.id, todo.clone());
+
+    (StatusCode::CREATED, Json(todo))
+}
+
+// 3️⃣ GET /todos/{id}
+/// Todo 조회
+#[utoipa::path(
+    get,
+    path = "/todos/{id}",
+    tag = TODO_TAG,
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    responses(
+        (status = OK, description = "Todo", body = Todo),
+        (status = NOT_FOUND, description = "존재하지 않는 Todo")
+    )
+)]
+async fn todos_get(Path(id): Path<Uuid>, State(db): State<Db>) -> Result<Json<Todo>, StatusCode> {
+    db.read()
+        .unwrap()
+        .get(&id)
+        .cloned()
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+// 4️⃣ PATCH /todos/{id}
+/// Todo 수정
+#[utoipa::path(
+    patch,
+    path = "/todos/{id}",
+    tag = TODO_TAG,
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    request_body = UpdateTodo,
+    responses(
+        (status = OK, description = "수정된 Todo", body = Todo),
+        (status = NOT_FOUND, description = "존재하지 않는 Todo")
+    )
+)]
+async fn todos_update(
+    Path(id): Path<Uuid>,
+    State(db): State<Db>,
+    Json(input): Json<UpdateTodo>,
+) -> Result<Json<Todo>, StatusCode> {
+    let mut db = db.write().unwrap();
+    let todo = db.get_mut(&id).ok_or(StatusCode::NOT_FOUND)?;
+
+    if let Some(text) = input.text {
+        todo.text = text;
+    }
+
+    if let Some(completed) = input.completed {
+        todo.completed = completed;
+    }
+
+    Ok(Json(todo.clone()))
+}
+
+// 5️⃣ DELETE /todos/{id}
+/// Todo 삭제
+#[utoipa::path(
+    delete,
+    path = "/todos/{id}",
+    tag = TODO_TAG,
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    responses(
+        (status = NO_CONTENT, description = "삭제됨"),
+        (status = NOT_FOUND, description = "존재하지 않는 Todo")
+    )
+)]
+async fn todos_delete(Path(id): Path<Uuid>, State(db): State<Db>) -> StatusCode {
+    if db.write().unwrap().remove(&id).is_some() {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use contract::{Contract, Interaction};
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use std::collections::BTreeSet;
+    use tower::ServiceExt;
+
+    // 문서에 나와야 하는 (method, path) 목록
+    // 라우트를 추가했는데 여기에 없으면 아래 테스트가 실패하므로 함께 갱신해야 함
+    const EXPECTED_OPERATIONS: &[(&str, &str)] = &[
+        ("get", "/todos"),
+        ("post", "/todos"),
+        ("get", "/todos/{id}"),
+        ("patch", "/todos/{id}"),
+        ("delete", "/todos/{id}"),
+    ];
+
+    async fn get_json(uri: &str) -> Value {
+        let response = app()
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    fn spec_operations(spec: &Value) -> BTreeSet<(String, String)> {
+        spec["paths"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .flat_map(|(path, item)| {
+                item.as_object()
+                    .unwrap()
+                    .keys()
+                    .map(move |method| (method.clone(), path.clone()))
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn spec_lists_every_route() {
+        let spec = get_json(OPENAPI_JSON).await;
+
+        let expected = EXPECTED_OPERATIONS
+            .iter()
+            .map(|(method, path)| (method.to_string(), path.to_string()))
+            .collect::<BTreeSet<_>>();
+        assert_eq!(spec_operations(&spec), expected);
+    }
+
+    // 문서에 있는 모든 (method, path)가 실제 라우터에 등록되어 있는지 확인
+    // 핸들러의 404와 구분하기 위해 등록되지 않은 경로는 fallback(418)으로 보냄
+    #[tokio::test]
+    async fn every_documented_operation_is_routed() {
+        let spec = get_json(OPENAPI_JSON).await;
+        let (router, _) = api_router().with_state(Db::default()).split_for_parts();
+        let router = router.fallback(|| async { StatusCode::IM_A_TEAPOT });
+
+        for (method, path) in spec_operations(&spec) {
+            let uri = path.replace("{id}", &Uuid::nil().to_string());
+            let request = Request::builder()
+                .method(method.to_uppercase().as_str())
+                .uri(&uri)
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"text":"x"}"#))
+                .unwrap();
+            let status = router.clone().oneshot(request).await.unwrap().status();
+
+            assert!(
+                status != StatusCode::IM_A_TEAPOT && status != StatusCode::METHOD_NOT_ALLOWED,
+                "{method} {path} is documented but not routed ({status})"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn spec_includes_schemas() {
+        let spec = get_json(OPENAPI_JSON).await;
+
+        assert_eq!(spec["info"]["title"], "Todo API");
+        let schemas = spec["components"]["schemas"].as_object().unwrap();
+        for name in ["Todo", "CreateTodo", "UpdateTodo"] {
+            assert!(schemas.contains_key(name), "missing schema {name}");
+        }
+        assert_eq!(
+            spec["paths"]["/todos"]["get"]["parameters"][0]["name"],
+            "offset"
+        );
+    }
+
+    // 📜 계약 테스트: 기록된 요청을 재생하고 요청/응답을 문서의 스키마로 검증
+    #[tokio::test]
+    async fn recorded_requests_match_the_contract() {
+        let contract = Contract::new(get_json(OPENAPI_JSON).await);
+        let interactions: Vec<Interaction> =
+            serde_json::from_str(include_str!("../contract/recorded.json")).unwrap();
+
+        let failures = contract.replay(app(), &interactions).await;
+
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    // 핸들러가 문서와 달라지면 계약 테스트가 잡아내는지 확인
+    #[tokio::test]
+    async fn drift_from_the_documented_schema_is_caught() {
+        let contract = Contract::new(get_json(OPENAPI_JSON).await);
+        let id = Uuid::nil();
+        let uri = format!("/todos/{id}");
+        let check = |status: u16, body: Value| {
+            contract.check_response(
+                "GET",
+                &uri,
+                status,
+                Some("application/json"),
+                body.to_string().as_bytes(),
+            )
+        };
+
+        assert!(check(200, json!({ "id": id, "text": "x", "completed": false })).is_empty());
+        for (status, body) in [
+            // 필드 누락
+            (200, json!({ "id": id, "text": "x" })),
+            // 타입 변경
+            (200, json!({ "id": id, "text": "x", "completed": "yes" })),
+            // 문서에 없는 필드
+            (
+                200,
+                json!({ "id": id, "text": "x", "completed": false, "due": null }),
+            ),
+            // 형식(format: uuid) 위반
+            (200, json!({ "id": 1, "text": "x", "completed": false })),
+            (200, json!({ "id": "abc", "text": "x", "completed": false })),
+            // 문서에 없는 상태 코드
+            (500, json!({ "error": "boom" })),
+            // 본문이 없어야 하는 404에 본문
+            (404, json!({ "error": "not found" })),
+        ] {
+            assert!(!check(status, body.clone()).is_empty(), "{status} {body}");
+        }
+
+        // 요청 쪽: 잘못된 본문, 문서에 없는 쿼리, 범위 밖 값, 문서에 없는 경로
+        assert!(!contract
+            .check_request("POST", "/todos", Some(&json!({ "text": 1 })))
+            .is_empty());
+        assert!(!contract.check_request("POST", "/todos", None).is_empty());
+        assert!(!contract
+            .check_request("GET", "/todos?page=2", None)
+            .is_empty());
+        assert!(!contract
+            .check_request("GET", "/todos?limit=-1", None)
+            .is_empty());
+        assert!(!contract
+            .check_request("GET", "/todos/not-a-uuid", None)
+            .is_empty());
+        assert!(!contract.check_request("PUT", &uri, None).is_empty());
+
+        // 실제로 어긋난 핸들러를 재생하면 실패 목록에 나옴
+        let drifted = Router::new().route(
+            "/todos/{id}",
+            axum::routing::get(|| async { Json(json!({ "id": 1, "title": "x" })) }),
+        );
+        let interactions: Vec<Interaction> = serde_json::from_value(json!([
+            { "name": "get", "method": "GET", "uri": uri, "status": 200 }
+        ]))
+        .unwrap();
+        let failures = contract.replay(drifted, &interactions).await;
+        let failures = failures.join("\n");
+        assert!(failures.contains("completed"), "{failures}");
+        assert!(failures.contains("title"), "{failures}");
+    }
+
+    #[tokio::test]
+    async fn swagger_ui_is_served() {
+        let response = app()
+            .oneshot(Request::get("/swagger/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/html"));
+    }
+}
+
+// 🧪 테스트 예시 (curl)
+//
+// ✅ OpenAPI 문서
+// curl http://localhost:3000/api-docs/openapi.json
+//
+// ✅ 새 Todo 추가
+// curl -X POST http://localhost:3000/todos -H 'Content-Type: application/json' \
+// -d '{"text": "Buy milk"}'
+//
+// ✅ Swagger UI
+// 브라우저에서 http://localhost:3000/swagger 접속 → "Try it out"으로 바로 호출 가능
+//
+// ✅ 계약 테스트
+// cargo test -p example-openapi contract
+// # 핸들러나 스키마를 바꾸고 문서를 맞추지 않으면 어떤 요청의 어느 필드가 어긋났는지 출력됨
+// # 새 API를 추가하면 contract/recorded.json에도 요청을 기록
@@ -0,0 +1,428 @@
+//! 이미지 업로드 + 썸네일 생성 예제
+//!
+//! 1. multipart 폼으로 이미지를 받음 (`POST /images`, 필드 이름 `image`)
+//! 2. 실제 바이트로 형식과 크기를 검사 (Content-Type, 파일 이름은 믿지 않음)
+//! 3. `spawn_blocking` 안에서 `image` 크레이트로 썸네일 생성 (CPU 작업이 런타임을 막지 않도록)
+//! 4. 원본과 썸네일을 내용 해시(SHA-256) 경로에 저장하고, 캐시 헤더와 함께 다시 제공
+//!
+//! ```not_rust
+//! cargo run -p example-image-upload
+//! ```
+
+mod processing;
+mod store;
+
+use axum::{
+    extract::{multipart::MultipartError, DefaultBodyLimit, Multipart, Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{Html, IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde::Serialize;
+use std::{fmt, sync::Arc};
+use store::{ImageStore, Variant};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+// 업로드 최대 크기: 10MB
+const MAX_UPLOAD_SIZE: usize = 10 * 1024 * 1024;
+// 내용이 바뀌지 않는 경로이므로 1년 동안 재검증 없이 캐시
+const CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let store = ImageStore::open("uploads")
+        .await
+        .expect("failed to create `uploads` directory");
+    tracing::debug!("storing images in {}", store.root().display());
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(store)).await.unwrap();
+}
+
+fn app(store: ImageStore) -> Router {
+    Router::new()
+        .route("/", get(show_form))
+        .route("/images", axum::routing::post(upload))
+        .route("/images/{id}", get(original))
+        .route("/images/{id}/thumbnail", get(thumbnail))
+        // 기본 제한(2MB)보다 크게 허용
+        .layer(DefaultBodyLimit::max(MAX_UPLOAD_SIZE))
+        .with_state(Arc::new(store))
+}
+
+async fn show_form() -> Html<&'static str> {
+    Html(
+        r#"
+        <!doctype html>
+        <html>
+            <head>
+                <title>Upload an image</title>
+            </head>
+            <body>
+                <form action="/images" method="post" enctype="multipart/form-data">
+                    <label>
+                        Image (PNG, JPEG, GIF, WebP):
+                        <input type="file" name="image" accept="image/*">
+                    </label>
+
+                    <input type="submit" value="Upload">
+                </form>
+            </body>
+        </html>
+        "#,
+    )
+}
+
+/// 📤 업로드 결과
+#[derive(Debug, Serialize)]
+struct ImageInfo {
+    id: String,
+    content_type: &'static str,
+    width: u32,
+    height: u32,
+    original: String,
+    thumbnail: String,
+}
+
+async fn upload(
+    State(store): State<Arc<ImageStore>>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<ImageInfo>), UploadError> {
+    let mut bytes = None;
+    while let Some(field) = multipart.next_field().await? {
+        if field.name() == Some("image") {
+            bytes = Some(field.bytes().await?);
+            break;
+        }
+    }
+    let bytes = bytes.ok_or(UploadError::MissingFile)?;
+
+    // 헤더만 읽어 검사 → 통과한 이미지만 전체 디코딩
+    let inspected = processing::inspect(&bytes)?;
+    let id = store::content_id(&bytes);
+
+    let info = ImageInfo {
+        content_type: inspected.format.to_mime_type(),
+        width: inspected.width,
+        height: inspected.height,
+        original: format!("/images/{id}"),
+        thumbnail: format!("/images/{id}/thumbnail"),
+        id,
+    };
+
+    // 이미 있는 이미지면 다시 처리하지 않음
+    if store.contains(&info.id).await? {
+        return Ok((StatusCode::OK, Json(info)));
+    }
+
+    let thumbnail = {
+        let bytes = bytes.clone();
+        tokio::task::spawn_blocking(move || processing::make_thumbnail(&bytes, inspected.format))
+            .await
+            .map_err(|err| UploadError::Corrupt(err.to_string()))??
+    };
+    store.save(&info.id, &bytes, &thumbnail).await?;
+    tracing::debug!(id = info.id, "stored {}x{}", info.width, info.height);
+
+    Ok((StatusCode::CREATED, Json(info)))
+}
+
+async fn original(
+    State(store): State<Arc<ImageStore>>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, UploadError> {
+    serve(&store, &id, Variant::Original, &headers).await
+}
+
+async fn thumbnail(
+    State(store): State<Arc<ImageStore>>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, UploadError> {
+    serve(&store, &id, Variant::Thumbnail, &headers).await
+}
+
+// 📥 저장된 이미지 응답
+// ID가 곧 내용이므로 ETag도 ID로 만들고, If-None-Match가 같으면 파일을 읽지 않고 304
+async fn serve(
+    store: &ImageStore,
+    id: &str,
+    variant: Variant,
+    headers: &HeaderMap,
+) -> Result<Response, UploadError> {
+    if !store::is_valid_id(id) {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    }
+
+    let etag = HeaderValue::from_str(&format!("\"{id}-{variant:?}\"")).unwrap();
+    let cache_headers = [
+        (header::ETAG, etag.clone()),
+        (
+            header::CACHE_CONTROL,
+            HeaderValue::from_static(CACHE_CONTROL),
+        ),
+    ];
+
+    if headers.get(header::IF_NONE_MATCH) == Some(&etag) && store.contains(id).await? {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
+
+    let Some(bytes) = store.read(id, variant).await? else {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    };
+    let content_type = image::guess_format(&bytes)
+        .map(|format| format.to_mime_type())
+        .unwrap_or("application/octet-stream");
+
+    Ok((
+        cache_headers,
+        [(header::CONTENT_TYPE, HeaderValue::from_static(content_type))],
+        bytes,
+    )
+        .into_response())
+}
+
+/// ⚠️ 업로드 에러 → 상태 코드
+#[derive(Debug)]
+pub enum UploadError {
+    MissingFile,
+    UnsupportedType,
+    TooSmall { width: u32, height: u32 },
+    TooLarge { width: u32, height: u32 },
+    Corrupt(String),
+    Multipart(MultipartError),
+    Storage(std::io::Error),
+}
+
+impl fmt::Display for UploadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use processing::{MAX_DIMENSION, MIN_DIMENSION};
+
+        match self {
+            Self::MissingFile => write!(f, "missing `image` field"),
+            Self::UnsupportedType => {
+                write!(f, "unsupported image type (use PNG, JPEG, GIF or WebP)")
+            }
+            Self::TooSmall { width, height } => write!(
+                f,
+                "image is {width}x{height}, must be at least {MIN_DIMENSION}x{MIN_DIMENSION}"
+            ),
+            Self::TooLarge { width, height } => write!(
+                f,
+                "image is {width}x{height}, must be at most {MAX_DIMENSION}x{MAX_DIMENSION}"
+            ),
+            Self::Corrupt(err) => write!(f, "could not decode image: {err}"),
+            Self::Multipart(err) => write!(f, "{}", err.body_text()),
+            Self::Storage(_) => write!(f, "failed to store image"),
+        }
+    }
+}
+
+impl From<MultipartError> for UploadError {
+    fn from(err: MultipartError) -> Self {
+        Self::Multipart(err)
+    }
+}
+
+impl From<std::io::Error> for UploadError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Storage(err)
+    }
+}
+
+impl IntoResponse for UploadError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            Self::MissingFile => StatusCode::BAD_REQUEST,
+            Self::UnsupportedType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::TooSmall { .. } | Self::TooLarge { .. } | Self::Corrupt(_) => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            // 크기 제한 초과는 413 등 multipart 에러가 가진 상태 코드를 그대로 사용
+            Self::Multipart(err) => err.status(),
+            Self::Storage(err) => {
+                tracing::error!(%err, "storage error");
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+        (status, self.to_string()).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use http_body_util::BodyExt;
+    use image::ImageFormat;
+    use processing::encode;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::ServiceExt;
+
+    const BOUNDARY: &str = "X-TEST-BOUNDARY";
+
+    // 테스트마다 다른 임시 디렉토리
+    async fn test_app() -> (Router, std::path::PathBuf) {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let root = std::env::temp_dir().join(format!(
+            "example-image-upload-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let store = ImageStore::open(&root).await.unwrap();
+        (app(store), root)
+    }
+
+    fn upload_request(field: &str, bytes: &[u8]) -> Request<Body> {
+        let mut body = format!(
+            "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{field}\"; filename=\"upload\"\r\nContent-Type: application/octet-stream\r\n\r\n"
+        )
+        .into_bytes();
+        body.extend_from_slice(bytes);
+        body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+
+        Request::post("/images")
+            .header(
+                header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={BOUNDARY}"),
+            )
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    async fn send(app: &Router, request: Request<Body>) -> (StatusCode, HeaderMap, Vec<u8>) {
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, headers, body.to_vec())
+    }
+
+    #[tokio::test]
+    async fn upload_stores_original_and_thumbnail() {
+        let (app, root) = test_app().await;
+        let png = encode(600, 300, ImageFormat::Png);
+
+        let (status, _, body) = send(&app, upload_request("image", &png)).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let info: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let id = info["id"].as_str().unwrap();
+        assert_eq!(id, store::content_id(&png));
+        assert_eq!(info["content_type"], "image/png");
+        assert_eq!(
+            (info["width"].as_u64(), info["height"].as_u64()),
+            (Some(600), Some(300))
+        );
+        assert!(root.join(&id[..2]).join(id).join("thumbnail").exists());
+
+        // 원본은 올린 바이트 그대로
+        let request = Request::get(info["original"].as_str().unwrap()).body(Body::empty());
+        let (status, headers, body) = send(&app, request.unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers[header::CONTENT_TYPE], "image/png");
+        assert_eq!(headers[header::CACHE_CONTROL], CACHE_CONTROL);
+        assert_eq!(body, png);
+
+        let request = Request::get(info["thumbnail"].as_str().unwrap()).body(Body::empty());
+        let (status, _, body) = send(&app, request.unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        let thumbnail = processing::inspect(&body).unwrap();
+        assert_eq!((thumbnail.width, thumbnail.height), (256, 128));
+
+        // 같은 이미지를 다시 올리면 같은 ID, 200
+        let (status, _, body) = send(&app, upload_request("image", &png)).await;
+        assert_eq!(status, StatusCode::OK);
+        let again: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(again["id"], id);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_uploads() {
+        let (app, root) = test_app().await;
+
+        let cases = [
+            (
+                upload_request("image", b"not an image"),
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ),
+            (
+                upload_request("image", &encode(8, 8, ImageFormat::Png)),
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                upload_request("image", &encode(5000, 16, ImageFormat::Png)),
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                upload_request("avatar", &encode(64, 64, ImageFormat::Png)),
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                upload_request("image", &vec![0; MAX_UPLOAD_SIZE + 1]),
+                StatusCode::PAYLOAD_TOO_LARGE,
+            ),
+        ];
+        for (request, expected) in cases {
+            let (status, _, body) = send(&app, request).await;
+            assert_eq!(status, expected, "{}", String::from_utf8_lossy(&body));
+        }
+
+        // 아무것도 저장되지 않음
+        assert_eq!(std::fs::read_dir(&root).unwrap().count(), 0);
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn conditional_get_and_unknown_ids() {
+        let (app, root) = test_app().await;
+        let jpeg = encode(64, 64, ImageFormat::Jpeg);
+        let (status, _, _) = send(&app, upload_request("image", &jpeg)).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let id = store::content_id(&jpeg);
+
+        let request = Request::get(format!("/images/{id}/thumbnail")).body(Body::empty());
+        let (_, headers, _) = send(&app, request.unwrap()).await;
+        let etag = headers[header::ETAG].clone();
+
+        let request = Request::get(format!("/images/{id}/thumbnail"))
+            .header(header::IF_NONE_MATCH, etag.clone())
+            .body(Body::empty());
+        let (status, headers, body) = send(&app, request.unwrap()).await;
+        assert_eq!(status, StatusCode::NOT_MODIFIED);
+        assert_eq!(headers[header::ETAG], etag);
+        assert!(body.is_empty());
+
+        // 원본과 썸네일의 ETag는 다름
+        let request = Request::get(format!("/images/{id}"))
+            .header(header::IF_NONE_MATCH, etag)
+            .body(Body::empty());
+        let (status, _, _) = send(&app, request.unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+
+        for path in [
+            format!("/images/{}", store::content_id(b"missing")),
+            "/images/not-a-hash".to_owned(),
+            "/images/..%2F..%2Fetc%2Fpasswd/thumbnail".to_owned(),
+        ] {
+            let (status, _, _) = send(&app, Request::get(path).body(Body::empty()).unwrap()).await;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+        }
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
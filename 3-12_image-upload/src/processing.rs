@@ -0,0 +1,134 @@
+//! 🖼️ 이미지 검사 + 썸네일 생성 (CPU 작업이므로 모두 동기 함수)
+//!
+//! - `inspect`: 헤더만 읽어 형식/크기 확인 → 전체 디코딩 전에 거절 (압축 폭탄 방지)
+//! - `make_thumbnail`: 전체 디코딩 + 축소 + 인코딩 → 핸들러에서 `spawn_blocking`으로 호출
+
+use image::{ImageFormat, ImageReader};
+use std::io::Cursor;
+
+use crate::UploadError;
+
+// 허용하는 형식 (Content-Type이나 파일 이름이 아니라 실제 바이트로 판단)
+const ALLOWED_FORMATS: [ImageFormat; 4] = [
+    ImageFormat::Png,
+    ImageFormat::Jpeg,
+    ImageFormat::Gif,
+    ImageFormat::WebP,
+];
+pub const MIN_DIMENSION: u32 = 16;
+pub const MAX_DIMENSION: u32 = 4096;
+// 썸네일은 비율을 유지한 채 이 크기 안에 들어가도록 축소
+pub const THUMBNAIL_SIZE: u32 = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Inspected {
+    pub format: ImageFormat,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// 형식과 크기 검사 (헤더만 읽으므로 요청 처리 중에 바로 호출해도 됨)
+pub fn inspect(bytes: &[u8]) -> Result<Inspected, UploadError> {
+    let format = image::guess_format(bytes).map_err(|_| UploadError::UnsupportedType)?;
+    if !ALLOWED_FORMATS.contains(&format) {
+        return Err(UploadError::UnsupportedType);
+    }
+
+    let (width, height) = ImageReader::with_format(Cursor::new(bytes), format)
+        .into_dimensions()
+        .map_err(|err| UploadError::Corrupt(err.to_string()))?;
+
+    if width < MIN_DIMENSION || height < MIN_DIMENSION {
+        return Err(UploadError::TooSmall { width, height });
+    }
+    if width > MAX_DIMENSION || height > MAX_DIMENSION {
+        return Err(UploadError::TooLarge { width, height });
+    }
+
+    Ok(Inspected {
+        format,
+        width,
+        height,
+    })
+}
+
+/// 썸네일 생성 (JPEG는 JPEG로, 나머지는 투명도를 살리기 위해 PNG로 저장)
+pub fn make_thumbnail(bytes: &[u8], format: ImageFormat) -> Result<Vec<u8>, UploadError> {
+    let image = image::load_from_memory_with_format(bytes, format)
+        .map_err(|err| UploadError::Corrupt(err.to_string()))?;
+    // 이미 작은 이미지는 키우지 않음 (`thumbnail`은 틀에 맞춰 확대도 함)
+    let thumbnail = if image.width() <= THUMBNAIL_SIZE && image.height() <= THUMBNAIL_SIZE {
+        image
+    } else {
+        image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+    };
+
+    let mut out = Cursor::new(Vec::new());
+    let result = if format == ImageFormat::Jpeg {
+        // JPEG 인코더는 알파 채널을 지원하지 않음
+        thumbnail.to_rgb8().write_to(&mut out, ImageFormat::Jpeg)
+    } else {
+        thumbnail.write_to(&mut out, ImageFormat::Png)
+    };
+    result.map_err(|err| UploadError::Corrupt(err.to_string()))?;
+
+    Ok(out.into_inner())
+}
+
+#[cfg(test)]
+pub(crate) fn encode(width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+    let image = image::RgbImage::from_fn(width, height, |x, y| {
+        image::Rgb([(x % 256) as u8, (y % 256) as u8, 128])
+    });
+    let mut out = Cursor::new(Vec::new());
+    image.write_to(&mut out, format).unwrap();
+    out.into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inspect_reads_format_and_dimensions() {
+        let inspected = inspect(&encode(640, 480, ImageFormat::Jpeg)).unwrap();
+        assert_eq!(
+            inspected,
+            Inspected {
+                format: ImageFormat::Jpeg,
+                width: 640,
+                height: 480
+            }
+        );
+
+        assert!(matches!(
+            inspect(b"\x89PNG\r\n\x1a\n truncated"),
+            Err(UploadError::Corrupt(_))
+        ));
+        assert!(matches!(
+            inspect(b"hello world"),
+            Err(UploadError::UnsupportedType)
+        ));
+        // BMP는 디코딩할 수 있어도 허용 목록에 없음
+        assert!(matches!(
+            inspect(b"BM\0\0\0\0"),
+            Err(UploadError::UnsupportedType)
+        ));
+    }
+
+    #[test]
+    fn thumbnail_keeps_aspect_ratio_and_format() {
+        let thumbnail =
+            make_thumbnail(&encode(600, 300, ImageFormat::Jpeg), ImageFormat::Jpeg).unwrap();
+        let inspected = inspect(&thumbnail).unwrap();
+        assert_eq!(inspected.format, ImageFormat::Jpeg);
+        assert_eq!((inspected.width, inspected.height), (256, 128));
+
+        // 작은 이미지는 크기 그대로, GIF는 PNG로
+        let thumbnail =
+            make_thumbnail(&encode(100, 50, ImageFormat::Gif), ImageFormat::Gif).unwrap();
+        let inspected = inspect(&thumbnail).unwrap();
+        assert_eq!(inspected.format, ImageFormat::Png);
+        assert_eq!((inspected.width, inspected.height), (100, 50));
+    }
+}
@@ -0,0 +1,120 @@
+//! 💾 내용 주소 기반(content-addressed) 저장소
+//!
+//! 파일 이름 대신 내용의 SHA-256 해시를 ID로 사용
+//!
+//! ```not_rust
+//! uploads/
+//! └── 3f/
+//!     └── 3fa9…c2/        (SHA-256, 64자리 hex)
+//!         ├── original
+//!         └── thumbnail
+//! ```
+//!
+//! - 같은 이미지를 여러 번 올려도 한 번만 저장됨
+//! - 같은 ID의 내용은 절대 바뀌지 않음 → 응답에 `immutable` 캐시 헤더를 붙일 수 있음
+//! - 앞 두 글자로 디렉토리를 나눠 한 디렉토리에 파일이 너무 많아지지 않게 함
+
+use sha2::{Digest, Sha256};
+use std::{
+    fmt::Write,
+    io,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    Original,
+    Thumbnail,
+}
+
+impl Variant {
+    fn file_name(self) -> &'static str {
+        match self {
+            Variant::Original => "original",
+            Variant::Thumbnail => "thumbnail",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImageStore {
+    root: PathBuf,
+}
+
+impl ImageStore {
+    pub async fn open(root: impl Into<PathBuf>) -> io::Result<Self> {
+        let root = root.into();
+        tokio::fs::create_dir_all(&root).await?;
+        Ok(Self { root })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path(&self, id: &str, variant: Variant) -> PathBuf {
+        self.root.join(&id[..2]).join(id).join(variant.file_name())
+    }
+
+    /// 원본이 있으면 썸네일까지 저장이 끝난 상태 (`save`에서 원본을 마지막에 씀)
+    pub async fn contains(&self, id: &str) -> io::Result<bool> {
+        tokio::fs::try_exists(self.path(id, Variant::Original)).await
+    }
+
+    pub async fn save(&self, id: &str, original: &[u8], thumbnail: &[u8]) -> io::Result<()> {
+        let dir = self.root.join(&id[..2]).join(id);
+        tokio::fs::create_dir_all(&dir).await?;
+
+        write_atomic(&self.path(id, Variant::Thumbnail), thumbnail).await?;
+        write_atomic(&self.path(id, Variant::Original), original).await
+    }
+
+    /// 없는 ID면 `None`
+    pub async fn read(&self, id: &str, variant: Variant) -> io::Result<Option<Vec<u8>>> {
+        match tokio::fs::read(self.path(id, variant)).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+// 임시 파일에 다 쓴 뒤 rename → 읽는 쪽에서 반쯤 쓰인 파일을 볼 일이 없음
+async fn write_atomic(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    tokio::fs::write(&tmp, bytes).await?;
+    tokio::fs::rename(&tmp, path).await
+}
+
+/// 내용 → ID (SHA-256 hex)
+pub fn content_id(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+/// 경로에서 받은 ID 검사 (`../` 같은 값으로 저장소 밖을 읽지 못하도록)
+pub fn is_valid_id(id: &str) -> bool {
+    id.len() == 64 && id.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_id_is_sha256_hex() {
+        assert_eq!(
+            content_id(b"hello"),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert!(is_valid_id(&content_id(b"hello")));
+
+        assert!(!is_valid_id("../../etc/passwd"));
+        assert!(!is_valid_id(&content_id(b"hello").to_uppercase()));
+        assert!(!is_valid_id(&content_id(b"hello")[..63]));
+    }
+}
@@ -0,0 +1,343 @@
+//! 이어받기(resumable)가 가능한 파일 다운로드 예제
+//!
+//! - `Range` 헤더를 직접 파싱해 `206 Partial Content`로 파일 일부만 스트리밍
+//! - `Accept-Ranges`, `Content-Range`, `Content-Length`를 정확히 설정
+//! - `ETag` + `If-Range`: 중간에 파일이 바뀌었으면 이어받지 않고 처음부터 다시 보냄
+//! - `If-None-Match`: 바뀌지 않았으면 304
+//!
+//! ```not_rust
+//! cargo run -p example-file-download
+//!
+//! curl -o sample.bin http://localhost:3000/files/sample.bin
+//! curl -r 0-99 -i http://localhost:3000/files/sample.bin        # 206, 100바이트
+//! curl -C - -o sample.bin http://localhost:3000/files/sample.bin # 중단된 다운로드 이어받기
+//! ```
+
+mod range;
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use range::RangeRequest;
+use std::{
+    io::SeekFrom,
+    path::{Component, PathBuf},
+    sync::Arc,
+    time::UNIX_EPOCH,
+};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio_util::io::ReaderStream;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+const FILES_DIRECTORY: &str = "files";
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    // 처음 실행할 때 다운로드해 볼 샘플 파일(1MB) 생성
+    tokio::fs::create_dir_all(FILES_DIRECTORY).await.unwrap();
+    let sample = PathBuf::from(FILES_DIRECTORY).join("sample.bin");
+    if !sample.exists() {
+        let bytes: Vec<u8> = (0..1024 * 1024).map(|i| (i % 251) as u8).collect();
+        tokio::fs::write(&sample, bytes).await.unwrap();
+    }
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(FILES_DIRECTORY.into()))
+        .await
+        .unwrap();
+}
+
+// GET 라우트는 HEAD도 처리 (axum이 본문만 빼고 같은 헤더로 응답)
+fn app(root: PathBuf) -> Router {
+    Router::new()
+        .route("/files/{name}", get(download))
+        .with_state(Arc::new(root))
+}
+
+async fn download(
+    State(root): State<Arc<PathBuf>>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    if !name_is_valid(&name) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let mut file = match tokio::fs::File::open(root.join(&name)).await {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Err(StatusCode::NOT_FOUND)
+        }
+        Err(err) => {
+            tracing::error!(%err, "failed to open {name}");
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    let metadata = file
+        .metadata()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if !metadata.is_file() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let total = metadata.len();
+    let etag = etag(&metadata);
+
+    let common_headers = [
+        (header::ACCEPT_RANGES, HeaderValue::from_static("bytes")),
+        (header::ETAG, etag.clone()),
+    ];
+
+    if headers.get(header::IF_NONE_MATCH) == Some(&etag) {
+        return Ok((StatusCode::NOT_MODIFIED, common_headers).into_response());
+    }
+
+    // If-Range가 현재 ETag와 다르면 (받던 중에 파일이 바뀜) Range를 무시하고 전체를 보냄
+    let range_request = match headers.get(header::RANGE).and_then(|v| v.to_str().ok()) {
+        Some(_) if !if_range_matches(&headers, &etag) => RangeRequest::Full,
+        Some(value) => range::parse(value, total),
+        None => RangeRequest::Full,
+    };
+
+    let content_disposition = HeaderValue::from_str(&format!("attachment; filename=\"{name}\""))
+        .map_err(|_| StatusCode::NOT_FOUND)?;
+    let file_headers = [
+        (
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/octet-stream"),
+        ),
+        (header::CONTENT_DISPOSITION, content_disposition),
+    ];
+
+    match range_request {
+        RangeRequest::Full => {
+            let body = Body::from_stream(ReaderStream::new(file));
+            Ok((
+                common_headers,
+                file_headers,
+                [(header::CONTENT_LENGTH, HeaderValue::from(total))],
+                body,
+            )
+                .into_response())
+        }
+        RangeRequest::Partial(range) => {
+            // 시작 위치로 이동한 뒤 필요한 길이만큼만 읽도록 제한
+            file.seek(SeekFrom::Start(range.start))
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            let body = Body::from_stream(ReaderStream::new(file.take(range.len())));
+            Ok((
+                StatusCode::PARTIAL_CONTENT,
+                common_headers,
+                file_headers,
+                [
+                    (header::CONTENT_LENGTH, HeaderValue::from(range.len())),
+                    (
+                        header::CONTENT_RANGE,
+                        HeaderValue::from_str(&range.content_range(total)).unwrap(),
+                    ),
+                ],
+                body,
+            )
+                .into_response())
+        }
+        RangeRequest::Unsatisfiable => Ok((
+            StatusCode::RANGE_NOT_SATISFIABLE,
+            common_headers,
+            [(
+                header::CONTENT_RANGE,
+                HeaderValue::from_str(&format!("bytes */{total}")).unwrap(),
+            )],
+        )
+            .into_response()),
+    }
+}
+
+// 🏷️ 크기 + 수정 시각으로 만든 ETag (내용을 해시하지 않아도 바뀌었는지 알 수 있음)
+fn etag(metadata: &std::fs::Metadata) -> HeaderValue {
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_nanos());
+    HeaderValue::from_str(&format!("\"{:x}-{modified:x}\"", metadata.len())).unwrap()
+}
+
+// If-Range가 없으면 항상 Range 적용
+// 날짜 형식(If-Range: <HTTP-date>)은 지원하지 않으므로 일치하지 않는 것으로 보고 전체를 보냄
+fn if_range_matches(headers: &HeaderMap, etag: &HeaderValue) -> bool {
+    match headers.get(header::IF_RANGE) {
+        None => true,
+        // 약한 ETag(W/"...")는 If-Range에 쓸 수 없음 (강한 비교만 허용)
+        Some(value) => value == etag && !value.as_bytes().starts_with(b"W/"),
+    }
+}
+
+// 디렉토리 탈출 방지: 경로 구성 요소가 정확히 하나(일반 파일 이름)여야 함
+fn name_is_valid(name: &str) -> bool {
+    let mut components = std::path::Path::new(name).components();
+    matches!(components.next(), Some(Component::Normal(_))) && components.next().is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use sha2::{Digest, Sha256};
+    use tower::ServiceExt;
+
+    const SIZE: usize = 100_003;
+
+    // 테스트마다 다른 임시 디렉토리에 파일 하나 생성
+    async fn setup(test: &str) -> (Router, PathBuf, Vec<u8>) {
+        let root = std::env::temp_dir().join(format!(
+            "example-file-download-{}-{test}",
+            std::process::id()
+        ));
+        tokio::fs::create_dir_all(&root).await.unwrap();
+        let bytes: Vec<u8> = (0..SIZE).map(|i| (i * 31 % 256) as u8).collect();
+        tokio::fs::write(root.join("data.bin"), &bytes)
+            .await
+            .unwrap();
+        (app(root.clone()), root, bytes)
+    }
+
+    async fn get(app: &Router, headers: &[(header::HeaderName, &str)]) -> Response {
+        let mut request = Request::get("/files/data.bin");
+        for (name, value) in headers {
+            request = request.header(name, *value);
+        }
+        app.clone()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn body(response: Response) -> Vec<u8> {
+        response
+            .into_body()
+            .collect()
+            .await
+            .unwrap()
+            .to_bytes()
+            .to_vec()
+    }
+
+    #[tokio::test]
+    async fn download_in_two_halves() {
+        let (app, root, bytes) = setup("halves").await;
+        let half = SIZE / 2;
+
+        // 1. 앞 절반
+        let response = get(&app, &[(header::RANGE, &format!("bytes=0-{}", half - 1))]).await;
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[header::ACCEPT_RANGES], "bytes");
+        assert_eq!(
+            response.headers()[header::CONTENT_RANGE],
+            format!("bytes 0-{}/{SIZE}", half - 1)
+        );
+        assert_eq!(response.headers()[header::CONTENT_LENGTH], half.to_string());
+        let etag = response.headers()[header::ETAG]
+            .to_str()
+            .unwrap()
+            .to_owned();
+        let mut downloaded = body(response).await;
+        assert_eq!(downloaded.len(), half);
+
+        // 2. 나머지를 이어받기 (If-Range로 같은 파일인지 확인)
+        let response = get(
+            &app,
+            &[
+                (header::RANGE, &format!("bytes={half}-")),
+                (header::IF_RANGE, &etag),
+            ],
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(
+            response.headers()[header::CONTENT_RANGE],
+            format!("bytes {half}-{}/{SIZE}", SIZE - 1)
+        );
+        downloaded.extend(body(response).await);
+
+        assert_eq!(Sha256::digest(&downloaded), Sha256::digest(&bytes));
+        tokio::fs::remove_dir_all(root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn changed_file_restarts_download() {
+        let (app, root, _) = setup("changed").await;
+        let response = get(&app, &[(header::RANGE, "bytes=0-9")]).await;
+        let etag = response.headers()[header::ETAG]
+            .to_str()
+            .unwrap()
+            .to_owned();
+
+        // 받는 도중 파일이 바뀜 → 크기가 달라져 ETag도 달라짐
+        tokio::fs::write(root.join("data.bin"), b"new contents")
+            .await
+            .unwrap();
+        let response = get(
+            &app,
+            &[(header::RANGE, "bytes=10-"), (header::IF_RANGE, &etag)],
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body(response).await, b"new contents");
+
+        tokio::fs::remove_dir_all(root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn full_unsatisfiable_and_not_modified() {
+        let (app, root, bytes) = setup("misc").await;
+
+        let response = get(&app, &[]).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_LENGTH], SIZE.to_string());
+        assert_eq!(
+            response.headers()[header::CONTENT_DISPOSITION],
+            "attachment; filename=\"data.bin\""
+        );
+        let etag = response.headers()[header::ETAG]
+            .to_str()
+            .unwrap()
+            .to_owned();
+        assert_eq!(body(response).await, bytes);
+
+        let response = get(&app, &[(header::RANGE, &format!("bytes={SIZE}-"))]).await;
+        assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(
+            response.headers()[header::CONTENT_RANGE],
+            format!("bytes */{SIZE}")
+        );
+
+        let response = get(&app, &[(header::IF_NONE_MATCH, &etag)]).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        for path in ["/files/missing.bin", "/files/..%2Fdata.bin"] {
+            let request = Request::get(path).body(Body::empty()).unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND, "{path}");
+        }
+
+        tokio::fs::remove_dir_all(root).await.unwrap();
+    }
+}
@@ -0,0 +1,127 @@
+//! 📐 `Range` 헤더 파싱 (RFC 9110 §14)
+//!
+//! 지원하는 형식 (단일 범위만)
+//! - `bytes=0-499`: 처음 500바이트
+//! - `bytes=500-`: 500번째 바이트부터 끝까지
+//! - `bytes=-500`: 마지막 500바이트
+//!
+//! 형식이 잘못됐거나 여러 범위(`bytes=0-1,5-6`)를 요청하면 헤더를 무시하고 전체를 보냄
+//! (RFC에서 허용하는 동작, multipart/byteranges 응답은 구현하지 않음)
+
+/// 포함 범위 `start..=end`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl ByteRange {
+    pub fn len(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    /// `Content-Range` 헤더 값
+    pub fn content_range(&self, total: u64) -> String {
+        format!("bytes {}-{}/{total}", self.start, self.end)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeRequest {
+    /// 전체 응답 (200)
+    Full,
+    /// 일부 응답 (206)
+    Partial(ByteRange),
+    /// 파일 범위를 벗어남 (416)
+    Unsatisfiable,
+}
+
+pub fn parse(header: &str, total: u64) -> RangeRequest {
+    let Some(spec) = header.trim().strip_prefix("bytes=") else {
+        return RangeRequest::Full;
+    };
+    if spec.contains(',') {
+        return RangeRequest::Full;
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return RangeRequest::Full;
+    };
+
+    let range = match (start.trim(), end.trim()) {
+        // 마지막 n바이트
+        ("", suffix) => match suffix.parse::<u64>() {
+            Ok(0) => return RangeRequest::Unsatisfiable,
+            Ok(n) if total > 0 => ByteRange {
+                start: total.saturating_sub(n),
+                end: total - 1,
+            },
+            Ok(_) => return RangeRequest::Unsatisfiable,
+            Err(_) => return RangeRequest::Full,
+        },
+        (start, end) => {
+            let Ok(start) = start.parse::<u64>() else {
+                return RangeRequest::Full;
+            };
+            let end = match end {
+                "" => u64::MAX,
+                end => match end.parse::<u64>() {
+                    Ok(end) if end >= start => end,
+                    _ => return RangeRequest::Full,
+                },
+            };
+            if start >= total {
+                return RangeRequest::Unsatisfiable;
+            }
+            // 끝이 파일 크기를 넘으면 마지막 바이트까지로 줄임
+            ByteRange {
+                start,
+                end: end.min(total - 1),
+            }
+        }
+    };
+
+    RangeRequest::Partial(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partial(start: u64, end: u64) -> RangeRequest {
+        RangeRequest::Partial(ByteRange { start, end })
+    }
+
+    #[test]
+    fn parses_single_ranges() {
+        assert_eq!(parse("bytes=0-499", 1000), partial(0, 499));
+        assert_eq!(parse("bytes=500-", 1000), partial(500, 999));
+        assert_eq!(parse("bytes=-200", 1000), partial(800, 999));
+        assert_eq!(parse("bytes=900-5000", 1000), partial(900, 999));
+        assert_eq!(parse("bytes=-5000", 1000), partial(0, 999));
+        assert_eq!(parse(" bytes= 10 - 19 ", 1000), partial(10, 19));
+    }
+
+    #[test]
+    fn unsatisfiable_and_ignored_ranges() {
+        assert_eq!(parse("bytes=1000-", 1000), RangeRequest::Unsatisfiable);
+        assert_eq!(parse("bytes=-0", 1000), RangeRequest::Unsatisfiable);
+        assert_eq!(parse("bytes=0-", 0), RangeRequest::Unsatisfiable);
+
+        for ignored in [
+            "items=0-1",
+            "bytes=5-1",
+            "bytes=a-b",
+            "bytes=0-1,5-6",
+            "bytes=5",
+        ] {
+            assert_eq!(parse(ignored, 1000), RangeRequest::Full, "{ignored}");
+        }
+    }
+
+    #[test]
+    fn content_range_header() {
+        let range = ByteRange { start: 0, end: 499 };
+        assert_eq!(range.len(), 500);
+        assert_eq!(range.content_range(1000), "bytes 0-499/1000");
+    }
+}
@@ -0,0 +1,7 @@
+CREATE TABLE blobs (
+    hash TEXT PRIMARY KEY,            -- 내용의 SHA-256 (hex, 소문자)
+    size INTEGER NOT NULL,
+    content_type TEXT NOT NULL,       -- 처음 업로드할 때의 Content-Type
+    created_at INTEGER NOT NULL,      -- Unix time (초)
+    uploads INTEGER NOT NULL DEFAULT 1 -- 같은 내용이 업로드된 횟수 (중복 제거된 업로드 포함)
+);
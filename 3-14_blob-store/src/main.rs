@@ -0,0 +1,278 @@
+//! 내용 주소 기반(content-addressable) blob 저장소 예제
+//!
+//! • `POST /blobs`: 요청 바디를 스트림으로 받아 저장하고 SHA-256 해시를 ID로 돌려줌
+//!   새로 저장했으면 `201 Created`, 이미 있는 내용이면 `200 OK` (파일은 하나만 남음)
+//! • `GET /blobs/{hash}`: 내용이 절대 바뀌지 않으므로 해시를 그대로 `ETag`로 쓰고
+//!   `Cache-Control: immutable`로 캐시 (`If-None-Match`가 맞으면 `304`)
+//! • `GET /blobs/{hash}/meta`: 크기, Content-Type, 업로드 횟수
+//!
+//! ```not_rust
+//! cargo run -p example-blob-store
+//! ```
+
+mod store;
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use example_common_errors::ApiError;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool};
+use std::str::FromStr;
+use store::{Blob, BlobStore};
+use tokio_util::io::ReaderStream;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+const DATA_DIRECTORY: &str = "data";
+
+// 해시가 같으면 내용도 같으므로 1년 동안 다시 확인할 필요 없음
+const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let url = std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:blobs.db".into());
+    let options = SqliteConnectOptions::from_str(&url)
+        .unwrap()
+        .create_if_missing(true);
+    let pool = SqlitePool::connect_with(options).await.unwrap();
+    let store = BlobStore::new(DATA_DIRECTORY, pool).await.unwrap();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(store)).await.unwrap();
+}
+
+// GET 라우트는 HEAD도 처리 (axum이 본문만 빼고 같은 헤더로 응답)
+fn app(store: BlobStore) -> Router {
+    Router::new()
+        .route("/blobs", post(upload))
+        .route("/blobs/{hash}", get(download))
+        .route("/blobs/{hash}/meta", get(metadata))
+        .with_state(store)
+}
+
+/// 📤 업로드 (바디 전체를 메모리에 올리지 않고 디스크에 쓰면서 해시 계산)
+async fn upload(
+    State(store): State<BlobStore>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<Response, ApiError> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("application/octet-stream");
+
+    let (blob, created) = store.put(content_type, body.into_data_stream()).await?;
+    tracing::debug!(hash = blob.hash, size = blob.size, created, "stored blob");
+
+    let status = if created {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    let location = format!("/blobs/{}", blob.hash);
+    Ok((status, [(header::LOCATION, location)], Json(blob)).into_response())
+}
+
+/// 📥 다운로드 (조건부 요청 지원)
+async fn download(
+    State(store): State<BlobStore>,
+    Path(hash): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let blob = find(&store, &hash).await?;
+    let etag = HeaderValue::from_str(&format!("\"{}\"", blob.hash)).unwrap();
+    let cache_headers = [
+        (header::ETAG, etag),
+        (header::CACHE_CONTROL, HeaderValue::from_static(IMMUTABLE)),
+    ];
+
+    if if_none_match(&headers, &blob.hash) {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
+
+    let file = tokio::fs::File::open(store.path(&blob.hash))
+        .await
+        .map_err(ApiError::internal)?;
+    let content_type = HeaderValue::from_str(&blob.content_type)
+        .unwrap_or(HeaderValue::from_static("application/octet-stream"));
+
+    Ok((
+        cache_headers,
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CONTENT_LENGTH, HeaderValue::from(blob.size)),
+            // 업로드한 사람이 정한 Content-Type을 브라우저가 다르게 해석하지 않도록
+            (
+                header::X_CONTENT_TYPE_OPTIONS,
+                HeaderValue::from_static("nosniff"),
+            ),
+        ],
+        Body::from_stream(ReaderStream::new(file)),
+    )
+        .into_response())
+}
+
+/// 🏷️ 메타데이터
+async fn metadata(
+    State(store): State<BlobStore>,
+    Path(hash): Path<String>,
+) -> Result<Json<Blob>, ApiError> {
+    Ok(Json(find(&store, &hash).await?))
+}
+
+async fn find(store: &BlobStore, hash: &str) -> Result<Blob, ApiError> {
+    let not_found = || ApiError::NotFound(format!("blob {hash} not found"));
+    // 형식이 틀린 해시는 경로에 쓰지 않고 바로 404
+    if !store::is_valid_hash(hash) {
+        return Err(not_found());
+    }
+    store.get(hash).await?.ok_or_else(not_found)
+}
+
+// `If-None-Match: "a", W/"b"` 또는 `*` (GET에서는 약한 비교를 씀)
+fn if_none_match(headers: &HeaderMap, hash: &str) -> bool {
+    let Some(value) = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+
+    value
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/").trim_matches('"') == hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    const HELLO_HASH: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    async fn setup(test: &str) -> (Router, std::path::PathBuf) {
+        let root = std::env::temp_dir().join(format!(
+            "example-blob-store-{}-app-{test}",
+            std::process::id()
+        ));
+        (app(BlobStore::in_memory(&root).await), root)
+    }
+
+    async fn send(app: &Router, request: Request<Body>) -> Response {
+        app.clone().oneshot(request).await.unwrap()
+    }
+
+    async fn upload(app: &Router, body: &'static str) -> (StatusCode, Value) {
+        let request = Request::post("/blobs")
+            .header(header::CONTENT_TYPE, "text/plain")
+            .body(Body::from(body))
+            .unwrap();
+        let response = send(app, request).await;
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn duplicate_uploads_return_the_existing_blob() {
+        let (app, root) = setup("dedup").await;
+
+        let (status, blob) = upload(&app, "hello").await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(blob["hash"], HELLO_HASH);
+        assert_eq!(blob["size"], 5);
+
+        let (status, blob) = upload(&app, "hello").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(blob["uploads"], 2);
+
+        let meta = Request::get(format!("/blobs/{HELLO_HASH}/meta"))
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(send(&app, meta).await.status(), StatusCode::OK);
+
+        tokio::fs::remove_dir_all(root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn downloads_are_immutable_and_support_if_none_match() {
+        let (app, root) = setup("download").await;
+        upload(&app, "hello").await;
+
+        let get = |if_none_match: Option<&str>| {
+            let mut request = Request::get(format!("/blobs/{HELLO_HASH}"));
+            if let Some(value) = if_none_match {
+                request = request.header(header::IF_NONE_MATCH, value);
+            }
+            send(&app, request.body(Body::empty()).unwrap())
+        };
+
+        let response = get(None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers();
+        assert_eq!(headers[header::ETAG], format!("\"{HELLO_HASH}\""));
+        assert_eq!(headers[header::CACHE_CONTROL], IMMUTABLE);
+        assert_eq!(headers[header::CONTENT_TYPE], "text/plain");
+        assert_eq!(headers[header::CONTENT_LENGTH], "5");
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, "hello");
+
+        for value in [
+            format!("\"{HELLO_HASH}\""),
+            format!("\"other\", W/\"{HELLO_HASH}\""),
+            "*".to_owned(),
+        ] {
+            let response = get(Some(&value)).await;
+            assert_eq!(response.status(), StatusCode::NOT_MODIFIED, "{value}");
+            assert_eq!(response.headers()[header::CACHE_CONTROL], IMMUTABLE);
+        }
+        assert_eq!(get(Some("\"other\"")).await.status(), StatusCode::OK);
+
+        tokio::fs::remove_dir_all(root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn unknown_or_malformed_hashes_are_not_found() {
+        let (app, root) = setup("missing").await;
+
+        for path in [
+            format!("/blobs/{HELLO_HASH}"),
+            "/blobs/..%2F..%2Fblobs.db".to_owned(),
+            format!("/blobs/{}", HELLO_HASH.to_uppercase()),
+        ] {
+            let response = send(&app, Request::get(&path).body(Body::empty()).unwrap()).await;
+            assert_eq!(response.status(), StatusCode::NOT_FOUND, "{path}");
+        }
+
+        tokio::fs::remove_dir_all(root).await.unwrap();
+    }
+}
+
+// 🧪 테스트 방법
+//
+// > curl -i --data-binary @Cargo.toml -H 'content-type: text/plain' localhost:3000/blobs   → 201 + Location
+// > curl -i --data-binary @Cargo.toml -H 'content-type: text/plain' localhost:3000/blobs   → 200 (중복, 파일은 하나)
+// > HASH=$(sha256sum Cargo.toml | cut -d' ' -f1)
+// > curl -i localhost:3000/blobs/$HASH                                  → ETag + Cache-Control: immutable
+// > curl -i localhost:3000/blobs/$HASH -H "If-None-Match: \"$HASH\""   → 304
+// > curl localhost:3000/blobs/$HASH/meta
+// > ls data/blobs/*/*/
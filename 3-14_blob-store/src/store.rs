@@ -0,0 +1,239 @@
+//! 🗃️ 내용 주소 기반(content-addressable) 저장소
+//!
+//! • 파일 이름 = 내용의 SHA-256 → 같은 내용은 한 번만 저장됨 (중복 제거)
+//! • 경로는 `blobs/ab/cd/<hash>` (한 디렉토리에 파일이 너무 많아지지 않도록 앞 4글자로 나눔)
+//! • 업로드는 임시 파일에 쓰면서 동시에 해시를 계산 → 다 받은 뒤 최종 경로로 `rename`
+//!   (받는 도중 실패해도 `blobs/`에는 완성된 파일만 있음)
+//! • 크기, Content-Type 등 메타데이터는 SQLite `blobs` 테이블에 저장
+
+use axum::body::Bytes;
+use example_common_errors::{ApiError, BoxError};
+use futures::{Stream, TryStreamExt};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use std::{
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    fs::{self, File},
+    io::{AsyncWriteExt, BufWriter},
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, sqlx::FromRow)]
+pub struct Blob {
+    pub hash: String,
+    pub size: i64,
+    pub content_type: String,
+    /// Unix time (초)
+    pub created_at: i64,
+    /// 같은 내용이 업로드된 횟수
+    pub uploads: i64,
+}
+
+#[derive(Clone)]
+pub struct BlobStore {
+    root: PathBuf,
+    pool: SqlitePool,
+}
+
+impl BlobStore {
+    pub async fn new(root: impl Into<PathBuf>, pool: SqlitePool) -> Result<Self, BoxError> {
+        let root = root.into();
+        fs::create_dir_all(root.join("tmp")).await?;
+        sqlx::migrate!().run(&pool).await?;
+        Ok(Self { root, pool })
+    }
+
+    /// 테스트용 메모리 DB (연결마다 DB가 따로 생기므로 연결 하나만 유지)
+    #[cfg(test)]
+    pub async fn in_memory(root: impl Into<PathBuf>) -> Self {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        Self::new(root, pool).await.unwrap()
+    }
+
+    /// 스트림을 저장하고 `(메타데이터, 새로 저장했는지)`를 반환
+    ///
+    /// 이미 있는 내용이면 임시 파일을 지우고 기존 blob을 반환 (업로드 횟수만 증가)
+    pub async fn put<S, E>(&self, content_type: &str, stream: S) -> Result<(Blob, bool), ApiError>
+    where
+        S: Stream<Item = Result<Bytes, E>>,
+        E: Into<BoxError>,
+    {
+        let tmp = self.root.join("tmp").join(uuid::Uuid::new_v4().to_string());
+        let written = write_hashed(&tmp, stream).await;
+        let (hash, size) = match written {
+            Ok(written) => written,
+            Err(err) => {
+                let _ = fs::remove_file(&tmp).await;
+                return Err(ApiError::Internal(err));
+            }
+        };
+
+        // 같은 내용이 동시에 올라와도 결과는 같음
+        // (rename은 같은 내용으로 덮어쓰고, INSERT는 한쪽만 성공하고 다른 쪽은 업로드 횟수를 올림)
+        let path = self.path(&hash);
+        let created = if fs::try_exists(&path).await.map_err(ApiError::internal)? {
+            fs::remove_file(&tmp).await.map_err(ApiError::internal)?;
+            false
+        } else {
+            fs::create_dir_all(path.parent().unwrap())
+                .await
+                .map_err(ApiError::internal)?;
+            fs::rename(&tmp, &path).await.map_err(ApiError::internal)?;
+            true
+        };
+
+        let blob = sqlx::query_as(
+            "INSERT INTO blobs (hash, size, content_type, created_at) VALUES (?, ?, ?, ?)
+             ON CONFLICT (hash) DO UPDATE SET uploads = uploads + 1
+             RETURNING hash, size, content_type, created_at, uploads",
+        )
+        .bind(&hash)
+        .bind(size as i64)
+        .bind(content_type)
+        .bind(unix_now())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok((blob, created))
+    }
+
+    pub async fn get(&self, hash: &str) -> Result<Option<Blob>, ApiError> {
+        Ok(sqlx::query_as(
+            "SELECT hash, size, content_type, created_at, uploads FROM blobs WHERE hash = ?",
+        )
+        .bind(hash)
+        .fetch_optional(&self.pool)
+        .await?)
+    }
+
+    /// `blobs/ab/cd/abcd…` (`hash`는 검증된 64자리 hex여야 함)
+    pub fn path(&self, hash: &str) -> PathBuf {
+        self.root
+            .join("blobs")
+            .join(&hash[..2])
+            .join(&hash[2..4])
+            .join(hash)
+    }
+}
+
+/// 소문자 64자리 hex인지 (경로에 쓰기 전에 항상 확인)
+pub fn is_valid_hash(hash: &str) -> bool {
+    hash.len() == 64 && hash.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+// 파일에 쓰면서 SHA-256과 크기를 계산 (전체를 메모리에 올리지 않음)
+async fn write_hashed<S, E>(path: &Path, stream: S) -> Result<(String, u64), BoxError>
+where
+    S: Stream<Item = Result<Bytes, E>>,
+    E: Into<BoxError>,
+{
+    let mut stream = std::pin::pin!(stream.map_err(Into::into));
+    let mut file = BufWriter::new(File::create(path).await?);
+    let mut hasher = Sha256::new();
+    let mut size = 0;
+
+    while let Some(chunk) = stream.try_next().await? {
+        hasher.update(&chunk);
+        size += chunk.len() as u64;
+        file.write_all(&chunk).await?;
+    }
+    file.flush().await?;
+
+    let hash = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    Ok((hash, size))
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 테스트마다 다른 임시 디렉토리
+    async fn test_store(test: &str) -> (BlobStore, PathBuf) {
+        let root = std::env::temp_dir().join(format!(
+            "example-blob-store-{}-store-{test}",
+            std::process::id()
+        ));
+        (BlobStore::in_memory(&root).await, root)
+    }
+
+    fn chunks(data: &'static [u8]) -> impl Stream<Item = Result<Bytes, BoxError>> {
+        futures::stream::iter(data.chunks(3).map(|chunk| Ok(Bytes::from_static(chunk))))
+    }
+
+    #[tokio::test]
+    async fn identical_uploads_are_stored_once() {
+        let (store, root) = test_store("dedup").await;
+
+        let (first, created) = store.put("text/plain", chunks(b"hello")).await.unwrap();
+        assert!(created);
+        // echo -n hello | sha256sum
+        assert_eq!(
+            first.hash,
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert_eq!(first.size, 5);
+        assert!(store.path(&first.hash).ends_with(
+            "blobs/2c/f2/2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        ));
+        assert_eq!(fs::read(store.path(&first.hash)).await.unwrap(), b"hello");
+
+        // 두 번째 업로드는 새 파일을 만들지 않고, 처음의 Content-Type을 유지
+        let (second, created) = store
+            .put("application/octet-stream", chunks(b"hello"))
+            .await
+            .unwrap();
+        assert!(!created);
+        assert_eq!(second.content_type, "text/plain");
+        assert_eq!(second.uploads, 2);
+        assert_eq!(store.get(&first.hash).await.unwrap(), Some(second));
+
+        // 임시 파일은 남지 않음
+        let mut tmp = fs::read_dir(root.join("tmp")).await.unwrap();
+        assert!(tmp.next_entry().await.unwrap().is_none());
+        fs::remove_dir_all(root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn failed_uploads_leave_nothing_behind() {
+        let (store, root) = test_store("failed").await;
+        let broken = futures::stream::iter([
+            Ok(Bytes::from_static(b"partial")),
+            Err::<Bytes, BoxError>("connection reset".into()),
+        ]);
+
+        assert!(store.put("text/plain", broken).await.is_err());
+
+        let mut tmp = fs::read_dir(root.join("tmp")).await.unwrap();
+        assert!(tmp.next_entry().await.unwrap().is_none());
+        assert!(!fs::try_exists(root.join("blobs")).await.unwrap());
+        fs::remove_dir_all(root).await.unwrap();
+    }
+
+    #[test]
+    fn only_lowercase_sha256_hex_is_a_valid_hash() {
+        assert!(is_valid_hash(&"a".repeat(64)));
+        assert!(!is_valid_hash(&"A".repeat(64)));
+        assert!(!is_valid_hash(&"a".repeat(63)));
+        assert!(!is_valid_hash("../../etc/passwd"));
+    }
+}
@@ -0,0 +1,13 @@
+-- 잔액 검사는 핸들러에서 (음수가 되면 에러 → 미들웨어가 롤백)
+CREATE TABLE accounts (
+    id BIGSERIAL PRIMARY KEY,
+    owner TEXT NOT NULL,
+    balance BIGINT NOT NULL
+);
+
+CREATE TABLE transfers (
+    id BIGSERIAL PRIMARY KEY,
+    from_account BIGINT NOT NULL REFERENCES accounts (id),
+    to_account BIGINT NOT NULL REFERENCES accounts (id),
+    amount BIGINT NOT NULL CHECK (amount > 0)
+);
@@ -0,0 +1,102 @@
+//! 🔁 요청 하나 = 트랜잭션 하나
+//!
+//! • `transaction` 미들웨어: 요청 전에 `BEGIN`, 트랜잭션을 request extensions에 넣음
+//!   → 응답이 2xx면 `COMMIT`, 그 외(4xx/5xx)는 `ROLLBACK`
+//!   → `COMMIT`이 실패하면 핸들러의 성공 응답 대신 500
+//! • `Tx` 추출기: 핸들러가 extensions에서 트랜잭션을 꺼내 씀 (`&mut *tx`로 쿼리 실행)
+//!   → 핸들러가 끝나면 `Tx`가 drop되고, 미들웨어가 다시 트랜잭션을 가져감
+//! • panic: 안쪽의 `CatchPanicLayer`가 500으로 바꾸므로 롤백됨
+//!   → 그 레이어가 없어도 트랜잭션이 커밋 없이 drop되면 sqlx가 롤백
+//!
+//! extensions의 값은 `Clone`이어야 해서 `Arc<Mutex<Option<Transaction>>>`로 감쌈
+//!   → `Option`: 미들웨어가 커밋/롤백하려고 꺼내면 `None`
+
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::request::Parts,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use example_common_errors::ApiError;
+use sqlx::{PgConnection, PgPool, Postgres, Transaction};
+use std::{
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+use tokio::sync::{Mutex, OwnedMappedMutexGuard, OwnedMutexGuard};
+
+type PgTransaction = Transaction<'static, Postgres>;
+
+#[derive(Clone)]
+struct TxSlot(Arc<Mutex<Option<PgTransaction>>>);
+
+/// `middleware::from_fn_with_state(pool, tx::transaction)`
+pub async fn transaction(State(pool): State<PgPool>, mut req: Request, next: Next) -> Response {
+    let tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(err) => return ApiError::from(err).into_response(),
+    };
+    let slot = TxSlot(Arc::new(Mutex::new(Some(tx))));
+    req.extensions_mut().insert(slot.clone());
+
+    let response = next.run(req).await;
+
+    // 핸들러가 `Tx`를 어딘가(spawn한 태스크 등)에 들고 있으면 꺼낼 수 없음
+    // → 커밋하지 않고 둠 (마지막 참조가 drop될 때 롤백)
+    let Some(tx) = slot.0.try_lock().ok().and_then(|mut tx| tx.take()) else {
+        tracing::error!("transaction still in use after the response, rolling back");
+        return response;
+    };
+
+    if response.status().is_success() {
+        if let Err(err) = tx.commit().await {
+            return ApiError::from(err).into_response();
+        }
+    } else {
+        tracing::debug!(status = %response.status(), "rolling back");
+        if let Err(err) = tx.rollback().await {
+            tracing::warn!(%err, "rollback failed");
+        }
+    }
+    response
+}
+
+/// 현재 요청의 트랜잭션 (요청당 한 번만 꺼낼 수 있음)
+pub struct Tx(OwnedMappedMutexGuard<Option<PgTransaction>, PgTransaction>);
+
+impl<S> FromRequestParts<S> for Tx
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let slot = parts
+            .extensions
+            .get::<TxSlot>()
+            .cloned()
+            .ok_or_else(|| ApiError::internal("transaction middleware is not installed"))?;
+        let guard = slot
+            .0
+            .try_lock_owned()
+            .map_err(|_| ApiError::internal("transaction is already extracted"))?;
+
+        OwnedMutexGuard::try_map(guard, Option::as_mut)
+            .map(Self)
+            .map_err(|_| ApiError::internal("transaction is already finished"))
+    }
+}
+
+impl Deref for Tx {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &PgConnection {
+        &self.0
+    }
+}
+
+impl DerefMut for Tx {
+    fn deref_mut(&mut self) -> &mut PgConnection {
+        &mut self.0
+    }
+}
@@ -0,0 +1,2 @@
+-- 이미 데이터가 있는 테이블에 컬럼 추가 (기본값으로 기존 행 채움)
+ALTER TABLE notes ADD COLUMN pinned BOOLEAN NOT NULL DEFAULT FALSE;
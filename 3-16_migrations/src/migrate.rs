@@ -0,0 +1,178 @@
+//! 🔒 advisory lock을 잡고 시작 시 마이그레이션 실행
+//!
+//! • 인스턴스 여러 개가 동시에 떠도 마이그레이션은 한 곳에서만 실행
+//!   → `pg_try_advisory_lock`이 실패하면 `WaitingForLock` 상태로 기다렸다가 다시 시도
+//!   → 먼저 끝낸 인스턴스가 lock을 풀면 나머지는 "적용할 것 없음"으로 바로 끝남
+//! • sqlx의 `Migrator`도 내부에서 lock을 잡지만 `run` 하는 동안뿐
+//!   → 직접 잡으면 기다리는 중이라는 상태를 보고할 수 있고, 시드 데이터 등 다른 시작 작업도 같은 lock 안에서 실행 가능
+//!   → 같은 일을 두 번 하지 않도록 `set_locking(false)`
+//! • advisory lock은 세션(커넥션)에 붙음
+//!   → 같은 커넥션에서 잡고, 실행하고, 풀어야 함
+//!   → 풀지 못한 커넥션은 풀로 돌려보내지 않고 닫음 (닫히면 lock도 풀림)
+
+use serde::Serialize;
+use sqlx::{migrate::Migrator, PgPool};
+use std::{sync::Arc, time::Duration};
+use tokio::sync::watch;
+
+/// 이 앱의 마이그레이션 전용 lock 번호 (DB 안에서 다른 용도와 겹치지 않게 고정)
+pub const MIGRATION_LOCK_KEY: i64 = 0x0065_7861_6d70_6c65; // "example"
+
+pub fn migrator() -> Migrator {
+    let mut migrator = sqlx::migrate!();
+    migrator.set_locking(false);
+    migrator
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum Phase {
+    Pending,
+    WaitingForLock,
+    Migrating,
+    Ready,
+    Failed { error: String },
+}
+
+/// 마이그레이션 진행 상태 (readiness, `/admin/migrations`에서 읽음)
+#[derive(Clone)]
+pub struct MigrationStatus(Arc<watch::Sender<Phase>>);
+
+impl Default for MigrationStatus {
+    fn default() -> Self {
+        Self(Arc::new(watch::Sender::new(Phase::Pending)))
+    }
+}
+
+impl MigrationStatus {
+    pub fn phase(&self) -> Phase {
+        self.0.borrow().clone()
+    }
+
+    pub fn is_ready(&self) -> bool {
+        *self.0.borrow() == Phase::Ready
+    }
+
+    #[cfg(test)]
+    pub fn subscribe(&self) -> watch::Receiver<Phase> {
+        self.0.subscribe()
+    }
+
+    fn set(&self, phase: Phase) {
+        self.0.send_replace(phase);
+    }
+}
+
+/// lock을 잡을 때까지 `poll` 간격으로 다시 시도하고, 결과를 `status`에 남김
+pub async fn run(
+    pool: &PgPool,
+    migrator: &Migrator,
+    status: &MigrationStatus,
+    poll: Duration,
+) -> Result<(), sqlx::Error> {
+    let result = lock_and_migrate(pool, migrator, status, poll).await;
+    match &result {
+        Ok(()) => status.set(Phase::Ready),
+        Err(err) => status.set(Phase::Failed {
+            error: err.to_string(),
+        }),
+    }
+    result
+}
+
+async fn lock_and_migrate(
+    pool: &PgPool,
+    migrator: &Migrator,
+    status: &MigrationStatus,
+    poll: Duration,
+) -> Result<(), sqlx::Error> {
+    let mut conn = pool.acquire().await?;
+
+    loop {
+        let locked: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+            .bind(MIGRATION_LOCK_KEY)
+            .fetch_one(&mut *conn)
+            .await?;
+        if locked {
+            break;
+        }
+        if status.phase() != Phase::WaitingForLock {
+            tracing::info!("another instance is migrating, waiting for the lock");
+            status.set(Phase::WaitingForLock);
+        }
+        tokio::time::sleep(poll).await;
+    }
+
+    status.set(Phase::Migrating);
+    tracing::info!("lock acquired, running migrations");
+    let migrated = migrator.run_direct(&mut *conn).await;
+
+    let unlocked = sqlx::query("SELECT pg_advisory_unlock($1)")
+        .bind(MIGRATION_LOCK_KEY)
+        .execute(&mut *conn)
+        .await;
+    if let Err(err) = unlocked {
+        tracing::warn!(%err, "failed to release the migration lock, closing the connection");
+        // 세션이 끝나면 lock도 풀림
+        drop(conn.detach());
+    }
+
+    migrated?;
+    tracing::info!("migrations are up to date");
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+pub struct MigrationInfo {
+    pub version: i64,
+    pub description: String,
+    pub applied: bool,
+    /// 적용된 뒤에 파일이 바뀌었으면 false
+    pub checksum_ok: Option<bool>,
+    pub installed_on: Option<String>,
+    pub execution_time_ms: Option<i64>,
+}
+
+#[derive(sqlx::FromRow)]
+struct AppliedMigration {
+    version: i64,
+    checksum: Vec<u8>,
+    installed_on: String,
+    execution_time: i64,
+}
+
+/// 바이너리에 포함된 마이그레이션과 DB에 적용된 기록(`_sqlx_migrations`)을 합침
+pub async fn report(pool: &PgPool, migrator: &Migrator) -> Result<Vec<MigrationInfo>, sqlx::Error> {
+    // 첫 마이그레이션 전에는 기록 테이블이 없음
+    let table_exists: bool =
+        sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+            .fetch_one(pool)
+            .await?;
+    let applied: Vec<AppliedMigration> = if table_exists {
+        sqlx::query_as(
+            "SELECT version, checksum, installed_on::text AS installed_on, execution_time
+             FROM _sqlx_migrations WHERE success ORDER BY version",
+        )
+        .fetch_all(pool)
+        .await?
+    } else {
+        Vec::new()
+    };
+
+    let migrations = migrator
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .map(|migration| {
+            let record = applied.iter().find(|row| row.version == migration.version);
+            MigrationInfo {
+                version: migration.version,
+                description: migration.description.to_string(),
+                applied: record.is_some(),
+                checksum_ok: record.map(|row| *row.checksum == *migration.checksum),
+                installed_on: record.map(|row| row.installed_on.clone()),
+                execution_time_ms: record.map(|row| row.execution_time / 1_000_000),
+            }
+        })
+        .collect();
+    Ok(migrations)
+}
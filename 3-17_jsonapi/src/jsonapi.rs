@@ -0,0 +1,455 @@
+//! 🧩 serde 모델을 JSON:API 문서로 감싸는 계층
+//!
+//! 모델은 평소처럼 `#[derive(Serialize)]`만 하고 `Resource`로 type / id / 관계만 알려주면 됨
+//! → 직렬화한 객체에서 `id`와 외래 키 필드를 빼고 나머지를 `attributes`로 사용
+//!
+//! • `JsonApiQuery`: `include=owner`, `fields[todos]=text,completed` (sparse fieldsets)
+//!   → sparse fieldsets는 attributes와 relationships 모두에 적용
+//! • `Document`: `data` + `included` (같은 type/id는 한 번만)
+//! • `JsonApiBody<R, A>`: 리소스 `R`을 만드는 `{"data": {"type", "attributes": A, "relationships"}}` 요청 본문
+//!   → Content-Type이 정확히 `application/vnd.api+json`이 아니면 415 (미디어 타입 파라미터도 금지)
+//! • `JsonApiError`: `{"errors": [{"status", "title", "detail"}]}`
+//!
+//! 모든 응답의 Content-Type은 `application/vnd.api+json`
+
+use axum::{
+    extract::{FromRequest, FromRequestParts, Request},
+    http::{header, request::Parts, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::{
+    collections::{HashMap, HashSet},
+    marker::PhantomData,
+};
+
+pub const MEDIA_TYPE: &str = "application/vnd.api+json";
+
+/// JSON:API 리소스로 내보낼 수 있는 모델
+pub trait Resource: Serialize {
+    /// 리소스 type (`/todos/1`처럼 self 링크 경로에도 사용)
+    const TYPE: &'static str;
+
+    fn id(&self) -> String;
+
+    /// to-one 관계 목록 (기본: 없음)
+    fn relationships(&self) -> Vec<Relationship> {
+        Vec::new()
+    }
+}
+
+/// to-one 관계 하나
+pub struct Relationship {
+    /// relationships 객체의 키 (`owner`)
+    pub name: &'static str,
+    /// attributes에서 뺄 외래 키 필드 (`owner_id`)
+    pub field: &'static str,
+    pub data: Option<Identifier>,
+}
+
+/// `{"type": "users", "id": "1"}`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Identifier {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub id: String,
+}
+
+impl Identifier {
+    pub fn of<T: Resource>(id: impl ToString) -> Self {
+        Self {
+            kind: T::TYPE.to_owned(),
+            id: id.to_string(),
+        }
+    }
+}
+
+// 🔎 쿼리 파라미터
+
+/// `include`와 `fields[TYPE]` 쿼리 파라미터
+#[derive(Debug, Default)]
+pub struct JsonApiQuery {
+    include: Vec<String>,
+    fields: HashMap<String, HashSet<String>>,
+}
+
+impl JsonApiQuery {
+    pub fn includes(&self, path: &str) -> bool {
+        self.include.iter().any(|include| include == path)
+    }
+
+    /// 지원하지 않는 include 경로는 400 (JSON:API 명세)
+    pub fn supported_includes(&self, supported: &[&str]) -> Result<(), JsonApiError> {
+        match self
+            .include
+            .iter()
+            .find(|include| !supported.contains(&include.as_str()))
+        {
+            Some(include) => Err(JsonApiError::bad_request(format!(
+                "unsupported include path `{include}`"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    // 해당 type에 fields가 지정되지 않았으면 모든 필드
+    fn wants(&self, kind: &str, field: &str) -> bool {
+        self.fields
+            .get(kind)
+            .is_none_or(|fields| fields.contains(field))
+    }
+
+    fn parse(query: &str) -> Result<Self, JsonApiError> {
+        let mut parsed = Self::default();
+        for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+            let list = || {
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(str::to_owned)
+            };
+
+            if key == "include" {
+                parsed.include.extend(list());
+            } else if let Some(rest) = key.strip_prefix("fields") {
+                let kind = rest
+                    .strip_prefix('[')
+                    .and_then(|rest| rest.strip_suffix(']'))
+                    .filter(|kind| !kind.is_empty())
+                    .ok_or_else(|| {
+                        JsonApiError::bad_request(format!(
+                            "`{key}` must be written as `fields[TYPE]`"
+                        ))
+                    })?;
+                // `fields[users]=`는 필드 없이 type/id만
+                parsed
+                    .fields
+                    .entry(kind.to_owned())
+                    .or_default()
+                    .extend(list());
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for JsonApiQuery {
+    type Rejection = JsonApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Self::parse(parts.uri.query().unwrap_or_default())
+    }
+}
+
+// 📦 응답 문서
+
+/// JSON:API 최상위 문서 (`data` + `included`)
+pub struct Document {
+    data: Value,
+    included: Vec<Value>,
+    // 이미 data나 included에 있는 리소스 (compound document에는 같은 리소스가 한 번만)
+    seen: HashSet<Identifier>,
+    status: StatusCode,
+}
+
+impl Document {
+    /// 리소스 하나
+    pub fn one<T: Resource>(resource: &T, query: &JsonApiQuery) -> Self {
+        Self {
+            data: resource_object(resource, query),
+            included: Vec::new(),
+            seen: HashSet::from([Identifier::of::<T>(resource.id())]),
+            status: StatusCode::OK,
+        }
+    }
+
+    /// 리소스 목록
+    pub fn many<'a, T: Resource + 'a>(
+        resources: impl IntoIterator<Item = &'a T>,
+        query: &JsonApiQuery,
+    ) -> Self {
+        let mut seen = HashSet::new();
+        let data = resources
+            .into_iter()
+            .map(|resource| {
+                seen.insert(Identifier::of::<T>(resource.id()));
+                resource_object(resource, query)
+            })
+            .collect();
+        Self {
+            data: Value::Array(data),
+            included: Vec::new(),
+            seen,
+            status: StatusCode::OK,
+        }
+    }
+
+    /// `included`에 관련 리소스 추가 (이미 있으면 무시)
+    pub fn include<T: Resource>(mut self, resource: &T, query: &JsonApiQuery) -> Self {
+        if self.seen.insert(Identifier::of::<T>(resource.id())) {
+            self.included.push(resource_object(resource, query));
+        }
+        self
+    }
+
+    pub fn with_status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+}
+
+impl IntoResponse for Document {
+    fn into_response(self) -> Response {
+        let mut body = json!({ "data": self.data });
+        // include를 요청하지 않았으면 included 키 자체를 생략
+        if !self.included.is_empty() {
+            body["included"] = Value::Array(self.included);
+        }
+        (self.status, jsonapi_content_type(), body.to_string()).into_response()
+    }
+}
+
+// 모델 → `{type, id, attributes, relationships, links}`
+fn resource_object<T: Resource>(resource: &T, query: &JsonApiQuery) -> Value {
+    let id = resource.id();
+    // 구조체가 아닌 모델(튜플 등)은 attributes 없음
+    let mut attributes = match serde_json::to_value(resource) {
+        Ok(Value::Object(object)) => object,
+        _ => Map::new(),
+    };
+    attributes.remove("id");
+
+    let mut relationships = Map::new();
+    for relationship in resource.relationships() {
+        attributes.remove(relationship.field);
+        if query.wants(T::TYPE, relationship.name) {
+            relationships.insert(
+                relationship.name.to_owned(),
+                json!({ "data": relationship.data }),
+            );
+        }
+    }
+    attributes.retain(|field, _| query.wants(T::TYPE, field));
+
+    let mut object = json!({
+        "type": T::TYPE,
+        "id": id,
+        "attributes": attributes,
+        "links": { "self": format!("/{}/{id}", T::TYPE) },
+    });
+    if !relationships.is_empty() {
+        object["relationships"] = Value::Object(relationships);
+    }
+    object
+}
+
+fn jsonapi_content_type() -> [(header::HeaderName, HeaderValue); 1] {
+    [(header::CONTENT_TYPE, HeaderValue::from_static(MEDIA_TYPE))]
+}
+
+// 📨 요청 본문
+
+/// 리소스 `R`에 대한 요청 본문의 `data` (`type`은 `R::TYPE`이어야 함)
+pub struct JsonApiBody<R, A> {
+    pub attributes: A,
+    /// 관계 이름 → 대상 (`null`이면 None)
+    pub relationships: HashMap<String, Option<Identifier>>,
+    _resource: PhantomData<R>,
+}
+
+#[derive(Deserialize)]
+struct RequestDocument<T> {
+    data: RequestData<T>,
+}
+
+#[derive(Deserialize)]
+struct RequestData<T> {
+    #[serde(rename = "type")]
+    kind: String,
+    attributes: T,
+    #[serde(default)]
+    relationships: HashMap<String, RelationshipData>,
+}
+
+#[derive(Deserialize)]
+struct RelationshipData {
+    data: Option<Identifier>,
+}
+
+impl<S, R, A> FromRequest<S> for JsonApiBody<R, A>
+where
+    S: Send + Sync,
+    R: Resource,
+    A: DeserializeOwned,
+{
+    type Rejection = JsonApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        // 파라미터가 붙은 미디어 타입(`; charset=utf-8` 등)도 415 (JSON:API 명세)
+        let content_type = req.headers().get(header::CONTENT_TYPE);
+        if content_type.is_none_or(|value| value != MEDIA_TYPE) {
+            return Err(JsonApiError::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                format!("Content-Type must be `{MEDIA_TYPE}` without parameters"),
+            ));
+        }
+
+        let bytes = axum::body::Bytes::from_request(req, state)
+            .await
+            .map_err(|err| JsonApiError::bad_request(err.body_text()))?;
+        let document: RequestDocument<A> = serde_json::from_slice(&bytes)
+            .map_err(|err| JsonApiError::new(StatusCode::UNPROCESSABLE_ENTITY, err.to_string()))?;
+
+        if document.data.kind != R::TYPE {
+            return Err(JsonApiError::new(
+                StatusCode::CONFLICT,
+                format!(
+                    "resource type `{}` does not match `{}`",
+                    document.data.kind,
+                    R::TYPE
+                ),
+            ));
+        }
+
+        Ok(Self {
+            attributes: document.data.attributes,
+            relationships: document
+                .data
+                .relationships
+                .into_iter()
+                .map(|(name, relationship)| (name, relationship.data))
+                .collect(),
+            _resource: PhantomData,
+        })
+    }
+}
+
+// 🚨 에러
+
+/// `{"errors": [{"status": "404", "title": "Not Found", "detail": ..}]}`
+#[derive(Debug)]
+pub struct JsonApiError {
+    status: StatusCode,
+    detail: String,
+}
+
+impl JsonApiError {
+    pub fn new(status: StatusCode, detail: impl Into<String>) -> Self {
+        Self {
+            status,
+            detail: detail.into(),
+        }
+    }
+
+    pub fn bad_request(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, detail)
+    }
+
+    pub fn not_found(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, detail)
+    }
+}
+
+impl IntoResponse for JsonApiError {
+    fn into_response(self) -> Response {
+        // status는 명세상 문자열
+        let body = json!({
+            "errors": [{
+                "status": self.status.as_str(),
+                "title": self.status.canonical_reason().unwrap_or_default(),
+                "detail": self.detail,
+            }]
+        });
+        (self.status, jsonapi_content_type(), body.to_string()).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Article {
+        id: u32,
+        title: String,
+        body: String,
+        author_id: Option<u32>,
+    }
+
+    impl Resource for Article {
+        const TYPE: &'static str = "articles";
+
+        fn id(&self) -> String {
+            self.id.to_string()
+        }
+
+        fn relationships(&self) -> Vec<Relationship> {
+            vec![Relationship {
+                name: "author",
+                field: "author_id",
+                data: self.author_id.map(|id| Identifier {
+                    kind: "people".to_owned(),
+                    id: id.to_string(),
+                }),
+            }]
+        }
+    }
+
+    fn article(author_id: Option<u32>) -> Article {
+        Article {
+            id: 1,
+            title: "JSON:API".to_owned(),
+            body: "...".to_owned(),
+            author_id,
+        }
+    }
+
+    #[test]
+    fn id_and_foreign_keys_are_moved_out_of_attributes() {
+        let object = resource_object(&article(Some(9)), &JsonApiQuery::default());
+        assert_eq!(
+            object,
+            json!({
+                "type": "articles",
+                "id": "1",
+                "attributes": { "title": "JSON:API", "body": "..." },
+                "relationships": { "author": { "data": { "type": "people", "id": "9" } } },
+                "links": { "self": "/articles/1" },
+            })
+        );
+
+        // 비어 있는 to-one 관계는 data: null
+        let object = resource_object(&article(None), &JsonApiQuery::default());
+        assert_eq!(object["relationships"]["author"], json!({ "data": null }));
+    }
+
+    #[test]
+    fn sparse_fieldsets_apply_to_attributes_and_relationships() {
+        let query = JsonApiQuery::parse("fields%5Barticles%5D=title&fields[people]=name").unwrap();
+        let object = resource_object(&article(Some(9)), &query);
+        assert_eq!(object["attributes"], json!({ "title": "JSON:API" }));
+        assert!(object.get("relationships").is_none());
+
+        let query = JsonApiQuery::parse("fields[articles]=").unwrap();
+        let object = resource_object(&article(Some(9)), &query);
+        assert_eq!(object["attributes"], json!({}));
+
+        for invalid in ["fields=title", "fields[]=title", "fields[articles=title"] {
+            let error = JsonApiQuery::parse(invalid).unwrap_err();
+            assert_eq!(error.status, StatusCode::BAD_REQUEST, "{invalid}");
+        }
+    }
+
+    #[test]
+    fn include_paths_are_comma_separated() {
+        let query = JsonApiQuery::parse("include=author,%20comments").unwrap();
+        assert!(query.includes("author"));
+        assert!(query.includes("comments"));
+        assert!(query.supported_includes(&["author", "comments"]).is_ok());
+
+        let error = query.supported_includes(&["author"]).unwrap_err();
+        assert_eq!(error.detail, "unsupported include path `comments`");
+    }
+}
@@ -0,0 +1,382 @@
+//! JSON:API(<https://jsonapi.org>) 형식으로 Todo 리소스를 제공하는 예제
+//!
+//! - `GET /todos`: Todo 목록 (`include=owner`, `fields[todos]=..`, `fields[users]=..`)
+//! - `POST /todos`: Todo 생성 (`Content-Type: application/vnd.api+json`)
+//! - `GET /todos/{id}`: Todo 하나
+//! - `GET /users/{id}`: 사용자 하나
+//!
+//! 모델(`Todo`, `User`)은 일반 serde 구조체이고, JSON:API 문서로 감싸는 일은 `jsonapi` 모듈이 담당
+//! → 3-09_todos의 모델에 `Resource` 구현만 추가하면 같은 방식으로 내보낼 수 있음
+
+mod jsonapi;
+
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use jsonapi::{
+    Document, Identifier, JsonApiBody, JsonApiError, JsonApiQuery, Relationship, Resource,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(seeded_db())).await.unwrap();
+}
+
+fn app(db: Db) -> Router {
+    Router::new()
+        .route("/todos", get(list_todos).post(create_todo))
+        .route("/todos/{id}", get(get_todo))
+        .route("/users/{id}", get(get_user))
+        .with_state(db)
+}
+
+// 📦 모델 (일반 serde 구조체)
+
+#[derive(Debug, Clone, Serialize)]
+struct Todo {
+    id: u64,
+    text: String,
+    completed: bool,
+    owner_id: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct User {
+    id: u64,
+    name: String,
+    email: String,
+}
+
+// 🧩 JSON:API 매핑 (type, id, 관계)
+
+impl Resource for Todo {
+    const TYPE: &'static str = "todos";
+
+    fn id(&self) -> String {
+        self.id.to_string()
+    }
+
+    fn relationships(&self) -> Vec<Relationship> {
+        vec![Relationship {
+            name: "owner",
+            field: "owner_id",
+            data: Some(Identifier::of::<User>(self.owner_id)),
+        }]
+    }
+}
+
+impl Resource for User {
+    const TYPE: &'static str = "users";
+
+    fn id(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+#[derive(Default)]
+struct Store {
+    todos: BTreeMap<u64, Todo>,
+    users: BTreeMap<u64, User>,
+    next_id: u64,
+}
+
+type Db = Arc<RwLock<Store>>;
+
+// 사용자 2명 + alice의 Todo 2개
+fn seeded_db() -> Db {
+    let mut store = Store::default();
+    for (id, name) in [(1, "alice"), (2, "bob")] {
+        store.users.insert(
+            id,
+            User {
+                id,
+                name: name.to_owned(),
+                email: format!("{name}@example.com"),
+            },
+        );
+    }
+    for (id, text) in [(1, "Buy milk"), (2, "Write docs")] {
+        store.todos.insert(
+            id,
+            Todo {
+                id,
+                text: text.to_owned(),
+                completed: false,
+                owner_id: 1,
+            },
+        );
+    }
+    store.next_id = 3;
+    Arc::new(RwLock::new(store))
+}
+
+// JSON:API의 id는 문자열이므로 Path<String>으로 받고, 숫자가 아니면 그냥 없는 리소스
+fn parse_id(id: &str) -> Option<u64> {
+    id.parse().ok()
+}
+
+// 📚 핸들러
+
+// GET /todos?include=owner&fields[todos]=text&fields[users]=name
+async fn list_todos(State(db): State<Db>, query: JsonApiQuery) -> Result<Document, JsonApiError> {
+    query.supported_includes(&["owner"])?;
+    let store = db.read().unwrap();
+
+    let mut document = Document::many(store.todos.values(), &query);
+    if query.includes("owner") {
+        // 같은 사용자는 included에 한 번만 들어감
+        for todo in store.todos.values() {
+            if let Some(owner) = store.users.get(&todo.owner_id) {
+                document = document.include(owner, &query);
+            }
+        }
+    }
+    Ok(document)
+}
+
+// GET /todos/{id}
+async fn get_todo(
+    State(db): State<Db>,
+    Path(id): Path<String>,
+    query: JsonApiQuery,
+) -> Result<Document, JsonApiError> {
+    query.supported_includes(&["owner"])?;
+    let store = db.read().unwrap();
+
+    let todo = parse_id(&id)
+        .and_then(|id| store.todos.get(&id))
+        .ok_or_else(|| JsonApiError::not_found(format!("todo {id} not found")))?;
+
+    let mut document = Document::one(todo, &query);
+    if query.includes("owner") {
+        if let Some(owner) = store.users.get(&todo.owner_id) {
+            document = document.include(owner, &query);
+        }
+    }
+    Ok(document)
+}
+
+#[derive(Debug, Deserialize)]
+struct NewTodo {
+    text: String,
+    #[serde(default)]
+    completed: bool,
+}
+
+// POST /todos
+// {"data": {"type": "todos", "attributes": {"text": ..}, "relationships": {"owner": {"data": {"type": "users", "id": "1"}}}}}
+async fn create_todo(
+    State(db): State<Db>,
+    query: JsonApiQuery,
+    body: JsonApiBody<Todo, NewTodo>,
+) -> Result<impl IntoResponse, JsonApiError> {
+    let owner = body
+        .relationships
+        .get("owner")
+        .cloned()
+        .flatten()
+        .ok_or_else(|| {
+            JsonApiError::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "relationship `owner` is required",
+            )
+        })?;
+
+    let mut store = db.write().unwrap();
+    let owner_id = parse_id(&owner.id)
+        .filter(|id| owner.kind == User::TYPE && store.users.contains_key(id))
+        .ok_or_else(|| JsonApiError::not_found(format!("user {} not found", owner.id)))?;
+
+    let todo = Todo {
+        id: store.next_id,
+        text: body.attributes.text,
+        completed: body.attributes.completed,
+        owner_id,
+    };
+    store.next_id += 1;
+    store.todos.insert(todo.id, todo.clone());
+
+    // 201 + Location (JSON:API 명세)
+    let location = format!("/{}/{}", Todo::TYPE, todo.id);
+    Ok((
+        [(header::LOCATION, location)],
+        Document::one(&todo, &query).with_status(StatusCode::CREATED),
+    ))
+}
+
+// GET /users/{id}
+async fn get_user(
+    State(db): State<Db>,
+    Path(id): Path<String>,
+    query: JsonApiQuery,
+) -> Result<Document, JsonApiError> {
+    query.supported_includes(&[])?;
+    let store = db.read().unwrap();
+
+    let user = parse_id(&id)
+        .and_then(|id| store.users.get(&id))
+        .ok_or_else(|| JsonApiError::not_found(format!("user {id} not found")))?;
+    Ok(Document::one(user, &query))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request, response::Response};
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    async fn send(app: &Router, request: Request<Body>) -> (Response<()>, Value) {
+        let response = app.clone().oneshot(request).await.unwrap();
+        let (parts, body) = response.into_parts();
+        let bytes = body.collect().await.unwrap().to_bytes();
+        (
+            Response::from_parts(parts, ()),
+            serde_json::from_slice(&bytes).unwrap(),
+        )
+    }
+
+    fn get(uri: &str) -> Request<Body> {
+        Request::get(uri).body(Body::empty()).unwrap()
+    }
+
+    fn create(content_type: &str, body: Value) -> Request<Body> {
+        Request::post("/todos")
+            .header("content-type", content_type)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn compound_document_with_sparse_fieldsets() {
+        let app = app(seeded_db());
+        let (response, body) = send(
+            &app,
+            get("/todos?include=owner&fields[todos]=text,owner&fields[users]=name"),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], jsonapi::MEDIA_TYPE,);
+        assert_eq!(
+            body["data"][0],
+            json!({
+                "type": "todos",
+                "id": "1",
+                "attributes": { "text": "Buy milk" },
+                "relationships": { "owner": { "data": { "type": "users", "id": "1" } } },
+                "links": { "self": "/todos/1" },
+            })
+        );
+        // 두 Todo의 owner가 같으므로 included에는 한 번만
+        assert_eq!(
+            body["included"],
+            json!([{
+                "type": "users",
+                "id": "1",
+                "attributes": { "name": "alice" },
+                "links": { "self": "/users/1" },
+            }])
+        );
+
+        let (response, body) = send(&app, get("/todos?include=comments")).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(body["errors"][0]["status"], "400");
+    }
+
+    #[tokio::test]
+    async fn create_requires_the_jsonapi_media_type() {
+        let app = app(seeded_db());
+        let document = json!({
+            "data": {
+                "type": "todos",
+                "attributes": { "text": "Ship it" },
+                "relationships": { "owner": { "data": { "type": "users", "id": "2" } } },
+            }
+        });
+
+        for content_type in [
+            "application/json",
+            "application/vnd.api+json; charset=utf-8",
+        ] {
+            let (response, body) = send(&app, create(content_type, document.clone())).await;
+            assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+            assert_eq!(body["errors"][0]["title"], "Unsupported Media Type");
+        }
+
+        let (response, body) = send(&app, create(jsonapi::MEDIA_TYPE, document)).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.headers()["location"], "/todos/3");
+        assert_eq!(body["data"]["attributes"]["completed"], false);
+        assert_eq!(body["data"]["relationships"]["owner"]["data"]["id"], "2");
+
+        let (response, _) = send(&app, get("/todos/3?include=owner")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn create_rejects_mismatched_type_and_unknown_owner() {
+        let app = app(seeded_db());
+
+        let wrong_type = json!({ "data": { "type": "users", "attributes": { "text": "x" } } });
+        let (response, _) = send(&app, create(jsonapi::MEDIA_TYPE, wrong_type)).await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let no_owner = json!({ "data": { "type": "todos", "attributes": { "text": "x" } } });
+        let (response, _) = send(&app, create(jsonapi::MEDIA_TYPE, no_owner)).await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let unknown_owner = json!({
+            "data": {
+                "type": "todos",
+                "attributes": { "text": "x" },
+                "relationships": { "owner": { "data": { "type": "users", "id": "42" } } },
+            }
+        });
+        let (response, body) = send(&app, create(jsonapi::MEDIA_TYPE, unknown_owner)).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(body["errors"][0]["detail"], "user 42 not found");
+    }
+}
+
+// 🧪 테스트 방법
+//
+// ✅ 목록 + owner 포함 + 필요한 필드만
+// curl -g 'localhost:3000/todos?include=owner&fields[todos]=text,owner&fields[users]=name'
+// → {"data":[{"type":"todos","id":"1","attributes":{"text":"Buy milk"},
+//    "relationships":{"owner":{"data":{"type":"users","id":"1"}}},"links":{"self":"/todos/1"}},...],
+//    "included":[{"type":"users","id":"1","attributes":{"name":"alice"},"links":{"self":"/users/1"}}]}
+//
+// ✅ 생성
+// curl -i localhost:3000/todos -H 'content-type: application/vnd.api+json' \
+//   -d '{"data":{"type":"todos","attributes":{"text":"Ship it"},"relationships":{"owner":{"data":{"type":"users","id":"2"}}}}}'
+// → 201, location: /todos/3
+//
+// ❌ 일반 JSON으로 보내면 415
+// curl -i localhost:3000/todos -H 'content-type: application/json' -d '{}'
+// → {"errors":[{"status":"415","title":"Unsupported Media Type","detail":"..."}]}
+//
+// (curl -g: 대괄호를 URL glob으로 해석하지 않도록)
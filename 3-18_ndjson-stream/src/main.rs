@@ -0,0 +1,278 @@
+//! 큰 조회 결과를 NDJSON(JSON Lines)으로 스트리밍하는 예제
+//!
+//! `GET /events/export` → `Content-Type: application/x-ndjson`, 한 줄에 이벤트 하나
+//!
+//! • `Json(Vec<_>)`로 응답하면 모든 행을 메모리에 올린 뒤에야 첫 바이트를 보냄
+//!   → 행을 읽는 대로 한 줄씩 보내면 메모리 사용량이 결과 크기와 상관없이 일정
+//! • 백프레셔: DB를 읽는 태스크와 응답 본문 사이에 크기가 정해진 채널(`CHANNEL_CAPACITY`)
+//!   → 클라이언트가 느리면 채널이 가득 차서 `send`에서 기다림 = DB에서 더 읽지 않음
+//! • 클라이언트 연결 끊김: hyper가 응답 본문(채널 receiver)을 drop
+//!   → `send` 실패 또는 `tx.closed()`로 알아채고 DB 커서를 닫음 (끝까지 읽지 않음)
+//! • 도중에 DB 에러가 나면 상태 코드는 이미 200으로 나갔으므로 본문을 에러로 끝냄
+//!   → 클라이언트는 불완전한 전송(chunked 종료 없음)으로 실패를 알 수 있음
+//!
+//! ```not_rust
+//! cargo run -p example-ndjson-stream                                # 메모리 SQLite + 예제 데이터
+//! DATABASE_URL=sqlite:events.db cargo run -p example-ndjson-stream  # 파일 DB
+//! ```
+
+use axum::{
+    body::{Body, Bytes},
+    extract::State,
+    http::header,
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use futures::TryStreamExt;
+use serde::Serialize;
+use sqlx::{sqlite::SqlitePoolOptions, SqlitePool};
+use std::io;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+// 응답 본문에 쌓아 둘 수 있는 최대 줄 수 (이만큼 앞서 읽으면 DB 읽기를 멈춤)
+const CHANNEL_CAPACITY: usize = 64;
+
+// 예제 데이터 행 수
+const SEED_ROWS: i64 = 100_000;
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let url = std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite::memory:".into());
+    // 메모리 DB는 연결마다 따로 생기므로 연결 하나만 사용
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect(&url)
+        .await
+        .expect("can't connect to database");
+    seed(&pool, SEED_ROWS).await.unwrap();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(pool)).await.unwrap();
+}
+
+fn app(pool: SqlitePool) -> Router {
+    Router::new()
+        .route("/events/export", get(export_events))
+        .with_state(pool)
+}
+
+// 테이블이 비어 있으면 `rows`개의 이벤트를 채움
+async fn seed(pool: &SqlitePool, rows: i64) -> sqlx::Result<()> {
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS events (
+            id INTEGER PRIMARY KEY,
+            kind TEXT NOT NULL,
+            payload TEXT NOT NULL
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM events")
+        .fetch_one(pool)
+        .await?;
+    if count == 0 {
+        sqlx::query(
+            "WITH RECURSIVE seq(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM seq WHERE n < ?)
+             INSERT INTO events (id, kind, payload)
+             SELECT n, CASE n % 3 WHEN 0 THEN 'signup' WHEN 1 THEN 'login' ELSE 'purchase' END,
+                    'event #' || n
+             FROM seq",
+        )
+        .bind(rows)
+        .execute(pool)
+        .await?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+struct Event {
+    id: i64,
+    kind: String,
+    payload: String,
+}
+
+// 📤 GET /events/export
+async fn export_events(State(pool): State<SqlitePool>) -> impl IntoResponse {
+    let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+    // 응답을 돌려준 뒤에도 계속 읽어야 하므로 별도 태스크
+    tokio::spawn(stream_rows(pool, tx));
+
+    (
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(ReceiverStream::new(rx)),
+    )
+}
+
+/// 스트리밍이 끝난 이유 (보낸 줄 수 포함)
+#[derive(Debug, PartialEq, Eq)]
+enum Export {
+    Finished(u64),
+    Disconnected(u64),
+    Failed(u64),
+}
+
+// DB 커서에서 한 행씩 읽어 NDJSON 한 줄로 보냄
+async fn stream_rows(pool: SqlitePool, tx: mpsc::Sender<io::Result<Bytes>>) -> Export {
+    let mut rows =
+        sqlx::query_as::<_, Event>("SELECT id, kind, payload FROM events ORDER BY id").fetch(&pool);
+    let mut sent = 0;
+
+    let outcome = loop {
+        // 다음 행을 기다리는 동안 연결이 끊겨도 바로 멈춤
+        let row = tokio::select! {
+            _ = tx.closed() => break Export::Disconnected(sent),
+            row = rows.try_next() => row,
+        };
+
+        match row {
+            Ok(Some(event)) => {
+                let mut line = serde_json::to_vec(&event).expect("event is serializable");
+                line.push(b'\n');
+                // 채널이 가득 차 있으면 여기서 기다림 (백프레셔)
+                if tx.send(Ok(line.into())).await.is_err() {
+                    break Export::Disconnected(sent);
+                }
+                sent += 1;
+            }
+            Ok(None) => break Export::Finished(sent),
+            Err(err) => {
+                tracing::error!(%err, sent, "export failed");
+                // 본문을 에러로 끝내서 클라이언트가 잘린 응답임을 알 수 있게 함
+                let _ = tx.send(Err(io::Error::other(err))).await;
+                break Export::Failed(sent);
+            }
+        }
+    };
+
+    if let Export::Disconnected(sent) = outcome {
+        tracing::info!(sent, "client disconnected, export stopped");
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    async fn pool(rows: i64) -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        seed(&pool, rows).await.unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn lines_can_be_read_before_the_export_finishes() {
+        let app = app(pool(10_000).await);
+        let response = app
+            .oneshot(Request::get("/events/export").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/x-ndjson"
+        );
+
+        // 프레임을 하나씩 읽으며 처음 세 줄만 확인
+        let mut body = response.into_body();
+        let mut buffer = Vec::new();
+        while buffer.iter().filter(|&&byte| byte == b'\n').count() < 3 {
+            let frame = body.frame().await.unwrap().unwrap();
+            buffer.extend_from_slice(&frame.into_data().unwrap());
+        }
+        let ids: Vec<i64> = buffer
+            .split(|&byte| byte == b'\n')
+            .take(3)
+            .map(|line| {
+                serde_json::from_slice::<Value>(line).unwrap()["id"]
+                    .as_i64()
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(ids, [1, 2, 3]);
+
+        // 나머지도 모두 한 줄에 하나씩
+        buffer.extend_from_slice(&body.collect().await.unwrap().to_bytes());
+        let lines: Vec<Value> = buffer
+            .split(|&byte| byte == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 10_000);
+        assert_eq!(lines[9_999]["payload"], "event #10000");
+    }
+
+    #[tokio::test]
+    async fn slow_or_gone_clients_stop_the_database_reader() {
+        let (tx, mut rx) = mpsc::channel(4);
+        let export = tokio::spawn(stream_rows(pool(10_000).await, tx));
+
+        // 두 줄만 읽고 잠시 멈춤 → 채널(4) + 읽은 2줄보다 훨씬 많이 읽지 않아야 함
+        rx.recv().await.unwrap().unwrap();
+        rx.recv().await.unwrap().unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(!export.is_finished());
+
+        // 연결이 끊긴 것처럼 receiver를 drop
+        drop(rx);
+        let Export::Disconnected(sent) = export.await.unwrap() else {
+            panic!("export should stop when the client goes away");
+        };
+        assert!(sent <= 2 + 4, "sent {sent} lines");
+    }
+
+    #[tokio::test]
+    async fn database_errors_end_the_body_with_an_error() {
+        let pool = pool(1).await;
+        sqlx::query("DROP TABLE events")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let response = app(pool)
+            .oneshot(Request::get("/events/export").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.into_body().collect().await.is_err());
+    }
+}
+
+// 🧪 테스트 방법
+//
+// ✅ 스트리밍으로 받기 (-N: curl 버퍼링 끄기)
+// curl -N localhost:3000/events/export | head -3
+// → {"id":1,"kind":"login","payload":"event #1"}
+//   {"id":2,"kind":"purchase","payload":"event #2"}
+//   {"id":3,"kind":"signup","payload":"event #3"}
+// → head가 끝나면서 연결을 끊으면 서버 로그에 "client disconnected, export stopped"
+//
+// ✅ 느린 클라이언트 (초당 1KB만 읽음, 서버 메모리는 늘지 않음)
+// curl -N --limit-rate 1K localhost:3000/events/export > /dev/null
+//
+// ✅ 줄 단위 처리
+// curl -sN localhost:3000/events/export | jq -c 'select(.kind == "purchase")' | wc -l
@@ -0,0 +1,378 @@
+//! CSV 내보내기/가져오기 예제
+//!
+//! - `GET /todos.csv`: Todo 전체를 CSV로 다운로드
+//! - `POST /todos/import`: multipart `file` 필드의 CSV를 읽어 Todo로 추가, 줄별 에러를 요약해서 응답
+//!
+//! • 내보내기: 저장소에서 `CHUNK_SIZE`개씩 읽어 바로 직렬화 → 전체 CSV를 메모리에 만들지 않음
+//!   → 직렬화 태스크와 응답 본문 사이는 `tokio::io::duplex` 파이프 (버퍼가 차면 직렬화가 기다림)
+//! • 가져오기: 업로드 스트림을 `StreamReader`로 감싸 csv_async가 한 줄씩 역직렬화
+//!   → 파일 크기와 상관없이 한 행만 메모리에 있음
+//!   → 형식이 틀린 줄은 건너뛰고 `{line, message}`로 보고 (나머지 줄은 계속 가져옴)
+//! • 헤더 이름으로 열을 찾으므로 열 순서는 상관없고, 내보낸 CSV를 그대로 다시 가져올 수 있음 (`id` 열은 무시)
+
+use axum::{
+    body::Body,
+    extract::{DefaultBodyLimit, Multipart, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use futures::{StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    io,
+    ops::Bound,
+    sync::{Arc, RwLock},
+};
+use tokio::io::AsyncWrite;
+use tokio_util::io::{ReaderStream, StreamReader};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+// 내보낼 때 한 번에 읽는 Todo 수 (읽는 동안만 잠금)
+const CHUNK_SIZE: usize = 100;
+// 직렬화 태스크 → 응답 본문 파이프 버퍼
+const PIPE_BUFFER: usize = 64 * 1024;
+// 업로드 크기 제한 (스트리밍으로 읽으므로 메모리 사용량과는 무관)
+const MAX_UPLOAD_BYTES: usize = 50 * 1024 * 1024;
+// 응답에 담을 줄별 에러 최대 개수 (나머지는 `failed` 개수로만)
+const MAX_REPORTED_ERRORS: usize = 100;
+const MAX_TEXT_LEN: usize = 200;
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(Db::default())).await.unwrap();
+}
+
+fn app(db: Db) -> Router {
+    Router::new()
+        .route("/todos.csv", get(export_csv))
+        .route(
+            "/todos/import",
+            post(import_csv).layer(DefaultBodyLimit::max(MAX_UPLOAD_BYTES)),
+        )
+        .with_state(db)
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Todo {
+    id: u64,
+    text: String,
+    completed: bool,
+}
+
+#[derive(Default)]
+struct Store {
+    todos: BTreeMap<u64, Todo>,
+    next_id: u64,
+}
+
+type Db = Arc<RwLock<Store>>;
+
+// 📤 GET /todos.csv
+async fn export_csv(State(db): State<Db>) -> impl IntoResponse {
+    let (writer, reader) = tokio::io::duplex(PIPE_BUFFER);
+    tokio::spawn(async move {
+        // 클라이언트가 끊으면 reader가 drop되어 쓰기가 BrokenPipe로 실패
+        if let Err(err) = write_csv(&db, writer).await {
+            tracing::debug!(%err, "csv export stopped");
+        }
+    });
+
+    (
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"todos.csv\"",
+            ),
+        ],
+        Body::from_stream(ReaderStream::new(reader)),
+    )
+}
+
+async fn write_csv(db: &Db, writer: impl AsyncWrite + Unpin) -> csv_async::Result<()> {
+    let mut serializer = csv_async::AsyncSerializer::from_writer(writer);
+    let mut after = Bound::Unbounded;
+
+    loop {
+        // 잠금은 await 전에 풀어야 하므로 한 묶음씩 복사
+        let chunk: Vec<Todo> = db
+            .read()
+            .unwrap()
+            .todos
+            .range((after, Bound::Unbounded))
+            .take(CHUNK_SIZE)
+            .map(|(_, todo)| todo.clone())
+            .collect();
+        let Some(last) = chunk.last() else {
+            break;
+        };
+        after = Bound::Excluded(last.id);
+
+        for todo in &chunk {
+            serializer.serialize(todo).await?;
+        }
+    }
+
+    serializer.flush().await?;
+    Ok(())
+}
+
+// 📥 POST /todos/import
+
+// 헤더 이름으로 매칭 (id 등 다른 열은 무시)
+#[derive(Debug, Deserialize)]
+struct ImportRow {
+    text: String,
+    // 열이 없거나 값이 비어 있으면 false
+    #[serde(default)]
+    completed: Option<bool>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct ImportSummary {
+    imported: u64,
+    failed: u64,
+    /// 처음 `MAX_REPORTED_ERRORS`개만
+    errors: Vec<LineError>,
+}
+
+#[derive(Debug, Serialize)]
+struct LineError {
+    /// CSV 파일의 줄 번호 (헤더가 1번 줄)
+    line: u64,
+    message: String,
+}
+
+impl ImportSummary {
+    fn fail(&mut self, line: u64, message: impl Into<String>) {
+        self.failed += 1;
+        if self.errors.len() < MAX_REPORTED_ERRORS {
+            self.errors.push(LineError {
+                line,
+                message: message.into(),
+            });
+        }
+    }
+}
+
+async fn import_csv(
+    State(db): State<Db>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let bad_request =
+        |err: axum::extract::multipart::MultipartError| (StatusCode::BAD_REQUEST, err.body_text());
+
+    // `file` 필드를 찾을 때까지 다른 필드는 건너뜀
+    let field = loop {
+        match multipart.next_field().await.map_err(bad_request)? {
+            Some(field) if field.name() == Some("file") => break field,
+            Some(_) => continue,
+            None => return Err((StatusCode::BAD_REQUEST, "missing `file` field".to_owned())),
+        }
+    };
+
+    let reader = StreamReader::new(field.map_err(io::Error::other));
+    let mut deserializer = csv_async::AsyncReaderBuilder::new()
+        .trim(csv_async::Trim::All)
+        .create_deserializer(reader);
+    let mut rows = deserializer.deserialize_with_pos::<ImportRow>();
+
+    let mut summary = ImportSummary::default();
+    while let Some((row, position)) = rows.next().await {
+        let line = position.line();
+        let row = match row {
+            Ok(row) => row,
+            // 업로드가 끊긴 경우: 이미 가져온 행은 그대로 두고 요약과 함께 400
+            Err(err) if matches!(err.kind(), csv_async::ErrorKind::Io(_)) => {
+                summary.fail(line, format!("upload interrupted: {err}"));
+                return Ok((StatusCode::BAD_REQUEST, Json(summary)));
+            }
+            Err(err) => {
+                let message = match err.kind() {
+                    csv_async::ErrorKind::Deserialize { err, .. } => err.to_string(),
+                    _ => err.to_string(),
+                };
+                summary.fail(line, message);
+                continue;
+            }
+        };
+
+        if let Err(message) = validate(&row) {
+            summary.fail(line, message);
+            continue;
+        }
+
+        let mut store = db.write().unwrap();
+        store.next_id += 1;
+        let todo = Todo {
+            id: store.next_id,
+            text: row.text,
+            completed: row.completed.unwrap_or_default(),
+        };
+        store.todos.insert(todo.id, todo);
+        summary.imported += 1;
+    }
+
+    tracing::debug!(summary.imported, summary.failed, "csv import finished");
+    Ok((StatusCode::OK, Json(summary)))
+}
+
+fn validate(row: &ImportRow) -> Result<(), String> {
+    if row.text.is_empty() {
+        return Err("text must not be empty".to_owned());
+    }
+    if row.text.chars().count() > MAX_TEXT_LEN {
+        return Err(format!("text must be at most {MAX_TEXT_LEN} characters"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    const BOUNDARY: &str = "csv-boundary";
+
+    fn upload(csv: &str) -> Request<Body> {
+        let body = format!(
+            "--{BOUNDARY}\r\n\
+             Content-Disposition: form-data; name=\"file\"; filename=\"todos.csv\"\r\n\
+             Content-Type: text/csv\r\n\r\n\
+             {csv}\r\n\
+             --{BOUNDARY}--\r\n"
+        );
+        Request::post("/todos/import")
+            .header(
+                "content-type",
+                format!("multipart/form-data; boundary={BOUNDARY}"),
+            )
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    async fn import(app: &Router, csv: &str) -> (StatusCode, Value) {
+        let response = app.clone().oneshot(upload(csv)).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    async fn export(app: &Router) -> String {
+        let response = app
+            .clone()
+            .oneshot(Request::get("/todos.csv").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/csv; charset=utf-8"
+        );
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn invalid_lines_are_reported_and_the_rest_imported() {
+        let app = app(Db::default());
+        let csv = "text,completed\n\
+                   Buy milk,false\n\
+                   ,true\n\
+                   \"Write, with comma\",true\n\
+                   Walk dog,maybe\n\
+                   Call mom,\n";
+        let (status, summary) = import(&app, csv).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(summary["imported"], 3);
+        assert_eq!(summary["failed"], 2);
+        assert_eq!(
+            summary["errors"][0],
+            json!({ "line": 3, "message": "text must not be empty" })
+        );
+        assert_eq!(summary["errors"][1]["line"], 5);
+        assert_eq!(
+            summary["errors"][1],
+            json!({ "line": 5, "message": "field 2: provided string was not `true` or `false`" })
+        );
+
+        assert_eq!(
+            export(&app).await,
+            "id,text,completed\n\
+             1,Buy milk,false\n\
+             2,\"Write, with comma\",true\n\
+             3,Call mom,false\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn export_round_trips_across_chunks() {
+        let app = app(Db::default());
+        let rows = CHUNK_SIZE * 2 + 7;
+        let csv: String = std::iter::once("completed,text\n".to_owned())
+            .chain((1..=rows).map(|n| format!("{},todo {n}\n", n % 2 == 0)))
+            .collect();
+        let (_, summary) = import(&app, &csv).await;
+        assert_eq!(summary["imported"], rows);
+
+        let exported = export(&app).await;
+        assert_eq!(exported.lines().count(), rows + 1);
+        assert_eq!(
+            exported.lines().last().unwrap(),
+            format!("{rows},todo {rows},false")
+        );
+
+        // 내보낸 CSV를 다시 가져오면 id 열은 무시하고 새 id
+        let (_, summary) = import(&app, &exported).await;
+        assert_eq!(summary["imported"], rows);
+        assert_eq!(summary["failed"], 0);
+    }
+
+    #[tokio::test]
+    async fn missing_file_field_is_rejected() {
+        let request = Request::post("/todos/import")
+            .header(
+                "content-type",
+                format!("multipart/form-data; boundary={BOUNDARY}"),
+            )
+            .body(Body::from(format!(
+                "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"other\"\r\n\r\nx\r\n--{BOUNDARY}--\r\n"
+            )))
+            .unwrap();
+        let response = app(Db::default()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
+
+// 🧪 테스트 방법
+//
+// ✅ 가져오기 (줄별 에러 요약)
+// printf 'text,completed\nBuy milk,false\n,true\nWalk dog,maybe\n' > todos.csv
+// curl -F file=@todos.csv localhost:3000/todos/import
+// → {"imported":1,"failed":2,"errors":[{"line":3,"message":"text must not be empty"},
+//    {"line":4,"message":"field 2: provided string was not `true` or `false`"}]}
+//
+// ✅ 내보내기
+// curl -OJ localhost:3000/todos.csv   # Content-Disposition의 파일 이름(todos.csv)으로 저장
+//
+// ✅ 큰 파일 (10만 줄도 메모리 사용량은 그대로)
+// seq 1 100000 | sed 's/^/todo /' | sed '1i text' > big.csv
+// curl -F file=@big.csv localhost:3000/todos/import
@@ -0,0 +1,232 @@
+//! 엑셀(xlsx) 파일 내보내기 예제
+//!
+//! `GET /exports/sales.xlsx?rows=50000` → 판매 데이터 시트를 만들어 다운로드
+//!
+//! • xlsx 생성은 CPU를 오래 쓰는 동기 코드 → `spawn_blocking`에서 실행 (async 워커 스레드를 막지 않음)
+//! • 결과는 이름 없는 임시 파일에 저장한 뒤 `ReaderStream`으로 스트리밍
+//!   → 파일 전체를 메모리에 올리지 않고, 전송이 끝나 파일이 닫히면 OS가 삭제
+//! • 동시에 만들 수 있는 파일 수는 state의 `Semaphore`로 제한 (`MAX_CONCURRENT_EXPORTS`)
+//!   → 자리가 없으면 기다리지 않고 503 + `Retry-After`
+//!   → 허가(permit)는 생성이 끝날 때까지만 잡고, 전송 중에는 다음 작업이 시작될 수 있음
+
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use rust_xlsxwriter::{Format, Workbook};
+use serde::Deserialize;
+use std::{
+    error::Error,
+    fs::File,
+    io::{Seek, SeekFrom},
+    sync::Arc,
+};
+use tokio::sync::Semaphore;
+use tokio_util::io::ReaderStream;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+const MAX_CONCURRENT_EXPORTS: usize = 2;
+const DEFAULT_ROWS: u32 = 10_000;
+const MAX_ROWS: u32 = 1_000_000;
+
+const XLSX_CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+#[derive(Clone)]
+struct AppState {
+    // 진행 중인 내보내기 작업 수 제한
+    exports: Arc<Semaphore>,
+}
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let state = AppState {
+        exports: Arc::new(Semaphore::new(MAX_CONCURRENT_EXPORTS)),
+    };
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(state)).await.unwrap();
+}
+
+fn app(state: AppState) -> Router {
+    Router::new()
+        .route("/exports/sales.xlsx", get(export_sales))
+        .with_state(state)
+}
+
+#[derive(Debug, Deserialize)]
+struct ExportParams {
+    rows: Option<u32>,
+}
+
+// 📊 GET /exports/sales.xlsx
+async fn export_sales(
+    State(state): State<AppState>,
+    Query(params): Query<ExportParams>,
+) -> Result<Response, (StatusCode, String)> {
+    // 자리가 없으면 바로 503 (요청이 쌓여 메모리/CPU를 다 쓰지 않도록)
+    let Ok(permit) = state.exports.try_acquire_owned() else {
+        return Ok((
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::RETRY_AFTER, "5")],
+            "too many exports in progress, try again later",
+        )
+            .into_response());
+    };
+
+    let rows = params.rows.unwrap_or(DEFAULT_ROWS).clamp(1, MAX_ROWS);
+    let file = tokio::task::spawn_blocking(move || {
+        // 생성이 끝나면 permit이 drop되어 다음 작업이 들어올 수 있음
+        let _permit = permit;
+        build_workbook(rows)
+    })
+    .await
+    .map_err(internal_error)?
+    .map_err(internal_error)?;
+
+    let len = file.metadata().map_err(internal_error)?.len();
+    tracing::debug!(rows, bytes = len, "export ready");
+
+    let stream = ReaderStream::new(tokio::fs::File::from_std(file));
+    Ok((
+        [
+            (header::CONTENT_TYPE, XLSX_CONTENT_TYPE.to_owned()),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"sales.xlsx\"".to_owned(),
+            ),
+            (header::CONTENT_LENGTH, len.to_string()),
+        ],
+        Body::from_stream(stream),
+    )
+        .into_response())
+}
+
+fn internal_error(err: impl std::fmt::Display) -> (StatusCode, String) {
+    tracing::error!(%err, "export failed");
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "export failed".to_owned(),
+    )
+}
+
+// 🧱 시트 생성 (동기, spawn_blocking 안에서 실행)
+// 실제 서비스에서는 DB에서 읽은 행을 쓰면 됨
+fn build_workbook(rows: u32) -> Result<File, Box<dyn Error + Send + Sync>> {
+    const REGIONS: [&str; 4] = ["Seoul", "Busan", "Incheon", "Daegu"];
+    const PRODUCTS: [&str; 3] = ["Keyboard", "Mouse", "Monitor"];
+
+    let mut workbook = Workbook::new();
+    let bold = Format::new().set_bold();
+    let money = Format::new().set_num_format("#,##0.00");
+
+    let sheet = workbook.add_worksheet_with_constant_memory();
+    sheet.set_name("Sales")?;
+    sheet.set_freeze_panes(1, 0)?; // 헤더 줄 고정
+    sheet.set_column_width(2, 12)?;
+
+    for (col, title) in ["order_id", "region", "product", "quantity", "amount"]
+        .into_iter()
+        .enumerate()
+    {
+        sheet.write_with_format(0, col as u16, title, &bold)?;
+    }
+
+    // constant_memory 모드에서는 행 순서대로 써야 함
+    for row in 1..=rows {
+        let quantity = row % 10 + 1;
+        sheet.write(row, 0, row)?;
+        sheet.write(row, 1, REGIONS[row as usize % REGIONS.len()])?;
+        sheet.write(row, 2, PRODUCTS[row as usize % PRODUCTS.len()])?;
+        sheet.write(row, 3, quantity)?;
+        sheet.write_with_format(row, 4, f64::from(quantity) * 19.99, &money)?;
+    }
+
+    // 이름 없는 임시 파일: 경로가 없으니 정리할 필요도 없음
+    let mut file = tempfile::tempfile()?;
+    workbook.save_to_writer(&mut file)?;
+    file.seek(SeekFrom::Start(0))?;
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    fn state(permits: usize) -> AppState {
+        AppState {
+            exports: Arc::new(Semaphore::new(permits)),
+        }
+    }
+
+    fn export(rows: u32) -> Request<Body> {
+        Request::get(format!("/exports/sales.xlsx?rows={rows}"))
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn streams_an_xlsx_attachment() {
+        let response = app(state(1)).oneshot(export(500)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], XLSX_CONTENT_TYPE);
+        assert_eq!(
+            response.headers()[header::CONTENT_DISPOSITION],
+            "attachment; filename=\"sales.xlsx\""
+        );
+        let len: usize = response.headers()[header::CONTENT_LENGTH]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body.len(), len);
+        // xlsx는 zip 파일
+        assert_eq!(&body[..2], b"PK");
+    }
+
+    #[tokio::test]
+    async fn exports_beyond_the_limit_are_rejected() {
+        let state = state(1);
+        let app = app(state.clone());
+
+        // 다른 작업이 진행 중인 것처럼 자리를 차지
+        let running = state.exports.clone().acquire_owned().await.unwrap();
+        let response = app.clone().oneshot(export(10)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "5");
+
+        drop(running);
+        let response = app.oneshot(export(10)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        // 생성이 끝나면 permit은 반납됨 (전송 중에도)
+        assert_eq!(state.exports.available_permits(), 1);
+    }
+}
+
+// 🧪 테스트 방법
+//
+// ✅ 다운로드 (Content-Disposition의 파일 이름으로 저장)
+// curl -OJ 'localhost:3000/exports/sales.xlsx?rows=50000'
+//
+// ✅ 동시 작업 제한 (MAX_CONCURRENT_EXPORTS = 2)
+// for i in 1 2 3; do curl -s -o /dev/null -w '%{http_code}\n' 'localhost:3000/exports/sales.xlsx?rows=1000000' & done; wait
+// → 200, 200, 503
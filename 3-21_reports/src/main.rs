@@ -0,0 +1,348 @@
+//! 리포트(PDF) 생성 작업 + 다운로드 예제
+//!
+//! `POST /reports` → 202 + 작업 URL, `GET /reports/{id}`로 상태 확인, ready가 되면 `/download`
+//!
+//! • 핸들러는 작업을 등록만 하고 바로 응답 (생성은 백그라운드 태스크, `reports.rs`)
+//!   → 오래 걸리는 작업 때문에 요청이 타임아웃되거나 워커 스레드가 막히지 않음
+//! • 템플릿(minijinja) → 텍스트 줄 → PDF (`template.rs`, `pdf.rs`)
+//! • 완성된 파일은 `REPORT_TTL` 동안만 보관, 정리 태스크가 주기적으로 삭제
+//!   → 만료된 리포트 다운로드는 410 Gone, 아직 준비 안 된 리포트는 409 Conflict
+//! • 끝나지 않은 작업이 `MAX_PENDING_REPORTS`개면 새 요청은 503 + `Retry-After`
+
+mod pdf;
+mod reports;
+mod template;
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Serialize;
+use std::time::Duration;
+use tokio_util::io::ReaderStream;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use uuid::Uuid;
+
+use crate::{
+    reports::{ReportStatus, Reports},
+    template::ReportRequest,
+};
+
+const REPORT_TTL: Duration = Duration::from_secs(10 * 60);
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+const MAX_RUNNING_REPORTS: usize = 2;
+const MAX_PENDING_REPORTS: usize = 100;
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let dir = std::env::temp_dir().join("axum-example-reports");
+    tokio::fs::create_dir_all(&dir).await.unwrap();
+    tracing::debug!("reports are stored in {}", dir.display());
+
+    let reports = Reports::new(dir, REPORT_TTL, MAX_RUNNING_REPORTS, MAX_PENDING_REPORTS);
+    reports.spawn_cleanup(CLEANUP_INTERVAL);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(reports)).await.unwrap();
+}
+
+fn app(reports: Reports) -> Router {
+    Router::new()
+        .route("/reports", post(create_report))
+        .route("/reports/{id}", get(report_status))
+        .route("/reports/{id}/download", get(download_report))
+        .with_state(reports)
+}
+
+#[derive(Serialize)]
+struct Accepted {
+    id: Uuid,
+    status_url: String,
+}
+
+// 📝 POST /reports → 202 Accepted + Location
+async fn create_report(
+    State(reports): State<Reports>,
+    Json(request): Json<ReportRequest>,
+) -> Result<impl IntoResponse, Response> {
+    request
+        .validate()
+        .map_err(|err| (StatusCode::UNPROCESSABLE_ENTITY, err).into_response())?;
+
+    let id = reports.enqueue(request).map_err(|_| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::RETRY_AFTER, "5")],
+            "too many reports are being generated, try again later",
+        )
+            .into_response()
+    })?;
+    let status_url = format!("/reports/{id}");
+    Ok((
+        StatusCode::ACCEPTED,
+        [(header::LOCATION, status_url.clone())],
+        Json(Accepted { id, status_url }),
+    ))
+}
+
+#[derive(Serialize)]
+struct StatusView {
+    id: Uuid,
+    status: ReportStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    download_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_in_secs: Option<u64>,
+}
+
+// 🔎 GET /reports/{id} (클라이언트가 주기적으로 호출)
+async fn report_status(
+    State(reports): State<Reports>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<StatusView>, StatusCode> {
+    let report = reports.get(id).ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(StatusView {
+        id,
+        status: report.status,
+        download_url: report.file().map(|_| format!("/reports/{id}/download")),
+        expires_in_secs: report.expires_in(reports.ttl).map(|left| left.as_secs()),
+        error: report.error,
+    }))
+}
+
+// 📥 GET /reports/{id}/download
+async fn download_report(
+    State(reports): State<Reports>,
+    Path(id): Path<Uuid>,
+) -> Result<Response, (StatusCode, &'static str)> {
+    let report = reports
+        .get(id)
+        .ok_or((StatusCode::NOT_FOUND, "report not found"))?;
+    let path = match report.status {
+        ReportStatus::Ready => report.file().cloned(),
+        ReportStatus::Queued | ReportStatus::Running => {
+            return Err((StatusCode::CONFLICT, "report is not ready yet"))
+        }
+        ReportStatus::Failed => return Err((StatusCode::CONFLICT, "report generation failed")),
+        ReportStatus::Expired => return Err((StatusCode::GONE, "report has expired")),
+    };
+
+    // 상태를 읽은 직후 정리 태스크가 파일을 지웠을 수도 있음
+    let file = match path {
+        Some(path) => tokio::fs::File::open(path).await.ok(),
+        None => None,
+    }
+    .ok_or((StatusCode::GONE, "report has expired"))?;
+    let len = file
+        .metadata()
+        .await
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "failed to read report"))?
+        .len();
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_owned()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"report-{id}.pdf\""),
+            ),
+            (header::CONTENT_LENGTH, len.to_string()),
+        ],
+        Body::from_stream(ReaderStream::new(file)),
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use tokio::time::Instant;
+    use tower::ServiceExt;
+
+    fn reports_with_limit(max_pending: usize) -> Reports {
+        let dir = std::env::temp_dir().join(format!("axum-example-reports-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        Reports::new(dir, REPORT_TTL, MAX_RUNNING_REPORTS, max_pending)
+    }
+
+    fn reports() -> Reports {
+        reports_with_limit(MAX_PENDING_REPORTS)
+    }
+
+    fn get(uri: &str) -> Request<Body> {
+        Request::get(uri).body(Body::empty()).unwrap()
+    }
+
+    async fn json_body(response: Response) -> Value {
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    async fn create(app: &Router, request: Value) -> Response {
+        app.clone()
+            .oneshot(
+                Request::post("/reports")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(request.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+
+    // ready가 될 때까지 상태 확인
+    async fn wait_until_ready(app: &Router, status_url: &str) -> Value {
+        for _ in 0..100 {
+            let status = json_body(app.clone().oneshot(get(status_url)).await.unwrap()).await;
+            if status["status"] == "ready" {
+                return status;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("report did not become ready");
+    }
+
+    fn sample() -> Value {
+        json!({
+            "title": "October sales",
+            "items": [
+                { "name": "Keyboard", "quantity": 2, "unit_price": 49.5 },
+                { "name": "Mouse", "quantity": 1, "unit_price": 19.99 },
+            ],
+        })
+    }
+
+    #[tokio::test]
+    async fn report_is_generated_in_the_background_and_downloadable() {
+        let app = app(reports());
+
+        let response = create(&app, sample()).await;
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let location = response.headers()[header::LOCATION]
+            .to_str()
+            .unwrap()
+            .to_owned();
+        let accepted = json_body(response).await;
+        assert_eq!(accepted["status_url"], location);
+
+        let status = wait_until_ready(&app, &location).await;
+        assert!(status["expires_in_secs"].as_u64().unwrap() > 0);
+        let download_url = status["download_url"].as_str().unwrap();
+
+        let response = app.oneshot(get(download_url)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/pdf");
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert!(body.starts_with(b"%PDF-"));
+    }
+
+    #[tokio::test]
+    async fn expired_reports_are_cleaned_up() {
+        let reports = reports();
+        let app = app(reports.clone());
+
+        let location = create(&app, sample()).await.headers()[header::LOCATION]
+            .to_str()
+            .unwrap()
+            .to_owned();
+        wait_until_ready(&app, &location).await;
+        let id: Uuid = location.trim_start_matches("/reports/").parse().unwrap();
+        let path = reports.get(id).unwrap().file().cloned().unwrap();
+        assert!(path.exists());
+
+        // 아직 만료 전이면 아무것도 지우지 않음
+        assert_eq!(reports.sweep(Instant::now()).await, 0);
+        assert_eq!(reports.sweep(Instant::now() + REPORT_TTL).await, 1);
+        assert!(!path.exists());
+
+        let status = json_body(app.clone().oneshot(get(&location)).await.unwrap()).await;
+        assert_eq!(status["status"], "expired");
+        assert!(status.get("download_url").is_none());
+
+        let response = app
+            .clone()
+            .oneshot(get(&format!("{location}/download")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::GONE);
+
+        // 기록도 ttl이 한 번 더 지나면 제거
+        reports.sweep(Instant::now() + REPORT_TTL * 3).await;
+        let response = app.oneshot(get(&location)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn pending_reports_are_capped() {
+        let reports = reports_with_limit(2);
+        let request: ReportRequest = serde_json::from_value(sample()).unwrap();
+
+        // await 전이라 생성 태스크는 아직 시작하지 않음 → 둘 다 대기 중
+        let first = reports.enqueue(request.clone()).unwrap();
+        let second = reports.enqueue(request.clone()).unwrap();
+        assert!(reports.enqueue(request).is_err());
+
+        let app = app(reports);
+        let response = create(&app, sample()).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert!(response.headers().contains_key(header::RETRY_AFTER));
+
+        // 끝난 작업만큼 다시 받음
+        for id in [first, second] {
+            wait_until_ready(&app, &format!("/reports/{id}")).await;
+        }
+        assert_eq!(create(&app, sample()).await.status(), StatusCode::ACCEPTED);
+    }
+
+    #[tokio::test]
+    async fn invalid_requests_and_unknown_reports_are_rejected() {
+        let app = app(reports());
+
+        let response = create(&app, json!({ "title": "empty", "items": [] })).await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let unknown = Uuid::new_v4();
+        for uri in [
+            format!("/reports/{unknown}"),
+            format!("/reports/{unknown}/download"),
+        ] {
+            let response = app.clone().oneshot(get(&uri)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+    }
+}
+
+// 🧪 테스트 방법
+//
+// ✅ 작업 등록 → 202 + Location
+// curl -i -X POST localhost:3000/reports -H 'content-type: application/json' \
+//   -d '{"title":"October sales","items":[{"name":"Keyboard","quantity":2,"unit_price":49.5},{"name":"Mouse","quantity":1,"unit_price":19.99}]}'
+//
+// ✅ 상태 확인 (queued → running → ready)
+// curl localhost:3000/reports/<id>
+// → {"id":"...","status":"ready","download_url":"/reports/<id>/download","expires_in_secs":599}
+//
+// ✅ 다운로드
+// curl -OJ localhost:3000/reports/<id>/download
+//
+// ❌ 빈 items → 422, 없는 id → 404, 만료된 리포트 다운로드 → 410, 대기열이 가득 차면 503
@@ -0,0 +1,134 @@
+//! 📄 텍스트 줄을 PDF로 만드는 최소한의 writer
+//!
+//! 외부 라이브러리 없이 PDF 1.4 구조(객체 + xref 표)를 직접 씀
+//!
+//! • A4, 기본 글꼴 Helvetica 11pt, 페이지당 `LINES_PER_PAGE`줄
+//! • 기본 글꼴은 WinAnsi 문자만 표시하므로 ASCII 밖의 문자는 `?`로 바꿈
+//!   → 한글 등이 필요하면 글꼴을 포함하는 라이브러리(printpdf, typst 등)를 사용
+
+use std::fmt::Write;
+
+const PAGE_WIDTH: u32 = 595;
+const PAGE_HEIGHT: u32 = 842;
+const MARGIN: u32 = 50;
+const FONT_SIZE: u32 = 11;
+const LEADING: u32 = 14;
+pub const LINES_PER_PAGE: usize = ((PAGE_HEIGHT - 2 * MARGIN) / LEADING) as usize;
+
+/// `lines`를 순서대로 찍은 PDF 파일 내용
+pub fn render(lines: &[String]) -> Vec<u8> {
+    let pages: Vec<&[String]> = if lines.is_empty() {
+        vec![&[]]
+    } else {
+        lines.chunks(LINES_PER_PAGE).collect()
+    };
+
+    // 객체 번호: 1 카탈로그, 2 페이지 목록, 3 글꼴, 그다음 페이지마다 (페이지, 내용) 두 개
+    let page_id = |index: usize| 4 + index * 2;
+    let kids = (0..pages.len())
+        .map(|index| format!("{} 0 R", page_id(index)))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_owned(),
+        format!("<< /Type /Pages /Kids [{kids}] /Count {} >>", pages.len()),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_owned(),
+    ];
+    for (index, lines) in pages.iter().enumerate() {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+             /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            page_id(index) + 1
+        ));
+        let content = page_content(lines);
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{content}\nendstream",
+            content.len()
+        ));
+    }
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        let _ = write!(pdf, "{} 0 obj\n{object}\nendobj\n", index + 1);
+    }
+
+    // xref: 각 객체가 시작하는 바이트 위치 (항목마다 정확히 20바이트)
+    let xref = pdf.len();
+    let _ = write!(pdf, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(pdf, "{offset:010} 00000 n ");
+    }
+    let _ = write!(
+        pdf,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+        objects.len() + 1
+    );
+    pdf.into_bytes()
+}
+
+// 위에서 아래로 한 줄씩 (`T*`: 다음 줄로 이동)
+fn page_content(lines: &[String]) -> String {
+    let mut content = format!(
+        "BT /F1 {FONT_SIZE} Tf {LEADING} TL {MARGIN} {} Td",
+        PAGE_HEIGHT - MARGIN
+    );
+    for line in lines {
+        let _ = write!(content, " ({}) Tj T*", escape(line));
+    }
+    content.push_str(" ET");
+    content
+}
+
+// 문자열 리터럴 안의 `\`, `(`, `)`는 escape, 표시할 수 없는 문자는 `?`
+fn escape(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\\' | '(' | ')' => format!("\\{c}"),
+            ' '..='~' => c.to_string(),
+            _ => "?".to_owned(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(pdf: &[u8]) -> &str {
+        std::str::from_utf8(pdf).unwrap()
+    }
+
+    #[test]
+    fn xref_offsets_point_at_objects() {
+        let lines: Vec<String> = (0..LINES_PER_PAGE + 1)
+            .map(|n| format!("line {n}"))
+            .collect();
+        let pdf = render(&lines);
+        let pdf = text(&pdf);
+
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        assert!(pdf.contains("/Count 2"));
+
+        let xref = pdf.rsplit("startxref\n").next().unwrap();
+        let xref: usize = xref.lines().next().unwrap().parse().unwrap();
+        let entries: Vec<&str> = pdf[xref..].lines().skip(3).take(7).collect();
+        for (index, entry) in entries.iter().enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(
+                pdf[offset..].starts_with(&format!("{} 0 obj", index + 1)),
+                "{entry}"
+            );
+        }
+    }
+
+    #[test]
+    fn special_characters_are_escaped() {
+        let pdf = render(&["total (net): 5\\10 원".to_owned()]);
+        assert!(text(&pdf).contains("(total \\(net\\): 5\\\\10 ?) Tj"));
+    }
+}
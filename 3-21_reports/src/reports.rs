@@ -0,0 +1,197 @@
+//! 🗂️ 리포트 작업 저장소, 생성 태스크, 만료 정리
+//!
+//! queued → running → ready → expired
+//!                  ↘ failed
+//!
+//! • 생성은 `tokio::spawn`한 태스크에서, 동시에 `max_running`개까지만 (나머지는 queued로 대기)
+//!   → 대기 중 + 실행 중인 작업은 `max_pending`개까지, 넘치면 `enqueue`가 거절 (대기열이 메모리를 끝없이 쓰지 않도록)
+//!   → 템플릿 렌더링 + PDF 작성은 동기 코드라 `spawn_blocking`에서 실행
+//! • 완성된 파일은 `dir/{id}.pdf`에 저장하고 `ttl`이 지나면 `sweep`이 삭제 (상태는 expired)
+//!   → expired/failed 기록도 `ttl`이 한 번 더 지나면 저장소에서 제거
+
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+use tokio::{sync::Semaphore, time::Instant};
+use uuid::Uuid;
+
+use crate::template::{self, ReportRequest};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportStatus {
+    Queued,
+    Running,
+    Ready,
+    Failed,
+    /// 파일이 정리되어 더 이상 받을 수 없음
+    Expired,
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub status: ReportStatus,
+    pub error: Option<String>,
+    /// ready/failed/expired가 된 시각 (만료 계산용)
+    finished_at: Option<Instant>,
+    path: Option<PathBuf>,
+}
+
+impl Report {
+    /// ready일 때만 파일 경로
+    pub fn file(&self) -> Option<&PathBuf> {
+        self.path
+            .as_ref()
+            .filter(|_| self.status == ReportStatus::Ready)
+    }
+
+    /// ready 상태가 끝나는 시각까지 남은 시간
+    pub fn expires_in(&self, ttl: Duration) -> Option<Duration> {
+        let finished_at = self.finished_at?;
+        (self.status == ReportStatus::Ready)
+            .then(|| (finished_at + ttl).saturating_duration_since(Instant::now()))
+    }
+}
+
+/// 대기 중인 작업이 너무 많음
+#[derive(Debug)]
+pub struct QueueFull;
+
+#[derive(Clone)]
+pub struct Reports {
+    reports: Arc<RwLock<HashMap<Uuid, Report>>>,
+    running: Arc<Semaphore>,
+    // 작업 하나가 끝날 때까지 허가 하나를 가지고 있음
+    pending: Arc<Semaphore>,
+    dir: PathBuf,
+    pub ttl: Duration,
+}
+
+impl Reports {
+    pub fn new(dir: PathBuf, ttl: Duration, max_running: usize, max_pending: usize) -> Self {
+        Self {
+            reports: Default::default(),
+            running: Arc::new(Semaphore::new(max_running)),
+            pending: Arc::new(Semaphore::new(max_pending)),
+            dir,
+            ttl,
+        }
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<Report> {
+        self.reports.read().unwrap().get(&id).cloned()
+    }
+
+    /// 작업을 등록하고 바로 반환 (생성은 백그라운드), 대기열이 가득 차면 `QueueFull`
+    pub fn enqueue(&self, request: ReportRequest) -> Result<Uuid, QueueFull> {
+        let permit = self
+            .pending
+            .clone()
+            .try_acquire_owned()
+            .map_err(|_| QueueFull)?;
+        let id = Uuid::new_v4();
+        self.reports.write().unwrap().insert(
+            id,
+            Report {
+                status: ReportStatus::Queued,
+                error: None,
+                finished_at: None,
+                path: None,
+            },
+        );
+        let reports = self.clone();
+        tokio::spawn(async move {
+            reports.generate(id, request).await;
+            drop(permit);
+        });
+        Ok(id)
+    }
+
+    async fn generate(self, id: Uuid, request: ReportRequest) {
+        // 동시에 실행되는 생성 작업 수 제한 (Semaphore는 닫지 않으므로 실패하지 않음)
+        let _permit = self.running.acquire().await.unwrap();
+        self.update(id, |report| report.status = ReportStatus::Running);
+
+        let path = self.dir.join(format!("{id}.pdf"));
+        let result = tokio::task::spawn_blocking({
+            let path = path.clone();
+            move || -> Result<(), String> {
+                let pdf = template::render_pdf(&request)?;
+                std::fs::write(&path, pdf).map_err(|err| err.to_string())
+            }
+        })
+        .await
+        .unwrap_or_else(|err| Err(err.to_string()));
+
+        self.update(id, |report| {
+            report.finished_at = Some(Instant::now());
+            match result {
+                Ok(()) => {
+                    report.status = ReportStatus::Ready;
+                    report.path = Some(path);
+                }
+                Err(error) => {
+                    tracing::error!(%id, %error, "report generation failed");
+                    report.status = ReportStatus::Failed;
+                    report.error = Some(error);
+                }
+            }
+        });
+    }
+
+    fn update(&self, id: Uuid, f: impl FnOnce(&mut Report)) {
+        if let Some(report) = self.reports.write().unwrap().get_mut(&id) {
+            f(report);
+        }
+    }
+
+    /// `now` 기준으로 만료된 파일을 지우고 오래된 기록을 정리, 지운 파일 수 반환
+    pub async fn sweep(&self, now: Instant) -> usize {
+        let mut expired_files = Vec::new();
+        {
+            let mut reports = self.reports.write().unwrap();
+            reports.retain(|_, report| {
+                let Some(finished_at) = report.finished_at else {
+                    return true; // 아직 진행 중
+                };
+                let age = now.saturating_duration_since(finished_at);
+                match report.status {
+                    ReportStatus::Ready if age >= self.ttl => {
+                        report.status = ReportStatus::Expired;
+                        expired_files.extend(report.path.take());
+                        true
+                    }
+                    ReportStatus::Failed | ReportStatus::Expired => age < self.ttl * 2,
+                    _ => true,
+                }
+            });
+        }
+
+        // 파일 삭제는 잠금을 푼 뒤에
+        for path in &expired_files {
+            if let Err(err) = tokio::fs::remove_file(path).await {
+                tracing::warn!(path = %path.display(), %err, "failed to remove expired report");
+            }
+        }
+        expired_files.len()
+    }
+
+    /// `interval`마다 `sweep` (서버가 살아 있는 동안)
+    pub fn spawn_cleanup(&self, interval: Duration) {
+        let reports = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let removed = reports.sweep(Instant::now()).await;
+                if removed > 0 {
+                    tracing::debug!(removed, "expired reports removed");
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,131 @@
+//! 🧾 리포트 요청 → 템플릿(minijinja) → 텍스트 줄 → PDF
+
+use minijinja::{context, Environment};
+use serde::{Deserialize, Serialize};
+
+use crate::pdf;
+
+const REPORT_TEMPLATE: &str = "\
+{{ title }}
+
+Items: {{ items | length }}
+{% for item in items %}
+- {{ item.name }}: {{ item.quantity }} x {{ item.unit_price }} = {{ item.amount }}
+{%- endfor %}
+
+Total: {{ total }}
+";
+
+const MAX_ITEMS: usize = 10_000;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReportRequest {
+    pub title: String,
+    pub items: Vec<Item>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Item {
+    pub name: String,
+    pub quantity: u32,
+    pub unit_price: f64,
+}
+
+impl ReportRequest {
+    /// 큐에 넣기 전에 확인 (나중에 실패하는 것보다 바로 422가 나음)
+    pub fn validate(&self) -> Result<(), String> {
+        if self.title.trim().is_empty() {
+            return Err("title must not be empty".to_owned());
+        }
+        if self.items.is_empty() || self.items.len() > MAX_ITEMS {
+            return Err(format!("items must contain 1..={MAX_ITEMS} entries"));
+        }
+        if let Some(item) = self
+            .items
+            .iter()
+            .find(|item| !item.unit_price.is_finite() || item.unit_price < 0.0)
+        {
+            return Err(format!("invalid unit_price for `{}`", item.name));
+        }
+        Ok(())
+    }
+}
+
+// 템플릿에는 계산과 포맷이 끝난 값만 넘김
+#[derive(Serialize)]
+struct Row<'a> {
+    name: &'a str,
+    quantity: u32,
+    unit_price: String,
+    amount: String,
+}
+
+pub fn render_pdf(request: &ReportRequest) -> Result<Vec<u8>, String> {
+    let rows: Vec<Row> = request
+        .items
+        .iter()
+        .map(|item| Row {
+            name: &item.name,
+            quantity: item.quantity,
+            unit_price: format!("{:.2}", item.unit_price),
+            amount: format!("{:.2}", f64::from(item.quantity) * item.unit_price),
+        })
+        .collect();
+    let total: f64 = request
+        .items
+        .iter()
+        .map(|item| f64::from(item.quantity) * item.unit_price)
+        .sum();
+
+    let mut env = Environment::new();
+    env.add_template("report", REPORT_TEMPLATE)
+        .map_err(|err| err.to_string())?;
+    let text = env
+        .get_template("report")
+        .and_then(|template| {
+            template.render(context! {
+                title => request.title,
+                items => rows,
+                total => format!("{total:.2}"),
+            })
+        })
+        .map_err(|err| err.to_string())?;
+
+    let lines: Vec<String> = text.lines().map(str::to_owned).collect();
+    Ok(pdf::render(&lines))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template_lines_end_up_in_the_pdf() {
+        let request = ReportRequest {
+            title: "October sales".to_owned(),
+            items: vec![
+                Item {
+                    name: "Keyboard".to_owned(),
+                    quantity: 2,
+                    unit_price: 49.5,
+                },
+                Item {
+                    name: "Mouse".to_owned(),
+                    quantity: 1,
+                    unit_price: 19.99,
+                },
+            ],
+        };
+        assert!(request.validate().is_ok());
+
+        let pdf = String::from_utf8(render_pdf(&request).unwrap()).unwrap();
+        for line in [
+            "(October sales) Tj",
+            "(Items: 2) Tj",
+            "(- Keyboard: 2 x 49.50 = 99.00) Tj",
+            "(Total: 118.99) Tj",
+        ] {
+            assert!(pdf.contains(line), "{line}");
+        }
+    }
+}
@@ -0,0 +1,262 @@
+//! 🔑 RS256 서명 키 관리 + JWKS
+//!
+//! - 토큰은 개인 키로 서명하고, 헤더의 `kid`로 어떤 키로 서명했는지 표시
+//! - 공개 키는 `/.well-known/jwks.json`으로 공개 → 다른 서비스가 비밀 값 공유 없이 토큰 검증 가능
+//! - 키를 교체(rotate)해도 직전 키 몇 개는 검증용으로 남겨 둠 → 이미 발급된 토큰이 바로 무효가 되지 않음
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{
+    decode, decode_header, encode,
+    jwk::{
+        AlgorithmParameters, CommonParameters, Jwk, JwkSet, KeyAlgorithm, PublicKeyUse,
+        RSAKeyParameters, RSAKeyType,
+    },
+    Algorithm, DecodingKey, EncodingKey, Header, Validation,
+};
+use rsa::{pkcs1::EncodeRsaPrivateKey, traits::PublicKeyParts, RsaPrivateKey};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::VecDeque,
+    sync::RwLock,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const RSA_BITS: usize = 2048;
+// 교체된 뒤에도 검증(및 JWKS 공개)에 사용할 이전 키 개수
+const RETIRED_KEYS: usize = 2;
+
+/// `exp` 검증에 허용하는 시계 오차 (초), 만료 후 이 시간까지는 토큰이 통과함
+/// → deny-list도 `exp + LEEWAY_SECS`까지 보관해야 함
+pub const LEEWAY_SECS: u64 = 60;
+
+/// 서명 키 하나 (개인 키 + 공개 키)
+pub struct SigningKey {
+    kid: String,
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    jwk: Jwk,
+}
+
+impl SigningKey {
+    /// 새 RSA 키 생성 (CPU를 많이 쓰므로 요청 처리 중에는 `spawn_blocking`에서 호출)
+    pub fn generate() -> Self {
+        let private = RsaPrivateKey::new(&mut rand::rngs::OsRng, RSA_BITS)
+            .expect("failed to generate RSA key");
+        let der = private.to_pkcs1_der().expect("failed to encode RSA key");
+        let (n, e) = (private.n().to_bytes_be(), private.e().to_bytes_be());
+
+        // 생성 시각 기반 ID (키마다 달라지기만 하면 됨)
+        let kid = format!(
+            "{:x}",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos()
+        );
+        let jwk = Jwk {
+            common: CommonParameters {
+                public_key_use: Some(PublicKeyUse::Signature),
+                key_algorithm: Some(KeyAlgorithm::RS256),
+                key_id: Some(kid.clone()),
+                ..Default::default()
+            },
+            algorithm: AlgorithmParameters::RSA(RSAKeyParameters {
+                key_type: RSAKeyType::RSA,
+                n: URL_SAFE_NO_PAD.encode(&n),
+                e: URL_SAFE_NO_PAD.encode(&e),
+            }),
+        };
+
+        Self {
+            kid,
+            encoding: EncodingKey::from_rsa_der(der.as_bytes()),
+            decoding: DecodingKey::from_rsa_raw_components(&n, &e),
+            jwk,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum KeyError {
+    Sign,
+    // 헤더에 kid가 없거나, 모르는 kid
+    UnknownKey,
+    Invalid,
+}
+
+pub struct KeyStore {
+    // 맨 앞이 현재 서명 키, 나머지는 검증만 하는 이전 키
+    keys: RwLock<VecDeque<SigningKey>>,
+}
+
+impl KeyStore {
+    pub fn new(key: SigningKey) -> Self {
+        Self {
+            keys: RwLock::new(VecDeque::from([key])),
+        }
+    }
+
+    /// 현재 키로 서명 (헤더에 alg=RS256, kid 포함)
+    pub fn sign<T: Serialize>(&self, claims: &T) -> Result<String, KeyError> {
+        let keys = self.keys.read().unwrap();
+        let active = &keys[0];
+        let header = Header {
+            kid: Some(active.kid.clone()),
+            ..Header::new(Algorithm::RS256)
+        };
+        encode(&header, claims, &active.encoding).map_err(|_| KeyError::Sign)
+    }
+
+    /// `kid`로 키를 골라 검증 (RS256만 허용 → HS256 등으로 바꿔치기한 토큰 거부)
+    pub fn verify<T: DeserializeOwned>(&self, token: &str) -> Result<T, KeyError> {
+        let kid = decode_header(token)
+            .map_err(|_| KeyError::Invalid)?
+            .kid
+            .ok_or(KeyError::UnknownKey)?;
+
+        let keys = self.keys.read().unwrap();
+        let key = keys
+            .iter()
+            .find(|key| key.kid == kid)
+            .ok_or(KeyError::UnknownKey)?;
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.leeway = LEEWAY_SECS;
+        decode(token, &key.decoding, &validation)
+            .map(|data| data.claims)
+            .map_err(|_| KeyError::Invalid)
+    }
+
+    /// 공개 키 목록 (현재 키 + 이전 키)
+    pub fn jwks(&self) -> JwkSet {
+        JwkSet {
+            keys: self
+                .keys
+                .read()
+                .unwrap()
+                .iter()
+                .map(|key| key.jwk.clone())
+                .collect(),
+        }
+    }
+
+    /// 새 키로 교체하고 (새 kid, 더 이상 검증하지 않는 kid 목록) 반환
+    pub fn rotate(&self, key: SigningKey) -> (String, Vec<String>) {
+        let kid = key.kid.clone();
+        let mut keys = self.keys.write().unwrap();
+        keys.push_front(key);
+        let keep = keys.len().min(1 + RETIRED_KEYS);
+        let dropped = keys.drain(keep..).map(|key| key.kid).collect();
+        (kid, dropped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct TestClaims {
+        sub: String,
+        exp: u64,
+    }
+
+    fn claims() -> TestClaims {
+        TestClaims {
+            sub: "b@b.com".to_owned(),
+            exp: 2_000_000_000,
+        }
+    }
+
+    #[test]
+    fn sign_and_verify_with_kid() {
+        let store = KeyStore::new(SigningKey::generate());
+        let token = store.sign(&claims()).unwrap();
+
+        let header = decode_header(&token).unwrap();
+        assert_eq!(header.alg, Algorithm::RS256);
+        assert_eq!(header.kid, store.jwks().keys[0].common.key_id);
+        assert_eq!(store.verify::<TestClaims>(&token).unwrap(), claims());
+
+        // 다른 키 저장소에서 만든 토큰은 kid를 모름
+        let other = KeyStore::new(SigningKey::generate());
+        assert_eq!(
+            other.verify::<TestClaims>(&token),
+            Err(KeyError::UnknownKey)
+        );
+
+        // kid를 흉내 낸 HS256 토큰 거부
+        let forged = encode(
+            &Header {
+                kid: header.kid,
+                ..Header::new(Algorithm::HS256)
+            },
+            &claims(),
+            &EncodingKey::from_secret(b"secret"),
+        )
+        .unwrap();
+        assert_eq!(store.verify::<TestClaims>(&forged), Err(KeyError::Invalid));
+    }
+
+    #[test]
+    fn expired_tokens_pass_until_the_leeway_ends() {
+        let store = KeyStore::new(SigningKey::generate());
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let expired = |ago: u64| TestClaims {
+            exp: now - ago,
+            ..claims()
+        };
+
+        // deny-list가 exp + LEEWAY_SECS까지 보관해야 하는 이유
+        let token = store.sign(&expired(LEEWAY_SECS / 2)).unwrap();
+        assert!(store.verify::<TestClaims>(&token).is_ok());
+        let token = store.sign(&expired(LEEWAY_SECS + 10)).unwrap();
+        assert_eq!(store.verify::<TestClaims>(&token), Err(KeyError::Invalid));
+    }
+
+    #[test]
+    fn rotation_keeps_recent_keys_for_verification() {
+        let store = KeyStore::new(SigningKey::generate());
+        let first = store.sign(&claims()).unwrap();
+
+        for rotation in 1..=RETIRED_KEYS {
+            let (kid, dropped) = store.rotate(SigningKey::generate());
+            assert!(dropped.is_empty());
+            assert_eq!(store.jwks().keys.len(), rotation + 1);
+            assert_eq!(
+                decode_header(&store.sign(&claims()).unwrap()).unwrap().kid,
+                Some(kid)
+            );
+            // 이전 키로 발급한 토큰도 아직 유효
+            assert!(store.verify::<TestClaims>(&first).is_ok());
+        }
+
+        // 한 번 더 교체하면 가장 오래된 키는 빠짐
+        let (_, dropped) = store.rotate(SigningKey::generate());
+        assert_eq!(dropped, [decode_header(&first).unwrap().kid.unwrap()]);
+        assert_eq!(store.jwks().keys.len(), RETIRED_KEYS + 1);
+        assert_eq!(
+            store.verify::<TestClaims>(&first),
+            Err(KeyError::UnknownKey)
+        );
+    }
+
+    #[test]
+    fn jwks_is_enough_to_verify() {
+        let store = KeyStore::new(SigningKey::generate());
+        let token = store.sign(&claims()).unwrap();
+
+        // 다른 서비스 입장: JWKS JSON만 받아서 검증
+        let json = serde_json::to_string(&store.jwks()).unwrap();
+        let jwks: JwkSet = serde_json::from_str(&json).unwrap();
+        let kid = decode_header(&token).unwrap().kid.unwrap();
+        let jwk = jwks.find(&kid).unwrap();
+
+        let key = DecodingKey::from_jwk(jwk).unwrap();
+        let data = decode::<TestClaims>(&token, &key, &Validation::new(Algorithm::RS256)).unwrap();
+        assert_eq!(data.claims, claims());
+    }
+}
@@ -12,21 +12,29 @@ use axum_extra::{
     headers::{authorization::Bearer, Authorization},
     TypedHeader,
 };
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::jwk::JwkSet;
+use keys::{KeyStore, SigningKey};
+use revocation::DenyList;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::fmt::Display;
 use std::sync::LazyLock;
+use subtle::ConstantTimeEq;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-/// 🔐 JWT 키를 초기화하는 전역 정적 객체
-/// 실행 중 최초 접근 시 환경변수 JWT_SECRET로부터 키를 읽어 Encoding/Decoding 키를 설정.
+mod keys;
+mod revocation;
+
+/// 🔐 JWT 서명 키를 보관하는 전역 정적 객체
+/// 공유 비밀 값(HS256) 대신 RSA 키 쌍(RS256)을 사용 → 검증하는 쪽은 공개 키(JWKS)만 있으면 됨.
 /// LazyLock은 처음 접근할 때만 초기화됨. (once_cell::sync::Lazy의 최신 버전 alias)
-static KEYS: LazyLock<Keys> = LazyLock::new(|| {
-    // let secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
-    let secret = "secret"; // $ JWT_SECRET=secret cargo run -p example-jwt 를 대체
-    Keys::new(secret.as_bytes())
-});
+static KEYS: LazyLock<KeyStore> = LazyLock::new(|| KeyStore::new(SigningKey::generate()));
+
+/// 키 교체 API 호출에 필요한 관리자 토큰 (실제 서비스에서는 환경변수/시크릿 저장소 사용)
+const ADMIN_TOKEN: &str = "admin-secret";
+
+/// 🚫 로그아웃한 토큰(jti) 목록 (서버가 여러 대라면 Redis 등 공유 저장소로 대체)
+static DENY_LIST: LazyLock<DenyList> = LazyLock::new(DenyList::default);
 
 /// 🔧 main 함수
 #[tokio::main]
@@ -40,10 +48,16 @@ async fn main() {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
+    // 첫 요청이 키 생성을 기다리지 않도록 시작할 때 미리 초기화
+    LazyLock::force(&KEYS);
+
     // API 라우터 구성
     let app = Router::new()
         .route("/protected", get(protected)) // JWT 인증이 필요한 라우트
-        .route("/authorize", post(authorize)); // JWT 토큰을 발급받는 라우트
+        .route("/authorize", post(authorize)) // JWT 토큰을 발급받는 라우트
+        .route("/logout", post(logout)) // 현재 토큰 무효화
+        .route("/.well-known/jwks.json", get(jwks)) // 토큰 검증용 공개 키 목록
+        .route("/admin/rotate-keys", post(rotate_keys)); // 서명 키 교체
 
     // 서버를 127.0.0.1:3000 포트에 바인딩
     let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
@@ -56,10 +70,10 @@ async fn main() {
 }
 
 /// ✅ GET /protected: JWT를 헤더에 담아야 접근 가능한 보호된 API
-/// •	즉, 유효한 JWT가 있을 경우에만 접근 가능.
-/// •	이 함수에서 'Claims'가 파라미터로 직접 들어오는 점이 중요.
-/// •	Axum은 요청에서 자동으로 JWT를 추출 → 디코딩 → 검증하여 Claims로 변경해줌.
-/// •	이 처리를 가능하게 하는 것이 FromRequestParts의 구현.
+/// • 즉, 유효한 JWT가 있을 경우에만 접근 가능.
+/// • 이 함수에서 'Claims'가 파라미터로 직접 들어오는 점이 중요.
+/// • Axum은 요청에서 자동으로 JWT를 추출 → 디코딩 → 검증하여 Claims로 변경해줌.
+/// • 이 처리를 가능하게 하는 것이 FromRequestParts의 구현.
 async fn protected(claims: Claims) -> Result<String, AuthError> {
     // JWT 내부 클레임 정보를 포맷팅하여 응답합니다.
     Ok(format!(
@@ -68,8 +82,8 @@ async fn protected(claims: Claims) -> Result<String, AuthError> {
 }
 
 /// 🔓 POST /authorize: 사용자 자격증명(client_id, client_secret)을 받아 JWT를 발급
-/// •	사용자 자격 정보를 받아 JWT를 생성해줌.
-/// •	클라이언트가 보낸 client_id, client_secret이 “foo”, “bar”와 일치하면 JWT 토큰 발급
+/// • 사용자 자격 정보를 받아 JWT를 생성해줌.
+/// • 클라이언트가 보낸 client_id, client_secret이 “foo”, “bar”와 일치하면 JWT 토큰 발급
 async fn authorize(Json(payload): Json<AuthPayload>) -> Result<Json<AuthBody>, AuthError> {
     // 클라이언트 ID 또는 시크릿이 비어있으면(자격증명 누락) 에러 반환
     if payload.client_id.is_empty() || payload.client_secret.is_empty() {
@@ -87,16 +101,53 @@ async fn authorize(Json(payload): Json<AuthPayload>) -> Result<Json<AuthBody>, A
         sub: "b@b.com".to_owned(),
         company: "ACME".to_owned(),
         exp: 2000000000, // 만료 시간 (UTC UNIX timestamp: 2033년)
+        jti: format!("{:032x}", rand::random::<u128>()), // 토큰마다 고유한 ID (로그아웃 시 사용)
     };
 
-    // JWT 토큰 생성 (암호화 실패 시 에러 처리)
-    let token = encode(&Header::default(), &claims, &KEYS.encoding)
-        .map_err(|_| AuthError::TokenCreation)?;
+    // JWT 토큰 생성 (현재 키로 서명, 헤더에 kid 포함 / 실패 시 에러 처리)
+    let token = KEYS.sign(&claims).map_err(|_| AuthError::TokenCreation)?;
 
     // JWT를 포함한 응답 본문 반환
     Ok(Json(AuthBody::new(token)))
 }
 
+/// 🚪 POST /logout: 요청에 사용한 토큰을 무효화
+/// • 토큰의 jti를 deny-list에 만료 시각(exp)까지 기록.
+/// • 이후 같은 토큰으로 요청하면 401 + "token_revoked" 에러 코드로 거부됨.
+async fn logout(claims: Claims) -> StatusCode {
+    DENY_LIST.revoke(&claims.jti, claims.exp as u64);
+    tracing::debug!(jti = %claims.jti, "token revoked");
+    StatusCode::NO_CONTENT
+}
+
+/// 🔑 GET /.well-known/jwks.json: 토큰 검증에 필요한 공개 키 목록 (JWK Set)
+/// • 다른 서비스는 토큰 헤더의 kid와 같은 키를 골라 서명을 검증하면 됨.
+/// • 교체된 이전 키도 잠시 포함되므로, 이미 발급된 토큰도 계속 검증 가능.
+async fn jwks() -> Json<JwkSet> {
+    Json(KEYS.jwks())
+}
+
+/// 🔄 POST /admin/rotate-keys: 새 서명 키를 만들어 교체 (관리자 토큰 필요)
+/// • 이후 발급되는 토큰은 새 키로 서명됨.
+/// • 가장 오래된 키는 JWKS에서 빠지고, 그 키로 서명된 토큰은 더 이상 유효하지 않음.
+async fn rotate_keys(
+    TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>,
+) -> Result<Json<serde_json::Value>, AuthError> {
+    // 상수 시간 비교: `!=`는 처음 다른 바이트에서 멈추므로 응답 시간으로 토큰을 한 글자씩 추측할 수 있음
+    if !bool::from(bearer.token().as_bytes().ct_eq(ADMIN_TOKEN.as_bytes())) {
+        return Err(AuthError::WrongCredentials);
+    }
+
+    // RSA 키 생성은 CPU를 오래 쓰므로 런타임 스레드를 막지 않도록 분리
+    let key = tokio::task::spawn_blocking(SigningKey::generate)
+        .await
+        .map_err(|_| AuthError::TokenCreation)?;
+    let (kid, retired) = KEYS.rotate(key);
+    tracing::info!(%kid, ?retired, "signing key rotated");
+
+    Ok(Json(json!({ "kid": kid, "retired": retired })))
+}
+
 /// Claims 구조체를 문자열로 포맷팅해주는 구현
 impl Display for Claims {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -115,8 +166,9 @@ impl AuthBody {
 }
 
 /// 🔁 사용자 요청 헤더에서 JWT를 추출하고 검증하여 Claims로 변환하는 커스텀 추출기 구현
-/// •	Authorization: Bearer <토큰> 형식의 헤더에서 JWT를 추출
-/// •	jsonwebtoken::decode를 통해 Claims로 디코딩
+/// • Authorization: Bearer <토큰> 형식의 헤더에서 JWT를 추출
+/// • 토큰 헤더의 kid로 검증 키를 골라 Claims로 디코딩 (모르는 kid는 거부)
+/// • 로그아웃한 토큰(deny-list에 있는 jti)은 거부
 impl<S> FromRequestParts<S> for Claims
 where
     S: Send + Sync,
@@ -131,51 +183,62 @@ where
             .map_err(|_| AuthError::InvalidToken)?;
 
         // JWT를 디코딩하여 Claims 추출 (검증 실패 시 에러 반환)
-        let token_data = decode::<Claims>(bearer.token(), &KEYS.decoding, &Validation::default())
-            .map_err(|_| AuthError::InvalidToken)?;
+        let claims = KEYS.verify::<Claims>(bearer.token()).map_err(|err| {
+            tracing::debug!(?err, "token rejected");
+            AuthError::InvalidToken
+        })?;
+
+        // 로그아웃한 토큰인지 확인
+        if DENY_LIST.is_revoked(&claims.jti) {
+            return Err(AuthError::RevokedToken);
+        }
 
-        Ok(token_data.claims)
+        Ok(claims)
     }
 }
 
 /// 인증 관련 에러를 HTTP 응답으로 변환하는 구현
 impl IntoResponse for AuthError {
     fn into_response(self) -> Response {
-        let (status, error_message) = match self {
-            AuthError::WrongCredentials => (StatusCode::UNAUTHORIZED, "Wrong credentials"),
-            AuthError::MissingCredentials => (StatusCode::BAD_REQUEST, "Missing credentials"),
-            AuthError::TokenCreation => (StatusCode::INTERNAL_SERVER_ERROR, "Token creation error"),
-            AuthError::InvalidToken => (StatusCode::BAD_REQUEST, "Invalid token"),
+        // code: 클라이언트가 분기 처리에 쓰는 고정 문자열 (error 문구는 바뀔 수 있음)
+        let (status, code, error_message) = match self {
+            AuthError::WrongCredentials => (
+                StatusCode::UNAUTHORIZED,
+                "wrong_credentials",
+                "Wrong credentials",
+            ),
+            AuthError::MissingCredentials => (
+                StatusCode::BAD_REQUEST,
+                "missing_credentials",
+                "Missing credentials",
+            ),
+            AuthError::TokenCreation => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "token_creation",
+                "Token creation error",
+            ),
+            AuthError::InvalidToken => (StatusCode::BAD_REQUEST, "invalid_token", "Invalid token"),
+            AuthError::RevokedToken => (
+                StatusCode::UNAUTHORIZED,
+                "token_revoked",
+                "Token has been revoked",
+            ),
         };
         let body = Json(json!({
             "error": error_message,
+            "code": code,
         }));
         (status, body).into_response()
     }
 }
 
-/// 🧰 JWT 인코딩/디코딩 키를 보관하는 구조체
-
-struct Keys {
-    encoding: EncodingKey,
-    decoding: DecodingKey,
-}
-
-impl Keys {
-    fn new(secret: &[u8]) -> Self {
-        Self {
-            encoding: EncodingKey::from_secret(secret),
-            decoding: DecodingKey::from_secret(secret),
-        }
-    }
-}
-
 /// 🧾 JWT에 담기는 클레임 구조체 (사용자 정보 및 만료시간 포함)
 #[derive(Debug, Serialize, Deserialize)]
 struct Claims {
     sub: String,     // 사용자 이메일 또는 ID
     company: String, // 부가 정보
     exp: usize,      // 만료 시간 (UTC timestamp)
+    jti: String,     // 토큰 고유 ID (로그아웃 시 deny-list에 기록)
 }
 
 /// JWT 토큰을 담아 클라이언트에 반환할 구조체
@@ -199,6 +262,7 @@ enum AuthError {
     MissingCredentials, // 자격 정보 누락
     TokenCreation,      // 토큰 생성 실패
     InvalidToken,       // 잘못된 토큰 또는 디코딩 실패
+    RevokedToken,       // 로그아웃으로 무효화된 토큰
 }
 
 // 테스트 방법
@@ -217,3 +281,16 @@ enum AuthError {
 //  > GET http://localhost:3000/protected
 //  &
 //  Authorization: Bearer blahblahblah
+//
+// 로그아웃 (이후 같은 토큰으로 /protected 요청 시 401 + "code": "token_revoked"):
+//  > POST http://localhost:3000/logout
+//  &
+//  Authorization: Bearer ey...gM
+//
+// 공개 키 목록 확인 (토큰 헤더의 kid와 같은 키로 검증):
+//  > GET http://localhost:3000/.well-known/jwks.json
+//
+// 서명 키 교체 (이전 토큰은 키가 2번 더 교체될 때까지 유효):
+//  > POST http://localhost:3000/admin/rotate-keys
+//  &
+//  Authorization: Bearer admin-secret
@@ -0,0 +1,97 @@
+//! 🚫 로그아웃된 토큰의 deny-list (메모리 보관)
+//!
+//! - JWT는 서버에 상태가 없어 만료 전에는 그대로 유효 → 로그아웃한 토큰의 `jti`를 따로 기록
+//! - 토큰이 만료되면 어차피 거부되므로 그때까지만 보관하고 지움
+//!   → exp 검증은 시계 오차(`LEEWAY_SECS`)만큼 늦게 거부하므로 `exp + LEEWAY_SECS`까지 보관
+//! - 서버가 여러 대라면 Redis 등 공유 저장소에 `SET jti 1 EXAT exp+leeway` 형태로 두면 됨
+
+use crate::keys::LEEWAY_SECS;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Default)]
+pub struct DenyList {
+    // jti → 보관할 시각 (토큰 만료 시각 + leeway, UNIX timestamp)
+    revoked: Mutex<HashMap<String, u64>>,
+}
+
+impl DenyList {
+    /// 토큰 무효화 (만료 시각 + leeway까지 보관)
+    pub fn revoke(&self, jti: &str, exp: u64) {
+        self.revoke_at(jti, exp, now());
+    }
+
+    pub fn is_revoked(&self, jti: &str) -> bool {
+        self.is_revoked_at(jti, now())
+    }
+
+    fn revoke_at(&self, jti: &str, exp: u64, now: u64) {
+        let mut revoked = self.revoked.lock().unwrap();
+        // 추가할 때마다 이미 만료된 항목을 정리 → 크기가 유효한 토큰 수를 넘지 않음
+        revoked.retain(|_, until| *until > now);
+        let until = exp.saturating_add(LEEWAY_SECS);
+        if until > now {
+            revoked.insert(jti.to_owned(), until);
+        }
+    }
+
+    fn is_revoked_at(&self, jti: &str, now: u64) -> bool {
+        self.revoked
+            .lock()
+            .unwrap()
+            .get(jti)
+            .is_some_and(|until| *until > now)
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.revoked.lock().unwrap().len()
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn revoked_until_expiry_plus_leeway() {
+        let list = DenyList::default();
+        list.revoke_at("a", 200, 100);
+
+        assert!(list.is_revoked_at("a", 100));
+        assert!(list.is_revoked_at("a", 199));
+        assert!(!list.is_revoked_at("b", 100));
+        // 만료됐지만 leeway 안이라 exp 검증을 통과하는 동안에도 거부
+        assert!(list.is_revoked_at("a", 200));
+        assert!(list.is_revoked_at("a", 200 + LEEWAY_SECS - 1));
+        // 그 뒤로는 exp 검증에서 거부됨
+        assert!(!list.is_revoked_at("a", 200 + LEEWAY_SECS));
+    }
+
+    #[test]
+    fn expired_entries_are_pruned() {
+        let list = DenyList::default();
+        list.revoke_at("a", 200, 100);
+        list.revoke_at("b", 300, 100);
+        // leeway까지 지난 토큰은 기록하지 않음
+        list.revoke_at("old", 100 - LEEWAY_SECS, 100);
+        assert_eq!(list.len(), 2);
+
+        let later = 200 + LEEWAY_SECS;
+        list.revoke_at("c", 400, later);
+        assert_eq!(list.len(), 2);
+        assert!(!list.is_revoked_at("a", later));
+        assert!(list.is_revoked_at("b", later));
+        assert!(list.is_revoked_at("c", later));
+    }
+}
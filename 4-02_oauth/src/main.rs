@@ -15,7 +15,13 @@
 //! 01_ GET /auth/discord -> GET /auth/authorized (자동이라서 수동으로 실행하면 에러남)
 //! 02_ GET / : index 페이지
 //! 03_ GET /protected : 인증된 영역의 정보를 보여줌.
-//! 04_ GET /logout (이후 protected 이동 시도하면 Discord 로 리다이렉트 됨.)
+//! 04_ GET /sessions : 로그인된 세션(기기) 목록, 다른 세션 원격 로그아웃 (POST /sessions/revoke)
+//! 05_ GET /logout (이후 protected 이동 시도하면 Discord 로 리다이렉트 됨.)
+//!
+//! 세션 보안:
+//! • 로그인에 성공하면 세션 ID를 새로 발급 (session fixation 방지)
+//! • 마지막 요청 후 `IDLE_TIMEOUT`, 로그인 후 `ABSOLUTE_TIMEOUT`이 지나면 세션 만료
+//!   → `User` 추출기가 요청마다 검사하고, 만료된 세션은 파기 후 로그인으로 리다이렉트
 //!
 
 use anyhow::{anyhow, Context, Result};
@@ -23,9 +29,9 @@ use async_session::{MemoryStore, Session, SessionStore};
 use axum::{
     extract::{FromRef, FromRequestParts, OptionalFromRequestParts, Query, State},
     http::{header::SET_COOKIE, HeaderMap},
-    response::{IntoResponse, Redirect, Response},
-    routing::get,
-    RequestPartsExt, Router,
+    response::{Html, IntoResponse, Redirect, Response},
+    routing::{get, post},
+    Form, RequestPartsExt, Router,
 };
 use axum_extra::{headers, typed_header::TypedHeaderRejectionReason, TypedHeader};
 use http::{header, request::Parts, StatusCode};
@@ -34,7 +40,14 @@ use oauth2::{
     ClientSecret, CsrfToken, RedirectUrl, Scope, TokenResponse, TokenUrl,
 };
 use serde::{Deserialize, Serialize};
-use std::{convert::Infallible, env};
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    env,
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 /// 세션 저장소에 사용될 쿠키 이름
@@ -42,6 +55,11 @@ static COOKIE_NAME: &str = "SESSION";
 /// CSRF 토큰 키 (세션 내부에서 사용)
 static CSRF_TOKEN: &str = "csrf_token";
 
+/// 마지막 요청 이후 이 시간 동안 활동이 없으면 세션 만료
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+/// 로그인 이후 활동과 상관없이 세션이 만료되는 시간
+const ABSOLUTE_TIMEOUT: Duration = Duration::from_secs(12 * 60 * 60);
+
 /// ✅ 서버 초기화 및 상태 구성
 #[tokio::main]
 async fn main() {
@@ -60,10 +78,30 @@ async fn main() {
     // OAuth 클라이언트 구성 (CLIENT_ID, CLIENT_SECRET 등 환경변수 기반)
     let oauth_client = oauth_client().unwrap();
 
+    // 로그인된 세션 목록 (`/sessions`, 만료/원격 로그아웃 확인용)
+    let sessions = SessionRegistry::default();
+
+    // 만료된 세션을 주기적으로 정리
+    tokio::spawn({
+        let store = store.clone();
+        let sessions = sessions.clone();
+        async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                if let Err(err) = store.cleanup().await {
+                    tracing::warn!("failed to clean up sessions: {err:#}");
+                }
+                sessions.purge_expired(unix_now());
+            }
+        }
+    });
+
     // 앱 전체 상태 구성
     let app_state = AppState {
         store,
         oauth_client,
+        sessions,
     };
 
     // 라우터 정의: 각 URL에 핸들러 연결 및 상태 주입
@@ -72,6 +110,8 @@ async fn main() {
         .route("/auth/discord", get(discord_auth)) // Discord 인증 요청 (자동)
         .route("/auth/authorized", get(login_authorized)) // OAuth 콜백 처리
         .route("/protected", get(protected)) // 보호된 라우트
+        .route("/sessions", get(list_sessions)) // 로그인된 세션 목록
+        .route("/sessions/revoke", post(revoke_session)) // 세션 원격 로그아웃
         .route("/logout", get(logout)) // 로그아웃
         .with_state(app_state); // 상태 주입
 
@@ -97,6 +137,7 @@ async fn main() {
 struct AppState {
     store: MemoryStore,        // 세션 저장소
     oauth_client: BasicClient, // OAuth2 클라이언트
+    sessions: SessionRegistry, // 로그인된 세션 목록
 }
 
 /// `AppState`에서 `MemoryStore`를 추출하기 위한 구현
@@ -113,6 +154,108 @@ impl FromRef<AppState> for BasicClient {
     }
 }
 
+/// `AppState`에서 `SessionRegistry`를 추출하기 위한 구현
+impl FromRef<AppState> for SessionRegistry {
+    fn from_ref(state: &AppState) -> Self {
+        state.sessions.clone()
+    }
+}
+
+/// ✅ 로그인된 세션 목록 (세션 ID → 메타데이터)
+/// - MemoryStore는 사용자별 세션을 나열할 수 없어서 생성/마지막 사용 시각과 함께 따로 기록
+/// - 여기에 없는 세션은 쿠키가 남아 있어도 무효 (로그아웃, 원격 로그아웃, 만료)
+#[derive(Clone, Default)]
+struct SessionRegistry(Arc<Mutex<HashMap<String, SessionInfo>>>);
+
+#[derive(Debug, Clone)]
+struct SessionInfo {
+    user_id: String,
+    created_at: u64, // 로그인 시각 (Unix 초)
+    last_seen: u64,  // 마지막 요청 시각 (Unix 초)
+    user_agent: Option<String>,
+}
+
+/// 세션이 만료된 이유
+#[derive(Debug, PartialEq)]
+enum Expired {
+    Idle,
+    Absolute,
+}
+
+impl SessionInfo {
+    fn check_lifetime(&self, now: u64) -> Result<(), Expired> {
+        if now.saturating_sub(self.created_at) >= ABSOLUTE_TIMEOUT.as_secs() {
+            return Err(Expired::Absolute);
+        }
+        if now.saturating_sub(self.last_seen) >= IDLE_TIMEOUT.as_secs() {
+            return Err(Expired::Idle);
+        }
+        Ok(())
+    }
+}
+
+impl SessionRegistry {
+    fn insert(&self, id: String, info: SessionInfo) {
+        self.0.lock().unwrap().insert(id, info);
+    }
+
+    /// 유효한 세션이면 마지막 사용 시각을 갱신, 만료됐으면 목록에서 제거
+    fn touch(&self, id: &str, now: u64) -> Result<(), Option<Expired>> {
+        let mut sessions = self.0.lock().unwrap();
+        let info = sessions.get_mut(id).ok_or(None)?;
+        if let Err(expired) = info.check_lifetime(now) {
+            sessions.remove(id);
+            return Err(Some(expired));
+        }
+        info.last_seen = now;
+        Ok(())
+    }
+
+    fn remove(&self, id: &str) {
+        self.0.lock().unwrap().remove(id);
+    }
+
+    /// 사용자의 세션 목록 (오래된 로그인 순)
+    fn list(&self, user_id: &str, now: u64) -> Vec<(String, SessionInfo)> {
+        let mut sessions: Vec<_> = self
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, info)| info.user_id == user_id && info.check_lifetime(now).is_ok())
+            .map(|(id, info)| (id.clone(), info.clone()))
+            .collect();
+        sessions.sort_by_key(|(_, info)| info.created_at);
+        sessions
+    }
+
+    /// 사용자 본인의 세션만 제거할 수 있음
+    fn revoke(&self, user_id: &str, id: &str) -> bool {
+        let mut sessions = self.0.lock().unwrap();
+        match sessions.get(id) {
+            Some(info) if info.user_id == user_id => {
+                sessions.remove(id);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn purge_expired(&self, now: u64) {
+        self.0
+            .lock()
+            .unwrap()
+            .retain(|_, info| info.check_lifetime(now).is_ok());
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
 /// ✅ OAuth 클라이언트 설정 함수
 /// Discord OAuth2 서버와 통신할 수 있도록 `BasicClient`를 설정합니다.
 /// 환경변수에서 다음 값을 로딩하며, 설정되지 않으면 에러를 반환합니다:
@@ -165,7 +308,7 @@ struct User {
 async fn index(user: Option<User>) -> impl IntoResponse {
     match user {
         Some(u) => format!(
-            "Hey {}! You're logged in!\nYou may now access `/protected`.\nLog out with `/logout`.",
+            "Hey {}! You're logged in!\nYou may now access `/protected`.\nSee your sessions at `/sessions`.\nLog out with `/logout`.",
             u.username
         ),
         None => "You're not logged in.\nVisit `/auth/discord` to do so.".to_string(),
@@ -219,12 +362,79 @@ async fn protected(user: User) -> impl IntoResponse {
     format!("Welcome to the protected area :)\nHere's your info:\n{user:?}")
 }
 
+/// ✅ 세션 목록 핸들러: `/sessions`
+/// - 현재 사용자로 로그인된 모든 세션(브라우저/기기)을 보여줌
+/// - 각 세션 옆의 버튼으로 다른 기기를 원격 로그아웃할 수 있음
+async fn list_sessions(auth: AuthSession, State(sessions): State<SessionRegistry>) -> Html<String> {
+    let now = unix_now();
+    let mut html = format!(
+        "<h1>Sessions for {}</h1>\n<ul>\n",
+        escape_html(&auth.user.username)
+    );
+    for (id, info) in sessions.list(&auth.user.id, now) {
+        let current = if id == auth.id { " (this session)" } else { "" };
+        let _ = write!(
+            html,
+            "<li>{}{current}<br>signed in {}s ago, last seen {}s ago\n\
+             <form method=\"post\" action=\"/sessions/revoke\">\
+             <input type=\"hidden\" name=\"id\" value=\"{}\">\
+             <button>Revoke</button></form></li>\n",
+            escape_html(info.user_agent.as_deref().unwrap_or("unknown device")),
+            now.saturating_sub(info.created_at),
+            now.saturating_sub(info.last_seen),
+            escape_html(&id),
+        );
+    }
+    html.push_str("</ul>\n");
+    Html(html)
+}
+
+#[derive(Debug, Deserialize)]
+struct RevokeRequest {
+    id: String,
+}
+
+/// ✅ 원격 로그아웃 핸들러: `POST /sessions/revoke`
+/// - 세션 목록에서 지우면 그 세션의 쿠키로 오는 다음 요청은 `User` 추출기에서 거부됨
+/// - 다른 사용자의 세션 ID를 보내면 404
+/// - 쿠키가 `SameSite=Lax`라서 다른 사이트의 form POST에는 세션이 실리지 않음
+async fn revoke_session(
+    auth: AuthSession,
+    State(sessions): State<SessionRegistry>,
+    Form(request): Form<RevokeRequest>,
+) -> Result<Redirect, StatusCode> {
+    if !sessions.revoke(&auth.user.id, &request.id) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    tracing::info!(user = %auth.user.id, "session revoked");
+
+    // 현재 세션을 지웠다면 `/sessions`에서 다시 로그인으로 리다이렉트됨
+    Ok(Redirect::to("/sessions"))
+}
+
+/// User-Agent 등 사용자가 보낸 값을 HTML에 넣기 전에 이스케이프
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 /// ✅ 로그아웃 핸들러: `/logout`
 /// - 쿠키에서 세션 ID를 가져와 해당 세션을 파기합니다.
 /// - 세션이 없다면 그냥 `/` 경로로 리다이렉트만 수행
 /// - 로그아웃 후 사용자 인증 정보는 서버에서 삭제됨
 async fn logout(
     State(store): State<MemoryStore>,
+    State(sessions): State<SessionRegistry>,
     TypedHeader(cookies): TypedHeader<headers::Cookie>,
 ) -> Result<impl IntoResponse, AppError> {
     // 1. 쿠키에서 세션 ID 추출
@@ -243,7 +453,8 @@ async fn logout(
         None => return Ok(Redirect::to("/")),
     };
 
-    // 3. 세션 파기 (MemoryStore 내 데이터 삭제)
+    // 3. 세션 파기 (MemoryStore 내 데이터 삭제, 세션 목록에서도 제거)
+    sessions.remove(session.id());
     store
         .destroy_session(session)
         .await
@@ -264,11 +475,12 @@ struct AuthRequest {
 /// ✅ CSRF 토큰 검증 로직 (내부 사용)
 /// - 요청에 포함된 `state` 값과, 세션에 저장된 `csrf_token` 값이 일치하는지 확인
 /// - 검증 실패 시 인증 오류 반환
+/// - 성공하면 로그인 전 세션을 반환 (저장소에서는 이미 제거됨)
 async fn csrf_token_validation_workflow(
     auth_request: &AuthRequest,
     cookies: &headers::Cookie,
     store: &MemoryStore,
-) -> Result<(), AppError> {
+) -> Result<Session, AppError> {
     // 1. 쿠키에서 세션 ID 추출
     let cookie = cookies
         .get(COOKIE_NAME)
@@ -293,7 +505,7 @@ async fn csrf_token_validation_workflow(
 
     // 4. 세션 제거 (CSRF 토큰은 일회성이므로)
     store
-        .destroy_session(session)
+        .destroy_session(session.clone())
         .await
         .context("Failed to destroy old session")?;
 
@@ -302,22 +514,24 @@ async fn csrf_token_validation_workflow(
         return Err(anyhow!("CSRF token mismatch").into());
     }
 
-    Ok(())
+    Ok(session)
 }
 
 /// ✅ OAuth 인증 완료 후 콜백 처리 핸들러: `/auth/authorized`
 /// - Discord 인증 서버에서 Authorization Code와 함께 state(csrf_token) 전달됨
 /// - 세션에서 저장된 CSRF 토큰과 비교하여 유효성 확인
 /// - 토큰 교환 후, 사용자 정보를 요청하여 세션에 저장
-/// - 세션 쿠키를 다시 발급하여 클라이언트에 전달하고 루트로 리다이렉트
+/// - 세션 ID를 새로 발급하여 클라이언트에 전달하고 루트로 리다이렉트
 async fn login_authorized(
     Query(query): Query<AuthRequest>,
     State(store): State<MemoryStore>,
+    State(sessions): State<SessionRegistry>,
     State(oauth_client): State<BasicClient>,
     TypedHeader(cookies): TypedHeader<headers::Cookie>,
+    user_agent: Option<TypedHeader<headers::UserAgent>>,
 ) -> Result<impl IntoResponse, AppError> {
     // 1. CSRF 토큰 유효성 검증
-    csrf_token_validation_workflow(&query, &cookies, &store).await?;
+    let mut session = csrf_token_validation_workflow(&query, &cookies, &store).await?;
 
     // 2. Authorization Code → Access Token 교환
     let token = oauth_client
@@ -338,13 +552,29 @@ async fn login_authorized(
         .await
         .context("failed to deserialize response as JSON")?;
 
-    // 4. 사용자 정보를 세션에 저장
-    let mut session = Session::new();
+    // 4. 세션 고정(session fixation) 방지: 로그인 전 세션 ID를 그대로 쓰지 않고 새로 발급
+    //    → 공격자가 미리 심어 둔 쿠키 값은 로그인 후에 아무 의미가 없음
+    session.regenerate();
+    session.remove(CSRF_TOKEN);
     session
         .insert("user", &user_data)
         .context("failed in inserting serialized value into session")?;
+    // 저장소에서도 절대 만료 시간이 지나면 세션을 버림
+    session.expire_in(ABSOLUTE_TIMEOUT);
+
+    // 5. 세션 목록에 생성 시각과 함께 기록
+    let now = unix_now();
+    sessions.insert(
+        session.id().to_owned(),
+        SessionInfo {
+            user_id: user_data.id.clone(),
+            created_at: now,
+            last_seen: now,
+            user_agent: user_agent.map(|TypedHeader(ua)| ua.as_str().to_owned()),
+        },
+    );
 
-    // 5. 세션 저장 및 쿠키 발급
+    // 6. 세션 저장 및 쿠키 발급
     let cookie = store
         .store_session(session)
         .await
@@ -358,7 +588,7 @@ async fn login_authorized(
         cookie.parse().context("failed to parse cookie")?,
     );
 
-    // 6. 루트 경로로 리다이렉트
+    // 7. 루트 경로로 리다이렉트
     Ok((headers, Redirect::to("/")))
 }
 
@@ -371,19 +601,30 @@ impl IntoResponse for AuthRedirect {
     }
 }
 
-/// ✅ 커스텀 요청 추출기: `impl FromRequestParts for User`
+/// 로그인된 세션 (현재 세션 ID + 사용자 정보)
+/// - `/sessions`처럼 현재 세션을 구분해야 하는 핸들러에서 사용
+struct AuthSession {
+    id: String,
+    user: User,
+}
+
+/// ✅ 커스텀 요청 추출기: `impl FromRequestParts for AuthSession`
 /// - 세션 쿠키에서 사용자 정보를 꺼내 `User`로 복원
-/// - 세션이 없거나 사용자 정보가 없으면 `/auth/discord`로 리다이렉트
-impl<S> FromRequestParts<S> for User
+/// - 세션이 없거나, 사용자 정보가 없거나, 세션 목록에 없거나(로그아웃/원격 로그아웃),
+///   idle/absolute 만료 시간이 지났으면 `/auth/discord`로 리다이렉트
+/// - 유효한 세션이면 마지막 사용 시각을 갱신 (idle timeout 연장)
+impl<S> FromRequestParts<S> for AuthSession
 where
     MemoryStore: FromRef<S>,
+    SessionRegistry: FromRef<S>,
     S: Send + Sync,
 {
     type Rejection = AuthRedirect;
 
     async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
-        // MemoryStore 추출
+        // MemoryStore, SessionRegistry 추출
         let store = MemoryStore::from_ref(state);
+        let sessions = SessionRegistry::from_ref(state);
 
         // 쿠키 파싱
         let cookies = parts
@@ -410,7 +651,34 @@ where
         // 세션에서 사용자 정보 꺼내기
         let user = session.get::<User>("user").ok_or(AuthRedirect)?;
 
-        Ok(user)
+        // 만료/원격 로그아웃 확인 → 무효한 세션은 저장소에서도 파기
+        let id = session.id().to_owned();
+        if let Err(expired) = sessions.touch(&id, unix_now()) {
+            tracing::debug!(user = %user.id, ?expired, "rejecting invalid session");
+            // 파기에 실패해도 레지스트리가 이미 무효로 보므로 로그만 남기고 로그인으로
+            if let Err(err) = store.destroy_session(session).await {
+                tracing::warn!(user = %user.id, %err, "failed to destroy invalid session");
+            }
+            return Err(AuthRedirect);
+        }
+
+        Ok(AuthSession { id, user })
+    }
+}
+
+/// ✅ 커스텀 요청 추출기: `impl FromRequestParts for User`
+/// - `AuthSession`과 같은 검사를 하고 사용자 정보만 반환
+impl<S> FromRequestParts<S> for User
+where
+    MemoryStore: FromRef<S>,
+    SessionRegistry: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AuthRedirect;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let auth = AuthSession::from_request_parts(parts, state).await?;
+        Ok(auth.user)
     }
 }
 
@@ -419,6 +687,7 @@ where
 impl<S> OptionalFromRequestParts<S> for User
 where
     MemoryStore: FromRef<S>,
+    SessionRegistry: FromRef<S>,
     S: Send + Sync,
 {
     type Rejection = Infallible;
@@ -462,9 +731,66 @@ where
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(user_id: &str, created_at: u64, last_seen: u64) -> SessionInfo {
+        SessionInfo {
+            user_id: user_id.to_owned(),
+            created_at,
+            last_seen,
+            user_agent: None,
+        }
+    }
+
+    #[test]
+    fn sessions_expire_after_idle_or_absolute_timeout() {
+        let idle = IDLE_TIMEOUT.as_secs();
+        let absolute = ABSOLUTE_TIMEOUT.as_secs();
+
+        assert_eq!(info("u", 0, 0).check_lifetime(idle - 1), Ok(()));
+        assert_eq!(info("u", 0, 0).check_lifetime(idle), Err(Expired::Idle));
+
+        // 계속 사용 중이어도 절대 만료 시간은 넘길 수 없음
+        let busy = info("u", 0, absolute - 1);
+        assert_eq!(busy.check_lifetime(absolute - 1), Ok(()));
+        assert_eq!(busy.check_lifetime(absolute), Err(Expired::Absolute));
+    }
+
+    #[test]
+    fn touching_extends_the_idle_timeout_until_revoked() {
+        let idle = IDLE_TIMEOUT.as_secs();
+        let sessions = SessionRegistry::default();
+        sessions.insert("a".to_owned(), info("alice", 0, 0));
+        sessions.insert("b".to_owned(), info("alice", 10, 10));
+        sessions.insert("c".to_owned(), info("bob", 0, 0));
+
+        // 요청이 있을 때마다 idle timeout이 연장됨
+        assert_eq!(sessions.touch("a", idle - 1), Ok(()));
+        assert_eq!(sessions.touch("a", 2 * idle - 2), Ok(()));
+        // 한동안 사용하지 않은 세션은 만료되고 목록에서 사라짐
+        assert_eq!(sessions.touch("b", idle + 10), Err(Some(Expired::Idle)));
+        assert_eq!(sessions.touch("b", idle + 10), Err(None));
+
+        let listed: Vec<_> = sessions
+            .list("alice", 2 * idle - 2)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(listed, ["a"]);
+
+        // 다른 사용자의 세션은 원격 로그아웃할 수 없음
+        assert!(!sessions.revoke("alice", "c"));
+        assert!(sessions.revoke("alice", "a"));
+        assert_eq!(sessions.touch("a", 2 * idle - 1), Err(None));
+    }
+}
+
 // ✅ 마무리 요약:
 // - 이 예제는 Discord OAuth 인증 흐름을 Axum + async_session 기반으로 구현한 전체적인 인증 플로우를 담고 있음
 // - 로그인, 토큰 교환, 세션 기반 상태 유지, 보호된 라우트, 로그아웃, CSRF 보호 등 실무 구성의 좋은 참고 예시
+// - 로그인 시 세션 ID 재발급, idle/absolute 만료, 세션 목록과 원격 로그아웃까지 포함
 // - MemoryStore는 데모 용도이며, Redis, DynamoDB 등으로 대체 필요
 // - 실제 배포 시 HTTPS 적용 및 Secure 쿠키, CSRF 강화, state 무결성 검사 추가 고려
 
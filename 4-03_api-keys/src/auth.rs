@@ -0,0 +1,129 @@
+//! 🛂 `X-Api-Key` 헤더 인증 + scope 검사
+//!
+//! - `ApiKey` 추출기: 헤더의 키를 검증해서 키 정보를 핸들러에 전달
+//! - `require_scope` 미들웨어: 라우트에 필요한 scope가 없으면 403
+
+use crate::keys::{KeyError, KeyInfo, KeyStore, Scope};
+use axum::{
+    extract::{FromRef, FromRequestParts, Request, State},
+    http::{request::Parts, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// 🔑 인증된 API 키
+#[derive(Debug, Clone)]
+pub struct ApiKey(pub KeyInfo);
+
+impl<S> FromRequestParts<S> for ApiKey
+where
+    KeyStore: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        // require_scope 미들웨어에서 이미 검증했다면 그 결과를 재사용
+        if let Some(key) = parts.extensions.get::<ApiKey>() {
+            return Ok(key.clone());
+        }
+
+        let key = parts
+            .headers
+            .get(API_KEY_HEADER)
+            .ok_or(AuthError::MissingKey)?
+            .to_str()
+            .map_err(|_| AuthError::Key(KeyError::Invalid))?;
+        let info = KeyStore::from_ref(state)
+            .verify(key)
+            .map_err(AuthError::Key)?;
+
+        let key = ApiKey(info);
+        parts.extensions.insert(key.clone());
+        Ok(key)
+    }
+}
+
+/// `require_scope` 미들웨어의 상태: 키 저장소 + 필요한 scope
+#[derive(Clone)]
+pub struct ScopeGuard {
+    keys: KeyStore,
+    scope: Scope,
+}
+
+impl ScopeGuard {
+    pub fn new(keys: &KeyStore, scope: Scope) -> Self {
+        Self {
+            keys: keys.clone(),
+            scope,
+        }
+    }
+}
+
+impl FromRef<ScopeGuard> for KeyStore {
+    fn from_ref(guard: &ScopeGuard) -> Self {
+        guard.keys.clone()
+    }
+}
+
+/// 🧱 미들웨어: 키 인증 → scope 확인 → 핸들러
+///
+/// `route_layer(middleware::from_fn_with_state(ScopeGuard::new(&keys, scope), require_scope))`
+pub async fn require_scope(
+    State(guard): State<ScopeGuard>,
+    ApiKey(key): ApiKey,
+    request: Request,
+    next: Next,
+) -> Result<Response, AuthError> {
+    if !key.has_scope(guard.scope) {
+        tracing::debug!(key = key.id, scope = guard.scope.as_str(), "missing scope");
+        return Err(AuthError::InsufficientScope(guard.scope));
+    }
+    Ok(next.run(request).await)
+}
+
+/// ❌ 인증/인가 실패
+#[derive(Debug)]
+pub enum AuthError {
+    MissingKey,
+    Key(KeyError),
+    InsufficientScope(Scope),
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        // code: 클라이언트가 분기 처리에 쓰는 고정 문자열
+        let (status, code, message) = match self {
+            Self::MissingKey => (
+                StatusCode::UNAUTHORIZED,
+                "missing_api_key",
+                format!("missing `{API_KEY_HEADER}` header"),
+            ),
+            Self::Key(KeyError::Invalid) => (
+                StatusCode::UNAUTHORIZED,
+                "invalid_api_key",
+                "invalid API key".to_owned(),
+            ),
+            Self::Key(KeyError::Revoked) => (
+                StatusCode::UNAUTHORIZED,
+                "api_key_revoked",
+                "API key has been revoked".to_owned(),
+            ),
+            Self::Key(KeyError::Expired) => (
+                StatusCode::UNAUTHORIZED,
+                "api_key_expired",
+                "API key has expired, use the rotated key".to_owned(),
+            ),
+            Self::InsufficientScope(scope) => (
+                StatusCode::FORBIDDEN,
+                "insufficient_scope",
+                format!("API key requires the `{}` scope", scope.as_str()),
+            ),
+        };
+        (status, Json(json!({ "error": message, "code": code }))).into_response()
+    }
+}
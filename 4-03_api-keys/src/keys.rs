@@ -0,0 +1,295 @@
+//! 🔑 API 키 발급 / 저장 / 검증
+//!
+//! 키 형식: `ak_<id>_<secret>`
+//! - id: 키를 찾기 위한 공개 식별자 (로그나 목록 API에 보여도 됨)
+//! - secret: 32바이트 난수, 서버에는 SHA-256 해시만 저장 → 저장소가 유출돼도 키를 복원할 수 없음
+//!
+//! 비밀번호와 달리 키 자체가 충분히 긴 난수라서 bcrypt/argon2 같은 느린 해시는 필요 없음
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use subtle::ConstantTimeEq;
+
+const KEY_PREFIX: &str = "ak_";
+const ID_BYTES: usize = 8;
+const SECRET_BYTES: usize = 32;
+
+type KeyHash = [u8; 32];
+
+/// 🎫 키마다 허용되는 작업
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Scope {
+    #[serde(rename = "items:read")]
+    ItemsRead,
+    #[serde(rename = "items:write")]
+    ItemsWrite,
+    /// 키 발급/교체/폐기
+    #[serde(rename = "keys:admin")]
+    KeysAdmin,
+}
+
+impl Scope {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ItemsRead => "items:read",
+            Self::ItemsWrite => "items:write",
+            Self::KeysAdmin => "keys:admin",
+        }
+    }
+}
+
+/// 키 메타데이터 (해시는 포함하지 않음)
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyInfo {
+    pub id: String,
+    pub name: String,
+    pub scopes: Vec<Scope>,
+    pub created_at: u64,
+    /// 교체된 키는 유예 기간이 끝나면 만료
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    pub revoked: bool,
+}
+
+impl KeyInfo {
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
+
+/// 새로 발급한 키 (평문 키는 이 응답에서 한 번만 보여줌)
+#[derive(Debug, Serialize)]
+pub struct IssuedKey {
+    pub key: String,
+    #[serde(flatten)]
+    pub info: KeyInfo,
+}
+
+struct StoredKey {
+    info: KeyInfo,
+    hash: KeyHash,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyError {
+    /// 형식이 틀렸거나, 모르는 id거나, secret이 틀림 (구분하지 않음)
+    Invalid,
+    Revoked,
+    Expired,
+}
+
+/// 🗄️ 키 저장소 (예제라서 메모리에 보관, 실서비스에서는 DB에 id/해시/scope 저장)
+#[derive(Clone)]
+pub struct KeyStore {
+    keys: Arc<RwLock<HashMap<String, StoredKey>>>,
+    // 교체 후 이전 키를 계속 허용하는 시간 (클라이언트가 새 키로 바꿀 시간)
+    rotation_grace: Duration,
+}
+
+impl KeyStore {
+    pub fn new(rotation_grace: Duration) -> Self {
+        Self {
+            keys: Default::default(),
+            rotation_grace,
+        }
+    }
+
+    pub fn issue(&self, name: impl Into<String>, scopes: Vec<Scope>) -> IssuedKey {
+        let id = random_hex(ID_BYTES);
+        let secret = random_hex(SECRET_BYTES);
+        let info = KeyInfo {
+            id: id.clone(),
+            name: name.into(),
+            scopes,
+            created_at: unix_now(),
+            expires_at: None,
+            revoked: false,
+        };
+
+        self.keys.write().unwrap().insert(
+            id.clone(),
+            StoredKey {
+                info: info.clone(),
+                hash: hash(&secret),
+            },
+        );
+
+        IssuedKey {
+            key: format!("{KEY_PREFIX}{id}_{secret}"),
+            info,
+        }
+    }
+
+    pub fn verify(&self, key: &str) -> Result<KeyInfo, KeyError> {
+        self.verify_at(key, unix_now())
+    }
+
+    fn verify_at(&self, key: &str, now: u64) -> Result<KeyInfo, KeyError> {
+        let (id, secret) = key
+            .strip_prefix(KEY_PREFIX)
+            .and_then(|rest| rest.split_once('_'))
+            .ok_or(KeyError::Invalid)?;
+
+        let keys = self.keys.read().unwrap();
+        let stored = keys.get(id);
+
+        // 모르는 id여도 해시 계산과 비교는 똑같이 수행 → 응답 시간으로 id 존재 여부를 알 수 없게 함
+        // 비교는 상수 시간 (일치하는 앞부분 길이에 따라 시간이 달라지지 않음)
+        let expected = stored.map_or([0; 32], |stored| stored.hash);
+        let matches = bool::from(hash(secret).ct_eq(&expected));
+        let stored = stored.filter(|_| matches).ok_or(KeyError::Invalid)?;
+
+        if stored.info.revoked {
+            return Err(KeyError::Revoked);
+        }
+        if stored
+            .info
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= now)
+        {
+            return Err(KeyError::Expired);
+        }
+        Ok(stored.info.clone())
+    }
+
+    pub fn list(&self) -> Vec<KeyInfo> {
+        let mut keys: Vec<_> = self
+            .keys
+            .read()
+            .unwrap()
+            .values()
+            .map(|stored| stored.info.clone())
+            .collect();
+        keys.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+        keys
+    }
+
+    /// 같은 이름/scope로 새 키를 발급하고, 이전 키는 유예 기간 뒤 만료되도록 설정
+    /// 폐기됐거나 이미 교체된 키는 `None`
+    pub fn rotate(&self, id: &str) -> Option<IssuedKey> {
+        let (name, scopes) = {
+            let mut keys = self.keys.write().unwrap();
+            let old = &mut keys.get_mut(id)?.info;
+            if old.revoked || old.expires_at.is_some() {
+                return None;
+            }
+            old.expires_at = Some(unix_now() + self.rotation_grace.as_secs());
+            (old.name.clone(), old.scopes.clone())
+        };
+        Some(self.issue(name, scopes))
+    }
+
+    /// 즉시 폐기 (목록에는 revoked로 남음), 모르는 id면 `false`
+    pub fn revoke(&self, id: &str) -> bool {
+        match self.keys.write().unwrap().get_mut(id) {
+            Some(stored) => {
+                stored.info.revoked = true;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+fn hash(secret: &str) -> KeyHash {
+    Sha256::digest(secret.as_bytes()).into()
+}
+
+fn random_hex(len: usize) -> String {
+    let mut bytes = vec![0; len];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRACE: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn issued_key_verifies_and_only_hash_is_stored() {
+        let store = KeyStore::new(GRACE);
+        let issued = store.issue("ci", vec![Scope::ItemsRead]);
+
+        let info = store.verify(&issued.key).unwrap();
+        assert_eq!(info.id, issued.info.id);
+        assert_eq!(info.scopes, [Scope::ItemsRead]);
+
+        let secret = issued.key.rsplit('_').next().unwrap();
+        assert_eq!(secret.len(), SECRET_BYTES * 2);
+        let keys = store.keys.read().unwrap();
+        assert_eq!(keys[&info.id].hash, hash(secret));
+    }
+
+    #[test]
+    fn wrong_or_malformed_keys_are_invalid() {
+        let store = KeyStore::new(GRACE);
+        let issued = store.issue("ci", vec![Scope::ItemsRead]);
+        let other = KeyStore::new(GRACE).issue("other", vec![Scope::ItemsRead]);
+
+        // id는 맞지만 secret이 다른 키
+        let (prefix, _) = issued.key.rsplit_once('_').unwrap();
+        let forged = format!("{prefix}_{}", "0".repeat(SECRET_BYTES * 2));
+
+        for key in [
+            forged.as_str(),
+            other.key.as_str(),
+            "ak_",
+            "ak_nounderscore",
+            "not-a-key",
+            "",
+        ] {
+            assert_eq!(store.verify(key).unwrap_err(), KeyError::Invalid, "{key}");
+        }
+    }
+
+    #[test]
+    fn rotation_keeps_old_key_until_grace_ends() {
+        let store = KeyStore::new(GRACE);
+        let old = store.issue("ci", vec![Scope::ItemsRead, Scope::ItemsWrite]);
+
+        let new = store.rotate(&old.info.id).unwrap();
+        assert_ne!(new.info.id, old.info.id);
+        assert_eq!(new.info.name, "ci");
+        assert_eq!(new.info.scopes, old.info.scopes);
+
+        // 이미 교체된 키는 다시 교체할 수 없음
+        assert!(store.rotate(&old.info.id).is_none());
+
+        let now = unix_now();
+        assert!(store.verify_at(&old.key, now).is_ok());
+        assert_eq!(
+            store
+                .verify_at(&old.key, now + GRACE.as_secs() + 1)
+                .unwrap_err(),
+            KeyError::Expired
+        );
+        assert!(store.verify_at(&new.key, now + GRACE.as_secs() + 1).is_ok());
+    }
+
+    #[test]
+    fn revoked_key_is_rejected() {
+        let store = KeyStore::new(GRACE);
+        let issued = store.issue("ci", vec![Scope::ItemsRead]);
+
+        assert!(store.revoke(&issued.info.id));
+        assert!(!store.revoke("unknown"));
+        assert_eq!(store.verify(&issued.key).unwrap_err(), KeyError::Revoked);
+        assert!(store.rotate(&issued.info.id).is_none());
+        assert!(store.list()[0].revoked);
+    }
+}
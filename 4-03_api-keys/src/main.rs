@@ -0,0 +1,379 @@
+//! API 키 인증 예제
+//!
+//! 서버 간 호출이나 CI 스크립트처럼 사용자 로그인 없이 호출하는 클라이언트용 인증
+//!
+//! - 키는 발급할 때 한 번만 평문으로 보여주고, 서버에는 해시만 저장
+//! - 요청마다 `X-Api-Key` 헤더의 키를 상수 시간 비교로 검증
+//! - 키마다 scope를 붙이고, 라우트마다 `require_scope` 레이어로 필요한 scope 검사
+//! - 교체(rotate)하면 이전 키는 유예 기간 동안만 유효, 폐기(revoke)하면 즉시 무효
+//!
+//! | 라우트                         | 필요한 scope  |
+//! |--------------------------------|---------------|
+//! | `GET /items`                   | `items:read`  |
+//! | `POST /items`                  | `items:write` |
+//! | `GET /keys`, `POST /keys`      | `keys:admin`  |
+//! | `POST /keys/{id}/rotate`       | `keys:admin`  |
+//! | `DELETE /keys/{id}`            | `keys:admin`  |
+
+mod auth;
+mod keys;
+
+use auth::{require_scope, ApiKey, ScopeGuard};
+use axum::{
+    extract::{FromRef, Path, State},
+    http::StatusCode,
+    middleware,
+    routing::{delete, get, post},
+    Json, Router,
+};
+use keys::{IssuedKey, KeyInfo, KeyStore, Scope};
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+// 키를 교체한 뒤 이전 키를 계속 허용하는 시간
+const ROTATION_GRACE: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let state = AppState::new(ROTATION_GRACE);
+
+    // 첫 관리자 키는 시작할 때 발급해서 로그로만 보여줌 (이 키로 다른 키를 발급)
+    let admin = state.keys.issue("bootstrap-admin", vec![Scope::KeysAdmin]);
+    tracing::info!(key = admin.key, "bootstrap admin key issued");
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(state)).await.unwrap();
+}
+
+fn app(state: AppState) -> Router {
+    let scope =
+        |scope| middleware::from_fn_with_state(ScopeGuard::new(&state.keys, scope), require_scope);
+
+    // 같은 경로라도 메서드마다 필요한 scope가 다르므로 scope별 라우터를 따로 만들어 합침
+    let read = Router::new()
+        .route("/items", get(list_items))
+        .route_layer(scope(Scope::ItemsRead));
+    let write = Router::new()
+        .route("/items", post(create_item))
+        .route_layer(scope(Scope::ItemsWrite));
+    let admin = Router::new()
+        .route("/keys", get(list_keys).post(issue_key))
+        .route("/keys/{id}/rotate", post(rotate_key))
+        .route("/keys/{id}", delete(revoke_key))
+        .route_layer(scope(Scope::KeysAdmin));
+
+    Router::new()
+        .merge(read)
+        .merge(write)
+        .merge(admin)
+        .with_state(state)
+}
+
+#[derive(Clone)]
+struct AppState {
+    keys: KeyStore,
+    items: Arc<Mutex<Vec<Item>>>,
+}
+
+impl AppState {
+    fn new(rotation_grace: Duration) -> Self {
+        Self {
+            keys: KeyStore::new(rotation_grace),
+            items: Default::default(),
+        }
+    }
+}
+
+// `ApiKey` 추출기가 AppState에서 키 저장소를 꺼낼 수 있게 함
+impl FromRef<AppState> for KeyStore {
+    fn from_ref(state: &AppState) -> Self {
+        state.keys.clone()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Item {
+    name: String,
+    // 어떤 키로 만들었는지 (감사 로그 용도)
+    created_by: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateItem {
+    name: String,
+}
+
+async fn list_items(State(state): State<AppState>) -> Json<Vec<Item>> {
+    Json(state.items.lock().unwrap().clone())
+}
+
+async fn create_item(
+    State(state): State<AppState>,
+    ApiKey(key): ApiKey,
+    Json(input): Json<CreateItem>,
+) -> (StatusCode, Json<Item>) {
+    let item = Item {
+        name: input.name,
+        created_by: key.id,
+    };
+    state.items.lock().unwrap().push(item.clone());
+    (StatusCode::CREATED, Json(item))
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueKey {
+    name: String,
+    scopes: Vec<Scope>,
+}
+
+/// 🆕 POST /keys: 새 키 발급 (응답의 `key`는 다시 조회할 수 없음)
+async fn issue_key(
+    State(state): State<AppState>,
+    ApiKey(admin): ApiKey,
+    Json(input): Json<IssueKey>,
+) -> Result<(StatusCode, Json<IssuedKey>), (StatusCode, &'static str)> {
+    if input.name.trim().is_empty() || input.scopes.is_empty() {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "name and at least one scope are required",
+        ));
+    }
+    let issued = state.keys.issue(input.name, input.scopes);
+    tracing::info!(by = admin.id, key = issued.info.id, "api key issued");
+    Ok((StatusCode::CREATED, Json(issued)))
+}
+
+async fn list_keys(State(state): State<AppState>) -> Json<Vec<KeyInfo>> {
+    Json(state.keys.list())
+}
+
+/// 🔄 POST /keys/{id}/rotate: 새 키 발급 + 이전 키는 유예 기간 뒤 만료
+async fn rotate_key(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<IssuedKey>, StatusCode> {
+    let issued = state.keys.rotate(&id).ok_or(StatusCode::NOT_FOUND)?;
+    tracing::info!(old = id, new = issued.info.id, "api key rotated");
+    Ok(Json(issued))
+}
+
+/// 🗑️ DELETE /keys/{id}: 즉시 폐기
+async fn revoke_key(State(state): State<AppState>, Path(id): Path<String>) -> StatusCode {
+    if state.keys.revoke(&id) {
+        tracing::info!(key = id, "api key revoked");
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use auth::API_KEY_HEADER;
+    use axum::{body::Body, http::Request};
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    struct TestApp {
+        router: Router,
+        admin: String,
+    }
+
+    impl TestApp {
+        fn new(rotation_grace: Duration) -> Self {
+            let state = AppState::new(rotation_grace);
+            let admin = state.keys.issue("admin", vec![Scope::KeysAdmin]).key;
+            Self {
+                router: app(state),
+                admin,
+            }
+        }
+
+        async fn send(
+            &self,
+            method: &str,
+            uri: &str,
+            key: Option<&str>,
+            body: Option<Value>,
+        ) -> (StatusCode, Value) {
+            let mut request = Request::builder().method(method).uri(uri);
+            if let Some(key) = key {
+                request = request.header(API_KEY_HEADER, key);
+            }
+            let request = match body {
+                Some(body) => request
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string())),
+                None => request.body(Body::empty()),
+            }
+            .unwrap();
+
+            let response = self.router.clone().oneshot(request).await.unwrap();
+            let status = response.status();
+            let bytes = response.into_body().collect().await.unwrap().to_bytes();
+            (
+                status,
+                serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+            )
+        }
+
+        async fn issue(&self, scopes: Value) -> Value {
+            let (status, body) = self
+                .send(
+                    "POST",
+                    "/keys",
+                    Some(&self.admin),
+                    Some(json!({ "name": "client", "scopes": scopes })),
+                )
+                .await;
+            assert_eq!(status, StatusCode::CREATED);
+            body
+        }
+    }
+
+    #[tokio::test]
+    async fn missing_or_invalid_key_is_unauthorized() {
+        let app = TestApp::new(ROTATION_GRACE);
+
+        let (status, body) = app.send("GET", "/items", None, None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["code"], "missing_api_key");
+
+        let (status, body) = app.send("GET", "/items", Some("ak_x_y"), None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["code"], "invalid_api_key");
+    }
+
+    #[tokio::test]
+    async fn scopes_are_enforced_per_route() {
+        let app = TestApp::new(ROTATION_GRACE);
+        let reader = app.issue(json!(["items:read"])).await;
+        let reader = reader["key"].as_str().unwrap();
+        let writer = app.issue(json!(["items:read", "items:write"])).await;
+        let writer_id = writer["id"].clone();
+        let writer = writer["key"].as_str().unwrap();
+
+        let (status, item) = app
+            .send("POST", "/items", Some(writer), Some(json!({ "name": "a" })))
+            .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(item["created_by"], writer_id);
+
+        let (status, items) = app.send("GET", "/items", Some(reader), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(items.as_array().unwrap().len(), 1);
+
+        let (status, body) = app
+            .send("POST", "/items", Some(reader), Some(json!({ "name": "b" })))
+            .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["code"], "insufficient_scope");
+
+        // 관리자 키도 keys:admin 외의 scope는 없음
+        let (status, _) = app.send("GET", "/items", Some(&app.admin), None).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, _) = app.send("GET", "/keys", Some(writer), None).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn issued_key_is_only_shown_once() {
+        let app = TestApp::new(ROTATION_GRACE);
+        let issued = app.issue(json!(["items:read"])).await;
+        assert!(issued["key"].as_str().unwrap().starts_with("ak_"));
+
+        let (status, keys) = app.send("GET", "/keys", Some(&app.admin), None).await;
+        assert_eq!(status, StatusCode::OK);
+        let listed = keys
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|key| key["id"] == issued["id"])
+            .unwrap();
+        assert_eq!(listed["scopes"], json!(["items:read"]));
+        assert!(listed.get("key").is_none());
+        assert!(listed.get("hash").is_none());
+
+        // 모르는 scope는 거부
+        let (status, _) = app
+            .send(
+                "POST",
+                "/keys",
+                Some(&app.admin),
+                Some(json!({ "name": "x", "scopes": ["items:delete"] })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn rotation_and_revocation() {
+        // 유예 기간 0 → 교체 즉시 이전 키 만료
+        let app = TestApp::new(Duration::ZERO);
+        let old = app.issue(json!(["items:read"])).await;
+        let old_key = old["key"].as_str().unwrap();
+
+        let (status, new) = app
+            .send(
+                "POST",
+                &format!("/keys/{}/rotate", old["id"].as_str().unwrap()),
+                Some(&app.admin),
+                None,
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK);
+        let new_key = new["key"].as_str().unwrap();
+        assert_eq!(new["scopes"], json!(["items:read"]));
+
+        let (status, body) = app.send("GET", "/items", Some(old_key), None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["code"], "api_key_expired");
+        let (status, _) = app.send("GET", "/items", Some(new_key), None).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let uri = format!("/keys/{}", new["id"].as_str().unwrap());
+        let (status, _) = app.send("DELETE", &uri, Some(&app.admin), None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, body) = app.send("GET", "/items", Some(new_key), None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["code"], "api_key_revoked");
+
+        let (status, _) = app
+            .send("DELETE", "/keys/unknown", Some(&app.admin), None)
+            .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
+
+// 🧪 테스트 방법 (bash)
+//
+// 서버 로그의 bootstrap admin key를 ADMIN에 넣고:
+//
+// curl -X POST http://localhost:3000/keys \
+//   -H "X-Api-Key: $ADMIN" -H 'Content-Type: application/json' \
+//   -d '{"name":"ci","scopes":["items:read","items:write"]}'
+// → 201, 응답의 "key"를 KEY에 저장 (다시 볼 수 없음)
+//
+// curl -X POST http://localhost:3000/items \
+//   -H "X-Api-Key: $KEY" -H 'Content-Type: application/json' -d '{"name":"first"}'
+// curl http://localhost:3000/items -H "X-Api-Key: $KEY"
+//
+// curl -X POST http://localhost:3000/keys/<id>/rotate -H "X-Api-Key: $ADMIN"
+// curl -X DELETE http://localhost:3000/keys/<id> -H "X-Api-Key: $ADMIN"
@@ -0,0 +1,150 @@
+//! 👤 계정 저장소 + 비밀번호 해시
+//!
+//! - 비밀번호는 argon2로 해시해서 저장 (salt 포함 PHC 문자열)
+//! - 해시 계산은 일부러 느리므로 핸들러에서는 `spawn_blocking`으로 호출
+//! - 예제라서 메모리에 보관, 실서비스에서는 DB에 저장
+
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, RwLock},
+};
+
+pub const MIN_PASSWORD_LEN: usize = 8;
+pub const MAX_PASSWORD_LEN: usize = 128;
+
+#[derive(Debug, Clone)]
+pub struct Account {
+    pub email: String,
+    password_hash: String,
+    pub verified: bool,
+    /// 비밀번호를 바꿀 때마다 증가 → 이전 버전으로 발급된 재설정 링크는 무효
+    pub password_version: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountError {
+    AlreadyExists,
+    /// 없는 계정이거나 비밀번호가 틀림 (구분하지 않음)
+    InvalidCredentials,
+    NotVerified,
+}
+
+#[derive(Default)]
+pub struct Accounts {
+    accounts: RwLock<HashMap<String, Account>>,
+}
+
+impl Accounts {
+    pub fn create(&self, email: &str, password_hash: String) -> Result<(), AccountError> {
+        let mut accounts = self.accounts.write().unwrap();
+        if accounts.contains_key(email) {
+            return Err(AccountError::AlreadyExists);
+        }
+        accounts.insert(
+            email.to_owned(),
+            Account {
+                email: email.to_owned(),
+                password_hash,
+                verified: false,
+                password_version: 0,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn get(&self, email: &str) -> Option<Account> {
+        self.accounts.read().unwrap().get(email).cloned()
+    }
+
+    /// 없는 계정이면 `false`
+    pub fn mark_verified(&self, email: &str) -> bool {
+        match self.accounts.write().unwrap().get_mut(email) {
+            Some(account) => {
+                account.verified = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 없는 계정이면 `false`
+    pub fn set_password(&self, email: &str, password_hash: String) -> bool {
+        match self.accounts.write().unwrap().get_mut(email) {
+            Some(account) => {
+                account.password_hash = password_hash;
+                account.password_version += 1;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// 로그인 확인 (CPU를 많이 쓰므로 `spawn_blocking`에서 호출)
+///
+/// 없는 계정이어도 더미 해시로 같은 계산을 수행 → 응답 시간으로 가입 여부를 알 수 없게 함
+pub fn authenticate(account: Option<Account>, password: &str) -> Result<Account, AccountError> {
+    let hash = match &account {
+        Some(account) => account.password_hash.as_str(),
+        None => dummy_hash(),
+    };
+    let matches = verify_password(hash, password);
+    let account = account
+        .filter(|_| matches)
+        .ok_or(AccountError::InvalidCredentials)?;
+    if !account.verified {
+        return Err(AccountError::NotVerified);
+    }
+    Ok(account)
+}
+
+/// 비밀번호 해시 (CPU를 많이 쓰므로 `spawn_blocking`에서 호출)
+pub fn hash_password(password: &str) -> String {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .expect("argon2 hashing with default params cannot fail")
+        .to_string()
+}
+
+fn verify_password(hash: &str, password: &str) -> bool {
+    PasswordHash::new(hash).is_ok_and(|hash| {
+        Argon2::default()
+            .verify_password(password.as_bytes(), &hash)
+            .is_ok()
+    })
+}
+
+fn dummy_hash() -> &'static str {
+    static DUMMY: OnceLock<String> = OnceLock::new();
+    DUMMY.get_or_init(|| hash_password("dummy password"))
+}
+
+/// 앞뒤 공백을 지우고 소문자로 바꾼 이메일, 형식이 틀리면 `None`
+///
+/// 실제로 받을 수 있는 주소인지는 인증 메일로 확인하므로 여기서는 최소한만 검사
+pub fn normalize_email(email: &str) -> Option<String> {
+    let email = email.trim().to_lowercase();
+    let (local, domain) = email.split_once('@')?;
+    let valid = !local.is_empty()
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !domain.contains('@')
+        && email.len() <= 254
+        && !email.chars().any(|c| c.is_whitespace() || c.is_control());
+    valid.then_some(email)
+}
+
+/// 비밀번호 길이 검사, 틀리면 사용자에게 보여줄 메시지
+pub fn validate_password(password: &str) -> Result<(), &'static str> {
+    match password.chars().count() {
+        n if n < MIN_PASSWORD_LEN => Err("password must be at least 8 characters"),
+        n if n > MAX_PASSWORD_LEN => Err("password must be at most 128 characters"),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,78 @@
+//! ✉️ 메일 발송 (구현을 바꿔 끼울 수 있는 `Mailer` 트레이트)
+//!
+//! - `LogMailer`: 로그로만 출력 (개발용 기본값, 로그에서 링크를 복사해 사용)
+//! - `SmtpMailer`: SMTP 서버로 발송 (`--features smtp`)
+//! - 테스트에서는 보낸 메일을 채널로 받는 구현을 사용 (`main.rs`의 tests 참고)
+
+use std::future::Future;
+
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Debug, Clone)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+pub trait Mailer: Clone + Send + Sync + 'static {
+    fn send(&self, email: Email) -> impl Future<Output = Result<(), BoxError>> + Send;
+}
+
+#[derive(Clone)]
+pub struct LogMailer;
+
+impl Mailer for LogMailer {
+    async fn send(&self, email: Email) -> Result<(), BoxError> {
+        tracing::info!(to = email.to, subject = email.subject, "📧\n{}", email.body);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "smtp")]
+pub use smtp::SmtpMailer;
+
+#[cfg(feature = "smtp")]
+mod smtp {
+    use super::{BoxError, Email, Mailer};
+    use lettre::{
+        message::Mailbox, transport::smtp::authentication::Credentials, AsyncSmtpTransport,
+        AsyncTransport, Message, Tokio1Executor,
+    };
+
+    /// STARTTLS로 SMTP 서버에 접속해서 발송
+    #[derive(Clone)]
+    pub struct SmtpMailer {
+        transport: AsyncSmtpTransport<Tokio1Executor>,
+        from: Mailbox,
+    }
+
+    impl SmtpMailer {
+        pub fn new(
+            host: &str,
+            credentials: Option<(String, String)>,
+            from: &str,
+        ) -> Result<Self, BoxError> {
+            let mut builder = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)?;
+            if let Some((username, password)) = credentials {
+                builder = builder.credentials(Credentials::new(username, password));
+            }
+            Ok(Self {
+                transport: builder.build(),
+                from: from.parse()?,
+            })
+        }
+    }
+
+    impl Mailer for SmtpMailer {
+        async fn send(&self, email: Email) -> Result<(), BoxError> {
+            let message = Message::builder()
+                .from(self.from.clone())
+                .to(email.to.parse()?)
+                .subject(email.subject)
+                .body(email.body)?;
+            self.transport.send(message).await?;
+            Ok(())
+        }
+    }
+}
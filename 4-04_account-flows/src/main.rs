@@ -0,0 +1,770 @@
+//! 📬 회원가입 → 이메일 인증, 비밀번호 재설정 예제
+//!
+//! ```not_rust
+//! cargo run -p example-account-flows
+//! # SMTP로 실제 발송 (SMTP_HOST, SMTP_USERNAME, SMTP_PASSWORD, MAIL_FROM)
+//! cargo run -p example-account-flows --features smtp
+//! ```
+//!
+//! | 라우트                      | 설명                                         |
+//! |-----------------------------|----------------------------------------------|
+//! | `POST /signup`              | 계정 생성 + 인증 메일 발송                   |
+//! | `POST /verify/resend`       | 인증 메일 다시 보내기                        |
+//! | `GET /verify?token=`        | 메일의 인증 링크 (결과 페이지)               |
+//! | `POST /password/forgot`     | 재설정 메일 발송                             |
+//! | `GET /password/reset?token=`| 메일의 재설정 링크 (새 비밀번호 입력 폼)     |
+//! | `POST /password/reset`      | 폼 제출 → 비밀번호 변경                      |
+//! | `POST /login`               | 인증된 계정만 로그인 가능                    |
+//!
+//! • 링크의 토큰은 서명된 일회용 토큰 (`tokens.rs`)
+//!   → 재설정 링크는 비밀번호가 바뀌면 아직 쓰지 않은 링크도 무효
+//! • 이메일 주소마다 발송 횟수 제한 (`rate_limit.rs`)
+//! • 재발송/비밀번호 찾기는 계정이 있든 없든 같은 응답(202) → 가입 여부 노출 방지
+//! • 메일 발송 방식은 `Mailer` 구현으로 교체 (`mailer.rs`)
+
+mod accounts;
+mod mailer;
+mod rate_limit;
+mod tokens;
+
+use accounts::{normalize_email, AccountError, Accounts};
+use axum::{
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{Html, IntoResponse, Response},
+    routing::{get, post},
+    Form, Json, Router,
+};
+use mailer::{Email, LogMailer, Mailer};
+use minijinja::{context, Environment};
+use rate_limit::EmailRateLimiter;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::{
+    env,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokens::{Claims, Purpose, TokenError, TokenSigner};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+/// 인증 링크 유효 기간
+const VERIFY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// 재설정 링크 유효 기간 (계정 탈취에 쓰일 수 있으므로 짧게)
+const RESET_TTL: Duration = Duration::from_secs(30 * 60);
+/// 주소마다 `MAIL_WINDOW` 동안 보낼 수 있는 메일 수
+const MAILS_PER_WINDOW: usize = 3;
+const MAIL_WINDOW: Duration = Duration::from_secs(15 * 60);
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    // 토큰 서명 키 (여러 서버가 같은 링크를 처리하려면 같은 키를 사용)
+    let tokens = match env::var("TOKEN_SECRET") {
+        Ok(secret) => TokenSigner::new(secret),
+        Err(_) => {
+            tracing::warn!("TOKEN_SECRET is not set, links will stop working after a restart");
+            TokenSigner::random()
+        }
+    };
+    // 메일 속 링크의 주소
+    let base_url = env::var("BASE_URL").unwrap_or_else(|_| "http://127.0.0.1:3000".to_owned());
+
+    #[cfg(feature = "smtp")]
+    if let Ok(host) = env::var("SMTP_HOST") {
+        let credentials = env::var("SMTP_USERNAME")
+            .ok()
+            .zip(env::var("SMTP_PASSWORD").ok());
+        let from = env::var("MAIL_FROM").expect("MAIL_FROM is required with SMTP_HOST");
+        let mailer =
+            mailer::SmtpMailer::new(&host, credentials, &from).expect("invalid SMTP configuration");
+        return serve(AppState::new(mailer, tokens, &base_url)).await;
+    }
+
+    serve(AppState::new(LogMailer, tokens, &base_url)).await;
+}
+
+async fn serve<M: Mailer>(state: AppState<M>) {
+    // 만료된 토큰 사용 기록과 발송 기록 정리
+    tokio::spawn({
+        let state = state.clone();
+        async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                let now = unix_now();
+                state.tokens.purge_used(now);
+                state.limiter.purge(now);
+            }
+        }
+    });
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(state)).await.unwrap();
+}
+
+fn app<M: Mailer>(state: AppState<M>) -> Router {
+    Router::new()
+        .route("/signup", post(signup::<M>))
+        .route("/login", post(login::<M>))
+        .route("/verify", get(verify_email::<M>))
+        .route("/verify/resend", post(resend_verification::<M>))
+        .route("/password/forgot", post(forgot_password::<M>))
+        .route(
+            "/password/reset",
+            get(reset_form::<M>).post(reset_password::<M>),
+        )
+        .with_state(state)
+}
+
+#[derive(Clone)]
+struct AppState<M> {
+    accounts: Arc<Accounts>,
+    tokens: Arc<TokenSigner>,
+    limiter: Arc<EmailRateLimiter>,
+    templates: Arc<Environment<'static>>,
+    mailer: M,
+    base_url: Arc<str>,
+}
+
+impl<M: Mailer> AppState<M> {
+    fn new(mailer: M, tokens: TokenSigner, base_url: &str) -> Self {
+        Self {
+            accounts: Default::default(),
+            tokens: Arc::new(tokens),
+            limiter: Arc::new(EmailRateLimiter::new(MAILS_PER_WINDOW, MAIL_WINDOW)),
+            templates: Arc::new(templates()),
+            mailer,
+            base_url: base_url.trim_end_matches('/').into(),
+        }
+    }
+}
+
+// .html 템플릿은 자동 이스케이프, .txt(메일 본문)는 그대로
+fn templates() -> Environment<'static> {
+    let mut env = Environment::new();
+    for (name, source) in [
+        ("layout.html", include_str!("../templates/layout.html")),
+        ("message.html", include_str!("../templates/message.html")),
+        ("reset.html", include_str!("../templates/reset.html")),
+        (
+            "verify_email.txt",
+            include_str!("../templates/verify_email.txt"),
+        ),
+        (
+            "reset_email.txt",
+            include_str!("../templates/reset_email.txt"),
+        ),
+    ] {
+        env.add_template(name, source).unwrap();
+    }
+    env
+}
+
+// --- 📮 JSON API
+
+#[derive(Deserialize)]
+struct Credentials {
+    email: String,
+    password: String,
+}
+
+#[derive(Deserialize)]
+struct EmailRequest {
+    email: String,
+}
+
+async fn signup<M: Mailer>(
+    State(state): State<AppState<M>>,
+    Json(input): Json<Credentials>,
+) -> Result<(StatusCode, Json<Value>), AppError> {
+    let email =
+        normalize_email(&input.email).ok_or(AppError::Validation("invalid email address"))?;
+    accounts::validate_password(&input.password).map_err(AppError::Validation)?;
+
+    let hash = hash_password(input.password).await;
+    state.accounts.create(&email, hash)?;
+    tracing::info!(email, "account created");
+
+    // 발송 제한에 걸려도 가입은 성공 (나중에 `/verify/resend`로 다시 받음)
+    if let Err(err) = send_verification(&state, &email) {
+        tracing::warn!(email, ?err, "verification email not sent");
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({ "email": email, "verified": false })),
+    ))
+}
+
+async fn resend_verification<M: Mailer>(
+    State(state): State<AppState<M>>,
+    Json(input): Json<EmailRequest>,
+) -> Result<StatusCode, AppError> {
+    let email =
+        normalize_email(&input.email).ok_or(AppError::Validation("invalid email address"))?;
+    match state.accounts.get(&email) {
+        Some(account) if !account.verified => send_verification(&state, &email)?,
+        // 없는 계정이나 이미 인증된 계정도 제한은 똑같이 적용 → 429 여부로 구분할 수 없음
+        _ => check_rate_limit(&state, &email)?,
+    }
+    Ok(StatusCode::ACCEPTED)
+}
+
+async fn forgot_password<M: Mailer>(
+    State(state): State<AppState<M>>,
+    Json(input): Json<EmailRequest>,
+) -> Result<StatusCode, AppError> {
+    let email =
+        normalize_email(&input.email).ok_or(AppError::Validation("invalid email address"))?;
+    check_rate_limit(&state, &email)?;
+
+    if let Some(account) = state.accounts.get(&email) {
+        let token = state.tokens.issue(
+            Purpose::ResetPassword,
+            &email,
+            account.password_version,
+            RESET_TTL,
+            unix_now(),
+        );
+        let body = state
+            .templates
+            .get_template("reset_email.txt")?
+            .render(context! {
+                link => format!("{}/password/reset?token={token}", state.base_url),
+                minutes => RESET_TTL.as_secs() / 60,
+            })?;
+        deliver(&state.mailer, email, "Reset your password", body);
+    }
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// 실제 서비스라면 여기서 세션이나 JWT를 발급 (4-01_jwt, 4-02_oauth 참고)
+async fn login<M: Mailer>(
+    State(state): State<AppState<M>>,
+    Json(input): Json<Credentials>,
+) -> Result<Json<Value>, AppError> {
+    let account = normalize_email(&input.email).and_then(|email| state.accounts.get(&email));
+    let account =
+        tokio::task::spawn_blocking(move || accounts::authenticate(account, &input.password))
+            .await
+            .expect("password verification panicked")?;
+    Ok(Json(json!({ "email": account.email })))
+}
+
+// --- 🔗 메일 링크로 여는 페이지
+
+#[derive(Deserialize)]
+struct TokenQuery {
+    token: String,
+}
+
+async fn verify_email<M: Mailer>(
+    State(state): State<AppState<M>>,
+    Query(query): Query<TokenQuery>,
+) -> Result<(StatusCode, Html<String>), AppError> {
+    let claims = match state
+        .tokens
+        .redeem(Purpose::VerifyEmail, &query.token, unix_now())
+    {
+        Ok(claims) => claims,
+        Err(err) => return link_error(&state, err),
+    };
+    if !state.accounts.mark_verified(&claims.email) {
+        return link_error(&state, TokenError::Invalid);
+    }
+    tracing::info!(email = claims.email, "email verified");
+
+    message_page(
+        &state,
+        StatusCode::OK,
+        "Email verified",
+        "Thanks! Your email address is confirmed and you can now log in.",
+    )
+}
+
+async fn reset_form<M: Mailer>(
+    State(state): State<AppState<M>>,
+    Query(query): Query<TokenQuery>,
+) -> Result<(StatusCode, Html<String>), AppError> {
+    match reset_claims(&state, &query.token, false) {
+        Ok(claims) => reset_page(&state, StatusCode::OK, &claims.email, &query.token, None),
+        Err(err) => link_error(&state, err),
+    }
+}
+
+#[derive(Deserialize)]
+struct ResetForm {
+    token: String,
+    password: String,
+}
+
+async fn reset_password<M: Mailer>(
+    State(state): State<AppState<M>>,
+    Form(form): Form<ResetForm>,
+) -> Result<(StatusCode, Html<String>), AppError> {
+    let claims = match reset_claims(&state, &form.token, false) {
+        Ok(claims) => claims,
+        Err(err) => return link_error(&state, err),
+    };
+    // 입력이 틀리면 토큰을 쓰지 않고 폼을 다시 보여줌
+    if let Err(error) = accounts::validate_password(&form.password) {
+        let status = StatusCode::UNPROCESSABLE_ENTITY;
+        return reset_page(&state, status, &claims.email, &form.token, Some(error));
+    }
+    let hash = hash_password(form.password).await;
+
+    // 여기서 토큰을 사용 처리 → 같은 링크로 동시에 제출해도 한 번만 바뀜
+    if let Err(err) = reset_claims(&state, &form.token, true) {
+        return link_error(&state, err);
+    }
+    state.accounts.set_password(&claims.email, hash);
+    // 메일로 받은 링크를 열었으므로 주소도 확인된 것
+    state.accounts.mark_verified(&claims.email);
+    tracing::info!(email = claims.email, "password reset");
+
+    message_page(
+        &state,
+        StatusCode::OK,
+        "Password updated",
+        "Your password has been changed. You can now log in with the new password.",
+    )
+}
+
+// 재설정 토큰 확인 (`redeem`이면 사용 처리까지)
+// 링크를 보낸 뒤 비밀번호가 바뀌었으면(다른 링크로 재설정 등) 아직 쓰지 않은 링크도 무효
+fn reset_claims<M>(state: &AppState<M>, token: &str, redeem: bool) -> Result<Claims, TokenError> {
+    let now = unix_now();
+    let claims = if redeem {
+        state.tokens.redeem(Purpose::ResetPassword, token, now)?
+    } else {
+        state.tokens.verify(Purpose::ResetPassword, token, now)?
+    };
+    match state.accounts.get(&claims.email) {
+        Some(account) if account.password_version == claims.stamp => Ok(claims),
+        Some(_) => Err(TokenError::AlreadyUsed),
+        None => Err(TokenError::Invalid),
+    }
+}
+
+// --- 🧰 helpers
+
+fn send_verification<M: Mailer>(state: &AppState<M>, email: &str) -> Result<(), AppError> {
+    check_rate_limit(state, email)?;
+    let token = state
+        .tokens
+        .issue(Purpose::VerifyEmail, email, 0, VERIFY_TTL, unix_now());
+    let body = state
+        .templates
+        .get_template("verify_email.txt")?
+        .render(context! {
+            link => format!("{}/verify?token={token}", state.base_url),
+            hours => VERIFY_TTL.as_secs() / 3600,
+        })?;
+    deliver(
+        &state.mailer,
+        email.to_owned(),
+        "Confirm your email address",
+        body,
+    );
+    Ok(())
+}
+
+fn check_rate_limit<M>(state: &AppState<M>, email: &str) -> Result<(), AppError> {
+    state
+        .limiter
+        .check(email, unix_now())
+        .map_err(AppError::RateLimited)
+}
+
+// 발송은 백그라운드에서 → SMTP가 느려도 응답이 늦어지지 않고, 응답 시간으로 가입 여부를 알 수 없음
+fn deliver<M: Mailer>(mailer: &M, to: String, subject: &str, body: String) {
+    let mailer = mailer.clone();
+    let email = Email {
+        to,
+        subject: subject.to_owned(),
+        body,
+    };
+    tokio::spawn(async move {
+        let to = email.to.clone();
+        if let Err(err) = mailer.send(email).await {
+            tracing::error!(to, %err, "failed to send email");
+        }
+    });
+}
+
+async fn hash_password(password: String) -> String {
+    tokio::task::spawn_blocking(move || accounts::hash_password(&password))
+        .await
+        .expect("password hashing panicked")
+}
+
+fn message_page<M>(
+    state: &AppState<M>,
+    status: StatusCode,
+    title: &str,
+    message: &str,
+) -> Result<(StatusCode, Html<String>), AppError> {
+    let html = state
+        .templates
+        .get_template("message.html")?
+        .render(context! {
+            title,
+            message,
+            error => !status.is_success(),
+        })?;
+    Ok((status, Html(html)))
+}
+
+fn link_error<M>(
+    state: &AppState<M>,
+    err: TokenError,
+) -> Result<(StatusCode, Html<String>), AppError> {
+    message_page(
+        state,
+        StatusCode::BAD_REQUEST,
+        "Link not valid",
+        err.message(),
+    )
+}
+
+fn reset_page<M>(
+    state: &AppState<M>,
+    status: StatusCode,
+    email: &str,
+    token: &str,
+    error: Option<&str>,
+) -> Result<(StatusCode, Html<String>), AppError> {
+    let html = state
+        .templates
+        .get_template("reset.html")?
+        .render(context! {
+            email,
+            token,
+            error,
+            min_len => accounts::MIN_PASSWORD_LEN,
+        })?;
+    Ok((status, Html(html)))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+// --- 🚨 에러
+
+#[derive(Debug)]
+enum AppError {
+    Validation(&'static str),
+    Account(AccountError),
+    /// 다시 보낼 수 있을 때까지 남은 초
+    RateLimited(u64),
+    Template(minijinja::Error),
+}
+
+impl From<AccountError> for AppError {
+    fn from(err: AccountError) -> Self {
+        Self::Account(err)
+    }
+}
+
+impl From<minijinja::Error> for AppError {
+    fn from(err: minijinja::Error) -> Self {
+        Self::Template(err)
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Validation(message) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, message).into_response()
+            }
+            Self::Account(AccountError::AlreadyExists) => (
+                StatusCode::CONFLICT,
+                "an account with this email already exists",
+            )
+                .into_response(),
+            Self::Account(AccountError::InvalidCredentials) => {
+                (StatusCode::UNAUTHORIZED, "invalid email or password").into_response()
+            }
+            Self::Account(AccountError::NotVerified) => {
+                (StatusCode::FORBIDDEN, "email address is not verified").into_response()
+            }
+            Self::RateLimited(retry_after) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, retry_after.to_string())],
+                "too many emails sent to this address, try again later",
+            )
+                .into_response(),
+            Self::Template(err) => {
+                tracing::error!(%err, "template rendering failed");
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use http_body_util::BodyExt;
+    use tokio::sync::mpsc;
+    use tower::ServiceExt;
+
+    /// 보낸 메일을 채널로 넘기는 테스트용 Mailer
+    #[derive(Clone)]
+    struct TestMailer(mpsc::UnboundedSender<Email>);
+
+    impl Mailer for TestMailer {
+        async fn send(&self, email: Email) -> Result<(), mailer::BoxError> {
+            self.0.send(email)?;
+            Ok(())
+        }
+    }
+
+    struct TestApp {
+        router: Router,
+        outbox: mpsc::UnboundedReceiver<Email>,
+    }
+
+    impl TestApp {
+        fn new() -> Self {
+            let (tx, outbox) = mpsc::unbounded_channel();
+            let state = AppState::new(
+                TestMailer(tx),
+                TokenSigner::new("test secret"),
+                "http://localhost/",
+            );
+            Self {
+                router: app(state),
+                outbox,
+            }
+        }
+
+        async fn send(&self, request: Request<Body>) -> (StatusCode, String) {
+            let response = self.router.clone().oneshot(request).await.unwrap();
+            let status = response.status();
+            let bytes = response.into_body().collect().await.unwrap().to_bytes();
+            (status, String::from_utf8(bytes.to_vec()).unwrap())
+        }
+
+        async fn json(&self, uri: &str, body: Value) -> (StatusCode, String) {
+            self.send(
+                Request::post(uri)
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+        }
+
+        async fn get(&self, uri: &str) -> (StatusCode, String) {
+            self.send(Request::get(uri).body(Body::empty()).unwrap())
+                .await
+        }
+
+        async fn reset(&self, token: &str, password: &str) -> (StatusCode, String) {
+            self.send(
+                Request::post("/password/reset")
+                    .header("content-type", "application/x-www-form-urlencoded")
+                    .body(Body::from(format!("token={token}&password={password}")))
+                    .unwrap(),
+            )
+            .await
+        }
+
+        /// 다음 메일의 링크 경로 (`/verify?token=...`)
+        async fn next_link(&mut self, to: &str) -> String {
+            let email = self.outbox.recv().await.unwrap();
+            assert_eq!(email.to, to);
+            let link = email
+                .body
+                .split_whitespace()
+                .find(|word| word.contains("token="))
+                .unwrap();
+            link.strip_prefix("http://localhost").unwrap().to_owned()
+        }
+
+        async fn signup_verified(&mut self, email: &str, password: &str) {
+            let (status, _) = self
+                .json("/signup", json!({ "email": email, "password": password }))
+                .await;
+            assert_eq!(status, StatusCode::CREATED);
+            let link = self.next_link(email).await;
+            assert_eq!(self.get(&link).await.0, StatusCode::OK);
+        }
+    }
+
+    #[tokio::test]
+    async fn signup_requires_email_verification_before_login() {
+        let mut app = TestApp::new();
+        let credentials = json!({ "email": " Alice@Example.com ", "password": "correct horse" });
+
+        let (status, body) = app.json("/signup", credentials.clone()).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body, r#"{"email":"alice@example.com","verified":false}"#);
+        assert_eq!(
+            app.json("/signup", credentials.clone()).await.0,
+            StatusCode::CONFLICT
+        );
+
+        let (status, body) = app.json("/login", credentials.clone()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body, "email address is not verified");
+
+        let link = app.next_link("alice@example.com").await;
+        assert!(link.starts_with("/verify?token="));
+        let (status, page) = app.get(&link).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(page.contains("<h1>Email verified</h1>"));
+
+        // 링크는 한 번만 사용 가능
+        let (status, page) = app.get(&link).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(page.contains("This link has already been used."));
+
+        assert_eq!(app.json("/login", credentials).await.0, StatusCode::OK);
+        let wrong = json!({ "email": "alice@example.com", "password": "wrong password" });
+        assert_eq!(app.json("/login", wrong).await.0, StatusCode::UNAUTHORIZED);
+        let unknown = json!({ "email": "bob@example.com", "password": "correct horse" });
+        assert_eq!(
+            app.json("/login", unknown).await.0,
+            StatusCode::UNAUTHORIZED
+        );
+
+        let invalid = json!({ "email": "not-an-email", "password": "correct horse" });
+        assert_eq!(
+            app.json("/signup", invalid).await.0,
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        let short = json!({ "email": "carol@example.com", "password": "short" });
+        assert_eq!(
+            app.json("/signup", short).await.0,
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+
+        // 이미 인증된 계정에는 다시 보내지 않음
+        let resend = json!({ "email": "alice@example.com" });
+        assert_eq!(
+            app.json("/verify/resend", resend).await.0,
+            StatusCode::ACCEPTED
+        );
+        assert!(app.outbox.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn password_reset_links_work_once_and_older_links_are_invalidated() {
+        let mut app = TestApp::new();
+        app.signup_verified("alice@example.com", "old password")
+            .await;
+
+        let forgot = json!({ "email": "alice@example.com" });
+        assert_eq!(
+            app.json("/password/forgot", forgot.clone()).await.0,
+            StatusCode::ACCEPTED
+        );
+        let first = app.next_link("alice@example.com").await;
+        app.json("/password/forgot", forgot).await;
+        let second = app.next_link("alice@example.com").await;
+
+        let (status, page) = app.get(&first).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(page.contains("Choose a new password for alice@example.com."));
+
+        // 입력이 틀리면 폼을 다시 보여주고 링크는 그대로 사용 가능
+        let token = first.strip_prefix("/password/reset?token=").unwrap();
+        let (status, page) = app.reset(token, "short").await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(page.contains("password must be at least 8 characters"));
+
+        let (status, page) = app.reset(token, "new password").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(page.contains("<h1>Password updated</h1>"));
+
+        let old = json!({ "email": "alice@example.com", "password": "old password" });
+        assert_eq!(app.json("/login", old).await.0, StatusCode::UNAUTHORIZED);
+        let new = json!({ "email": "alice@example.com", "password": "new password" });
+        assert_eq!(app.json("/login", new).await.0, StatusCode::OK);
+
+        // 사용한 링크도, 비밀번호가 바뀌기 전에 받은 링크도 무효
+        assert_eq!(
+            app.reset(token, "another password").await.0,
+            StatusCode::BAD_REQUEST
+        );
+        let (status, page) = app.get(&second).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(page.contains("This link has already been used."));
+
+        // 서명이 없는 토큰
+        let (status, page) = app.get("/password/reset?token=garbage").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(page.contains("This link is invalid."));
+    }
+
+    #[tokio::test]
+    async fn emails_are_rate_limited_per_address_without_revealing_accounts() {
+        let mut app = TestApp::new();
+        app.signup_verified("alice@example.com", "password one")
+            .await;
+
+        // 가입 메일 1통 + 재설정 2통 = 3통
+        let alice = json!({ "email": "alice@example.com" });
+        for _ in 0..2 {
+            assert_eq!(
+                app.json("/password/forgot", alice.clone()).await.0,
+                StatusCode::ACCEPTED
+            );
+            app.next_link("alice@example.com").await;
+        }
+        assert_eq!(
+            app.json("/password/forgot", alice).await.0,
+            StatusCode::TOO_MANY_REQUESTS
+        );
+
+        // 없는 계정도 같은 응답, 같은 제한 (메일은 보내지 않음)
+        let nobody = json!({ "email": "nobody@example.com" });
+        for _ in 0..MAILS_PER_WINDOW {
+            assert_eq!(
+                app.json("/password/forgot", nobody.clone()).await.0,
+                StatusCode::ACCEPTED
+            );
+        }
+        assert_eq!(
+            app.json("/password/forgot", nobody).await.0,
+            StatusCode::TOO_MANY_REQUESTS
+        );
+        assert!(app.outbox.try_recv().is_err());
+    }
+}
+
+// 🧪 테스트 방법
+//
+// cargo test -p example-account-flows
+//
+// curl -i -X POST http://127.0.0.1:3000/signup \
+//   -H 'content-type: application/json' \
+//   -d '{"email":"alice@example.com","password":"correct horse"}'
+// # 서버 로그에 찍힌 인증 링크를 브라우저로 열기 → 로그인 가능
+//
+// curl -i -X POST http://127.0.0.1:3000/login \
+//   -H 'content-type: application/json' \
+//   -d '{"email":"alice@example.com","password":"correct horse"}'
+//
+// curl -i -X POST http://127.0.0.1:3000/password/forgot \
+//   -H 'content-type: application/json' -d '{"email":"alice@example.com"}'
+// # 로그의 재설정 링크를 열어 새 비밀번호 입력, 4번째 요청부터는 429
@@ -0,0 +1,78 @@
+//! ⏱️ 이메일 주소별 발송 제한 (sliding window)
+//!
+//! 재발송/비밀번호 찾기 버튼을 반복해서 누르거나, 남의 주소로 메일 폭탄을 보내는 것을 막음
+//! 계정이 없는 주소도 똑같이 제한 → 429 응답으로 가입 여부를 알 수 없음
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::Duration,
+};
+
+pub struct EmailRateLimiter {
+    max: usize,
+    window: Duration,
+    // 주소 → window 안에서 보낸 시각들 (오래된 순)
+    sent: Mutex<HashMap<String, VecDeque<u64>>>,
+}
+
+impl EmailRateLimiter {
+    pub fn new(max: usize, window: Duration) -> Self {
+        Self {
+            max,
+            window,
+            sent: Mutex::default(),
+        }
+    }
+
+    /// 보낼 수 있으면 기록하고 `Ok`, 아니면 다시 시도할 수 있을 때까지 남은 초
+    pub fn check(&self, email: &str, now: u64) -> Result<(), u64> {
+        let window = self.window.as_secs();
+        let mut sent = self.sent.lock().unwrap();
+        let times = sent.entry(email.to_owned()).or_default();
+        while times.front().is_some_and(|&at| at + window <= now) {
+            times.pop_front();
+        }
+
+        if times.len() >= self.max {
+            // 가장 오래된 발송이 window를 벗어나는 시각까지
+            return Err(times[0] + window - now);
+        }
+        times.push_back(now);
+        Ok(())
+    }
+
+    /// window가 지난 주소를 지움
+    pub fn purge(&self, now: u64) {
+        let window = self.window.as_secs();
+        self.sent
+            .lock()
+            .unwrap()
+            .retain(|_, times| times.back().is_some_and(|&at| at + window > now));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_each_address_within_the_window() {
+        let limiter = EmailRateLimiter::new(2, Duration::from_secs(60));
+
+        assert_eq!(limiter.check("a@example.com", 0), Ok(()));
+        assert_eq!(limiter.check("a@example.com", 10), Ok(()));
+        assert_eq!(limiter.check("a@example.com", 20), Err(40));
+        // 다른 주소는 따로 셈
+        assert_eq!(limiter.check("b@example.com", 20), Ok(()));
+
+        // 첫 발송이 window를 벗어나면 하나 더 보낼 수 있음
+        assert_eq!(limiter.check("a@example.com", 60), Ok(()));
+        assert_eq!(limiter.check("a@example.com", 61), Err(9));
+
+        limiter.purge(80);
+        assert_eq!(limiter.sent.lock().unwrap().len(), 1);
+        limiter.purge(121);
+        assert!(limiter.sent.lock().unwrap().is_empty());
+    }
+}
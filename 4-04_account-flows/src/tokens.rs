@@ -0,0 +1,240 @@
+//! 🎟️ 서명된 일회용 토큰 (이메일 인증 / 비밀번호 재설정 링크)
+//!
+//! 형식: `<payload>.<signature>` (둘 다 base64url)
+//! - payload: 용도, 이메일, 계정 상태 값, 만료 시각, nonce를 담은 JSON
+//! - signature: 서버 비밀 키로 만든 HMAC-SHA256 → 토큰을 저장하지 않아도 위조/변조를 막을 수 있음
+//!
+//! 서명만으로는 같은 링크를 여러 번 쓰는 것을 막을 수 없으므로,
+//! 사용한 토큰의 nonce를 만료 시각까지 기록해 두고 두 번째 사용은 거부함 (`redeem`)
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 토큰 용도 (인증 링크를 재설정 링크로 쓰는 것을 막음)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Purpose {
+    VerifyEmail,
+    ResetPassword,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub purpose: Purpose,
+    pub email: String,
+    /// 발급할 때의 계정 상태 (재설정 링크는 비밀번호 버전 → 비밀번호가 바뀌면 무효로 처리)
+    pub stamp: u32,
+    pub expires_at: u64,
+    nonce: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenError {
+    /// 형식, 서명, 용도 중 하나가 틀림 (구분하지 않음)
+    Invalid,
+    Expired,
+    AlreadyUsed,
+}
+
+impl TokenError {
+    /// 링크를 연 사용자에게 보여줄 메시지
+    pub fn message(self) -> &'static str {
+        match self {
+            Self::Invalid => "This link is invalid.",
+            Self::Expired => "This link has expired. Please request a new one.",
+            Self::AlreadyUsed => "This link has already been used.",
+        }
+    }
+}
+
+pub struct TokenSigner {
+    key: Vec<u8>,
+    // 사용한 토큰의 nonce → 만료 시각 (만료된 토큰은 서명 검사에서 걸러지므로 그 뒤엔 지워도 됨)
+    used: Mutex<HashMap<String, u64>>,
+}
+
+impl TokenSigner {
+    pub fn new(key: impl Into<Vec<u8>>) -> Self {
+        Self {
+            key: key.into(),
+            used: Mutex::default(),
+        }
+    }
+
+    /// 임의의 키로 생성 (서버를 재시작하면 이전에 보낸 링크는 모두 무효)
+    pub fn random() -> Self {
+        let mut key = [0; 32];
+        rand::thread_rng().fill_bytes(&mut key);
+        Self::new(key)
+    }
+
+    pub fn issue(
+        &self,
+        purpose: Purpose,
+        email: &str,
+        stamp: u32,
+        ttl: Duration,
+        now: u64,
+    ) -> String {
+        let mut nonce = [0; 16];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let claims = Claims {
+            purpose,
+            email: email.to_owned(),
+            stamp,
+            expires_at: now + ttl.as_secs(),
+            nonce: URL_SAFE_NO_PAD.encode(nonce),
+        };
+
+        let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&claims).unwrap());
+        let signature = URL_SAFE_NO_PAD.encode(self.sign(&payload));
+        format!("{payload}.{signature}")
+    }
+
+    /// 서명, 용도, 만료를 확인 (사용 처리는 하지 않음 → 재설정 폼을 보여줄 때 사용)
+    pub fn verify(&self, purpose: Purpose, token: &str, now: u64) -> Result<Claims, TokenError> {
+        let (payload, signature) = token.split_once('.').ok_or(TokenError::Invalid)?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| TokenError::Invalid)?;
+
+        // verify_slice는 상수 시간 비교
+        let mut mac = self.mac();
+        mac.update(payload.as_bytes());
+        mac.verify_slice(&signature)
+            .map_err(|_| TokenError::Invalid)?;
+
+        let claims: Claims = URL_SAFE_NO_PAD
+            .decode(payload)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .ok_or(TokenError::Invalid)?;
+        if claims.purpose != purpose {
+            return Err(TokenError::Invalid);
+        }
+        if claims.expires_at <= now {
+            return Err(TokenError::Expired);
+        }
+        if self.used.lock().unwrap().contains_key(&claims.nonce) {
+            return Err(TokenError::AlreadyUsed);
+        }
+        Ok(claims)
+    }
+
+    /// `verify` + 사용 처리 (같은 토큰으로 동시에 요청해도 한 번만 성공)
+    pub fn redeem(&self, purpose: Purpose, token: &str, now: u64) -> Result<Claims, TokenError> {
+        let claims = self.verify(purpose, token, now)?;
+        let mut used = self.used.lock().unwrap();
+        if used.contains_key(&claims.nonce) {
+            return Err(TokenError::AlreadyUsed);
+        }
+        used.insert(claims.nonce.clone(), claims.expires_at);
+        Ok(claims)
+    }
+
+    /// 만료된 토큰의 사용 기록을 지우고 지운 수를 반환
+    pub fn purge_used(&self, now: u64) -> usize {
+        let mut used = self.used.lock().unwrap();
+        let before = used.len();
+        used.retain(|_, expires_at| *expires_at > now);
+        before - used.len()
+    }
+
+    fn sign(&self, payload: &str) -> Vec<u8> {
+        let mut mac = self.mac();
+        mac.update(payload.as_bytes());
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    fn mac(&self) -> HmacSha256 {
+        HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any length")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn tokens_are_single_use_and_expire() {
+        let signer = TokenSigner::new("secret");
+        let token = signer.issue(Purpose::ResetPassword, "a@example.com", 0, TTL, 1000);
+
+        // 확인만 하면 몇 번이든 가능
+        assert!(signer.verify(Purpose::ResetPassword, &token, 1000).is_ok());
+        assert!(signer.verify(Purpose::ResetPassword, &token, 1030).is_ok());
+
+        let claims = signer.redeem(Purpose::ResetPassword, &token, 1030).unwrap();
+        assert_eq!(claims.email, "a@example.com");
+        assert_eq!(
+            signer
+                .redeem(Purpose::ResetPassword, &token, 1031)
+                .unwrap_err(),
+            TokenError::AlreadyUsed
+        );
+
+        let other = signer.issue(Purpose::ResetPassword, "a@example.com", 0, TTL, 1000);
+        assert_eq!(
+            signer
+                .redeem(Purpose::ResetPassword, &other, 1060)
+                .unwrap_err(),
+            TokenError::Expired
+        );
+
+        // 만료되면 사용 기록도 필요 없음
+        assert_eq!(signer.purge_used(1059), 0);
+        assert_eq!(signer.purge_used(1060), 1);
+    }
+
+    #[test]
+    fn tampered_or_misused_tokens_are_invalid() {
+        let signer = TokenSigner::new("secret");
+        let token = signer.issue(Purpose::VerifyEmail, "a@example.com", 0, TTL, 0);
+
+        // 용도가 다름
+        assert_eq!(
+            signer
+                .verify(Purpose::ResetPassword, &token, 0)
+                .unwrap_err(),
+            TokenError::Invalid
+        );
+
+        // 다른 키로 서명됨
+        let forged =
+            TokenSigner::new("other").issue(Purpose::VerifyEmail, "a@example.com", 0, TTL, 0);
+        assert_eq!(
+            signer.verify(Purpose::VerifyEmail, &forged, 0).unwrap_err(),
+            TokenError::Invalid
+        );
+
+        // payload의 이메일을 바꾸고 서명은 그대로
+        let (_, signature) = token.split_once('.').unwrap();
+        let claims = signer.verify(Purpose::VerifyEmail, &token, 0).unwrap();
+        let tampered = Claims {
+            email: "b@example.com".to_owned(),
+            ..claims
+        };
+        let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&tampered).unwrap());
+        assert_eq!(
+            signer
+                .verify(Purpose::VerifyEmail, &format!("{payload}.{signature}"), 0)
+                .unwrap_err(),
+            TokenError::Invalid
+        );
+
+        for garbage in ["", ".", "abc", "abc.def", "%%%.%%%"] {
+            assert_eq!(
+                signer.verify(Purpose::VerifyEmail, garbage, 0).unwrap_err(),
+                TokenError::Invalid
+            );
+        }
+    }
+}
@@ -0,0 +1,630 @@
+//! 🔐 TOTP 2단계 인증 예제
+//!
+//! ```not_rust
+//! cargo run -p example-2fa
+//! ```
+//!
+//! 데모 계정: `alice` / `wonderland`
+//!
+//! | 라우트                        | 필요한 단계      | 설명                                         |
+//! |-------------------------------|------------------|----------------------------------------------|
+//! | `POST /login`                 | -                | 비밀번호 확인 (2단계 인증을 켰으면 대기 상태) |
+//! | `POST /login/mfa`             | 대기 상태        | TOTP 코드 또는 복구 코드로 로그인 완료       |
+//! | `GET /me`                     | 로그인           | 사용자 정보와 현재 인증 단계                 |
+//! | `POST /mfa/enroll`            | 로그인           | 비밀 키 발급 (`otpauth://` URL)              |
+//! | `GET /mfa/enroll/qr.svg`      | 로그인           | 인증 앱으로 스캔할 QR 코드                   |
+//! | `POST /mfa/enroll/confirm`    | 로그인           | 첫 코드 확인 → 켜짐 + 복구 코드 발급         |
+//! | `POST /mfa/recovery-codes`    | 2단계 인증       | 복구 코드 다시 발급                          |
+//! | `POST /mfa/disable`           | 2단계 인증       | 2단계 인증 끄기                              |
+//! | `POST /logout`                | -                | 로그아웃                                     |
+//!
+//! • 인증 단계는 세션에 기록하고 `AuthUser`/`RequireMfa` 추출기로 검사 (`session.rs`)
+//! • TOTP 코드는 한 번만 사용 가능 (`totp.rs`), 복구 코드도 한 번씩 (`recovery.rs`)
+
+mod recovery;
+mod session;
+mod totp;
+
+use axum::{
+    extract::{FromRef, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use axum_extra::extract::cookie::{Cookie, CookieJar};
+use recovery::RecoveryCodes;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use session::{AuthLevel, AuthUser, CurrentSession, RequireMfa, SessionStore, COOKIE_NAME};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use subtle::ConstantTimeEq;
+use totp::TotpSecret;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(AppState::new())).await.unwrap();
+}
+
+fn app(state: AppState) -> Router {
+    Router::new()
+        .route("/login", post(login))
+        .route("/login/mfa", post(login_mfa))
+        .route("/logout", post(logout))
+        .route("/me", get(me))
+        .route("/mfa/enroll", post(enroll))
+        .route("/mfa/enroll/qr.svg", get(enroll_qr))
+        .route("/mfa/enroll/confirm", post(confirm_enrollment))
+        .route("/mfa/recovery-codes", post(regenerate_recovery_codes))
+        .route("/mfa/disable", post(disable))
+        .with_state(state)
+}
+
+#[derive(Clone)]
+struct AppState {
+    users: Arc<Mutex<HashMap<String, User>>>,
+    sessions: Arc<SessionStore>,
+}
+
+impl AppState {
+    fn new() -> Self {
+        let users = HashMap::from([("alice".to_owned(), User::new("wonderland"))]);
+        Self {
+            users: Arc::new(Mutex::new(users)),
+            sessions: Default::default(),
+        }
+    }
+}
+
+// 세션 추출기가 AppState에서 세션 저장소를 꺼낼 수 있게 함
+impl FromRef<AppState> for Arc<SessionStore> {
+    fn from_ref(state: &AppState) -> Self {
+        state.sessions.clone()
+    }
+}
+
+struct User {
+    // 데모라서 평문 (실제로는 argon2 등으로 해시, 4-04_account-flows 참고)
+    password: String,
+    totp: Option<Totp>,
+    // 발급했지만 아직 첫 코드로 확인하지 않은 비밀 키
+    pending_secret: Option<TotpSecret>,
+    recovery_codes: RecoveryCodes,
+}
+
+struct Totp {
+    secret: TotpSecret,
+    // 마지막으로 사용한 코드의 단계 (같은 코드 재사용 방지)
+    last_step: u64,
+}
+
+impl User {
+    fn new(password: &str) -> Self {
+        Self {
+            password: password.to_owned(),
+            totp: None,
+            pending_secret: None,
+            recovery_codes: RecoveryCodes::default(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct LoginRequest {
+    username: String,
+    password: String,
+}
+
+#[derive(Deserialize)]
+struct CodeRequest {
+    code: String,
+}
+
+/// 1단계: 비밀번호 확인
+/// - 2단계 인증을 켠 사용자는 `PendingMfa` 세션만 받고 `/login/mfa`로 마무리
+async fn login(
+    State(state): State<AppState>,
+    jar: CookieJar,
+    Json(input): Json<LoginRequest>,
+) -> Result<(CookieJar, Json<Value>), AppError> {
+    let mfa_required = {
+        let users = state.users.lock().unwrap();
+        let user = users
+            .get(&input.username)
+            .filter(|user| bool::from(user.password.as_bytes().ct_eq(input.password.as_bytes())))
+            .ok_or(AppError::InvalidCredentials)?;
+        user.totp.is_some()
+    };
+
+    // 로그인 전 세션이 있으면 버림
+    if let Some(old) = jar.get(COOKIE_NAME) {
+        state.sessions.remove(old.value());
+    }
+    let level = if mfa_required {
+        AuthLevel::PendingMfa
+    } else {
+        AuthLevel::Password
+    };
+    let id = state.sessions.create(&input.username, level);
+    tracing::info!(username = input.username, ?level, "password accepted");
+
+    Ok((
+        jar.add(session::session_cookie(id)),
+        Json(json!({ "mfa_required": mfa_required })),
+    ))
+}
+
+/// 2단계: TOTP 코드(6자리) 또는 복구 코드로 세션을 `Mfa` 단계로 올림
+async fn login_mfa(
+    State(state): State<AppState>,
+    CurrentSession { id, session }: CurrentSession,
+    jar: CookieJar,
+    Json(input): Json<CodeRequest>,
+) -> Result<(CookieJar, Json<Value>), AppError> {
+    if session.level != AuthLevel::PendingMfa {
+        return Err(AppError::Conflict("no two-factor login in progress"));
+    }
+
+    let verified = {
+        let mut users = state.users.lock().unwrap();
+        let user = users
+            .get_mut(&session.username)
+            .ok_or(AppError::InvalidCode)?;
+        verify_second_factor(user, &input.code, unix_now())
+    };
+    let Some((method, recovery_codes_remaining)) = verified else {
+        if !state.sessions.record_failure(&id) {
+            tracing::warn!(username = session.username, "too many invalid codes");
+            return Err(AppError::TooManyAttempts);
+        }
+        return Err(AppError::InvalidCode);
+    };
+
+    let id = state
+        .sessions
+        .elevate(&id, AuthLevel::Mfa)
+        .ok_or(AppError::TooManyAttempts)?;
+    tracing::info!(
+        username = session.username,
+        method,
+        "two-factor login completed"
+    );
+
+    Ok((
+        jar.add(session::session_cookie(id)),
+        Json(json!({
+            "method": method,
+            "recovery_codes_remaining": recovery_codes_remaining,
+        })),
+    ))
+}
+
+// 성공하면 (사용한 방법, 남은 복구 코드 수)
+fn verify_second_factor(user: &mut User, code: &str, now: u64) -> Option<(&'static str, usize)> {
+    let totp = user.totp.as_mut()?;
+    let code = code.trim();
+    if code.len() == 6 && code.bytes().all(|b| b.is_ascii_digit()) {
+        totp.last_step = totp.secret.verify(code, now, Some(totp.last_step))?;
+        Some(("totp", user.recovery_codes.remaining()))
+    } else if user.recovery_codes.consume(code) {
+        Some(("recovery_code", user.recovery_codes.remaining()))
+    } else {
+        None
+    }
+}
+
+async fn logout(State(state): State<AppState>, jar: CookieJar) -> (CookieJar, StatusCode) {
+    if let Some(cookie) = jar.get(COOKIE_NAME) {
+        state.sessions.remove(cookie.value());
+    }
+    (
+        jar.remove(Cookie::build(COOKIE_NAME).path("/")),
+        StatusCode::NO_CONTENT,
+    )
+}
+
+async fn me(State(state): State<AppState>, user: AuthUser) -> Json<Value> {
+    let users = state.users.lock().unwrap();
+    let account = &users[&user.username];
+    Json(json!({
+        "username": user.username,
+        "level": user.level,
+        "mfa_enabled": account.totp.is_some(),
+        "recovery_codes_remaining": account.recovery_codes.remaining(),
+    }))
+}
+
+/// 비밀 키를 발급하고 인증 앱에 등록할 정보를 반환 (아직 켜지지 않음)
+async fn enroll(State(state): State<AppState>, user: AuthUser) -> Result<Json<Value>, AppError> {
+    let mut users = state.users.lock().unwrap();
+    let account = users
+        .get_mut(&user.username)
+        .ok_or(AppError::NotEnrolling)?;
+    if account.totp.is_some() {
+        return Err(AppError::Conflict(
+            "two-factor authentication is already enabled",
+        ));
+    }
+
+    // 다시 요청하면 새 키로 교체 (이전 QR 코드는 무효)
+    let secret = TotpSecret::generate();
+    let body = json!({
+        "secret": secret.base32(),
+        "otpauth_url": secret.otpauth_url(&user.username),
+        "qr_code": "/mfa/enroll/qr.svg",
+    });
+    account.pending_secret = Some(secret);
+    Ok(Json(body))
+}
+
+async fn enroll_qr(State(state): State<AppState>, user: AuthUser) -> Result<Response, AppError> {
+    let users = state.users.lock().unwrap();
+    let secret = users[&user.username]
+        .pending_secret
+        .as_ref()
+        .ok_or(AppError::NotEnrolling)?;
+    let svg = totp::qr_svg(&secret.otpauth_url(&user.username));
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/svg+xml"),
+            // 비밀 키가 들어 있으므로 캐시하지 않음
+            (header::CACHE_CONTROL, "no-store"),
+        ],
+        svg,
+    )
+        .into_response())
+}
+
+/// 인증 앱이 만든 첫 코드를 확인하면 2단계 인증을 켜고 복구 코드를 한 번만 보여줌
+/// - 방금 두 번째 요소를 증명했으므로 현재 세션도 `Mfa` 단계로 올림
+async fn confirm_enrollment(
+    State(state): State<AppState>,
+    user: AuthUser,
+    jar: CookieJar,
+    Json(input): Json<CodeRequest>,
+) -> Result<(CookieJar, Json<Value>), AppError> {
+    let codes = {
+        let mut users = state.users.lock().unwrap();
+        let account = users
+            .get_mut(&user.username)
+            .ok_or(AppError::NotEnrolling)?;
+        let secret = account
+            .pending_secret
+            .as_ref()
+            .ok_or(AppError::NotEnrolling)?;
+        let step = secret
+            .verify(input.code.trim(), unix_now(), None)
+            .ok_or(AppError::InvalidCode)?;
+
+        let (recovery_codes, codes) = RecoveryCodes::generate();
+        account.totp = account.pending_secret.take().map(|secret| Totp {
+            secret,
+            last_step: step,
+        });
+        account.recovery_codes = recovery_codes;
+        codes
+    };
+
+    let id = state
+        .sessions
+        .elevate(&user.session_id, AuthLevel::Mfa)
+        .ok_or(AppError::InvalidCode)?;
+    tracing::info!(
+        username = user.username,
+        "two-factor authentication enabled"
+    );
+
+    Ok((
+        jar.add(session::session_cookie(id)),
+        Json(json!({ "recovery_codes": codes })),
+    ))
+}
+
+async fn regenerate_recovery_codes(
+    State(state): State<AppState>,
+    RequireMfa(user): RequireMfa,
+) -> Result<Json<Value>, AppError> {
+    let mut users = state.users.lock().unwrap();
+    let account = users
+        .get_mut(&user.username)
+        .ok_or(AppError::NotEnrolling)?;
+    let (recovery_codes, codes) = RecoveryCodes::generate();
+    // 이전 코드는 모두 무효
+    account.recovery_codes = recovery_codes;
+    Ok(Json(json!({ "recovery_codes": codes })))
+}
+
+async fn disable(
+    State(state): State<AppState>,
+    RequireMfa(user): RequireMfa,
+) -> Result<Json<Value>, AppError> {
+    let mut users = state.users.lock().unwrap();
+    let account = users
+        .get_mut(&user.username)
+        .ok_or(AppError::NotEnrolling)?;
+    account.totp = None;
+    account.recovery_codes = RecoveryCodes::default();
+    tracing::info!(
+        username = user.username,
+        "two-factor authentication disabled"
+    );
+    Ok(Json(json!({ "mfa_enabled": false })))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[derive(Debug)]
+enum AppError {
+    InvalidCredentials,
+    InvalidCode,
+    /// 두 번째 단계를 너무 많이 틀려 세션이 파기됨
+    TooManyAttempts,
+    NotEnrolling,
+    Conflict(&'static str),
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            Self::InvalidCredentials => (StatusCode::UNAUTHORIZED, "invalid username or password"),
+            Self::InvalidCode => (StatusCode::UNAUTHORIZED, "invalid code"),
+            Self::TooManyAttempts => (
+                StatusCode::UNAUTHORIZED,
+                "too many invalid codes, log in again",
+            ),
+            Self::NotEnrolling => (
+                StatusCode::NOT_FOUND,
+                "start enrollment with POST /mfa/enroll first",
+            ),
+            Self::Conflict(message) => (StatusCode::CONFLICT, message),
+        };
+        (status, message).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    /// 쿠키를 기억하는 테스트 클라이언트
+    struct Client {
+        router: Router,
+        state: AppState,
+        session: Option<String>,
+    }
+
+    impl Client {
+        fn new() -> Self {
+            let state = AppState::new();
+            Self {
+                router: app(state.clone()),
+                state,
+                session: None,
+            }
+        }
+
+        async fn send(
+            &mut self,
+            method: &str,
+            uri: &str,
+            body: Option<Value>,
+        ) -> (StatusCode, Value) {
+            let mut request = Request::builder().method(method).uri(uri);
+            if let Some(id) = &self.session {
+                request = request.header(header::COOKIE, format!("{COOKIE_NAME}={id}"));
+            }
+            let request = match body {
+                Some(body) => request
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(body.to_string())),
+                None => request.body(Body::empty()),
+            }
+            .unwrap();
+
+            let response = self.router.clone().oneshot(request).await.unwrap();
+            if let Some(cookie) = response.headers().get(header::SET_COOKIE) {
+                let cookie = Cookie::parse(cookie.to_str().unwrap().to_owned()).unwrap();
+                self.session = Some(cookie.value().to_owned()).filter(|id| !id.is_empty());
+            }
+            let status = response.status();
+            let bytes = response.into_body().collect().await.unwrap().to_bytes();
+            let body = serde_json::from_slice(&bytes)
+                .unwrap_or_else(|_| String::from_utf8_lossy(&bytes).into());
+            (status, body)
+        }
+
+        async fn login(&mut self) -> Value {
+            let credentials = json!({ "username": "alice", "password": "wonderland" });
+            let (status, body) = self.send("POST", "/login", Some(credentials)).await;
+            assert_eq!(status, StatusCode::OK);
+            body
+        }
+
+        async fn code(&mut self, code: &str) -> (StatusCode, Value) {
+            self.send("POST", "/login/mfa", Some(json!({ "code": code })))
+                .await
+        }
+
+        /// 2단계 인증을 켜고 (비밀 키, 복구 코드) 반환
+        async fn enroll(&mut self) -> (TotpSecret, Vec<String>) {
+            self.login().await;
+            let (status, _) = self.send("POST", "/mfa/enroll", None).await;
+            assert_eq!(status, StatusCode::OK);
+            let secret = self.state.users.lock().unwrap()["alice"]
+                .pending_secret
+                .clone()
+                .unwrap();
+
+            let code = json!({ "code": secret.code_at(unix_now()) });
+            let (status, body) = self.send("POST", "/mfa/enroll/confirm", Some(code)).await;
+            assert_eq!(status, StatusCode::OK);
+            let codes = serde_json::from_value(body["recovery_codes"].clone()).unwrap();
+            (secret, codes)
+        }
+    }
+
+    #[tokio::test]
+    async fn enrolling_elevates_the_session_and_unlocks_sensitive_routes() {
+        let mut client = Client::new();
+        assert_eq!(
+            client.send("GET", "/me", None).await.0,
+            StatusCode::UNAUTHORIZED
+        );
+
+        assert_eq!(client.login().await["mfa_required"], false);
+        let (_, me) = client.send("GET", "/me", None).await;
+        assert_eq!(me["level"], "password");
+        let (status, _) = client.send("POST", "/mfa/recovery-codes", None).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        // 발급 전에는 QR 코드가 없음
+        let (status, _) = client.send("GET", "/mfa/enroll/qr.svg", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, body) = client.send("POST", "/mfa/enroll", None).await;
+        assert_eq!(status, StatusCode::OK);
+        let url = body["otpauth_url"].as_str().unwrap();
+        assert!(url.starts_with("otpauth://totp/axum-examples:alice?secret="));
+        let (status, svg) = client.send("GET", "/mfa/enroll/qr.svg", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(svg.as_str().unwrap().contains("<svg"));
+
+        let wrong = json!({ "code": "000000" });
+        let (status, _) = client
+            .send("POST", "/mfa/enroll/confirm", Some(wrong))
+            .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let password_session = client.session.clone();
+        let secret = client.state.users.lock().unwrap()["alice"]
+            .pending_secret
+            .clone()
+            .unwrap();
+        let code = json!({ "code": secret.code_at(unix_now()) });
+        let (status, body) = client.send("POST", "/mfa/enroll/confirm", Some(code)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body["recovery_codes"].as_array().unwrap().len(),
+            recovery::COUNT
+        );
+
+        // 세션 ID가 바뀌고 Mfa 단계가 됨
+        assert_ne!(client.session, password_session);
+        let (_, me) = client.send("GET", "/me", None).await;
+        assert_eq!(me["level"], "mfa");
+        assert_eq!(me["mfa_enabled"], true);
+        let (status, _) = client.send("POST", "/mfa/recovery-codes", None).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = client.send("POST", "/mfa/enroll", None).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn login_requires_a_fresh_totp_code_after_the_password() {
+        let mut client = Client::new();
+        let (secret, _) = client.enroll().await;
+        client.send("POST", "/logout", None).await;
+        assert_eq!(client.session, None);
+
+        assert_eq!(client.login().await["mfa_required"], true);
+        let (status, body) = client.send("GET", "/me", None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body, "finish logging in with a code at POST /login/mfa");
+
+        // 등록할 때 쓴 코드는 다시 쓸 수 없음
+        let used_step = client.state.users.lock().unwrap()["alice"]
+            .totp
+            .as_ref()
+            .unwrap()
+            .last_step;
+        let (status, _) = client.code(&secret.code_at(used_step * 30)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        // 다음 단계의 코드는 (현재 단계이거나 시계 오차 범위라서) 허용
+        let (status, body) = client.code(&secret.code_at((used_step + 1) * 30)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["method"], "totp");
+
+        let (_, me) = client.send("GET", "/me", None).await;
+        assert_eq!(me["level"], "mfa");
+        // 이미 Mfa 단계
+        let (status, _) = client.code("123456").await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let (status, body) = client.send("POST", "/mfa/disable", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["mfa_enabled"], false);
+        assert_eq!(client.login().await["mfa_required"], false);
+    }
+
+    #[tokio::test]
+    async fn recovery_codes_work_once_and_guessing_is_limited() {
+        let mut client = Client::new();
+        let (_, codes) = client.enroll().await;
+        client.send("POST", "/logout", None).await;
+
+        client.login().await;
+        let (status, body) = client.code(&codes[0]).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["method"], "recovery_code");
+        assert_eq!(body["recovery_codes_remaining"], recovery::COUNT - 1);
+
+        client.send("POST", "/logout", None).await;
+        client.login().await;
+        assert_eq!(client.code(&codes[0]).await.0, StatusCode::UNAUTHORIZED);
+
+        // 앞에서 한 번 틀렸으므로 남은 기회는 MAX_MFA_ATTEMPTS - 1번
+        for _ in 2..session::MAX_MFA_ATTEMPTS {
+            let (status, body) = client.code("wrong-code").await;
+            assert_eq!(
+                (status, body.as_str().unwrap()),
+                (StatusCode::UNAUTHORIZED, "invalid code")
+            );
+        }
+        let (_, body) = client.code("wrong-code").await;
+        assert_eq!(body, "too many invalid codes, log in again");
+
+        // 세션이 파기돼서 맞는 코드도 소용없음
+        let (_, body) = client.code(&codes[1]).await;
+        assert_eq!(body, "not logged in");
+    }
+}
+
+// 🧪 테스트 방법
+//
+// cargo test -p example-2fa
+//
+// curl -i -c cookies.txt -X POST http://127.0.0.1:3000/login \
+//   -H 'content-type: application/json' -d '{"username":"alice","password":"wonderland"}'
+// curl -b cookies.txt -c cookies.txt -X POST http://127.0.0.1:3000/mfa/enroll
+// # 브라우저에서 로그인한 상태라면 /mfa/enroll/qr.svg를 인증 앱으로 스캔, 아니면 secret을 직접 입력
+// curl -b cookies.txt -c cookies.txt -X POST http://127.0.0.1:3000/mfa/enroll/confirm \
+//   -H 'content-type: application/json' -d '{"code":"123456"}'   # 인증 앱의 코드
+// # → 복구 코드 10개 (한 번만 보여줌)
+//
+// 다시 로그인하면 {"mfa_required":true} → POST /login/mfa {"code":"..."}로 마무리
@@ -0,0 +1,86 @@
+//! 🧯 복구 코드 (인증 앱을 잃어버렸을 때 TOTP 코드 대신 한 번씩 사용)
+//!
+//! - 2단계 인증을 켤 때(또는 다시 발급할 때) 10개를 만들어 평문은 한 번만 보여줌
+//! - 서버에는 SHA-256 해시만 저장 (코드 자체가 충분히 긴 난수라서 느린 해시는 필요 없음)
+//! - 사용한 코드는 지움 → 남은 개수가 적으면 다시 발급하도록 안내
+
+use rand::{seq::SliceRandom, thread_rng};
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+pub const COUNT: usize = 10;
+
+// 헷갈리기 쉬운 문자(0/o, 1/l/i) 제외
+const ALPHABET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+const HALF_LEN: usize = 5;
+
+#[derive(Default)]
+pub struct RecoveryCodes {
+    hashes: Vec<[u8; 32]>,
+}
+
+impl RecoveryCodes {
+    /// 새 코드 묶음과 사용자에게 보여줄 평문 (`xxxxx-xxxxx`)
+    pub fn generate() -> (Self, Vec<String>) {
+        let mut rng = thread_rng();
+        let mut half = || -> String {
+            (0..HALF_LEN)
+                .map(|_| *ALPHABET.choose(&mut rng).unwrap() as char)
+                .collect()
+        };
+        let codes: Vec<String> = (0..COUNT)
+            .map(|_| format!("{}-{}", half(), half()))
+            .collect();
+        let hashes = codes.iter().map(|code| hash(code)).collect();
+        (Self { hashes }, codes)
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// 맞는 코드면 지우고 `true`
+    pub fn consume(&mut self, code: &str) -> bool {
+        let hash = hash(code);
+        // 모든 코드와 상수 시간 비교 (어느 위치에서 일치했는지 시간으로 드러나지 않게)
+        let mut found = None;
+        for (i, stored) in self.hashes.iter().enumerate() {
+            if bool::from(stored.ct_eq(&hash)) {
+                found = Some(i);
+            }
+        }
+        found.map(|i| self.hashes.swap_remove(i)).is_some()
+    }
+}
+
+// 사용자가 대소문자, 하이픈, 공백을 다르게 입력해도 같은 코드로 봄
+fn hash(code: &str) -> [u8; 32] {
+    let normalized: String = code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    Sha256::digest(normalized.as_bytes()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_can_be_used_once() {
+        let (mut stored, codes) = RecoveryCodes::generate();
+        assert_eq!(codes.len(), COUNT);
+        assert!(codes.iter().all(|code| code.len() == 2 * HALF_LEN + 1));
+
+        assert!(stored.consume(&codes[3]));
+        assert!(!stored.consume(&codes[3]));
+        assert_eq!(stored.remaining(), COUNT - 1);
+
+        // 입력 형식은 달라도 됨
+        let typed = codes[0].replace('-', " ").to_uppercase();
+        assert!(stored.consume(&typed));
+        assert!(!stored.consume("aaaaa-aaaaa"));
+        assert_eq!(stored.remaining(), COUNT - 2);
+    }
+}
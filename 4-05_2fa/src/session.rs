@@ -0,0 +1,268 @@
+//! 🍪 세션 + 인증 단계
+//!
+//! 로그인은 두 단계로 진행되고, 세션에 어느 단계까지 통과했는지 기록함
+//!
+//! | `AuthLevel`  | 상태                                         | 통과하는 추출기          |
+//! |--------------|----------------------------------------------|--------------------------|
+//! | `PendingMfa` | 비밀번호는 맞음, TOTP/복구 코드 입력 대기    | `CurrentSession`         |
+//! | `Password`   | 2단계 인증을 켜지 않은 사용자의 일반 로그인  | + `AuthUser`             |
+//! | `Mfa`        | 2단계 인증까지 통과                          | + `RequireMfa`           |
+//!
+//! • 단계가 올라갈 때마다 세션 ID를 새로 발급 (session fixation 방지, 4-02_oauth 참고)
+//! • `PendingMfa` 세션은 짧게 유지하고, 코드를 `MAX_MFA_ATTEMPTS`번 틀리면 파기
+
+use axum::{
+    extract::{FromRef, FromRequestParts},
+    http::{request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+};
+use axum_extra::extract::cookie::{Cookie, CookieJar, SameSite};
+use rand::RngCore;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::time::Instant;
+
+pub const COOKIE_NAME: &str = "session";
+
+/// 비밀번호 확인 후 두 번째 단계를 마쳐야 하는 시간
+const PENDING_TTL: Duration = Duration::from_secs(5 * 60);
+/// 로그인 세션 유지 시간
+const SESSION_TTL: Duration = Duration::from_secs(12 * 60 * 60);
+/// 두 번째 단계에서 허용하는 실패 횟수 (6자리 코드 무차별 대입 방지)
+pub const MAX_MFA_ATTEMPTS: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthLevel {
+    PendingMfa,
+    Password,
+    Mfa,
+}
+
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub username: String,
+    pub level: AuthLevel,
+    failed_attempts: u32,
+    expires_at: Instant,
+}
+
+#[derive(Default)]
+pub struct SessionStore {
+    sessions: Mutex<HashMap<String, Session>>,
+}
+
+impl SessionStore {
+    /// 새 세션을 만들고 세션 ID를 반환
+    pub fn create(&self, username: &str, level: AuthLevel) -> String {
+        let id = new_session_id();
+        let ttl = match level {
+            AuthLevel::PendingMfa => PENDING_TTL,
+            AuthLevel::Password | AuthLevel::Mfa => SESSION_TTL,
+        };
+        self.sessions.lock().unwrap().insert(
+            id.clone(),
+            Session {
+                username: username.to_owned(),
+                level,
+                failed_attempts: 0,
+                expires_at: Instant::now() + ttl,
+            },
+        );
+        id
+    }
+
+    pub fn get(&self, id: &str) -> Option<Session> {
+        let mut sessions = self.sessions.lock().unwrap();
+        match sessions.get(id) {
+            Some(session) if session.expires_at > Instant::now() => Some(session.clone()),
+            Some(_) => {
+                sessions.remove(id);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// 이전 세션을 지우고 `level`의 새 세션 ID를 발급
+    pub fn elevate(&self, id: &str, level: AuthLevel) -> Option<String> {
+        let session = self.sessions.lock().unwrap().remove(id)?;
+        if session.expires_at <= Instant::now() {
+            return None;
+        }
+        Some(self.create(&session.username, level))
+    }
+
+    /// 두 번째 단계 실패를 기록, 허용 횟수를 넘으면 세션을 지우고 `false`
+    pub fn record_failure(&self, id: &str) -> bool {
+        let mut sessions = self.sessions.lock().unwrap();
+        let Some(session) = sessions.get_mut(id) else {
+            return false;
+        };
+        session.failed_attempts += 1;
+        if session.failed_attempts >= MAX_MFA_ATTEMPTS {
+            sessions.remove(id);
+            return false;
+        }
+        true
+    }
+
+    pub fn remove(&self, id: &str) {
+        self.sessions.lock().unwrap().remove(id);
+    }
+}
+
+fn new_session_id() -> String {
+    let mut bytes = [0; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes.iter().fold(String::new(), |mut id, b| {
+        let _ = write!(id, "{b:02x}");
+        id
+    })
+}
+
+/// 세션 쿠키 (HTTPS로 서비스할 때는 `.secure(true)` 추가)
+pub fn session_cookie(id: String) -> Cookie<'static> {
+    Cookie::build((COOKIE_NAME, id))
+        .http_only(true)
+        .same_site(SameSite::Lax)
+        .path("/")
+        .build()
+}
+
+#[derive(Debug)]
+pub enum AuthError {
+    Unauthenticated,
+    /// 비밀번호만 확인된 상태
+    MfaPending,
+    /// 2단계 인증이 필요한 라우트
+    MfaRequired,
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Unauthenticated => (StatusCode::UNAUTHORIZED, "not logged in").into_response(),
+            Self::MfaPending => (
+                StatusCode::UNAUTHORIZED,
+                "finish logging in with a code at POST /login/mfa",
+            )
+                .into_response(),
+            Self::MfaRequired => (
+                StatusCode::FORBIDDEN,
+                "this action requires two-factor authentication",
+            )
+                .into_response(),
+        }
+    }
+}
+
+/// 단계와 상관없이 유효한 세션 (두 번째 단계 입력, 로그아웃에서 사용)
+pub struct CurrentSession {
+    pub id: String,
+    pub session: Session,
+}
+
+impl<S> FromRequestParts<S> for CurrentSession
+where
+    Arc<SessionStore>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let store = Arc::<SessionStore>::from_ref(state);
+        let jar = CookieJar::from_headers(&parts.headers);
+        let id = jar
+            .get(COOKIE_NAME)
+            .map(|cookie| cookie.value().to_owned())
+            .ok_or(AuthError::Unauthenticated)?;
+        let session = store.get(&id).ok_or(AuthError::Unauthenticated)?;
+        Ok(Self { id, session })
+    }
+}
+
+/// 로그인을 마친 사용자 (`Password` 또는 `Mfa`)
+pub struct AuthUser {
+    pub session_id: String,
+    pub username: String,
+    pub level: AuthLevel,
+}
+
+impl<S> FromRequestParts<S> for AuthUser
+where
+    Arc<SessionStore>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let CurrentSession { id, session } =
+            CurrentSession::from_request_parts(parts, state).await?;
+        if session.level == AuthLevel::PendingMfa {
+            return Err(AuthError::MfaPending);
+        }
+        Ok(Self {
+            session_id: id,
+            username: session.username,
+            level: session.level,
+        })
+    }
+}
+
+/// 2단계 인증까지 마친 사용자만 통과 (민감한 작업을 하는 라우트에 사용)
+///
+/// 2단계 인증을 켜지 않은 사용자는 먼저 `/mfa/enroll`로 켜야 함
+pub struct RequireMfa(pub AuthUser);
+
+impl<S> FromRequestParts<S> for RequireMfa
+where
+    Arc<SessionStore>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user = AuthUser::from_request_parts(parts, state).await?;
+        if user.level != AuthLevel::Mfa {
+            return Err(AuthError::MfaRequired);
+        }
+        Ok(Self(user))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn pending_sessions_expire_quickly_and_lock_after_failures() {
+        tokio::time::pause();
+        let store = SessionStore::default();
+
+        let pending = store.create("alice", AuthLevel::PendingMfa);
+        for _ in 1..MAX_MFA_ATTEMPTS {
+            assert!(store.record_failure(&pending));
+        }
+        assert!(!store.record_failure(&pending));
+        assert!(store.get(&pending).is_none());
+
+        let pending = store.create("alice", AuthLevel::PendingMfa);
+        tokio::time::advance(PENDING_TTL).await;
+        assert!(store.get(&pending).is_none());
+        assert!(store.elevate(&pending, AuthLevel::Mfa).is_none());
+
+        // 단계가 올라가면 ID가 바뀌고 이전 ID는 무효
+        let pending = store.create("alice", AuthLevel::PendingMfa);
+        let elevated = store.elevate(&pending, AuthLevel::Mfa).unwrap();
+        assert_ne!(elevated, pending);
+        assert!(store.get(&pending).is_none());
+        tokio::time::advance(PENDING_TTL).await;
+        assert_eq!(store.get(&elevated).unwrap().level, AuthLevel::Mfa);
+    }
+}
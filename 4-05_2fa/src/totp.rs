@@ -0,0 +1,128 @@
+//! ⏱️ TOTP (RFC 6238) 비밀 키 발급 / 코드 검증
+//!
+//! - 비밀 키: 160비트 난수, 인증 앱(Google Authenticator 등)에는 `otpauth://` URL을 QR 코드로 전달
+//! - 코드: 30초마다 바뀌는 6자리, 시계 오차를 고려해 앞뒤 한 단계(±30초)까지 허용
+//! - 같은 코드를 두 번 쓸 수 없도록 마지막으로 사용한 단계를 기억 (`verify`의 `last_step`)
+
+use qrcode::{render::svg, QrCode};
+use subtle::ConstantTimeEq;
+use totp_rs::{Algorithm, Secret, TOTP};
+
+/// 인증 앱에 표시되는 서비스 이름
+pub const ISSUER: &str = "axum-examples";
+const STEP: u64 = 30;
+const SKEW: u64 = 1;
+
+#[derive(Clone)]
+pub struct TotpSecret(Vec<u8>);
+
+impl TotpSecret {
+    pub fn generate() -> Self {
+        let secret = Secret::generate_secret()
+            .to_bytes()
+            .expect("generated secrets are raw bytes");
+        Self(secret)
+    }
+
+    /// 인증 앱에 직접 입력할 때 쓰는 base32 문자열
+    pub fn base32(&self) -> String {
+        self.totp("").get_secret_base32()
+    }
+
+    /// `otpauth://totp/axum-examples:alice?secret=...&issuer=axum-examples`
+    pub fn otpauth_url(&self, account: &str) -> String {
+        self.totp(account).get_url()
+    }
+
+    /// `time`(Unix 초)의 코드 (테스트에서 인증 앱 대신 사용)
+    #[cfg(test)]
+    pub fn code_at(&self, time: u64) -> String {
+        self.totp("").generate(time)
+    }
+
+    /// 맞으면 코드의 단계(`time / 30`)를 반환
+    ///
+    /// `last_step` 이하의 단계는 이미 사용한 코드로 보고 거부 (재전송 공격 방지)
+    pub fn verify(&self, code: &str, now: u64, last_step: Option<u64>) -> Option<u64> {
+        let totp = self.totp("");
+        let current = now / STEP;
+        (current.saturating_sub(SKEW)..=current + SKEW)
+            .filter(|&step| last_step.is_none_or(|last| step > last))
+            // 상수 시간 비교 (코드 길이가 다르면 false)
+            .find(|&step| bool::from(totp.generate(step * STEP).as_bytes().ct_eq(code.as_bytes())))
+    }
+
+    fn totp(&self, account: &str) -> TOTP {
+        TOTP::new(
+            Algorithm::SHA1,
+            6,
+            0,
+            STEP,
+            self.0.clone(),
+            Some(ISSUER.to_owned()),
+            account.to_owned(),
+        )
+        .expect("160-bit secret and account names without ':'")
+    }
+}
+
+/// `otpauth://` URL을 인증 앱으로 스캔할 QR 코드 (SVG)
+pub fn qr_svg(data: &str) -> String {
+    QrCode::new(data)
+        .expect("otpauth URLs fit in a QR code")
+        .render::<svg::Color>()
+        .min_dimensions(200, 200)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_accepted_within_one_step_and_only_once() {
+        let secret = TotpSecret::generate();
+        let now = 1_700_000_000; // 단계 56_666_666의 20초 지점
+
+        let step = secret.verify(&secret.code_at(now), now, None).unwrap();
+        assert_eq!(step, now / 30);
+        // 시계가 30초 늦거나 빠른 기기의 코드도 허용
+        assert_eq!(
+            secret.verify(&secret.code_at(now - 30), now, None),
+            Some(step - 1)
+        );
+        assert_eq!(
+            secret.verify(&secret.code_at(now + 30), now, None),
+            Some(step + 1)
+        );
+        assert_eq!(secret.verify(&secret.code_at(now + 60), now, None), None);
+
+        // 이미 사용한 단계와 그 이전 코드는 거부
+        assert_eq!(secret.verify(&secret.code_at(now), now, Some(step)), None);
+        assert_eq!(
+            secret.verify(&secret.code_at(now - 30), now, Some(step)),
+            None
+        );
+        assert_eq!(
+            secret.verify(&secret.code_at(now + 30), now, Some(step)),
+            Some(step + 1)
+        );
+
+        assert_eq!(secret.verify("", now, None), None);
+        assert_eq!(secret.verify("12345", now, None), None);
+    }
+
+    #[test]
+    fn provisioning_url_names_the_issuer_and_account() {
+        let secret = TotpSecret::generate();
+        let url = secret.otpauth_url("alice");
+        assert_eq!(
+            url,
+            format!(
+                "otpauth://totp/axum-examples:alice?secret={}&issuer=axum-examples",
+                secret.base32()
+            )
+        );
+        assert!(qr_svg(&url).starts_with("<?xml"));
+    }
+}
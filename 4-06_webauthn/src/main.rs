@@ -0,0 +1,705 @@
+//! 🔑 WebAuthn / 패스키 예제 (비밀번호 없이 기기의 생체 인증·보안 키로 로그인)
+//!
+//! ```not_rust
+//! cargo run -p example-webauthn
+//! ```
+//!
+//! 브라우저에서 http://localhost:3000 을 열어서 사용
+//! (WebAuthn은 HTTPS 또는 `localhost`에서만 동작하므로 `127.0.0.1`이 아니라 `localhost`로 접속)
+//!
+//! | 라우트                         | 설명                                                   |
+//! |--------------------------------|--------------------------------------------------------|
+//! | `GET /`                        | 등록 / 로그인 데모 페이지 (`index.html`)               |
+//! | `POST /register/start`         | 등록 시작 → 브라우저에 넘길 challenge                  |
+//! | `POST /register/finish`        | 기기가 만든 공개 키 확인 후 저장 (새 계정이면 로그인)  |
+//! | `POST /login/start`            | 로그인 시작 → 사용자의 패스키 목록이 담긴 challenge    |
+//! | `POST /login/finish`           | 서명 확인 후 세션 쿠키 발급                            |
+//! | `GET /me`                      | 로그인한 사용자와 등록된 패스키 목록                   |
+//! | `DELETE /passkeys/{id}`        | 패스키 삭제 (마지막 하나는 삭제 불가)                  |
+//! | `POST /logout`                 | 로그아웃                                               |
+//!
+//! • 등록과 로그인은 모두 두 번의 요청(start → finish)으로 진행되고,
+//!   그 사이의 상태(challenge)는 서버에만 저장 (`store.rs`, 쿠키에는 키만 전달)
+//! • 한 사용자가 여러 패스키(휴대폰, 노트북, 보안 키 ...)를 등록할 수 있음
+//!   → 이미 있는 사용자 이름에 패스키를 추가하려면 그 사용자로 로그인한 상태여야 함
+
+mod store;
+
+use axum::{
+    extract::{FromRequestParts, Path, State},
+    http::{request::Parts, StatusCode},
+    response::{Html, IntoResponse, Response},
+    routing::{delete, get, post},
+    Json, Router,
+};
+use axum_extra::extract::cookie::{Cookie, CookieJar, SameSite};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use store::ExpiringStore;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use webauthn_rs::prelude::*;
+
+const SESSION_COOKIE: &str = "session";
+const CEREMONY_COOKIE: &str = "webauthn_ceremony";
+
+/// 등록 / 로그인 절차를 마쳐야 하는 시간 (브라우저 기본 timeout과 같음)
+const CEREMONY_TTL: Duration = Duration::from_secs(5 * 60);
+const SESSION_TTL: Duration = Duration::from_secs(12 * 60 * 60);
+
+/// 브라우저 주소창의 도메인과 같아야 함 (패스키는 이 도메인에 묶임)
+const RP_ID: &str = "localhost";
+const RP_ORIGIN: &str = "http://localhost:3000";
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("open {RP_ORIGIN} in a browser");
+    axum::serve(listener, app(AppState::new())).await.unwrap();
+}
+
+fn app(state: AppState) -> Router {
+    Router::new()
+        .route("/", get(index))
+        .route("/register/start", post(start_registration))
+        .route("/register/finish", post(finish_registration))
+        .route("/login/start", post(start_login))
+        .route("/login/finish", post(finish_login))
+        .route("/me", get(me))
+        .route("/passkeys/{id}", delete(delete_passkey))
+        .route("/logout", post(logout))
+        .with_state(state)
+}
+
+#[derive(Clone)]
+struct AppState {
+    webauthn: Arc<Webauthn>,
+    users: Arc<Mutex<HashMap<String, User>>>,
+    ceremonies: Arc<ExpiringStore<Ceremony>>,
+    /// 세션 ID → 사용자 이름
+    sessions: Arc<ExpiringStore<String>>,
+}
+
+impl AppState {
+    fn new() -> Self {
+        let origin = Url::parse(RP_ORIGIN).unwrap();
+        let webauthn = WebauthnBuilder::new(RP_ID, &origin)
+            .and_then(|builder| builder.rp_name("axum-examples").build())
+            .expect("RP_ID must be the host of RP_ORIGIN");
+        Self {
+            webauthn: Arc::new(webauthn),
+            users: Default::default(),
+            ceremonies: Arc::new(ExpiringStore::new(CEREMONY_TTL)),
+            sessions: Arc::new(ExpiringStore::new(SESSION_TTL)),
+        }
+    }
+}
+
+struct User {
+    /// 인증기에 저장되는 사용자 핸들 (이름이 바뀌어도 유지되는 임의의 값)
+    id: Uuid,
+    passkeys: Vec<StoredPasskey>,
+}
+
+/// 예제라서 메모리에 보관, `Passkey`는 Serialize를 구현하므로 DB에 JSON으로 저장하면 됨
+struct StoredPasskey {
+    label: String,
+    passkey: Passkey,
+    created_at: u64,
+    last_used_at: Option<u64>,
+}
+
+/// start와 finish 사이에 서버가 기억하는 상태
+#[derive(Clone)]
+enum Ceremony {
+    Registration {
+        username: String,
+        user_id: Uuid,
+        label: String,
+        state: PasskeyRegistration,
+    },
+    Authentication {
+        username: String,
+        state: PasskeyAuthentication,
+    },
+}
+
+#[derive(Deserialize)]
+struct RegisterRequest {
+    username: String,
+    /// 목록에서 구분할 이름 (예: "MacBook Touch ID")
+    label: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LoginRequest {
+    username: String,
+}
+
+async fn index() -> Html<&'static str> {
+    Html(include_str!("../index.html"))
+}
+
+/// 등록 1단계: challenge를 만들어 브라우저의 `navigator.credentials.create()`에 넘김
+/// - 새 사용자 이름이면 새 계정, 로그인한 사용자의 이름이면 패스키 추가
+/// - 이미 등록된 패스키는 `exclude_credentials`로 알려서 같은 기기를 두 번 등록하지 않게 함
+async fn start_registration(
+    State(state): State<AppState>,
+    jar: CookieJar,
+    Json(input): Json<RegisterRequest>,
+) -> Result<(CookieJar, Json<CreationChallengeResponse>), AppError> {
+    let username = input.username.trim().to_owned();
+    if username.is_empty() || username.chars().count() > 64 {
+        return Err(AppError::InvalidUsername);
+    }
+    let logged_in_as = current_user(&state, &jar);
+    let (user_id, existing) = {
+        let users = state.users.lock().unwrap();
+        match users.get(&username) {
+            Some(user) if logged_in_as.as_deref() == Some(username.as_str()) => {
+                let existing: Vec<CredentialID> = user
+                    .passkeys
+                    .iter()
+                    .map(|stored| stored.passkey.cred_id().clone())
+                    .collect();
+                (user.id, existing)
+            }
+            Some(_) => {
+                return Err(AppError::Conflict(
+                    "username is taken, log in to add another passkey",
+                ))
+            }
+            None => (Uuid::new_v4(), Vec::new()),
+        }
+    };
+    let label = input
+        .label
+        .map(|label| label.trim().to_owned())
+        .filter(|label| !label.is_empty())
+        .unwrap_or_else(|| format!("passkey {}", existing.len() + 1));
+
+    let exclude_credentials = (!existing.is_empty()).then_some(existing);
+    let (challenge, registration) = state.webauthn.start_passkey_registration(
+        user_id,
+        &username,
+        &username,
+        exclude_credentials,
+    )?;
+    let id = state.ceremonies.insert(Ceremony::Registration {
+        username,
+        user_id,
+        label,
+        state: registration,
+    });
+    Ok((jar.add(ceremony_cookie(id)), Json(challenge)))
+}
+
+/// 등록 2단계: 인증기가 만든 공개 키를 확인하고 사용자에게 추가
+async fn finish_registration(
+    State(state): State<AppState>,
+    jar: CookieJar,
+    Json(credential): Json<RegisterPublicKeyCredential>,
+) -> Result<(CookieJar, Json<Value>), AppError> {
+    let (jar, ceremony) = take_ceremony(&state, jar);
+    let Some(Ceremony::Registration {
+        username,
+        user_id,
+        label,
+        state: registration,
+    }) = ceremony
+    else {
+        return Err(AppError::NoCeremony);
+    };
+    let passkey = state
+        .webauthn
+        .finish_passkey_registration(&credential, &registration)?;
+
+    let (new_account, count) = {
+        let mut users = state.users.lock().unwrap();
+        // 같은 인증기 키가 다른 계정에 이미 등록되어 있으면 거부 (webauthn-rs 문서의 요구 사항)
+        let registered = users
+            .values()
+            .flat_map(|user| &user.passkeys)
+            .any(|stored| stored.passkey.cred_id() == passkey.cred_id());
+        if registered {
+            return Err(AppError::Conflict("this passkey is already registered"));
+        }
+        let new_account = !users.contains_key(&username);
+        let user = users.entry(username.clone()).or_insert_with(|| User {
+            id: user_id,
+            passkeys: Vec::new(),
+        });
+        // start와 finish 사이에 다른 사람이 같은 이름으로 가입한 경우
+        if user.id != user_id {
+            return Err(AppError::Conflict("username is taken"));
+        }
+        user.passkeys.push(StoredPasskey {
+            label: label.clone(),
+            passkey,
+            created_at: unix_now(),
+            last_used_at: None,
+        });
+        (new_account, user.passkeys.len())
+    };
+    tracing::info!(username, label, new_account, "passkey registered");
+
+    // 새 계정은 바로 로그인 (패스키를 추가한 경우에는 이미 로그인한 상태)
+    let jar = if new_account {
+        start_session(&state, jar, username.clone())
+    } else {
+        jar
+    };
+    Ok((
+        jar,
+        Json(json!({ "username": username, "label": label, "passkeys": count })),
+    ))
+}
+
+/// 로그인 1단계: 사용자가 등록한 모든 패스키를 허용 목록에 담은 challenge
+///
+/// 사용자 이름으로 패스키 목록을 찾으므로 가입 여부가 드러남
+/// (사용자 이름 없이 기기에 저장된 패스키를 고르는 discoverable 로그인은 이 예제에서 다루지 않음)
+async fn start_login(
+    State(state): State<AppState>,
+    jar: CookieJar,
+    Json(input): Json<LoginRequest>,
+) -> Result<(CookieJar, Json<RequestChallengeResponse>), AppError> {
+    let username = input.username.trim().to_owned();
+    let passkeys: Vec<Passkey> = state
+        .users
+        .lock()
+        .unwrap()
+        .get(&username)
+        .map(|user| {
+            user.passkeys
+                .iter()
+                .map(|stored| stored.passkey.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+    if passkeys.is_empty() {
+        return Err(AppError::UnknownUser);
+    }
+    let (challenge, authentication) = state.webauthn.start_passkey_authentication(&passkeys)?;
+    let id = state.ceremonies.insert(Ceremony::Authentication {
+        username,
+        state: authentication,
+    });
+    Ok((jar.add(ceremony_cookie(id)), Json(challenge)))
+}
+
+/// 로그인 2단계: 서명을 확인하고 세션 발급
+async fn finish_login(
+    State(state): State<AppState>,
+    jar: CookieJar,
+    Json(credential): Json<PublicKeyCredential>,
+) -> Result<(CookieJar, Json<Value>), AppError> {
+    let (jar, ceremony) = take_ceremony(&state, jar);
+    let Some(Ceremony::Authentication {
+        username,
+        state: authentication,
+    }) = ceremony
+    else {
+        return Err(AppError::NoCeremony);
+    };
+    // 서명 카운터가 줄어든 경우(복제된 인증기 의심)도 여기서 거부됨
+    let result = state
+        .webauthn
+        .finish_passkey_authentication(&credential, &authentication)?;
+
+    let label = {
+        let mut users = state.users.lock().unwrap();
+        // start와 finish 사이에 패스키가 삭제되었을 수 있음
+        let stored = users
+            .get_mut(&username)
+            .and_then(|user| {
+                user.passkeys
+                    .iter_mut()
+                    .find(|stored| stored.passkey.cred_id() == result.cred_id())
+            })
+            .ok_or(AppError::Unauthenticated)?;
+        // 서명 카운터와 백업 상태를 갱신
+        stored.passkey.update_credential(&result);
+        stored.last_used_at = Some(unix_now());
+        stored.label.clone()
+    };
+    tracing::info!(username, label, "logged in with passkey");
+
+    // 로그인 전 세션이 있으면 버리고 새 ID 발급
+    if let Some(old) = jar.get(SESSION_COOKIE) {
+        state.sessions.remove(old.value());
+    }
+    let jar = start_session(&state, jar, username.clone());
+    Ok((jar, Json(json!({ "username": username, "passkey": label }))))
+}
+
+async fn me(State(state): State<AppState>, AuthUser(username): AuthUser) -> Json<Value> {
+    let users = state.users.lock().unwrap();
+    let passkeys: Vec<Value> = users
+        .get(&username)
+        .map(|user| user.passkeys.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .map(|stored| {
+            json!({
+                // base64url 문자열, `DELETE /passkeys/{id}`에 사용
+                "id": stored.passkey.cred_id(),
+                "label": stored.label,
+                "created_at": stored.created_at,
+                "last_used_at": stored.last_used_at,
+            })
+        })
+        .collect();
+    Json(json!({ "username": username, "passkeys": passkeys }))
+}
+
+/// 잃어버린 기기의 패스키 삭제 (로그인할 방법이 없어지지 않게 마지막 하나는 남김)
+async fn delete_passkey(
+    State(state): State<AppState>,
+    AuthUser(username): AuthUser,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let id: CredentialID =
+        serde_json::from_value(Value::String(id)).map_err(|_| AppError::PasskeyNotFound)?;
+    let mut users = state.users.lock().unwrap();
+    let user = users.get_mut(&username).ok_or(AppError::PasskeyNotFound)?;
+    let index = user
+        .passkeys
+        .iter()
+        .position(|stored| stored.passkey.cred_id() == &id)
+        .ok_or(AppError::PasskeyNotFound)?;
+    if user.passkeys.len() == 1 {
+        return Err(AppError::Conflict("cannot remove the last passkey"));
+    }
+    let removed = user.passkeys.remove(index);
+    tracing::info!(username, label = removed.label, "passkey removed");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn logout(State(state): State<AppState>, jar: CookieJar) -> (CookieJar, StatusCode) {
+    if let Some(cookie) = jar.get(SESSION_COOKIE) {
+        state.sessions.remove(cookie.value());
+    }
+    (
+        jar.remove(Cookie::build(SESSION_COOKIE).path("/")),
+        StatusCode::NO_CONTENT,
+    )
+}
+
+/// 로그인한 사용자 이름
+struct AuthUser(String);
+
+impl FromRequestParts<AppState> for AuthUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let jar = CookieJar::from_headers(&parts.headers);
+        current_user(state, &jar)
+            .map(Self)
+            .ok_or(AppError::Unauthenticated)
+    }
+}
+
+fn current_user(state: &AppState, jar: &CookieJar) -> Option<String> {
+    let id = jar.get(SESSION_COOKIE)?;
+    state.sessions.get(id.value())
+}
+
+fn start_session(state: &AppState, jar: CookieJar, username: String) -> CookieJar {
+    let id = state.sessions.insert(username);
+    // HTTPS로 서비스할 때는 `.secure(true)` 추가
+    jar.add(
+        Cookie::build((SESSION_COOKIE, id))
+            .http_only(true)
+            .same_site(SameSite::Lax)
+            .path("/")
+            .build(),
+    )
+}
+
+fn ceremony_cookie(id: String) -> Cookie<'static> {
+    Cookie::build((CEREMONY_COOKIE, id))
+        .http_only(true)
+        .same_site(SameSite::Strict)
+        .path("/")
+        .max_age(CEREMONY_TTL.try_into().unwrap())
+        .build()
+}
+
+// 진행 중인 절차를 꺼내고 쿠키를 지움
+// (확인에 실패해 쿠키가 남더라도 서버의 상태는 이미 지워졌으므로 challenge는 한 번만 사용됨)
+fn take_ceremony(state: &AppState, jar: CookieJar) -> (CookieJar, Option<Ceremony>) {
+    let ceremony = jar
+        .get(CEREMONY_COOKIE)
+        .and_then(|cookie| state.ceremonies.take(cookie.value()));
+    (
+        jar.remove(Cookie::build(CEREMONY_COOKIE).path("/")),
+        ceremony,
+    )
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[derive(Debug)]
+enum AppError {
+    InvalidUsername,
+    /// 진행 중인 등록/로그인이 없거나 만료됨
+    NoCeremony,
+    UnknownUser,
+    Unauthenticated,
+    PasskeyNotFound,
+    Conflict(&'static str),
+    /// challenge, origin, 서명 등 확인 실패
+    Webauthn(WebauthnError),
+}
+
+impl From<WebauthnError> for AppError {
+    fn from(err: WebauthnError) -> Self {
+        Self::Webauthn(err)
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            Self::InvalidUsername => (
+                StatusCode::BAD_REQUEST,
+                "username must be 1 to 64 characters",
+            ),
+            Self::NoCeremony => (
+                StatusCode::BAD_REQUEST,
+                "no registration or login in progress, start again",
+            ),
+            Self::UnknownUser => (
+                StatusCode::NOT_FOUND,
+                "no passkeys registered for this username",
+            ),
+            Self::Unauthenticated => (StatusCode::UNAUTHORIZED, "not logged in"),
+            Self::PasskeyNotFound => (StatusCode::NOT_FOUND, "passkey not found"),
+            Self::Conflict(message) => (StatusCode::CONFLICT, message),
+            Self::Webauthn(err) => {
+                // 자세한 이유는 로그에만 남김
+                tracing::warn!(%err, "webauthn verification failed");
+                (StatusCode::BAD_REQUEST, "passkey verification failed")
+            }
+        };
+        (status, message).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::header, http::Request};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    /// 쿠키(세션, 진행 중인 절차)를 기억하는 테스트 클라이언트
+    struct Client {
+        router: Router,
+        state: AppState,
+        cookies: HashMap<String, String>,
+    }
+
+    impl Client {
+        fn new() -> Self {
+            let state = AppState::new();
+            Self {
+                router: app(state.clone()),
+                state,
+                cookies: HashMap::new(),
+            }
+        }
+
+        async fn send(
+            &mut self,
+            method: &str,
+            uri: &str,
+            body: Option<Value>,
+        ) -> (StatusCode, Value) {
+            let mut request = Request::builder().method(method).uri(uri);
+            if !self.cookies.is_empty() {
+                let cookies: Vec<String> = self
+                    .cookies
+                    .iter()
+                    .map(|(name, value)| format!("{name}={value}"))
+                    .collect();
+                request = request.header(header::COOKIE, cookies.join("; "));
+            }
+            let request = match body {
+                Some(body) => request
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(body.to_string())),
+                None => request.body(Body::empty()),
+            }
+            .unwrap();
+
+            let response = self.router.clone().oneshot(request).await.unwrap();
+            for cookie in response.headers().get_all(header::SET_COOKIE) {
+                let cookie = Cookie::parse(cookie.to_str().unwrap().to_owned()).unwrap();
+                if cookie.value().is_empty() {
+                    self.cookies.remove(cookie.name());
+                } else {
+                    self.cookies
+                        .insert(cookie.name().to_owned(), cookie.value().to_owned());
+                }
+            }
+            let status = response.status();
+            let bytes = response.into_body().collect().await.unwrap().to_bytes();
+            let body = serde_json::from_slice(&bytes)
+                .unwrap_or_else(|_| String::from_utf8_lossy(&bytes).into());
+            (status, body)
+        }
+
+        /// 인증기 없이 시험할 수 있도록 패스키가 없는 사용자를 만들고 로그인
+        fn log_in_as(&mut self, username: &str) {
+            self.state.users.lock().unwrap().insert(
+                username.to_owned(),
+                User {
+                    id: Uuid::new_v4(),
+                    passkeys: Vec::new(),
+                },
+            );
+            let id = self.state.sessions.insert(username.to_owned());
+            self.cookies.insert(SESSION_COOKIE.to_owned(), id);
+        }
+    }
+
+    // 형식만 맞는 (서명 확인은 실패하는) 인증기 응답
+    fn bogus_attestation() -> Value {
+        json!({
+            "id": "AAAA",
+            "rawId": "AAAA",
+            "type": "public-key",
+            "response": { "attestationObject": "AAAA", "clientDataJSON": "AAAA" },
+        })
+    }
+
+    #[tokio::test]
+    async fn registration_challenge_is_kept_server_side_and_used_once() {
+        let mut client = Client::new();
+
+        let (status, page) = client.send("GET", "/", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(page.as_str().unwrap().contains("navigator.credentials"));
+
+        let input = json!({ "username": "alice" });
+        let (status, challenge) = client.send("POST", "/register/start", Some(input)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(challenge["publicKey"]["rp"]["id"], RP_ID);
+        assert_eq!(challenge["publicKey"]["user"]["name"], "alice");
+        assert!(challenge["publicKey"]["challenge"].is_string());
+        // 쿠키에는 키만 있고 상태는 서버에
+        assert!(client.cookies.contains_key(CEREMONY_COOKIE));
+
+        // 확인에 실패해도 challenge는 소모됨
+        let (status, body) = client
+            .send("POST", "/register/finish", Some(bogus_attestation()))
+            .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body, "passkey verification failed");
+
+        let (status, body) = client
+            .send("POST", "/register/finish", Some(bogus_attestation()))
+            .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body, "no registration or login in progress, start again");
+
+        // 아무 계정도 만들어지지 않음
+        assert!(client.state.users.lock().unwrap().is_empty());
+        let (status, _) = client.send("GET", "/me", None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn only_the_owner_can_add_passkeys_to_an_existing_username() {
+        let mut owner = Client::new();
+        owner.log_in_as("alice");
+        let mut stranger = Client {
+            router: owner.router.clone(),
+            state: owner.state.clone(),
+            cookies: HashMap::new(),
+        };
+
+        let input = json!({ "username": "alice" });
+        let (status, _) = stranger
+            .send("POST", "/register/start", Some(input.clone()))
+            .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let (status, challenge) = owner.send("POST", "/register/start", Some(input)).await;
+        assert_eq!(status, StatusCode::OK);
+        // 같은 사용자 핸들로 등록
+        let user_id = owner.state.users.lock().unwrap()["alice"].id;
+        let handle: CredentialID =
+            serde_json::from_value(challenge["publicKey"]["user"]["id"].clone()).unwrap();
+        assert_eq!(handle.as_ref(), user_id.as_bytes());
+
+        let (status, _) = owner
+            .send("POST", "/register/start", Some(json!({ "username": "  " })))
+            .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn login_and_passkey_management_need_registered_passkeys() {
+        let mut client = Client::new();
+
+        let input = json!({ "username": "nobody" });
+        let (status, _) = client.send("POST", "/login/start", Some(input)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = client.send("DELETE", "/passkeys/AAAA", None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        client.log_in_as("alice");
+        let (status, me) = client.send("GET", "/me", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(me, json!({ "username": "alice", "passkeys": [] }));
+        let (status, _) = client.send("DELETE", "/passkeys/AAAA", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // 패스키가 없는 사용자는 로그인 challenge를 받을 수 없음
+        let input = json!({ "username": "alice" });
+        let (status, _) = client.send("POST", "/login/start", Some(input)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _) = client.send("POST", "/logout", None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = client.send("GET", "/me", None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+}
+
+// 🧪 테스트 방법
+//
+// 1. cargo run -p example-webauthn
+// 2. 브라우저에서 http://localhost:3000 열기 (127.0.0.1이 아니라 localhost)
+// 3. 사용자 이름을 입력하고 "Register" → 기기의 지문/얼굴/PIN 또는 보안 키로 등록
+//    → 새 계정이면 바로 로그인되고 아래에 패스키 목록이 표시됨
+// 4. 로그인한 상태에서 다른 이름(label)으로 "Register"를 한 번 더 → 같은 계정에 두 번째 패스키
+//    (이미 등록한 인증기는 브라우저가 거부함)
+// 5. "Log out" 후 사용자 이름만 입력하고 "Log in" → 등록한 패스키 중 아무거나로 로그인
+// 6. 목록에서 "Remove"로 패스키 삭제 (마지막 하나는 409 Conflict)
+// 7. 로그인하지 않은 다른 브라우저에서 같은 이름으로 "Register" → 409 Conflict
@@ -0,0 +1,89 @@
+//! ⏳ 만료 시간이 있는 메모리 저장소 (세션, 진행 중인 WebAuthn 절차)
+//!
+//! - 키는 32바이트 난수(hex) → 쿠키에 담아 브라우저에 전달
+//! - 진행 중인 절차(challenge)는 `take`로 꺼내서 한 번만 사용 → 같은 응답을 다시 보내도 실패
+//! - 만료된 항목은 꺼낼 때 지움 (예제라서 주기적인 정리는 생략)
+
+use rand::RngCore;
+use std::{collections::HashMap, fmt::Write, sync::Mutex, time::Duration};
+use tokio::time::Instant;
+
+pub struct ExpiringStore<T> {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (T, Instant)>>,
+}
+
+impl<T: Clone> ExpiringStore<T> {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::default(),
+        }
+    }
+
+    /// 값을 저장하고 새 키를 반환
+    pub fn insert(&self, value: T) -> String {
+        let id = new_id();
+        let expires_at = Instant::now() + self.ttl;
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(id.clone(), (value, expires_at));
+        id
+    }
+
+    pub fn get(&self, id: &str) -> Option<T> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(id) {
+            Some((value, expires_at)) if *expires_at > Instant::now() => Some(value.clone()),
+            Some(_) => {
+                entries.remove(id);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// 꺼내면서 지움 (한 번만 사용할 값)
+    pub fn take(&self, id: &str) -> Option<T> {
+        let (value, expires_at) = self.entries.lock().unwrap().remove(id)?;
+        (expires_at > Instant::now()).then_some(value)
+    }
+
+    pub fn remove(&self, id: &str) {
+        self.entries.lock().unwrap().remove(id);
+    }
+}
+
+fn new_id() -> String {
+    let mut bytes = [0; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes.iter().fold(String::new(), |mut id, b| {
+        let _ = write!(id, "{b:02x}");
+        id
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn taken_and_expired_entries_are_gone() {
+        tokio::time::pause();
+        let store = ExpiringStore::new(Duration::from_secs(60));
+
+        let id = store.insert("challenge");
+        assert_eq!(store.get(&id), Some("challenge"));
+        assert_eq!(store.take(&id), Some("challenge"));
+        assert_eq!(store.take(&id), None);
+
+        let id = store.insert("challenge");
+        tokio::time::advance(Duration::from_secs(60)).await;
+        assert_eq!(store.get(&id), None);
+
+        let id = store.insert("challenge");
+        tokio::time::advance(Duration::from_secs(60)).await;
+        assert_eq!(store.take(&id), None);
+    }
+}
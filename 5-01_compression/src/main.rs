@@ -7,6 +7,8 @@ use tower_http::{
 };
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+mod streaming;
+
 /// 🧪 테스트 구조
 #[cfg(test)]
 mod tests;
@@ -37,6 +39,7 @@ async fn main() {
 }
 
 /// 📦 app() 함수
+/// 스트리밍 라우트(`streaming` 모듈)는 최소 크기와 SSE 제외 조건을 둔 별도 압축 레이어를 사용
 fn app() -> Router {
     Router::new()
         .route("/", post(root)) // POST / → root 핸들러로 연결
@@ -47,6 +50,7 @@ fn app() -> Router {
                 // 2️⃣ 응답을 클라이언트가 요청한 방식으로 압축
                 .layer(CompressionLayer::new()),
         )
+        .merge(streaming::router())
 }
 
 /// 🧾 핸들러 root
@@ -54,3 +58,19 @@ async fn root(Json(value): Json<Value>) -> Json<Value> {
     // JSON body 를 그대로 echo 하듯 응답
     Json(value)
 }
+
+// 🧪 테스트 방법
+//
+// 1. 스트리밍 JSON Lines (압축됨, Content-Length 없이 chunked):
+//    curl -si -H 'Accept-Encoding: zstd, br, gzip' 'localhost:3000/export.ndjson?rows=5' -o /dev/null -D -
+//    curl -s --compressed 'localhost:3000/export.ndjson?rows=5'
+//
+// 2. q 값으로 인코딩 선택:
+//    curl -si -H 'Accept-Encoding: gzip, br;q=0.5' 'localhost:3000/export.ndjson?rows=5' -o /dev/null -D -
+//    → content-encoding: gzip
+//
+// 3. SSE는 압축하지 않음 (이벤트가 0.1초마다 바로 도착):
+//    curl -N -H 'Accept-Encoding: gzip' 'localhost:3000/events?count=5'
+//
+// 4. 1 KiB 미만 응답은 압축하지 않음:
+//    curl -si -H 'Accept-Encoding: gzip' localhost:3000/status
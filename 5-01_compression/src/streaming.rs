@@ -0,0 +1,94 @@
+//! 🌊 스트리밍 응답 + 압축
+//!
+//! • `GET /export.ndjson?rows=N`: JSON Lines를 한 줄씩 chunked로 전송 → 압축됨
+//! • `GET /events?count=N`: SSE 이벤트 스트림 → 압축하지 않음
+//! • `GET /status`: 작은 JSON (최소 크기 미만) → 압축하지 않음
+//!
+//! 압축 여부는 `compress_when` 조건으로 정함
+//! • `SizeAbove`: 크기를 알 수 있는 응답(`Content-Length`, 정확한 size hint)만 비교함
+//!   스트리밍 응답은 크기를 모르므로 항상 압축 대상
+//! • `NotForContentType::SSE`: 압축기는 블록이 찰 때까지 출력을 모아 두기 때문에
+//!   SSE를 압축하면 이벤트가 제때 도착하지 않음 (기본 조건에도 들어 있지만 직접 조합할 때 빠뜨리기 쉬움)
+//! • 인코딩은 `Accept-Encoding`의 q 값으로 고르고, 같으면 zstd > br > gzip > deflate 순
+
+use axum::{
+    body::{Body, Bytes},
+    extract::Query,
+    http::header,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse,
+    },
+    routing::get,
+    Json, Router,
+};
+use futures_util::{stream, Stream, StreamExt};
+use serde::Deserialize;
+use serde_json::json;
+use std::{convert::Infallible, time::Duration};
+use tower_http::compression::{
+    predicate::{NotForContentType, Predicate, SizeAbove},
+    CompressionLayer,
+};
+
+// 이보다 작은 응답은 압축해도 이득이 적음 (헤더와 CPU 비용이 더 큼)
+pub const MIN_COMPRESS_SIZE: u16 = 1024;
+
+pub fn router() -> Router {
+    let predicate = SizeAbove::new(MIN_COMPRESS_SIZE)
+        .and(NotForContentType::GRPC)
+        .and(NotForContentType::IMAGES)
+        .and(NotForContentType::SSE);
+
+    Router::new()
+        .route("/export.ndjson", get(export))
+        .route("/events", get(events))
+        .route("/status", get(status))
+        .layer(CompressionLayer::new().compress_when(predicate))
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamParams {
+    rows: Option<usize>,
+    count: Option<usize>,
+}
+
+/// 📤 JSON Lines 내보내기 (행마다 chunk 하나, 전체 크기는 미리 알 수 없음)
+async fn export(Query(params): Query<StreamParams>) -> impl IntoResponse {
+    let rows = params.rows.unwrap_or(10_000);
+    let lines = stream::iter(0..rows).map(|id| {
+        let mut line = serde_json::to_vec(&json!({
+            "id": id,
+            "sku": format!("SKU-{id:06}"),
+            "description": "streamed row (repetitive text compresses well)",
+        }))
+        .expect("serializing a json! value does not fail");
+        line.push(b'\n');
+        Ok::<_, Infallible>(Bytes::from(line))
+    });
+
+    (
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(lines),
+    )
+}
+
+/// 📡 SSE 이벤트 (압축 제외 대상)
+async fn events(
+    Query(params): Query<StreamParams>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let count = params.count.unwrap_or(usize::MAX);
+    let events = stream::iter(0..count).then(|n| async move {
+        if n > 0 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        Ok(Event::default().event("tick").data(n.to_string()))
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+/// 🩺 작은 JSON (Content-Length가 정해져 있어 `SizeAbove`가 비교할 수 있음)
+async fn status() -> Json<serde_json::Value> {
+    Json(json!({ "status": "ok" }))
+}
@@ -216,3 +216,121 @@ fn compress_zstd(json: &Value) -> Vec<u8> {
     let request_body = serde_json::to_vec(&json).unwrap();
     zstd::stream::encode_all(std::io::Cursor::new(request_body), 4).unwrap()
 }
+
+// --- 🌊 스트리밍 응답 (streaming 모듈)
+
+/// GET 요청 (Accept-Encoding 지정 가능)
+async fn get_with_encoding(uri: &str, accept_encoding: Option<&str>) -> Response {
+    let mut request = http::Request::get(uri);
+    if let Some(accept_encoding) = accept_encoding {
+        request = request.header(header::ACCEPT_ENCODING, accept_encoding);
+    }
+    app()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+fn content_encoding(response: &Response) -> Option<&str> {
+    response
+        .headers()
+        .get(header::CONTENT_ENCODING)
+        .map(|value| value.to_str().unwrap())
+}
+
+fn decompress(encoding: &str, bytes: &[u8]) -> Vec<u8> {
+    let mut decompressed = Vec::new();
+    match encoding {
+        "gzip" => {
+            GzDecoder::new(bytes)
+                .read_to_end(&mut decompressed)
+                .unwrap();
+        }
+        "br" => {
+            brotli::BrotliDecompress(&mut &bytes[..], &mut decompressed).unwrap();
+        }
+        "zstd" => decompressed = zstd::stream::decode_all(bytes).unwrap(),
+        other => panic!("unexpected encoding {other}"),
+    }
+    decompressed
+}
+
+/// ✅ 크기를 모르는 스트리밍 응답도 gzip/br/zstd 모두로 압축
+#[tokio::test]
+async fn compress_streamed_json_lines_with_each_encoding() {
+    for encoding in ["gzip", "br", "zstd"] {
+        let response = get_with_encoding("/export.ndjson?rows=500", Some(encoding)).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(content_encoding(&response), Some(encoding));
+        // 압축 후 크기를 미리 알 수 없으므로 Content-Length 없이 전송
+        assert!(!response.headers().contains_key(header::CONTENT_LENGTH));
+
+        let compressed = byte_from_response(response).await;
+        let body = decompress(encoding, &compressed);
+        assert!(
+            compressed.len() * 5 < body.len(),
+            "{encoding} should shrink"
+        );
+
+        let rows: Vec<Value> = body
+            .split(|&byte| byte == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice(line).unwrap())
+            .collect();
+        assert_eq!(rows.len(), 500);
+        assert_eq!(rows[499]["sku"], "SKU-000499");
+    }
+}
+
+/// ✅ Accept-Encoding의 q 값과 우선순위에 따라 인코딩 선택
+#[tokio::test]
+async fn negotiate_encoding_by_quality() {
+    let cases = [
+        ("gzip, br;q=0.5", Some("gzip")),
+        ("gzip;q=0.2, zstd;q=0.9, br;q=0.5", Some("zstd")),
+        // q 값이 같으면 서버 우선순위 (zstd > br > gzip)
+        ("gzip, br", Some("br")),
+        ("zstd;q=0, gzip", Some("gzip")),
+        ("identity", None),
+    ];
+    for (accept_encoding, expected) in cases {
+        let response = get_with_encoding("/export.ndjson?rows=10", Some(accept_encoding)).await;
+        assert_eq!(content_encoding(&response), expected, "{accept_encoding}");
+    }
+
+    let response = get_with_encoding("/export.ndjson?rows=10", None).await;
+    assert_eq!(content_encoding(&response), None);
+    let body = byte_from_response(response).await;
+    assert_eq!(body.iter().filter(|&&byte| byte == b'\n').count(), 10);
+}
+
+/// ✅ SSE는 클라이언트가 압축을 받아도 그대로 보냄
+#[tokio::test]
+async fn do_not_compress_event_streams() {
+    let response = get_with_encoding("/events?count=3", Some("gzip, br, zstd")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/event-stream"
+    );
+    assert_eq!(content_encoding(&response), None);
+
+    let body = byte_from_response(response).await;
+    let body = std::str::from_utf8(&body).unwrap();
+    assert!(body.contains("event: tick\ndata: 0\n\n"));
+    assert!(body.contains("event: tick\ndata: 2\n\n"));
+}
+
+/// ✅ 최소 크기(1 KiB) 미만의 응답은 압축하지 않음
+#[tokio::test]
+async fn do_not_compress_below_minimum_size() {
+    let response = get_with_encoding("/status", Some("gzip")).await;
+
+    assert_eq!(content_encoding(&response), None);
+    assert_json_eq!(
+        json_from_response(response).await,
+        json!({ "status": "ok" })
+    );
+}
@@ -0,0 +1,457 @@
+//! 📝 요청/응답 본문 로깅 레이어 (`BodyLogLayer`)
+//!
+//! `print_request_body` 미들웨어를 여러 서비스에서 재사용할 수 있게 다듬은 버전
+//!
+//! • 크기 제한: 길이를 알 수 없거나 `max_size`보다 큰 본문은 버퍼링하지 않고 그대로 흘려보냄
+//! • 비밀값 가리기: JSON/폼 본문에서 지정한 필드(기본 `password`, `token`)는 `[REDACTED]`로 바꿔서 기록
+//! • 바이너리 건너뛰기: 이미지, 파일 등 텍스트가 아닌 `Content-Type`은 본문을 읽지 않음
+//! • `Content-Type`이 없으면 어떤 형식인지(가릴 필드가 어디인지) 모르므로 본문 대신 다이제스트만 기록
+//! • 다이제스트: 원본 본문의 SHA-256을 tracing span(`request.digest`, `response.digest`)에 기록
+//!   → 로그에 본문을 남기지 않아도 "같은 요청이 다시 왔는지" 비교 가능
+//!
+//! ```rust,ignore
+//! Router::new()
+//!     .route("/login", post(login))
+//!     .layer(BodyLogLayer::new().max_size(16 * 1024).redact("secret"));
+//! ```
+
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    extract::Request,
+    http::{header::CONTENT_TYPE, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::{
+    fmt::Write,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+use tracing::{field::Empty, Instrument, Span};
+
+const DEFAULT_MAX_SIZE: usize = 64 * 1024;
+const REDACTED: &str = "[REDACTED]";
+
+#[derive(Debug, Clone)]
+pub struct BodyLogLayer {
+    max_size: usize,
+    redact: Vec<String>,
+}
+
+impl Default for BodyLogLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BodyLogLayer {
+    /// 64 KiB 까지 기록, `password`와 `token` 필드는 가림
+    pub fn new() -> Self {
+        Self {
+            max_size: DEFAULT_MAX_SIZE,
+            redact: vec!["password".to_owned(), "token".to_owned()],
+        }
+    }
+
+    /// 이보다 큰 본문은 버퍼링하지 않음 (메모리 보호)
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// 가릴 필드 추가 (대소문자 구분 없음, JSON은 중첩된 객체와 배열 안까지, 폼은 같은 이름의 값 모두)
+    pub fn redact(mut self, field: impl Into<String>) -> Self {
+        self.redact.push(field.into());
+        self
+    }
+}
+
+impl<S> Layer<S> for BodyLogLayer {
+    type Service = BodyLog<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BodyLog {
+            inner,
+            config: Arc::new(self.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BodyLog<S> {
+    inner: S,
+    config: Arc<BodyLogLayer>,
+}
+
+impl<S> Service<Request> for BodyLog<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // poll_ready를 통과한 서비스를 사용하고, 복제본은 다음 호출을 위해 남겨 둠
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let config = self.config.clone();
+
+        let span = tracing::debug_span!(
+            "body_log",
+            method = %request.method(),
+            uri = %request.uri(),
+            request.digest = Empty,
+            response.digest = Empty,
+        );
+
+        Box::pin(
+            async move {
+                let (parts, body) = request.into_parts();
+                let body = match config
+                    .capture(Direction::Request, &parts.headers, body)
+                    .await
+                {
+                    Ok(body) => body,
+                    Err(err) => {
+                        return Ok((StatusCode::BAD_REQUEST, err.to_string()).into_response())
+                    }
+                };
+
+                let response = inner.call(Request::from_parts(parts, body)).await?;
+
+                let (parts, body) = response.into_parts();
+                let body = match config
+                    .capture(Direction::Response, &parts.headers, body)
+                    .await
+                {
+                    Ok(body) => body,
+                    Err(err) => {
+                        tracing::warn!(%err, "failed to read response body");
+                        return Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+                    }
+                };
+                Ok(Response::from_parts(parts, body))
+            }
+            .instrument(span),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Direction {
+    Request,
+    Response,
+}
+
+impl Direction {
+    fn digest_field(self) -> &'static str {
+        match self {
+            Direction::Request => "request.digest",
+            Direction::Response => "response.digest",
+        }
+    }
+}
+
+/// 로그에 본문을 어떻게 남길지 (`Content-Type`으로 판단)
+#[derive(Debug, PartialEq, Eq)]
+enum Kind {
+    Json,
+    Form,
+    Text,
+    /// `Content-Type` 없음: 다이제스트만
+    Untyped,
+    Binary,
+}
+
+impl BodyLogLayer {
+    /// 본문을 읽어 기록하고, 같은 내용으로 다시 만든 본문을 반환
+    async fn capture(
+        &self,
+        direction: Direction,
+        headers: &HeaderMap,
+        body: Body,
+    ) -> Result<Body, axum::Error> {
+        let kind = kind(headers);
+        if kind == Kind::Binary {
+            tracing::debug!(?direction, "skipping binary body");
+            return Ok(body);
+        }
+        // 길이를 모르는 스트리밍 본문이나 큰 본문은 끝까지 읽지 않음
+        match body.size_hint().upper() {
+            Some(0) => return Ok(body),
+            Some(len) if len <= self.max_size as u64 => {}
+            len => {
+                tracing::debug!(?direction, ?len, "body too large to log");
+                return Ok(body);
+            }
+        }
+
+        let bytes = axum::body::to_bytes(body, self.max_size).await?;
+        Span::current().record(direction.digest_field(), sha256_hex(&bytes));
+
+        let logged = match kind {
+            Kind::Json => match serde_json::from_slice::<Value>(&bytes) {
+                Ok(mut value) => {
+                    redact(&mut value, &self.redact);
+                    value.to_string()
+                }
+                // 파싱하지 못하면 어떤 필드를 가려야 할지 모르므로 기록하지 않음
+                Err(_) => "<invalid JSON>".to_owned(),
+            },
+            Kind::Form => redact_form(&bytes, &self.redact),
+            Kind::Untyped => {
+                tracing::debug!(?direction, "body without content-type, digest only");
+                return Ok(Body::from(bytes));
+            }
+            _ => String::from_utf8_lossy(&bytes).into_owned(),
+        };
+        tracing::debug!(?direction, body = %logged);
+
+        Ok(Body::from(bytes))
+    }
+}
+
+fn kind(headers: &HeaderMap) -> Kind {
+    let Some(content_type) = headers.get(CONTENT_TYPE) else {
+        return Kind::Untyped;
+    };
+    let essence = content_type
+        .to_str()
+        .unwrap_or_default()
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    if essence == "application/json" || essence.ends_with("+json") {
+        Kind::Json
+    } else if essence == "application/x-www-form-urlencoded" {
+        Kind::Form
+    } else if essence.starts_with("text/")
+        || essence.ends_with("+xml")
+        || matches!(
+            essence.as_str(),
+            "application/xml" | "application/javascript"
+        )
+    {
+        Kind::Text
+    } else {
+        Kind::Binary
+    }
+}
+
+fn redact(value: &mut Value, fields: &[String]) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if fields.iter().any(|field| field.eq_ignore_ascii_case(key)) {
+                    *value = Value::String(REDACTED.to_owned());
+                } else {
+                    redact(value, fields);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| redact(item, fields)),
+        _ => {}
+    }
+}
+
+// `username=alice&password=hunter2` → `username=alice&password=%5BREDACTED%5D`
+fn redact_form(bytes: &[u8], fields: &[String]) -> String {
+    let mut serializer = form_urlencoded::Serializer::new(String::new());
+    for (key, value) in form_urlencoded::parse(bytes) {
+        if fields.iter().any(|field| field.eq_ignore_ascii_case(&key)) {
+            serializer.append_pair(&key, REDACTED);
+        } else {
+            serializer.append_pair(&key, &value);
+        }
+    }
+    serializer.finish()
+}
+
+fn sha256_hex(bytes: &Bytes) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::post, Router};
+    use serde_json::json;
+    use std::sync::Mutex;
+    use tower::ServiceExt;
+
+    #[test]
+    fn redacts_nested_fields() {
+        let fields = BodyLogLayer::new().redact("secret").redact;
+        let mut value = json!({
+            "username": "alice",
+            "Password": "hunter2",
+            "sessions": [{ "token": "abc", "device": "phone" }],
+            "profile": { "secret": 42 }
+        });
+        redact(&mut value, &fields);
+        assert_eq!(
+            value,
+            json!({
+                "username": "alice",
+                "Password": REDACTED,
+                "sessions": [{ "token": REDACTED, "device": "phone" }],
+                "profile": { "secret": REDACTED }
+            })
+        );
+    }
+
+    #[test]
+    fn classifies_content_types() {
+        let kind_of = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(CONTENT_TYPE, value.parse().unwrap());
+            kind(&headers)
+        };
+        assert_eq!(kind_of("application/json; charset=utf-8"), Kind::Json);
+        assert_eq!(kind_of("application/problem+json"), Kind::Json);
+        assert_eq!(kind_of("application/x-www-form-urlencoded"), Kind::Form);
+        assert_eq!(kind_of("text/plain"), Kind::Text);
+        assert_eq!(kind_of("image/png"), Kind::Binary);
+        assert_eq!(kind_of("application/octet-stream"), Kind::Binary);
+        assert_eq!(kind(&HeaderMap::new()), Kind::Untyped);
+    }
+
+    // 로그를 메모리에 모아 두는 writer
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    async fn send(app: Router, request: Request) -> (Bytes, String) {
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::DEBUG)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let response = app.oneshot(request).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let logs = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        (body, logs)
+    }
+
+    fn echo_app(layer: BodyLogLayer) -> Router {
+        Router::new()
+            .route(
+                "/",
+                post(|headers: HeaderMap, body: Bytes| async move {
+                    ([(CONTENT_TYPE, headers[CONTENT_TYPE].clone())], body)
+                }),
+            )
+            .layer(layer)
+    }
+
+    fn post_body(content_type: &str, body: impl Into<Body>) -> Request {
+        Request::post("/")
+            .header(CONTENT_TYPE, content_type)
+            .body(body.into())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn logs_redacted_json_and_digest_but_passes_original_body() {
+        let body = r#"{"username":"alice","password":"hunter2"}"#;
+        let (echoed, logs) = send(
+            echo_app(BodyLogLayer::new()),
+            post_body("application/json", body),
+        )
+        .await;
+
+        // 핸들러와 클라이언트는 원본 그대로 받음
+        assert_eq!(echoed, body);
+        assert!(logs.contains(r#"{"password":"[REDACTED]","username":"alice"}"#));
+        assert!(!logs.contains("hunter2"));
+        assert!(logs.contains(&format!(
+            "request.digest=\"{}\"",
+            sha256_hex(&Bytes::from(body))
+        )));
+        assert!(logs.contains("response.digest"));
+    }
+
+    #[tokio::test]
+    async fn redacts_form_fields_and_untyped_bodies() {
+        let body = "username=alice&Password=hunter2&token=abc";
+        let (echoed, logs) = send(
+            echo_app(BodyLogLayer::new()),
+            post_body("application/x-www-form-urlencoded", body),
+        )
+        .await;
+        assert_eq!(echoed, body);
+        assert!(logs.contains("username=alice&Password=%5BREDACTED%5D&token=%5BREDACTED%5D"));
+        assert!(!logs.contains("hunter2"));
+
+        // Content-Type 없는 JSON → 본문은 남기지 않고 다이제스트만
+        let body = r#"{"password":"hunter2"}"#;
+        let app = Router::new()
+            .route("/", post(|body: Bytes| async move { body }))
+            .layer(BodyLogLayer::new());
+        let (echoed, logs) = send(app, Request::post("/").body(Body::from(body)).unwrap()).await;
+        assert_eq!(echoed, body);
+        assert!(logs.contains("digest only"));
+        assert!(!logs.contains("hunter2"));
+        assert!(logs.contains(&format!(
+            "request.digest=\"{}\"",
+            sha256_hex(&Bytes::from(body))
+        )));
+    }
+
+    #[tokio::test]
+    async fn skips_binary_and_oversized_bodies() {
+        let png = vec![0x89, b'P', b'N', b'G', 0, 1, 2, 3];
+        let (echoed, logs) = send(
+            echo_app(BodyLogLayer::new()),
+            post_body("image/png", png.clone()),
+        )
+        .await;
+        assert_eq!(echoed, png);
+        assert!(logs.contains("skipping binary body"));
+        assert!(!logs.contains("request.digest"));
+
+        let large = "x".repeat(100);
+        let (echoed, logs) = send(
+            echo_app(BodyLogLayer::new().max_size(10)),
+            post_body("text/plain", large.clone()),
+        )
+        .await;
+        assert_eq!(echoed, large);
+        assert!(logs.contains("body too large to log"));
+        assert!(!logs.contains("xxxxxxxxxx"));
+    }
+}
@@ -2,6 +2,12 @@
 //! > 요청 바디(Request Body)를 미들웨어 또는 추출기에서 선(先) 소비하는 방법을 설명
 //! > Rust 서버 개발에서 흔히 부딪히는 “한 번 읽은 Body는 다시 읽을 수 없다” 문제를 해결하는 예제
 //!
+//! 📝 `body_log.rs`: 같은 원리로 만든 재사용 가능한 `BodyLogLayer`
+//! > 크기 제한, JSON 비밀 필드 가리기, 바이너리 건너뛰기, 본문 다이제스트를 span에 기록
+//! > `/login`, `/echo` 라우트에 적용
+//!
+
+mod body_log;
 
 use axum::{
     body::{Body, Bytes},
@@ -10,9 +16,11 @@ use axum::{
     middleware::{self, Next},
     response::{IntoResponse, Response},
     routing::post,
-    Router,
+    Json, Router,
 };
+use body_log::BodyLogLayer;
 use http_body_util::BodyExt; // body 수집용 확장 trait
+use serde_json::{json, Value};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
@@ -26,10 +34,17 @@ async fn main() {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
+    // BodyLogLayer 적용 라우트 (`layer` 이후에 merge 하므로 print_request_body는 적용되지 않음)
+    let logged = Router::new()
+        .route("/login", post(login))
+        .route("/echo", post(echo))
+        .layer(BodyLogLayer::new().max_size(16 * 1024).redact("api_key"));
+
     // Router 구성
     let app = Router::new()
         .route("/", post(handler))
-        .layer(middleware::from_fn(print_request_body)); // body를 미리 읽는 미들웨어 추가
+        .layer(middleware::from_fn(print_request_body)) // body를 미리 읽는 미들웨어 추가
+        .merge(logged);
 
     let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
         .await
@@ -40,7 +55,7 @@ async fn main() {
     axum::serve(listener, app).await.unwrap();
 }
 
-/// 🔁 미들웨어에서 body 읽기
+// 🔁 미들웨어에서 body 읽기
 
 /// 요청 본문을 미리 읽고 로깅하는 미들웨어
 async fn print_request_body(request: Request, next: Next) -> Result<impl IntoResponse, Response> {
@@ -74,7 +89,7 @@ fn do_thing_with_request_body(bytes: Bytes) {
     tracing::debug!(body = ?bytes);
 }
 
-/// 🧲 핸들러와 추출기 구현
+// 🧲 핸들러와 추출기 구현
 
 // 실제 라우트 핸들러: 커스텀 추출기 사용
 async fn handler(BufferRequestBody(body): BufferRequestBody) {
@@ -102,6 +117,32 @@ where
     }
 }
 
+// 📝 BodyLogLayer 적용 핸들러
+
+// 로그에는 password, token이 [REDACTED]로 남음
+async fn login(Json(credentials): Json<Value>) -> Json<Value> {
+    let username = credentials["username"].as_str().unwrap_or("guest");
+    Json(json!({ "username": username, "token": "demo-token" }))
+}
+
+// 받은 본문을 같은 Content-Type으로 그대로 돌려줌 (이미지 등은 로그에서 건너뜀)
+async fn echo(headers: axum::http::HeaderMap, body: Bytes) -> impl IntoResponse {
+    let content_type = headers
+        .get(axum::http::header::CONTENT_TYPE)
+        .cloned()
+        .unwrap_or(axum::http::HeaderValue::from_static("text/plain"));
+    ([(axum::http::header::CONTENT_TYPE, content_type)], body)
+}
+
+// 🧪 테스트 방법
+// curl -X POST localhost:3000/login -H 'content-type: application/json' \
+//   -d '{"username":"alice","password":"hunter2"}'
+// # 로그: body={"password":"[REDACTED]","username":"alice"} (request.digest=...)
+// curl -X POST localhost:3000/echo -d 'username=alice&password=hunter2'
+// # 로그: body=username=alice&password=%5BREDACTED%5D (curl -d는 form content-type)
+// curl -X POST localhost:3000/echo -H 'content-type: image/png' --data-binary @some.png
+// # 로그: skipping binary body
+
 // 🧠 핵심 요점 요약
 // > 요청 바디는 stream 이기 때문에 한 번만 읽을 수 있음.
 // > Bytes 로 수집하고, 복제해서 Body::from() 으로 다시 만들어야 함
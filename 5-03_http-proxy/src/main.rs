@@ -8,8 +8,17 @@
 //! 다른 터미널에서 테스트:
 //! curl -v -x "127.0.0.1:3000" https://tokio.rs
 //!
+//! 🕵️ MITM 모드 (`MITM=1`, `mitm.rs`)
+//! 터널의 HTTPS를 로컬 CA로 풀어서 요청/응답을 로그로 남기는 디버깅 프록시 (mitmproxy 방식)
+//! CA는 처음 실행할 때 `mitm_ca/`에 만들어지며 클라이언트가 이 CA를 신뢰해야 함
+//!
+//! MITM=1 cargo run -p example-http-proxy
+//! curl --cacert mitm_ca/ca.pem -x "127.0.0.1:3000" https://tokio.rs
+//!
 //! Example is based on <https://github.com/hyperium/hyper/blob/master/examples/http_proxy.rs>
 
+mod mitm;
+
 use axum::{
     body::Body,
     extract::Request,
@@ -28,6 +37,8 @@ use tower::Service;
 use tower::ServiceExt;
 
 use hyper_util::rt::TokioIo;
+use mitm::{CertificateAuthority, Mitm};
+use std::path::PathBuf;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
@@ -42,18 +53,38 @@ async fn main() {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
+    // MITM=1 이면 CONNECT 터널을 가로챔 (기본값은 내용을 보지 않는 일반 터널)
+    let mitm = std::env::var("MITM").is_ok_and(|v| v == "1").then(|| {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("mitm_ca");
+        let ca = CertificateAuthority::load_or_generate(&dir).expect("failed to load MITM CA");
+        tracing::info!(
+            "MITM mode: trust {} in the client",
+            dir.join("ca.pem").display()
+        );
+        Mitm::new(ca).unwrap()
+    });
+
+    // 서버 리스너 시작
+    let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
+    tracing::debug!("listening on {}", addr);
+    let listener = TcpListener::bind(addr).await.unwrap();
+    serve(listener, mitm).await;
+}
+
+async fn serve(listener: TcpListener, mitm: Option<Mitm>) {
     // 간단한 라우터: GET / 요청 시 Hello 응답
     let router_svc = Router::new().route("/", get(|| async { "Hello, World!" }));
 
     // tower service 함수 생성
     let tower_service = tower::service_fn(move |req: Request<_>| {
         let router_svc = router_svc.clone();
+        let mitm = mitm.clone();
         let req = req.map(Body::new); // hyper용 요청 타입으로 변환
 
         async move {
             // CONNECT 요청이면 프록시 처리
             if req.method() == Method::CONNECT {
-                proxy(req).await
+                proxy(req, mitm).await
             } else {
                 // 그 외는 라우터로 처리
                 router_svc.oneshot(req).await.map_err(|err| match err {})
@@ -66,11 +97,6 @@ async fn main() {
         tower_service.clone().call(request)
     });
 
-    // 서버 리스너 시작
-    let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
-    tracing::debug!("listening on {}", addr);
-    let listener = TcpListener::bind(addr).await.unwrap();
-
     loop {
         let (stream, _) = listener.accept().await.unwrap();
         let io = TokioIo::new(stream);
@@ -93,16 +119,21 @@ async fn main() {
 
 /// 🔌 proxy() 함수: CONNECT 처리
 // CONNECT 요청 처리 → TCP 터널 생성
-async fn proxy(req: Request) -> Result<Response, hyper::Error> {
+async fn proxy(req: Request, mitm: Option<Mitm>) -> Result<Response, hyper::Error> {
     tracing::trace!(?req);
 
     // 요청 URI에서 호스트 주소 추출
-    if let Some(host_addr) = req.uri().authority().map(|auth| auth.to_string()) {
-        // 업그레이드 요청을 기다렸다가 → 업그레이드 완료되면 TCP 터널 생성
+    if let Some(authority) = req.uri().authority().cloned() {
+        let host_addr = authority.to_string();
+        // 업그레이드 요청을 기다렸다가 → 업그레이드 완료되면 TCP 터널 생성 (MITM 모드면 가로채기)
         tokio::task::spawn(async move {
             match hyper::upgrade::on(req).await {
                 Ok(upgraded) => {
-                    if let Err(e) = tunnel(upgraded, host_addr).await {
+                    let result = match mitm {
+                        Some(mitm) => mitm.intercept(upgraded, authority.host(), &host_addr).await,
+                        None => tunnel(upgraded, host_addr).await,
+                    };
+                    if let Err(e) = result {
                         tracing::warn!("server io error: {}", e);
                     }
                 }
@@ -141,6 +172,146 @@ async fn tunnel(upgraded: Upgraded, addr: String) -> std::io::Result<()> {
     Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::client::conn::http1 as client_http1;
+    use openssl::{
+        ssl::{Ssl, SslConnector, SslMethod},
+        x509::X509,
+    };
+    use std::pin::Pin;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_openssl::SslStream;
+
+    // 실서버 역할: 별도 CA로 서명된 인증서를 쓰는 HTTPS 서버
+    async fn spawn_upstream(upstream_ca: CertificateAuthority) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let acceptor = upstream_ca.acceptor("127.0.0.1").unwrap();
+        let app = Router::new().route("/hello", get(|| async { "hello from upstream" }));
+
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let ssl = Ssl::new(acceptor.context()).unwrap();
+                let app = app.clone();
+                tokio::spawn(async move {
+                    let mut tls = SslStream::new(ssl, stream).unwrap();
+                    Pin::new(&mut tls).accept().await.unwrap();
+                    let service = hyper::service::service_fn(move |req: Request<Incoming>| {
+                        app.clone().call(req)
+                    });
+                    let _ = http1::Builder::new()
+                        .serve_connection(TokioIo::new(tls), service)
+                        .await;
+                });
+            }
+        });
+        addr
+    }
+
+    fn connector_trusting(ca: &CertificateAuthority) -> SslConnector {
+        let mut builder = SslConnector::builder(SslMethod::tls_client()).unwrap();
+        let cert = X509::from_pem(&ca.cert_pem().unwrap()).unwrap();
+        builder.cert_store_mut().add_cert(cert).unwrap();
+        builder.build()
+    }
+
+    async fn spawn_proxy(mitm: Mitm) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, Some(mitm)));
+        addr
+    }
+
+    /// curl -x 처럼 CONNECT 후 터널 안에서 TLS 연결, `GET /hello` 결과 반환
+    ///
+    /// (클라이언트가 받은 인증서의 발급자, 응답 본문)
+    async fn get_through_proxy(
+        proxy: SocketAddr,
+        target: SocketAddr,
+        client_tls: &SslConnector,
+    ) -> (String, Result<String, hyper::Error>) {
+        let mut stream = TcpStream::connect(proxy).await.unwrap();
+        stream
+            .write_all(format!("CONNECT {target} HTTP/1.1\r\nHost: {target}\r\n\r\n").as_bytes())
+            .await
+            .unwrap();
+        let mut head = Vec::new();
+        while !head.ends_with(b"\r\n\r\n") {
+            head.push(stream.read_u8().await.unwrap());
+        }
+        assert!(head.starts_with(b"HTTP/1.1 200"));
+
+        let ssl = client_tls
+            .configure()
+            .unwrap()
+            .into_ssl("127.0.0.1")
+            .unwrap();
+        let mut tls = SslStream::new(ssl, stream).unwrap();
+        Pin::new(&mut tls).connect().await.unwrap();
+        let issuer = tls
+            .ssl()
+            .peer_certificate()
+            .unwrap()
+            .issuer_name()
+            .entries()
+            .next()
+            .unwrap()
+            .data()
+            .to_string()
+            .unwrap();
+
+        let (mut sender, connection) = client_http1::handshake(TokioIo::new(tls)).await.unwrap();
+        tokio::spawn(connection);
+        let request = Request::get("/hello")
+            .header("host", target.to_string())
+            .body(Body::empty())
+            .unwrap();
+        let body = match sender.send_request(request).await {
+            Ok(res) => {
+                let bytes = axum::body::to_bytes(Body::new(res.into_body()), usize::MAX)
+                    .await
+                    .unwrap();
+                Ok(String::from_utf8(bytes.to_vec()).unwrap())
+            }
+            Err(err) => Err(err),
+        };
+        (issuer, body)
+    }
+
+    #[tokio::test]
+    async fn intercepts_https_with_generated_certificate() {
+        let upstream_ca = CertificateAuthority::generate("upstream CA").unwrap();
+        let upstream_tls = connector_trusting(&upstream_ca);
+        let target = spawn_upstream(upstream_ca).await;
+
+        let mitm_ca = CertificateAuthority::generate("test MITM CA").unwrap();
+        let client_tls = connector_trusting(&mitm_ca);
+        let proxy = spawn_proxy(Mitm::with_upstream_tls(mitm_ca, upstream_tls)).await;
+
+        // 클라이언트는 실서버가 아니라 MITM CA가 서명한 인증서를 받지만, 요청은 정상 처리됨
+        let (issuer, body) = get_through_proxy(proxy, target, &client_tls).await;
+        assert_eq!(issuer, "test MITM CA");
+        assert_eq!(body.unwrap(), "hello from upstream");
+    }
+
+    #[tokio::test]
+    async fn upstream_certificate_is_still_verified() {
+        let upstream_ca = CertificateAuthority::generate("untrusted CA").unwrap();
+        let target = spawn_upstream(upstream_ca).await;
+
+        // 실서버 CA를 신뢰하지 않는 기본 설정 → 프록시가 실서버 연결을 거절
+        let mitm_ca = CertificateAuthority::generate("test MITM CA").unwrap();
+        let client_tls = connector_trusting(&mitm_ca);
+        let proxy = spawn_proxy(Mitm::new(mitm_ca).unwrap()).await;
+
+        let (_, body) = get_through_proxy(proxy, target, &client_tls).await;
+        assert!(body.is_err());
+    }
+}
+
 // 🔁 실행 흐름 요약
 // 	1.	클라이언트는 프록시 서버에 CONNECT 요청을 보냄
 // 	2.	서버는 CONNECT 요청을 인식하고 proxy() 함수로 처리
@@ -181,6 +352,12 @@ async fn tunnel(upgraded: Upgraded, addr: String) -> std::io::Result<()> {
 //	•	클라이언트 ↔ 프록시 ↔ tokio.rs 서버 간의 raw TCP 통신 유지됨
 //. •	프록시는 내용을 해석하거나 개입하지 않음, 그냥 중계
 
+// 🕵️ MITM 모드에서 달라지는 점 (MITM=1)
+//  •	4번 이후 프록시가 터널 안의 TLS를 직접 받음 → tokio.rs용 인증서를 즉석에서 만들어 제시
+//  •	curl은 그 인증서가 mitm_ca/ca.pem 으로 서명되었으므로 신뢰 (--cacert 없이는 실패)
+//  •	프록시는 복호화된 요청을 보고 로그를 남긴 뒤, tokio.rs:443 에 새 TLS 연결로 전달
+//    INFO intercepted method=GET url=https://tokio.rs/ status=200 ... elapsed_ms=...
+
 // ✅ 실무 응용 예시
 // 사내 프록시 서버 -> 인터넷 접근 통제, 로그 남기기.
 // HTTPS 통과 프록시 (Man-in-the-middle) -> 보안 분석, SSL termination.
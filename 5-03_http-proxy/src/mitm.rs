@@ -0,0 +1,393 @@
+//! 🕵️ MITM(중간자) 모드: CONNECT 터널 안의 HTTPS를 풀어서 보는 디버깅 프록시
+//!
+//! 일반 CONNECT 터널은 암호화된 바이트를 그대로 옮기기만 하지만, 이 모드에서는
+//!
+//!  1. 프록시가 직접 TLS를 종료 → 접속하려는 호스트 이름으로 인증서를 즉석에서 만들어
+//!     로컬 CA로 서명 (호스트마다 한 번 만들고 재사용)
+//!  2. 복호화된 HTTP 요청/응답의 메타데이터(메서드, 경로, 상태 코드, 크기, 시간)를 로그로 남김
+//!  3. 실제 서버에는 새 TLS 연결로 다시 암호화해서 전달 (실서버 인증서는 정상 검증)
+//!
+//! 클라이언트가 로컬 CA를 신뢰해야만 동작함 (mitmproxy, Charles, Fiddler와 같은 원리)
+//!
+//! ⚠️ 자신이 관리하는 기기와 트래픽의 디버깅 용도로만 사용할 것
+
+use axum::body::Body;
+use hyper::{
+    body::Incoming, client::conn::http1 as client_http1, server::conn::http1 as server_http1,
+    upgrade::Upgraded, Request, Response, StatusCode,
+};
+use hyper_util::rt::TokioIo;
+use openssl::{
+    asn1::{Asn1Integer, Asn1Time},
+    bn::{BigNum, MsbOption},
+    ec::{EcGroup, EcKey},
+    error::ErrorStack,
+    hash::MessageDigest,
+    nid::Nid,
+    pkey::{PKey, Private},
+    ssl::{Ssl, SslAcceptor, SslConnector, SslMethod},
+    x509::{
+        extension::{
+            AuthorityKeyIdentifier, BasicConstraints, ExtendedKeyUsage, KeyUsage,
+            SubjectAlternativeName, SubjectKeyIdentifier,
+        },
+        X509Name, X509,
+    },
+};
+use std::{
+    collections::HashMap,
+    fs, io,
+    net::IpAddr,
+    path::Path,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio::net::TcpStream;
+use tokio_openssl::SslStream;
+
+// 만든 인증서의 유효 기간 (CA는 길게, 호스트 인증서는 짧게)
+const CA_VALID_DAYS: u32 = 3650;
+const LEAF_VALID_DAYS: u32 = 30;
+
+/// 🔐 호스트 인증서를 서명하는 로컬 CA
+///
+/// 호스트 인증서는 모두 같은 키를 공유 (매번 키를 만드는 비용을 줄임, mitmproxy도 같은 방식)
+pub struct CertificateAuthority {
+    cert: X509,
+    key: PKey<Private>,
+    leaf_key: PKey<Private>,
+    acceptors: Mutex<HashMap<String, SslAcceptor>>,
+}
+
+impl CertificateAuthority {
+    /// 새 CA 생성 (메모리에만 존재)
+    pub fn generate(name: &str) -> Result<Self, ErrorStack> {
+        let key = generate_key()?;
+
+        let mut subject = X509Name::builder()?;
+        subject.append_entry_by_nid(Nid::COMMONNAME, name)?;
+        let subject = subject.build();
+
+        let mut builder = X509::builder()?;
+        builder.set_version(2)?;
+        let serial = random_serial()?;
+        builder.set_serial_number(&serial)?;
+        builder.set_subject_name(&subject)?;
+        builder.set_issuer_name(&subject)?;
+        builder.set_pubkey(&key)?;
+        builder.set_not_before(&*not_before()?)?;
+        builder.set_not_after(&*Asn1Time::days_from_now(CA_VALID_DAYS)?)?;
+        builder.append_extension(BasicConstraints::new().critical().ca().pathlen(0).build()?)?;
+        builder.append_extension(
+            KeyUsage::new()
+                .critical()
+                .key_cert_sign()
+                .crl_sign()
+                .build()?,
+        )?;
+        let ski = SubjectKeyIdentifier::new().build(&builder.x509v3_context(None, None))?;
+        builder.append_extension(ski)?;
+        builder.sign(&key, MessageDigest::sha256())?;
+
+        Self::from_parts(builder.build(), key)
+    }
+
+    /// `dir`의 `ca.pem`/`ca.key`를 읽고, 없으면 새로 만들어 저장
+    ///
+    /// 매번 새 CA를 만들면 클라이언트에 다시 등록해야 하므로 파일로 유지
+    pub fn load_or_generate(dir: &Path) -> io::Result<Self> {
+        let (cert_path, key_path) = (dir.join("ca.pem"), dir.join("ca.key"));
+        if cert_path.exists() && key_path.exists() {
+            let cert = X509::from_pem(&fs::read(&cert_path)?)?;
+            let key = PKey::private_key_from_pem(&fs::read(&key_path)?)?;
+            return Ok(Self::from_parts(cert, key)?);
+        }
+
+        let ca = Self::generate("axum-examples MITM CA")?;
+        fs::create_dir_all(dir)?;
+        fs::write(&cert_path, ca.cert_pem()?)?;
+        // 한쪽 파일만 남아 있던 경우: 예전 키는 지우고 새로 만든 키를 저장
+        if key_path.exists() {
+            fs::remove_file(&key_path)?;
+        }
+        write_private_key(&key_path, &ca.key.private_key_to_pem_pkcs8()?)?;
+        tracing::info!("generated a new MITM CA at {}", cert_path.display());
+        Ok(ca)
+    }
+
+    fn from_parts(cert: X509, key: PKey<Private>) -> Result<Self, ErrorStack> {
+        Ok(Self {
+            cert,
+            key,
+            leaf_key: generate_key()?,
+            acceptors: Mutex::default(),
+        })
+    }
+
+    /// 클라이언트에 신뢰할 인증서로 등록할 CA 인증서 (PEM)
+    pub fn cert_pem(&self) -> Result<Vec<u8>, ErrorStack> {
+        self.cert.to_pem()
+    }
+
+    /// `host`용 인증서를 CA로 서명해서 생성 (도메인이면 DNS, IP면 IP SAN)
+    pub fn leaf_cert(&self, host: &str) -> Result<X509, ErrorStack> {
+        let mut subject = X509Name::builder()?;
+        subject.append_entry_by_nid(Nid::COMMONNAME, host)?;
+        let subject = subject.build();
+
+        let mut builder = X509::builder()?;
+        builder.set_version(2)?;
+        let serial = random_serial()?;
+        builder.set_serial_number(&serial)?;
+        builder.set_subject_name(&subject)?;
+        builder.set_issuer_name(self.cert.subject_name())?;
+        builder.set_pubkey(&self.leaf_key)?;
+        builder.set_not_before(&*not_before()?)?;
+        builder.set_not_after(&*Asn1Time::days_from_now(LEAF_VALID_DAYS)?)?;
+        builder.append_extension(BasicConstraints::new().critical().build()?)?;
+        builder.append_extension(
+            KeyUsage::new()
+                .critical()
+                .digital_signature()
+                .key_encipherment()
+                .build()?,
+        )?;
+        builder.append_extension(ExtendedKeyUsage::new().server_auth().build()?)?;
+
+        let context = builder.x509v3_context(Some(&self.cert), None);
+        let mut san = SubjectAlternativeName::new();
+        if host.parse::<IpAddr>().is_ok() {
+            san.ip(host);
+        } else {
+            san.dns(host);
+        }
+        let san = san.build(&context)?;
+        let aki = AuthorityKeyIdentifier::new().keyid(false).build(&context)?;
+        builder.append_extension(san)?;
+        builder.append_extension(aki)?;
+
+        builder.sign(&self.key, MessageDigest::sha256())?;
+        Ok(builder.build())
+    }
+
+    /// `host`용 TLS acceptor (처음 요청된 호스트만 인증서를 만들고 이후에는 재사용)
+    pub fn acceptor(&self, host: &str) -> Result<SslAcceptor, ErrorStack> {
+        if let Some(acceptor) = self.acceptors.lock().unwrap().get(host) {
+            return Ok(acceptor.clone());
+        }
+
+        let cert = self.leaf_cert(host)?;
+        let mut builder = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls())?;
+        builder.set_certificate(&cert)?;
+        builder.add_extra_chain_cert(self.cert.clone())?;
+        builder.set_private_key(&self.leaf_key)?;
+        builder.check_private_key()?;
+        let acceptor = builder.build();
+        tracing::debug!(host, "issued certificate");
+
+        self.acceptors
+            .lock()
+            .unwrap()
+            .insert(host.to_owned(), acceptor.clone());
+        Ok(acceptor)
+    }
+}
+
+/// 📦 MITM 모드 설정: 로컬 CA + 실서버 연결용 TLS 설정
+#[derive(Clone)]
+pub struct Mitm {
+    ca: Arc<CertificateAuthority>,
+    upstream_tls: SslConnector,
+}
+
+impl Mitm {
+    /// 실서버 인증서는 시스템 신뢰 저장소로 검증
+    pub fn new(ca: CertificateAuthority) -> Result<Self, ErrorStack> {
+        let mut builder = SslConnector::builder(SslMethod::tls_client())?;
+        builder.set_alpn_protos(b"\x08http/1.1")?;
+        Ok(Self::with_upstream_tls(ca, builder.build()))
+    }
+
+    /// 실서버 검증 방식을 직접 지정 (테스트에서 자체 서명 실서버를 신뢰할 때 등)
+    pub fn with_upstream_tls(ca: CertificateAuthority, upstream_tls: SslConnector) -> Self {
+        Self {
+            ca: Arc::new(ca),
+            upstream_tls,
+        }
+    }
+
+    /// 업그레이드된 CONNECT 연결을 가로채서 HTTP 요청 단위로 중계
+    ///
+    /// `addr`는 CONNECT 대상 (`tokio.rs:443`), `host`는 포트를 뺀 이름
+    pub async fn intercept(&self, upgraded: Upgraded, host: &str, addr: &str) -> io::Result<()> {
+        // 1️⃣ 클라이언트 쪽: 가짜 인증서로 TLS 종료
+        let acceptor = self.ca.acceptor(host)?;
+        let mut client_tls = SslStream::new(Ssl::new(acceptor.context())?, TokioIo::new(upgraded))?;
+        Pin::new(&mut client_tls)
+            .accept()
+            .await
+            .map_err(io::Error::other)?;
+
+        // 2️⃣ 실서버 쪽: 진짜 TLS 연결 (인증서 검증 실패 시 클라이언트 연결도 끊음)
+        let ssl = self.upstream_tls.configure()?.into_ssl(host)?;
+        let mut upstream_tls = SslStream::new(ssl, TcpStream::connect(addr).await?)?;
+        Pin::new(&mut upstream_tls)
+            .connect()
+            .await
+            .map_err(io::Error::other)?;
+
+        let (sender, connection) = client_http1::handshake(TokioIo::new(upstream_tls))
+            .await
+            .map_err(io::Error::other)?;
+        tokio::spawn(async move {
+            if let Err(err) = connection.await {
+                tracing::debug!("upstream connection closed: {err}");
+            }
+        });
+
+        // 3️⃣ 복호화된 요청을 하나씩 실서버로 보내고 메타데이터 기록
+        // (HTTP/1.1 연결 하나에서는 요청이 차례대로 오므로 잠금 경합이 없음)
+        let sender = Arc::new(tokio::sync::Mutex::new(sender));
+        let host = host.to_owned();
+        let service = hyper::service::service_fn(move |req: Request<Incoming>| {
+            let sender = sender.clone();
+            let host = host.clone();
+            async move {
+                let started = Instant::now();
+                let (method, path) = (req.method().clone(), req.uri().to_string());
+
+                let response = match sender.lock().await.send_request(req).await {
+                    Ok(res) => res.map(Body::new),
+                    Err(err) => {
+                        tracing::warn!(%method, host, path, "upstream error: {err}");
+                        return Ok::<_, hyper::Error>(
+                            Response::builder()
+                                .status(StatusCode::BAD_GATEWAY)
+                                .body(Body::empty())
+                                .unwrap(),
+                        );
+                    }
+                };
+
+                tracing::info!(
+                    %method,
+                    url = format!("https://{host}{path}"),
+                    status = response.status().as_u16(),
+                    content_type = ?response.headers().get(hyper::header::CONTENT_TYPE),
+                    content_length = ?response.headers().get(hyper::header::CONTENT_LENGTH),
+                    elapsed_ms = started.elapsed().as_millis(),
+                    "intercepted"
+                );
+                Ok(response)
+            }
+        });
+
+        server_http1::Builder::new()
+            .serve_connection(TokioIo::new(client_tls), service)
+            .await
+            .map_err(io::Error::other)
+    }
+}
+
+fn generate_key() -> Result<PKey<Private>, ErrorStack> {
+    let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)?;
+    PKey::from_ec_key(EcKey::generate(&group)?)
+}
+
+fn random_serial() -> Result<Asn1Integer, ErrorStack> {
+    let mut serial = BigNum::new()?;
+    serial.rand(128, MsbOption::MAYBE_ZERO, false)?;
+    serial.to_asn1_integer()
+}
+
+// 클라이언트와 시계가 조금 어긋나도 "아직 유효하지 않음"이 되지 않도록 하루 전부터 유효
+fn not_before() -> Result<Asn1Time, ErrorStack> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    Asn1Time::from_unix((now - 24 * 60 * 60) as _)
+}
+
+// 🔑 CA 개인키는 소유자만 읽을 수 있게 (기본 umask면 보통 0644 → 누구나 읽을 수 있음)
+// 이 키가 새면 CA를 신뢰하는 기기의 모든 HTTPS를 가로챌 수 있음
+// create_new: 이미 있는 파일을 덮어쓰거나 다른 권한으로 열지 않음
+#[cfg(unix)]
+fn write_private_key(path: &Path, pem: &[u8]) -> io::Result<()> {
+    use std::{io::Write, os::unix::fs::OpenOptionsExt};
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)?
+        .write_all(pem)
+}
+
+#[cfg(not(unix))]
+fn write_private_key(path: &Path, pem: &[u8]) -> io::Result<()> {
+    fs::write(path, pem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::x509::X509StoreContext;
+    use openssl::{stack::Stack, x509::store::X509StoreBuilder};
+
+    fn verify(ca: &CertificateAuthority, cert: &X509) -> bool {
+        let mut store = X509StoreBuilder::new().unwrap();
+        store.add_cert(ca.cert.clone()).unwrap();
+        let store = store.build();
+        let mut context = X509StoreContext::new().unwrap();
+        context
+            .init(&store, cert, &Stack::new().unwrap(), |c| c.verify_cert())
+            .unwrap()
+    }
+
+    #[test]
+    fn leaf_certificates_are_signed_by_the_ca() {
+        let ca = CertificateAuthority::generate("test CA").unwrap();
+        let other = CertificateAuthority::generate("other CA").unwrap();
+
+        let cert = ca.leaf_cert("example.com").unwrap();
+        assert!(verify(&ca, &cert));
+        assert!(!verify(&other, &cert));
+
+        let names = cert.subject_alt_names().unwrap();
+        assert_eq!(names.get(0).unwrap().dnsname(), Some("example.com"));
+
+        let cert = ca.leaf_cert("127.0.0.1").unwrap();
+        let names = cert.subject_alt_names().unwrap();
+        assert_eq!(names.get(0).unwrap().ipaddress(), Some(&[127, 0, 0, 1][..]));
+    }
+
+    #[test]
+    fn acceptors_are_cached_per_host() {
+        let ca = CertificateAuthority::generate("test CA").unwrap();
+        ca.acceptor("a.example").unwrap();
+        ca.acceptor("a.example").unwrap();
+        ca.acceptor("b.example").unwrap();
+        assert_eq!(ca.acceptors.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn ca_is_persisted() {
+        let dir =
+            std::env::temp_dir().join(format!("example-http-proxy-{}-ca", std::process::id()));
+        let first = CertificateAuthority::load_or_generate(&dir).unwrap();
+        let second = CertificateAuthority::load_or_generate(&dir).unwrap();
+        assert_eq!(first.cert_pem().unwrap(), second.cert_pem().unwrap());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(dir.join("ca.key"))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
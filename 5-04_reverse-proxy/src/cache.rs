@@ -0,0 +1,455 @@
+//! 🗄️ 프록시 응답 캐시 (메모리)
+//!
+//! 공유 캐시(shared cache) 규칙을 단순화해서 적용
+//!
+//! • 키: 메서드 + 경로/쿼리, 같은 키 안에서는 응답의 `Vary` 헤더에 적힌 요청 헤더 값으로 구분
+//! • 저장: `GET` 요청이고 응답에 `s-maxage` / `max-age` / `Expires` 중 하나가 있을 때만
+//!   (`no-store`, `private`, `Set-Cookie`, `Vary: *`는 저장하지 않음)
+//! • 만료: `ETag`가 있으면 실서버에 `If-None-Match`로 재검증 → 304면 저장된 본문 재사용
+//! • 요청의 `Cache-Control: no-cache` / `max-age=0`은 캐시를 쓰기 전에 항상 재검증
+
+use axum::{
+    body::{Body, Bytes},
+    http::{
+        header::{
+            AGE, AUTHORIZATION, CACHE_CONTROL, DATE, ETAG, EXPIRES, IF_NONE_MATCH, SET_COOKIE, VARY,
+        },
+        HeaderMap, HeaderName, HeaderValue, Method, StatusCode,
+    },
+    response::Response,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+
+/// 캐시 상태를 알려주는 응답 헤더 (`HIT`, `MISS`, `REVALIDATED`, `BYPASS`)
+pub const X_CACHE: &str = "x-cache";
+
+/// 이보다 큰 응답이나 길이를 모르는 응답(chunked)은 저장하지 않고 그대로 전달
+pub const MAX_BODY_SIZE: u64 = 1024 * 1024;
+
+/// 저장할 수 있는 최대 키 개수
+const MAX_KEYS: usize = 1024;
+
+// 명시적인 유효 기간이 있을 때 저장하는 상태 코드
+const CACHEABLE_STATUS: &[StatusCode] = &[
+    StatusCode::OK,
+    StatusCode::NON_AUTHORITATIVE_INFORMATION,
+    StatusCode::NO_CONTENT,
+    StatusCode::MOVED_PERMANENTLY,
+    StatusCode::NOT_FOUND,
+    StatusCode::GONE,
+];
+
+/// 캐시 키 (실서버가 하나뿐이므로 호스트는 포함하지 않음)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    method: Method,
+    path_and_query: String,
+}
+
+impl CacheKey {
+    pub fn new(method: &Method, path_and_query: &str) -> Self {
+        Self {
+            method: method.clone(),
+            path_and_query: path_and_query.to_owned(),
+        }
+    }
+}
+
+/// 저장된 응답 하나
+#[derive(Debug)]
+pub struct Entry {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+    // 이 응답을 받았을 때의 요청 헤더 값 (Vary에 적힌 헤더만)
+    vary: Vec<(HeaderName, Option<HeaderValue>)>,
+    stored_at: Instant,
+    ttl: Duration,
+}
+
+impl Entry {
+    pub fn is_fresh(&self) -> bool {
+        self.stored_at.elapsed() < self.ttl
+    }
+
+    pub fn etag(&self) -> Option<&HeaderValue> {
+        self.headers.get(ETAG)
+    }
+
+    fn matches(&self, request: &HeaderMap) -> bool {
+        self.vary
+            .iter()
+            .all(|(name, value)| request.get(name) == value.as_ref())
+    }
+
+    /// 저장된 응답으로 응답 생성
+    ///
+    /// 사용자가 같은 ETag로 조건부 요청을 보냈으면 본문 없이 304
+    pub fn respond(&self, request: &HeaderMap, x_cache: &'static str) -> Response {
+        let not_modified = match (request.get(IF_NONE_MATCH), self.etag()) {
+            (Some(if_none_match), Some(etag)) => etag_matches(if_none_match, etag),
+            _ => false,
+        };
+
+        let mut response = if not_modified {
+            let mut response = Response::new(Body::empty());
+            *response.status_mut() = StatusCode::NOT_MODIFIED;
+            for name in [ETAG, CACHE_CONTROL, EXPIRES, VARY] {
+                if let Some(value) = self.headers.get(&name) {
+                    response.headers_mut().insert(name, value.clone());
+                }
+            }
+            response
+        } else {
+            let mut response = Response::new(Body::from(self.body.clone()));
+            *response.status_mut() = self.status;
+            *response.headers_mut() = self.headers.clone();
+            response
+        };
+
+        let headers = response.headers_mut();
+        headers.insert(AGE, self.stored_at.elapsed().as_secs().into());
+        headers.insert(X_CACHE, HeaderValue::from_static(x_cache));
+        response
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HttpCache {
+    entries: Arc<Mutex<HashMap<CacheKey, Vec<Arc<Entry>>>>>,
+}
+
+impl HttpCache {
+    /// 요청 헤더와 Vary가 맞는 저장된 응답 (만료된 것 포함)
+    pub fn lookup(&self, key: &CacheKey, request: &HeaderMap) -> Option<Arc<Entry>> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(key)?
+            .iter()
+            .find(|entry| entry.matches(request))
+            .cloned()
+    }
+
+    /// 저장할 수 있는 응답이면 저장하고 `true`
+    pub fn store(
+        &self,
+        key: CacheKey,
+        request: &HeaderMap,
+        status: StatusCode,
+        headers: &HeaderMap,
+        body: Bytes,
+    ) -> bool {
+        if !CACHEABLE_STATUS.contains(&status) {
+            return false;
+        }
+        let Some(ttl) = freshness_lifetime(headers, SystemTime::now()) else {
+            return false;
+        };
+        // 바로 만료되는데 재검증할 수도 없으면 저장해도 쓸모가 없음
+        if ttl.is_zero() && !headers.contains_key(ETAG) {
+            return false;
+        }
+
+        let vary = vary_names(headers)
+            .into_iter()
+            .map(|name| {
+                let value = request.get(&name).cloned();
+                (name, value)
+            })
+            .collect();
+        let entry = Arc::new(Entry {
+            status,
+            headers: headers.clone(),
+            body,
+            vary,
+            stored_at: Instant::now(),
+            ttl,
+        });
+
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= MAX_KEYS && !entries.contains_key(&key) {
+            // 가득 차면 만료된 항목부터 비우고, 그래도 자리가 없으면 저장하지 않음
+            entries.retain(|_, variants| {
+                variants.retain(|entry| entry.is_fresh());
+                !variants.is_empty()
+            });
+            if entries.len() >= MAX_KEYS {
+                return false;
+            }
+        }
+        let variants = entries.entry(key).or_default();
+        variants.retain(|existing| existing.vary != entry.vary);
+        variants.push(entry);
+        true
+    }
+
+    /// 304 응답으로 재검증된 항목의 유효 기간과 헤더를 갱신
+    pub fn refresh(
+        &self,
+        key: &CacheKey,
+        old: &Arc<Entry>,
+        not_modified: &HeaderMap,
+    ) -> Arc<Entry> {
+        let mut headers = old.headers.clone();
+        // 304에 함께 온 캐시 관련 헤더로 덮어씀 (본문 관련 헤더는 그대로)
+        for name in [CACHE_CONTROL, ETAG, EXPIRES, DATE, VARY] {
+            if let Some(value) = not_modified.get(&name) {
+                headers.insert(name, value.clone());
+            }
+        }
+        let ttl = freshness_lifetime(&headers, SystemTime::now()).unwrap_or_default();
+        let entry = Arc::new(Entry {
+            status: old.status,
+            headers,
+            body: old.body.clone(),
+            vary: old.vary.clone(),
+            stored_at: Instant::now(),
+            ttl,
+        });
+
+        let mut entries = self.entries.lock().unwrap();
+        if let Some(variants) = entries.get_mut(key) {
+            for existing in variants.iter_mut() {
+                if Arc::ptr_eq(existing, old) {
+                    *existing = Arc::clone(&entry);
+                }
+            }
+        }
+        entry
+    }
+
+    /// 경로가 같은 항목(모든 메서드, 모든 Vary 변형)을 삭제, `None`이면 전체 삭제
+    ///
+    /// 삭제한 응답 개수를 반환
+    pub fn purge(&self, path_and_query: Option<&str>) -> usize {
+        let mut entries = self.entries.lock().unwrap();
+        let mut purged = 0;
+        entries.retain(|key, variants| {
+            let remove = path_and_query.is_none_or(|path| key.path_and_query == path);
+            if remove {
+                purged += variants.len();
+            }
+            !remove
+        });
+        purged
+    }
+}
+
+/// 캐시를 아예 쓰지 않는 요청 (`GET`이 아니거나, 인증 정보가 있거나, `no-store`)
+pub fn bypasses_cache(method: &Method, request: &HeaderMap) -> bool {
+    method != Method::GET
+        || request.contains_key(AUTHORIZATION)
+        || CacheControl::parse(request).no_store
+}
+
+/// 저장된 응답이 아직 유효해도 재검증을 요구하는 요청 (브라우저 새로고침 등)
+pub fn requires_revalidation(request: &HeaderMap) -> bool {
+    let cc = CacheControl::parse(request);
+    cc.no_cache || cc.max_age == Some(0)
+}
+
+/// 응답을 저장해도 되면 유효 기간, 저장하면 안 되면 `None`
+///
+/// `no-cache`는 저장은 하되 매번 재검증해야 하므로 유효 기간 0
+fn freshness_lifetime(headers: &HeaderMap, now: SystemTime) -> Option<Duration> {
+    let cc = CacheControl::parse(headers);
+    if cc.no_store || cc.private || headers.contains_key(SET_COOKIE) {
+        return None;
+    }
+    if vary_names(headers).iter().any(|name| name == "*") {
+        return None;
+    }
+    if cc.no_cache {
+        return Some(Duration::ZERO);
+    }
+    // 공유 캐시에는 s-maxage가 max-age보다 우선
+    if let Some(secs) = cc.s_maxage.or(cc.max_age) {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let expires = headers.get(EXPIRES)?;
+    // 형식이 잘못된 Expires(예: "0")는 이미 만료된 것으로 봄
+    let Some(expires) = http_date(expires) else {
+        return Some(Duration::ZERO);
+    };
+    let date = headers.get(DATE).and_then(http_date).unwrap_or(now);
+    Some(expires.duration_since(date).unwrap_or_default())
+}
+
+fn http_date(value: &HeaderValue) -> Option<SystemTime> {
+    httpdate::parse_http_date(value.to_str().ok()?).ok()
+}
+
+fn vary_names(headers: &HeaderMap) -> Vec<HeaderName> {
+    headers
+        .get_all(VARY)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|name| HeaderName::try_from(name.trim()).ok())
+        .collect()
+}
+
+/// `If-None-Match` 목록에 ETag가 있는지 (약한 비교: `W/` 접두사 무시)
+fn etag_matches(if_none_match: &HeaderValue, etag: &HeaderValue) -> bool {
+    let (Ok(if_none_match), Ok(etag)) = (if_none_match.to_str(), etag.to_str()) else {
+        return false;
+    };
+    let weak = |tag: &str| tag.trim().trim_start_matches("W/").to_owned();
+    if_none_match.trim() == "*" || if_none_match.split(',').any(|tag| weak(tag) == weak(etag))
+}
+
+/// 필요한 지시어만 읽는 `Cache-Control` 파서
+#[derive(Debug, Default)]
+struct CacheControl {
+    no_store: bool,
+    no_cache: bool,
+    private: bool,
+    max_age: Option<u64>,
+    s_maxage: Option<u64>,
+}
+
+impl CacheControl {
+    fn parse(headers: &HeaderMap) -> Self {
+        let mut cc = Self::default();
+        let directives = headers
+            .get_all(CACHE_CONTROL)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','));
+
+        for directive in directives {
+            let (name, value) = match directive.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                None => (directive.trim(), None),
+            };
+            let seconds = || value.and_then(|value| value.parse().ok());
+            match name.to_ascii_lowercase().as_str() {
+                "no-store" => cc.no_store = true,
+                "no-cache" => cc.no_cache = true,
+                "private" => cc.private = true,
+                "max-age" => cc.max_age = seconds(),
+                "s-maxage" => cc.s_maxage = seconds(),
+                _ => {}
+            }
+        }
+        cc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(name, value)| {
+                (
+                    HeaderName::from_static(name),
+                    HeaderValue::from_str(value).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    fn lifetime(pairs: &[(&'static str, &str)]) -> Option<Duration> {
+        freshness_lifetime(&headers(pairs), SystemTime::UNIX_EPOCH)
+    }
+
+    #[test]
+    fn freshness_follows_cache_control_and_expires() {
+        let secs = |secs| Some(Duration::from_secs(secs));
+
+        assert_eq!(
+            lifetime(&[("cache-control", "public, max-age=60")]),
+            secs(60)
+        );
+        assert_eq!(
+            lifetime(&[("cache-control", "max-age=60, s-maxage=\"10\"")]),
+            secs(10)
+        );
+        assert_eq!(lifetime(&[("cache-control", "no-cache")]), secs(0));
+        assert_eq!(
+            lifetime(&[
+                ("date", "Sun, 06 Nov 1994 08:49:37 GMT"),
+                ("expires", "Sun, 06 Nov 1994 08:51:37 GMT"),
+            ]),
+            secs(120)
+        );
+        assert_eq!(lifetime(&[("expires", "0")]), secs(0));
+        // 유효 기간 정보가 없으면 추측하지 않고 저장하지 않음
+        assert_eq!(lifetime(&[]), None);
+    }
+
+    #[test]
+    fn private_and_personalized_responses_are_not_stored() {
+        assert_eq!(lifetime(&[("cache-control", "private, max-age=60")]), None);
+        assert_eq!(lifetime(&[("cache-control", "No-Store")]), None);
+        assert_eq!(
+            lifetime(&[("cache-control", "max-age=60"), ("set-cookie", "a=b")]),
+            None
+        );
+        assert_eq!(
+            lifetime(&[("cache-control", "max-age=60"), ("vary", "*")]),
+            None
+        );
+    }
+
+    #[test]
+    fn requests_can_bypass_or_revalidate() {
+        let get = Method::GET;
+        assert!(!bypasses_cache(&get, &headers(&[])));
+        assert!(bypasses_cache(&Method::POST, &headers(&[])));
+        assert!(bypasses_cache(
+            &get,
+            &headers(&[("authorization", "Bearer x")])
+        ));
+        assert!(bypasses_cache(
+            &get,
+            &headers(&[("cache-control", "no-store")])
+        ));
+
+        assert!(requires_revalidation(&headers(&[(
+            "cache-control",
+            "max-age=0"
+        )])));
+        assert!(requires_revalidation(&headers(&[(
+            "cache-control",
+            "no-cache"
+        )])));
+        assert!(!requires_revalidation(&headers(&[])));
+    }
+
+    #[test]
+    fn etags_use_weak_comparison() {
+        let value = HeaderValue::from_static;
+        assert!(etag_matches(&value("\"a\", W/\"b\""), &value("\"b\"")));
+        assert!(etag_matches(&value("*"), &value("\"b\"")));
+        assert!(!etag_matches(&value("\"a\""), &value("\"b\"")));
+    }
+
+    #[test]
+    fn vary_selects_a_variant_and_purge_removes_all() {
+        let cache = HttpCache::default();
+        let key = CacheKey::new(&Method::GET, "/lang");
+        let response = headers(&[("cache-control", "max-age=60"), ("vary", "Accept-Language")]);
+        let ko = headers(&[("accept-language", "ko")]);
+        let en = headers(&[("accept-language", "en")]);
+
+        assert!(cache.store(key.clone(), &ko, StatusCode::OK, &response, "안녕".into()));
+        assert!(cache.store(key.clone(), &en, StatusCode::OK, &response, "hello".into()));
+
+        assert_eq!(cache.lookup(&key, &ko).unwrap().body, "안녕");
+        assert_eq!(cache.lookup(&key, &en).unwrap().body, "hello");
+        assert!(cache.lookup(&key, &headers(&[])).is_none());
+
+        assert_eq!(cache.purge(Some("/other")), 0);
+        assert_eq!(cache.purge(Some("/lang")), 2);
+        assert!(cache.lookup(&key, &ko).is_none());
+    }
+}
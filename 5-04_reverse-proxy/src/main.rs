@@ -7,6 +7,7 @@
 //!   • 외부 사용자는 4000번 포트만 사용
 //!   • 내부에 존재하는 진짜 서비스는 3000번 포트에 존재
 //!   • Reverse Proxy는 이 둘을 연결해주는 중간자 역할
+//!   • WebSocket 같은 프로토콜 업그레이드 요청도 그대로 전달 (개발 서버의 HMR 소켓 등)
 //!
 //! 🧭 동작 흐름
 //! [사용자 브라우저/curl]
@@ -19,25 +20,75 @@
 //!       ↑   응답 forwarding
 //!  [사용자에게 응답]
 //!
+//! 🔌 업그레이드 요청 흐름 (`Connection: upgrade` + `Upgrade: websocket`)
+//!  1. 핸드셰이크 요청을 그대로 실서버에 전달
+//!  2. 실서버가 `101 Switching Protocols`로 응답하면 그 응답을 사용자에게 전달
+//!  3. 양쪽 연결이 업그레이드되면 5-03의 터널처럼 두 스트림을 양방향으로 복사
+//!     (이후로는 HTTP가 아니므로 프록시는 내용을 해석하지 않음)
+//!
+//! 🗄️ 응답 캐시 (`cache.rs`)
+//!  • 실서버가 `Cache-Control: max-age` 등으로 허락한 `GET` 응답을 메모리에 저장
+//!  • 모든 응답에 `X-Cache` 헤더: `HIT`(캐시), `MISS`(실서버), `REVALIDATED`(304로 재검증),
+//!    `BYPASS`(캐시를 쓰지 않는 요청)
+//!  • `DELETE /_proxy/cache[?uri=/경로]`: 저장된 응답 삭제 (Bearer 토큰 필요)
+//!
+
+mod cache;
 
 use axum::{
     body::Body,
-    extract::{Request, State},
-    http::uri::Uri,
+    extract::{
+        ws::{WebSocket, WebSocketUpgrade},
+        Query, Request, State,
+    },
+    http::{
+        header::{
+            AUTHORIZATION, CACHE_CONTROL, CONNECTION, CONTENT_LENGTH, ETAG, IF_NONE_MATCH, UPGRADE,
+            VARY,
+        },
+        uri::Uri,
+        HeaderMap, HeaderValue,
+    },
+    middleware::{self, Next},
     response::{IntoResponse, Response},
-    routing::get,
+    routing::{delete, get},
     Router,
 };
+use cache::{CacheKey, HttpCache, MAX_BODY_SIZE, X_CACHE};
 use hyper::StatusCode;
-use hyper_util::{client::legacy::connect::HttpConnector, rt::TokioExecutor};
+use hyper_util::{
+    client::legacy::connect::HttpConnector,
+    rt::{TokioExecutor, TokioIo},
+};
+use serde::Deserialize;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use subtle::ConstantTimeEq;
 
 // hyper 기반의 HTTP client 타입 정의
 type Client = hyper_util::client::legacy::Client<HttpConnector, Body>;
 
+// 캐시 관리 API에 사용하는 Bearer 토큰
+const ADMIN_TOKEN: &str = "secret-token";
+
+/// 📦 프록시 상태: HTTP 클라이언트 + 실서버 주소 + 응답 캐시
+#[derive(Clone)]
+struct Proxy {
+    client: Client,
+    upstream: String, // 예: "127.0.0.1:3000"
+    cache: HttpCache,
+}
+
 #[tokio::main]
 async fn main() {
     // 실서버(3000번) 먼저 띄움 (비동기 실행)
-    tokio::spawn(server());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    println!("listening on {}", listener.local_addr().unwrap());
+    tokio::spawn(server(listener));
 
     // hyper 기반 클라이언트 생성
     let client: Client =
@@ -45,7 +96,11 @@ async fn main() {
             .build(HttpConnector::new());
 
     // 4000번 포트에 바인딩된 리버스 프록시 서버 구성
-    let app = Router::new().route("/", get(handler)).with_state(client); // 클라이언트 주입
+    let app = proxy_app(Proxy {
+        client, // 클라이언트 주입
+        upstream: "127.0.0.1:3000".to_owned(),
+        cache: HttpCache::default(),
+    });
 
     let listener = tokio::net::TcpListener::bind("127.0.0.1:4000")
         .await
@@ -56,25 +111,80 @@ async fn main() {
     axum::serve(listener, app).await.unwrap();
 }
 
-/// 🔁 Reverse Proxy 핸들러 구현
+fn proxy_app(proxy: Proxy) -> Router {
+    // 🔐 캐시 관리 (실서버 경로와 겹치지 않도록 `/_proxy` 아래에 둠)
+    let purge = delete(purge_cache).layer(middleware::from_fn(require_admin));
+
+    Router::new()
+        .route("/_proxy/cache", purge)
+        // 경로와 상관없이 모든 요청을 전달 (SPA 개발 서버처럼 경로가 정해져 있지 않은 경우)
+        .fallback(handler)
+        .with_state(proxy)
+}
+
+// 🔁 Reverse Proxy 핸들러 구현
 
 // 4000번 포트에 들어온 요청을 3000번으로 프록시
-async fn handler(State(client): State<Client>, mut req: Request) -> Result<Response, StatusCode> {
-    // 요청 path 와 query 추출
+async fn handler(State(proxy): State<Proxy>, mut req: Request) -> Result<Response, StatusCode> {
+    // 요청 path 와 query 추출 (캐시 키로도 사용)
     let path = req.uri().path();
     let path_query = req
         .uri()
         .path_and_query()
         .map(|v| v.as_str())
-        .unwrap_or(path);
+        .unwrap_or(path)
+        .to_owned();
 
     // 새로운 URI 생성 (실서버 대상)
-    let uri = format!("http://127.0.0.1:3000{}", path_query);
+    let uri = format!("http://{}{}", proxy.upstream, path_query);
 
     // 요청 URI를 변경
     *req.uri_mut() = Uri::try_from(uri).unwrap();
 
-    // hyper 클라이언트를 통해 요청 전달
+    // WebSocket 등 업그레이드 요청은 연결을 이어 붙여야 하므로 따로 처리
+    if is_upgrade_request(req.headers()) {
+        return proxy_upgrade(proxy.client, req).await;
+    }
+
+    // 캐시를 쓰지 않는 요청은 그대로 전달
+    if cache::bypasses_cache(req.method(), req.headers()) {
+        let modifies = !req.method().is_safe();
+        let mut res = forward(&proxy.client, req).await?;
+        // 데이터를 바꾸는 요청이 성공하면 같은 경로의 저장된 응답은 더 이상 맞지 않음
+        if modifies && (res.status().is_success() || res.status().is_redirection()) {
+            proxy.cache.purge(Some(&path_query));
+        }
+        res.headers_mut()
+            .insert(X_CACHE, HeaderValue::from_static("BYPASS"));
+        return Ok(res);
+    }
+
+    let key = CacheKey::new(req.method(), &path_query);
+    let request_headers = req.headers().clone();
+    let cached = proxy.cache.lookup(&key, &request_headers);
+
+    if let Some(entry) = &cached {
+        if entry.is_fresh() && !cache::requires_revalidation(&request_headers) {
+            return Ok(entry.respond(&request_headers, "HIT"));
+        }
+        // 만료됨 → ETag가 있으면 바뀌었는지만 물어봄 (안 바뀌었으면 304, 본문 없음)
+        if let Some(etag) = entry.etag() {
+            req.headers_mut().insert(IF_NONE_MATCH, etag.clone());
+        }
+    }
+
+    let res = forward(&proxy.client, req).await?;
+    if res.status() == StatusCode::NOT_MODIFIED {
+        if let Some(entry) = cached.filter(|entry| entry.etag().is_some()) {
+            let entry = proxy.cache.refresh(&key, &entry, res.headers());
+            return Ok(entry.respond(&request_headers, "REVALIDATED"));
+        }
+    }
+    store_and_respond(&proxy.cache, key, &request_headers, res).await
+}
+
+// hyper 클라이언트를 통해 요청 전달
+async fn forward(client: &Client, req: Request) -> Result<Response, StatusCode> {
     Ok(client
         .request(req)
         .await
@@ -82,22 +192,436 @@ async fn handler(State(client): State<Client>, mut req: Request) -> Result<Respo
         .into_response())
 }
 
-/// 🧭 프록시 뒤에서 실제 응답을 제공하는 `실서버` 구성 (3000번 포트)
-async fn server() {
-    let app = Router::new().route("/", get(|| async { "Hello, world!" }));
+/// 실서버 응답을 저장할 수 있으면 저장하고 사용자에게 전달
+///
+/// 본문을 모두 받아야 저장할 수 있으므로 길이를 아는 작은 응답만 모으고,
+/// 나머지는 받는 대로 바로 흘려보냄
+async fn store_and_respond(
+    cache: &HttpCache,
+    key: CacheKey,
+    request_headers: &HeaderMap,
+    res: Response,
+) -> Result<Response, StatusCode> {
+    let (mut parts, body) = res.into_parts();
+    let content_length = parts
+        .headers
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok()?.parse::<u64>().ok());
 
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+    let res = if content_length.is_some_and(|len| len <= MAX_BODY_SIZE) {
+        let body = axum::body::to_bytes(body, MAX_BODY_SIZE as usize)
+            .await
+            .map_err(|_| StatusCode::BAD_GATEWAY)?;
+        cache.store(
+            key,
+            request_headers,
+            parts.status,
+            &parts.headers,
+            body.clone(),
+        );
+        parts
+            .headers
+            .insert(X_CACHE, HeaderValue::from_static("MISS"));
+        Response::from_parts(parts, Body::from(body))
+    } else {
+        parts
+            .headers
+            .insert(X_CACHE, HeaderValue::from_static("MISS"));
+        Response::from_parts(parts, body)
+    };
+    Ok(res)
+}
+
+// `Authorization: Bearer <ADMIN_TOKEN>` 이 없으면 401
+async fn require_admin(req: Request, next: Next) -> Result<Response, StatusCode> {
+    let token = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    // 비교에 걸린 시간으로 토큰을 추측하지 못하도록 상수 시간 비교
+    let valid =
+        token.is_some_and(|token| bool::from(token.as_bytes().ct_eq(ADMIN_TOKEN.as_bytes())));
+    if !valid {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    Ok(next.run(req).await)
+}
+
+#[derive(Deserialize)]
+struct PurgeParams {
+    uri: Option<String>, // 예: "/cached" (없으면 전체 삭제)
+}
+
+// 🧹 DELETE /_proxy/cache[?uri=/경로]
+async fn purge_cache(State(proxy): State<Proxy>, Query(params): Query<PurgeParams>) -> String {
+    let purged = proxy.cache.purge(params.uri.as_deref());
+    format!("purged {purged} entries\n")
+}
+
+/// `Connection` 헤더에 `upgrade` 토큰이 있고 `Upgrade` 헤더가 있으면 업그레이드 요청
+///
+/// `Connection: keep-alive, Upgrade`처럼 여러 토큰이 올 수 있고 대소문자도 구분하지 않음
+fn is_upgrade_request(headers: &HeaderMap) -> bool {
+    let connection_upgrade = headers
+        .get_all(CONNECTION)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|token| token.trim().eq_ignore_ascii_case("upgrade"));
+    connection_upgrade && headers.contains_key(UPGRADE)
+}
+
+/// 🔌 업그레이드 요청 프록시
+///
+/// 핸드셰이크 요청/응답은 일반 요청처럼 전달하고, 101 응답 이후에는
+/// 사용자 ↔ 프록시, 프록시 ↔ 실서버 두 연결을 이어 붙임
+async fn proxy_upgrade(client: Client, mut req: Request) -> Result<Response, StatusCode> {
+    // 사용자 쪽 연결의 업그레이드 핸들 (101 응답을 보낸 뒤에 완료됨)
+    let client_upgrade = hyper::upgrade::on(&mut req);
+
+    let mut res = client
+        .request(req)
         .await
-        .unwrap();
-    println!("listening on {}", listener.local_addr().unwrap());
+        .map_err(|_| StatusCode::BAD_GATEWAY)?;
+
+    // 실서버가 업그레이드를 거절하면(인증 실패 등) 그 응답을 그대로 전달
+    if res.status() != StatusCode::SWITCHING_PROTOCOLS {
+        return Ok(res.into_response());
+    }
+
+    // 실서버 쪽 연결의 업그레이드 핸들
+    let upstream_upgrade = hyper::upgrade::on(&mut res);
+
+    tokio::spawn(async move {
+        let (client, upstream) = match tokio::try_join!(client_upgrade, upstream_upgrade) {
+            Ok(upgraded) => upgraded,
+            Err(e) => {
+                println!("upgrade error: {e}");
+                return;
+            }
+        };
+
+        // 양방향 통신: 사용자 <-> 실서버 (어느 한쪽이 닫을 때까지)
+        let mut client = TokioIo::new(client);
+        let mut upstream = TokioIo::new(upstream);
+        match tokio::io::copy_bidirectional(&mut client, &mut upstream).await {
+            Ok((from_client, from_upstream)) => println!(
+                "upgraded connection closed: client wrote {from_client} bytes, upstream wrote {from_upstream} bytes"
+            ),
+            Err(e) => println!("upgraded connection error: {e}"),
+        }
+    });
+
+    // 101 응답을 사용자에게 보내면 사용자 쪽 업그레이드가 완료됨
+    Ok(res.into_response())
+}
+
+/// 🧭 프록시 뒤에서 실제 응답을 제공하는 `실서버` 구성 (3000번 포트)
+async fn server(listener: tokio::net::TcpListener) {
+    // 실서버가 응답한 횟수 (본문의 #번호가 같으면 캐시에서 온 응답)
+    let hits = Arc::new(AtomicUsize::new(0));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello, world!" }))
+        .route("/ws", get(ws_echo)) // WebSocket 에코 (업그레이드 프록시 확인용)
+        // 캐시 확인용 경로들
+        .route(
+            "/cached",
+            get(cached).post(|| async { StatusCode::NO_CONTENT }),
+        )
+        .route("/revalidate", get(revalidate))
+        .route("/lang", get(lang))
+        .route("/private", get(private))
+        .with_state(hits);
+
     axum::serve(listener, app).await.unwrap();
 }
 
+fn next_hit(hits: &AtomicUsize) -> usize {
+    hits.fetch_add(1, Ordering::SeqCst) + 1
+}
+
+// 10초 동안 캐시 가능
+async fn cached(State(hits): State<Arc<AtomicUsize>>) -> impl IntoResponse {
+    (
+        [(CACHE_CONTROL, "public, max-age=10")],
+        format!("cached response #{}\n", next_hit(&hits)),
+    )
+}
+
+// 저장은 하되 매번 ETag로 재검증 (내용이 바뀌지 않으므로 항상 304)
+async fn revalidate(State(hits): State<Arc<AtomicUsize>>, headers: HeaderMap) -> impl IntoResponse {
+    const VERSION: &str = "\"v1\"";
+    let hit = next_hit(&hits);
+    let cache_headers = [(CACHE_CONTROL, "no-cache"), (ETAG, VERSION)];
+
+    if headers.get(IF_NONE_MATCH).is_some_and(|tag| tag == VERSION) {
+        return (StatusCode::NOT_MODIFIED, cache_headers, String::new());
+    }
+    (
+        StatusCode::OK,
+        cache_headers,
+        format!("revalidated response #{hit}\n"),
+    )
+}
+
+// Accept-Language마다 따로 저장
+async fn lang(State(hits): State<Arc<AtomicUsize>>, headers: HeaderMap) -> impl IntoResponse {
+    let korean = headers
+        .get("accept-language")
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("ko"));
+    let greeting = if korean { "안녕하세요" } else { "hello" };
+    (
+        [(CACHE_CONTROL, "max-age=60"), (VARY, "Accept-Language")],
+        format!("{greeting} #{}\n", next_hit(&hits)),
+    )
+}
+
+// 사용자별 응답 → 공유 캐시(프록시)에는 저장하지 않음
+async fn private(State(hits): State<Arc<AtomicUsize>>) -> impl IntoResponse {
+    (
+        [(CACHE_CONTROL, "private, max-age=60")],
+        format!("private response #{}\n", next_hit(&hits)),
+    )
+}
+
+async fn ws_echo(ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(|mut socket: WebSocket| async move {
+        while let Some(Ok(msg)) = socket.recv().await {
+            if socket.send(msg).await.is_err() {
+                break;
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{SinkExt, StreamExt};
+    use std::net::SocketAddr;
+    use tokio_tungstenite::tungstenite::Message;
+    use tower::ServiceExt;
+
+    // 실서버를 임의 포트에 띄우고 그 앞의 프록시 라우터 반환
+    async fn proxy() -> Router {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let upstream = listener.local_addr().unwrap().to_string();
+        tokio::spawn(server(listener));
+
+        let client = hyper_util::client::legacy::Client::<(), ()>::builder(TokioExecutor::new())
+            .build(HttpConnector::new());
+        proxy_app(Proxy {
+            client,
+            upstream,
+            cache: HttpCache::default(),
+        })
+    }
+
+    // 프록시도 임의 포트에 띄우고 주소 반환 (WebSocket 테스트용)
+    async fn spawn_proxy() -> SocketAddr {
+        let app = proxy().await;
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        addr
+    }
+
+    #[tokio::test]
+    async fn websocket_is_proxied() {
+        let addr = spawn_proxy().await;
+
+        let (mut socket, response) = tokio_tungstenite::connect_async(format!("ws://{addr}/ws"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SWITCHING_PROTOCOLS);
+
+        for text in ["hello", "world"] {
+            socket.send(Message::text(text)).await.unwrap();
+            let echoed = socket.next().await.unwrap().unwrap();
+            assert_eq!(echoed, Message::text(text));
+        }
+        socket.close(None).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn rejected_upgrade_is_passed_through() {
+        let addr = spawn_proxy().await;
+
+        // 실서버에 없는 경로 → 업그레이드 대신 404가 그대로 전달됨
+        let err = tokio_tungstenite::connect_async(format!("ws://{addr}/nope"))
+            .await
+            .unwrap_err();
+        match err {
+            tokio_tungstenite::tungstenite::Error::Http(response) => {
+                assert_eq!(response.status(), StatusCode::NOT_FOUND)
+            }
+            err => panic!("unexpected error: {err}"),
+        }
+    }
+
+    // (상태 코드, X-Cache, 본문)
+    async fn send(app: &Router, req: Request) -> (StatusCode, String, String) {
+        let res = app.clone().oneshot(req).await.unwrap();
+        let status = res.status();
+        let x_cache = res.headers()[X_CACHE].to_str().unwrap().to_owned();
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, x_cache, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    fn get(uri: &str) -> Request {
+        Request::get(uri).body(Body::empty()).unwrap()
+    }
+
+    fn get_with(uri: &str, name: &'static str, value: &'static str) -> Request {
+        Request::get(uri)
+            .header(name, value)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn fresh_responses_are_served_from_cache() {
+        let app = proxy().await;
+
+        let first = send(&app, get("/cached")).await;
+        assert_eq!(
+            first,
+            (StatusCode::OK, "MISS".into(), "cached response #1\n".into())
+        );
+
+        let res = app.clone().oneshot(get("/cached")).await.unwrap();
+        assert_eq!(res.headers()[X_CACHE], "HIT");
+        assert_eq!(res.headers()["age"], "0");
+        let second = send(&app, get("/cached")).await;
+        assert_eq!(second.2, "cached response #1\n");
+
+        // 새로고침(no-cache)은 저장된 응답을 그대로 쓰지 않음 (ETag가 없으므로 새로 받음)
+        let reload = send(&app, get_with("/cached", "cache-control", "no-cache")).await;
+        assert_eq!(reload.1, "MISS");
+        assert_eq!(reload.2, "cached response #2\n");
+    }
+
+    #[tokio::test]
+    async fn stale_responses_are_revalidated_with_etag() {
+        let app = proxy().await;
+
+        let first = send(&app, get("/revalidate")).await;
+        assert_eq!(first.1, "MISS");
+
+        // 실서버는 304만 보내고, 본문은 저장된 것을 사용
+        let second = send(&app, get("/revalidate")).await;
+        assert_eq!(
+            second,
+            (
+                StatusCode::OK,
+                "REVALIDATED".into(),
+                "revalidated response #1\n".into()
+            )
+        );
+
+        // 사용자가 같은 ETag를 갖고 있으면 본문 없이 304
+        let conditional = send(&app, get_with("/revalidate", "if-none-match", "\"v1\"")).await;
+        assert_eq!(
+            conditional,
+            (
+                StatusCode::NOT_MODIFIED,
+                "REVALIDATED".into(),
+                String::new()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn vary_and_private_responses() {
+        let app = proxy().await;
+
+        let ko = send(&app, get_with("/lang", "accept-language", "ko-KR")).await;
+        let en = send(&app, get_with("/lang", "accept-language", "en-US")).await;
+        assert_eq!((ko.1.as_str(), ko.2.as_str()), ("MISS", "안녕하세요 #1\n"));
+        assert_eq!((en.1.as_str(), en.2.as_str()), ("MISS", "hello #2\n"));
+        let ko = send(&app, get_with("/lang", "accept-language", "ko-KR")).await;
+        assert_eq!((ko.1.as_str(), ko.2.as_str()), ("HIT", "안녕하세요 #1\n"));
+
+        let first = send(&app, get("/private")).await;
+        let second = send(&app, get("/private")).await;
+        assert_eq!(second.1, "MISS");
+        assert_ne!(first.2, second.2);
+    }
+
+    #[tokio::test]
+    async fn unsafe_requests_bypass_and_invalidate() {
+        let app = proxy().await;
+        send(&app, get("/cached")).await;
+
+        let post = Request::post("/cached").body(Body::empty()).unwrap();
+        assert_eq!(
+            send(&app, post).await,
+            (StatusCode::NO_CONTENT, "BYPASS".into(), String::new())
+        );
+        assert_eq!(send(&app, get("/cached")).await.1, "MISS");
+    }
+
+    #[tokio::test]
+    async fn purge_requires_token() {
+        let app = proxy().await;
+        send(&app, get("/cached")).await;
+
+        let purge = |token: &str| {
+            Request::delete("/_proxy/cache?uri=/cached")
+                .header(AUTHORIZATION, format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap()
+        };
+        let res = app.clone().oneshot(purge("wrong")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(send(&app, get("/cached")).await.1, "HIT");
+
+        let res = app.clone().oneshot(purge(ADMIN_TOKEN)).await.unwrap();
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, "purged 1 entries\n");
+        assert_eq!(send(&app, get("/cached")).await.1, "MISS");
+    }
+
+    #[test]
+    fn detects_upgrade_requests() {
+        let headers = |connection: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(CONNECTION, connection.parse().unwrap());
+            headers.insert(UPGRADE, "websocket".parse().unwrap());
+            headers
+        };
+        assert!(is_upgrade_request(&headers("Upgrade")));
+        assert!(is_upgrade_request(&headers("keep-alive, upgrade")));
+        assert!(!is_upgrade_request(&headers("keep-alive")));
+
+        let mut no_upgrade_header = headers("upgrade");
+        no_upgrade_header.remove(UPGRADE);
+        assert!(!is_upgrade_request(&no_upgrade_header));
+    }
+}
+
 // 🧪 테스트 방법
 // # 프록시 경유 요청
 // curl http://localhost:4000/
 // # → 프록시 서버가 받은 요청을 3000번에 전달
 // # → 3000번 서버의 응답을 사용자에게 전달
+//
+// # WebSocket 프록시 (websocat 사용)
+// websocat ws://localhost:4000/ws
+// # → 입력한 내용이 3000번 서버를 거쳐 그대로 돌아옴
+//
+// # 응답 캐시
+// curl -i http://localhost:4000/cached       # X-Cache: MISS → 다시 실행하면 HIT (10초 동안)
+// curl -i http://localhost:4000/revalidate   # 두 번째부터 X-Cache: REVALIDATED
+// curl -X DELETE http://localhost:4000/_proxy/cache \
+//   -H "Authorization: Bearer secret-token"
 
 // ✅ Reverse Proxy vs 일반 Proxy 비교
 // 1. 주 사용 대상
@@ -127,6 +651,5 @@ async fn server() {
 // 🧠 실무 확장 아이디어
 // 경로 기반 프록시: /api -> localhost:3000, /admin -> localhost:5000
 // 헤더 추가: 프록시 요청에 인증 헤더 자동 삽입
-// 캐싱: 프록시 응답을 캐싱하여 백엔드 부하 감소
 // 로드 밸런싱: 여러 백엔드 중 하나로 요청 분산
 // 보안 강화: 백엔드는 내부망만 열고, 프록시에서 인증 처리
@@ -1,25 +1,22 @@
 //! TLS 서버 구성 및 우아한 종료를 포함한 HTTPS Axum 예제
 //! Axum + rustls 기반의 HTTPS 서버에 대한 graceful shutdown 처리와 함께,
 //! HTTP 요청을 HTTPS로 자동 리디렉션하는 두 개의 서버를 동시에 실행하는 예제.
+//! 종료 시 각 서브시스템이 등록한 정리 작업은 `ShutdownCoordinator`가 순서대로 실행.
 
-use axum::{
-    handler::HandlerWithoutStateExt,
-    http::{uri::Authority, StatusCode, Uri},
-    response::Redirect,
-    routing::get,
-    BoxError, Router,
-};
-use axum_extra::extract::Host;
+mod shutdown;
+
+use axum::{routing::get, Router};
 use axum_server::tls_rustls::RustlsConfig;
-use std::{future::Future, net::SocketAddr, path::PathBuf, time::Duration};
+use example_common_tls::{spawn_http_redirect, Ports};
+use shutdown::ShutdownCoordinator;
+use std::{net::SocketAddr, path::PathBuf, time::Duration};
 use tokio::signal;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-#[derive(Clone, Copy)]
-struct Ports {
-    http: u16,  // 리디렉션용 HTTP 포트
-    https: u16, // TLS 처리용 HTTPS 포트
-}
+// 진행 중인 연결을 기다리는 시간 (docker가 강제 종료하기 전까지 기다리는 시간이 10초)
+const GRACE_PERIOD: Duration = Duration::from_secs(10);
+// 정리 hook 하나에 허용하는 시간
+const HOOK_TIMEOUT: Duration = Duration::from_secs(5);
 
 #[tokio::main]
 async fn main() {
@@ -37,14 +34,18 @@ async fn main() {
         https: 3000,
     };
 
+    // 서브시스템 시작 + 종료 시 실행할 정리 작업 등록
+    let coordinator = ShutdownCoordinator::new(HOOK_TIMEOUT);
+    start_subsystems(&coordinator);
+
     // TLS 서버의 종료 신호를 처리하기 위한 핸들 생성
     let handle = axum_server::Handle::new();
 
-    // Ctrl+C 또는 SIGTERM 수신 시 호출될 종료 future 준비
-    let shutdown_future = shutdown_signal(handle.clone());
+    // Ctrl+C 또는 SIGTERM 수신 시 종료 시작
+    tokio::spawn(shutdown_signal(handle.clone(), coordinator.clone()));
 
-    // 보조 서버: HTTP → HTTPS 리디렉션을 백그라운드로 실행
-    tokio::spawn(redirect_http_to_https(ports, shutdown_future));
+    // 보조 서버: HTTP → HTTPS 리디렉션을 백그라운드로 실행 (common-tls)
+    spawn_http_redirect(ports, coordinator.cancelled());
 
     // rustls 인증서 설정 (PEM 포맷 인증서 + 키)
     let config = RustlsConfig::from_pem_file(
@@ -58,7 +59,9 @@ async fn main() {
     .await
     .unwrap();
 
-    let app = Router::new().route("/", get(handler));
+    let app = Router::new()
+        .route("/", get(handler))
+        .route("/slow", get(slow_handler));
 
     // HTTPS 서버 구동
     let addr = SocketAddr::from(([127, 0, 0, 1], ports.https));
@@ -69,10 +72,46 @@ async fn main() {
         .serve(app.into_make_service())
         .await
         .unwrap();
+
+    // 연결이 모두 끝난(또는 유예 시간이 지난) 뒤에 정리 작업 실행
+    coordinator.run_hooks().await;
+    tracing::info!("shutdown complete");
+}
+
+// 🧩 예제용 서브시스템 (실제 앱에서는 DB 풀, 작업 큐, WebSocket 허브 등)
+// 의존하는 쪽부터 먼저 멈추도록 등록: 작업자 → WebSocket 허브 → DB 풀
+fn start_subsystems(coordinator: &ShutdownCoordinator) {
+    // 백그라운드 작업자: 종료가 시작되면 지금 하던 작업까지만 끝내고 멈춤
+    let cancelled = coordinator.cancelled();
+    let worker = tokio::spawn(async move {
+        tokio::pin!(cancelled);
+        let mut interval = tokio::time::interval(Duration::from_secs(3));
+        loop {
+            tokio::select! {
+                _ = &mut cancelled => break,
+                _ = interval.tick() => tracing::debug!("background worker: processing jobs"),
+            }
+        }
+    });
+    coordinator.register("background worker", || async move {
+        let _ = worker.await;
+    });
+
+    // WebSocket 허브: 접속 중인 클라이언트에게 종료를 알리고 세션을 닫음
+    coordinator.register("websocket hub", || async {
+        tracing::info!("notifying websocket clients and closing sessions");
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    });
+
+    // DB 풀: 다른 서브시스템이 모두 멈춘 뒤 마지막에 연결 닫기
+    coordinator.register("db pool", || async {
+        tracing::info!("closing database connections");
+        tokio::time::sleep(Duration::from_millis(300)).await;
+    });
 }
 
 // 종료 신호 수신 시 서버를 우아하게 종료하는 future
-async fn shutdown_signal(handle: axum_server::Handle) {
+async fn shutdown_signal(handle: axum_server::Handle, coordinator: ShutdownCoordinator) {
     let ctrl_c = async {
         signal::ctrl_c()
             .await
@@ -99,9 +138,11 @@ async fn shutdown_signal(handle: axum_server::Handle) {
     }
 
     tracing::info!("Received termination signal shutting down");
-    // 종료 요청: 10초 내 종료를 시도함
-    handle.graceful_shutdown(Some(Duration::from_secs(10))); // 10 secs is how long docker will wait
-                                                             // to force shutdown
+    coordinator.trigger();
+
+    // 종료 요청: 새 연결은 받지 않고, 진행 중인 연결은 GRACE_PERIOD 동안 기다림
+    handle.graceful_shutdown(Some(GRACE_PERIOD));
+    shutdown::log_draining(handle, GRACE_PERIOD).await;
 }
 
 // 기본 라우트 핸들러
@@ -109,58 +150,10 @@ async fn handler() -> &'static str {
     "Hello, World!"
 }
 
-// 보조 서버: HTTP 요청을 HTTPS로 리디렉션 처리
-async fn redirect_http_to_https<F>(ports: Ports, signal: F)
-where
-    F: Future<Output = ()> + Send + 'static,
-{
-    // 요청 host 와 URI 를 기반으로 HTTPS 버전으로 변환
-    fn make_https(host: &str, uri: Uri, https_port: u16) -> Result<Uri, BoxError> {
-        let mut parts = uri.into_parts();
-
-        parts.scheme = Some(axum::http::uri::Scheme::HTTPS);
-
-        // path가 비어 있으면 "/"로 설정
-        if parts.path_and_query.is_none() {
-            parts.path_and_query = Some("/".parse().unwrap());
-        }
-
-        // 호스트에서 포트 제거 (e.g. localhost:7878 → localhost)
-        let authority: Authority = host.parse()?;
-        let bare_host = match authority.port() {
-            Some(port_struct) => authority
-                .as_str()
-                .strip_suffix(port_struct.as_str())
-                .unwrap()
-                .strip_suffix(':')
-                .unwrap(), // if authority.port() is Some(port) then we can be sure authority ends with :{port}
-            None => authority.as_str(),
-        };
-
-        parts.authority = Some(format!("{bare_host}:{https_port}").parse()?);
-
-        Ok(Uri::from_parts(parts)?)
-    }
-
-    // 리디렉션 처리 핸들러
-    let redirect = move |Host(host): Host, uri: Uri| async move {
-        match make_https(&host, uri, ports.https) {
-            Ok(uri) => Ok(Redirect::permanent(&uri.to_string())),
-            Err(error) => {
-                tracing::warn!(%error, "failed to convert URI to HTTPS");
-                Err(StatusCode::BAD_REQUEST)
-            }
-        }
-    };
-
-    let addr = SocketAddr::from(([127, 0, 0, 1], ports.http));
-    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    tracing::debug!("listening on {addr}");
-
-    axum::serve(listener, redirect.into_make_service())
-        .with_graceful_shutdown(signal) // 종료 시 함께 멈추도록
-        .await
-        .unwrap();
+// 오래 걸리는 요청 (종료 중 연결이 정리되는 과정을 확인할 때 사용)
+async fn slow_handler() -> &'static str {
+    tokio::time::sleep(Duration::from_secs(5)).await;
+    "Finally done"
 }
 
 // • axum_server::Handle을 이용한 우아한 종료(graceful shutdown)
@@ -187,9 +180,15 @@ where
 //
 // 	2.	브라우저 또는 curl 요청:
 //   curl -v http://localhost:7878
-//   # → 301 리디렉션 → https://localhost:3000
+//   # → 308 리디렉션 → https://localhost:3000
 //
 //   curl -k https://localhost:3000
 //   # → "Hello, World!"
 //
 // 	3.	Ctrl+C 누르면 10초 동안 graceful하게 종료됨
+//
+// 	4.	진행 중인 요청이 있을 때 종료:
+//   curl -k https://localhost:3000/slow &
+//   kill -TERM <pid>
+//   # → 1초마다 "draining connections remaining=1" 로그
+//   # → 연결이 끝나면 background worker → websocket hub → db pool 순서로 정리 후 종료
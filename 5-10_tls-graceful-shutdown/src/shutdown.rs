@@ -0,0 +1,187 @@
+//! 🛑 종료 조정자 (ShutdownCoordinator)
+//!
+//! 1. 각 서브시스템(DB 풀, 백그라운드 작업, WebSocket 허브 등)은 시작할 때 정리 hook을 등록
+//! 2. 종료 신호를 받으면 `trigger()` → `cancelled()`를 기다리던 작업들이 멈추기 시작
+//! 3. 서버가 연결을 모두 정리한 뒤 `run_hooks()`로 hook을 **등록 순서대로** 하나씩 실행
+//!
+//! hook 하나가 멈춰도 전체 종료가 막히지 않도록 hook마다 타임아웃을 적용함
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::sync::watch;
+
+type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+type HookFn = Box<dyn FnOnce() -> BoxFuture + Send>;
+
+struct Hook {
+    name: String,
+    run: HookFn,
+}
+
+#[derive(Clone)]
+pub struct ShutdownCoordinator {
+    hooks: Arc<Mutex<Vec<Hook>>>,
+    hook_timeout: Duration,
+    triggered: watch::Sender<bool>,
+}
+
+/// hook 하나의 실행 결과
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookReport {
+    pub name: String,
+    pub elapsed: Duration,
+    pub timed_out: bool,
+}
+
+impl ShutdownCoordinator {
+    pub fn new(hook_timeout: Duration) -> Self {
+        Self {
+            hooks: Default::default(),
+            hook_timeout,
+            triggered: watch::channel(false).0,
+        }
+    }
+
+    /// 🪝 정리 작업 등록 (종료 시 등록한 순서대로 실행)
+    pub fn register<F, Fut>(&self, name: impl Into<String>, hook: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.hooks.lock().unwrap().push(Hook {
+            name: name.into(),
+            run: Box::new(move || Box::pin(hook())),
+        });
+    }
+
+    /// 종료 시작 알림 (여러 번 호출해도 한 번만 적용)
+    pub fn trigger(&self) {
+        self.triggered
+            .send_if_modified(|triggered| !std::mem::replace(triggered, true));
+    }
+
+    /// 종료가 시작되면 끝나는 future (작업 루프의 `select!`나 `with_graceful_shutdown`에 사용)
+    pub fn cancelled(&self) -> impl Future<Output = ()> + Send + 'static {
+        let mut triggered = self.triggered.subscribe();
+        async move {
+            // Sender가 self 안에 있으므로 wait_for가 에러를 낼 일은 없음
+            let _ = triggered.wait_for(|triggered| *triggered).await;
+        }
+    }
+
+    /// 등록된 hook 실행 (이미 실행한 hook은 다시 실행하지 않음)
+    pub async fn run_hooks(&self) -> Vec<HookReport> {
+        let hooks = std::mem::take(&mut *self.hooks.lock().unwrap());
+        let total = hooks.len();
+        let mut reports = Vec::with_capacity(total);
+
+        for (i, hook) in hooks.into_iter().enumerate() {
+            tracing::info!("[{}/{total}] running shutdown hook `{}`", i + 1, hook.name);
+            let start = Instant::now();
+            let timed_out = tokio::time::timeout(self.hook_timeout, (hook.run)())
+                .await
+                .is_err();
+            let elapsed = start.elapsed();
+
+            if timed_out {
+                tracing::warn!(?elapsed, "shutdown hook `{}` timed out", hook.name);
+            } else {
+                tracing::info!(?elapsed, "shutdown hook `{}` finished", hook.name);
+            }
+            reports.push(HookReport {
+                name: hook.name,
+                elapsed,
+                timed_out,
+            });
+        }
+        reports
+    }
+}
+
+/// 📉 유예 시간 동안 1초마다 남은 연결 수를 로그로 남김
+///
+/// 연결이 모두 끝나거나 유예 시간이 지나면 종료
+pub async fn log_draining(handle: axum_server::Handle, grace: Duration) {
+    let deadline = Instant::now() + grace;
+    let mut interval = tokio::time::interval(Duration::from_secs(1));
+
+    loop {
+        interval.tick().await;
+        let remaining = handle.connection_count();
+        if remaining == 0 {
+            tracing::info!("all connections drained");
+            return;
+        }
+
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            tracing::warn!(
+                remaining,
+                "grace period elapsed, closing remaining connections"
+            );
+            return;
+        }
+        tracing::info!(remaining, ?left, "draining connections");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn hooks_run_in_registration_order_once() {
+        let coordinator = ShutdownCoordinator::new(Duration::from_secs(1));
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        for name in ["workers", "websocket hub", "db pool"] {
+            let order = order.clone();
+            coordinator.register(name, move || async move {
+                order.lock().unwrap().push(name);
+            });
+        }
+
+        let reports = coordinator.run_hooks().await;
+        assert_eq!(
+            *order.lock().unwrap(),
+            ["workers", "websocket hub", "db pool"]
+        );
+        assert!(reports.iter().all(|report| !report.timed_out));
+
+        // 두 번째 호출에서는 아무것도 실행하지 않음
+        assert!(coordinator.run_hooks().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn slow_hook_times_out_without_blocking_the_rest() {
+        let coordinator = ShutdownCoordinator::new(Duration::from_millis(50));
+        coordinator.register("stuck", std::future::pending);
+        coordinator.register("fast", || async {});
+
+        let reports = coordinator.run_hooks().await;
+
+        assert_eq!(reports.len(), 2);
+        assert!(reports[0].timed_out);
+        assert!(reports[0].elapsed >= Duration::from_millis(50));
+        assert!(!reports[1].timed_out);
+    }
+
+    #[tokio::test]
+    async fn cancelled_resolves_after_trigger() {
+        let coordinator = ShutdownCoordinator::new(Duration::from_secs(1));
+        let before = coordinator.cancelled();
+        let task = tokio::spawn(before);
+
+        tokio::task::yield_now().await;
+        assert!(!task.is_finished());
+
+        coordinator.trigger();
+        task.await.unwrap();
+        // 이미 종료가 시작된 뒤에 만든 future는 바로 끝남
+        coordinator.cancelled().await;
+    }
+}
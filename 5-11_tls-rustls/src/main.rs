@@ -4,28 +4,12 @@
 //! 이전의 tls-graceful-shutdown 예제보다 더 단순화된 버전.
 //! axum_server::bind_rustls를 이용한 HTTPS 서버 설정과, 보조 HTTP 서버에서 HTTPS로 리디렉션 처리만을 담당.
 
-// 미사용 경고를 무시함
-#![allow(unused_imports)]
-
-use axum::{
-    handler::HandlerWithoutStateExt,
-    http::{uri::Authority, StatusCode, Uri},
-    response::Redirect,
-    routing::get,
-    BoxError, Router,
-};
-use axum_extra::extract::Host; // Host 헤더를 추출해 실제 요청 호스트 확인.
+use axum::{routing::get, Router};
 use axum_server::tls_rustls::RustlsConfig;
-use std::{net::SocketAddr, path::PathBuf};
+use example_common_tls::{spawn_http_redirect, Ports};
+use std::{future, net::SocketAddr, path::PathBuf};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-#[allow(dead_code)]
-#[derive(Clone, Copy)]
-struct Ports {
-    http: u16,  // 리디렉션용 HTTP 포트
-    https: u16, // 메인 HTTPS 포트
-}
-
 #[tokio::main]
 async fn main() {
     // 로깅 초기화
@@ -42,9 +26,9 @@ async fn main() {
         https: 3000,
     };
 
-    // 선택적 리디렉션 HTTP 서버 실행 (HTTP → HTTPS)
-    // HTTP 포트(7878)에서 들어온 요청을 HTTPS(3000)로 리다이렉션
-    tokio::spawn(redirect_http_to_https(ports));
+    // 선택적 리디렉션 HTTP 서버 실행 (HTTP → HTTPS, common-tls)
+    // HTTP 포트(7878)에서 들어온 요청을 HTTPS(3000)로 리다이렉션, 종료 처리는 없으므로 pending
+    spawn_http_redirect(ports, future::pending());
 
     // rustls 인증서 및 개인키 설정
     let config = RustlsConfig::from_pem_file(
@@ -72,68 +56,14 @@ async fn main() {
         .unwrap();
 }
 
-#[allow(dead_code)]
 async fn handler() -> &'static str {
     "Hello, World!"
 }
 
-#[allow(dead_code)]
-async fn redirect_http_to_https(ports: Ports) {
-    // 주어진 host/uri 조합을 HTTPS로 변경하는 함수
-    // 요청 URI를 .scheme = https, .authority = hostname:port 으로 바꿔줌.
-    fn make_https(host: &str, uri: Uri, https_port: u16) -> Result<Uri, BoxError> {
-        let mut parts = uri.into_parts();
-
-        parts.scheme = Some(axum::http::uri::Scheme::HTTPS);
-
-        // 경로가 없다면 "/"로 기본 설정
-        if parts.path_and_query.is_none() {
-            parts.path_and_query = Some("/".parse().unwrap());
-        }
-
-        // 호스트에서 포트를 제거하여 정제된 hostname 추출
-        let authority: Authority = host.parse()?;
-        let bare_host = match authority.port() {
-            Some(port_struct) => authority
-                .as_str()
-                .strip_suffix(port_struct.as_str())
-                .unwrap()
-                .strip_suffix(':')
-                .unwrap(), // if authority.port() is Some(port) then we can be sure authority ends with :{port}
-            None => authority.as_str(),
-        };
-
-        // 새 authority 설정: hostname:HTTPS_PORT
-        parts.authority = Some(format!("{bare_host}:{https_port}").parse()?);
-
-        Ok(Uri::from_parts(parts)?)
-    }
-
-    // 리디렉션 라우터 설정
-    let redirect = move |Host(host): Host, uri: Uri| async move {
-        match make_https(&host, uri, ports.https) {
-            Ok(uri) => Ok(Redirect::permanent(&uri.to_string())), // 301 리디렉션
-            Err(error) => {
-                tracing::warn!(%error, "failed to convert URI to HTTPS");
-                Err(StatusCode::BAD_REQUEST)
-            }
-        }
-    };
-
-    // HTTP 서버 바인딩 및 실행
-    let addr = SocketAddr::from(([127, 0, 0, 1], ports.http));
-    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    tracing::debug!("listening on {}", listener.local_addr().unwrap());
-
-    axum::serve(listener, redirect.into_make_service())
-        .await
-        .unwrap();
-}
-
 // 🧪 테스트 흐름
 // # HTTP 요청 → HTTPS로 리디렉션 (브라우저도 가능)
 // curl -v http://localhost:7878
-// # → 301 Moved Permanently → Location: https://localhost:3000
+// # → 308 Permanent Redirect → Location: https://localhost:3000
 
 // # HTTPS 요청 → 정상 응답
 // curl -k https://localhost:3000
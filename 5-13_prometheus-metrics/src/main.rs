@@ -5,37 +5,101 @@
 //! tower-http에서 공식 metrics 미들웨어가 제공되기 전까지
 //! Prometheus를 활용하여 직접 메트릭을 수집하는 예제임.
 //!
+//! scrape할 수 없는 환경이라면 Pushgateway 모드를 켤 수 있음 (`push.rs`)
+//!
+//! • 헬스 체크처럼 자주, 의미 없이 호출되는 경로는 기록하지 않음 (`METRICS_SKIP_PATHS`, 기본 `/healthz`)
+//! • 라우트에 매칭되지 않은 요청은 `path="UNMATCHED"` 하나로 묶음
+//!   → 스캐너가 보내는 `/wp-login.php`, `/.env` 같은 임의의 URI가 라벨 값으로 쌓이지 않도록 (cardinality 폭발 방지)
+//!
+//! ```not_rust
+//! PUSHGATEWAY_URL=http://localhost:9091 PUSHGATEWAY_INTERVAL_SECS=5 cargo run -p example-prometheus-metrics
+//! ```
+//!
+
+mod push;
 
 use axum::{
-    extract::{MatchedPath, Request},
+    extract::{MatchedPath, Request, State},
     middleware::{self, Next},
     response::IntoResponse,
     routing::get,
     Router,
 };
 use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use push::Pushgateway;
 use std::{
-    future::ready,
+    collections::HashSet,
+    future::Future,
+    sync::Arc,
     time::{Duration, Instant},
 };
+use tokio::{signal, sync::watch};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 // ============================
-// /metrics 엔드포인트 구성
+// 공유 상태
 // ============================
 
-fn metrics_app() -> Router {
-    let recorder_handle = setup_metrics_recorder();
+#[derive(Clone)]
+struct AppState {
+    // 전역 레코더에 기록된 메트릭을 렌더링하는 핸들
+    metrics: PrometheusHandle,
+    // 설정되어 있으면 주기적으로 Pushgateway에도 보냄 (None이면 scrape 모드만)
+    pushgateway: Option<Pushgateway>,
+}
+
+/// 요청 메트릭 기록 설정 (`track_metrics` 미들웨어의 상태)
+#[derive(Clone)]
+struct MetricsConfig {
+    // 기록하지 않을 라우트 (매칭된 경로 기준, 예: `/healthz`)
+    skip_paths: Arc<HashSet<String>>,
+}
 
+impl MetricsConfig {
+    /// 매칭되지 않은 요청의 `path` 라벨 값
+    const UNMATCHED: &'static str = "UNMATCHED";
+
+    /// 쉼표로 구분한 경로 목록 (`"/healthz, /readyz"`)
+    fn from_list(skip_paths: &str) -> Self {
+        let skip_paths = skip_paths
+            .split(',')
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+            .map(str::to_owned)
+            .collect();
+        Self {
+            skip_paths: Arc::new(skip_paths),
+        }
+    }
+
+    /// `METRICS_SKIP_PATHS` (기본 `/healthz`, 빈 값이면 모두 기록)
+    fn from_env() -> Self {
+        Self::from_list(
+            &std::env::var("METRICS_SKIP_PATHS").unwrap_or_else(|_| "/healthz".to_owned()),
+        )
+    }
+}
+
+// ============================
+// /metrics 엔드포인트 구성
+// ============================
+
+fn metrics_app(state: AppState) -> Router {
     // GET /metrics 요청 시 Prometheus 포맷으로 메트릭 렌더링
-    Router::new().route("/metrics", get(move || ready(recorder_handle.render())))
+    Router::new()
+        .route("/metrics", get(render_metrics))
+        .with_state(state)
+}
+
+async fn render_metrics(State(state): State<AppState>) -> String {
+    state.metrics.render()
 }
 
 // ============================
 // 실제 서비스용 라우터 구성
 // ============================
 
-fn main_app() -> Router {
+fn main_app(config: MetricsConfig) -> Router {
     Router::new()
         .route("/fast", get(|| async {})) // 빠른 응답
         .route(
@@ -44,16 +108,21 @@ fn main_app() -> Router {
                 tokio::time::sleep(Duration::from_secs(1)).await;
             }),
         )
+        .route("/healthz", get(|| async { "ok" })) // 로드 밸런서 헬스 체크 (기록 제외)
         // 모든 요청에 대해 메트릭 추적 미들웨어 적용
-        .route_layer(middleware::from_fn(track_metrics))
+        // `route_layer`가 아닌 `layer` → 매칭되지 않은 요청(404)도 `UNMATCHED`로 기록
+        .layer(middleware::from_fn_with_state(config, track_metrics))
 }
 
 // ============================
 // 첫 번째 서버: 메인 서비스 서버 (포트 3000)
 // ============================
 
-async fn start_main_server() {
-    let app = main_app();
+async fn start_main_server(
+    config: MetricsConfig,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) {
+    let app = main_app(config);
 
     let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
         .await
@@ -61,15 +130,21 @@ async fn start_main_server() {
 
     tracing::debug!("listening on {}", listener.local_addr().unwrap());
 
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown)
+        .await
+        .unwrap();
 }
 
 // ============================
 // 두 번째 서버: /metrics 전용 (포트 3001)
 // ============================
 
-async fn start_metrics_server() {
-    let app = metrics_app();
+async fn start_metrics_server(
+    state: AppState,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) {
+    let app = metrics_app(state);
 
     // 실무에서는 /metrics 를 외부에 노출하지 않도록 별도 포트로 구성함
     let listener = tokio::net::TcpListener::bind("127.0.0.1:3001")
@@ -78,7 +153,10 @@ async fn start_metrics_server() {
 
     tracing::debug!("listening on {}", listener.local_addr().unwrap());
 
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown)
+        .await
+        .unwrap();
 }
 
 // ============================
@@ -97,8 +175,69 @@ async fn main() {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
+    let state = AppState {
+        metrics: setup_metrics_recorder(),
+        pushgateway: Pushgateway::from_env(),
+    };
+
+    // 종료 신호를 두 서버와 push 루프에 함께 전달
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let shutdown = || {
+        let mut shutdown_rx = shutdown_rx.clone();
+        async move {
+            let _ = shutdown_rx.wait_for(|triggered| *triggered).await;
+        }
+    };
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        tracing::debug!("shutdown signal received");
+        let _ = shutdown_tx.send(true);
+    });
+
+    let push_task = state.pushgateway.clone().map(|pushgateway| {
+        tracing::debug!("pushing metrics to {}", pushgateway.url());
+        tokio::spawn(pushgateway.run(state.metrics.clone(), shutdown()))
+    });
+
     // 두 개의 서버를 병렬로 실행 (main + metrics)
-    let (_main_server, _metrics_server) = tokio::join!(start_main_server(), start_metrics_server());
+    tokio::join!(
+        start_main_server(MetricsConfig::from_env(), shutdown()),
+        start_metrics_server(state.clone(), shutdown())
+    );
+
+    // 📤 서버가 진행 중인 요청까지 모두 처리한 뒤 마지막으로 한 번 더 push
+    if let (Some(pushgateway), Some(push_task)) = (&state.pushgateway, push_task) {
+        let _ = push_task.await;
+        match pushgateway.push(&state.metrics).await {
+            Ok(()) => tracing::debug!("final metrics flushed"),
+            Err(err) => tracing::warn!(%err, "failed to flush metrics on shutdown"),
+        }
+    }
+}
+
+// Ctrl+C 또는 SIGTERM (2-10_graceful-shutdown과 같음)
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("failed to install signal handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
 }
 
 // ============================
@@ -126,15 +265,22 @@ fn setup_metrics_recorder() -> PrometheusHandle {
 // 메트릭 추적 미들웨어
 // ============================
 
-async fn track_metrics(req: Request, next: Next) -> impl IntoResponse {
+async fn track_metrics(
+    State(config): State<MetricsConfig>,
+    req: Request,
+    next: Next,
+) -> impl IntoResponse {
     // 시작 시간 기록
     let start = Instant::now();
 
-    // 요청 경로 추출 (라우팅 매칭된 path 우선)
-    let path = if let Some(matched_path) = req.extensions().get::<MatchedPath>() {
-        matched_path.as_str().to_owned()
-    } else {
-        req.uri().path().to_owned()
+    // 요청 경로 추출: 라우팅 매칭된 path (`/users/{id}`)만 라벨로 사용
+    // 실제 URI를 쓰면 요청마다 새 시계열이 생길 수 있으므로 매칭되지 않은 요청은 한 값으로 묶음
+    let path = match req.extensions().get::<MatchedPath>() {
+        Some(matched_path) if config.skip_paths.contains(matched_path.as_str()) => {
+            return next.run(req).await;
+        }
+        Some(matched_path) => matched_path.as_str().to_owned(),
+        None => MetricsConfig::UNMATCHED.to_owned(),
     };
 
     let method = req.method().clone();
@@ -162,6 +308,71 @@ async fn track_metrics(req: Request, next: Next) -> impl IntoResponse {
     response
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode};
+    use metrics_exporter_prometheus::PrometheusRecorder;
+    use std::sync::OnceLock;
+    use tower::ServiceExt;
+
+    // 미들웨어는 전역 레코더에 기록하므로 테스트 프로세스에서 한 번만 설치
+    fn handle() -> PrometheusHandle {
+        static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+        HANDLE
+            .get_or_init(|| {
+                let recorder: PrometheusRecorder = PrometheusBuilder::new().build_recorder();
+                let handle = recorder.handle();
+                metrics::set_global_recorder(recorder).unwrap();
+                handle
+            })
+            .clone()
+    }
+
+    async fn get(app: &Router, uri: &str) -> StatusCode {
+        let request = Request::get(uri).body(Body::empty()).unwrap();
+        app.clone().oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn skips_health_checks_and_buckets_unmatched_paths() {
+        let handle = handle();
+        let app = main_app(MetricsConfig::from_list("/healthz"));
+
+        assert_eq!(get(&app, "/fast").await, StatusCode::OK);
+        assert_eq!(get(&app, "/healthz").await, StatusCode::OK);
+        for uri in [
+            "/wp-login.php",
+            "/.env",
+            "/admin/../etc/passwd",
+            "/fast/extra?x=1",
+        ] {
+            assert_eq!(get(&app, uri).await, StatusCode::NOT_FOUND);
+        }
+
+        let rendered = handle.render();
+        assert!(
+            rendered.contains(r#"http_requests_total{method="GET",path="/fast",status="200"} 1"#),
+            "{rendered}"
+        );
+        assert!(
+            rendered
+                .contains(r#"http_requests_total{method="GET",path="UNMATCHED",status="404"} 4"#),
+            "{rendered}"
+        );
+        assert!(!rendered.contains("/healthz"), "{rendered}");
+        assert!(!rendered.contains("wp-login"), "{rendered}");
+    }
+
+    #[test]
+    fn skip_paths_come_from_a_comma_separated_list() {
+        let config = MetricsConfig::from_list(" /healthz, /readyz ,");
+        assert_eq!(config.skip_paths.len(), 2);
+        assert!(config.skip_paths.contains("/readyz"));
+        assert!(MetricsConfig::from_list("").skip_paths.is_empty());
+    }
+}
+
 // 🙅🏽 Prometheus 설치는 필수는 아님.
 // 예제에서 라우팅 요청(즉, HTTP 요청에 대한 메트릭)은 디스크나 DB에 저장되지 않음.
 // 메모리(RAM) 에만 임시로 저장됨.
@@ -185,6 +396,17 @@ async fn track_metrics(req: Request, next: Next) -> impl IntoResponse {
 //
 // 2. /metrics 확인 (다른 터미널에서):
 //    curl http://127.0.0.1:3001/metrics
+//
+// 2-1. 헬스 체크 제외 / 매칭되지 않은 경로 묶기:
+//    curl http://127.0.0.1:3000/healthz        → 메트릭에 나타나지 않음
+//    curl http://127.0.0.1:3000/wp-login.php   → http_requests_total{method="GET",path="UNMATCHED",status="404"}
+//    METRICS_SKIP_PATHS=/healthz,/fast cargo run -p example-prometheus-metrics  → /fast도 제외
+//
+// 3. Pushgateway 모드:
+//    docker run -p 9091:9091 prom/pushgateway
+//    PUSHGATEWAY_URL=http://localhost:9091 cargo run -p example-prometheus-metrics
+//    curl http://127.0.0.1:3000/fast 후 Ctrl+C
+//    → 종료 직전의 요청까지 포함된 메트릭이 http://localhost:9091/metrics 에 남아 있음
 
 // ⸻
 
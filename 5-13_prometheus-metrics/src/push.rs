@@ -0,0 +1,217 @@
+//! 📤 Pushgateway 모드
+//!
+//! Prometheus가 서버를 scrape할 수 없는 환경(방화벽 뒤, 짧게 실행되는 작업 등)에서는
+//! 서버가 주기적으로 Pushgateway에 메트릭을 보내고, Prometheus는 Pushgateway를 scrape함
+//!
+//! • `PUT {gateway}/metrics/job/{job}[/instance/{instance}]`로 같은 그룹의 메트릭을 통째로 교체
+//! • 종료할 때 서버가 요청을 모두 처리한 뒤 마지막으로 한 번 더 push (마지막 주기의 기록이 빠지지 않도록)
+
+use metrics_exporter_prometheus::PrometheusHandle;
+use std::{future::Future, time::Duration};
+
+#[derive(Debug, Clone)]
+pub struct Pushgateway {
+    client: reqwest::Client,
+    url: String,
+    interval: Duration,
+}
+
+impl Pushgateway {
+    /// `job`, `instance`는 URL 경로에 그대로 들어가므로 `/`가 없어야 함
+    ///
+    /// `interval`이 0이면 패닉 (`tokio::time::interval`이 spawn된 태스크 안에서 조용히 패닉하는 대신 시작할 때)
+    pub fn new(gateway: &str, job: &str, instance: Option<&str>, interval: Duration) -> Self {
+        assert!(
+            !interval.is_zero(),
+            "push interval must be greater than zero"
+        );
+        let mut url = format!("{}/metrics/job/{job}", gateway.trim_end_matches('/'));
+        if let Some(instance) = instance {
+            url.push_str(&format!("/instance/{instance}"));
+        }
+
+        Self {
+            client: reqwest::Client::builder()
+                // Pushgateway가 응답하지 않아도 종료가 막히지 않도록
+                .timeout(Duration::from_secs(5))
+                .build()
+                .unwrap(),
+            url,
+            interval,
+        }
+    }
+
+    /// 환경 변수로 설정 (`PUSHGATEWAY_URL`이 없으면 push 모드를 쓰지 않음)
+    ///
+    /// • `PUSHGATEWAY_URL`: 예) `http://localhost:9091`
+    /// • `PUSHGATEWAY_INTERVAL_SECS`: push 주기 (기본 10초, 1 이상의 정수가 아니면 시작하지 않음)
+    /// • `PUSHGATEWAY_INSTANCE`: grouping key의 instance (선택)
+    pub fn from_env() -> Option<Self> {
+        let gateway = std::env::var("PUSHGATEWAY_URL").ok()?;
+        let interval = parse_interval(std::env::var("PUSHGATEWAY_INTERVAL_SECS").ok().as_deref())
+            .unwrap_or_else(|err| panic!("{err}"));
+        let instance = std::env::var("PUSHGATEWAY_INSTANCE").ok();
+
+        Some(Self::new(
+            &gateway,
+            env!("CARGO_CRATE_NAME"),
+            instance.as_deref(),
+            interval,
+        ))
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// 현재 메트릭을 한 번 push
+    pub async fn push(&self, metrics: &PrometheusHandle) -> Result<(), reqwest::Error> {
+        self.client
+            .put(&self.url)
+            .header("content-type", "text/plain; version=0.0.4")
+            .body(metrics.render())
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// `shutdown`이 끝날 때까지 주기적으로 push
+    ///
+    /// 마지막 push는 서버가 요청을 모두 처리한 뒤 호출하는 쪽에서 `push`로 직접 함
+    pub async fn run(self, metrics: PrometheusHandle, shutdown: impl Future<Output = ()>) {
+        let mut interval = tokio::time::interval(self.interval);
+        // 첫 tick은 바로 끝나므로 건너뜀 (시작 직후의 빈 메트릭은 보내지 않음)
+        interval.tick().await;
+        tokio::pin!(shutdown);
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    // 실패해도 다음 주기에 다시 보내면 되므로 로그만 남김
+                    if let Err(err) = self.push(&metrics).await {
+                        tracing::warn!(%err, "failed to push metrics");
+                    }
+                }
+                _ = &mut shutdown => break,
+            }
+        }
+    }
+}
+
+fn parse_interval(secs: Option<&str>) -> Result<Duration, String> {
+    let Some(secs) = secs else {
+        return Ok(Duration::from_secs(10));
+    };
+    match secs.trim().parse::<u64>() {
+        Ok(secs) if secs > 0 => Ok(Duration::from_secs(secs)),
+        _ => Err(format!(
+            "PUSHGATEWAY_INTERVAL_SECS must be a positive number of seconds, got {secs:?}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::State, http::Uri, routing::put, Router};
+    use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusRecorder};
+    use std::sync::{Arc, Mutex};
+
+    type Received = Arc<Mutex<Vec<(String, String)>>>;
+
+    // 받은 (경로, body)를 기록하는 가짜 Pushgateway
+    async fn fake_gateway() -> (String, Received) {
+        let received = Received::default();
+        let app = Router::new()
+            .route("/metrics/{*group}", put(record))
+            .with_state(received.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{addr}"), received)
+    }
+
+    async fn record(State(received): State<Received>, uri: Uri, body: String) {
+        received.lock().unwrap().push((uri.path().to_owned(), body));
+    }
+
+    // 전역 레코더 대신 테스트마다 따로 만든 레코더 사용
+    fn recorder() -> PrometheusRecorder {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        metrics::with_local_recorder(&recorder, || {
+            metrics::counter!("jobs_processed_total").increment(3);
+        });
+        recorder
+    }
+
+    #[tokio::test]
+    async fn push_replaces_the_grouping_key() {
+        let (gateway, received) = fake_gateway().await;
+        let recorder = recorder();
+        let pusher = Pushgateway::new(
+            &format!("{gateway}/"),
+            "example",
+            Some("host-1"),
+            Duration::from_secs(10),
+        );
+
+        pusher.push(&recorder.handle()).await.unwrap();
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].0, "/metrics/job/example/instance/host-1");
+        assert!(received[0].1.contains("jobs_processed_total 3"));
+    }
+
+    #[tokio::test]
+    async fn run_pushes_on_interval_until_shutdown() {
+        let (gateway, received) = fake_gateway().await;
+        let recorder = recorder();
+        let pusher = Pushgateway::new(&gateway, "example", None, Duration::from_millis(50));
+
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let task = tokio::spawn(pusher.run(recorder.handle(), async {
+            let _ = shutdown_rx.await;
+        }));
+
+        tokio::time::sleep(Duration::from_millis(180)).await;
+        shutdown_tx.send(()).unwrap();
+        task.await.unwrap();
+        let pushed = received.lock().unwrap().len();
+        assert!((2..=4).contains(&pushed), "pushed {pushed} times");
+
+        // 종료 후에는 더 이상 push하지 않음
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        assert_eq!(received.lock().unwrap().len(), pushed);
+        assert!(received.lock().unwrap()[0]
+            .0
+            .ends_with("/metrics/job/example"));
+    }
+
+    #[tokio::test]
+    async fn gateway_errors_are_reported() {
+        let pusher = Pushgateway::new(
+            "http://127.0.0.1:1",
+            "example",
+            None,
+            Duration::from_secs(10),
+        );
+        assert!(pusher.push(&recorder().handle()).await.is_err());
+    }
+
+    #[test]
+    fn interval_must_be_positive() {
+        assert_eq!(parse_interval(None), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_interval(Some("5")), Ok(Duration::from_secs(5)));
+        for invalid in ["0", "-1", "abc", ""] {
+            assert!(parse_interval(Some(invalid)).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "greater than zero")]
+    fn zero_interval_is_rejected() {
+        Pushgateway::new("http://localhost:9091", "job", None, Duration::ZERO);
+    }
+}
@@ -0,0 +1,282 @@
+//! 🧾 JSON 로그 형식 (`LOG_FORMAT=json`)
+//!
+//! tracing-subscriber의 기본 JSON 형식은 span 필드를 `"span": {..}` / `"spans": [..]` 안에 넣음
+//! → 로그 수집기에서 `request_id`, `trace_id`로 검색하려면 이벤트마다 최상위에 있어야 편함
+//!
+//! • `FlatJson`: 이벤트 필드 + 이벤트를 감싼 모든 span의 필드를 한 단계 JSON 객체로 출력
+//!   → 같은 이름이면 안쪽 span, 그다음 이벤트 필드가 우선
+//! • `TraceContext`: W3C `traceparent` 헤더에서 trace_id를 이어받고, 이 서버의 span_id를 새로 만듦
+//!   → 헤더가 없거나 형식이 틀리면 trace_id도 새로 만듦
+
+use axum::http::HeaderMap;
+use serde_json::{Map, Value};
+use std::fmt;
+use tracing::{
+    field::{Field, Visit},
+    Event, Subscriber,
+};
+use tracing_subscriber::{
+    fmt::{
+        self as tracing_fmt,
+        format::{JsonFields, Writer},
+        time::{FormatTime, SystemTime},
+        FmtContext, FormatEvent, FormattedFields,
+    },
+    registry::LookupSpan,
+};
+use uuid::Uuid;
+
+/// 요청 하나의 trace_id / span_id (16진수 소문자, W3C Trace Context와 같은 길이)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    /// 32자 (16바이트)
+    pub trace_id: String,
+    /// 16자 (8바이트)
+    pub span_id: String,
+}
+
+impl TraceContext {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let trace_id = headers
+            .get("traceparent")
+            .and_then(|value| value.to_str().ok())
+            .and_then(parent_trace_id)
+            .unwrap_or_else(|| Uuid::new_v4().simple().to_string());
+        let mut span_id = Uuid::new_v4().simple().to_string();
+        span_id.truncate(16);
+
+        Self { trace_id, span_id }
+    }
+}
+
+// "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01" → trace_id
+fn parent_trace_id(traceparent: &str) -> Option<String> {
+    let mut parts = traceparent.trim().split('-');
+    let (version, trace_id, parent_id, flags) =
+        (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+
+    let is_hex = |value: &str, len: usize| {
+        value.len() == len
+            && value
+                .bytes()
+                .all(|byte| byte.is_ascii_digit() || (b'a'..=b'f').contains(&byte))
+    };
+    // 버전 ff는 금지, 모두 0인 id는 무효
+    let valid = is_hex(version, 2)
+        && version != "ff"
+        && is_hex(trace_id, 32)
+        && is_hex(parent_id, 16)
+        && is_hex(flags, 2)
+        && trace_id.bytes().any(|byte| byte != b'0')
+        && parent_id.bytes().any(|byte| byte != b'0');
+    valid.then(|| trace_id.to_owned())
+}
+
+/// `FlatJson` 형식으로 stdout에 출력하는 fmt 레이어
+pub fn layer<S>() -> tracing_fmt::Layer<S, JsonFields, FlatJson>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    // span 필드도 JSON으로 기록해 두어야 FlatJson이 다시 읽을 수 있음
+    tracing_fmt::layer()
+        .fmt_fields(JsonFields::new())
+        .event_format(FlatJson)
+}
+
+/// span 필드를 최상위로 펼친 JSON 한 줄
+pub struct FlatJson;
+
+impl<S> FormatEvent<S, JsonFields> for FlatJson
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, JsonFields>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let metadata = event.metadata();
+        let mut timestamp = String::new();
+        SystemTime.format_time(&mut Writer::new(&mut timestamp))?;
+
+        let mut object = Map::new();
+        object.insert("timestamp".to_owned(), timestamp.into());
+        object.insert("level".to_owned(), metadata.level().as_str().into());
+        object.insert("target".to_owned(), metadata.target().into());
+
+        // 바깥 span → 안쪽 span 순서로 덮어씀
+        if let Some(scope) = ctx.event_scope() {
+            for span in scope.from_root() {
+                object.insert("span".to_owned(), span.name().into());
+                let extensions = span.extensions();
+                let Some(fields) = extensions.get::<FormattedFields<JsonFields>>() else {
+                    continue;
+                };
+                // 필드가 없는 span은 빈 문자열
+                if let Ok(Value::Object(fields)) = serde_json::from_str::<Value>(fields) {
+                    object.extend(fields);
+                }
+            }
+        }
+
+        let mut visitor = JsonVisitor(Map::new());
+        event.record(&mut visitor);
+        object.extend(visitor.0);
+
+        writeln!(writer, "{}", Value::Object(object))
+    }
+}
+
+// 숫자/불리언은 JSON 타입 그대로, 나머지는 문자열
+struct JsonVisitor(Map<String, Value>);
+
+impl Visit for JsonVisitor {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_owned(), format!("{value:?}").into());
+    }
+}
+
+/// 테스트용: 출력된 로그 줄을 모으는 writer
+#[cfg(test)]
+pub mod capture {
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+    use tracing_subscriber::fmt::MakeWriter;
+
+    #[derive(Clone, Default)]
+    pub struct Lines(Arc<Mutex<Vec<u8>>>);
+
+    impl Lines {
+        pub fn json(&self) -> Vec<serde_json::Value> {
+            let output = String::from_utf8(self.0.lock().unwrap().clone()).unwrap();
+            output
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect()
+        }
+    }
+
+    impl io::Write for Lines {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Lines {
+        type Writer = Lines;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{capture::Lines, *};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn json_subscriber(lines: &Lines) -> impl Subscriber + Send + Sync {
+        tracing_subscriber::registry().with(layer().with_writer(lines.clone()))
+    }
+
+    #[test]
+    fn span_fields_are_flattened_into_each_event() {
+        let lines = Lines::default();
+        tracing::subscriber::with_default(json_subscriber(&lines), || {
+            let span = tracing::info_span!(
+                "http_request",
+                request_id = "req-1",
+                status = tracing::field::Empty,
+            );
+            let _guard = span.enter();
+            tracing::info!(user = "alice", "inside");
+            span.record("status", 200);
+            tracing::info!(latency_ms = 12u64, "finished");
+        });
+
+        let lines = lines.json();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["request_id"], "req-1");
+        assert_eq!(lines[0]["user"], "alice");
+        assert_eq!(lines[0]["message"], "inside");
+        assert_eq!(lines[0]["span"], "http_request");
+        assert!(lines[0].get("status").is_none());
+
+        assert_eq!(lines[1]["status"], 200);
+        assert_eq!(lines[1]["latency_ms"], 12);
+        assert_eq!(lines[1]["level"], "INFO");
+    }
+
+    #[test]
+    fn events_outside_spans_are_plain_objects() {
+        let lines = Lines::default();
+        tracing::subscriber::with_default(json_subscriber(&lines), || {
+            tracing::warn!(target: "startup", port = 3000, "listening");
+        });
+
+        let line = &lines.json()[0];
+        assert_eq!(line["target"], "startup");
+        assert_eq!(line["port"], 3000);
+        assert!(line.get("span").is_none());
+    }
+
+    #[test]
+    fn trace_id_is_taken_from_a_valid_traceparent() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+                .parse()
+                .unwrap(),
+        );
+        let context = TraceContext::from_headers(&headers);
+        assert_eq!(context.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+        // 이 서버의 span은 새 id
+        assert_eq!(context.span_id.len(), 16);
+        assert_ne!(context.span_id, "00f067aa0ba902b7");
+
+        for invalid in [
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736",
+            "garbage",
+        ] {
+            assert_eq!(parent_trace_id(invalid), None, "{invalid}");
+        }
+
+        let generated = TraceContext::from_headers(&HeaderMap::new());
+        assert_eq!(generated.trace_id.len(), 32);
+        assert_ne!(generated, TraceContext::from_headers(&HeaderMap::new()));
+    }
+}
@@ -0,0 +1,207 @@
+//! 🧯 최근 ERROR/WARN 이벤트를 메모리에 보관하는 레이어
+//!
+//! stdout을 뒤지지 않고 `/debug/last-errors`에서 최근 실패를 JSON으로 확인하기 위한 링 버퍼
+//!
+//! • 가득 차면 가장 오래된 이벤트부터 버림 (메모리 사용량 고정)
+//! • 이벤트가 발생한 span(`http_request` 등)의 필드도 함께 저장
+//!   → 어떤 요청(method, matched_path, client_ip)에서 난 에러인지 알 수 있음
+//! • 레벨은 `with_filter` 대신 `on_event`에서 직접 확인
+//!   → 레이어 필터는 span도 거르기 때문에 INFO 레벨인 요청 span의 필드를 읽을 수 없게 됨
+
+use serde::Serialize;
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::{
+    field::{Field, Visit},
+    span, Level, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorEvent {
+    /// Unix time (밀리초)
+    pub timestamp_ms: u128,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    /// message 외의 필드 (`key=value` 공백 구분)
+    pub fields: String,
+    /// 바깥 span부터 순서대로
+    pub spans: Vec<SpanFields>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpanFields {
+    pub name: String,
+    pub fields: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct LastErrors {
+    events: Arc<Mutex<VecDeque<ErrorEvent>>>,
+    capacity: usize,
+}
+
+impl LastErrors {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            events: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// 최신 이벤트가 앞에 오도록 반환
+    pub fn recent(&self) -> Vec<ErrorEvent> {
+        self.events.lock().unwrap().iter().rev().cloned().collect()
+    }
+
+    fn push(&self, event: ErrorEvent) {
+        let mut events = self.events.lock().unwrap();
+        if events.len() == self.capacity {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+}
+
+// span을 만들 때 필드를 문자열로 만들어 span extensions에 넣어 둠 (이벤트 시점엔 값을 다시 읽을 수 없음)
+struct RecordedFields(Vec<String>);
+
+impl<S> Layer<S> for LastErrors
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(RecordedFields(visitor.fields));
+        }
+    }
+
+    // `span.record(..)`로 나중에 채운 필드
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        values.record(&mut visitor);
+        if let Some(span) = ctx.span(id) {
+            if let Some(recorded) = span.extensions_mut().get_mut::<RecordedFields>() {
+                recorded.0.extend(visitor.fields);
+            }
+        }
+    }
+
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        // ERROR < WARN < INFO 순서 (더 자세한 레벨이 큼)
+        if *metadata.level() > Level::WARN {
+            return;
+        }
+
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+
+        let spans = ctx
+            .event_scope(event)
+            .into_iter()
+            .flat_map(|scope| scope.from_root())
+            .map(|span| SpanFields {
+                name: span.name().to_owned(),
+                fields: span
+                    .extensions()
+                    .get::<RecordedFields>()
+                    .map(|recorded| recorded.0.join(" "))
+                    .unwrap_or_default(),
+            })
+            .collect();
+
+        self.push(ErrorEvent {
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis(),
+            level: metadata.level().to_string(),
+            target: metadata.target().to_owned(),
+            message: visitor.message,
+            fields: visitor.fields.join(" "),
+            spans,
+        });
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: Vec<String>,
+}
+
+impl Visit for FieldVisitor {
+    // 문자열은 따옴표 없이 그대로
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_owned();
+        } else {
+            self.fields.push(format!("{}={value}", field.name()));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+        } else {
+            self.fields.push(format!("{}={value:?}", field.name()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn capture(log: &LastErrors, f: impl FnOnce()) {
+        let subscriber = tracing_subscriber::registry().with(log.clone());
+        tracing::subscriber::with_default(subscriber, f);
+    }
+
+    #[test]
+    fn keeps_only_recent_warnings_and_errors() {
+        let log = LastErrors::new(2);
+        capture(&log, || {
+            tracing::info!("not kept");
+            tracing::warn!("first");
+            tracing::error!("second");
+            tracing::debug!("not kept either");
+            tracing::error!("third");
+        });
+
+        let messages: Vec<_> = log.recent().into_iter().map(|e| e.message).collect();
+        assert_eq!(messages, ["third", "second"]);
+    }
+
+    #[test]
+    fn records_the_enclosing_request_span() {
+        let log = LastErrors::new(10);
+        capture(&log, || {
+            let span = tracing::info_span!(
+                "http_request",
+                method = "GET",
+                user_id = tracing::field::Empty
+            );
+            let _guard = span.enter();
+            span.record("user_id", 7);
+            tracing::error!(reason = "timeout", "failed to load");
+        });
+
+        let event = &log.recent()[0];
+        assert_eq!(event.level, "ERROR");
+        assert_eq!(event.message, "failed to load");
+        assert_eq!(event.fields, "reason=timeout");
+        assert_eq!(event.spans.len(), 1);
+        assert_eq!(event.spans[0].name, "http_request");
+        assert_eq!(event.spans[0].fields, "method=GET user_id=7");
+    }
+}
@@ -1,43 +1,127 @@
 //!
 //! Axum에서 tower-http::TraceLayer를 활용하여 HTTP 요청 흐름을 로깅(trace) 하는 방법을 보여주는 예제
 //!
+//! • 요청 span에 클라이언트 IP(`client_ip`)를 기록
+//!   → 프록시 뒤에서는 `TRUSTED_PROXIES`(CIDR 목록)를 설정하면 X-Forwarded-For(또는 `TRUSTED_PROXY_HEADER=forwarded`)의 주소 사용 (common-client-ip)
+//! • 최근 ERROR/WARN 이벤트를 요청 span 필드와 함께 링 버퍼에 보관 (`last_errors.rs`)
+//!   → `GET /debug/last-errors` (Bearer 토큰 필요)에서 JSON으로 확인
+//! • `PUT /debug/log-level`로 실행 중에 EnvFilter를 교체 (`reload::Layer`, Bearer 토큰 필요)
+//!   → 잘못된 필터는 400, 성공하면 이전 필터와 새 필터를 JSON으로 반환
+//! • `LOG_FORMAT=json`이면 한 줄에 JSON 객체 하나로 출력 (`json_log.rs`)
+//!   → `request_id`, `trace_id`, `span_id`, `status`, `latency_ms`가 모든 줄의 최상위 필드
+//!
+
+mod json_log;
+mod last_errors;
 
 use axum::{
     body::Bytes,
-    extract::MatchedPath,
-    http::{HeaderMap, Request},
+    extract::{ConnectInfo, MatchedPath, State},
+    http::{HeaderMap, Request, StatusCode},
     response::{Html, Response},
-    routing::get,
-    Router,
+    routing::{get, put},
+    Extension, Json, Router,
 };
-use std::time::Duration;
+use example_common_client_ip::{ClientIp, TrustedProxies};
+use json_log::TraceContext;
+use last_errors::{ErrorEvent, LastErrors};
+use serde::Serialize;
+use std::{env, net::SocketAddr, time::Duration};
 use tokio::net::TcpListener;
-use tower_http::{classify::ServerErrorsFailureClass, trace::TraceLayer};
+use tower_http::{
+    classify::ServerErrorsFailureClass,
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::TraceLayer,
+    validate_request::ValidateRequestHeaderLayer,
+};
 use tracing::{info_span, Span};
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{
+    layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Registry,
+};
+
+// `/debug/last-errors`에 필요한 Bearer 토큰
+const DEBUG_TOKEN: &str = "secret-token";
+
+#[derive(Clone)]
+struct AppState {
+    // 최근 ERROR/WARN 이벤트 (tracing 레이어와 같은 버퍼를 공유)
+    last_errors: LastErrors,
+    // 실행 중에 EnvFilter를 바꾸는 핸들 (registry 바로 위 레이어라 `Registry`)
+    log_filter: reload::Handle<EnvFilter, Registry>,
+}
 
 #[tokio::main]
 async fn main() {
+    let last_errors = LastErrors::new(100);
+
     // tracing 구독자 초기화 (환경 변수 기반 필터 설정 포함)
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-                // axum logs rejections from built-in extractors with the `axum::rejection`
-                // target, at `TRACE` level. `axum::rejection=trace` enables showing those events
-                // 기본 필터: 현재 크레이트 + tower_http + axum::rejection
-                format!(
-                    "{}=debug,tower_http=debug,axum::rejection=trace",
-                    env!("CARGO_CRATE_NAME")
-                )
-                .into()
-            }),
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+        // axum logs rejections from built-in extractors with the `axum::rejection`
+        // target, at `TRACE` level. `axum::rejection=trace` enables showing those events
+        // 기본 필터: 현재 크레이트 + tower_http + axum::rejection
+        format!(
+            "{}=debug,tower_http=debug,axum::rejection=trace",
+            env!("CARGO_CRATE_NAME")
         )
-        .with(tracing_subscriber::fmt::layer()) // stdout 출력용 layer
+        .into()
+    });
+    // 필터를 reload 레이어로 감싸 두면 핸들로 나중에 교체 가능
+    let (filter, log_filter) = reload::Layer::new(filter);
+
+    // LOG_FORMAT=json → 로그 수집기용 JSON, 그 외에는 사람이 읽기 쉬운 기본 형식
+    // (`Option<Layer>`도 Layer라서 둘 중 하나만 켜는 식으로 타입을 맞춤)
+    let json = env::var("LOG_FORMAT").is_ok_and(|format| format.eq_ignore_ascii_case("json"));
+    tracing_subscriber::registry()
+        .with(filter)
+        .with((!json).then(tracing_subscriber::fmt::layer)) // stdout 출력용 layer
+        .with(json.then(json_log::layer))
+        .with(last_errors.clone()) // 최근 ERROR/WARN 보관용 layer
         .init();
 
+    // 예: TRUSTED_PROXIES=10.0.0.0/8 (설정하지 않으면 연결한 주소를 그대로 기록)
+    // 프록시가 RFC 7239 헤더를 붙이면 TRUSTED_PROXY_HEADER=forwarded (기본 x-forwarded-for)
+    let trusted_proxies: TrustedProxies = env::var("TRUSTED_PROXIES")
+        .unwrap_or_default()
+        .parse::<TrustedProxies>()
+        .expect("TRUSTED_PROXIES must be a comma-separated list of CIDR ranges")
+        .header(
+            env::var("TRUSTED_PROXY_HEADER")
+                .map(|header| header.parse().unwrap())
+                .unwrap_or_default(),
+        );
+
+    let state = AppState {
+        last_errors,
+        log_filter,
+    };
+    let app = app(state, trusted_proxies);
+
+    // 서버 실행 (127.0.0.1:3000)
+    let listener = TcpListener::bind("127.0.0.1:3000").await.unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    // 클라이언트 IP 기록에 필요한 연결 정보(ConnectInfo)를 함께 전달
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
+}
+
+fn app(state: AppState, trusted_proxies: TrustedProxies) -> Router {
+    // 운영자용 엔드포인트는 토큰으로 보호
+    let debug = Router::new()
+        .route("/last-errors", get(last_errors))
+        .route("/log-level", put(set_log_level))
+        .layer(ValidateRequestHeaderLayer::bearer(DEBUG_TOKEN));
+
     // 라우터 구성
-    let app = Router::new()
+    Router::new()
         .route("/", get(handler)) // GET / → handler 실행
+        .route("/fail", get(fail)) // 항상 실패 → /debug/last-errors에 기록됨
+        .nest("/debug", debug)
+        .with_state(state)
+        .layer(Extension(trusted_proxies.clone())) // `ClientIp` 추출기가 사용
         // `TraceLayer` is provided by tower-http so you have to add that as a dependency.
         // It provides good defaults but is also very customizable.
         //
@@ -47,7 +131,7 @@ async fn main() {
         // TraceLayer 를 통해 요청/응답 흐름을 추적
         .layer(
             TraceLayer::new_for_http()
-                .make_span_with(|request: &Request<_>| {
+                .make_span_with(move |request: &Request<_>| {
                     // Log the matched route's path (with placeholders not filled in).
                     // Use request.uri() or OriginalUri if you want the real path.
                     // 요청 수신 시 tracing span 생성
@@ -57,10 +141,30 @@ async fn main() {
                         .get::<MatchedPath>()
                         .map(MatchedPath::as_str);
 
+                    // `ClientIp` 추출기와 같은 규칙 (span은 추출기 실행 전에 만들어지므로 직접 계산)
+                    // ConnectInfo는 `into_make_service_with_connect_info`가 넣어 둠
+                    let client_ip = request.extensions().get::<ConnectInfo<SocketAddr>>().map(
+                        |ConnectInfo(peer)| trusted_proxies.client_ip(peer.ip(), request.headers()),
+                    );
+
+                    // SetRequestIdLayer(바깥 레이어)가 넣어 둔 x-request-id
+                    let request_id = request
+                        .headers()
+                        .get("x-request-id")
+                        .and_then(|value| value.to_str().ok());
+                    // 상위 서비스가 보낸 traceparent가 있으면 같은 trace_id로 이어 감
+                    let trace = TraceContext::from_headers(request.headers());
+
                     info_span!(
                         "http_request",                  // 스팬 이름
                         method = ?request.method(),      // HTTP 메서드: GET, POST 등
                         matched_path,                    // 추출한 라우팅 경로
+                        client_ip = client_ip.map(tracing::field::display), // 클라이언트 IP (프록시 뒤에서도)
+                        request_id,                      // 요청 ID (응답 헤더에도 같은 값)
+                        trace_id = %trace.trace_id,      // 분산 트레이싱 ID
+                        span_id = %trace.span_id,        // 이 서버에서의 span ID
+                        status = tracing::field::Empty,  // on_response에서 기록
+                        latency_ms = tracing::field::Empty, // on_response에서 기록
                         some_other_field = tracing::field::Empty, // 나중에 record 가능
                     )
                 })
@@ -71,8 +175,12 @@ async fn main() {
                     // 요청 수신 직후 실행됨
                     // _span.record("some_other_field", value) 등으로 필드 기록 가능
                 })
-                .on_response(|_response: &Response, _latency: Duration, _span: &Span| {
+                .on_response(|response: &Response, latency: Duration, span: &Span| {
                     // 응답 직후 실행됨
+                    // span에 기록해 두면 이후 이벤트(JSON에서는 모든 줄)에 같은 값이 붙음
+                    span.record("status", response.status().as_u16());
+                    span.record("latency_ms", latency.as_millis() as u64);
+                    tracing::info!("finished processing request");
                 })
                 .on_body_chunk(|_chunk: &Bytes, _latency: Duration, _span: &Span| {
                     // 바디 청크 수신 시마다 호출됨 (스트리밍 시 유용)
@@ -87,19 +195,250 @@ async fn main() {
                         // 요청 처리 중 오류 발생 시 호출됨
                     },
                 ),
-        );
-
-    // 서버 실행 (127.0.0.1:3000)
-    let listener = TcpListener::bind("127.0.0.1:3000").await.unwrap();
-    tracing::debug!("listening on {}", listener.local_addr().unwrap());
-    axum::serve(listener, app).await.unwrap();
+        )
+        // request_id를 응답 헤더에도 그대로 전달 (5-12_request-id와 같음)
+        .layer(PropagateRequestIdLayer::x_request_id())
+        // 요청마다 UUID x-request-id 생성 (이미 있으면 유지) → TraceLayer보다 바깥에 있어야 span에 기록됨
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
 }
 
 // GET / 요청을 처리하는 핸들러
-async fn handler() -> Html<&'static str> {
+// 핸들러 안의 로그는 http_request 스팬 안에서 출력됨 (스팬 필드도 함께 출력)
+async fn handler(ClientIp(client_ip): ClientIp) -> Html<&'static str> {
+    tracing::debug!(%client_ip, "saying hello");
     Html("<h1>Hello, World!</h1>")
 }
 
+// 실패하는 핸들러: error! 이벤트는 http_request 스팬 필드와 함께 링 버퍼에 남음
+async fn fail() -> StatusCode {
+    tracing::error!(reason = "upstream timed out", "failed to load greeting");
+    StatusCode::INTERNAL_SERVER_ERROR
+}
+
+// 최근 ERROR/WARN 이벤트 (최신순)
+async fn last_errors(State(state): State<AppState>) -> Json<Vec<ErrorEvent>> {
+    Json(state.last_errors.recent())
+}
+
+#[derive(Serialize)]
+struct LogLevelChanged {
+    previous: String,
+    current: String,
+}
+
+// body: EnvFilter 문자열 (예: `debug,tower_http=trace`)
+async fn set_log_level(
+    State(state): State<AppState>,
+    directives: String,
+) -> Result<Json<LogLevelChanged>, (StatusCode, String)> {
+    let directives = directives.trim();
+    // 빈 필터는 ERROR만 남기므로 실수로 보낸 빈 body로 로그가 꺼지지 않도록 거절
+    if directives.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "empty filter".to_owned()));
+    }
+    let filter = EnvFilter::try_new(directives)
+        .map_err(|err| (StatusCode::BAD_REQUEST, format!("invalid filter: {err}")))?;
+
+    // 읽기와 교체를 한 번에 (동시에 두 요청이 와도 previous가 정확하도록)
+    let mut previous = String::new();
+    state
+        .log_filter
+        .modify(|current| {
+            previous = current.to_string();
+            *current = filter;
+        })
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    let current = state
+        .log_filter
+        .with_current(ToString::to_string)
+        .unwrap_or_default();
+
+    tracing::info!(%previous, %current, "log filter changed");
+    Ok(Json(LogLevelChanged { previous, current }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    // main과 같은 구성 (reload 필터 + 링 버퍼)을 thread-local 기본 subscriber로 설치
+    // #[tokio::test]는 현재 스레드 런타임이라 thread-local로 충분
+    fn setup(filter: &str) -> (Router, LastErrors, tracing::subscriber::DefaultGuard) {
+        let last_errors = LastErrors::new(10);
+        let (filter, log_filter) = reload::Layer::new(EnvFilter::new(filter));
+        let guard = tracing::subscriber::set_default(
+            tracing_subscriber::registry()
+                .with(filter)
+                .with(last_errors.clone()),
+        );
+        let state = AppState {
+            last_errors: last_errors.clone(),
+            log_filter,
+        };
+        (app(state, TrustedProxies::default()), last_errors, guard)
+    }
+
+    fn authorized(method: &str, uri: &str, body: &'static str) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("authorization", format!("Bearer {DEBUG_TOKEN}"))
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    async fn body_string(response: Response) -> String {
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn last_errors_are_served_to_authenticated_operators() {
+        let (app, _, _guard) = setup("info");
+
+        let response = app
+            .clone()
+            .oneshot(Request::get("/fail").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        // 토큰 없음 / 틀린 토큰 → 401
+        for authorization in [None, Some("Bearer wrong")] {
+            let mut request = Request::get("/debug/last-errors");
+            if let Some(authorization) = authorization {
+                request = request.header("authorization", authorization);
+            }
+            let response = app
+                .clone()
+                .oneshot(request.body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+
+        let response = app
+            .oneshot(authorized("GET", "/debug/last-errors", ""))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let events: serde_json::Value = serde_json::from_str(&body_string(response).await).unwrap();
+
+        let event = &events[0];
+        assert_eq!(event["level"], "ERROR");
+        assert_eq!(event["message"], "failed to load greeting");
+        assert_eq!(event["fields"], "reason=upstream timed out");
+        assert_eq!(event["spans"][0]["name"], "http_request");
+        let span_fields = event["spans"][0]["fields"].as_str().unwrap();
+        assert!(
+            span_fields.starts_with("method=GET matched_path=/fail request_id="),
+            "{span_fields}"
+        );
+    }
+
+    #[tokio::test]
+    async fn json_lines_carry_request_and_trace_fields() {
+        let lines = json_log::capture::Lines::default();
+        let (filter, log_filter) = reload::Layer::new(EnvFilter::new("info"));
+        let _guard = tracing::subscriber::set_default(
+            tracing_subscriber::registry()
+                .with(filter)
+                .with(json_log::layer().with_writer(lines.clone())),
+        );
+        let state = AppState {
+            last_errors: LastErrors::new(10),
+            log_filter,
+        };
+        let app = app(state, TrustedProxies::default());
+
+        let request = Request::get("/fail")
+            .header(
+                "traceparent",
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            )
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let request_id = response.headers()["x-request-id"].to_str().unwrap();
+
+        let lines = lines.json();
+        let handler = lines
+            .iter()
+            .find(|line| line["message"] == "failed to load greeting")
+            .unwrap();
+        let finished = lines
+            .iter()
+            .find(|line| line["message"] == "finished processing request")
+            .unwrap();
+
+        // 같은 요청의 모든 줄에 같은 ID가 최상위 필드로
+        for line in [handler, finished] {
+            assert_eq!(line["request_id"], request_id);
+            assert_eq!(line["trace_id"], "4bf92f3577b34da6a3ce929d0e0e4736");
+            assert_eq!(line["span_id"].as_str().unwrap().len(), 16);
+            assert_eq!(line["matched_path"], "/fail");
+        }
+        assert_eq!(handler["span_id"], finished["span_id"]);
+        assert_eq!(finished["status"], 500);
+        assert!(finished["latency_ms"].is_u64());
+    }
+
+    #[tokio::test]
+    async fn log_level_can_be_changed_at_runtime() {
+        // 처음에는 ERROR를 끔 → /fail의 error! 이벤트가 기록되지 않음
+        let (app, last_errors, _guard) = setup("off");
+        let fail = || Request::get("/fail").body(Body::empty()).unwrap();
+        app.clone().oneshot(fail()).await.unwrap();
+        assert!(last_errors.recent().is_empty());
+
+        let response = app
+            .clone()
+            .oneshot(authorized(
+                "PUT",
+                "/debug/log-level",
+                "warn,tower_http=debug",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let changed: serde_json::Value =
+            serde_json::from_str(&body_string(response).await).unwrap();
+        assert_eq!(changed["previous"], "off");
+        assert_eq!(changed["current"], "tower_http=debug,warn");
+
+        app.clone().oneshot(fail()).await.unwrap();
+        assert_eq!(last_errors.recent().len(), 1);
+
+        // 잘못된 필터 / 빈 body → 400, 필터는 그대로
+        for body in ["tower_http=loud", "  "] {
+            let response = app
+                .clone()
+                .oneshot(authorized("PUT", "/debug/log-level", body))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{body:?}");
+        }
+
+        // 토큰 없음 → 401
+        let request = Request::put("/debug/log-level")
+            .body(Body::from("trace"))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app
+            .oneshot(authorized("PUT", "/debug/log-level", "info"))
+            .await
+            .unwrap();
+        let changed: serde_json::Value =
+            serde_json::from_str(&body_string(response).await).unwrap();
+        assert_eq!(changed["previous"], "tower_http=debug,warn");
+    }
+}
+
 // ✅ 핵심 구성 요소 요약
 // TraceLayer: 요청/응답의 라이프사이클을 추적하는 미들웨어.
 // make_span_with: 요청마다 새 tracing 스팬을 생성.
@@ -113,8 +452,32 @@ async fn handler() -> Html<&'static str> {
 
 // 🧪 테스트 방법
 //  curl http://127.0.0.1:3000/
-//  # 터미널에서 로그 출력 확인 (예: http_request 스팬)
+//  # 터미널에서 로그 출력 확인 (예: http_request{method=GET matched_path="/" client_ip=127.0.0.1})
 // 	# tracing::debug!, info!, warn!, error! 수준으로 로그 필터링 가능
+//
+//  TRUSTED_PROXIES=127.0.0.1 cargo run -p example-tracing-aka-logging
+//  curl -H 'x-forwarded-for: 203.0.113.7' http://127.0.0.1:3000/
+//  # → http_request{... client_ip=203.0.113.7}: saying hello client_ip=203.0.113.7
+//  #   (TRUSTED_PROXIES 없이 실행하면 헤더는 무시되고 127.0.0.1)
+//
+//  curl http://127.0.0.1:3000/fail
+//  curl -H 'authorization: Bearer secret-token' http://127.0.0.1:3000/debug/last-errors
+//  # → [{"level":"ERROR","message":"failed to load greeting","fields":"reason=upstream timed out",
+//  #     "spans":[{"name":"http_request","fields":"method=GET matched_path=/fail client_ip=127.0.0.1"}], ...}]
+//  # 토큰이 없으면 401
+//
+//  curl -X PUT -H 'authorization: Bearer secret-token' \
+//       -d 'example_tracing_aka_logging=trace,tower_http=trace' http://127.0.0.1:3000/debug/log-level
+//  # → {"previous":"example_tracing_aka_logging=debug,...","current":"..."} 이후 trace 로그까지 출력
+//  curl -i -X PUT -H 'authorization: Bearer secret-token' -d 'tower_http=loud' http://127.0.0.1:3000/debug/log-level
+//  # → 400 invalid filter: ...
+//
+//  LOG_FORMAT=json cargo run -p example-tracing-aka-logging
+//  curl -H 'traceparent: 00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01' http://127.0.0.1:3000/
+//  # → {"timestamp":"...","level":"INFO","target":"example_tracing_aka_logging","span":"http_request",
+//  #    "method":"GET","matched_path":"/","request_id":"<x-request-id 응답 헤더와 같음>",
+//  #    "trace_id":"4bf92f3577b34da6a3ce929d0e0e4736","span_id":"...","status":200,"latency_ms":0,
+//  #    "message":"finished processing request"}
 
 // ⸻
 
@@ -0,0 +1,289 @@
+//! 웹훅 수신 예제 (HMAC-SHA256 서명 검증)
+//!
+//! 5-02 예제의 "body를 미리 읽어 검증한 뒤 핸들러에서 다시 사용" 패턴을 웹훅에 적용
+//!
+//! 1. 미들웨어가 body를 버퍼링
+//! 2. `X-Signature`를 공유 비밀키로 검증 (상수 시간 비교)
+//! 3. 허용 범위를 벗어난 타임스탬프 거절 (replay 방지)
+//! 4. 이미 처리한 delivery ID는 핸들러를 실행하지 않음 (발신자 재시도 대비)
+//! 5. 통과하면 핸들러가 `Json<WebhookEvent>`로 파싱해서 처리
+//!
+//! - `POST /webhooks`: 웹훅 수신
+//! - `GET /events`: 처리한 이벤트 목록
+
+mod verify;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    middleware,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use verify::{verify_webhook, WebhookVerifier};
+
+// 발신자와 미리 공유한 비밀키 (실서비스에서는 환경 변수나 시크릿 저장소에서 읽음)
+const DEFAULT_SECRET: &str = "whsec_example";
+// 타임스탬프 허용 오차
+const TOLERANCE: Duration = Duration::from_secs(5 * 60);
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let secret = std::env::var("WEBHOOK_SECRET").unwrap_or_else(|_| DEFAULT_SECRET.to_owned());
+    let verifier = WebhookVerifier::new(secret, TOLERANCE);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(verifier, Events::default()))
+        .await
+        .unwrap();
+}
+
+fn app(verifier: WebhookVerifier, events: Events) -> Router {
+    Router::new()
+        // 검증 미들웨어는 웹훅 라우트에만 적용
+        .route(
+            "/webhooks",
+            post(receive_webhook)
+                .route_layer(middleware::from_fn_with_state(verifier, verify_webhook)),
+        )
+        .route("/events", get(list_events))
+        .with_state(events)
+}
+
+/// 📦 웹훅 이벤트
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct WebhookEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    data: serde_json::Value,
+}
+
+// 처리한 이벤트 기록
+type Events = Arc<Mutex<Vec<WebhookEvent>>>;
+
+// 📨 여기까지 왔다면 서명, 타임스탬프, 중복 검사를 모두 통과한 요청
+// 실패(2xx가 아닌 응답)하면 미들웨어가 delivery 기록을 지우므로 발신자의 재시도를 다시 처리함
+async fn receive_webhook(
+    State(events): State<Events>,
+    Json(event): Json<WebhookEvent>,
+) -> StatusCode {
+    match event.kind.as_str() {
+        "order.paid" | "order.refunded" => {
+            tracing::info!(kind = event.kind, data = %event.data, "webhook processed");
+            events.lock().unwrap().push(event);
+            StatusCode::NO_CONTENT
+        }
+        _ => {
+            tracing::warn!(kind = event.kind, "unsupported webhook event");
+            StatusCode::UNPROCESSABLE_ENTITY
+        }
+    }
+}
+
+async fn list_events(State(events): State<Events>) -> Json<Vec<WebhookEvent>> {
+    Json(events.lock().unwrap().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+    use verify::{sign, unix_now, DELIVERY_ID_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+
+    const SECRET: &str = "test-secret";
+    const PAID: &str = r#"{"type":"order.paid","data":{"order_id":1}}"#;
+
+    struct TestApp {
+        router: Router,
+        events: Events,
+    }
+
+    impl TestApp {
+        fn new() -> Self {
+            let events = Events::default();
+            let verifier = WebhookVerifier::new(SECRET, TOLERANCE);
+            Self {
+                router: app(verifier, events.clone()),
+                events,
+            }
+        }
+
+        async fn send(&self, request: Request<Body>) -> (StatusCode, String) {
+            let response = self.router.clone().oneshot(request).await.unwrap();
+            let status = response.status();
+            let bytes = response.into_body().collect().await.unwrap().to_bytes();
+            (status, String::from_utf8(bytes.to_vec()).unwrap())
+        }
+
+        fn processed(&self) -> usize {
+            self.events.lock().unwrap().len()
+        }
+    }
+
+    fn webhook(id: &str, timestamp: u64, signature: &str, body: &str) -> Request<Body> {
+        Request::post("/webhooks")
+            .header("content-type", "application/json")
+            .header(DELIVERY_ID_HEADER, id)
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, signature)
+            .body(Body::from(body.to_owned()))
+            .unwrap()
+    }
+
+    fn signed(id: &str, body: &str) -> Request<Body> {
+        let now = unix_now();
+        webhook(
+            id,
+            now,
+            &sign(SECRET.as_bytes(), id, now, body.as_bytes()),
+            body,
+        )
+    }
+
+    #[tokio::test]
+    async fn valid_webhook_is_processed() {
+        let app = TestApp::new();
+
+        let (status, _) = app.send(signed("evt_1", PAID)).await;
+
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        assert_eq!(app.processed(), 1);
+        assert_eq!(app.events.lock().unwrap()[0].data["order_id"], 1);
+    }
+
+    #[tokio::test]
+    async fn wrong_secret_is_rejected() {
+        let app = TestApp::new();
+        let now = unix_now();
+        let signature = sign(b"other-secret", "evt_1", now, PAID.as_bytes());
+
+        let (status, body) = app.send(webhook("evt_1", now, &signature, PAID)).await;
+
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body, "invalid signature");
+        assert_eq!(app.processed(), 0);
+    }
+
+    #[tokio::test]
+    async fn tampered_body_is_rejected() {
+        let app = TestApp::new();
+        let now = unix_now();
+        let signature = sign(SECRET.as_bytes(), "evt_1", now, PAID.as_bytes());
+        let tampered = PAID.replace("order.paid", "order.refunded");
+
+        let (status, _) = app.send(webhook("evt_1", now, &signature, &tampered)).await;
+
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn stale_timestamp_is_rejected() {
+        let app = TestApp::new();
+        let old = unix_now() - TOLERANCE.as_secs() - 60;
+        let signature = sign(SECRET.as_bytes(), "evt_1", old, PAID.as_bytes());
+
+        let (status, body) = app.send(webhook("evt_1", old, &signature, PAID)).await;
+
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body, "timestamp outside tolerance");
+    }
+
+    #[tokio::test]
+    async fn missing_or_malformed_headers() {
+        let app = TestApp::new();
+
+        let request = Request::post("/webhooks")
+            .header("content-type", "application/json")
+            .body(Body::from(PAID))
+            .unwrap();
+        let (status, body) = app.send(request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body, "missing `x-webhook-timestamp` header");
+
+        let (status, body) = app
+            .send(webhook("evt_1", unix_now(), "md5=abcd", PAID))
+            .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body, "malformed `x-signature` header");
+    }
+
+    #[tokio::test]
+    async fn duplicate_delivery_is_ignored() {
+        let app = TestApp::new();
+
+        let (status, _) = app.send(signed("evt_1", PAID)).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, body) = app.send(signed("evt_1", PAID)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "duplicate delivery ignored");
+
+        assert_eq!(app.processed(), 1);
+    }
+
+    // 서명된 요청을 가로채 ID만 바꿔 다시 보내도 처리되지 않아야 함
+    #[tokio::test]
+    async fn replay_with_new_delivery_id_is_rejected() {
+        let app = TestApp::new();
+        let now = unix_now();
+        let signature = sign(SECRET.as_bytes(), "evt_1", now, PAID.as_bytes());
+
+        let (status, _) = app.send(webhook("evt_1", now, &signature, PAID)).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, body) = app.send(webhook("evt_2", now, &signature, PAID)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body, "invalid signature");
+
+        assert_eq!(app.processed(), 1);
+    }
+
+    // 핸들러가 실패한 delivery는 기록되지 않으므로 재시도하면 다시 처리됨
+    #[tokio::test]
+    async fn failed_delivery_can_be_retried() {
+        let app = TestApp::new();
+        let unknown = r#"{"type":"order.unknown","data":{}}"#;
+
+        let (status, _) = app.send(signed("evt_1", unknown)).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        let (status, _) = app.send(signed("evt_1", unknown)).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+        let (status, _) = app.send(signed("evt_1", PAID)).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+    }
+}
+
+// 🧪 테스트 방법 (bash)
+//
+// BODY='{"type":"order.paid","data":{"order_id":1}}'
+// TS=$(date +%s)
+// SIG=$(printf 'evt_1.%s.%s' "$TS" "$BODY" | openssl dgst -sha256 -hmac whsec_example | sed 's/^.* //')
+//
+// curl -i -X POST http://localhost:3000/webhooks \
+//   -H 'Content-Type: application/json' \
+//   -H "X-Webhook-Id: evt_1" \
+//   -H "X-Webhook-Timestamp: $TS" \
+//   -H "X-Signature: sha256=$SIG" \
+//   -d "$BODY"
+//
+// → 204, 같은 명령을 다시 실행하면 200 "duplicate delivery ignored"
+//
+// curl http://localhost:3000/events
@@ -0,0 +1,332 @@
+//! 웹훅 서명 검증 미들웨어
+//!
+//! 발신자는 요청마다 아래 헤더를 붙여 보냄
+//! - `X-Webhook-Timestamp`: 전송 시각 (Unix time, 초)
+//! - `X-Webhook-Id`: 전송(delivery) ID, 재시도 시에도 동일
+//! - `X-Signature`: `sha256=<hex>`, `HMAC-SHA256(secret, "{id}.{timestamp}.{body}")`
+//!
+//! 타임스탬프를 서명 대상에 포함하므로 오래된 요청을 그대로 재전송(replay)하면
+//! 타임스탬프 검사에서 걸리고, 타임스탬프만 바꾸면 서명 검사에서 걸림
+//! delivery ID도 서명 대상 → 허용 오차 안에서 ID만 바꿔 재전송해도 서명 검사에서 걸림
+//! (서명되지 않은 ID로 중복을 판단하면 같은 이벤트가 두 번 처리될 수 있음)
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub const SIGNATURE_HEADER: &str = "x-signature";
+pub const TIMESTAMP_HEADER: &str = "x-webhook-timestamp";
+pub const DELIVERY_ID_HEADER: &str = "x-webhook-id";
+
+const SIGNATURE_PREFIX: &str = "sha256=";
+// 웹훅 body 최대 크기
+const MAX_BODY_SIZE: usize = 256 * 1024;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 🔐 검증 설정 + 처리한 delivery ID 기록
+#[derive(Clone)]
+pub struct WebhookVerifier {
+    secret: Arc<[u8]>,
+    tolerance: Duration,
+    deliveries: Arc<Mutex<HashMap<String, Delivery>>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeliveryState {
+    Processing,
+    Done,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Delivery {
+    state: DeliveryState,
+    timestamp: u64,
+}
+
+impl WebhookVerifier {
+    /// `tolerance`: 현재 시각과 타임스탬프의 허용 오차 (앞뒤 모두)
+    pub fn new(secret: impl AsRef<[u8]>, tolerance: Duration) -> Self {
+        Self {
+            secret: secret.as_ref().into(),
+            tolerance,
+            deliveries: Default::default(),
+        }
+    }
+
+    fn verify(&self, headers: &HeaderMap, body: &[u8], now: u64) -> Result<Signed, WebhookError> {
+        let timestamp = header(headers, TIMESTAMP_HEADER)?
+            .parse::<u64>()
+            .map_err(|_| WebhookError::Malformed(TIMESTAMP_HEADER))?;
+        let delivery_id = header(headers, DELIVERY_ID_HEADER)?.to_owned();
+        let signature = header(headers, SIGNATURE_HEADER)?
+            .strip_prefix(SIGNATURE_PREFIX)
+            .and_then(decode_hex)
+            .ok_or(WebhookError::Malformed(SIGNATURE_HEADER))?;
+
+        // 서명 먼저 검사 → 서명이 맞아야 타임스탬프를 신뢰할 수 있음
+        // verify_slice는 상수 시간 비교 (비교 시간으로 서명을 추측하는 공격 방지)
+        mac(&self.secret, &delivery_id, timestamp, body)
+            .verify_slice(&signature)
+            .map_err(|_| WebhookError::InvalidSignature)?;
+
+        if now.abs_diff(timestamp) > self.tolerance.as_secs() {
+            return Err(WebhookError::StaleTimestamp);
+        }
+
+        Ok(Signed {
+            delivery_id,
+            timestamp,
+        })
+    }
+
+    /// 처음 보는 delivery ID면 Processing으로 기록
+    fn begin(&self, signed: &Signed, now: u64) -> Result<(), WebhookError> {
+        let mut deliveries = self.deliveries.lock().unwrap();
+
+        // 허용 오차보다 오래된 기록은 삭제해도 안전함 (같은 요청이 다시 와도 타임스탬프 검사에서 거절됨)
+        let tolerance = self.tolerance.as_secs();
+        deliveries.retain(|_, delivery| now.saturating_sub(delivery.timestamp) <= tolerance);
+
+        match deliveries.get(&signed.delivery_id).map(|d| d.state) {
+            Some(DeliveryState::Done) => Err(WebhookError::Duplicate),
+            Some(DeliveryState::Processing) => Err(WebhookError::InProgress),
+            None => {
+                deliveries.insert(
+                    signed.delivery_id.clone(),
+                    Delivery {
+                        state: DeliveryState::Processing,
+                        timestamp: signed.timestamp,
+                    },
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// 핸들러가 성공하면 Done, 실패하면 기록을 지워 발신자의 재시도를 받을 수 있게 함
+    fn finish(&self, delivery_id: &str, success: bool) {
+        let mut deliveries = self.deliveries.lock().unwrap();
+        if success {
+            if let Some(delivery) = deliveries.get_mut(delivery_id) {
+                delivery.state = DeliveryState::Done;
+            }
+        } else {
+            deliveries.remove(delivery_id);
+        }
+    }
+}
+
+struct Signed {
+    delivery_id: String,
+    timestamp: u64,
+}
+
+/// ❌ 검증 실패 종류
+#[derive(Debug, PartialEq, Eq)]
+pub enum WebhookError {
+    MissingHeader(&'static str),
+    Malformed(&'static str),
+    InvalidSignature,
+    StaleTimestamp,
+    BodyTooLarge,
+    /// 이미 처리한 delivery → 발신자가 재시도를 멈추도록 200
+    Duplicate,
+    /// 같은 delivery를 처리 중 → 나중에 다시 보내도록 409
+    InProgress,
+}
+
+impl IntoResponse for WebhookError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            Self::MissingHeader(name) => {
+                (StatusCode::BAD_REQUEST, format!("missing `{name}` header"))
+            }
+            Self::Malformed(name) => (
+                StatusCode::BAD_REQUEST,
+                format!("malformed `{name}` header"),
+            ),
+            Self::InvalidSignature => (StatusCode::UNAUTHORIZED, "invalid signature".to_owned()),
+            Self::StaleTimestamp => (
+                StatusCode::UNAUTHORIZED,
+                "timestamp outside tolerance".to_owned(),
+            ),
+            Self::BodyTooLarge => (StatusCode::PAYLOAD_TOO_LARGE, "body too large".to_owned()),
+            Self::Duplicate => (StatusCode::OK, "duplicate delivery ignored".to_owned()),
+            Self::InProgress => (
+                StatusCode::CONFLICT,
+                "delivery is being processed".to_owned(),
+            ),
+        };
+        (status, message).into_response()
+    }
+}
+
+/// 🧱 미들웨어: body 버퍼링 → 서명/타임스탬프 검증 → 중복 확인 → 핸들러
+///
+/// 검증에 사용한 body를 다시 Request에 붙여 핸들러가 `Json<T>`로 파싱할 수 있게 함
+pub async fn verify_webhook(
+    State(verifier): State<WebhookVerifier>,
+    request: Request,
+    next: Next,
+) -> Result<Response, WebhookError> {
+    let (parts, body) = request.into_parts();
+    let bytes = to_bytes(body, MAX_BODY_SIZE)
+        .await
+        .map_err(|_| WebhookError::BodyTooLarge)?;
+
+    let now = unix_now();
+    let signed = verifier.verify(&parts.headers, &bytes, now)?;
+    verifier.begin(&signed, now)?;
+
+    let response = next
+        .run(Request::from_parts(parts, Body::from(bytes)))
+        .await;
+
+    verifier.finish(&signed.delivery_id, response.status().is_success());
+    Ok(response)
+}
+
+/// ✍️ 서명 생성 (발신자 쪽에서 사용하는 것과 같은 계산, 여기서는 테스트용)
+#[cfg(test)]
+pub fn sign(secret: &[u8], delivery_id: &str, timestamp: u64, body: &[u8]) -> String {
+    let signature = mac(secret, delivery_id, timestamp, body)
+        .finalize()
+        .into_bytes();
+    format!("{SIGNATURE_PREFIX}{}", encode_hex(&signature))
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn mac(secret: &[u8], delivery_id: &str, timestamp: u64, body: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(delivery_id.as_bytes());
+    mac.update(b".");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &'static str) -> Result<&'a str, WebhookError> {
+    headers
+        .get(name)
+        .ok_or(WebhookError::MissingHeader(name))?
+        .to_str()
+        .map_err(|_| WebhookError::Malformed(name))
+}
+
+#[cfg(test)]
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"secret";
+
+    fn headers(timestamp: u64, id: &str, signature: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(TIMESTAMP_HEADER, timestamp.to_string().parse().unwrap());
+        headers.insert(DELIVERY_ID_HEADER, id.parse().unwrap());
+        headers.insert(SIGNATURE_HEADER, signature.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        let bytes = [0x00, 0x0f, 0xa0, 0xff];
+        assert_eq!(encode_hex(&bytes), "000fa0ff");
+        assert_eq!(decode_hex("000fa0ff").unwrap(), bytes);
+        assert_eq!(decode_hex("000FA0FF").unwrap(), bytes);
+        assert_eq!(decode_hex("abc"), None);
+        assert_eq!(decode_hex("zz"), None);
+    }
+
+    #[test]
+    fn signature_is_bound_to_id_timestamp_and_body() {
+        let verifier = WebhookVerifier::new(SECRET, Duration::from_secs(300));
+        let signature = sign(SECRET, "a", 1000, b"{}");
+
+        assert!(verifier
+            .verify(&headers(1000, "a", &signature), b"{}", 1000)
+            .is_ok());
+        assert_eq!(
+            verifier
+                .verify(&headers(1001, "a", &signature), b"{}", 1000)
+                .err(),
+            Some(WebhookError::InvalidSignature)
+        );
+        assert_eq!(
+            verifier
+                .verify(&headers(1000, "a", &signature), b"[]", 1000)
+                .err(),
+            Some(WebhookError::InvalidSignature)
+        );
+        assert_eq!(
+            verifier
+                .verify(&headers(1000, "b", &signature), b"{}", 1000)
+                .err(),
+            Some(WebhookError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn tolerance_applies_in_both_directions() {
+        let verifier = WebhookVerifier::new(SECRET, Duration::from_secs(300));
+        let check = |timestamp: u64| {
+            let signature = sign(SECRET, "a", timestamp, b"{}");
+            verifier.verify(&headers(timestamp, "a", &signature), b"{}", 1000)
+        };
+
+        assert!(check(700).is_ok());
+        assert!(check(1300).is_ok());
+        assert_eq!(check(699).err(), Some(WebhookError::StaleTimestamp));
+        assert_eq!(check(1301).err(), Some(WebhookError::StaleTimestamp));
+    }
+
+    #[test]
+    fn old_deliveries_are_pruned() {
+        let verifier = WebhookVerifier::new(SECRET, Duration::from_secs(300));
+        let signed = |id: &str, timestamp| Signed {
+            delivery_id: id.to_owned(),
+            timestamp,
+        };
+
+        verifier.begin(&signed("old", 1000), 1000).unwrap();
+        verifier.finish("old", true);
+        verifier.begin(&signed("new", 1400), 1400).unwrap();
+
+        let deliveries = verifier.deliveries.lock().unwrap();
+        assert!(!deliveries.contains_key("old"));
+        assert!(deliveries.contains_key("new"));
+    }
+}
@@ -0,0 +1,222 @@
+//! 웹훅 발송기: 서명 → 전송 → 5xx/연결 실패 시 지수 백오프로 재시도
+//!
+//! 서명 방식은 5-16 webhooks 예제(수신 측)와 동일
+//! - `X-Webhook-Id`: delivery ID (재시도해도 같음 → 수신 측 중복 제거용)
+//! - `X-Webhook-Timestamp`: 시도할 때마다 새 시각
+//! - `X-Signature`: `sha256=<hex>`, `HMAC-SHA256(secret, "{id}.{timestamp}.{body}")`
+//!   (ID도 서명해야 수신 측이 서명된 ID로 중복을 판단할 수 있음)
+
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::time::sleep;
+use uuid::Uuid;
+
+use crate::store::{Attempt, DeliveryState, Store, Subscription};
+
+pub const SIGNATURE_HEADER: &str = "x-signature";
+pub const TIMESTAMP_HEADER: &str = "x-webhook-timestamp";
+pub const DELIVERY_ID_HEADER: &str = "x-webhook-id";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// ⚙️ 재시도 설정
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    /// 최대 시도 횟수 (첫 시도 포함)
+    pub max_attempts: u32,
+    /// 첫 재시도 전 대기 시간, 이후 2배씩 증가
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+    /// 요청 하나의 타임아웃
+    pub timeout: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryConfig {
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// 📦 전송하는 본문
+#[derive(Debug, Clone, Serialize)]
+pub struct Event {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub data: serde_json::Value,
+}
+
+#[derive(Clone)]
+pub struct Dispatcher {
+    client: reqwest::Client,
+    store: Store,
+    config: RetryConfig,
+}
+
+impl Dispatcher {
+    pub fn new(store: Store, config: RetryConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .expect("failed to build HTTP client");
+        Self {
+            client,
+            store,
+            config,
+        }
+    }
+
+    /// 📣 이벤트를 원하는 모든 구독자에게 백그라운드로 전송
+    /// 반환값: (구독 ID, delivery ID) 목록
+    pub fn publish(&self, event: Event) -> Vec<(Uuid, Uuid)> {
+        // 재시도해도 같은 본문을 보내도록 한 번만 직렬화
+        let body = serde_json::to_vec(&event).expect("event is always serializable");
+
+        self.store
+            .subscriptions()
+            .into_iter()
+            .filter(|subscription| subscription.wants(&event.kind))
+            .map(|subscription| {
+                let delivery_id = self.store.start_delivery(subscription.id, &event.kind);
+                let dispatcher = self.clone();
+                let body = body.clone();
+                let subscription_id = subscription.id;
+                tokio::spawn(async move {
+                    dispatcher.deliver(subscription, delivery_id, body).await;
+                });
+                (subscription_id, delivery_id)
+            })
+            .collect()
+    }
+
+    async fn deliver(&self, subscription: Subscription, delivery_id: Uuid, body: Vec<u8>) {
+        for attempt in 1..=self.config.max_attempts {
+            let sent_at = unix_now();
+            let start = Instant::now();
+            let result = self
+                .client
+                .post(&subscription.url)
+                .header("content-type", "application/json")
+                .header(DELIVERY_ID_HEADER, delivery_id.to_string())
+                .header(TIMESTAMP_HEADER, sent_at.to_string())
+                .header(
+                    SIGNATURE_HEADER,
+                    sign(
+                        subscription.secret.as_bytes(),
+                        &delivery_id.to_string(),
+                        sent_at,
+                        &body,
+                    ),
+                )
+                .body(body.clone())
+                .send()
+                .await;
+            let duration_ms = start.elapsed().as_millis();
+
+            let (status, error) = match &result {
+                Ok(response) => (Some(response.status().as_u16()), None),
+                Err(err) => (None, Some(err.to_string())),
+            };
+            // 5xx와 연결 실패/타임아웃만 재시도, 2xx는 성공, 그 밖(4xx 등)은 재시도해도 같은 결과이므로 실패 처리
+            let retryable = match &result {
+                Ok(response) => response.status().is_server_error(),
+                Err(_) => true,
+            };
+            let succeeded = matches!(&result, Ok(response) if response.status().is_success());
+            let last = attempt == self.config.max_attempts;
+
+            let state = if succeeded {
+                DeliveryState::Delivered
+            } else if retryable && !last {
+                DeliveryState::Pending
+            } else {
+                DeliveryState::Failed
+            };
+
+            self.store.record_attempt(
+                subscription.id,
+                delivery_id,
+                Attempt {
+                    attempt,
+                    sent_at,
+                    duration_ms,
+                    status,
+                    error,
+                },
+                state,
+            );
+
+            if state != DeliveryState::Pending {
+                tracing::debug!(%delivery_id, url = subscription.url, attempt, ?state, "delivery finished");
+                return;
+            }
+
+            let backoff = self.config.backoff(attempt);
+            tracing::debug!(%delivery_id, url = subscription.url, attempt, ?status, ?backoff, "delivery failed, retrying");
+            sleep(backoff).await;
+        }
+    }
+}
+
+/// ✍️ 서명 생성
+pub fn sign(secret: &[u8], delivery_id: &str, timestamp: u64, body: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(delivery_id.as_bytes());
+    mac.update(b".");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    let signature = mac.finalize().into_bytes();
+
+    let hex = signature
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!("sha256={hex}")
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let config = RetryConfig::default();
+
+        assert_eq!(config.backoff(1), Duration::from_secs(1));
+        assert_eq!(config.backoff(2), Duration::from_secs(2));
+        assert_eq!(config.backoff(4), Duration::from_secs(8));
+        assert_eq!(config.backoff(10), Duration::from_secs(60));
+    }
+
+    // `printf 'evt_1.1700000000.{}' | openssl dgst -sha256 -hmac secret` 결과와 같아야 함
+    #[test]
+    fn signature_matches_openssl() {
+        assert_eq!(
+            sign(b"secret", "evt_1", 1_700_000_000, b"{}"),
+            "sha256=81cb8b50961f0fd250d94959303132527aa389817f295263b27dc72d42ec0a3e"
+        );
+    }
+}
@@ -0,0 +1,486 @@
+//! 웹훅 발송 예제 (5-16 webhooks 예제의 발신 측)
+//!
+//! - `POST /subscriptions`: 구독 등록 `{ "url": ..., "events": [...], "secret": ... }`
+//!   (`events`가 없으면 모든 이벤트, `secret`이 없으면 생성해서 응답에 한 번만 포함)
+//! - `GET /subscriptions`: 구독 목록
+//! - `DELETE /subscriptions/{id}`: 구독 삭제
+//! - `GET /subscriptions/{id}/deliveries`: 전송 기록 (시도별 상태 코드, 소요 시간, 에러)
+//! - `POST /events`: 이벤트 발행 → 구독자마다 서명해서 백그라운드로 전송
+//!
+//! 수신 측(5-16)이 3000번 포트를 쓰므로 이 예제는 3001번 포트에서 실행합니다.
+
+mod dispatch;
+mod store;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{delete, get, post},
+    Json, Router,
+};
+use dispatch::{Dispatcher, Event, RetryConfig};
+use serde::{Deserialize, Serialize};
+use store::{Delivery, Store, Subscription};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use uuid::Uuid;
+
+#[derive(Clone)]
+struct AppState {
+    store: Store,
+    dispatcher: Dispatcher,
+}
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3001")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(RetryConfig::default()))
+        .await
+        .unwrap();
+}
+
+fn app(config: RetryConfig) -> Router {
+    let store = Store::default();
+    let dispatcher = Dispatcher::new(store.clone(), config);
+
+    Router::new()
+        .route(
+            "/subscriptions",
+            post(create_subscription).get(list_subscriptions),
+        )
+        .route("/subscriptions/{id}", delete(delete_subscription))
+        .route("/subscriptions/{id}/deliveries", get(list_deliveries))
+        .route("/events", post(publish_event))
+        .with_state(AppState { store, dispatcher })
+}
+
+#[derive(Deserialize)]
+struct CreateSubscription {
+    url: String,
+    #[serde(default)]
+    events: Vec<String>,
+    secret: Option<String>,
+}
+
+// 생성 응답에만 secret 포함 (이후 조회에서는 숨김)
+#[derive(Serialize)]
+struct SubscriptionCreated {
+    #[serde(flatten)]
+    subscription: Subscription,
+    secret: String,
+}
+
+// 1️⃣ POST /subscriptions
+async fn create_subscription(
+    State(state): State<AppState>,
+    Json(input): Json<CreateSubscription>,
+) -> Result<(StatusCode, Json<SubscriptionCreated>), (StatusCode, &'static str)> {
+    let url = reqwest::Url::parse(&input.url)
+        .map_err(|_| (StatusCode::UNPROCESSABLE_ENTITY, "invalid url"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, "url must be http(s)"));
+    }
+
+    let subscription = Subscription {
+        id: Uuid::new_v4(),
+        url: input.url,
+        events: input.events,
+        secret: input
+            .secret
+            .unwrap_or_else(|| format!("whsec_{}", Uuid::new_v4().simple())),
+    };
+    state.store.add_subscription(subscription.clone());
+
+    Ok((
+        StatusCode::CREATED,
+        Json(SubscriptionCreated {
+            secret: subscription.secret.clone(),
+            subscription,
+        }),
+    ))
+}
+
+// 2️⃣ GET /subscriptions
+async fn list_subscriptions(State(state): State<AppState>) -> Json<Vec<Subscription>> {
+    Json(state.store.subscriptions())
+}
+
+// 3️⃣ DELETE /subscriptions/{id}
+async fn delete_subscription(State(state): State<AppState>, Path(id): Path<Uuid>) -> StatusCode {
+    if state.store.remove_subscription(id) {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
+// 4️⃣ GET /subscriptions/{id}/deliveries
+async fn list_deliveries(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<Delivery>>, StatusCode> {
+    state
+        .store
+        .deliveries(id)
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+#[derive(Deserialize)]
+struct PublishEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    data: serde_json::Value,
+}
+
+#[derive(Serialize)]
+struct Published {
+    subscription_id: Uuid,
+    delivery_id: Uuid,
+}
+
+// 5️⃣ POST /events
+// 전송은 백그라운드에서 진행되므로 결과를 기다리지 않고 202 반환
+async fn publish_event(
+    State(state): State<AppState>,
+    Json(input): Json<PublishEvent>,
+) -> (StatusCode, Json<Vec<Published>>) {
+    let deliveries = state
+        .dispatcher
+        .publish(Event {
+            kind: input.kind,
+            data: input.data,
+        })
+        .into_iter()
+        .map(|(subscription_id, delivery_id)| Published {
+            subscription_id,
+            delivery_id,
+        })
+        .collect();
+
+    (StatusCode::ACCEPTED, Json(deliveries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, extract::Request, http::HeaderMap};
+    use dispatch::{sign, DELIVERY_ID_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use std::{
+        net::SocketAddr,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+    use tower::ServiceExt;
+
+    const SECRET: &str = "test-secret";
+
+    fn test_config() -> RetryConfig {
+        RetryConfig {
+            max_attempts: 3,
+            base_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(50),
+            timeout: Duration::from_secs(2),
+        }
+    }
+
+    type Received = Vec<(HeaderMap, Vec<u8>)>;
+
+    // 📬 테스트용 수신 서버: 받은 요청을 기록하고, 미리 정한 상태 코드를 순서대로 응답
+    #[derive(Clone, Default)]
+    struct Receiver {
+        requests: Arc<Mutex<Received>>,
+        responses: Arc<Mutex<Vec<StatusCode>>>,
+    }
+
+    impl Receiver {
+        async fn spawn(responses: &[StatusCode]) -> (Self, SocketAddr) {
+            let receiver = Self::default();
+            // pop()으로 꺼내므로 역순으로 저장
+            *receiver.responses.lock().unwrap() = responses.iter().rev().copied().collect();
+
+            let handler = {
+                let receiver = receiver.clone();
+                move |request: Request| async move {
+                    let (parts, body) = request.into_parts();
+                    let body = body.collect().await.unwrap().to_bytes().to_vec();
+                    receiver
+                        .requests
+                        .lock()
+                        .unwrap()
+                        .push((parts.headers, body));
+                    receiver
+                        .responses
+                        .lock()
+                        .unwrap()
+                        .pop()
+                        .unwrap_or(StatusCode::OK)
+                }
+            };
+
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            tokio::spawn(async move {
+                axum::serve(listener, Router::new().route("/hook", post(handler)))
+                    .await
+                    .unwrap();
+            });
+            (receiver, addr)
+        }
+
+        fn requests(&self) -> Received {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    async fn send(
+        app: &Router,
+        method: &str,
+        uri: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let request = axum::http::Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    async fn subscribe(app: &Router, url: String, events: Value) -> String {
+        let (status, body) = send(
+            app,
+            "POST",
+            "/subscriptions",
+            Some(json!({ "url": url, "events": events, "secret": SECRET })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body["secret"], SECRET);
+        body["id"].as_str().unwrap().to_owned()
+    }
+
+    async fn publish(app: &Router, kind: &str) -> Value {
+        let (status, body) = send(
+            app,
+            "POST",
+            "/events",
+            Some(json!({ "type": kind, "data": { "order_id": 1 } })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        body
+    }
+
+    // 전송이 끝날 때까지(pending이 아닐 때까지) 기록을 조회
+    async fn wait_for_delivery(app: &Router, subscription_id: &str) -> Value {
+        for _ in 0..200 {
+            let uri = format!("/subscriptions/{subscription_id}/deliveries");
+            let (_, deliveries) = send(app, "GET", &uri, None).await;
+            if deliveries[0]["state"] != "pending" {
+                return deliveries[0].clone();
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("delivery did not finish");
+    }
+
+    #[tokio::test]
+    async fn delivers_signed_payload() {
+        let (receiver, addr) = Receiver::spawn(&[]).await;
+        let app = app(test_config());
+        let id = subscribe(&app, format!("http://{addr}/hook"), json!([])).await;
+
+        publish(&app, "order.paid").await;
+        let delivery = wait_for_delivery(&app, &id).await;
+
+        assert_eq!(delivery["state"], "delivered");
+        assert_eq!(delivery["attempts"][0]["status"], 200);
+
+        let requests = receiver.requests();
+        let (headers, body) = &requests[0];
+        assert_eq!(
+            headers[DELIVERY_ID_HEADER],
+            delivery["id"].as_str().unwrap()
+        );
+        let timestamp = headers[TIMESTAMP_HEADER].to_str().unwrap().parse().unwrap();
+        assert_eq!(
+            headers[SIGNATURE_HEADER],
+            sign(
+                SECRET.as_bytes(),
+                delivery["id"].as_str().unwrap(),
+                timestamp,
+                body
+            )
+            .as_str()
+        );
+        let body: Value = serde_json::from_slice(body).unwrap();
+        assert_eq!(
+            body,
+            json!({ "type": "order.paid", "data": { "order_id": 1 } })
+        );
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors_with_same_delivery_id() {
+        let (receiver, addr) = Receiver::spawn(&[
+            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::SERVICE_UNAVAILABLE,
+        ])
+        .await;
+        let app = app(test_config());
+        let id = subscribe(&app, format!("http://{addr}/hook"), json!([])).await;
+
+        publish(&app, "order.paid").await;
+        let delivery = wait_for_delivery(&app, &id).await;
+
+        assert_eq!(delivery["state"], "delivered");
+        let statuses = delivery["attempts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|attempt| attempt["status"].as_u64().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(statuses, [500, 503, 200]);
+
+        let requests = receiver.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests
+            .iter()
+            .all(|(headers, _)| headers[DELIVERY_ID_HEADER] == requests[0].0[DELIVERY_ID_HEADER]));
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let (_, addr) = Receiver::spawn(&[StatusCode::BAD_GATEWAY; 5]).await;
+        let app = app(test_config());
+        let id = subscribe(&app, format!("http://{addr}/hook"), json!([])).await;
+
+        publish(&app, "order.paid").await;
+        let delivery = wait_for_delivery(&app, &id).await;
+
+        assert_eq!(delivery["state"], "failed");
+        assert_eq!(delivery["attempts"].as_array().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried() {
+        let (receiver, addr) = Receiver::spawn(&[StatusCode::UNAUTHORIZED]).await;
+        let app = app(test_config());
+        let id = subscribe(&app, format!("http://{addr}/hook"), json!([])).await;
+
+        publish(&app, "order.paid").await;
+        let delivery = wait_for_delivery(&app, &id).await;
+
+        assert_eq!(delivery["state"], "failed");
+        assert_eq!(receiver.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn connection_errors_are_recorded() {
+        // 아무도 listen하지 않는 포트
+        let addr = {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            listener.local_addr().unwrap()
+        };
+        let app = app(test_config());
+        let id = subscribe(&app, format!("http://{addr}/hook"), json!([])).await;
+
+        publish(&app, "order.paid").await;
+        let delivery = wait_for_delivery(&app, &id).await;
+
+        assert_eq!(delivery["state"], "failed");
+        assert_eq!(delivery["attempts"][0]["status"], Value::Null);
+        assert!(delivery["attempts"][0]["error"].is_string());
+    }
+
+    #[tokio::test]
+    async fn only_matching_subscriptions_receive_events() {
+        let (_, addr) = Receiver::spawn(&[]).await;
+        let app = app(test_config());
+        let paid = subscribe(&app, format!("http://{addr}/hook"), json!(["order.paid"])).await;
+        subscribe(
+            &app,
+            format!("http://{addr}/hook"),
+            json!(["order.refunded"]),
+        )
+        .await;
+
+        let published = publish(&app, "order.paid").await;
+
+        assert_eq!(published.as_array().unwrap().len(), 1);
+        assert_eq!(published[0]["subscription_id"], paid);
+    }
+
+    #[tokio::test]
+    async fn subscription_management() {
+        let app = app(test_config());
+
+        let (status, _) = send(
+            &app,
+            "POST",
+            "/subscriptions",
+            Some(json!({ "url": "ftp://example.com" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+        let (_, created) = send(
+            &app,
+            "POST",
+            "/subscriptions",
+            Some(json!({ "url": "http://example.com/hook" })),
+        )
+        .await;
+        assert!(created["secret"].as_str().unwrap().starts_with("whsec_"));
+        let id = created["id"].as_str().unwrap();
+
+        let (_, list) = send(&app, "GET", "/subscriptions", None).await;
+        assert_eq!(list[0]["id"], id);
+        assert_eq!(list[0].get("secret"), None);
+
+        let (status, _) = send(&app, "DELETE", &format!("/subscriptions/{id}"), None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let uri = format!("/subscriptions/{id}/deliveries");
+        let (status, _) = send(&app, "GET", &uri, None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
+
+// 🧪 테스트 방법 (5-16 webhooks 예제와 함께)
+//
+// 1. 수신 측 실행 (3000번 포트, 비밀키 whsec_example)
+// cd ../5-16_webhooks && cargo run
+//
+// 2. 이 예제 실행 (3001번 포트)
+// cargo run
+//
+// 3. 구독 등록
+// curl -X POST http://localhost:3001/subscriptions -H 'Content-Type: application/json' \
+// -d '{"url": "http://127.0.0.1:3000/webhooks", "secret": "whsec_example"}'
+//
+// 4. 이벤트 발행
+// curl -X POST http://localhost:3001/events -H 'Content-Type: application/json' \
+// -d '{"type": "order.paid", "data": {"order_id": 1}}'
+//
+// 5. 전송 기록 확인 (수신 측에서는 curl http://localhost:3000/events)
+// curl http://localhost:3001/subscriptions/<id>/deliveries
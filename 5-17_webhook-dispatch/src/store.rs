@@ -0,0 +1,140 @@
+//! 구독(subscription)과 전송 기록(delivery log) 메모리 저장소
+
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+use uuid::Uuid;
+
+/// 📌 구독 정보
+#[derive(Debug, Clone, Serialize)]
+pub struct Subscription {
+    pub id: Uuid,
+    pub url: String,
+    /// 받을 이벤트 타입 (비어 있으면 모든 이벤트)
+    pub events: Vec<String>,
+    /// 서명용 비밀키 (생성 응답에서만 노출)
+    #[serde(skip)]
+    pub secret: String,
+}
+
+impl Subscription {
+    pub fn wants(&self, event_type: &str) -> bool {
+        self.events.is_empty() || self.events.iter().any(|event| event == event_type)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryState {
+    Pending,
+    Delivered,
+    /// 재시도를 모두 실패했거나 재시도하지 않는 응답(4xx)을 받음
+    Failed,
+}
+
+/// 📝 전송 한 건 (재시도해도 같은 delivery ID)
+#[derive(Debug, Clone, Serialize)]
+pub struct Delivery {
+    pub id: Uuid,
+    pub event_type: String,
+    pub state: DeliveryState,
+    pub attempts: Vec<Attempt>,
+}
+
+/// 🔁 시도 한 번의 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct Attempt {
+    pub attempt: u32,
+    /// 시도 시각 (Unix time, 초)
+    pub sent_at: u64,
+    pub duration_ms: u128,
+    /// 응답을 받았으면 상태 코드, 연결 실패 등이면 None
+    pub status: Option<u16>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Store {
+    inner: Arc<RwLock<StoreInner>>,
+}
+
+#[derive(Debug, Default)]
+struct StoreInner {
+    subscriptions: HashMap<Uuid, Subscription>,
+    // 구독 ID → 전송 기록 (최신이 뒤)
+    deliveries: HashMap<Uuid, Vec<Delivery>>,
+}
+
+impl Store {
+    pub fn add_subscription(&self, subscription: Subscription) {
+        let mut inner = self.inner.write().unwrap();
+        inner.deliveries.insert(subscription.id, Vec::new());
+        inner.subscriptions.insert(subscription.id, subscription);
+    }
+
+    pub fn remove_subscription(&self, id: Uuid) -> bool {
+        let mut inner = self.inner.write().unwrap();
+        inner.deliveries.remove(&id);
+        inner.subscriptions.remove(&id).is_some()
+    }
+
+    pub fn subscriptions(&self) -> Vec<Subscription> {
+        self.inner
+            .read()
+            .unwrap()
+            .subscriptions
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    pub fn deliveries(&self, subscription_id: Uuid) -> Option<Vec<Delivery>> {
+        self.inner
+            .read()
+            .unwrap()
+            .deliveries
+            .get(&subscription_id)
+            .cloned()
+    }
+
+    pub(crate) fn start_delivery(&self, subscription_id: Uuid, event_type: &str) -> Uuid {
+        let delivery = Delivery {
+            id: Uuid::new_v4(),
+            event_type: event_type.to_owned(),
+            state: DeliveryState::Pending,
+            attempts: Vec::new(),
+        };
+        let id = delivery.id;
+        if let Some(deliveries) = self
+            .inner
+            .write()
+            .unwrap()
+            .deliveries
+            .get_mut(&subscription_id)
+        {
+            deliveries.push(delivery);
+        }
+        id
+    }
+
+    pub(crate) fn record_attempt(
+        &self,
+        subscription_id: Uuid,
+        delivery_id: Uuid,
+        attempt: Attempt,
+        state: DeliveryState,
+    ) {
+        let mut inner = self.inner.write().unwrap();
+        let delivery = inner
+            .deliveries
+            .get_mut(&subscription_id)
+            .and_then(|deliveries| deliveries.iter_mut().find(|d| d.id == delivery_id));
+        // 전송 중에 구독이 삭제되었으면 기록하지 않음
+        if let Some(delivery) = delivery {
+            delivery.attempts.push(attempt);
+            delivery.state = state;
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! 📐 정규 요청(canonical request) 만들기
+//!
+//! 클라이언트와 서버가 같은 요청을 같은 문자열로 바꿀 수 있어야 서명이 일치함
+//!
+//! ```text
+//! METHOD
+//! /canonical/path
+//! a=1&b=2                        ← 쿼리: 키(같으면 값) 순으로 정렬, 다시 인코딩
+//! host:example.com               ← 서명한 헤더: 소문자 이름 순, 값은 앞뒤 공백 제거
+//! x-date:1700000000
+//! x-nonce:abc
+//!                                ← 헤더 블록 끝의 빈 줄
+//! host;x-date;x-nonce            ← 서명한 헤더 목록
+//! <hex(sha256(body))>
+//! ```
+//!
+//! 서명 대상 문자열(string to sign)은 알고리즘, 타임스탬프, nonce, 정규 요청의 해시를 줄바꿈으로 연결
+
+use axum::http::{header::HOST, HeaderMap, Method, Uri};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+pub const ALGORITHM: &str = "HMAC-SHA256";
+
+pub type HmacSha256 = Hmac<Sha256>;
+
+/// 서명 헤더 목록 정규화 (소문자, 정렬, 중복 제거)
+pub fn signed_headers(names: &str) -> Vec<String> {
+    let mut names: Vec<String> = names
+        .split(';')
+        .map(|name| name.trim().to_ascii_lowercase())
+        .filter(|name| !name.is_empty())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// 서명할 헤더가 요청에 없으면 `Err(헤더 이름)`
+pub fn canonical_request(
+    method: &Method,
+    uri: &Uri,
+    headers: &HeaderMap,
+    signed_headers: &[String],
+    body: &[u8],
+) -> Result<String, String> {
+    let mut canonical = format!(
+        "{method}\n{}\n{}\n",
+        canonical_path(uri.path()),
+        canonical_query(uri.query().unwrap_or_default())
+    );
+
+    for name in signed_headers {
+        let value = header_value(uri, headers, name).ok_or_else(|| name.clone())?;
+        canonical.push_str(&format!("{name}:{value}\n"));
+    }
+
+    canonical.push_str(&format!(
+        "\n{}\n{}",
+        signed_headers.join(";"),
+        hex_sha256(body)
+    ));
+    Ok(canonical)
+}
+
+pub fn string_to_sign(timestamp: u64, nonce: &str, canonical_request: &str) -> String {
+    format!(
+        "{ALGORITHM}\n{timestamp}\n{nonce}\n{}",
+        hex_sha256(canonical_request.as_bytes())
+    )
+}
+
+pub fn mac(secret: &[u8], string_to_sign: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(string_to_sign.as_bytes());
+    mac
+}
+
+pub fn hex_sha256(bytes: &[u8]) -> String {
+    encode_hex(&Sha256::digest(bytes))
+}
+
+pub fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+pub fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+// 같은 이름의 헤더가 여러 개면 `,`로 연결
+// HTTP/2에는 Host 헤더 대신 :authority가 오므로 URI에서 가져옴
+fn header_value(uri: &Uri, headers: &HeaderMap, name: &str) -> Option<String> {
+    let values: Vec<&str> = headers
+        .get_all(name)
+        .iter()
+        .map(|value| value.to_str().map(str::trim))
+        .collect::<Result<_, _>>()
+        .ok()?;
+
+    if values.is_empty() {
+        return (name == HOST.as_str())
+            .then(|| uri.authority().map(|authority| authority.to_string()))
+            .flatten();
+    }
+    Some(values.join(","))
+}
+
+// 세그먼트마다 디코딩 후 다시 인코딩 → `%7E`와 `~`처럼 표기만 다른 경로가 같은 문자열이 됨
+fn canonical_path(path: &str) -> String {
+    if path.is_empty() {
+        return "/".to_owned();
+    }
+    path.split('/')
+        .map(|segment| encode(&decode(segment)))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn canonical_query(query: &str) -> String {
+    let mut params: Vec<(String, String)> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (encode(&decode(key)), encode(&decode(value)))
+        })
+        .collect();
+    params.sort();
+    params
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// RFC 3986 unreserved 문자 외에는 모두 `%XX`로 인코딩
+fn encode(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// `%XX` 디코딩 (잘못된 `%`는 그대로 둠, `+`는 공백으로 바꾸지 않음)
+fn decode(s: &str) -> Vec<u8> {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| s.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_is_sorted_and_reencoded() {
+        assert_eq!(canonical_query(""), "");
+        assert_eq!(canonical_query("b=2&a=1&a=0"), "a=0&a=1&b=2");
+        assert_eq!(canonical_query("q=a%20b&flag"), "flag=&q=a%20b");
+        assert_eq!(canonical_query("name=%7Euser&x=%2f"), "name=~user&x=%2F");
+        assert_eq!(canonical_query("plus=a+b"), "plus=a%2Bb");
+    }
+
+    #[test]
+    fn path_is_normalized_per_segment() {
+        assert_eq!(canonical_path(""), "/");
+        assert_eq!(canonical_path("/orders/42"), "/orders/42");
+        assert_eq!(canonical_path("/a%7eb/c d"), "/a~b/c%20d");
+        // 인코딩된 `/`는 경로 구분자가 아님
+        assert_eq!(canonical_path("/a%2Fb"), "/a%2Fb");
+    }
+
+    #[test]
+    fn signed_header_list_is_normalized() {
+        assert_eq!(
+            signed_headers("X-Nonce; host;x-date;;host"),
+            ["host", "x-date", "x-nonce"]
+        );
+    }
+
+    #[test]
+    fn canonical_request_layout() {
+        let uri: Uri = "http://example.com/orders?b=2&a=1".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-date", " 1700000000 ".parse().unwrap());
+        headers.append("x-tag", "a".parse().unwrap());
+        headers.append("x-tag", "b".parse().unwrap());
+
+        let canonical = canonical_request(
+            &Method::POST,
+            &uri,
+            &headers,
+            &signed_headers("host;x-date;x-tag"),
+            b"{}",
+        )
+        .unwrap();
+
+        assert_eq!(
+            canonical,
+            format!(
+                "POST\n/orders\na=1&b=2\nhost:example.com\nx-date:1700000000\nx-tag:a,b\n\nhost;x-date;x-tag\n{}",
+                hex_sha256(b"{}")
+            )
+        );
+
+        let missing = canonical_request(
+            &Method::GET,
+            &"/orders".parse().unwrap(),
+            &headers,
+            &signed_headers("host"),
+            b"",
+        );
+        assert_eq!(missing, Err("host".to_owned()));
+    }
+}
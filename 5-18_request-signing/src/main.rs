@@ -0,0 +1,292 @@
+//! 요청 서명 검증 예제 (AWS SigV4 스타일)
+//!
+//! 5-16 웹훅 예제는 body만 서명하지만, 일반 API 호출은 메서드/경로/쿼리/헤더도 보호해야 함
+//! (예: 서명된 `GET /orders?limit=1`을 `limit=1000`으로 바꿔 보내는 것 방지)
+//!
+//! 1. 미들웨어가 body를 버퍼링 (5-02 패턴)
+//! 2. 요청을 정규 문자열(canonical request)로 바꿔 클라이언트 비밀키로 HMAC 서명 검증
+//! 3. 허용 범위를 벗어난 `X-Date` 거절 (시계 오차 허용)
+//! 4. 이미 사용한 `X-Nonce` 거절 (replay 방지)
+//! 5. 통과하면 핸들러가 `Extension<SignedBy>`로 어떤 클라이언트가 보냈는지 확인
+//!
+//! - `GET /orders`: 주문 목록 (`?status=paid` 필터)
+//! - `POST /orders`: 주문 생성
+
+mod canonical;
+mod verify;
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    middleware,
+    routing::get,
+    Extension, Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use verify::{verify_signature, SignatureVerifier, SignedBy};
+
+// 클라이언트와 미리 공유한 (key id, 비밀키) (실서비스에서는 DB나 시크릿 저장소에서 읽음)
+const CREDENTIALS: [(&str, &str); 1] = [("client-1", "sk_example")];
+// 서버와 클라이언트 시계 차이 허용 범위
+const TOLERANCE: Duration = Duration::from_secs(5 * 60);
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let verifier = SignatureVerifier::new(CREDENTIALS, TOLERANCE);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(verifier, Orders::default()))
+        .await
+        .unwrap();
+}
+
+fn app(verifier: SignatureVerifier, orders: Orders) -> Router {
+    Router::new()
+        .route("/orders", get(list_orders).post(create_order))
+        .route_layer(middleware::from_fn_with_state(verifier, verify_signature))
+        .with_state(orders)
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Order {
+    id: usize,
+    item: String,
+    status: String,
+    // 서명한 클라이언트
+    client: String,
+}
+
+type Orders = Arc<Mutex<Vec<Order>>>;
+
+#[derive(Debug, Deserialize)]
+struct OrderFilter {
+    status: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateOrder {
+    item: String,
+}
+
+async fn list_orders(
+    State(orders): State<Orders>,
+    Query(filter): Query<OrderFilter>,
+) -> Json<Vec<Order>> {
+    let orders = orders.lock().unwrap();
+    Json(
+        orders
+            .iter()
+            .filter(|order| filter.status.as_ref().is_none_or(|s| *s == order.status))
+            .cloned()
+            .collect(),
+    )
+}
+
+// 📨 여기까지 왔다면 서명, 타임스탬프, nonce 검사를 모두 통과한 요청
+async fn create_order(
+    State(orders): State<Orders>,
+    Extension(SignedBy(client)): Extension<SignedBy>,
+    Json(input): Json<CreateOrder>,
+) -> (StatusCode, Json<Order>) {
+    let mut orders = orders.lock().unwrap();
+    let order = Order {
+        id: orders.len() + 1,
+        item: input.item,
+        status: "pending".to_owned(),
+        client,
+    };
+    tracing::info!(id = order.id, client = order.client, "order created");
+    orders.push(order.clone());
+    (StatusCode::CREATED, Json(order))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header::AUTHORIZATION, Request},
+    };
+    use http_body_util::BodyExt;
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use verify::{authorization, unix_now, DATE_HEADER, NONCE_HEADER};
+
+    const KEY_ID: &str = "client-1";
+    const SECRET: &str = "test-secret";
+
+    fn app() -> Router {
+        super::app(
+            SignatureVerifier::new([(KEY_ID, SECRET)], TOLERANCE),
+            Orders::default(),
+        )
+    }
+
+    async fn send(app: &Router, request: Request<Body>) -> (StatusCode, String) {
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    /// host, `X-Date`, `X-Nonce` 헤더를 붙이고 `secret`으로 서명한 요청
+    fn signed(
+        method: &str,
+        uri: &str,
+        nonce: &str,
+        timestamp: u64,
+        secret: &str,
+        body: &str,
+    ) -> Request<Body> {
+        let (mut parts, ()) = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("host", "api.example.com")
+            .header("content-type", "application/json")
+            .header(DATE_HEADER, timestamp.to_string())
+            .header(NONCE_HEADER, nonce)
+            .body(())
+            .unwrap()
+            .into_parts();
+        let authorization = authorization(KEY_ID, secret.as_bytes(), &parts, body.as_bytes());
+        parts
+            .headers
+            .insert(AUTHORIZATION, authorization.parse().unwrap());
+        Request::from_parts(parts, Body::from(body.to_owned()))
+    }
+
+    fn valid(method: &str, uri: &str, nonce: &str, body: &str) -> Request<Body> {
+        signed(method, uri, nonce, unix_now(), SECRET, body)
+    }
+
+    #[tokio::test]
+    async fn signed_requests_reach_handlers() {
+        let app = app();
+
+        let (status, body) = send(&app, valid("POST", "/orders", "n1", r#"{"item":"book"}"#)).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let order: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(order["client"], KEY_ID);
+
+        let (status, body) = send(&app, valid("GET", "/orders?status=pending", "n2", "")).await;
+        assert_eq!(status, StatusCode::OK);
+        let orders: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(orders.as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn tampering_breaks_the_signature() {
+        let app = app();
+
+        // 서명 후 쿼리 변경
+        let mut request = valid("GET", "/orders?status=paid", "n1", "");
+        *request.uri_mut() = "/orders?status=pending".parse().unwrap();
+        let (status, body) = send(&app, request).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body, "invalid signature");
+
+        // 서명 후 메서드 변경
+        let mut request = valid("GET", "/orders", "n2", "");
+        *request.method_mut() = "POST".parse().unwrap();
+        let (status, _) = send(&app, request).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        // body 변경
+        let request = valid("POST", "/orders", "n3", r#"{"item":"book"}"#);
+        let (parts, _) = request.into_parts();
+        let request = Request::from_parts(parts, Body::from(r#"{"item":"car"}"#));
+        let (status, _) = send(&app, request).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        // 다른 비밀키
+        let request = signed("GET", "/orders", "n4", unix_now(), "other", "");
+        let (status, _) = send(&app, request).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn replayed_nonce_is_rejected() {
+        let app = app();
+        let body = r#"{"item":"book"}"#;
+        let now = unix_now();
+
+        let (status, _) = send(&app, signed("POST", "/orders", "n1", now, SECRET, body)).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let (status, message) =
+            send(&app, signed("POST", "/orders", "n1", now, SECRET, body)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(message, "nonce already used");
+
+        let (status, body) = send(&app, valid("GET", "/orders", "n2", "")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            serde_json::from_str::<Value>(&body).unwrap()[1],
+            Value::Null
+        );
+    }
+
+    #[tokio::test]
+    async fn stale_or_incomplete_requests_are_rejected() {
+        let app = app();
+
+        let old = unix_now() - TOLERANCE.as_secs() - 60;
+        let (status, body) = send(&app, signed("GET", "/orders", "n1", old, SECRET, "")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body, "timestamp outside tolerance");
+
+        let request = Request::get("/orders")
+            .header("host", "api.example.com")
+            .body(Body::empty())
+            .unwrap();
+        let (status, body) = send(&app, request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body, "missing `authorization` header");
+
+        // nonce를 서명에서 빼면 거절
+        let mut request = valid("GET", "/orders", "n2", "");
+        let authorization = request.headers()[AUTHORIZATION]
+            .to_str()
+            .unwrap()
+            .replace("host;x-date;x-nonce", "host;x-date");
+        request
+            .headers_mut()
+            .insert(AUTHORIZATION, authorization.parse().unwrap());
+        let (status, body) = send(&app, request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body, "`x-nonce` header must be signed");
+    }
+}
+
+// 🧪 테스트 방법 (bash)
+//
+// TS=$(date +%s); NONCE=$(openssl rand -hex 16); BODY='{"item":"book"}'
+// BODY_HASH=$(printf '%s' "$BODY" | openssl dgst -sha256 | sed 's/^.* //')
+// CANONICAL=$(printf 'POST\n/orders\n\nhost:localhost:3000\nx-date:%s\nx-nonce:%s\n\nhost;x-date;x-nonce\n%s' \
+//   "$TS" "$NONCE" "$BODY_HASH")
+// CANONICAL_HASH=$(printf '%s' "$CANONICAL" | openssl dgst -sha256 | sed 's/^.* //')
+// SIG=$(printf 'HMAC-SHA256\n%s\n%s\n%s' "$TS" "$NONCE" "$CANONICAL_HASH" \
+//   | openssl dgst -sha256 -hmac sk_example | sed 's/^.* //')
+//
+// curl -i -X POST http://localhost:3000/orders \
+//   -H 'Content-Type: application/json' \
+//   -H "X-Date: $TS" -H "X-Nonce: $NONCE" \
+//   -H "Authorization: HMAC-SHA256 Credential=client-1, SignedHeaders=host;x-date;x-nonce, Signature=$SIG" \
+//   -d "$BODY"
+//
+// → 201, 같은 명령을 다시 실행하면 401 "nonce already used"
@@ -0,0 +1,396 @@
+//! 요청 서명 검증 미들웨어 (AWS SigV4와 비슷한 방식)
+//!
+//! 클라이언트는 요청마다 아래 헤더를 붙여 보냄
+//! - `X-Date`: 서명 시각 (Unix time, 초)
+//! - `X-Nonce`: 요청마다 새로 만든 임의 문자열
+//! - `Authorization: HMAC-SHA256 Credential=<key id>, SignedHeaders=host;x-date;x-nonce, Signature=<hex>`
+//!
+//! 서명은 메서드, 경로, 쿼리, 서명한 헤더, body 해시를 모두 포함하므로 (`canonical` 모듈 참고)
+//! 어느 하나라도 바뀌면 검증에 실패함.
+//! 허용 오차 안의 요청을 그대로 다시 보내는 replay는 nonce 기록으로 막음
+
+use crate::canonical::{self, decode_hex, ALGORITHM};
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header::AUTHORIZATION, request::Parts, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use hmac::Mac;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub const DATE_HEADER: &str = "x-date";
+pub const NONCE_HEADER: &str = "x-nonce";
+
+// 반드시 서명에 포함해야 하는 헤더 (다른 호스트로 재전송하거나 시각/nonce만 바꾸는 것 방지)
+const REQUIRED_HEADERS: [&str; 3] = ["host", DATE_HEADER, NONCE_HEADER];
+// 서명 검증을 위해 버퍼링할 body 최대 크기
+const MAX_BODY_SIZE: usize = 1024 * 1024;
+
+/// 🔐 클라이언트별 비밀키 + 사용한 nonce 기록
+#[derive(Clone)]
+pub struct SignatureVerifier {
+    credentials: Arc<HashMap<String, Arc<[u8]>>>,
+    tolerance: Duration,
+    // (key id, nonce) → 요청 타임스탬프
+    nonces: Arc<Mutex<HashMap<(String, String), u64>>>,
+}
+
+/// ✅ 서명 검증을 통과한 요청의 key id (핸들러에서 `Extension<SignedBy>`로 사용)
+#[derive(Debug, Clone)]
+pub struct SignedBy(pub String);
+
+struct Verified {
+    key_id: String,
+    nonce: String,
+    timestamp: u64,
+}
+
+struct Credential {
+    key_id: String,
+    signed_headers: Vec<String>,
+    signature: Vec<u8>,
+}
+
+impl SignatureVerifier {
+    /// `credentials`: (key id, 비밀키) 목록, `tolerance`: 서버 시각과 `X-Date`의 허용 오차 (앞뒤 모두)
+    pub fn new<I, K, S>(credentials: I, tolerance: Duration) -> Self
+    where
+        I: IntoIterator<Item = (K, S)>,
+        K: Into<String>,
+        S: AsRef<[u8]>,
+    {
+        Self {
+            credentials: Arc::new(
+                credentials
+                    .into_iter()
+                    .map(|(id, secret)| (id.into(), secret.as_ref().into()))
+                    .collect(),
+            ),
+            tolerance,
+            nonces: Default::default(),
+        }
+    }
+
+    fn verify(&self, parts: &Parts, body: &[u8], now: u64) -> Result<Verified, SignatureError> {
+        let credential = parse_authorization(header(&parts.headers, AUTHORIZATION.as_str())?)?;
+        if let Some(name) = REQUIRED_HEADERS.into_iter().find(|name| {
+            !credential
+                .signed_headers
+                .iter()
+                .any(|signed| signed == name)
+        }) {
+            return Err(SignatureError::UnsignedHeader(name));
+        }
+
+        let timestamp = header(&parts.headers, DATE_HEADER)?
+            .parse::<u64>()
+            .map_err(|_| SignatureError::Malformed(DATE_HEADER))?;
+        let nonce = header(&parts.headers, NONCE_HEADER)?;
+        if nonce.is_empty() {
+            return Err(SignatureError::Malformed(NONCE_HEADER));
+        }
+
+        let secret = self
+            .credentials
+            .get(&credential.key_id)
+            .ok_or(SignatureError::UnknownCredential)?;
+        let canonical_request = canonical::canonical_request(
+            &parts.method,
+            &parts.uri,
+            &parts.headers,
+            &credential.signed_headers,
+            body,
+        )
+        .map_err(SignatureError::MissingHeader)?;
+        let string_to_sign = canonical::string_to_sign(timestamp, nonce, &canonical_request);
+
+        // 서명 먼저 검사 → 서명이 맞아야 타임스탬프와 nonce를 신뢰할 수 있음
+        // verify_slice는 상수 시간 비교
+        canonical::mac(secret, &string_to_sign)
+            .verify_slice(&credential.signature)
+            .map_err(|_| {
+                tracing::debug!(%canonical_request, "signature mismatch");
+                SignatureError::InvalidSignature
+            })?;
+
+        if now.abs_diff(timestamp) > self.tolerance.as_secs() {
+            return Err(SignatureError::ClockSkew);
+        }
+
+        Ok(Verified {
+            key_id: credential.key_id,
+            nonce: nonce.to_owned(),
+            timestamp,
+        })
+    }
+
+    /// 처음 보는 nonce면 기록, 이미 사용한 nonce면 replay
+    fn remember_nonce(&self, verified: &Verified, now: u64) -> Result<(), SignatureError> {
+        let mut nonces = self.nonces.lock().unwrap();
+
+        // 허용 오차보다 오래된 기록은 삭제해도 안전함 (같은 요청이 다시 와도 타임스탬프 검사에서 거절됨)
+        let tolerance = self.tolerance.as_secs();
+        nonces.retain(|_, timestamp| now.saturating_sub(*timestamp) <= tolerance);
+
+        let key = (verified.key_id.clone(), verified.nonce.clone());
+        if nonces.contains_key(&key) {
+            return Err(SignatureError::Replay);
+        }
+        nonces.insert(key, verified.timestamp);
+        Ok(())
+    }
+}
+
+/// ❌ 검증 실패 종류
+#[derive(Debug, PartialEq, Eq)]
+pub enum SignatureError {
+    /// 서명에 포함한다고 했지만 요청에 없는 헤더
+    MissingHeader(String),
+    Malformed(&'static str),
+    /// 반드시 서명해야 하는 헤더가 `SignedHeaders`에 없음
+    UnsignedHeader(&'static str),
+    UnknownCredential,
+    InvalidSignature,
+    ClockSkew,
+    Replay,
+    BodyTooLarge,
+}
+
+impl IntoResponse for SignatureError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            Self::MissingHeader(name) => {
+                (StatusCode::BAD_REQUEST, format!("missing `{name}` header"))
+            }
+            Self::Malformed(name) => (
+                StatusCode::BAD_REQUEST,
+                format!("malformed `{name}` header"),
+            ),
+            Self::UnsignedHeader(name) => (
+                StatusCode::BAD_REQUEST,
+                format!("`{name}` header must be signed"),
+            ),
+            Self::UnknownCredential => (StatusCode::UNAUTHORIZED, "unknown credential".to_owned()),
+            Self::InvalidSignature => (StatusCode::UNAUTHORIZED, "invalid signature".to_owned()),
+            Self::ClockSkew => (
+                StatusCode::UNAUTHORIZED,
+                "timestamp outside tolerance".to_owned(),
+            ),
+            Self::Replay => (StatusCode::UNAUTHORIZED, "nonce already used".to_owned()),
+            Self::BodyTooLarge => (StatusCode::PAYLOAD_TOO_LARGE, "body too large".to_owned()),
+        };
+        (status, message).into_response()
+    }
+}
+
+/// 🧱 미들웨어: body 버퍼링 → 서명/시각 검증 → nonce 기록 → 핸들러
+///
+/// 5-02 예제처럼 검증에 사용한 body를 다시 Request에 붙여 핸들러가 그대로 읽을 수 있게 함.
+/// 핸들러가 실패해도 nonce는 지우지 않음 → 재시도할 때는 새 nonce로 다시 서명해야 함
+pub async fn verify_signature(
+    State(verifier): State<SignatureVerifier>,
+    request: Request,
+    next: Next,
+) -> Result<Response, SignatureError> {
+    let (mut parts, body) = request.into_parts();
+    let bytes = to_bytes(body, MAX_BODY_SIZE)
+        .await
+        .map_err(|_| SignatureError::BodyTooLarge)?;
+
+    let now = unix_now();
+    let verified = verifier.verify(&parts, &bytes, now)?;
+    verifier.remember_nonce(&verified, now)?;
+
+    parts.extensions.insert(SignedBy(verified.key_id));
+    Ok(next
+        .run(Request::from_parts(parts, Body::from(bytes)))
+        .await)
+}
+
+/// ✍️ `Authorization` 헤더 생성 (클라이언트 쪽에서 하는 것과 같은 계산, 여기서는 테스트용)
+///
+/// `parts`에는 host, `X-Date`, `X-Nonce` 헤더가 들어 있어야 함
+#[cfg(test)]
+pub fn authorization(key_id: &str, secret: &[u8], parts: &Parts, body: &[u8]) -> String {
+    let signed_headers = canonical::signed_headers("host;x-date;x-nonce");
+    let canonical_request = canonical::canonical_request(
+        &parts.method,
+        &parts.uri,
+        &parts.headers,
+        &signed_headers,
+        body,
+    )
+    .unwrap();
+    let timestamp = parts.headers[DATE_HEADER]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let nonce = parts.headers[NONCE_HEADER].to_str().unwrap();
+    let signature = canonical::mac(
+        secret,
+        &canonical::string_to_sign(timestamp, nonce, &canonical_request),
+    )
+    .finalize()
+    .into_bytes();
+
+    format!(
+        "{ALGORITHM} Credential={key_id}, SignedHeaders={}, Signature={}",
+        signed_headers.join(";"),
+        canonical::encode_hex(&signature)
+    )
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+// `HMAC-SHA256 Credential=..., SignedHeaders=..., Signature=...`
+fn parse_authorization(value: &str) -> Result<Credential, SignatureError> {
+    let malformed = || SignatureError::Malformed("authorization");
+    let params = value
+        .strip_prefix(ALGORITHM)
+        .filter(|rest| rest.starts_with(' '))
+        .ok_or_else(malformed)?;
+
+    let (mut key_id, mut signed_headers, mut signature) = (None, None, None);
+    for param in params.split(',') {
+        match param.trim().split_once('=').ok_or_else(malformed)? {
+            ("Credential", value) => key_id = Some(value.to_owned()),
+            ("SignedHeaders", value) => signed_headers = Some(canonical::signed_headers(value)),
+            ("Signature", value) => signature = Some(decode_hex(value).ok_or_else(malformed)?),
+            _ => return Err(malformed()),
+        }
+    }
+
+    Ok(Credential {
+        key_id: key_id.ok_or_else(malformed)?,
+        signed_headers: signed_headers.ok_or_else(malformed)?,
+        signature: signature.ok_or_else(malformed)?,
+    })
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &'static str) -> Result<&'a str, SignatureError> {
+    headers
+        .get(name)
+        .ok_or_else(|| SignatureError::MissingHeader(name.to_owned()))?
+        .to_str()
+        .map_err(|_| SignatureError::Malformed(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"secret";
+
+    fn verifier() -> SignatureVerifier {
+        SignatureVerifier::new([("client", SECRET)], Duration::from_secs(300))
+    }
+
+    fn signed_parts(timestamp: u64, nonce: &str, body: &[u8]) -> Parts {
+        let (mut parts, ()) = Request::post("/orders?b=2&a=1")
+            .header("host", "example.com")
+            .header(DATE_HEADER, timestamp.to_string())
+            .header(NONCE_HEADER, nonce)
+            .body(())
+            .unwrap()
+            .into_parts();
+        let authorization = authorization("client", SECRET, &parts, body);
+        parts
+            .headers
+            .insert(AUTHORIZATION, authorization.parse().unwrap());
+        parts
+    }
+
+    #[test]
+    fn parses_authorization_header() {
+        let credential = parse_authorization(
+            "HMAC-SHA256 Credential=client, SignedHeaders=x-nonce;host;x-date, Signature=00ff",
+        )
+        .unwrap();
+        assert_eq!(credential.key_id, "client");
+        assert_eq!(credential.signed_headers, ["host", "x-date", "x-nonce"]);
+        assert_eq!(credential.signature, [0x00, 0xff]);
+
+        for invalid in [
+            "Bearer token",
+            "HMAC-SHA256Credential=client",
+            "HMAC-SHA256 Credential=client, SignedHeaders=host",
+            "HMAC-SHA256 Credential=client, SignedHeaders=host, Signature=zz",
+            "HMAC-SHA256 Credential=client, SignedHeaders=host, Signature=00, Extra=1",
+        ] {
+            assert!(
+                matches!(
+                    parse_authorization(invalid),
+                    Err(SignatureError::Malformed(_))
+                ),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn signature_covers_body_and_query() {
+        let verifier = verifier();
+        let parts = signed_parts(1000, "n1", b"{}");
+        assert!(verifier.verify(&parts, b"{}", 1000).is_ok());
+        assert_eq!(
+            verifier.verify(&parts, b"[]", 1000).err(),
+            Some(SignatureError::InvalidSignature)
+        );
+
+        let mut tampered = signed_parts(1000, "n1", b"{}");
+        tampered.uri = "/orders?b=3&a=1".parse().unwrap();
+        assert_eq!(
+            verifier.verify(&tampered, b"{}", 1000).err(),
+            Some(SignatureError::InvalidSignature)
+        );
+
+        // 쿼리 순서만 다른 것은 같은 요청
+        let mut reordered = signed_parts(1000, "n1", b"{}");
+        reordered.uri = "/orders?a=1&b=2".parse().unwrap();
+        assert!(verifier.verify(&reordered, b"{}", 1000).is_ok());
+    }
+
+    #[test]
+    fn clock_skew_applies_in_both_directions() {
+        let verifier = verifier();
+        let check = |timestamp: u64| verifier.verify(&signed_parts(timestamp, "n", b""), b"", 1000);
+
+        assert!(check(700).is_ok());
+        assert!(check(1300).is_ok());
+        assert_eq!(check(699).err(), Some(SignatureError::ClockSkew));
+        assert_eq!(check(1301).err(), Some(SignatureError::ClockSkew));
+    }
+
+    #[test]
+    fn nonces_are_single_use_and_pruned() {
+        let verifier = verifier();
+        let verified = |nonce: &str, timestamp| Verified {
+            key_id: "client".to_owned(),
+            nonce: nonce.to_owned(),
+            timestamp,
+        };
+
+        verifier.remember_nonce(&verified("a", 1000), 1000).unwrap();
+        assert_eq!(
+            verifier.remember_nonce(&verified("a", 1000), 1000),
+            Err(SignatureError::Replay)
+        );
+        verifier.remember_nonce(&verified("b", 1400), 1400).unwrap();
+
+        let nonces = verifier.nonces.lock().unwrap();
+        assert!(!nonces.contains_key(&("client".to_owned(), "a".to_owned())));
+        assert!(nonces.contains_key(&("client".to_owned(), "b".to_owned())));
+    }
+}
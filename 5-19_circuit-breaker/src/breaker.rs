@@ -0,0 +1,324 @@
+//! ⚡ 서킷 브레이커 상태 머신
+//!
+//! ```text
+//!            연속 실패 N번                 대기 시간 경과
+//!  Closed ─────────────────▶ Open ──────────────────────▶ HalfOpen
+//!    ▲                        ▲                              │
+//!    │                        └──────── probe 실패 ───────────┤
+//!    └──────────────────────────────── probe 성공 ───────────┘
+//! ```
+//!
+//! - Closed: 모든 요청 통과, 연속 실패 횟수를 셈 (성공하면 0으로)
+//! - Open: 업스트림을 호출하지 않고 바로 거절 (`Retry-After`로 남은 시간 안내)
+//! - HalfOpen: 요청 하나만 probe로 통과시켜 업스트림이 살아났는지 확인
+
+use serde::Serialize;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+// 테스트에서 시간을 멈추고 앞당길 수 있도록 tokio의 Instant 사용
+use tokio::time::Instant;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    /// 이만큼 연속으로 실패하면 Open
+    pub failure_threshold: u32,
+    /// Open 상태를 유지하는 시간 (지나면 HalfOpen)
+    pub open_duration: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    config: Config,
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: State,
+    consecutive_failures: u32,
+    metrics: Metrics,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum State {
+    Closed,
+    Open { until: Instant },
+    // probe 요청이 진행 중이면 true (그동안 다른 요청은 거절)
+    HalfOpen { probing: bool },
+}
+
+/// 📊 누적 지표
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Metrics {
+    pub successes: u64,
+    pub failures: u64,
+    /// 업스트림을 호출하지 않고 거절한 요청 수
+    pub rejected: u64,
+    /// Open으로 바뀐 횟수
+    pub times_opened: u64,
+}
+
+/// `GET /breaker/status` 응답
+#[derive(Debug, Serialize)]
+pub struct Status {
+    pub state: &'static str,
+    pub consecutive_failures: u32,
+    pub failure_threshold: u32,
+    /// Open 상태일 때 HalfOpen까지 남은 시간 (초, 올림)
+    pub retry_after_secs: Option<u64>,
+    pub metrics: Metrics,
+}
+
+/// 호출을 막았을 때 클라이언트에 알려 줄 대기 시간
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rejected {
+    pub retry_after: Duration,
+}
+
+// HalfOpen에서 probe 결과를 기다리는 동안 거절할 때 안내할 대기 시간
+const PROBE_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+impl CircuitBreaker {
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            inner: Arc::new(Mutex::new(Inner {
+                state: State::Closed,
+                consecutive_failures: 0,
+                metrics: Metrics::default(),
+            })),
+        }
+    }
+
+    /// 업스트림을 호출해도 되면 `Permit`, 아니면 `Rejected`
+    ///
+    /// 호출이 끝나면 `Permit::success` / `Permit::failure`로 결과를 알려야 함
+    pub fn try_acquire(&self) -> Result<Permit, Rejected> {
+        let mut inner = self.inner.lock().unwrap();
+        let now = Instant::now();
+
+        let retry_after = match inner.state {
+            State::Closed => return Ok(self.permit(false)),
+            State::Open { until } if now >= until => {
+                tracing::info!("circuit half-open, sending probe");
+                inner.state = State::HalfOpen { probing: true };
+                return Ok(self.permit(true));
+            }
+            State::HalfOpen { probing: false } => {
+                inner.state = State::HalfOpen { probing: true };
+                return Ok(self.permit(true));
+            }
+            State::Open { until } => until - now,
+            State::HalfOpen { probing: true } => PROBE_RETRY_AFTER,
+        };
+
+        inner.metrics.rejected += 1;
+        Err(Rejected { retry_after })
+    }
+
+    pub fn status(&self) -> Status {
+        let inner = self.inner.lock().unwrap();
+        let (state, retry_after_secs) = match inner.state {
+            State::Closed => ("closed", None),
+            State::Open { until } => {
+                let remaining = until.saturating_duration_since(Instant::now());
+                ("open", Some(ceil_secs(remaining)))
+            }
+            State::HalfOpen { .. } => ("half_open", None),
+        };
+        Status {
+            state,
+            consecutive_failures: inner.consecutive_failures,
+            failure_threshold: self.config.failure_threshold,
+            retry_after_secs,
+            metrics: inner.metrics.clone(),
+        }
+    }
+
+    fn permit(&self, probe: bool) -> Permit {
+        Permit {
+            breaker: self.clone(),
+            probe,
+            finished: false,
+        }
+    }
+
+    fn record(&self, probe: bool, success: bool) {
+        let mut inner = self.inner.lock().unwrap();
+
+        if success {
+            inner.metrics.successes += 1;
+            inner.consecutive_failures = 0;
+            if probe {
+                tracing::info!("probe succeeded, circuit closed");
+                inner.state = State::Closed;
+            }
+            return;
+        }
+
+        inner.metrics.failures += 1;
+        inner.consecutive_failures += 1;
+        let open = match inner.state {
+            State::Closed => inner.consecutive_failures >= self.config.failure_threshold,
+            State::HalfOpen { .. } => probe,
+            // Open 전에 시작된 요청이 늦게 실패한 경우 → 대기 시간을 늘리지 않음
+            State::Open { .. } => false,
+        };
+        if open {
+            tracing::warn!(
+                consecutive_failures = inner.consecutive_failures,
+                "circuit opened"
+            );
+            inner.state = State::Open {
+                until: Instant::now() + self.config.open_duration,
+            };
+            inner.metrics.times_opened += 1;
+        }
+    }
+
+    // 결과를 알리지 않고 버려진 probe (클라이언트가 연결을 끊는 등) → 다음 요청이 다시 probe
+    fn release_probe(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        if let State::HalfOpen { .. } = inner.state {
+            inner.state = State::HalfOpen { probing: false };
+        }
+    }
+}
+
+/// 🎫 업스트림 호출 허가
+#[must_use = "report the result with `success` or `failure`"]
+pub struct Permit {
+    breaker: CircuitBreaker,
+    probe: bool,
+    finished: bool,
+}
+
+impl Permit {
+    pub fn success(mut self) {
+        self.finished = true;
+        self.breaker.record(self.probe, true);
+    }
+
+    pub fn failure(mut self) {
+        self.finished = true;
+        self.breaker.record(self.probe, false);
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if !self.finished && self.probe {
+            self.breaker.release_probe();
+        }
+    }
+}
+
+/// `Retry-After`는 초 단위 정수 → 올림 (0초로 안내하지 않도록 최소 1)
+pub fn ceil_secs(duration: Duration) -> u64 {
+    let secs = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+    secs.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPEN_FOR: Duration = Duration::from_secs(10);
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(Config {
+            failure_threshold: 3,
+            open_duration: OPEN_FOR,
+        })
+    }
+
+    fn fail(breaker: &CircuitBreaker, times: usize) {
+        for _ in 0..times {
+            breaker.try_acquire().unwrap().failure();
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn opens_after_consecutive_failures() {
+        let breaker = breaker();
+
+        // 중간에 성공하면 연속 실패 횟수가 초기화됨
+        fail(&breaker, 2);
+        breaker.try_acquire().unwrap().success();
+        fail(&breaker, 2);
+        assert_eq!(breaker.status().state, "closed");
+
+        fail(&breaker, 1);
+        let status = breaker.status();
+        assert_eq!(status.state, "open");
+        assert_eq!(status.retry_after_secs, Some(10));
+        assert_eq!(status.metrics.times_opened, 1);
+
+        tokio::time::advance(Duration::from_millis(2500)).await;
+        assert_eq!(
+            breaker.try_acquire().err(),
+            Some(Rejected {
+                retry_after: Duration::from_millis(7500)
+            })
+        );
+        assert_eq!(breaker.status().retry_after_secs, Some(8));
+        assert_eq!(breaker.status().metrics.rejected, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn half_open_lets_a_single_probe_through() {
+        let breaker = breaker();
+        fail(&breaker, 3);
+        tokio::time::advance(OPEN_FOR).await;
+
+        let probe = breaker.try_acquire().unwrap();
+        assert_eq!(breaker.status().state, "half_open");
+        assert_eq!(
+            breaker.try_acquire().err(),
+            Some(Rejected {
+                retry_after: PROBE_RETRY_AFTER
+            })
+        );
+
+        probe.success();
+        let status = breaker.status();
+        assert_eq!(status.state, "closed");
+        assert_eq!(status.consecutive_failures, 0);
+        assert!(breaker.try_acquire().is_ok());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn failed_probe_reopens_the_circuit() {
+        let breaker = breaker();
+        fail(&breaker, 3);
+        tokio::time::advance(OPEN_FOR).await;
+
+        breaker.try_acquire().unwrap().failure();
+        let status = breaker.status();
+        assert_eq!(status.state, "open");
+        assert_eq!(status.retry_after_secs, Some(10));
+        assert_eq!(status.metrics.times_opened, 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn dropped_probe_frees_the_slot() {
+        let breaker = breaker();
+        fail(&breaker, 3);
+        tokio::time::advance(OPEN_FOR).await;
+
+        drop(breaker.try_acquire().unwrap());
+        assert_eq!(breaker.status().state, "half_open");
+        breaker.try_acquire().unwrap().success();
+        assert_eq!(breaker.status().state, "closed");
+    }
+
+    #[test]
+    fn retry_after_is_rounded_up() {
+        assert_eq!(ceil_secs(Duration::ZERO), 1);
+        assert_eq!(ceil_secs(Duration::from_millis(1)), 1);
+        assert_eq!(ceil_secs(Duration::from_secs(2)), 2);
+        assert_eq!(ceil_secs(Duration::from_millis(2001)), 3);
+    }
+}
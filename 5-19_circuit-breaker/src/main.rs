@@ -0,0 +1,284 @@
+//! 서킷 브레이커 예제
+//!
+//! 5-04 리버스 프록시처럼 4000번 포트로 받은 요청을 3000번 업스트림으로 전달하되,
+//! 업스트림이 계속 실패하면 잠시 호출을 멈춰 장애가 번지지 않도록 함
+//!
+//! • 연속 3번 실패(5xx, 연결 실패, 타임아웃)하면 Open → 10초 동안 바로 `503` + `Retry-After`
+//! • 10초가 지나면 HalfOpen → 요청 하나만 probe로 보내 성공하면 Closed, 실패하면 다시 Open
+//! • `GET /breaker/status`: 현재 상태와 누적 지표 (이 경로는 브레이커를 거치지 않음)
+//!
+//! 업스트림은 `POST /toggle`로 정상/장애 상태를 바꿀 수 있는 데모 서버
+//!
+//! ```not_rust
+//! cargo run -p example-circuit-breaker
+//! ```
+
+mod breaker;
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{header::RETRY_AFTER, uri::Uri, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use breaker::{ceil_secs, CircuitBreaker, Config, Rejected, Status};
+use hyper_util::{
+    client::legacy::{connect::HttpConnector, Client},
+    rt::TokioExecutor,
+};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+const BREAKER_CONFIG: Config = Config {
+    failure_threshold: 3,
+    open_duration: Duration::from_secs(10),
+};
+// 업스트림이 응답하지 않고 매달려 있는 것도 실패로 셈
+const UPSTREAM_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    // 업스트림(3000번) 먼저 띄움
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("upstream listening on {}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, upstream()).await.unwrap() });
+
+    let proxy = Proxy::new("http://127.0.0.1:3000", UPSTREAM_TIMEOUT);
+    let breaker = CircuitBreaker::new(BREAKER_CONFIG);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:4000")
+        .await
+        .unwrap();
+    tracing::debug!("proxy listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(proxy, breaker)).await.unwrap();
+}
+
+fn app(proxy: Proxy, breaker: CircuitBreaker) -> Router {
+    // 프록시로 가는 요청만 브레이커를 거침
+    let proxied = Router::new()
+        .fallback(proxy_handler)
+        .layer(middleware::from_fn_with_state(
+            breaker.clone(),
+            circuit_breaker,
+        ))
+        .with_state(proxy);
+
+    Router::new()
+        .route("/breaker/status", get(breaker_status))
+        .with_state(breaker)
+        .fallback_service(proxied)
+}
+
+// 🔌 브레이커 미들웨어: 5xx 응답(프록시의 502/504 포함)을 업스트림 실패로 기록
+async fn circuit_breaker(
+    State(breaker): State<CircuitBreaker>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let permit = match breaker.try_acquire() {
+        Ok(permit) => permit,
+        Err(Rejected { retry_after }) => {
+            tracing::debug!(?retry_after, "circuit open, request rejected");
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                [(RETRY_AFTER, ceil_secs(retry_after).to_string())],
+                "upstream unavailable (circuit open)",
+            )
+                .into_response();
+        }
+    };
+
+    let response = next.run(request).await;
+    if response.status().is_server_error() {
+        permit.failure();
+    } else {
+        permit.success();
+    }
+    response
+}
+
+async fn breaker_status(State(breaker): State<CircuitBreaker>) -> Json<Status> {
+    Json(breaker.status())
+}
+
+// 🔁 리버스 프록시 (5-04와 같은 방식)
+
+#[derive(Clone)]
+struct Proxy {
+    client: Client<HttpConnector, Body>,
+    upstream: String,
+    timeout: Duration,
+}
+
+impl Proxy {
+    fn new(upstream: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            client: Client::builder(TokioExecutor::new()).build(HttpConnector::new()),
+            upstream: upstream.into(),
+            timeout,
+        }
+    }
+}
+
+async fn proxy_handler(
+    State(proxy): State<Proxy>,
+    mut request: Request,
+) -> Result<Response, StatusCode> {
+    let path_query = request
+        .uri()
+        .path_and_query()
+        .map_or("/", |path_query| path_query.as_str());
+    *request.uri_mut() = Uri::try_from(format!("{}{path_query}", proxy.upstream))
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    match tokio::time::timeout(proxy.timeout, proxy.client.request(request)).await {
+        Ok(Ok(response)) => Ok(response.into_response()),
+        Ok(Err(err)) => {
+            tracing::warn!(%err, "upstream request failed");
+            Err(StatusCode::BAD_GATEWAY)
+        }
+        Err(_) => {
+            tracing::warn!("upstream timed out");
+            Err(StatusCode::GATEWAY_TIMEOUT)
+        }
+    }
+}
+
+// 🧭 데모 업스트림: `POST /toggle`로 장애 상태를 켜고 끔
+
+fn upstream() -> Router {
+    Router::new()
+        .route("/", get(hello))
+        .route("/toggle", post(toggle))
+        .with_state(Arc::new(AtomicBool::new(true)))
+}
+
+async fn hello(State(healthy): State<Arc<AtomicBool>>) -> Result<&'static str, StatusCode> {
+    if healthy.load(Ordering::SeqCst) {
+        Ok("Hello, world!")
+    } else {
+        Err(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+}
+
+async fn toggle(State(healthy): State<Arc<AtomicBool>>) -> String {
+    let healthy = !healthy.fetch_xor(true, Ordering::SeqCst);
+    format!("healthy: {healthy}\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_body_util::BodyExt;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    // 임의 포트에 데모 업스트림을 띄우고 그 앞에 프록시 구성
+    async fn setup() -> Router {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, upstream()).await.unwrap() });
+
+        app(
+            Proxy::new(format!("http://{addr}"), UPSTREAM_TIMEOUT),
+            CircuitBreaker::new(BREAKER_CONFIG),
+        )
+    }
+
+    async fn send(app: &Router, method: &str, uri: &str) -> Response {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+        app.clone().oneshot(request).await.unwrap()
+    }
+
+    async fn status_json(app: &Router) -> Value {
+        let response = send(app, "GET", "/breaker/status").await;
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn healthy_upstream_passes_through() {
+        let app = setup().await;
+
+        let response = send(&app, "GET", "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(&body[..], b"Hello, world!");
+
+        let status = status_json(&app).await;
+        assert_eq!(status["state"], "closed");
+        assert_eq!(status["metrics"]["successes"], 1);
+    }
+
+    #[tokio::test]
+    async fn repeated_failures_open_the_circuit() {
+        let app = setup().await;
+        send(&app, "POST", "/toggle").await;
+
+        for _ in 0..BREAKER_CONFIG.failure_threshold {
+            let response = send(&app, "GET", "/").await;
+            assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        }
+
+        // 업스트림을 고쳐도 Open 동안은 호출하지 않음 (toggle 요청도 거절됨)
+        let response = send(&app, "POST", "/toggle").await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()[RETRY_AFTER], "10");
+
+        let status = status_json(&app).await;
+        assert_eq!(status["state"], "open");
+        assert_eq!(status["retry_after_secs"], 10);
+        assert_eq!(status["metrics"]["failures"], 3);
+        assert_eq!(status["metrics"]["rejected"], 1);
+    }
+
+    #[tokio::test]
+    async fn unreachable_upstream_counts_as_failure() {
+        // 바인딩했다가 바로 닫은 포트 → 연결 거부
+        let addr = {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            listener.local_addr().unwrap()
+        };
+        let app = app(
+            Proxy::new(format!("http://{addr}"), UPSTREAM_TIMEOUT),
+            CircuitBreaker::new(BREAKER_CONFIG),
+        );
+
+        let response = send(&app, "GET", "/").await;
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+        assert_eq!(status_json(&app).await["consecutive_failures"], 1);
+    }
+}
+
+// 🧪 테스트 방법
+//
+// curl -i localhost:4000/              → 200 Hello, world!
+// curl -X POST localhost:3000/toggle   → healthy: false (업스트림에 직접 요청)
+// curl -i localhost:4000/              → 500 (세 번 반복)
+// curl -i localhost:4000/              → 503, Retry-After: 10 (업스트림 호출 안 함)
+// curl localhost:4000/breaker/status   → {"state":"open","retry_after_secs":...}
+// curl -X POST localhost:3000/toggle   → healthy: true
+// (10초 뒤) curl -i localhost:4000/    → probe 성공 → 200, 상태는 closed
@@ -0,0 +1,237 @@
+//! 🛡️ tower 레이어로 감싼 외부 호출용 HTTP 클라이언트
+//!
+//! ```text
+//! Retry (재시도 예산 + 지수 백오프)
+//!   └─ Hedge (선택: 느린 요청이면 같은 요청을 하나 더 보내 먼저 온 응답 사용)
+//!        └─ Timeout (시도 한 번마다)
+//!             └─ reqwest::Client (tower::Service 구현)
+//! ```
+//!
+//! 핸들러는 레이어 구성을 몰라도 되고 `OutboundClient`를 `oneshot`으로 호출하기만 하면 됨
+//!
+//! • 재시도는 두 번 실행돼도 결과가 같은 요청만 (GET, PUT, DELETE 등 또는 `Idempotency-Key`가 있는 요청)
+//!   → 타임아웃은 업스트림이 이미 처리했는지 모르는 상태라 POST를 다시 보내면 주문이 두 번 생길 수 있음
+
+use reqwest::{Request, Response, StatusCode};
+use std::{sync::Arc, time::Duration};
+use tower::{
+    buffer::Buffer,
+    hedge::Hedge,
+    retry::{
+        budget::{Budget, TpsBudget},
+        Policy,
+    },
+    util::BoxCloneSyncService,
+    BoxError, ServiceBuilder,
+};
+
+/// 핸들러 사이에서 복제해서 쓰는 완성된 클라이언트 서비스
+pub type OutboundClient = BoxCloneSyncService<Request, Response, BoxError>;
+
+// 재시도할 때 몇 번째 재시도인지 업스트림에 알려 주는 헤더 (로그 확인용)
+pub const RETRY_HEADER: &str = "x-retry-attempt";
+
+// 이 헤더가 있으면 업스트림이 중복 요청을 걸러 준다고 보고 POST/PATCH도 재시도
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+// 재시도 대기 시간의 상한 (`max_retries`가 커도 백오프가 끝없이 늘어나지 않도록)
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// 시도 한 번의 제한 시간
+    pub timeout: Duration,
+    /// 최대 재시도 횟수 (첫 시도 제외)
+    pub max_retries: u32,
+    /// 첫 재시도 전 대기 시간 (이후 2배씩 증가)
+    pub backoff: Duration,
+    /// 재시도 예산: 최근 요청 수 대비 재시도 비율 제한 → 장애 때 재시도가 부하를 키우지 않도록
+    pub budget: Arc<TpsBudget>,
+    pub hedge: Option<HedgeConfig>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct HedgeConfig {
+    /// 최근 응답 시간의 이 백분위수보다 오래 걸리면 같은 요청을 하나 더 보냄
+    pub latency_percentile: f32,
+    /// 응답 시간 기록이 이만큼 쌓이기 전에는 hedge하지 않음
+    pub min_data_points: u64,
+    /// 응답 시간 기록을 갱신하는 주기 (직전 주기의 기록으로 판단)
+    pub period: Duration,
+}
+
+pub fn build(client: reqwest::Client, config: &Config) -> OutboundClient {
+    let timed = ServiceBuilder::new()
+        .timeout(config.timeout)
+        .service(client);
+
+    let inner = match config.hedge {
+        Some(hedge) => {
+            let hedged = Hedge::new(
+                timed,
+                IdempotentOnly,
+                hedge.min_data_points,
+                hedge.latency_percentile,
+                hedge.period,
+            );
+            // Hedge는 Clone이 아니므로 Buffer로 감싸서 공유 (응답 시간 기록도 하나로 유지)
+            BoxCloneSyncService::new(Buffer::new(hedged, 1024))
+        }
+        None => BoxCloneSyncService::new(timed),
+    };
+
+    let retry = RetryPolicy {
+        attempt: 0,
+        max_retries: config.max_retries,
+        backoff: config.backoff,
+        budget: config.budget.clone(),
+    };
+    BoxCloneSyncService::new(ServiceBuilder::new().retry(retry).service(inner))
+}
+
+/// 🔁 재시도 정책 (요청마다 복제되므로 `attempt`는 요청별로 따로 셈)
+#[derive(Debug, Clone)]
+struct RetryPolicy {
+    attempt: u32,
+    max_retries: u32,
+    backoff: Duration,
+    budget: Arc<TpsBudget>,
+}
+
+impl Policy<Request, Response, BoxError> for RetryPolicy {
+    type Future = tokio::time::Sleep;
+
+    fn retry(
+        &mut self,
+        req: &mut Request,
+        result: &mut Result<Response, BoxError>,
+    ) -> Option<Self::Future> {
+        // 원래 요청마다 예산을 적립 (재시도는 적립하지 않음)
+        if self.attempt == 0 {
+            self.budget.deposit();
+        }
+
+        let retryable = match result {
+            Ok(res) => is_retryable_status(res.status()),
+            // 타임아웃, 연결 실패 등
+            Err(_) => true,
+        };
+        if !retryable || self.attempt >= self.max_retries || !is_idempotent(req) {
+            return None;
+        }
+        if !self.budget.withdraw() {
+            tracing::warn!(url = %req.url(), "retry budget exhausted, giving up");
+            return None;
+        }
+
+        let delay = self
+            .backoff
+            .saturating_mul(2u32.saturating_pow(self.attempt))
+            .min(MAX_BACKOFF);
+        self.attempt += 1;
+        tracing::debug!(url = %req.url(), attempt = self.attempt, ?delay, "retrying");
+        req.headers_mut()
+            .insert(RETRY_HEADER, self.attempt.to_string().parse().unwrap());
+        Some(tokio::time::sleep(delay))
+    }
+
+    // body가 스트림이면 복제할 수 없음 → 재시도하지 않음
+    fn clone_request(&mut self, req: &Request) -> Option<Request> {
+        req.try_clone()
+    }
+}
+
+// 서버 과부하나 일시적인 장애로 볼 수 있는 응답만 재시도
+fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+// 다시 보내도 결과가 같은 요청 (RFC 9110의 idempotent 메서드 또는 Idempotency-Key)
+fn is_idempotent(req: &Request) -> bool {
+    use reqwest::Method;
+    matches!(
+        *req.method(),
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE | Method::PUT | Method::DELETE
+    ) || req.headers().contains_key(IDEMPOTENCY_KEY_HEADER)
+}
+
+/// ✂️ hedge 정책: 두 번 보내도 안전한 요청(GET, HEAD)만
+#[derive(Debug, Clone, Copy)]
+struct IdempotentOnly;
+
+impl tower::hedge::Policy<Request> for IdempotentOnly {
+    fn clone_request(&self, req: &Request) -> Option<Request> {
+        self.can_retry(req).then(|| req.try_clone()).flatten()
+    }
+
+    fn can_retry(&self, req: &Request) -> bool {
+        matches!(*req.method(), reqwest::Method::GET | reqwest::Method::HEAD)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::Method;
+
+    fn policy(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            attempt: 0,
+            max_retries,
+            backoff: Duration::from_millis(100),
+            budget: Arc::new(TpsBudget::new(Duration::from_secs(10), 1000, 1.0)),
+        }
+    }
+
+    fn request(method: Method) -> Request {
+        Request::new(method, "http://localhost/".parse().unwrap())
+    }
+
+    fn unavailable() -> Result<Response, BoxError> {
+        Ok(axum::http::Response::builder()
+            .status(StatusCode::SERVICE_UNAVAILABLE)
+            .body("")
+            .unwrap()
+            .into())
+    }
+
+    #[tokio::test]
+    async fn only_idempotent_requests_are_retried() {
+        let mut get = request(Method::GET);
+        assert!(policy(2).retry(&mut get, &mut unavailable()).is_some());
+        let mut put = request(Method::PUT);
+        assert!(policy(2)
+            .retry(&mut put, &mut Err("timeout".into()))
+            .is_some());
+
+        // 업스트림이 처리했는지 모르는 POST는 다시 보내지 않음
+        let mut post = request(Method::POST);
+        assert!(policy(2).retry(&mut post, &mut unavailable()).is_none());
+        assert!(policy(2)
+            .retry(&mut post, &mut Err("timeout".into()))
+            .is_none());
+
+        // Idempotency-Key가 있으면 재시도
+        post.headers_mut()
+            .insert(IDEMPOTENCY_KEY_HEADER, "order-1".parse().unwrap());
+        assert!(policy(2).retry(&mut post, &mut unavailable()).is_some());
+    }
+
+    #[tokio::test]
+    async fn backoff_is_capped() {
+        let mut policy = policy(100);
+        let mut req = request(Method::GET);
+        for _ in 0..40 {
+            let sleep = policy.retry(&mut req, &mut unavailable()).unwrap();
+            let delay = sleep.deadline() - tokio::time::Instant::now();
+            assert!(delay <= MAX_BACKOFF, "{delay:?}");
+        }
+        assert_eq!(policy.attempt, 40);
+    }
+}
@@ -0,0 +1,262 @@
+//! 외부 API 호출에 타임아웃, 재시도, hedging 적용하기
+//!
+//! 핸들러에서 `reqwest::Client`를 그대로 쓰면 업스트림이 느리거나 잠깐 실패할 때
+//! 그대로 기다리거나 실패함. 여기서는 tower 레이어를 쌓은 클라이언트 서비스를 만들어
+//! 핸들러에서는 호출만 하도록 분리 (레이어 구성은 `client.rs`)
+//!
+//! • `GET /quote`: 타임아웃(시도당 1초) + 최대 2번 재시도(예산 안에서) + hedging
+//! • `GET /quote/naive`: 같은 업스트림을 `reqwest`로 한 번만 호출 (비교용)
+//!
+//! 업스트림(3000번)은 실패(503)와 지연(3초)을 섞어 응답하는 데모 서버
+//!
+//! ```not_rust
+//! cargo run -p example-outbound-resilience
+//! ```
+
+mod client;
+mod upstream;
+
+use axum::{extract::State, http::StatusCode, routing::get, Router};
+use client::{Config, HedgeConfig, OutboundClient};
+use reqwest::{Method, Url};
+use std::{fmt::Display, sync::Arc, time::Duration};
+use tower::{retry::budget::TpsBudget, ServiceExt};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use upstream::{Behavior, Flaky};
+
+#[derive(Clone)]
+struct AppState {
+    client: OutboundClient,
+    // 비교용: 아무 레이어 없는 reqwest 클라이언트
+    naive: reqwest::Client,
+    price_url: Url,
+}
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let flaky = Flaky::new([
+        Behavior::Ok,
+        Behavior::Fail,
+        Behavior::Ok,
+        Behavior::Slow(Duration::from_secs(3)),
+        Behavior::Ok,
+        Behavior::Ok,
+        Behavior::Fail,
+        Behavior::Fail,
+    ]);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("upstream listening on {}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, flaky.router()).await.unwrap() });
+
+    let config = Config {
+        timeout: Duration::from_secs(1),
+        max_retries: 2,
+        backoff: Duration::from_millis(100),
+        // 최근 10초 동안 요청 수의 20% + 초당 5번까지 재시도 허용
+        budget: Arc::new(TpsBudget::new(Duration::from_secs(10), 5, 0.2)),
+        hedge: Some(HedgeConfig {
+            latency_percentile: 0.9,
+            min_data_points: 10,
+            period: Duration::from_secs(10),
+        }),
+    };
+    let state = AppState {
+        client: client::build(reqwest::Client::new(), &config),
+        naive: reqwest::Client::new(),
+        price_url: "http://127.0.0.1:3000/price".parse().unwrap(),
+    };
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:4000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(state)).await.unwrap();
+}
+
+fn app(state: AppState) -> Router {
+    Router::new()
+        .route("/quote", get(quote))
+        .route("/quote/naive", get(naive_quote))
+        .with_state(state)
+}
+
+async fn quote(State(state): State<AppState>) -> Result<String, (StatusCode, String)> {
+    let request = reqwest::Request::new(Method::GET, state.price_url);
+    let response = state
+        .client
+        .oneshot(request)
+        .await
+        .map_err(upstream_error)?;
+    read_price(response).await
+}
+
+// ⚠️ 타임아웃이 없어 업스트림이 멈추면 같이 멈추고, 한 번 실패하면 그대로 실패
+async fn naive_quote(State(state): State<AppState>) -> Result<String, (StatusCode, String)> {
+    let response = state
+        .naive
+        .get(state.price_url)
+        .send()
+        .await
+        .map_err(upstream_error)?;
+    read_price(response).await
+}
+
+async fn read_price(response: reqwest::Response) -> Result<String, (StatusCode, String)> {
+    let status = response.status();
+    if !status.is_success() {
+        return Err(upstream_error(format!("status {status}")));
+    }
+    response.text().await.map_err(upstream_error)
+}
+
+fn upstream_error(err: impl Display) -> (StatusCode, String) {
+    tracing::warn!(%err, "upstream call failed");
+    (StatusCode::BAD_GATEWAY, format!("upstream error: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use http_body_util::BodyExt;
+    use std::time::Instant;
+
+    const SLOW: Duration = Duration::from_millis(800);
+
+    fn config() -> Config {
+        Config {
+            timeout: Duration::from_millis(300),
+            max_retries: 2,
+            backoff: Duration::from_millis(10),
+            budget: Arc::new(TpsBudget::new(Duration::from_secs(10), 10, 0.2)),
+            hedge: None,
+        }
+    }
+
+    async fn setup(pattern: Vec<Behavior>, config: Config) -> (Router, Flaky) {
+        let flaky = Flaky::new(pattern);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = flaky.router();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let state = AppState {
+            client: client::build(reqwest::Client::new(), &config),
+            naive: reqwest::Client::new(),
+            price_url: format!("http://{addr}/price").parse().unwrap(),
+        };
+        (app(state), flaky)
+    }
+
+    async fn get(app: &Router, uri: &str) -> (StatusCode, String) {
+        let request = Request::get(uri).body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn transient_failures_are_retried() {
+        let pattern = vec![Behavior::Fail, Behavior::Fail, Behavior::Ok];
+
+        let (app, flaky) = setup(pattern.clone(), config()).await;
+        assert_eq!(
+            get(&app, "/quote").await,
+            (StatusCode::OK, "price: 102\n".to_owned())
+        );
+        assert_eq!(flaky.hits(), 3);
+
+        // 같은 업스트림을 한 번만 호출하면 그대로 실패
+        let (app, flaky) = setup(pattern, config()).await;
+        let (status, _) = get(&app, "/quote/naive").await;
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+        assert_eq!(flaky.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn slow_attempt_times_out_and_is_retried() {
+        let (app, flaky) = setup(vec![Behavior::Slow(SLOW), Behavior::Ok], config()).await;
+
+        let started = Instant::now();
+        let (status, _) = get(&app, "/quote").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(started.elapsed() < SLOW);
+        assert_eq!(flaky.hits(), 2);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let (app, flaky) = setup(vec![Behavior::Fail], config()).await;
+
+        let (status, body) = get(&app, "/quote").await;
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+        assert_eq!(body, "upstream error: status 503 Service Unavailable");
+        assert_eq!(flaky.hits(), 3);
+    }
+
+    #[tokio::test]
+    async fn empty_budget_disables_retries() {
+        let config = Config {
+            budget: Arc::new(TpsBudget::new(Duration::from_secs(1), 0, 0.0)),
+            ..config()
+        };
+        let (app, flaky) = setup(vec![Behavior::Fail, Behavior::Ok], config).await;
+
+        let (status, _) = get(&app, "/quote").await;
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+        assert_eq!(flaky.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn slow_request_is_hedged() {
+        const PERIOD: Duration = Duration::from_millis(500);
+        let config = Config {
+            // 타임아웃으로 재시도되기 전에 hedge가 먼저 응답을 받는지 확인
+            timeout: Duration::from_secs(5),
+            hedge: Some(HedgeConfig {
+                latency_percentile: 0.9,
+                min_data_points: 5,
+                period: PERIOD,
+            }),
+            ..config()
+        };
+        let mut pattern = vec![Behavior::Ok; 5];
+        pattern.extend([Behavior::Slow(Duration::from_secs(2)), Behavior::Ok]);
+        let created = tokio::time::Instant::now();
+        let (app, flaky) = setup(pattern, config).await;
+
+        // 첫 주기에 응답 시간 기록을 쌓음 → 다음 주기부터 이 기록으로 hedge 여부를 판단
+        // (두 주기 이상 지나면 기록이 지워지므로 두 번째 주기 안에 요청)
+        for _ in 0..5 {
+            assert_eq!(get(&app, "/quote").await.0, StatusCode::OK);
+        }
+        tokio::time::sleep_until(created + PERIOD + PERIOD / 4).await;
+
+        let started = Instant::now();
+        let (status, body) = get(&app, "/quote").await;
+        assert_eq!(status, StatusCode::OK);
+        // 느린 6번째 요청 대신 hedge로 보낸 7번째 요청의 응답
+        assert_eq!(body, "price: 106\n");
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(flaky.hits(), 7);
+    }
+}
+
+// 🧪 테스트 방법
+//
+// for i in $(seq 8); do curl -s -w ' %{http_code} %{time_total}s\n' localhost:4000/quote; done
+// → 모두 200 (503은 재시도, 3초 지연은 1초 타임아웃 후 재시도 또는 hedge)
+//
+// for i in $(seq 8); do curl -s -w ' %{http_code} %{time_total}s\n' localhost:4000/quote/naive; done
+// → 일부는 502, 일부는 3초씩 걸림
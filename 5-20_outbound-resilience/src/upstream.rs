@@ -0,0 +1,67 @@
+//! 🎲 일부러 실패하거나 느리게 응답하는 데모 업스트림
+//!
+//! 정해진 동작 목록을 요청 순서대로 돌아가며 적용 → 실행할 때마다 같은 결과
+
+use axum::{extract::State, http::HeaderMap, http::StatusCode, routing::get, Router};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use crate::client::RETRY_HEADER;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Behavior {
+    Ok,
+    /// 503 Service Unavailable
+    Fail,
+    /// 이만큼 기다린 뒤 정상 응답
+    Slow(Duration),
+}
+
+#[derive(Debug, Clone)]
+pub struct Flaky {
+    pattern: Arc<[Behavior]>,
+    hits: Arc<AtomicUsize>,
+}
+
+impl Flaky {
+    pub fn new(pattern: impl Into<Arc<[Behavior]>>) -> Self {
+        Self {
+            pattern: pattern.into(),
+            hits: Default::default(),
+        }
+    }
+
+    /// 지금까지 받은 요청 수
+    #[cfg(test)]
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::SeqCst)
+    }
+
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/price", get(price))
+            .with_state(self.clone())
+    }
+}
+
+async fn price(State(flaky): State<Flaky>, headers: HeaderMap) -> Result<String, StatusCode> {
+    let hit = flaky.hits.fetch_add(1, Ordering::SeqCst);
+    let behavior = flaky.pattern[hit % flaky.pattern.len()];
+    let retry = headers
+        .get(RETRY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("0");
+    tracing::debug!(hit, retry, ?behavior, "upstream request");
+
+    match behavior {
+        Behavior::Ok => {}
+        Behavior::Fail => return Err(StatusCode::SERVICE_UNAVAILABLE),
+        Behavior::Slow(delay) => tokio::time::sleep(delay).await,
+    }
+    Ok(format!("price: {}\n", 100 + hit))
+}
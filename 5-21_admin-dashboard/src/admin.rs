@@ -0,0 +1,234 @@
+//! 🔐 관리자 대시보드 (`/admin` 하위)
+//!
+//! • `GET /admin`: 등록된 라우트와 요청 카운터, 최근 이벤트, 기능 플래그를 한 페이지에 표시
+//! • `POST /admin/flags/{name}/toggle`: 플래그 켜기/끄기 후 대시보드로 되돌아감
+//! • `GET|POST /admin/login`: 브라우저용 로그인 (랜덤 세션 ID를 쿠키로 저장)
+//!
+//! 3-02의 관리자 API처럼 `Authorization: Bearer <토큰>`으로 보호하되,
+//! 브라우저는 페이지 이동에 헤더를 붙일 수 없으므로 로그인 세션 쿠키도 허용
+//!   → 쿠키에는 토큰 대신 세션 ID를 넣음 (쿠키가 새어도 토큰 자체는 드러나지 않고 `SESSION_TTL` 뒤 만료)
+//!   → 토큰 비교는 `subtle::ConstantTimeEq`로 (비교 시간으로 토큰을 한 글자씩 알아내지 못하도록)
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{
+        header::{AUTHORIZATION, COOKIE, LOCATION, SET_COOKIE, WWW_AUTHENTICATE},
+        Request, Response, StatusCode,
+    },
+    response::{Html, IntoResponse, Redirect},
+    routing::{get, post},
+    Form, Router,
+};
+use minijinja::{context, Environment};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+use subtle::ConstantTimeEq;
+use tower_http::validate_request::{ValidateRequest, ValidateRequestHeaderLayer};
+
+use crate::{AppState, ROUTES};
+
+// 관리자 페이지에 사용하는 Bearer 토큰
+pub const ADMIN_TOKEN: &str = "secret-token";
+const SESSION_COOKIE: &str = "admin_session";
+const SESSION_TTL: Duration = Duration::from_secs(8 * 60 * 60);
+
+/// 템플릿 등록 (이름이 `.html.jinja`로 끝나야 HTML 자동 escape 적용)
+pub fn templates() -> Environment<'static> {
+    let mut env = Environment::new();
+    env.add_template(
+        "dashboard.html.jinja",
+        include_str!("../templates/dashboard.html.jinja"),
+    )
+    .unwrap();
+    env.add_template(
+        "login.html.jinja",
+        include_str!("../templates/login.html.jinja"),
+    )
+    .unwrap();
+    env
+}
+
+pub fn router(sessions: Sessions) -> Router<AppState> {
+    let protected = Router::new()
+        .route("/", get(dashboard)) // GET /admin
+        .route("/flags/{name}/toggle", post(toggle_flag)) // POST /admin/flags/{name}/toggle
+        .layer(ValidateRequestHeaderLayer::custom(AdminAuth { sessions }));
+
+    // 로그인 페이지는 인증 없이 접근 (merge 전에 건 layer는 protected에만 적용)
+    Router::new()
+        .route("/login", get(login_form).post(login))
+        .merge(protected)
+}
+
+// --- 🔑 인증
+
+fn is_admin_token(token: &str) -> bool {
+    token.as_bytes().ct_eq(ADMIN_TOKEN.as_bytes()).into()
+}
+
+/// 로그인 세션 (세션 ID → 만료 시각), 재시작하면 다시 로그인
+#[derive(Clone, Default)]
+pub struct Sessions(Arc<RwLock<HashMap<String, Instant>>>);
+
+impl Sessions {
+    fn create(&self) -> String {
+        let id =
+            rand::random::<[u8; 32]>()
+                .iter()
+                .fold(String::with_capacity(64), |mut hex, byte| {
+                    let _ = write!(hex, "{byte:02x}");
+                    hex
+                });
+        let now = Instant::now();
+        let mut sessions = self.0.write().unwrap();
+        // 만들 때마다 만료된 세션 정리
+        sessions.retain(|_, expires_at| *expires_at > now);
+        sessions.insert(id.clone(), now + SESSION_TTL);
+        id
+    }
+
+    fn is_valid(&self, id: &str) -> bool {
+        self.0
+            .read()
+            .unwrap()
+            .get(id)
+            .is_some_and(|expires_at| *expires_at > Instant::now())
+    }
+}
+
+/// Bearer 헤더 또는 로그인 쿠키 중 하나가 맞으면 통과
+#[derive(Clone)]
+struct AdminAuth {
+    sessions: Sessions,
+}
+
+impl<B> ValidateRequest<B> for AdminAuth {
+    type ResponseBody = Body;
+
+    fn validate(&mut self, request: &mut Request<B>) -> Result<(), Response<Body>> {
+        let headers = request.headers();
+        let bearer = headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(is_admin_token);
+        let cookie = headers
+            .get_all(COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(';'))
+            .filter_map(|pair| pair.trim().split_once('='))
+            .any(|(name, value)| name == SESSION_COOKIE && self.sessions.is_valid(value));
+
+        if bearer || cookie {
+            return Ok(());
+        }
+        Err((
+            StatusCode::UNAUTHORIZED,
+            [(WWW_AUTHENTICATE, "Bearer")],
+            Html(r#"unauthorized → <a href="/admin/login">log in</a>"#),
+        )
+            .into_response())
+    }
+}
+
+async fn login_form(State(state): State<AppState>) -> Result<Html<String>, StatusCode> {
+    render(&state, "login.html.jinja", context! { failed => false })
+}
+
+#[derive(Deserialize)]
+struct Login {
+    token: String,
+}
+
+async fn login(State(state): State<AppState>, Form(form): Form<Login>) -> Response<Body> {
+    if !is_admin_token(&form.token) {
+        tracing::warn!("admin login failed");
+        let page = render(&state, "login.html.jinja", context! { failed => true });
+        return (StatusCode::UNAUTHORIZED, page).into_response();
+    }
+
+    tracing::info!("admin logged in");
+    // SameSite=Strict → 다른 사이트에서 보낸 폼(CSRF)에는 쿠키가 붙지 않음
+    let cookie = format!(
+        "{SESSION_COOKIE}={}; Path=/admin; Max-Age={}; HttpOnly; SameSite=Strict",
+        state.sessions.create(),
+        SESSION_TTL.as_secs()
+    );
+    (
+        StatusCode::SEE_OTHER,
+        [(SET_COOKIE, cookie), (LOCATION, "/admin".to_owned())],
+    )
+        .into_response()
+}
+
+// --- 🖥️ 대시보드
+
+#[derive(Serialize)]
+struct RouteRow {
+    method: &'static str,
+    path: &'static str,
+    requests: u64,
+    errors: u64,
+    avg_ms: String,
+}
+
+async fn dashboard(State(state): State<AppState>) -> Result<Html<String>, StatusCode> {
+    let routes: Vec<RouteRow> = ROUTES
+        .iter()
+        .map(|&(method, path)| {
+            let stats = state.stats.get(method, path);
+            RouteRow {
+                method,
+                path,
+                requests: stats.requests,
+                errors: stats.errors,
+                avg_ms: format!("{:.2}", stats.avg_ms()),
+            }
+        })
+        .collect();
+    let total_requests: u64 = routes.iter().map(|route| route.requests).sum();
+
+    render(
+        &state,
+        "dashboard.html.jinja",
+        context! {
+            routes,
+            total_requests,
+            flags => state.flags.all(),
+            events => state.events.recent(),
+        },
+    )
+}
+
+async fn toggle_flag(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Redirect, (StatusCode, String)> {
+    let enabled = state
+        .flags
+        .toggle(&name)
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("unknown flag `{name}`\n")))?;
+    tracing::info!(flag = name, enabled, "feature flag toggled");
+
+    // POST 후 새로고침해도 다시 토글되지 않도록 GET으로 이동 (PRG 패턴)
+    Ok(Redirect::to("/admin"))
+}
+
+fn render(state: &AppState, name: &str, ctx: minijinja::Value) -> Result<Html<String>, StatusCode> {
+    state
+        .templates
+        .get_template(name)
+        .and_then(|template| template.render(ctx))
+        .map(Html)
+        .map_err(|err| {
+            tracing::error!(%err, "failed to render `{name}`");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
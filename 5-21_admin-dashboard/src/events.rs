@@ -0,0 +1,155 @@
+//! 📜 최근 tracing 이벤트를 메모리에 보관하는 레이어
+//!
+//! fmt 레이어가 터미널에 출력하는 것과 같은 이벤트를 최근 `capacity`개만 링 버퍼에 남김
+//! → 대시보드에서 로그 파일이나 터미널 없이 최근 로그 확인
+//!
+//! • 가득 차면 가장 오래된 이벤트부터 버림 (메모리 사용량 고정)
+//! • 어떤 레벨까지 남길지는 `with_filter`로 이 레이어에만 따로 지정
+
+use serde::Serialize;
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::{
+    field::{Field, Visit},
+    Subscriber,
+};
+use tracing_subscriber::{layer::Context, Layer};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Event {
+    /// `HH:MM:SS` (UTC)
+    pub time: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    /// message 외의 필드 (`key=value` 공백 구분)
+    pub fields: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct EventLog {
+    events: Arc<Mutex<VecDeque<Event>>>,
+    capacity: usize,
+}
+
+impl EventLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            events: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// 최신 이벤트가 앞에 오도록 반환
+    pub fn recent(&self) -> Vec<Event> {
+        self.events.lock().unwrap().iter().rev().cloned().collect()
+    }
+
+    fn push(&self, event: Event) {
+        let mut events = self.events.lock().unwrap();
+        if events.len() == self.capacity {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+}
+
+impl<S: Subscriber> Layer<S> for EventLog {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+
+        let metadata = event.metadata();
+        self.push(Event {
+            time: clock_time(SystemTime::now()),
+            level: metadata.level().to_string(),
+            target: metadata.target().to_owned(),
+            message: visitor.message,
+            fields: visitor.fields.join(" "),
+        });
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: Vec<String>,
+}
+
+impl Visit for FieldVisitor {
+    // 문자열은 따옴표 없이 그대로
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_owned();
+        } else {
+            self.fields.push(format!("{}={value}", field.name()));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+        } else {
+            self.fields.push(format!("{}={value:?}", field.name()));
+        }
+    }
+}
+
+// 날짜 계산 라이브러리 없이 하루 안의 시각만 표시
+fn clock_time(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        % 86_400;
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn capture(log: &EventLog, f: impl FnOnce()) {
+        let subscriber = tracing_subscriber::registry().with(log.clone());
+        tracing::subscriber::with_default(subscriber, f);
+    }
+
+    #[test]
+    fn keeps_only_the_most_recent_events() {
+        let log = EventLog::new(2);
+        capture(&log, || {
+            for i in 0..3 {
+                tracing::info!("event {i}");
+            }
+        });
+
+        let messages: Vec<_> = log.recent().into_iter().map(|e| e.message).collect();
+        assert_eq!(messages, ["event 2", "event 1"]);
+    }
+
+    #[test]
+    fn records_level_target_and_fields() {
+        let log = EventLog::new(10);
+        capture(&log, || {
+            tracing::warn!(target: "admin", user = "alice", attempts = 3, "login failed");
+        });
+
+        let event = &log.recent()[0];
+        assert_eq!(event.level, "WARN");
+        assert_eq!(event.target, "admin");
+        assert_eq!(event.message, "login failed");
+        assert_eq!(event.fields, "user=alice attempts=3");
+    }
+
+    #[test]
+    fn formats_time_of_day() {
+        let time = UNIX_EPOCH + Duration::from_secs(3 * 86_400 + 13 * 3600 + 5 * 60 + 9);
+        assert_eq!(clock_time(time), "13:05:09");
+    }
+}
@@ -0,0 +1,52 @@
+//! 🚩 실행 중에 켜고 끌 수 있는 기능 플래그
+//!
+//! 재시작하면 기본값으로 돌아감 (여러 인스턴스가 공유하려면 DB나 Redis에 저장)
+
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Flag {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Flags {
+    // 대시보드에 항상 같은 순서로 보이도록 BTreeMap
+    flags: Arc<RwLock<BTreeMap<&'static str, Flag>>>,
+}
+
+impl Flags {
+    pub fn new(flags: impl IntoIterator<Item = Flag>) -> Self {
+        let flags = flags.into_iter().map(|flag| (flag.name, flag)).collect();
+        Self {
+            flags: Arc::new(RwLock::new(flags)),
+        }
+    }
+
+    /// 등록되지 않은 플래그는 꺼진 것으로 봄
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.flags
+            .read()
+            .unwrap()
+            .get(name)
+            .is_some_and(|flag| flag.enabled)
+    }
+
+    /// 바뀐 값을 반환 (등록되지 않은 플래그면 `None`)
+    pub fn toggle(&self, name: &str) -> Option<bool> {
+        let mut flags = self.flags.write().unwrap();
+        let flag = flags.get_mut(name)?;
+        flag.enabled = !flag.enabled;
+        Some(flag.enabled)
+    }
+
+    pub fn all(&self) -> Vec<Flag> {
+        self.flags.read().unwrap().values().cloned().collect()
+    }
+}
@@ -0,0 +1,371 @@
+//! 관리자 대시보드 예제
+//!
+//! 별도의 모니터링 도구 없이 서버 안에서 현재 상태를 HTML 페이지로 확인하고 조작
+//!
+//! • 등록된 라우트별 요청 수, 에러 수, 평균 처리 시간 (`stats.rs`)
+//! • 최근 tracing 이벤트 (링 버퍼 레이어, `events.rs`)
+//! • 기능 플래그 켜기/끄기 → 공개 API 동작이 바로 바뀜 (`flags.rs`)
+//!
+//! 대시보드는 `/admin` 하위에 있고 Bearer 토큰으로 보호됨 (`admin.rs`)
+//!
+//! ```not_rust
+//! cargo run -p example-admin-dashboard
+//! ```
+
+mod admin;
+mod events;
+mod flags;
+mod stats;
+
+use axum::{
+    extract::{Path, Request, State},
+    http::StatusCode,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use events::EventLog;
+use flags::{Flag, Flags};
+use minijinja::Environment;
+use serde::Serialize;
+use stats::RequestStats;
+use std::sync::Arc;
+use tracing_subscriber::{
+    filter::LevelFilter, layer::SubscriberExt, util::SubscriberInitExt, Layer,
+};
+
+/// 대시보드에 표시할 라우트 목록 (메서드, 라우트 패턴)
+///
+/// axum `Router`는 등록된 경로 목록을 알려주지 않으므로 직접 관리
+/// → `app`에 라우트를 추가하면 여기에도 추가 (테스트로 확인)
+pub const ROUTES: &[(&str, &str)] = &[
+    ("GET", "/"),
+    ("GET", "/users"),
+    ("GET", "/users/{id}"),
+    ("GET", "/admin"),
+    ("GET", "/admin/login"),
+    ("POST", "/admin/login"),
+    ("POST", "/admin/flags/{name}/toggle"),
+];
+
+const MAINTENANCE_MODE: &str = "maintenance_mode";
+const NEW_GREETING: &str = "new_greeting";
+
+/// 📦 앱 상태 (모두 Arc로 감싸 Clone 비용이 작음)
+#[derive(Clone)]
+pub struct AppState {
+    flags: Flags,
+    stats: RequestStats,
+    events: EventLog,
+    templates: Arc<Environment<'static>>,
+    sessions: admin::Sessions,
+}
+
+impl AppState {
+    fn new(events: EventLog) -> Self {
+        Self {
+            flags: Flags::new([
+                Flag {
+                    name: MAINTENANCE_MODE,
+                    description: "공개 API(/users)를 503으로 막음",
+                    enabled: false,
+                },
+                Flag {
+                    name: NEW_GREETING,
+                    description: "`/`에서 새 인사말 사용",
+                    enabled: false,
+                },
+            ]),
+            stats: RequestStats::default(),
+            events,
+            templates: Arc::new(admin::templates()),
+            sessions: admin::Sessions::default(),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    // 최근 INFO 이상 이벤트 200개를 대시보드용으로 보관
+    let events = EventLog::new(200);
+
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .with(events.clone().with_filter(LevelFilter::INFO))
+        .init();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::info!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(AppState::new(events)))
+        .await
+        .unwrap();
+}
+
+fn app(state: AppState) -> Router {
+    // 🌐 공개 API (점검 모드면 503)
+    let users = Router::new()
+        .route("/users", get(list_users))
+        .route("/users/{id}", get(get_user))
+        .route_layer(middleware::from_fn_with_state(state.clone(), maintenance));
+
+    Router::new()
+        .route("/", get(root))
+        .merge(users)
+        .nest("/admin", admin::router(state.sessions.clone()))
+        // 모든 라우트(관리자 포함)의 요청 수를 셈 (매칭되지 않은 404는 제외)
+        .route_layer(middleware::from_fn_with_state(
+            state.stats.clone(),
+            stats::track,
+        ))
+        .with_state(state)
+}
+
+// --- 🚏 공개 API 핸들러
+
+async fn root(State(state): State<AppState>) -> &'static str {
+    if state.flags.is_enabled(NEW_GREETING) {
+        "Welcome back! ✨\n"
+    } else {
+        "Hello, World!\n"
+    }
+}
+
+#[derive(Clone, Serialize)]
+struct User {
+    id: u32,
+    name: &'static str,
+}
+
+const USERS: &[User] = &[
+    User {
+        id: 1,
+        name: "alice",
+    },
+    User { id: 2, name: "bob" },
+];
+
+async fn list_users() -> Json<&'static [User]> {
+    Json(USERS)
+}
+
+async fn get_user(Path(id): Path<u32>) -> Result<Json<User>, StatusCode> {
+    USERS
+        .iter()
+        .find(|user| user.id == id)
+        .cloned()
+        .map(Json)
+        .ok_or_else(|| {
+            tracing::warn!(id, "user not found");
+            StatusCode::NOT_FOUND
+        })
+}
+
+async fn maintenance(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    if state.flags.is_enabled(MAINTENANCE_MODE) {
+        return (StatusCode::SERVICE_UNAVAILABLE, "under maintenance\n").into_response();
+    }
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header, Method},
+    };
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    fn bearer() -> String {
+        format!("Bearer {}", admin::ADMIN_TOKEN)
+    }
+
+    async fn send(app: &Router, request: Request) -> (StatusCode, String) {
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    fn get(uri: &str) -> Request {
+        Request::get(uri).body(Body::empty()).unwrap()
+    }
+
+    fn admin(method: Method, uri: &str) -> Request {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::AUTHORIZATION, bearer())
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn every_listed_route_is_registered() {
+        let app = app(AppState::new(EventLog::new(10)));
+        for (method, path) in ROUTES {
+            let uri = path.replace("{id}", "1").replace("{name}", "x");
+            let request = Request::builder()
+                .method(*method)
+                .uri(&uri)
+                .body(Body::empty())
+                .unwrap();
+            let (status, _) = send(&app, request).await;
+            assert_ne!(status, StatusCode::NOT_FOUND, "{method} {uri}");
+            assert_ne!(status, StatusCode::METHOD_NOT_ALLOWED, "{method} {uri}");
+        }
+    }
+
+    #[tokio::test]
+    async fn admin_requires_token() {
+        let app = app(AppState::new(EventLog::new(10)));
+
+        let (status, _) = send(&app, get("/admin")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let request = Request::get("/admin")
+            .header(header::AUTHORIZATION, "Bearer wrong")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(send(&app, request).await.0, StatusCode::UNAUTHORIZED);
+
+        let (status, body) = send(&app, admin(Method::GET, "/admin")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("Admin Dashboard"));
+    }
+
+    #[tokio::test]
+    async fn login_sets_a_cookie_that_opens_the_dashboard() {
+        let app = app(AppState::new(EventLog::new(10)));
+        let login = |token: &str| {
+            Request::post("/admin/login")
+                .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .body(Body::from(format!("token={token}")))
+                .unwrap()
+        };
+
+        let (status, body) = send(&app, login("wrong")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert!(body.contains("invalid token"));
+
+        let response = app
+            .clone()
+            .oneshot(login(admin::ADMIN_TOKEN))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        let cookie = response.headers()[header::SET_COOKIE].to_str().unwrap();
+        assert!(cookie.contains("HttpOnly"));
+        // 쿠키에는 토큰이 아니라 세션 ID
+        assert!(!cookie.contains(admin::ADMIN_TOKEN));
+
+        let with_cookie = |cookie: &str| {
+            Request::get("/admin")
+                .header(header::COOKIE, format!("theme=dark; {cookie}"))
+                .body(Body::empty())
+                .unwrap()
+        };
+        let name_value = cookie.split(';').next().unwrap();
+        assert_eq!(send(&app, with_cookie(name_value)).await.0, StatusCode::OK);
+
+        // 토큰을 쿠키에 직접 넣거나 발급하지 않은 세션 ID는 거부
+        let forged = format!("admin_session={}", admin::ADMIN_TOKEN);
+        assert_eq!(
+            send(&app, with_cookie(&forged)).await.0,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn dashboard_shows_request_counters() {
+        let state = AppState::new(EventLog::new(10));
+        let app = app(state.clone());
+        send(&app, get("/users/1")).await;
+        send(&app, get("/users/2")).await;
+        send(&app, get("/users/99")).await;
+        // 매칭되지 않은 요청은 세지 않음
+        send(&app, get("/nope")).await;
+
+        // 경로 파라미터가 달라도 같은 라우트로 집계
+        let stats = state.stats.get("GET", "/users/{id}");
+        assert_eq!((stats.requests, stats.errors), (3, 1));
+
+        let (_, body) = send(&app, admin(Method::GET, "/admin")).await;
+        // 자동 escape로 `/`는 `&#x2f;`로 출력됨
+        assert!(body.contains("<code>&#x2f;users&#x2f;{id}</code>"));
+        assert!(body.contains("Routes (3 requests)"));
+    }
+
+    #[tokio::test]
+    async fn toggling_a_flag_changes_behavior_and_is_logged() {
+        let events = EventLog::new(10);
+        let subscriber = tracing_subscriber::registry().with(events.clone());
+        let _guard = tracing::subscriber::set_default(subscriber);
+        let app = app(AppState::new(events.clone()));
+
+        assert_eq!(send(&app, get("/users")).await.0, StatusCode::OK);
+
+        let response = app
+            .clone()
+            .oneshot(admin(Method::POST, "/admin/flags/maintenance_mode/toggle"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(response.headers()[header::LOCATION], "/admin");
+
+        assert_eq!(
+            send(&app, get("/users")).await,
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "under maintenance\n".to_owned()
+            )
+        );
+        // 점검 모드와 상관없는 경로는 그대로
+        assert_eq!(send(&app, get("/")).await.0, StatusCode::OK);
+
+        let event = &events.recent()[0];
+        assert_eq!(event.message, "feature flag toggled");
+        assert_eq!(event.fields, "flag=maintenance_mode enabled=true");
+
+        // 대시보드에 이벤트와 플래그 상태가 표시됨
+        let (_, body) = send(&app, admin(Method::GET, "/admin")).await;
+        assert!(body.contains("feature flag toggled"));
+        assert!(body.contains(">Disable<"));
+
+        let (status, _) = send(&app, admin(Method::POST, "/admin/flags/nope/toggle")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn event_messages_are_escaped() {
+        let events = EventLog::new(10);
+        let subscriber = tracing_subscriber::registry().with(events.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!("<script>alert(1)</script>");
+        });
+        let app = app(AppState::new(events));
+
+        let (_, body) = send(&app, admin(Method::GET, "/admin")).await;
+        assert!(!body.contains("<script>"));
+        assert!(body.contains("&lt;script&gt;"));
+    }
+}
+
+// 🧪 테스트 방법
+//
+// 브라우저: http://localhost:3000/admin/login 에서 `secret-token` 입력 → 대시보드로 이동
+//
+// > curl http://localhost:3000/admin -H "Authorization: Bearer secret-token"
+//
+// > curl -X POST http://localhost:3000/admin/flags/maintenance_mode/toggle \
+// >   -H "Authorization: Bearer secret-token"
+// > curl -i http://localhost:3000/users
+// → 503 under maintenance
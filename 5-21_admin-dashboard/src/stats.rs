@@ -0,0 +1,81 @@
+//! 📊 경로별 요청 카운터
+//!
+//! `route_layer`로 등록해 라우터에 매칭된 요청만 셈 → 키는 실제 URI가 아니라
+//! 라우트 패턴(`/users/{id}`)이므로 경로 파라미터가 달라도 항목이 늘어나지 않음
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct RouteStats {
+    pub requests: u64,
+    /// 4xx, 5xx 응답 수
+    pub errors: u64,
+    #[serde(skip)]
+    total_latency: Duration,
+}
+
+impl RouteStats {
+    /// 평균 처리 시간 (밀리초)
+    pub fn avg_ms(&self) -> f64 {
+        if self.requests == 0 {
+            return 0.0;
+        }
+        self.total_latency.as_secs_f64() * 1000.0 / self.requests as f64
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RequestStats {
+    // (메서드, 라우트 패턴) → 통계
+    routes: Arc<Mutex<HashMap<(String, String), RouteStats>>>,
+}
+
+impl RequestStats {
+    pub fn get(&self, method: &str, path: &str) -> RouteStats {
+        self.routes
+            .lock()
+            .unwrap()
+            .get(&(method.to_owned(), path.to_owned()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn record(&self, method: String, path: String, is_error: bool, latency: Duration) {
+        let mut routes = self.routes.lock().unwrap();
+        let stats = routes.entry((method, path)).or_default();
+        stats.requests += 1;
+        stats.errors += u64::from(is_error);
+        stats.total_latency += latency;
+    }
+}
+
+/// 요청 수, 에러 수, 처리 시간을 기록하는 미들웨어
+pub async fn track(
+    State(stats): State<RequestStats>,
+    path: MatchedPath,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().to_string();
+    let started = Instant::now();
+    let response = next.run(request).await;
+
+    let status = response.status();
+    stats.record(
+        method,
+        path.as_str().to_owned(),
+        status.is_client_error() || status.is_server_error(),
+        started.elapsed(),
+    );
+    response
+}
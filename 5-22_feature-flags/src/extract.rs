@@ -0,0 +1,117 @@
+//! 🧲 핸들러에서 쓰는 `Flags` 추출기
+//!
+//! ```rust,ignore
+//! async fn handler(flags: Flags) -> String {
+//!     if flags.enabled("new_ui") { .. } else { .. }
+//! }
+//! ```
+//!
+//! 부분 배포에 쓸 사용자 ID는 다음 순서로 찾음
+//!
+//! • `Authorization: Bearer <JWT>`의 `sub` claim (서명과 만료를 검증한 경우만)
+//! • `uid` 쿠키 (로그인 전 방문자를 구분하는 용도)
+//! • 둘 다 없으면 익명 → 전체 배포(`rollout` 100)된 플래그만 켜짐
+
+use axum::{
+    extract::{FromRef, FromRequestParts},
+    http::{
+        header::{AUTHORIZATION, COOKIE},
+        request::Parts,
+    },
+};
+use jsonwebtoken::{DecodingKey, Validation};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, convert::Infallible, sync::Arc};
+
+use crate::store::{evaluate, FlagSet, FlagStore};
+
+const USER_COOKIE: &str = "uid";
+
+/// JWT 검증 키 (HS256)
+#[derive(Clone)]
+pub struct JwtKey(pub DecodingKey);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub exp: u64,
+}
+
+/// 요청 하나 동안 사용할 플래그 스냅샷 + 사용자
+///
+/// 요청 도중 파일이 다시 읽혀도 같은 요청 안에서는 결과가 바뀌지 않음
+pub struct Flags {
+    set: Arc<FlagSet>,
+    user_id: Option<String>,
+}
+
+impl Flags {
+    /// 등록되지 않은 플래그는 꺼진 것으로 봄 (파일에서 지워도 안전)
+    pub fn enabled(&self, name: &str) -> bool {
+        self.set
+            .get(name)
+            .is_some_and(|flag| evaluate(name, flag, self.user_id.as_deref()))
+    }
+
+    pub fn user_id(&self) -> Option<&str> {
+        self.user_id.as_deref()
+    }
+
+    /// 모든 플래그의 평가 결과 (디버깅, 프론트엔드 전달용)
+    pub fn all(&self) -> BTreeMap<String, bool> {
+        self.set
+            .keys()
+            .map(|name| (name.clone(), self.enabled(name)))
+            .collect()
+    }
+}
+
+// 사용자를 못 찾아도 실패하지 않음 (익명으로 평가)
+impl<S> FromRequestParts<S> for Flags
+where
+    FlagStore: FromRef<S>,
+    JwtKey: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let key = JwtKey::from_ref(state);
+        let user_id = jwt_subject(parts, &key).or_else(|| cookie_user(parts));
+
+        Ok(Self {
+            set: FlagStore::from_ref(state).snapshot(),
+            user_id,
+        })
+    }
+}
+
+fn jwt_subject(parts: &Parts, key: &JwtKey) -> Option<String> {
+    let token = parts
+        .headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")?;
+
+    match jsonwebtoken::decode::<Claims>(token, &key.0, &Validation::default()) {
+        Ok(data) => Some(data.claims.sub),
+        Err(err) => {
+            // 인증은 이 추출기의 역할이 아니므로 거절하지 않고 익명으로 처리
+            tracing::debug!(%err, "ignoring invalid token");
+            None
+        }
+    }
+}
+
+fn cookie_user(parts: &Parts) -> Option<String> {
+    parts
+        .headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(name, value)| *name == USER_COOKIE && !value.is_empty())
+        .map(|(_, value)| value.to_owned())
+}
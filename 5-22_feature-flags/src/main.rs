@@ -0,0 +1,278 @@
+//! 기능 플래그 예제
+//!
+//! 배포와 기능 공개를 분리: 코드는 먼저 배포해 두고, 플래그 파일만 고쳐서
+//! 일부 사용자에게 먼저 열어 보거나 문제가 생기면 바로 끔 (서버 재시작 없음)
+//!
+//! • `store.rs`: 플래그 저장소 (메모리 / JSON 파일 + hot reload), 부분 배포 계산
+//! • `extract.rs`: 핸들러에서 `flags.enabled("new_ui")`로 쓰는 `Flags` 추출기
+//!
+//! `FLAGS_FILE`을 지정하면 그 파일을 읽고 변경을 감시, 없으면 바이너리에 포함된
+//! `flags.json`을 고정값으로 사용
+//!
+//! ```not_rust
+//! FLAGS_FILE=flags.json cargo run -p example-feature-flags
+//! ```
+
+mod extract;
+mod store;
+
+use axum::{
+    extract::{FromRef, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use extract::{Claims, Flags, JwtKey};
+use jsonwebtoken::{DecodingKey, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use store::FlagStore;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+/// 📦 앱 상태 (`Flags` 추출기는 `FromRef`로 저장소와 키를 꺼내 씀)
+#[derive(Clone, FromRef)]
+struct AppState {
+    flags: FlagStore,
+    jwt: JwtKey,
+    // 데모용 토큰 발급 (`POST /login`)
+    encoding: EncodingKey,
+}
+
+impl AppState {
+    fn new(flags: FlagStore, secret: &[u8]) -> Self {
+        Self {
+            flags,
+            jwt: JwtKey(DecodingKey::from_secret(secret)),
+            encoding: EncodingKey::from_secret(secret),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let flags = match std::env::var("FLAGS_FILE") {
+        Ok(path) => FlagStore::from_file(&path).unwrap_or_else(|err| panic!("{path}: {err}")),
+        // 파일을 지정하지 않으면 빌드할 때 포함된 flags.json을 고정값으로 사용
+        Err(_) => {
+            FlagStore::in_memory(serde_json::from_str(include_str!("../flags.json")).unwrap())
+        }
+    };
+    let secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "secret".to_owned());
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(AppState::new(flags, secret.as_bytes())))
+        .await
+        .unwrap();
+}
+
+fn app(state: AppState) -> Router {
+    Router::new()
+        .route("/", get(home))
+        .route("/checkout", get(checkout))
+        .route("/flags", get(evaluated_flags))
+        .route("/login", post(login))
+        .with_state(state)
+}
+
+// --- 🚏 핸들러
+
+async fn home(flags: Flags) -> String {
+    let user = flags.user_id().unwrap_or("guest");
+    let mut page = if flags.enabled("new_ui") {
+        format!("✨ new UI — welcome, {user}\n")
+    } else {
+        format!("classic UI — hello, {user}\n")
+    };
+    if flags.enabled("holiday_banner") {
+        page.insert_str(0, "🎄 holiday sale! 🎄\n");
+    }
+    page
+}
+
+async fn checkout(flags: Flags) -> &'static str {
+    if flags.enabled("express_checkout") {
+        "express checkout (1 click)\n"
+    } else {
+        "standard checkout\n"
+    }
+}
+
+#[derive(Serialize)]
+struct EvaluatedFlags {
+    user_id: Option<String>,
+    flags: BTreeMap<String, bool>,
+}
+
+/// 현재 사용자 기준 평가 결과 (프론트엔드가 받아서 화면을 바꾸는 용도로도 사용)
+async fn evaluated_flags(flags: Flags) -> Json<EvaluatedFlags> {
+    Json(EvaluatedFlags {
+        user_id: flags.user_id().map(str::to_owned),
+        flags: flags.all(),
+    })
+}
+
+#[derive(Deserialize)]
+struct Login {
+    username: String,
+}
+
+/// 데모용: 비밀번호 없이 사용자 이름으로 1시간짜리 토큰 발급
+async fn login(
+    State(state): State<AppState>,
+    Json(login): Json<Login>,
+) -> Result<String, StatusCode> {
+    let exp = (SystemTime::now() + Duration::from_secs(3600))
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let claims = Claims {
+        sub: login.username,
+        exp,
+    };
+    jsonwebtoken::encode(&Header::default(), &claims, &state.encoding)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header, Request},
+    };
+    use http_body_util::BodyExt;
+    use store::FlagConfig;
+    use tower::ServiceExt;
+
+    const SECRET: &[u8] = b"test-secret";
+
+    fn app() -> Router {
+        let flags = [
+            ("new_ui", true, 50),
+            ("express_checkout", true, 100),
+            ("holiday_banner", false, 100),
+        ]
+        .into_iter()
+        .map(|(name, enabled, rollout)| (name.to_owned(), FlagConfig { enabled, rollout }))
+        .collect();
+        super::app(AppState::new(FlagStore::in_memory(flags), SECRET))
+    }
+
+    async fn send(app: &Router, request: Request<Body>) -> String {
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    fn get(uri: &str, header: Option<(header::HeaderName, String)>) -> Request<Body> {
+        let mut request = Request::get(uri);
+        if let Some((name, value)) = header {
+            request = request.header(name, value);
+        }
+        request.body(Body::empty()).unwrap()
+    }
+
+    fn cookie(uid: &str) -> Option<(header::HeaderName, String)> {
+        Some((header::COOKIE, format!("theme=dark; uid={uid}")))
+    }
+
+    fn bearer(token: &str) -> Option<(header::HeaderName, String)> {
+        Some((header::AUTHORIZATION, format!("Bearer {token}")))
+    }
+
+    async fn token(app: &Router, username: &str) -> String {
+        let request = Request::post("/login")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(format!(r#"{{"username":"{username}"}}"#)))
+            .unwrap();
+        send(app, request).await
+    }
+
+    #[tokio::test]
+    async fn partial_rollout_follows_the_cookie_user() {
+        let app = app();
+        // new_ui 50%: bob은 29번 구간(켜짐), alice는 98번 구간(꺼짐)
+        assert_eq!(
+            send(&app, get("/", cookie("bob"))).await,
+            "✨ new UI — welcome, bob\n"
+        );
+        assert_eq!(
+            send(&app, get("/", cookie("alice"))).await,
+            "classic UI — hello, alice\n"
+        );
+        // 전체 배포된 플래그는 익명 사용자도 켜짐
+        assert_eq!(
+            send(&app, get("/", None)).await,
+            "classic UI — hello, guest\n"
+        );
+        assert_eq!(
+            send(&app, get("/checkout", None)).await,
+            "express checkout (1 click)\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn jwt_subject_takes_precedence_over_cookie() {
+        let app = app();
+        let token = token(&app, "bob").await;
+
+        let request = Request::get("/flags")
+            .header(header::AUTHORIZATION, format!("Bearer {token}"))
+            .header(header::COOKIE, "uid=alice")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(
+            send(&app, request).await,
+            r#"{"user_id":"bob","flags":{"express_checkout":true,"holiday_banner":false,"new_ui":true}}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_token_is_treated_as_anonymous() {
+        let app = app();
+        let forged = jsonwebtoken::encode(
+            &Header::default(),
+            &Claims {
+                sub: "bob".to_owned(),
+                exp: u64::MAX,
+            },
+            &EncodingKey::from_secret(b"other-secret"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            send(&app, get("/", bearer(&forged))).await,
+            "classic UI — hello, guest\n"
+        );
+        assert_eq!(
+            send(&app, get("/", bearer("not-a-jwt"))).await,
+            "classic UI — hello, guest\n"
+        );
+    }
+}
+
+// 🧪 테스트 방법
+//
+// > curl localhost:3000/ -b uid=bob      → new UI (new_ui 50% 구간 안)
+// > curl localhost:3000/ -b uid=alice    → classic UI
+//
+// > TOKEN=$(curl -s localhost:3000/login -H 'content-type: application/json' -d '{"username":"bob"}')
+// > curl localhost:3000/flags -H "Authorization: Bearer $TOKEN"
+//
+// FLAGS_FILE로 실행했다면 서버를 켜 둔 채 flags.json의 rollout이나 enabled를 바꾸면
+// 다음 요청부터 바로 반영
@@ -0,0 +1,255 @@
+//! 🚩 기능 플래그 저장소
+//!
+//! • `FlagStore::in_memory`: 코드로 정한 플래그 (테스트, 설정 파일 없는 환경)
+//! • `FlagStore::from_file`: JSON 파일에서 읽고, 파일이 바뀌면 서버 재시작 없이 다시 읽음
+//!
+//! 파일 형식 (`rollout`을 생략하면 100 → 켜져 있으면 모두에게 적용)
+//!
+//! ```json
+//! { "new_ui": { "enabled": true, "rollout": 50 } }
+//! ```
+//!
+//! 부분 배포(`rollout` < 100)는 `플래그 이름 + 사용자 ID`의 해시로 0~99 구간을 정해
+//! 그 값이 `rollout`보다 작은 사용자에게만 켬 → 같은 사용자는 항상 같은 결과를 받고,
+//! 비율을 올리면 이미 켜진 사용자는 그대로 유지됨
+
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct FlagConfig {
+    pub enabled: bool,
+    /// 켤 사용자 비율 (0~100, 100보다 크면 100으로 봄)
+    #[serde(default = "full_rollout")]
+    pub rollout: u8,
+}
+
+fn full_rollout() -> u8 {
+    100
+}
+
+/// 한 시점의 플래그 전체 (요청 하나는 처음 받은 스냅샷으로 끝까지 평가)
+pub type FlagSet = HashMap<String, FlagConfig>;
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    Parse(serde_json::Error),
+    Watch(notify::Error),
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Io(err) => write!(f, "failed to read flag file: {err}"),
+            LoadError::Parse(err) => write!(f, "invalid flag file: {err}"),
+            LoadError::Watch(err) => write!(f, "failed to watch flag file: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// 📦 상태에 넣어 공유하는 저장소 (Clone 비용은 Arc 복사뿐)
+#[derive(Clone)]
+pub struct FlagStore {
+    flags: Arc<RwLock<Arc<FlagSet>>>,
+    // 저장소가 살아 있는 동안 감시를 유지하기 위해 보관 (drop되면 감시 중단)
+    _watcher: Option<Arc<notify::RecommendedWatcher>>,
+}
+
+impl FlagStore {
+    pub fn in_memory(flags: FlagSet) -> Self {
+        Self {
+            flags: Arc::new(RwLock::new(Arc::new(flags))),
+            _watcher: None,
+        }
+    }
+
+    /// 🔄 파일에서 읽고 변경 감시
+    ///
+    /// 처음 읽기에 실패하면 에러, 실행 중 잘못된 내용으로 바뀌면 이전 플래그를 계속 사용
+    pub fn from_file(path: impl Into<PathBuf>) -> Result<Self, LoadError> {
+        use notify::{RecursiveMode, Watcher};
+
+        let path = path.into();
+        let flags = Arc::new(RwLock::new(Arc::new(load(&path)?)));
+
+        let watched = Arc::clone(&flags);
+        let file = path.clone();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                match event {
+                    // 파일을 읽기만 한 이벤트나 다른 파일의 이벤트는 무시
+                    Ok(event) if event.kind.is_access() => {}
+                    Ok(event) if !event.paths.iter().any(|p| p.ends_with(&file)) => {}
+                    Ok(_) => match load(&file) {
+                        Ok(set) => {
+                            tracing::info!(path = %file.display(), count = set.len(), "flags reloaded");
+                            *watched.write().unwrap() = Arc::new(set);
+                        }
+                        Err(err) => tracing::error!(%err, "keeping previous flags"),
+                    },
+                    Err(err) => tracing::error!(%err, "flag watcher error"),
+                }
+            })
+            .map_err(LoadError::Watch)?;
+        // 편집기는 파일을 새로 만들어 바꿔치기하는 경우가 많아 파일 대신 디렉터리를 감시
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(LoadError::Watch)?;
+
+        Ok(Self {
+            flags,
+            _watcher: Some(Arc::new(watcher)),
+        })
+    }
+
+    /// 현재 플래그 스냅샷
+    pub fn snapshot(&self) -> Arc<FlagSet> {
+        Arc::clone(&self.flags.read().unwrap())
+    }
+}
+
+fn load(path: &Path) -> Result<FlagSet, LoadError> {
+    let json = std::fs::read_to_string(path).map_err(LoadError::Io)?;
+    serde_json::from_str(&json).map_err(LoadError::Parse)
+}
+
+/// 플래그 하나를 사용자 기준으로 평가
+///
+/// 사용자를 알 수 없으면(`None`) 전체 배포된 플래그만 켜짐
+pub fn evaluate(name: &str, flag: &FlagConfig, user_id: Option<&str>) -> bool {
+    if !flag.enabled {
+        return false;
+    }
+    match (flag.rollout, user_id) {
+        (100.., _) => true,
+        (0, _) | (_, None) => false,
+        (rollout, Some(user_id)) => bucket(name, user_id) < rollout,
+    }
+}
+
+/// `플래그:사용자`를 0~99 구간에 배정
+///
+/// 표준 라이브러리의 `DefaultHasher`는 Rust 버전에 따라 결과가 바뀔 수 있어
+/// 배포 후에도 같은 값을 내는 FNV-1a를 직접 구현
+fn bucket(name: &str, user_id: &str) -> u8 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let hash = name
+        .bytes()
+        .chain([b':'])
+        .chain(user_id.bytes())
+        .fold(OFFSET, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        });
+    (hash % 100) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn flag(enabled: bool, rollout: u8) -> FlagConfig {
+        FlagConfig { enabled, rollout }
+    }
+
+    #[test]
+    fn rollout_is_stable_and_roughly_proportional() {
+        let half = flag(true, 50);
+        let users: Vec<String> = (0..2000).map(|i| format!("user-{i}")).collect();
+
+        let enabled = users
+            .iter()
+            .filter(|user| evaluate("new_ui", &half, Some(user)))
+            .count();
+        assert!(
+            (900..1100).contains(&enabled),
+            "enabled for {enabled} users"
+        );
+
+        // 재시작이나 Rust 버전 업데이트 후에도 같은 사용자는 같은 구간
+        assert_eq!(bucket("new_ui", "alice"), 98);
+        assert_eq!(bucket("new_ui", "bob"), 29);
+        // 플래그마다 구간이 달라 같은 사용자들이 모든 실험에 먼저 걸리지 않음
+        assert_eq!(bucket("express_checkout", "bob"), 9);
+
+        // 비율을 올려도 이미 켜진 사용자는 그대로
+        let more = flag(true, 80);
+        assert!(users
+            .iter()
+            .filter(|user| evaluate("new_ui", &half, Some(user)))
+            .all(|user| evaluate("new_ui", &more, Some(user))));
+    }
+
+    #[test]
+    fn disabled_and_anonymous() {
+        assert!(!evaluate("f", &flag(false, 100), Some("alice")));
+        assert!(evaluate("f", &flag(true, 100), None));
+        assert!(!evaluate("f", &flag(true, 99), None));
+        assert!(!evaluate("f", &flag(true, 0), Some("alice")));
+    }
+
+    fn temp_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "example-feature-flags-{}-{name}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("flags.json")
+    }
+
+    async fn wait_for(store: &FlagStore, f: impl Fn(&FlagSet) -> bool) -> bool {
+        for _ in 0..100 {
+            if f(&store.snapshot()) {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        false
+    }
+
+    #[tokio::test]
+    async fn file_changes_are_reloaded() {
+        let path = temp_file("reload");
+        std::fs::write(&path, r#"{ "new_ui": { "enabled": false } }"#).unwrap();
+
+        let store = FlagStore::from_file(&path).unwrap();
+        assert_eq!(store.snapshot()["new_ui"], flag(false, 100));
+
+        std::fs::write(&path, r#"{ "new_ui": { "enabled": true, "rollout": 30 } }"#).unwrap();
+        assert!(wait_for(&store, |set| set["new_ui"] == flag(true, 30)).await);
+
+        // 잘못된 내용으로 바뀌면 이전 플래그 유지
+        std::fs::write(&path, "{ not json").unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(store.snapshot()["new_ui"], flag(true, 30));
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn invalid_file_fails_on_startup() {
+        let path = temp_file("invalid");
+        std::fs::write(&path, r#"{ "new_ui": { "rollout": 30 } }"#).unwrap();
+        assert!(matches!(
+            FlagStore::from_file(&path),
+            Err(LoadError::Parse(_))
+        ));
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        assert!(matches!(FlagStore::from_file(&path), Err(LoadError::Io(_))));
+    }
+}
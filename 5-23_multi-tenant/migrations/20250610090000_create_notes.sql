@@ -0,0 +1,5 @@
+-- 테넌트마다 별도 DB이므로 tenant_id 컬럼이 필요 없음
+CREATE TABLE notes (
+    id INTEGER PRIMARY KEY,
+    body TEXT NOT NULL
+);
@@ -0,0 +1,262 @@
+//! 멀티 테넌트 예제
+//!
+//! 하나의 서버가 여러 고객사(테넌트)를 서비스하면서 데이터는 테넌트마다 분리
+//!
+//! • 미들웨어가 서브도메인 또는 `X-Tenant-Id` 헤더로 테넌트를 찾아 `Extension<Tenant>`로 전달
+//! • 테넌트마다 설정(요금제)과 DB 커넥션 풀이 따로 있음 → 핸들러는 `tenant.db`만 쓰면
+//!   다른 테넌트의 데이터에 접근할 방법이 없음 (`WHERE tenant_id = ?` 누락 걱정 없음)
+//! • 등록되지 않은 테넌트는 404
+//!
+//! 예제에서는 테넌트마다 SQLite 메모리 DB를 사용 (재시작하면 비워짐)
+//!
+//! ```not_rust
+//! cargo run -p example-multi-tenant
+//! ```
+
+mod tenant;
+
+use axum::{http::StatusCode, middleware, routing::get, Extension, Json, Router};
+use example_common_errors::ApiError;
+use serde::{Deserialize, Serialize};
+use tenant::{Plan, Tenant, TenantConfig, TenantRegistry};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    // `*.localhost`는 브라우저와 curl이 127.0.0.1로 연결 → DNS 설정 없이 서브도메인 테스트
+    let base_domain = std::env::var("BASE_DOMAIN").unwrap_or_else(|_| "localhost".to_owned());
+    let registry = TenantRegistry::new(&base_domain, demo_tenants())
+        .await
+        .expect("failed to set up tenants");
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(registry)).await.unwrap();
+}
+
+/// 실제 서비스에서는 관리용 DB나 설정 파일에서 읽음
+fn demo_tenants() -> Vec<TenantConfig> {
+    [
+        ("acme", "ACME Corp.", Plan::Pro),
+        ("globex", "Globex", Plan::Free),
+    ]
+    .into_iter()
+    .map(|(id, name, plan)| TenantConfig {
+        id: id.to_owned(),
+        display_name: name.to_owned(),
+        plan,
+        database_url: "sqlite::memory:".to_owned(),
+    })
+    .collect()
+}
+
+fn app(registry: TenantRegistry) -> Router {
+    // 🏢 테넌트가 필요한 라우트
+    let tenant_routes = Router::new()
+        .route("/", get(tenant_info))
+        .route("/notes", get(list_notes).post(create_note))
+        .route_layer(middleware::from_fn_with_state(
+            registry,
+            tenant::resolve_tenant,
+        ));
+
+    // 테넌트와 상관없는 라우트 (로드밸런서 헬스 체크 등)
+    Router::new()
+        .route("/health", get(|| async { "ok" }))
+        .merge(tenant_routes)
+}
+
+// --- 🚏 핸들러 (모두 `Extension<Tenant>`로 현재 테넌트를 받음)
+
+async fn tenant_info(Extension(tenant): Extension<Tenant>) -> Json<TenantConfig> {
+    Json(TenantConfig::clone(&tenant.config))
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+struct Note {
+    id: i64,
+    body: String,
+}
+
+async fn list_notes(Extension(tenant): Extension<Tenant>) -> Result<Json<Vec<Note>>, ApiError> {
+    let notes = sqlx::query_as("SELECT id, body FROM notes ORDER BY id")
+        .fetch_all(&tenant.db)
+        .await?;
+    Ok(Json(notes))
+}
+
+#[derive(Deserialize)]
+struct NewNote {
+    body: String,
+}
+
+async fn create_note(
+    Extension(tenant): Extension<Tenant>,
+    Json(new_note): Json<NewNote>,
+) -> Result<(StatusCode, Json<Note>), ApiError> {
+    // 요금제별 제한은 테넌트 설정에서 읽음
+    if let Some(limit) = tenant.config.plan.note_limit() {
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM notes")
+            .fetch_one(&tenant.db)
+            .await?;
+        if count >= limit {
+            return Err(ApiError::Validation(format!(
+                "the {:?} plan allows up to {limit} notes",
+                tenant.config.plan
+            )));
+        }
+    }
+
+    let note = sqlx::query_as("INSERT INTO notes (body) VALUES (?) RETURNING id, body")
+        .bind(new_note.body)
+        .fetch_one(&tenant.db)
+        .await?;
+    Ok((StatusCode::CREATED, Json(note)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header, Request},
+    };
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use tenant::TENANT_HEADER;
+    use tower::ServiceExt;
+
+    async fn app() -> Router {
+        let registry = TenantRegistry::new("example.com", demo_tenants())
+            .await
+            .unwrap();
+        super::app(registry)
+    }
+
+    async fn send(app: &Router, request: Request<Body>) -> (StatusCode, Value) {
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    fn get(host: &str, uri: &str) -> Request<Body> {
+        Request::get(uri)
+            .header(header::HOST, host)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    fn post_note(host: &str, body: &str) -> Request<Body> {
+        Request::post("/notes")
+            .header(header::HOST, host)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(json!({ "body": body }).to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn tenant_is_resolved_from_subdomain_or_header() {
+        let app = app().await;
+
+        let (status, body) = send(&app, get("acme.example.com:3000", "/")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            json!({ "id": "acme", "display_name": "ACME Corp.", "plan": "pro" })
+        );
+
+        let request = Request::get("/")
+            .header(header::HOST, "api.internal")
+            .header(TENANT_HEADER, "globex")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(send(&app, request).await.1["id"], "globex");
+
+        // 서브도메인이 있으면 헤더보다 우선
+        let request = Request::get("/")
+            .header(header::HOST, "acme.example.com")
+            .header(TENANT_HEADER, "globex")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(send(&app, request).await.1["id"], "acme");
+    }
+
+    #[tokio::test]
+    async fn unknown_or_missing_tenant_is_not_found() {
+        let app = app().await;
+
+        let (status, body) = send(&app, get("initech.example.com", "/notes")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"]["message"], "unknown tenant `initech`");
+
+        let (status, body) = send(&app, get("example.com", "/notes")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"]["message"], "no tenant in request");
+
+        // 테넌트가 필요 없는 경로
+        let response = app
+            .clone()
+            .oneshot(get("example.com", "/health"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn data_is_isolated_per_tenant() {
+        let app = app().await;
+
+        let (status, note) = send(&app, post_note("acme.example.com", "acme secret")).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(note, json!({ "id": 1, "body": "acme secret" }));
+
+        let (_, globex) = send(&app, get("globex.example.com", "/notes")).await;
+        assert_eq!(globex, json!([]));
+        let (_, acme) = send(&app, get("acme.example.com", "/notes")).await;
+        assert_eq!(acme, json!([{ "id": 1, "body": "acme secret" }]));
+    }
+
+    #[tokio::test]
+    async fn plan_limits_come_from_tenant_config() {
+        let app = app().await;
+
+        for i in 0..3 {
+            let (status, _) = send(&app, post_note("globex.example.com", &i.to_string())).await;
+            assert_eq!(status, StatusCode::CREATED);
+        }
+        let (status, body) = send(&app, post_note("globex.example.com", "one more")).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            body["error"]["message"],
+            "the Free plan allows up to 3 notes"
+        );
+
+        // Pro 요금제는 제한 없음
+        for i in 0..4 {
+            let (status, _) = send(&app, post_note("acme.example.com", &i.to_string())).await;
+            assert_eq!(status, StatusCode::CREATED);
+        }
+    }
+}
+
+// 🧪 테스트 방법
+//
+// > curl http://acme.localhost:3000/
+// > curl -X POST http://acme.localhost:3000/notes -H 'content-type: application/json' -d '{"body":"hi"}'
+// > curl http://globex.localhost:3000/notes             → [] (acme의 노트는 보이지 않음)
+// > curl http://localhost:3000/notes -H 'X-Tenant-Id: acme'
+// > curl -i http://initech.localhost:3000/notes         → 404
@@ -0,0 +1,166 @@
+//! 🏢 테넌트 설정과 조회
+//!
+//! • `TenantRegistry`: 테넌트 ID → 설정 + 전용 DB 커넥션 풀 (시작할 때 한 번 만듦)
+//! • `resolve_tenant`: 요청에서 테넌트를 찾아 `Extension<Tenant>`로 넣어 주는 미들웨어
+//!
+//! 테넌트는 다음 순서로 찾음
+//!
+//! • 서브도메인: `acme.localhost:3000` → `acme` (`base_domain`이 `localhost`일 때)
+//! • `X-Tenant-Id` 헤더: 서브도메인이 없을 때만 (API 게이트웨이 뒤, 로컬 테스트 등)
+//!
+//! 찾지 못하거나 등록되지 않은 테넌트면 핸들러까지 가지 않고 404
+
+use axum::{
+    extract::{Request, State},
+    http::header::HOST,
+    middleware::Next,
+    response::Response,
+};
+use example_common_errors::ApiError;
+use serde::Serialize;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+pub const TENANT_HEADER: &str = "x-tenant-id";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Plan {
+    Free,
+    Pro,
+}
+
+impl Plan {
+    /// 저장할 수 있는 노트 수 (`None`이면 제한 없음)
+    pub fn note_limit(self) -> Option<i64> {
+        match self {
+            Plan::Free => Some(3),
+            Plan::Pro => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TenantConfig {
+    pub id: String,
+    pub display_name: String,
+    pub plan: Plan,
+    #[serde(skip)]
+    pub database_url: String,
+}
+
+/// 핸들러가 `Extension<Tenant>`로 받는 현재 요청의 테넌트
+#[derive(Debug, Clone)]
+pub struct Tenant {
+    pub config: Arc<TenantConfig>,
+    pub db: SqlitePool,
+}
+
+#[derive(Debug, Clone)]
+pub struct TenantRegistry {
+    tenants: Arc<HashMap<String, Tenant>>,
+    base_domain: Arc<str>,
+}
+
+impl TenantRegistry {
+    /// 테넌트마다 DB에 연결하고 마이그레이션 실행
+    pub async fn new(
+        base_domain: &str,
+        configs: impl IntoIterator<Item = TenantConfig>,
+    ) -> Result<Self, sqlx::Error> {
+        let mut tenants = HashMap::new();
+        for config in configs {
+            let db = connect(&config.database_url).await?;
+            sqlx::migrate!().run(&db).await?;
+            tracing::debug!(tenant = config.id, "tenant database ready");
+
+            let config = Arc::new(config);
+            tenants.insert(config.id.clone(), Tenant { config, db });
+        }
+
+        Ok(Self {
+            tenants: Arc::new(tenants),
+            base_domain: base_domain.into(),
+        })
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Tenant> {
+        self.tenants.get(id)
+    }
+
+    /// 요청에서 테넌트 ID 추출 (등록 여부는 확인하지 않음)
+    fn tenant_id<'a>(&self, request: &'a Request) -> Option<&'a str> {
+        // HTTP/2는 Host 헤더 대신 URI에 authority가 들어옴
+        let host = request.uri().host().or_else(|| {
+            request
+                .headers()
+                .get(HOST)
+                .and_then(|value| value.to_str().ok())
+        });
+        host.and_then(|host| subdomain(host, &self.base_domain))
+            .or_else(|| {
+                request
+                    .headers()
+                    .get(TENANT_HEADER)
+                    .and_then(|value| value.to_str().ok())
+            })
+    }
+}
+
+// 메모리 DB는 연결마다 따로 생기므로 연결 하나만 계속 유지
+async fn connect(url: &str) -> Result<SqlitePool, sqlx::Error> {
+    let options = SqliteConnectOptions::from_str(url)?.create_if_missing(true);
+    let pool = if url.contains(":memory:") {
+        SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+    } else {
+        SqlitePoolOptions::new().max_connections(5)
+    };
+    pool.connect_with(options).await
+}
+
+/// `acme.example.com:3000` + `example.com` → `acme`
+///
+/// 서브도메인이 없거나 여러 단계(`a.b.example.com`)면 `None`
+fn subdomain<'a>(host: &'a str, base_domain: &str) -> Option<&'a str> {
+    let host = host.split(':').next()?;
+    let label = host.strip_suffix(base_domain)?.strip_suffix('.')?;
+    (!label.is_empty() && !label.contains('.')).then_some(label)
+}
+
+/// 테넌트를 찾아 request extension으로 넣는 미들웨어
+pub async fn resolve_tenant(
+    State(registry): State<TenantRegistry>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let id = registry
+        .tenant_id(&request)
+        .ok_or_else(|| ApiError::NotFound("no tenant in request".to_owned()))?;
+    let tenant = registry
+        .get(id)
+        .cloned()
+        .ok_or_else(|| ApiError::NotFound(format!("unknown tenant `{id}`")))?;
+
+    request.extensions_mut().insert(tenant);
+    Ok(next.run(request).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subdomain_is_a_single_label_under_the_base_domain() {
+        assert_eq!(subdomain("acme.localhost:3000", "localhost"), Some("acme"));
+        assert_eq!(subdomain("acme.example.com", "example.com"), Some("acme"));
+
+        assert_eq!(subdomain("localhost:3000", "localhost"), None);
+        assert_eq!(subdomain("a.b.example.com", "example.com"), None);
+        // 이름만 끝이 같은 다른 도메인
+        assert_eq!(subdomain("acme.notexample.com", "example.com"), None);
+        assert_eq!(subdomain("acme.other.org", "example.com"), None);
+    }
+}
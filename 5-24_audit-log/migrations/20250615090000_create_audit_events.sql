@@ -0,0 +1,25 @@
+CREATE TABLE audit_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    at INTEGER NOT NULL,          -- Unix time (초)
+    actor TEXT,                   -- 익명 요청이면 NULL
+    method TEXT NOT NULL,
+    route TEXT NOT NULL,          -- 매칭된 경로 템플릿 (예: /projects/{id})
+    entity_ids TEXT NOT NULL,     -- 경로 파라미터 JSON (예: {"id":"7"})
+    digest TEXT,                  -- 요청 본문 SHA-256 (본문이 없으면 NULL)
+    status INTEGER NOT NULL,
+    outcome TEXT NOT NULL         -- success | denied | failed
+);
+
+CREATE INDEX audit_events_actor ON audit_events (actor, at);
+CREATE INDEX audit_events_route ON audit_events (route, at);
+
+-- 추가만 가능 (애플리케이션 버그나 실수로 기록을 고치거나 지울 수 없음)
+CREATE TRIGGER audit_events_no_update BEFORE UPDATE ON audit_events
+BEGIN
+    SELECT RAISE(ABORT, 'audit log is append-only');
+END;
+
+CREATE TRIGGER audit_events_no_delete BEFORE DELETE ON audit_events
+BEGIN
+    SELECT RAISE(ABORT, 'audit log is append-only');
+END;
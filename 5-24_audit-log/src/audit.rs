@@ -0,0 +1,192 @@
+//! 🕵️ 누가(actor) 무엇을(route, entity) 어떻게(digest, outcome) 바꿨는지 기록하는 미들웨어
+//!
+//! • `resolve_actor` (`layer`): JWT의 `sub` 또는 `session` 쿠키로 사용자를 찾아 `Actor`로 전달
+//! • `record` (`route_layer`): 데이터를 바꾸는 요청(POST, PUT, PATCH, DELETE)이 끝나면 기록 추가
+//!
+//! 핸들러는 감사 로그를 전혀 몰라도 되고, 새 라우트를 추가해도 자동으로 기록됨
+
+use crate::store::{AuditEvent, AuditStore, Outcome};
+use axum::{
+    extract::{FromRequestParts, MatchedPath, RawPathParams, Request, State},
+    http::{
+        header::{AUTHORIZATION, COOKIE},
+        request::Parts,
+        HeaderMap, StatusCode,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::types::Json;
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    sync::{Arc, RwLock},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const SESSION_COOKIE: &str = "session";
+// 감사 로그용으로 본문을 읽을 최대 크기
+const MAX_BODY_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Claims {
+    sub: String,
+    exp: u64,
+}
+
+/// 🔑 토큰 발급/검증 + 세션 저장소
+#[derive(Clone)]
+pub struct Auth {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    // 세션 ID → 사용자 이름
+    sessions: Arc<RwLock<HashMap<String, String>>>,
+}
+
+impl Auth {
+    pub fn new(secret: &[u8]) -> Self {
+        Self {
+            encoding: EncodingKey::from_secret(secret),
+            decoding: DecodingKey::from_secret(secret),
+            sessions: Arc::default(),
+        }
+    }
+
+    /// 1시간짜리 JWT
+    pub fn issue_token(&self, user: &str) -> String {
+        let claims = Claims {
+            sub: user.to_owned(),
+            exp: unix_now() as u64 + 3600,
+        };
+        jsonwebtoken::encode(&Header::default(), &claims, &self.encoding)
+            .expect("HS256 encoding does not fail")
+    }
+
+    /// 새 세션 ID (쿠키 값)
+    pub fn start_session(&self, user: &str) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        self.sessions
+            .write()
+            .unwrap()
+            .insert(id.clone(), user.to_owned());
+        id
+    }
+
+    /// `Authorization: Bearer <JWT>`가 우선, 없으면 `session` 쿠키
+    fn actor(&self, headers: &HeaderMap) -> Option<String> {
+        let bearer = headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        if let Some(token) = bearer {
+            // 잘못된 토큰은 익명으로 처리 (거절은 핸들러의 몫, 거절 결과도 기록됨)
+            return jsonwebtoken::decode::<Claims>(token, &self.decoding, &Validation::default())
+                .ok()
+                .map(|data| data.claims.sub);
+        }
+
+        let session = headers
+            .get_all(COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(';'))
+            .filter_map(|pair| pair.trim().split_once('='))
+            .find_map(|(name, value)| (name == SESSION_COOKIE).then_some(value))?;
+        self.sessions.read().unwrap().get(session).cloned()
+    }
+}
+
+/// 현재 요청의 사용자 (`None`이면 익명)
+#[derive(Debug, Clone)]
+pub struct Actor(pub Option<String>);
+
+/// 로그인이 필요한 핸들러용 추출기 (익명이면 401)
+pub struct User(pub String);
+
+impl<S: Send + Sync> FromRequestParts<S> for User {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        match parts.extensions.get::<Actor>() {
+            Some(Actor(Some(user))) => Ok(User(user.clone())),
+            _ => Err(StatusCode::UNAUTHORIZED),
+        }
+    }
+}
+
+pub async fn resolve_actor(State(auth): State<Auth>, mut request: Request, next: Next) -> Response {
+    let actor = auth.actor(request.headers());
+    request.extensions_mut().insert(Actor(actor));
+    next.run(request).await
+}
+
+/// 데이터를 바꾸는 요청의 결과를 감사 로그에 추가
+///
+/// `route_layer`로 붙여야 `MatchedPath`와 경로 파라미터를 읽을 수 있음
+pub async fn record(
+    State(store): State<AuditStore>,
+    route: MatchedPath,
+    params: RawPathParams,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method().is_safe() {
+        return next.run(request).await;
+    }
+
+    let actor = request
+        .extensions()
+        .get::<Actor>()
+        .and_then(|actor| actor.0.clone());
+    let method = request.method().to_string();
+    let entity_ids = params
+        .iter()
+        .map(|(name, value)| (name.to_owned(), value.to_owned()))
+        .collect();
+
+    // 다이제스트를 계산하려면 본문을 먼저 읽고 다시 넣어야 함 (5-02 예제 참고)
+    let (parts, body) = request.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, MAX_BODY_SIZE).await else {
+        return StatusCode::PAYLOAD_TOO_LARGE.into_response();
+    };
+    let digest = (!bytes.is_empty()).then(|| sha256_hex(&bytes));
+
+    let response = next.run(Request::from_parts(parts, bytes.into())).await;
+
+    let status = response.status().as_u16();
+    let event = AuditEvent {
+        id: 0,
+        at: unix_now(),
+        actor,
+        method,
+        route: route.as_str().to_owned(),
+        entity_ids: Json(entity_ids),
+        digest,
+        status,
+        outcome: Outcome::from_status(status),
+    };
+    // 작업은 이미 끝났으므로 기록에 실패해도 응답은 그대로 보냄 (대신 크게 알림)
+    if let Err(err) = store.append(event).await {
+        tracing::error!(?err, "failed to write audit event");
+    }
+    response
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
@@ -0,0 +1,441 @@
+//! 감사 로그(audit log) 예제
+//!
+//! 금융, 의료, 관리자 도구처럼 "누가 언제 무엇을 바꿨는지" 증명해야 하는 서비스를 위한 구조
+//!
+//! • `audit.rs`: 사용자 확인(JWT/세션) + 데이터를 바꾸는 요청마다 기록을 남기는 미들웨어
+//! • `store.rs`: 추가만 가능한 저장소 (JSON Lines 파일 또는 SQLite)
+//! • `GET /audit?actor=&route=&since=`: 기록 조회 (`admin`만)
+//!
+//! 기록 항목: 시각, 사용자, 메서드, 경로 템플릿, 경로의 엔티티 ID, 요청 본문 SHA-256, 상태 코드, 결과
+//!
+//! ```not_rust
+//! cargo run -p example-audit-log                          # SQLite (audit.db)
+//! AUDIT_FILE=audit.jsonl cargo run -p example-audit-log   # JSON Lines 파일
+//! ```
+
+mod audit;
+mod store;
+
+use audit::{Auth, User};
+use axum::{
+    extract::{FromRef, Path, Query, State},
+    http::{header::SET_COOKIE, StatusCode},
+    middleware,
+    response::IntoResponse,
+    routing::{get, post, put},
+    Json, Router,
+};
+use example_common_errors::ApiError;
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+use store::{AuditEvent, AuditQuery, AuditStore};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+// 감사 로그를 조회할 수 있는 사용자
+const AUDITOR: &str = "admin";
+
+#[derive(Clone, FromRef)]
+struct AppState {
+    audit: AuditStore,
+    auth: Auth,
+    projects: Projects,
+}
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let audit = match std::env::var("AUDIT_FILE") {
+        Ok(path) => AuditStore::file(&path)
+            .await
+            .unwrap_or_else(|err| panic!("{path}: {err}")),
+        Err(_) => {
+            let url = std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:audit.db".into());
+            let options = SqliteConnectOptions::from_str(&url)
+                .unwrap()
+                .create_if_missing(true);
+            let pool = SqlitePool::connect_with(options).await.unwrap();
+            AuditStore::sqlite(pool).await.unwrap()
+        }
+    };
+    let secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "secret".to_owned());
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(audit, Auth::new(secret.as_bytes())))
+        .await
+        .unwrap();
+}
+
+fn app(audit: AuditStore, auth: Auth) -> Router {
+    let state = AppState {
+        audit,
+        auth,
+        projects: Projects::default(),
+    };
+
+    Router::new()
+        .route("/login", post(login))
+        .route("/projects", get(list_projects).post(create_project))
+        .route("/projects/{id}", put(rename_project).delete(delete_project))
+        .route("/projects/{id}/members/{user}", put(add_member))
+        // ⬆️ 여기까지의 라우트는 데이터를 바꾸면 기록됨 (매칭된 라우트에만 적용되는 route_layer)
+        .route_layer(middleware::from_fn_with_state(state.clone(), audit::record))
+        .route("/audit", get(query_audit))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            audit::resolve_actor,
+        ))
+        .with_state(state)
+}
+
+// --- 🔑 로그인 (데모용: 비밀번호 없음)
+
+#[derive(Deserialize)]
+struct Login {
+    username: String,
+}
+
+#[derive(Serialize)]
+struct LoginResponse {
+    token: String,
+}
+
+/// JWT(API 클라이언트용)와 세션 쿠키(브라우저용)를 함께 발급
+async fn login(State(auth): State<Auth>, Json(login): Json<Login>) -> impl IntoResponse {
+    let session = auth.start_session(&login.username);
+    let cookie = format!("session={session}; Path=/; HttpOnly; SameSite=Strict");
+    (
+        [(SET_COOKIE, cookie)],
+        Json(LoginResponse {
+            token: auth.issue_token(&login.username),
+        }),
+    )
+}
+
+// --- 📁 프로젝트 (감사 대상 예시 도메인)
+
+#[derive(Debug, Clone, Serialize)]
+struct Project {
+    id: u64,
+    name: String,
+    owner: String,
+    members: BTreeSet<String>,
+}
+
+#[derive(Clone, Default)]
+struct Projects(Arc<RwLock<BTreeMap<u64, Project>>>);
+
+type HandlerError = (StatusCode, &'static str);
+
+impl Projects {
+    /// 소유자만 수정 가능
+    fn update<T>(
+        &self,
+        id: u64,
+        user: &str,
+        f: impl FnOnce(&mut BTreeMap<u64, Project>) -> T,
+    ) -> Result<T, HandlerError> {
+        let mut projects = self.0.write().unwrap();
+        match projects.get(&id) {
+            None => Err((StatusCode::NOT_FOUND, "project not found")),
+            Some(project) if project.owner != user => {
+                Err((StatusCode::FORBIDDEN, "only the owner can change a project"))
+            }
+            Some(_) => Ok(f(&mut projects)),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ProjectName {
+    name: String,
+}
+
+async fn list_projects(State(projects): State<Projects>) -> Json<Vec<Project>> {
+    Json(projects.0.read().unwrap().values().cloned().collect())
+}
+
+async fn create_project(
+    State(projects): State<Projects>,
+    User(user): User,
+    Json(input): Json<ProjectName>,
+) -> (StatusCode, Json<Project>) {
+    let mut projects = projects.0.write().unwrap();
+    let id = projects.keys().next_back().map_or(1, |id| id + 1);
+    let project = Project {
+        id,
+        name: input.name,
+        owner: user.clone(),
+        members: BTreeSet::from([user]),
+    };
+    projects.insert(id, project.clone());
+    (StatusCode::CREATED, Json(project))
+}
+
+async fn rename_project(
+    State(projects): State<Projects>,
+    User(user): User,
+    Path(id): Path<u64>,
+    Json(input): Json<ProjectName>,
+) -> Result<Json<Project>, HandlerError> {
+    projects
+        .update(id, &user, |projects| {
+            let project = projects.get_mut(&id).unwrap();
+            project.name = input.name;
+            project.clone()
+        })
+        .map(Json)
+}
+
+async fn delete_project(
+    State(projects): State<Projects>,
+    User(user): User,
+    Path(id): Path<u64>,
+) -> Result<StatusCode, HandlerError> {
+    projects.update(id, &user, |projects| projects.remove(&id))?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn add_member(
+    State(projects): State<Projects>,
+    User(user): User,
+    Path((id, member)): Path<(u64, String)>,
+) -> Result<Json<Project>, HandlerError> {
+    projects
+        .update(id, &user, |projects| {
+            let project = projects.get_mut(&id).unwrap();
+            project.members.insert(member);
+            project.clone()
+        })
+        .map(Json)
+}
+
+// --- 🔍 감사 로그 조회
+
+async fn query_audit(
+    State(audit): State<AuditStore>,
+    User(user): User,
+    Query(query): Query<AuditQuery>,
+) -> Result<Json<Vec<AuditEvent>>, axum::response::Response> {
+    if user != AUDITOR {
+        return Err((StatusCode::FORBIDDEN, "auditors only").into_response());
+    }
+    let events = audit.query(&query).await.map_err(ApiError::into_response)?;
+    Ok(Json(events))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header, Request},
+    };
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    async fn app() -> (Router, Auth) {
+        // 메모리 DB는 연결마다 따로 생기므로 연결 하나만 사용
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let auth = Auth::new(b"test-secret");
+        (
+            super::app(AuditStore::sqlite(pool).await.unwrap(), auth.clone()),
+            auth,
+        )
+    }
+
+    async fn send(app: &Router, request: Request<Body>) -> (StatusCode, Value) {
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    fn request(method: &str, uri: &str, token: Option<&str>, body: Option<Value>) -> Request<Body> {
+        let mut request = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        match body {
+            Some(body) => request
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => request.body(Body::empty()),
+        }
+        .unwrap()
+    }
+
+    async fn audit_log(app: &Router, auth: &Auth, query: &str) -> Vec<Value> {
+        let admin = auth.issue_token(AUDITOR);
+        let (status, events) = send(
+            app,
+            request("GET", &format!("/audit{query}"), Some(&admin), None),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        serde_json::from_value(events).unwrap()
+    }
+
+    #[tokio::test]
+    async fn mutating_requests_are_recorded() {
+        let (app, auth) = app().await;
+        let alice = auth.issue_token("alice");
+
+        let body = json!({ "name": "apollo" });
+        let (status, _) = send(
+            &app,
+            request("POST", "/projects", Some(&alice), Some(body.clone())),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        send(
+            &app,
+            request("PUT", "/projects/1/members/bob", Some(&alice), None),
+        )
+        .await;
+        // 조회는 기록하지 않음
+        send(&app, request("GET", "/projects", Some(&alice), None)).await;
+
+        let events = audit_log(&app, &auth, "").await;
+        assert_eq!(events.len(), 2);
+
+        let member = &events[0];
+        assert_eq!(member["actor"], "alice");
+        assert_eq!(member["method"], "PUT");
+        assert_eq!(member["route"], "/projects/{id}/members/{user}");
+        assert_eq!(member["entity_ids"], json!({ "id": "1", "user": "bob" }));
+        assert_eq!(member["digest"], Value::Null);
+        assert_eq!(member["outcome"], "success");
+
+        let created = &events[1];
+        assert_eq!(created["route"], "/projects");
+        assert_eq!(created["status"], 201);
+        assert_eq!(created["digest"], audit_digest(body.to_string().as_bytes()));
+    }
+
+    fn audit_digest(bytes: &[u8]) -> String {
+        use sha2::{Digest, Sha256};
+        Sha256::digest(bytes)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn denied_and_failed_requests_are_recorded() {
+        let (app, auth) = app().await;
+        let alice = auth.issue_token("alice");
+        let bob = auth.issue_token("bob");
+        send(
+            &app,
+            request(
+                "POST",
+                "/projects",
+                Some(&alice),
+                Some(json!({ "name": "apollo" })),
+            ),
+        )
+        .await;
+
+        let (status, _) = send(&app, request("DELETE", "/projects/1", None, None)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = send(&app, request("DELETE", "/projects/1", Some(&bob), None)).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, _) = send(&app, request("DELETE", "/projects/9", Some(&bob), None)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let events = audit_log(&app, &auth, "?route=/projects/{id}").await;
+        let summary: Vec<_> = events
+            .iter()
+            .map(|e| {
+                (
+                    e["actor"].clone(),
+                    e["outcome"].clone(),
+                    e["entity_ids"]["id"].clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (json!("bob"), json!("failed"), json!("9")),
+                (json!("bob"), json!("denied"), json!("1")),
+                (Value::Null, json!("denied"), json!("1")),
+            ]
+        );
+
+        let events = audit_log(&app, &auth, "?actor=alice").await;
+        assert_eq!(events.len(), 1);
+        let events = audit_log(&app, &auth, "?since=99999999999").await;
+        assert!(events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn session_cookie_identifies_the_actor() {
+        let (app, auth) = app().await;
+
+        let login = request("POST", "/login", None, Some(json!({ "username": "carol" })));
+        let response = app.clone().oneshot(login).await.unwrap();
+        let cookie = response.headers()[header::SET_COOKIE]
+            .to_str()
+            .unwrap()
+            .split(';')
+            .next()
+            .unwrap()
+            .to_owned();
+
+        let create = Request::post("/projects")
+            .header(header::COOKIE, cookie)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(r#"{"name":"gemini"}"#))
+            .unwrap();
+        assert_eq!(send(&app, create).await.0, StatusCode::CREATED);
+
+        let events = audit_log(&app, &auth, "?route=/projects").await;
+        assert_eq!(events[0]["actor"], "carol");
+    }
+
+    #[tokio::test]
+    async fn only_auditors_can_read_the_log() {
+        let (app, auth) = app().await;
+        let alice = auth.issue_token("alice");
+
+        let (status, _) = send(&app, request("GET", "/audit", Some(&alice), None)).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, _) = send(&app, request("GET", "/audit", None, None)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+}
+
+// 🧪 테스트 방법
+//
+// > TOKEN=$(curl -s localhost:3000/login -H 'content-type: application/json' -d '{"username":"alice"}' | jq -r .token)
+// > curl -X POST localhost:3000/projects -H "Authorization: Bearer $TOKEN" -H 'content-type: application/json' -d '{"name":"apollo"}'
+// > curl -X PUT localhost:3000/projects/1/members/bob -H "Authorization: Bearer $TOKEN"
+// > curl -X DELETE localhost:3000/projects/1                       → 401 (익명, outcome=denied로 기록)
+//
+// > ADMIN=$(curl -s localhost:3000/login -H 'content-type: application/json' -d '{"username":"admin"}' | jq -r .token)
+// > curl "localhost:3000/audit?actor=alice" -H "Authorization: Bearer $ADMIN"
+// > curl "localhost:3000/audit?route=/projects/%7Bid%7D&since=1700000000" -H "Authorization: Bearer $ADMIN"
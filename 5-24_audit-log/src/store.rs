@@ -0,0 +1,309 @@
+//! 🗄️ 감사 로그 저장소 (추가와 조회만 가능)
+//!
+//! • `File`: JSON Lines 파일에 한 줄씩 추가 (로그 수집기가 그대로 읽어 가기 쉬움)
+//! • `Sqlite`: 트리거로 UPDATE/DELETE를 막은 테이블 (조건 조회가 빠름)
+//!
+//! 어느 쪽이든 `append`와 `query`만 제공 → 애플리케이션 코드에는 기록을 고치는 경로가 없음
+
+use example_common_errors::ApiError;
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, SqlitePool};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tokio::{
+    fs::{self, File, OpenOptions},
+    io::AsyncWriteExt,
+    sync::Mutex,
+};
+
+const DEFAULT_LIMIT: u32 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
+pub enum Outcome {
+    Success,
+    /// 인증/권한 부족 (401, 403)
+    Denied,
+    Failed,
+}
+
+impl Outcome {
+    pub fn from_status(status: u16) -> Self {
+        match status {
+            200..=399 => Outcome::Success,
+            401 | 403 => Outcome::Denied,
+            _ => Outcome::Failed,
+        }
+    }
+}
+
+/// 📝 데이터를 바꾸는 요청 한 건의 기록
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AuditEvent {
+    /// 저장할 때 매겨지는 일련번호
+    pub id: i64,
+    /// Unix time (초)
+    pub at: i64,
+    pub actor: Option<String>,
+    pub method: String,
+    pub route: String,
+    /// 경로 파라미터 (`/projects/{id}` → `{"id": "7"}`)
+    pub entity_ids: Json<BTreeMap<String, String>>,
+    /// 요청 본문 SHA-256 (본문 자체는 저장하지 않음)
+    pub digest: Option<String>,
+    pub status: u16,
+    pub outcome: Outcome,
+}
+
+/// `GET /audit?actor=&route=&since=` 조건 (모두 선택)
+#[derive(Debug, Default, Deserialize)]
+pub struct AuditQuery {
+    pub actor: Option<String>,
+    pub route: Option<String>,
+    /// 이 시각(Unix time, 초) 이후 기록만
+    pub since: Option<i64>,
+    /// 최대 개수 (기본 100, 최신순)
+    pub limit: Option<u32>,
+}
+
+impl AuditQuery {
+    fn matches(&self, event: &AuditEvent) -> bool {
+        self.actor
+            .as_ref()
+            .is_none_or(|actor| event.actor.as_ref() == Some(actor))
+            && self
+                .route
+                .as_ref()
+                .is_none_or(|route| &event.route == route)
+            && self.since.is_none_or(|since| event.at >= since)
+    }
+
+    fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_LIMIT) as usize
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum AuditStore {
+    File(Arc<FileStore>),
+    Sqlite(SqlitePool),
+}
+
+#[derive(Debug)]
+pub struct FileStore {
+    path: PathBuf,
+    // 파일 핸들과 다음 일련번호 (쓰기는 한 번에 하나씩)
+    writer: Mutex<(File, i64)>,
+}
+
+impl AuditStore {
+    /// 파일이 있으면 이어서 기록 (일련번호도 이어서 매김)
+    pub async fn file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref().to_owned();
+        let existing = match fs::read_to_string(&path).await {
+            Ok(contents) => contents.lines().count() as i64,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(err) => return Err(err),
+        };
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await?;
+
+        Ok(Self::File(Arc::new(FileStore {
+            path,
+            writer: Mutex::new((file, existing + 1)),
+        })))
+    }
+
+    /// 마이그레이션(테이블 + 수정 방지 트리거)을 실행하고 사용
+    pub async fn sqlite(pool: SqlitePool) -> Result<Self, sqlx::migrate::MigrateError> {
+        sqlx::migrate!().run(&pool).await?;
+        Ok(Self::Sqlite(pool))
+    }
+
+    /// 기록 추가 (`event.id`는 무시하고 새로 매김)
+    pub async fn append(&self, mut event: AuditEvent) -> Result<AuditEvent, ApiError> {
+        match self {
+            AuditStore::File(store) => {
+                let mut writer = store.writer.lock().await;
+                let (file, next_id) = &mut *writer;
+                event.id = *next_id;
+
+                let mut line = serde_json::to_vec(&event).map_err(ApiError::internal)?;
+                line.push(b'\n');
+                file.write_all(&line).await.map_err(ApiError::internal)?;
+                // 응답보다 기록이 먼저 디스크에 닿도록
+                file.sync_data().await.map_err(ApiError::internal)?;
+
+                *next_id += 1;
+                Ok(event)
+            }
+            AuditStore::Sqlite(pool) => {
+                let (id,): (i64,) = sqlx::query_as(
+                    "INSERT INTO audit_events \
+                     (at, actor, method, route, entity_ids, digest, status, outcome) \
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?) RETURNING id",
+                )
+                .bind(event.at)
+                .bind(&event.actor)
+                .bind(&event.method)
+                .bind(&event.route)
+                .bind(&event.entity_ids)
+                .bind(&event.digest)
+                .bind(event.status)
+                .bind(event.outcome)
+                .fetch_one(pool)
+                .await?;
+                event.id = id;
+                Ok(event)
+            }
+        }
+    }
+
+    /// 조건에 맞는 기록 (최신순)
+    pub async fn query(&self, query: &AuditQuery) -> Result<Vec<AuditEvent>, ApiError> {
+        match self {
+            AuditStore::File(store) => {
+                // 쓰는 도중인 줄을 읽지 않도록 잠금
+                let _writer = store.writer.lock().await;
+                let contents = fs::read_to_string(&store.path)
+                    .await
+                    .map_err(ApiError::internal)?;
+
+                let mut events = Vec::new();
+                for line in contents.lines().rev() {
+                    let event: AuditEvent =
+                        serde_json::from_str(line).map_err(ApiError::internal)?;
+                    if query.matches(&event) {
+                        events.push(event);
+                        if events.len() == query.limit() {
+                            break;
+                        }
+                    }
+                }
+                Ok(events)
+            }
+            AuditStore::Sqlite(pool) => {
+                let events = sqlx::query_as(
+                    "SELECT * FROM audit_events \
+                     WHERE (?1 IS NULL OR actor = ?1) \
+                       AND (?2 IS NULL OR route = ?2) \
+                       AND (?3 IS NULL OR at >= ?3) \
+                     ORDER BY id DESC LIMIT ?4",
+                )
+                .bind(&query.actor)
+                .bind(&query.route)
+                .bind(query.since)
+                .bind(query.limit() as i64)
+                .fetch_all(pool)
+                .await?;
+                Ok(events)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(at: i64, actor: Option<&str>, route: &str) -> AuditEvent {
+        AuditEvent {
+            id: 0,
+            at,
+            actor: actor.map(str::to_owned),
+            method: "POST".to_owned(),
+            route: route.to_owned(),
+            entity_ids: Json(BTreeMap::from([("id".to_owned(), "7".to_owned())])),
+            digest: None,
+            status: 201,
+            outcome: Outcome::Success,
+        }
+    }
+
+    async fn check_append_and_query(store: &AuditStore) {
+        store
+            .append(event(100, Some("alice"), "/projects"))
+            .await
+            .unwrap();
+        store
+            .append(event(200, Some("bob"), "/projects/{id}"))
+            .await
+            .unwrap();
+        let last = store
+            .append(event(300, Some("alice"), "/projects/{id}"))
+            .await
+            .unwrap();
+        assert_eq!(last.id, 3);
+
+        let ids = |events: Vec<AuditEvent>| events.iter().map(|e| e.id).collect::<Vec<_>>();
+        let all = store.query(&AuditQuery::default()).await.unwrap();
+        assert_eq!(all[0], last);
+        assert_eq!(ids(all), [3, 2, 1]);
+
+        let query = AuditQuery {
+            actor: Some("alice".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(ids(store.query(&query).await.unwrap()), [3, 1]);
+
+        let query = AuditQuery {
+            route: Some("/projects/{id}".to_owned()),
+            since: Some(250),
+            ..Default::default()
+        };
+        assert_eq!(ids(store.query(&query).await.unwrap()), [3]);
+
+        let query = AuditQuery {
+            limit: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(ids(store.query(&query).await.unwrap()), [3]);
+    }
+
+    #[tokio::test]
+    async fn sqlite_store_appends_and_queries() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let store = AuditStore::sqlite(pool.clone()).await.unwrap();
+        check_append_and_query(&store).await;
+
+        // 직접 SQL로도 고치거나 지울 수 없음
+        let err = sqlx::query("UPDATE audit_events SET actor = 'mallory'")
+            .execute(&pool)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("append-only"));
+        assert!(sqlx::query("DELETE FROM audit_events")
+            .execute(&pool)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn file_store_appends_and_continues_numbering() {
+        let path =
+            std::env::temp_dir().join(format!("example-audit-log-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let store = AuditStore::file(&path).await.unwrap();
+        check_append_and_query(&store).await;
+
+        // 다시 열면 이어서 기록
+        let reopened = AuditStore::file(&path).await.unwrap();
+        let event = reopened.append(event(400, None, "/login")).await.unwrap();
+        assert_eq!(event.id, 4);
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 4);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
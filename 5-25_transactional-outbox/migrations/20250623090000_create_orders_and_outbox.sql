@@ -0,0 +1,25 @@
+CREATE TABLE orders (
+    id BIGSERIAL PRIMARY KEY,
+    customer TEXT NOT NULL,
+    total_cents BIGINT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'placed',      -- placed | cancelled
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- 도메인 데이터와 같은 트랜잭션에서 기록되는 "보내야 할 이벤트" 목록
+CREATE TABLE outbox (
+    id BIGSERIAL PRIMARY KEY,                   -- 기록 순서 = 전송 순서
+    idempotency_key UUID NOT NULL UNIQUE,       -- 재전송해도 같음 (수신 측 중복 제거용)
+    aggregate_type TEXT NOT NULL,               -- 예: order
+    aggregate_id TEXT NOT NULL,                 -- 예: 42
+    event_type TEXT NOT NULL,                   -- 예: order.created
+    payload JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    last_error TEXT,
+    delivered_at TIMESTAMPTZ                    -- NULL이면 아직 전송 전
+);
+
+-- relay가 읽는 "보낼 차례가 된 이벤트"만 색인
+CREATE INDEX outbox_pending ON outbox (next_attempt_at, id) WHERE delivered_at IS NULL;
@@ -0,0 +1,300 @@
+//! 📮 Transactional outbox
+//!
+//! "DB에 저장"과 "이벤트 발행"을 따로 하면 둘 중 하나만 성공하는 순간이 생김
+//! (저장 후 서버가 죽으면 이벤트 유실, 발행 후 커밋이 실패하면 없는 주문의 이벤트가 나감)
+//!
+//! • 핸들러: 도메인 행과 `outbox` 행을 **같은 트랜잭션**에서 기록 (`enqueue`) → 둘 다 남거나 둘 다 없음
+//! • `Relay`: 백그라운드에서 `outbox`를 읽어 전송하고 `delivered_at`을 기록
+//!   → `FOR UPDATE SKIP LOCKED`: relay를 여러 개 띄워도 같은 이벤트를 동시에 잡지 않음
+//!   → 전송 후 커밋 전에 죽으면 다음 relay가 다시 보냄 → **at-least-once**
+//!     그래서 이벤트마다 `idempotency_key`를 붙여 보내고, 수신 측이 중복을 걸러냄
+//!   → 실패하면 지수 백오프로 `next_attempt_at`을 미룸 (그동안 뒤의 이벤트는 계속 나감 → 순서는 보장하지 않음)
+//! • 지표: 밀린 이벤트 수(`outbox_pending_events`), 가장 오래된 미전송 이벤트의 나이(`outbox_lag_seconds`)
+
+use example_common_errors::BoxError;
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::{PgConnection, PgPool};
+use std::{future::Future, sync::Arc, time::Duration};
+use tokio::sync::Notify;
+use uuid::Uuid;
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// 트랜잭션 안에서 기록할 이벤트
+pub struct NewEvent {
+    pub aggregate_type: &'static str,
+    pub aggregate_id: String,
+    pub event_type: &'static str,
+    pub payload: Value,
+}
+
+/// `outbox` 테이블의 행 (전송되는 본문이기도 함)
+#[derive(Debug, Clone, PartialEq, Serialize, sqlx::FromRow)]
+pub struct OutboxEvent {
+    #[serde(skip)]
+    pub id: i64,
+    pub idempotency_key: Uuid,
+    pub aggregate_type: String,
+    pub aggregate_id: String,
+    pub event_type: String,
+    pub payload: Value,
+    #[serde(skip)]
+    pub attempts: i32,
+}
+
+/// 도메인 변경과 같은 트랜잭션(`&mut *tx`)에서 호출 → 커밋되어야 relay에게 보임
+pub async fn enqueue(conn: &mut PgConnection, event: NewEvent) -> Result<Uuid, sqlx::Error> {
+    let key = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO outbox (idempotency_key, aggregate_type, aggregate_id, event_type, payload)
+         VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(key)
+    .bind(event.aggregate_type)
+    .bind(event.aggregate_id)
+    .bind(event.event_type)
+    .bind(event.payload)
+    .execute(conn)
+    .await?;
+    Ok(key)
+}
+
+/// 이벤트를 밖으로 내보내는 곳 (웹훅, 메시지 큐 등)
+///
+/// 큐로 보낼 때도 `idempotency_key`를 메시지 ID(SQS의 deduplication ID 등)로 쓰면 됨
+pub trait Publisher: Clone + Send + Sync + 'static {
+    fn publish(&self, event: &OutboxEvent) -> impl Future<Output = Result<(), BoxError>> + Send;
+}
+
+/// 🌐 웹훅으로 전송 (2xx가 아니면 실패로 보고 다시 보냄)
+#[derive(Clone)]
+pub struct WebhookPublisher {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookPublisher {
+    pub fn new(url: impl Into<String>, timeout: Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("failed to build HTTP client");
+        Self {
+            client,
+            url: url.into(),
+        }
+    }
+}
+
+impl Publisher for WebhookPublisher {
+    async fn publish(&self, event: &OutboxEvent) -> Result<(), BoxError> {
+        self.client
+            .post(&self.url)
+            .header(IDEMPOTENCY_KEY_HEADER, event.idempotency_key.to_string())
+            .json(event)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// ⚙️ relay 설정
+#[derive(Debug, Clone)]
+pub struct RelayConfig {
+    /// 트랜잭션 하나에서 처리할 최대 이벤트 수
+    pub batch_size: i64,
+    /// 새 이벤트 알림이 없어도 이 간격으로 확인 (다른 서버가 기록한 이벤트, 재시도 시각 도달)
+    pub poll_interval: Duration,
+    /// 첫 재시도 전 대기 시간, 이후 2배씩 증가
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RelayConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 50,
+            poll_interval: Duration::from_secs(1),
+            base_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(300),
+        }
+    }
+}
+
+impl RelayConfig {
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// 📊 `GET /outbox/stats`와 지표에 쓰는 현황
+#[derive(Debug, Clone, PartialEq, Serialize, sqlx::FromRow)]
+pub struct OutboxStats {
+    pub pending: i64,
+    pub delivered: i64,
+    /// 가장 오래된 미전송 이벤트가 기록된 지 몇 초 지났는지 (없으면 0)
+    pub lag_seconds: f64,
+}
+
+pub async fn stats(pool: &PgPool) -> Result<OutboxStats, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT
+             COUNT(*) FILTER (WHERE delivered_at IS NULL) AS pending,
+             COUNT(*) FILTER (WHERE delivered_at IS NOT NULL) AS delivered,
+             COALESCE(EXTRACT(EPOCH FROM now() - MIN(created_at) FILTER (WHERE delivered_at IS NULL)), 0)::float8
+                 AS lag_seconds
+         FROM outbox",
+    )
+    .fetch_one(pool)
+    .await
+}
+
+#[derive(Clone)]
+pub struct Relay<P> {
+    pool: PgPool,
+    publisher: P,
+    config: Arc<RelayConfig>,
+    wake: Arc<Notify>,
+}
+
+impl<P: Publisher> Relay<P> {
+    pub fn new(pool: PgPool, publisher: P, config: RelayConfig) -> Self {
+        Self {
+            pool,
+            publisher,
+            config: Arc::new(config),
+            wake: Arc::new(Notify::new()),
+        }
+    }
+
+    /// 커밋 직후 `notify_one()`을 호출하면 폴링 간격을 기다리지 않고 바로 전송
+    pub fn waker(&self) -> Arc<Notify> {
+        self.wake.clone()
+    }
+
+    /// 🔁 백그라운드 루프 (`tokio::spawn(relay.run())`)
+    pub async fn run(self) {
+        loop {
+            let processed = match self.relay_batch().await {
+                Ok(processed) => processed,
+                Err(err) => {
+                    tracing::error!(%err, "outbox relay failed");
+                    0
+                }
+            };
+
+            match stats(&self.pool).await {
+                Ok(stats) => {
+                    metrics::gauge!("outbox_pending_events").set(stats.pending as f64);
+                    metrics::gauge!("outbox_lag_seconds").set(stats.lag_seconds);
+                }
+                Err(err) => tracing::warn!(%err, "failed to read outbox stats"),
+            }
+
+            // 한 배치가 가득 찼으면 더 밀려 있을 수 있으므로 바로 다음 배치
+            if processed < self.config.batch_size as usize {
+                let _ = tokio::time::timeout(self.config.poll_interval, self.wake.notified()).await;
+            }
+        }
+    }
+
+    /// 보낼 차례가 된 이벤트를 한 배치 전송하고, 처리한(성공 + 실패) 개수를 반환
+    pub async fn relay_batch(&self) -> Result<usize, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        // 잡은 행은 커밋할 때까지 잠겨 있어서 다른 relay는 건너뜀
+        let events: Vec<OutboxEvent> = sqlx::query_as(
+            "SELECT id, idempotency_key, aggregate_type, aggregate_id, event_type, payload, attempts
+             FROM outbox
+             WHERE delivered_at IS NULL AND next_attempt_at <= now()
+             ORDER BY id
+             LIMIT $1
+             FOR UPDATE SKIP LOCKED",
+        )
+        .bind(self.config.batch_size)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for event in &events {
+            match self.publisher.publish(event).await {
+                Ok(()) => {
+                    sqlx::query(
+                        "UPDATE outbox SET delivered_at = now(), attempts = attempts + 1, last_error = NULL
+                         WHERE id = $1",
+                    )
+                    .bind(event.id)
+                    .execute(&mut *tx)
+                    .await?;
+                    metrics::counter!("outbox_delivered_total", "event_type" => event.event_type.clone())
+                        .increment(1);
+                }
+                Err(err) => {
+                    let attempt = event.attempts as u32 + 1;
+                    let backoff = self.config.backoff(attempt);
+                    tracing::warn!(key = %event.idempotency_key, attempt, ?backoff, %err, "outbox delivery failed");
+
+                    sqlx::query(
+                        "UPDATE outbox
+                         SET attempts = attempts + 1, last_error = $2,
+                             next_attempt_at = now() + make_interval(secs => $3)
+                         WHERE id = $1",
+                    )
+                    .bind(event.id)
+                    .bind(err.to_string())
+                    .bind(backoff.as_secs_f64())
+                    .execute(&mut *tx)
+                    .await?;
+                    metrics::counter!("outbox_delivery_failures_total", "event_type" => event.event_type.clone())
+                        .increment(1);
+                }
+            }
+        }
+
+        tx.commit().await?;
+        Ok(events.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let config = RelayConfig::default();
+
+        assert_eq!(config.backoff(1), Duration::from_secs(1));
+        assert_eq!(config.backoff(2), Duration::from_secs(2));
+        assert_eq!(config.backoff(5), Duration::from_secs(16));
+        assert_eq!(config.backoff(20), Duration::from_secs(300));
+    }
+
+    #[test]
+    fn delivered_body_carries_the_idempotency_key_but_not_bookkeeping() {
+        let event = OutboxEvent {
+            id: 7,
+            idempotency_key: Uuid::nil(),
+            aggregate_type: "order".to_owned(),
+            aggregate_id: "42".to_owned(),
+            event_type: "order.created".to_owned(),
+            payload: serde_json::json!({ "total_cents": 1200 }),
+            attempts: 3,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({
+                "idempotency_key": "00000000-0000-0000-0000-000000000000",
+                "aggregate_type": "order",
+                "aggregate_id": "42",
+                "event_type": "order.created",
+                "payload": { "total_cents": 1200 },
+            })
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! ✍️ 쓰기 모델: 명령(command)을 검증해서 이벤트를 만드는 `TodoAggregate`
+//!
+//! • 상태는 저장하지 않음 → 이 할 일의 이벤트를 처음부터 `apply`해서 만듦
+//! • `handle`은 상태를 바꾸지 않고 "일어날 일"(이벤트)만 결정 → 저장에 성공한 뒤에야 사실이 됨
+//! • 이미 그 상태인 명령(완료된 할 일 다시 완료 등)은 이벤트 없이 성공 (재시도해도 안전)
+
+use example_common_errors::ApiError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const MAX_TITLE_LEN: usize = 200;
+
+/// 📜 일어난 일 (과거형, 한 번 저장되면 바뀌지 않음)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TodoEvent {
+    Created { title: String },
+    Renamed { title: String },
+    Completed,
+    Reopened,
+    Deleted,
+}
+
+/// 📨 요청된 일 (거절될 수 있음)
+#[derive(Debug, Clone)]
+pub enum TodoCommand {
+    Create { title: String },
+    Rename { title: String },
+    Complete,
+    Reopen,
+    Delete,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CommandError {
+    AlreadyExists,
+    NotFound,
+    InvalidTitle(&'static str),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AlreadyExists => f.write_str("todo already exists"),
+            Self::NotFound => f.write_str("todo not found"),
+            Self::InvalidTitle(reason) => write!(f, "invalid title: {reason}"),
+        }
+    }
+}
+
+impl From<CommandError> for ApiError {
+    fn from(err: CommandError) -> Self {
+        match err {
+            CommandError::AlreadyExists => ApiError::Conflict(err.to_string()),
+            CommandError::NotFound => ApiError::NotFound(err.to_string()),
+            CommandError::InvalidTitle(_) => ApiError::Validation(err.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct TodoAggregate {
+    /// 적용한 이벤트 수 (저장할 때 낙관적 동시성 검사에 씀)
+    pub version: u64,
+    created: bool,
+    deleted: bool,
+    title: String,
+    completed: bool,
+}
+
+impl TodoAggregate {
+    pub fn from_events<'a>(events: impl IntoIterator<Item = &'a TodoEvent>) -> Self {
+        let mut aggregate = Self::default();
+        for event in events {
+            aggregate.apply(event);
+        }
+        aggregate
+    }
+
+    pub fn apply(&mut self, event: &TodoEvent) {
+        match event {
+            TodoEvent::Created { title } => {
+                self.created = true;
+                self.title.clone_from(title);
+            }
+            TodoEvent::Renamed { title } => self.title.clone_from(title),
+            TodoEvent::Completed => self.completed = true,
+            TodoEvent::Reopened => self.completed = false,
+            TodoEvent::Deleted => self.deleted = true,
+        }
+        self.version += 1;
+    }
+
+    pub fn handle(&self, command: TodoCommand) -> Result<Vec<TodoEvent>, CommandError> {
+        if let TodoCommand::Create { title } = command {
+            if self.created {
+                return Err(CommandError::AlreadyExists);
+            }
+            return Ok(vec![TodoEvent::Created {
+                title: validate_title(&title)?,
+            }]);
+        }
+
+        if !self.created || self.deleted {
+            return Err(CommandError::NotFound);
+        }
+
+        let events = match command {
+            TodoCommand::Create { .. } => unreachable!("handled above"),
+            TodoCommand::Rename { title } => {
+                let title = validate_title(&title)?;
+                if title == self.title {
+                    vec![]
+                } else {
+                    vec![TodoEvent::Renamed { title }]
+                }
+            }
+            TodoCommand::Complete if self.completed => vec![],
+            TodoCommand::Complete => vec![TodoEvent::Completed],
+            TodoCommand::Reopen if !self.completed => vec![],
+            TodoCommand::Reopen => vec![TodoEvent::Reopened],
+            TodoCommand::Delete => vec![TodoEvent::Deleted],
+        };
+        Ok(events)
+    }
+}
+
+fn validate_title(title: &str) -> Result<String, CommandError> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(CommandError::InvalidTitle("must not be empty"));
+    }
+    if title.chars().count() > MAX_TITLE_LEN {
+        return Err(CommandError::InvalidTitle("must be at most 200 characters"));
+    }
+    Ok(title.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn created(title: &str) -> TodoAggregate {
+        TodoAggregate::from_events(&[TodoEvent::Created {
+            title: title.to_owned(),
+        }])
+    }
+
+    #[test]
+    fn commands_on_missing_or_deleted_todos_are_rejected() {
+        let missing = TodoAggregate::default();
+        assert_eq!(
+            missing.handle(TodoCommand::Complete),
+            Err(CommandError::NotFound)
+        );
+
+        let deleted = TodoAggregate::from_events(&[
+            TodoEvent::Created {
+                title: "a".to_owned(),
+            },
+            TodoEvent::Deleted,
+        ]);
+        assert_eq!(deleted.version, 2);
+        assert_eq!(
+            deleted.handle(TodoCommand::Delete),
+            Err(CommandError::NotFound)
+        );
+        assert_eq!(
+            deleted.handle(TodoCommand::Create {
+                title: "again".to_owned()
+            }),
+            Err(CommandError::AlreadyExists)
+        );
+    }
+
+    #[test]
+    fn commands_produce_events_only_when_state_changes() {
+        let todo = created("write docs");
+
+        assert_eq!(
+            todo.handle(TodoCommand::Rename {
+                title: "  write docs ".to_owned()
+            }),
+            Ok(vec![])
+        );
+        assert_eq!(todo.handle(TodoCommand::Reopen), Ok(vec![]));
+        assert_eq!(
+            todo.handle(TodoCommand::Complete),
+            Ok(vec![TodoEvent::Completed])
+        );
+
+        let done = TodoAggregate::from_events(&[
+            TodoEvent::Created {
+                title: "write docs".to_owned(),
+            },
+            TodoEvent::Completed,
+        ]);
+        assert_eq!(done.handle(TodoCommand::Complete), Ok(vec![]));
+        assert_eq!(
+            done.handle(TodoCommand::Reopen),
+            Ok(vec![TodoEvent::Reopened])
+        );
+    }
+
+    #[test]
+    fn titles_are_trimmed_and_validated() {
+        let missing = TodoAggregate::default();
+        assert_eq!(
+            missing.handle(TodoCommand::Create {
+                title: " buy milk ".to_owned()
+            }),
+            Ok(vec![TodoEvent::Created {
+                title: "buy milk".to_owned()
+            }])
+        );
+        assert!(matches!(
+            missing.handle(TodoCommand::Create {
+                title: "   ".to_owned()
+            }),
+            Err(CommandError::InvalidTitle(_))
+        ));
+        assert!(matches!(
+            created("a").handle(TodoCommand::Rename {
+                title: "x".repeat(201)
+            }),
+            Err(CommandError::InvalidTitle(_))
+        ));
+    }
+}
@@ -0,0 +1,234 @@
+//! 🗄️ 이벤트 저장소 (추가와 읽기만 가능)
+//!
+//! • 이벤트마다 전체 순번(`sequence`)과 할 일(stream)별 버전(`version`)을 매김
+//!   → 프로젝션은 `sequence` 순서로 읽고, 명령 처리는 `version`으로 동시 수정을 감지
+//! • 메모리: 재시작하면 사라짐 (테스트, 데모)
+//! • 파일: JSON Lines에 한 줄씩 추가하고, 열 때 전부 읽어 메모리에도 둠
+
+use crate::aggregate::TodoEvent;
+use example_common_errors::ApiError;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    fs::{self, File, OpenOptions},
+    io::AsyncWriteExt,
+    sync::Mutex,
+};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredEvent {
+    /// 저장소 전체에서 1부터 증가
+    pub sequence: u64,
+    pub stream_id: Uuid,
+    /// 이 stream에서 1부터 증가
+    pub version: u64,
+    /// Unix time (초)
+    pub recorded_at: u64,
+    pub event: TodoEvent,
+}
+
+#[derive(Clone)]
+pub struct EventStore {
+    inner: Arc<Mutex<Inner>>,
+}
+
+struct Inner {
+    events: Vec<StoredEvent>,
+    // stream별 마지막 버전
+    versions: HashMap<Uuid, u64>,
+    file: Option<File>,
+}
+
+impl EventStore {
+    pub fn memory() -> Self {
+        Self::with_events(Vec::new(), None)
+    }
+
+    /// 파일이 있으면 기존 이벤트를 읽고 이어서 기록
+    pub async fn file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        let events = match fs::read_to_string(path).await {
+            Ok(contents) => contents
+                .lines()
+                .map(serde_json::from_str)
+                .collect::<Result<Vec<StoredEvent>, _>>()?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err),
+        };
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await?;
+
+        Ok(Self::with_events(events, Some(file)))
+    }
+
+    fn with_events(events: Vec<StoredEvent>, file: Option<File>) -> Self {
+        let versions = events
+            .iter()
+            .map(|event| (event.stream_id, event.version))
+            .collect();
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                events,
+                versions,
+                file,
+            })),
+        }
+    }
+
+    /// stream의 현재 버전이 `expected_version`일 때만 추가 (아니면 409)
+    ///
+    /// 명령을 처리하는 사이에 다른 요청이 같은 할 일을 바꿨다면 그 결정은 오래된 상태를 기준으로 한 것
+    pub async fn append(
+        &self,
+        stream_id: Uuid,
+        expected_version: u64,
+        events: Vec<TodoEvent>,
+    ) -> Result<Vec<StoredEvent>, ApiError> {
+        let mut inner = self.inner.lock().await;
+        let current = inner.versions.get(&stream_id).copied().unwrap_or(0);
+        if current != expected_version {
+            return Err(ApiError::Conflict(format!(
+                "todo {stream_id} is at version {current}, expected {expected_version}"
+            )));
+        }
+
+        let recorded_at = unix_now();
+        let first_sequence = inner.events.len() as u64 + 1;
+        let stored: Vec<_> = events
+            .into_iter()
+            .enumerate()
+            .map(|(index, event)| StoredEvent {
+                sequence: first_sequence + index as u64,
+                stream_id,
+                version: expected_version + index as u64 + 1,
+                recorded_at,
+                event,
+            })
+            .collect();
+
+        if let Some(file) = &mut inner.file {
+            // 여러 이벤트를 한 번에 써서 일부만 기록되는 일을 줄임
+            let mut lines = Vec::new();
+            for event in &stored {
+                serde_json::to_writer(&mut lines, event).map_err(ApiError::internal)?;
+                lines.push(b'\n');
+            }
+            file.write_all(&lines).await.map_err(ApiError::internal)?;
+            file.sync_data().await.map_err(ApiError::internal)?;
+        }
+
+        if let Some(last) = stored.last() {
+            inner.versions.insert(stream_id, last.version);
+        }
+        inner.events.extend(stored.iter().cloned());
+        Ok(stored)
+    }
+
+    /// 할 일 하나의 이벤트 (버전 순)
+    pub async fn stream(&self, stream_id: Uuid) -> Vec<StoredEvent> {
+        let inner = self.inner.lock().await;
+        inner
+            .events
+            .iter()
+            .filter(|event| event.stream_id == stream_id)
+            .cloned()
+            .collect()
+    }
+
+    /// `after`보다 뒤의 모든 이벤트 (순번 순)
+    pub async fn read_all(&self, after: u64) -> Vec<StoredEvent> {
+        let inner = self.inner.lock().await;
+        inner
+            .events
+            .get(after as usize..)
+            .unwrap_or_default()
+            .to_vec()
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn created(title: &str) -> TodoEvent {
+        TodoEvent::Created {
+            title: title.to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn appends_check_the_expected_version() {
+        let store = EventStore::memory();
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+
+        store.append(a, 0, vec![created("a")]).await.unwrap();
+        let stored = store
+            .append(b, 0, vec![created("b"), TodoEvent::Completed])
+            .await
+            .unwrap();
+        assert_eq!(
+            stored
+                .iter()
+                .map(|e| (e.sequence, e.version))
+                .collect::<Vec<_>>(),
+            [(2, 1), (3, 2)]
+        );
+
+        // 같은 버전을 기준으로 한 두 번째 쓰기는 거절
+        let err = store
+            .append(a, 0, vec![TodoEvent::Deleted])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ApiError::Conflict(_)));
+
+        assert_eq!(store.stream(a).await.len(), 1);
+        assert_eq!(store.read_all(1).await, stored);
+        assert!(store.read_all(3).await.is_empty());
+        assert!(store.read_all(99).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn file_store_survives_reopening() {
+        let path = std::env::temp_dir().join(format!(
+            "example-event-sourcing-{}.jsonl",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let id = Uuid::new_v4();
+
+        let store = EventStore::file(&path).await.unwrap();
+        store.append(id, 0, vec![created("a")]).await.unwrap();
+        store
+            .append(id, 1, vec![TodoEvent::Completed])
+            .await
+            .unwrap();
+
+        let reopened = EventStore::file(&path).await.unwrap();
+        assert_eq!(reopened.read_all(0).await, store.read_all(0).await);
+        // 버전과 순번도 이어서 매김
+        let stored = reopened
+            .append(id, 2, vec![TodoEvent::Deleted])
+            .await
+            .unwrap();
+        assert_eq!((stored[0].sequence, stored[0].version), (3, 3));
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 3);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,463 @@
+//! 이벤트 소싱 + CQRS 미니 예제 (할 일 목록)
+//!
+//! 현재 상태 대신 "일어난 일"(이벤트)을 저장하고, 상태는 이벤트로부터 계산
+//!
+//! • 쓰기(command): `POST /todos`, `POST /todos/{id}/rename|complete|reopen`, `DELETE /todos/{id}`
+//!   → 이벤트를 읽어 `TodoAggregate`를 만들고, 명령을 검증해 새 이벤트를 `EventStore`에 추가
+//! • 읽기(query): `GET /todos`, `GET /todos/{id}`, `GET /stats`
+//!   → 이벤트를 적용해 둔 읽기 모델(`Projections`)에서 바로 응답
+//! • `GET /todos/{id}/events`: 할 일 하나의 전체 이력
+//! • `POST /admin/replay`: 읽기 모델을 버리고 모든 이벤트로 다시 만듦
+//!
+//! ```not_rust
+//! cargo run -p example-event-sourcing                                  # 메모리
+//! EVENT_STORE_FILE=events.jsonl cargo run -p example-event-sourcing   # JSON Lines 파일
+//! ```
+
+mod aggregate;
+mod event_store;
+mod projection;
+
+use aggregate::{TodoAggregate, TodoCommand};
+use axum::{
+    extract::{FromRef, Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use event_store::{EventStore, StoredEvent};
+use example_common_errors::ApiError;
+use projection::{Projections, TodoStats, TodoView};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use uuid::Uuid;
+
+type SharedProjections = Arc<RwLock<Projections>>;
+
+#[derive(Clone, FromRef)]
+struct AppState {
+    store: EventStore,
+    projections: SharedProjections,
+}
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let store = match std::env::var("EVENT_STORE_FILE") {
+        Ok(path) => EventStore::file(&path)
+            .await
+            .unwrap_or_else(|err| panic!("{path}: {err}")),
+        Err(_) => EventStore::memory(),
+    };
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(store).await).await.unwrap();
+}
+
+// 시작할 때 저장된 이벤트로 읽기 모델을 만듦
+async fn app(store: EventStore) -> Router {
+    let projections = Projections::rebuild(&store.read_all(0).await);
+    tracing::debug!(position = projections.position, "projections rebuilt");
+    let state = AppState {
+        store,
+        projections: Arc::new(RwLock::new(projections)),
+    };
+
+    Router::new()
+        .route("/todos", get(list_todos).post(create_todo))
+        .route("/todos/{id}", get(get_todo).delete(delete_todo))
+        .route("/todos/{id}/rename", post(rename_todo))
+        .route("/todos/{id}/complete", post(complete_todo))
+        .route("/todos/{id}/reopen", post(reopen_todo))
+        .route("/todos/{id}/events", get(todo_events))
+        .route("/stats", get(stats))
+        .route("/admin/replay", post(replay))
+        .with_state(state)
+}
+
+// --- ✍️ 명령
+
+#[derive(Debug, Deserialize)]
+struct TitleInput {
+    title: String,
+}
+
+/// 명령 처리 결과 (이벤트가 없으면 이미 원하는 상태였다는 뜻)
+#[derive(Debug, Serialize)]
+struct CommandResult {
+    id: Uuid,
+    version: u64,
+    events: Vec<StoredEvent>,
+}
+
+/// POST /todos
+async fn create_todo(
+    State(state): State<AppState>,
+    Json(input): Json<TitleInput>,
+) -> Result<impl IntoResponse, ApiError> {
+    let id = Uuid::new_v4();
+    let result = execute(&state, id, None, TodoCommand::Create { title: input.title }).await?;
+    let location = format!("/todos/{id}");
+    Ok((
+        StatusCode::CREATED,
+        [(header::LOCATION, location)],
+        Json(result),
+    ))
+}
+
+/// POST /todos/{id}/rename
+async fn rename_todo(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(input): Json<TitleInput>,
+) -> Result<Json<CommandResult>, ApiError> {
+    let command = TodoCommand::Rename { title: input.title };
+    execute(&state, id, if_match(&headers)?, command)
+        .await
+        .map(Json)
+}
+
+/// POST /todos/{id}/complete
+async fn complete_todo(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<Json<CommandResult>, ApiError> {
+    execute(&state, id, if_match(&headers)?, TodoCommand::Complete)
+        .await
+        .map(Json)
+}
+
+/// POST /todos/{id}/reopen
+async fn reopen_todo(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<Json<CommandResult>, ApiError> {
+    execute(&state, id, if_match(&headers)?, TodoCommand::Reopen)
+        .await
+        .map(Json)
+}
+
+/// DELETE /todos/{id}
+async fn delete_todo(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<Json<CommandResult>, ApiError> {
+    execute(&state, id, if_match(&headers)?, TodoCommand::Delete)
+        .await
+        .map(Json)
+}
+
+/// 이벤트 읽기 → 상태 복원 → 명령 검증 → 새 이벤트 추가 → 읽기 모델 갱신
+///
+/// `expected_version`(`If-Match`)을 주면 클라이언트가 본 버전과 다를 때 409
+async fn execute(
+    state: &AppState,
+    id: Uuid,
+    expected_version: Option<u64>,
+    command: TodoCommand,
+) -> Result<CommandResult, ApiError> {
+    let history = state.store.stream(id).await;
+    let aggregate = TodoAggregate::from_events(history.iter().map(|stored| &stored.event));
+    if let Some(expected) = expected_version {
+        if expected != aggregate.version {
+            return Err(ApiError::Conflict(format!(
+                "todo {id} is at version {}, expected {expected}",
+                aggregate.version
+            )));
+        }
+    }
+
+    let events = aggregate.handle(command)?;
+    if events.is_empty() {
+        return Ok(CommandResult {
+            id,
+            version: aggregate.version,
+            events: Vec::new(),
+        });
+    }
+
+    // 읽은 뒤에 다른 요청이 이벤트를 추가했으면 여기서 409
+    let stored = state.store.append(id, aggregate.version, events).await?;
+    catch_up(state).await;
+
+    Ok(CommandResult {
+        id,
+        version: stored
+            .last()
+            .map_or(aggregate.version, |event| event.version),
+        events: stored,
+    })
+}
+
+/// 읽기 모델이 아직 적용하지 않은 이벤트를 순번 순서대로 적용
+///
+/// 동시에 들어온 명령의 이벤트도 저장된 순서대로 반영됨
+async fn catch_up(state: &AppState) {
+    let position = state.projections.read().unwrap().position;
+    let events = state.store.read_all(position).await;
+    let mut projections = state.projections.write().unwrap();
+    for event in &events {
+        projections.apply(event);
+    }
+}
+
+// `If-Match: "3"` 또는 `If-Match: 3`
+fn if_match(headers: &HeaderMap) -> Result<Option<u64>, ApiError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    value
+        .to_str()
+        .ok()
+        .and_then(|value| value.trim().trim_matches('"').parse().ok())
+        .map(Some)
+        .ok_or_else(|| ApiError::Validation("If-Match must be a version number".to_owned()))
+}
+
+// --- 📖 조회 (읽기 모델만 사용)
+
+/// GET /todos
+async fn list_todos(State(projections): State<SharedProjections>) -> Json<Vec<TodoView>> {
+    let projections = projections.read().unwrap();
+    Json(projections.todos.values().cloned().collect())
+}
+
+/// GET /todos/{id}
+async fn get_todo(
+    State(projections): State<SharedProjections>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let todo = projections
+        .read()
+        .unwrap()
+        .todos
+        .get(&id)
+        .cloned()
+        .ok_or_else(|| ApiError::NotFound(format!("todo {id} not found")))?;
+    let etag = format!("\"{}\"", todo.version);
+    Ok(([(header::ETAG, etag)], Json(todo)))
+}
+
+/// GET /stats
+async fn stats(State(projections): State<SharedProjections>) -> Json<TodoStats> {
+    Json(projections.read().unwrap().stats.clone())
+}
+
+/// GET /todos/{id}/events (삭제된 할 일의 이력도 남아 있음)
+async fn todo_events(
+    State(store): State<EventStore>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<StoredEvent>>, ApiError> {
+    let events = store.stream(id).await;
+    if events.is_empty() {
+        return Err(ApiError::NotFound(format!("todo {id} not found")));
+    }
+    Ok(Json(events))
+}
+
+#[derive(Debug, Serialize)]
+struct ReplayResult {
+    events_replayed: usize,
+    position: u64,
+    stats: TodoStats,
+}
+
+/// POST /admin/replay (데모라 인증 없음, 실제로는 관리자만 호출할 수 있어야 함)
+async fn replay(State(state): State<AppState>) -> Json<ReplayResult> {
+    let events = state.store.read_all(0).await;
+    let rebuilt = Projections::rebuild(&events);
+    let result = ReplayResult {
+        events_replayed: events.len(),
+        position: rebuilt.position,
+        stats: rebuilt.stats.clone(),
+    };
+    tracing::info!(events = events.len(), "projections rebuilt");
+
+    // 다시 만드는 동안 추가된 이벤트는 다음 catch_up에서 적용됨 (position 이후부터)
+    *state.projections.write().unwrap() = rebuilt;
+    Json(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    async fn send(app: &Router, request: Request<Body>) -> (StatusCode, Value) {
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    fn request(method: &str, uri: &str, body: Option<Value>) -> Request<Body> {
+        let builder = Request::builder().method(method).uri(uri);
+        match body {
+            Some(body) => builder
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => builder.body(Body::empty()),
+        }
+        .unwrap()
+    }
+
+    async fn create(app: &Router, title: &str) -> String {
+        let (status, result) = send(
+            app,
+            request("POST", "/todos", Some(json!({ "title": title }))),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        result["id"].as_str().unwrap().to_owned()
+    }
+
+    #[tokio::test]
+    async fn commands_update_the_read_models() {
+        let app = app(EventStore::memory()).await;
+        let milk = create(&app, "buy milk").await;
+        let docs = create(&app, "write docs").await;
+
+        let (status, result) = send(
+            &app,
+            request("POST", &format!("/todos/{milk}/complete"), None),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(result["version"], 2);
+        assert_eq!(result["events"][0]["event"]["type"], "completed");
+
+        // 이미 완료된 할 일 → 이벤트 없이 성공
+        let (_, result) = send(
+            &app,
+            request("POST", &format!("/todos/{milk}/complete"), None),
+        )
+        .await;
+        assert_eq!(result["events"], json!([]));
+
+        let rename = request(
+            "POST",
+            &format!("/todos/{docs}/rename"),
+            Some(json!({ "title": "write more docs" })),
+        );
+        assert_eq!(send(&app, rename).await.0, StatusCode::OK);
+
+        let (_, todo) = send(&app, request("GET", &format!("/todos/{docs}"), None)).await;
+        assert_eq!(todo["title"], "write more docs");
+        assert_eq!(todo["version"], 2);
+        let (_, stats) = send(&app, request("GET", "/stats", None)).await;
+        assert_eq!(stats, json!({ "total": 2, "completed": 1, "active": 1 }));
+
+        assert_eq!(
+            send(&app, request("DELETE", &format!("/todos/{milk}"), None))
+                .await
+                .0,
+            StatusCode::OK
+        );
+        let (_, todos) = send(&app, request("GET", "/todos", None)).await;
+        assert_eq!(todos.as_array().unwrap().len(), 1);
+        let (status, _) = send(&app, request("GET", &format!("/todos/{milk}"), None)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // 삭제돼도 이력은 남아 있음
+        let (_, events) = send(&app, request("GET", &format!("/todos/{milk}/events"), None)).await;
+        let types: Vec<_> = events
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["event"]["type"].clone())
+            .collect();
+        assert_eq!(types, ["created", "completed", "deleted"]);
+    }
+
+    #[tokio::test]
+    async fn invalid_and_stale_commands_are_rejected() {
+        let app = app(EventStore::memory()).await;
+        let id = create(&app, "a").await;
+
+        let (status, _) = send(
+            &app,
+            request("POST", "/todos", Some(json!({ "title": " " }))),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        let missing = format!("/todos/{}/complete", Uuid::new_v4());
+        assert_eq!(
+            send(&app, request("POST", &missing, None)).await.0,
+            StatusCode::NOT_FOUND
+        );
+
+        // 클라이언트가 본 버전(1)이 현재 버전과 같을 때만 적용
+        let complete = |version: &str| {
+            let mut request = request("POST", &format!("/todos/{id}/complete"), None);
+            request
+                .headers_mut()
+                .insert(header::IF_MATCH, version.parse().unwrap());
+            request
+        };
+        assert_eq!(send(&app, complete("\"1\"")).await.0, StatusCode::OK);
+        assert_eq!(send(&app, complete("\"1\"")).await.0, StatusCode::CONFLICT);
+        assert_eq!(
+            send(&app, complete("latest")).await.0,
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+
+    #[tokio::test]
+    async fn replay_rebuilds_the_same_read_models() {
+        let store = EventStore::memory();
+        let app = app(store.clone()).await;
+        let a = create(&app, "a").await;
+        create(&app, "b").await;
+        send(&app, request("POST", &format!("/todos/{a}/complete"), None)).await;
+
+        let (_, before) = send(&app, request("GET", "/todos", None)).await;
+        let (status, result) = send(&app, request("POST", "/admin/replay", None)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(result["events_replayed"], 3);
+        assert_eq!(result["position"], 3);
+        assert_eq!(
+            result["stats"],
+            json!({ "total": 2, "completed": 1, "active": 1 })
+        );
+        let (_, after) = send(&app, request("GET", "/todos", None)).await;
+        assert_eq!(before, after);
+
+        // 재시작해도 같은 저장소에서 같은 읽기 모델이 만들어짐
+        let restarted = super::app(store).await;
+        let (_, restored) = send(&restarted, request("GET", "/todos", None)).await;
+        assert_eq!(restored, before);
+    }
+}
+
+// 🧪 테스트 방법
+//
+// > ID=$(curl -s localhost:3000/todos -H 'content-type: application/json' -d '{"title":"buy milk"}' | jq -r .id)
+// > curl -X POST localhost:3000/todos/$ID/complete -H 'If-Match: "1"'
+// > curl -X POST localhost:3000/todos/$ID/complete -H 'If-Match: "1"'   → 409 (이미 버전 2)
+// > curl -X POST localhost:3000/todos/$ID/rename -H 'content-type: application/json' -d '{"title":"buy oat milk"}'
+// > curl localhost:3000/todos
+// > curl localhost:3000/stats
+// > curl localhost:3000/todos/$ID/events
+// > curl -X POST localhost:3000/admin/replay
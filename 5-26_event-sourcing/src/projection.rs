@@ -0,0 +1,153 @@
+//! 📖 읽기 모델 (프로젝션)
+//!
+//! • 이벤트를 순번 순서로 적용해서 GET 핸들러가 바로 응답할 수 있는 모양으로 유지
+//!   → `todos`: 할 일 목록 (삭제된 할 일은 빠짐)
+//!   → `stats`: 전체 / 완료 / 남은 개수
+//! • 읽기 모델은 언제든 버리고 이벤트로부터 다시 만들 수 있음 (`rebuild`)
+//!   → 화면에 필요한 모양이 바뀌면 프로젝션 코드만 고치고 `/admin/replay`
+//! • `position`: 마지막으로 적용한 순번 (이미 적용한 이벤트는 건너뜀)
+
+use crate::{aggregate::TodoEvent, event_store::StoredEvent};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TodoView {
+    pub id: Uuid,
+    pub title: String,
+    pub completed: bool,
+    /// 명령을 보낼 때 `If-Match`로 쓰는 버전
+    pub version: u64,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TodoStats {
+    pub total: u64,
+    pub completed: u64,
+    pub active: u64,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Projections {
+    pub todos: BTreeMap<Uuid, TodoView>,
+    pub stats: TodoStats,
+    pub position: u64,
+}
+
+impl Projections {
+    pub fn rebuild<'a>(events: impl IntoIterator<Item = &'a StoredEvent>) -> Self {
+        let mut projections = Self::default();
+        for event in events {
+            projections.apply(event);
+        }
+        projections
+    }
+
+    pub fn apply(&mut self, stored: &StoredEvent) {
+        if stored.sequence <= self.position {
+            return;
+        }
+        self.position = stored.sequence;
+
+        let id = stored.stream_id;
+        if let TodoEvent::Created { title } = &stored.event {
+            self.todos.insert(
+                id,
+                TodoView {
+                    id,
+                    title: title.clone(),
+                    completed: false,
+                    version: stored.version,
+                    created_at: stored.recorded_at,
+                    updated_at: stored.recorded_at,
+                },
+            );
+            self.stats.total += 1;
+            self.stats.active += 1;
+            return;
+        }
+
+        let Some(todo) = self.todos.get_mut(&id) else {
+            tracing::warn!(%id, sequence = stored.sequence, "event for unknown todo");
+            return;
+        };
+        todo.version = stored.version;
+        todo.updated_at = stored.recorded_at;
+
+        match &stored.event {
+            TodoEvent::Created { .. } => unreachable!("handled above"),
+            TodoEvent::Renamed { title } => todo.title.clone_from(title),
+            TodoEvent::Completed => {
+                todo.completed = true;
+                self.stats.completed += 1;
+                self.stats.active -= 1;
+            }
+            TodoEvent::Reopened => {
+                todo.completed = false;
+                self.stats.completed -= 1;
+                self.stats.active += 1;
+            }
+            TodoEvent::Deleted => {
+                if todo.completed {
+                    self.stats.completed -= 1;
+                } else {
+                    self.stats.active -= 1;
+                }
+                self.stats.total -= 1;
+                self.todos.remove(&id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored(sequence: u64, stream_id: Uuid, version: u64, event: TodoEvent) -> StoredEvent {
+        StoredEvent {
+            sequence,
+            stream_id,
+            version,
+            recorded_at: 1_700_000_000 + sequence,
+            event,
+        }
+    }
+
+    #[test]
+    fn projections_follow_the_event_stream() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let events = [
+            stored(1, a, 1, TodoEvent::Created { title: "a".into() }),
+            stored(2, b, 1, TodoEvent::Created { title: "b".into() }),
+            stored(3, a, 2, TodoEvent::Completed),
+            stored(4, b, 2, TodoEvent::Renamed { title: "b2".into() }),
+            stored(5, a, 3, TodoEvent::Deleted),
+        ];
+
+        let projections = Projections::rebuild(&events);
+        assert_eq!(projections.position, 5);
+        assert_eq!(
+            projections.stats,
+            TodoStats {
+                total: 1,
+                completed: 0,
+                active: 1
+            }
+        );
+        let todo = &projections.todos[&b];
+        assert_eq!((todo.title.as_str(), todo.version), ("b2", 2));
+        assert_eq!(todo.updated_at, 1_700_000_004);
+        assert!(!projections.todos.contains_key(&a));
+
+        // 이미 적용한 이벤트를 다시 적용해도 그대로
+        let mut again = Projections::rebuild(&events);
+        for event in &events {
+            again.apply(event);
+        }
+        assert_eq!(again, projections);
+    }
+}
@@ -0,0 +1,314 @@
+//! 🔁 `Idempotency-Key` 미들웨어 (메모리 저장소)
+//!
+//! 9-02_tokio-redis의 미들웨어와 같은 흐름을 Redis 대신 메모리에 저장
+//!
+//! 1. 요청 본문을 버퍼링해서 fingerprint(메서드 + 경로 + 본문의 SHA-256)를 만듦
+//! 2. 키가 처음이면 "처리 중"으로 표시하고 핸들러 실행 → 응답(상태, 헤더, 본문)을 저장
+//! 3. TTL 안에 같은 키로 다시 오면
+//!    → fingerprint가 같으면 핸들러를 실행하지 않고 저장된 응답을 재전송 (`idempotent-replayed: true`)
+//!    → fingerprint가 다르면 409 (같은 키를 다른 요청에 재사용한 클라이언트 버그)
+//!    → 아직 처리 중이면 409 + `Retry-After`
+//!
+//! 5xx 응답은 저장하지 않음 → 클라이언트가 같은 키로 다시 시도하면 핸들러가 다시 실행됨
+//! 클라이언트가 연결을 끊어 핸들러가 중간에 취소돼도 "처리 중" 표시는 지워짐 (`InFlightGuard`)
+
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{task::JoinHandle, time::Instant};
+
+pub const IDEMPOTENCY_KEY: &str = "idempotency-key";
+
+/// fingerprint를 만들기 위해 버퍼링할 요청 본문의 최대 크기
+const MAX_REQUEST_BYTES: usize = 1024 * 1024;
+
+/// 저장할 응답 본문의 최대 크기
+const MAX_RESPONSE_BYTES: usize = 64 * 1024;
+
+type Fingerprint = [u8; 32];
+
+#[derive(Debug, Clone)]
+struct StoredResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+#[derive(Debug)]
+enum EntryStatus {
+    InFlight,
+    Completed(StoredResponse),
+}
+
+#[derive(Debug)]
+struct Entry {
+    fingerprint: Fingerprint,
+    expires_at: Instant,
+    status: EntryStatus,
+}
+
+/// `begin`의 결과
+#[derive(Debug)]
+enum Begin {
+    /// 처음 보는 키 → 핸들러 실행
+    Proceed,
+    Replay(StoredResponse),
+    InFlight,
+    Mismatch,
+}
+
+pub struct IdempotencyStore {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl IdempotencyStore {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::default(),
+        }
+    }
+
+    fn begin(&self, key: &str, fingerprint: Fingerprint) -> Begin {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some(entry) if entry.expires_at > now => {
+                if entry.fingerprint != fingerprint {
+                    Begin::Mismatch
+                } else {
+                    match &entry.status {
+                        EntryStatus::InFlight => Begin::InFlight,
+                        EntryStatus::Completed(stored) => Begin::Replay(stored.clone()),
+                    }
+                }
+            }
+            // 없거나 만료됨
+            _ => {
+                entries.insert(
+                    key.to_owned(),
+                    Entry {
+                        fingerprint,
+                        expires_at: now + self.ttl,
+                        status: EntryStatus::InFlight,
+                    },
+                );
+                Begin::Proceed
+            }
+        }
+    }
+
+    fn complete(&self, key: &str, stored: StoredResponse) {
+        if let Some(entry) = self.entries.lock().unwrap().get_mut(key) {
+            entry.status = EntryStatus::Completed(stored);
+        }
+    }
+
+    fn abandon(&self, key: &str) {
+        let mut entries = self.entries.lock().unwrap();
+        if matches!(entries.get(key), Some(e) if matches!(e.status, EntryStatus::InFlight)) {
+            entries.remove(key);
+        }
+    }
+
+    /// 만료된 키를 지우고 지운 수를 반환
+    pub fn purge_expired(&self) -> usize {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        let before = entries.len();
+        entries.retain(|_, entry| entry.expires_at > now);
+        before - entries.len()
+    }
+}
+
+/// `every`마다 만료된 키를 지우는 백그라운드 태스크
+pub fn spawn_purge(store: Arc<IdempotencyStore>, every: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        loop {
+            interval.tick().await;
+            let purged = store.purge_expired();
+            if purged > 0 {
+                tracing::debug!(purged, "purged expired idempotency keys");
+            }
+        }
+    })
+}
+
+// 핸들러가 끝나기 전에 drop되면(취소, 패닉) "처리 중" 표시를 지움
+struct InFlightGuard<'a> {
+    store: &'a IdempotencyStore,
+    key: &'a str,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.store.abandon(self.key);
+    }
+}
+
+/// `route_layer(middleware::from_fn_with_state(store, idempotency))`로 POST 라우트에 등록
+pub async fn idempotency(
+    State(store): State<Arc<IdempotencyStore>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Some(key) = req.headers().get(IDEMPOTENCY_KEY) else {
+        // 헤더가 없으면 일반 요청처럼 처리
+        return next.run(req).await;
+    };
+    let Some(key) = key
+        .to_str()
+        .ok()
+        .filter(|key| (1..=255).contains(&key.len()))
+    else {
+        return (
+            StatusCode::BAD_REQUEST,
+            "idempotency-key must be 1-255 visible ASCII characters",
+        )
+            .into_response();
+    };
+    // 같은 키라도 다른 엔드포인트의 응답이 재사용되지 않도록 메서드와 경로를 포함
+    // (실제 서비스라면 인증된 사용자 ID도 넣어 다른 사용자와 키가 겹치지 않게 함)
+    let key = format!("{} {} {key}", req.method(), req.uri().path());
+
+    let (parts, body) = req.into_parts();
+    let Ok(body) = to_bytes(body, MAX_REQUEST_BYTES).await else {
+        return (StatusCode::PAYLOAD_TOO_LARGE, "request body too large").into_response();
+    };
+    let fingerprint: Fingerprint = Sha256::new()
+        .chain_update(parts.method.as_str())
+        .chain_update([0])
+        .chain_update(parts.uri.path())
+        .chain_update([0])
+        .chain_update(&body)
+        .finalize()
+        .into();
+
+    match store.begin(&key, fingerprint) {
+        Begin::Proceed => {}
+        Begin::Replay(stored) => {
+            tracing::debug!(%key, "replaying stored response");
+            return replay(stored);
+        }
+        Begin::InFlight => {
+            return (
+                StatusCode::CONFLICT,
+                [(header::RETRY_AFTER, HeaderValue::from_static("1"))],
+                "a request with this idempotency key is still being processed",
+            )
+                .into_response();
+        }
+        Begin::Mismatch => {
+            return (
+                StatusCode::CONFLICT,
+                "this idempotency key was already used with a different request",
+            )
+                .into_response();
+        }
+    }
+
+    let guard = InFlightGuard {
+        store: &store,
+        key: &key,
+    };
+    let res = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+    // 서버 에러는 저장하지 않음 → guard가 표시를 지워 다시 시도할 수 있게 함
+    if res.status().is_server_error() {
+        return res;
+    }
+
+    let (parts, body) = res.into_parts();
+    let body = match to_bytes(body, MAX_RESPONSE_BYTES).await {
+        Ok(body) => body,
+        Err(err) => {
+            // 저장할 수 없는 응답 (본문은 이미 읽어 버렸으므로 500)
+            tracing::error!(%err, %key, "failed to buffer response for idempotency");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    store.complete(
+        &key,
+        StoredResponse {
+            status: parts.status,
+            headers: parts.headers.clone(),
+            body: body.clone(),
+        },
+    );
+    // 완료됐으므로 abandon은 아무것도 하지 않음
+    drop(guard);
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+// 저장된 응답으로 새 Response를 만들고, 재전송된 응답임을 헤더로 알림
+fn replay(stored: StoredResponse) -> Response {
+    let mut res = Response::new(Body::from(stored.body));
+    *res.status_mut() = stored.status;
+    *res.headers_mut() = stored.headers;
+    res.headers_mut()
+        .insert("idempotent-replayed", HeaderValue::from_static("true"));
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored(body: &'static str) -> StoredResponse {
+        StoredResponse {
+            status: StatusCode::CREATED,
+            headers: HeaderMap::new(),
+            body: Bytes::from_static(body.as_bytes()),
+        }
+    }
+
+    #[tokio::test]
+    async fn keys_move_from_in_flight_to_completed_and_expire() {
+        tokio::time::pause();
+        let store = IdempotencyStore::new(Duration::from_secs(60));
+
+        assert!(matches!(store.begin("k", [1; 32]), Begin::Proceed));
+        assert!(matches!(store.begin("k", [1; 32]), Begin::InFlight));
+        assert!(matches!(store.begin("k", [2; 32]), Begin::Mismatch));
+
+        store.complete("k", stored("done"));
+        match store.begin("k", [1; 32]) {
+            Begin::Replay(res) => assert_eq!(res.body, "done"),
+            other => panic!("expected a replay but got {other:?}"),
+        }
+        // 완료된 키는 abandon으로 지워지지 않음
+        store.abandon("k");
+        assert!(matches!(store.begin("k", [2; 32]), Begin::Mismatch));
+
+        tokio::time::advance(Duration::from_secs(61)).await;
+        assert_eq!(store.purge_expired(), 1);
+        assert!(matches!(store.begin("k", [2; 32]), Begin::Proceed));
+    }
+
+    #[tokio::test]
+    async fn abandoned_keys_can_be_retried() {
+        let store = IdempotencyStore::new(Duration::from_secs(60));
+        assert!(matches!(store.begin("k", [1; 32]), Begin::Proceed));
+
+        // 핸들러가 취소됨
+        drop(InFlightGuard {
+            store: &store,
+            key: "k",
+        });
+        assert!(matches!(store.begin("k", [1; 32]), Begin::Proceed));
+    }
+}
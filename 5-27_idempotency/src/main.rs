@@ -0,0 +1,258 @@
+//! 🔁 `Idempotency-Key`로 POST 재시도를 안전하게 만드는 예제
+//!
+//! ```not_rust
+//! cargo run -p example-idempotency
+//! ```
+//!
+//! • `POST /payments`: 결제 생성 (재시도되면 두 번 결제되면 안 되는 대표적인 API)
+//! • `GET /payments`: 지금까지 실제로 처리된 결제 목록
+//!
+//! 클라이언트는 요청마다 고유한 키(UUID 등)를 `Idempotency-Key` 헤더로 보내고,
+//! 타임아웃 등으로 다시 보낼 때는 같은 키와 같은 본문을 사용
+//! → 서버는 첫 응답을 `TTL` 동안 저장해 두고 재시도에는 그 응답을 그대로 돌려줌 (`idempotency.rs`)
+
+mod idempotency;
+
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    middleware,
+    response::IntoResponse,
+    routing::post,
+    Json, Router,
+};
+use idempotency::IdempotencyStore;
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+/// 저장된 응답을 재전송하는 기간
+const TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Clone, Default)]
+struct AppState {
+    payments: Arc<Mutex<Vec<Payment>>>,
+}
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let store = Arc::new(IdempotencyStore::new(TTL));
+    idempotency::spawn_purge(store.clone(), Duration::from_secs(60));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(AppState::default(), store))
+        .await
+        .unwrap();
+}
+
+fn app(state: AppState, store: Arc<IdempotencyStore>) -> Router {
+    Router::new()
+        .route("/payments", post(create_payment).get(list_payments))
+        // route_layer라서 라우트가 있는 요청에만 적용, 헤더가 없는 요청과 GET은 그대로 통과
+        .route_layer(middleware::from_fn_with_state(
+            store,
+            idempotency::idempotency,
+        ))
+        .with_state(state)
+}
+
+#[derive(Debug, Deserialize)]
+struct NewPayment {
+    amount: u64,
+    currency: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Payment {
+    id: u64,
+    amount: u64,
+    currency: String,
+}
+
+async fn create_payment(
+    State(state): State<AppState>,
+    Json(new): Json<NewPayment>,
+) -> Result<impl IntoResponse, (StatusCode, &'static str)> {
+    if new.amount == 0 {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, "amount must be positive"));
+    }
+
+    let mut payments = state.payments.lock().unwrap();
+    let payment = Payment {
+        id: payments.len() as u64 + 1,
+        amount: new.amount,
+        currency: new.currency,
+    };
+    payments.push(payment.clone());
+    tracing::info!(id = payment.id, "payment created");
+
+    Ok((
+        StatusCode::CREATED,
+        [(header::LOCATION, format!("/payments/{}", payment.id))],
+        Json(payment),
+    ))
+}
+
+async fn list_payments(State(state): State<AppState>) -> Json<Vec<Payment>> {
+    Json(state.payments.lock().unwrap().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{HeaderMap, Request},
+        routing::post,
+    };
+    use http_body_util::BodyExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::ServiceExt;
+
+    async fn send(
+        app: &Router,
+        key: Option<&str>,
+        body: &str,
+    ) -> (StatusCode, HeaderMap, serde_json::Value) {
+        let mut request = Request::post("/payments").header("content-type", "application/json");
+        if let Some(key) = key {
+            request = request.header("idempotency-key", key);
+        }
+        let response = app
+            .clone()
+            .oneshot(request.body(Body::from(body.to_owned())).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let headers = response.headers().clone();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let body = serde_json::from_slice(&bytes)
+            .unwrap_or_else(|_| String::from_utf8_lossy(&bytes).into());
+        (status, headers, body)
+    }
+
+    fn test_app() -> (Router, AppState) {
+        let state = AppState::default();
+        let store = Arc::new(IdempotencyStore::new(TTL));
+        (app(state.clone(), store), state)
+    }
+
+    const BODY: &str = r#"{"amount":1200,"currency":"KRW"}"#;
+
+    #[tokio::test]
+    async fn retries_replay_the_first_response() {
+        let (app, state) = test_app();
+
+        let (status, headers, first) = send(&app, Some("pay-1"), BODY).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert!(!headers.contains_key("idempotent-replayed"));
+
+        let (status, headers, retried) = send(&app, Some("pay-1"), BODY).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(headers["idempotent-replayed"], "true");
+        assert_eq!(headers[header::LOCATION], "/payments/1");
+        assert_eq!(retried, first);
+
+        // 결제는 한 번만 처리됨, 다른 키는 새 결제
+        assert_eq!(state.payments.lock().unwrap().len(), 1);
+        let (_, _, other) = send(&app, Some("pay-2"), BODY).await;
+        assert_eq!(other["id"], 2);
+
+        // 키가 없으면 매번 새로 처리
+        send(&app, None, BODY).await;
+        send(&app, None, BODY).await;
+        assert_eq!(state.payments.lock().unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn reusing_a_key_with_a_different_body_conflicts() {
+        let (app, state) = test_app();
+        send(&app, Some("pay-1"), BODY).await;
+
+        let (status, _, body) =
+            send(&app, Some("pay-1"), r#"{"amount":9900,"currency":"KRW"}"#).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(
+            body,
+            "this idempotency key was already used with a different request"
+        );
+        assert_eq!(state.payments.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_replayed_until_the_key_expires() {
+        tokio::time::pause();
+        let (app, state) = test_app();
+        let invalid = r#"{"amount":0,"currency":"KRW"}"#;
+
+        // 4xx도 같은 요청에 대한 결과이므로 저장
+        let (status, _, _) = send(&app, Some("pay-1"), invalid).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        let (status, headers, _) = send(&app, Some("pay-1"), invalid).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(headers["idempotent-replayed"], "true");
+
+        // TTL이 지나면 같은 키를 새 요청으로 사용할 수 있음
+        tokio::time::advance(TTL + Duration::from_secs(1)).await;
+        let (status, _, _) = send(&app, Some("pay-1"), BODY).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(state.payments.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn server_errors_are_not_stored() {
+        // 처음 호출만 실패하는 핸들러
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = Router::new()
+            .route(
+                "/payments",
+                post({
+                    let calls = calls.clone();
+                    move || async move {
+                        match calls.fetch_add(1, Ordering::SeqCst) {
+                            0 => (StatusCode::SERVICE_UNAVAILABLE, "try again"),
+                            _ => (StatusCode::CREATED, "created"),
+                        }
+                    }
+                }),
+            )
+            .route_layer(middleware::from_fn_with_state(
+                Arc::new(IdempotencyStore::new(TTL)),
+                idempotency::idempotency,
+            ));
+
+        let (status, _, _) = send(&app, Some("pay-1"), BODY).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+
+        let (status, headers, _) = send(&app, Some("pay-1"), BODY).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert!(!headers.contains_key("idempotent-replayed"));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
+
+// 🧪 테스트 방법
+//
+// cargo test -p example-idempotency
+//
+// curl -i -X POST http://127.0.0.1:3000/payments \
+//   -H 'content-type: application/json' -H 'idempotency-key: 3f1c9e' \
+//   -d '{"amount":1200,"currency":"KRW"}'
+// # 같은 명령을 다시 실행 → 같은 결제 ID + idempotent-replayed: true
+// # 본문을 바꿔서 같은 키로 보내면 409
+//
+// curl http://127.0.0.1:3000/payments
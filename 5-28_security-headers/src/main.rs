@@ -0,0 +1,296 @@
+//! 🛡️ 보안 헤더 미들웨어 예제 (CSP nonce, HSTS, X-Frame-Options ...)
+//!
+//! ```not_rust
+//! cargo run -p example-security-headers
+//! ```
+//!
+//! • `security.rs`의 미들웨어가 모든 응답에 보안 헤더를 붙이고, 응답 종류(HTML 여부)에 따라 구성을 바꿈
+//! • HTML 응답의 CSP에는 요청마다 새 nonce → 핸들러가 `CspNonce`로 받아 MiniJinja 템플릿에 `csp_nonce`로 전달
+//! • 구성은 `SecurityHeaders` 필드로 바꿈 (예: 로컬 HTTP에서는 HSTS 끔)
+
+mod security;
+
+use axum::{
+    extract::{Query, State},
+    http::{header, StatusCode},
+    middleware,
+    response::{Html, IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use minijinja::{context, Environment};
+use security::{CspNonce, SecurityHeaders};
+use serde_json::{json, Value};
+use std::{collections::HashMap, sync::Arc};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let config = SecurityHeaders {
+        // HTTP로 실행하는 예제라서 끔 (HTTPS로 배포할 때는 기본값 사용)
+        hsts: None,
+        ..Default::default()
+    };
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(config)).await.unwrap();
+}
+
+fn app(config: SecurityHeaders) -> Router {
+    let state = AppState {
+        templates: Arc::new(templates()),
+    };
+    Router::new()
+        .route("/", get(index))
+        .route("/api/status", get(status))
+        .route("/static/app.css", get(stylesheet))
+        .layer(middleware::from_fn_with_state(
+            Arc::new(config),
+            security::security_headers,
+        ))
+        .with_state(state)
+}
+
+#[derive(Clone)]
+struct AppState {
+    templates: Arc<Environment<'static>>,
+}
+
+fn templates() -> Environment<'static> {
+    let mut env = Environment::new();
+    env.add_template("index.html", include_str!("../templates/index.html"))
+        .unwrap();
+    env
+}
+
+/// 템플릿마다 nonce를 따로 넘기지 않도록 `csp_nonce`를 항상 추가
+fn render(
+    state: &AppState,
+    nonce: &CspNonce,
+    name: &str,
+    ctx: minijinja::Value,
+) -> Result<Html<String>, AppError> {
+    let ctx = context! { csp_nonce => nonce.0, ..ctx };
+    Ok(Html(state.templates.get_template(name)?.render(ctx)?))
+}
+
+async fn index(
+    State(state): State<AppState>,
+    nonce: CspNonce,
+    Query(query): Query<HashMap<String, String>>,
+) -> Result<Html<String>, AppError> {
+    // 사용자 입력은 MiniJinja가 escape (CSP는 escape를 놓쳤을 때를 위한 두 번째 방어선)
+    let name = query.get("name").map_or("world", String::as_str);
+    render(&state, &nonce, "index.html", context! { name })
+}
+
+async fn status() -> Json<Value> {
+    Json(json!({ "status": "ok" }))
+}
+
+async fn stylesheet() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/css")],
+        "h1 { color: rebeccapurple; }\n",
+    )
+}
+
+#[derive(Debug)]
+enum AppError {
+    Template(minijinja::Error),
+}
+
+impl From<minijinja::Error> for AppError {
+    fn from(err: minijinja::Error) -> Self {
+        Self::Template(err)
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Template(err) => {
+                tracing::error!("template error: {err:#}");
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    async fn send(app: &Router, uri: &str) -> Response {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        app.clone().oneshot(request).await.unwrap()
+    }
+
+    fn header(response: &Response, name: header::HeaderName) -> Option<&str> {
+        response
+            .headers()
+            .get(name)
+            .map(|value| value.to_str().unwrap())
+    }
+
+    async fn text(response: Response) -> String {
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    /// CSP의 `'nonce-...'` 값
+    fn csp_nonce(response: &Response) -> String {
+        let csp = header(response, header::CONTENT_SECURITY_POLICY).unwrap();
+        let start = csp.find("'nonce-").unwrap() + "'nonce-".len();
+        let end = start + csp[start..].find('\'').unwrap();
+        csp[start..end].to_owned()
+    }
+
+    #[tokio::test]
+    async fn html_gets_a_fresh_nonce_that_matches_the_template() {
+        let app = app(SecurityHeaders::default());
+
+        let response = send(&app, "/?name=%3Cscript%3Ealert(1)%3C/script%3E").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(header(&response, header::X_FRAME_OPTIONS), Some("DENY"));
+        assert_eq!(
+            header(&response, header::X_CONTENT_TYPE_OPTIONS),
+            Some("nosniff")
+        );
+        assert_eq!(
+            header(&response, header::STRICT_TRANSPORT_SECURITY),
+            Some("max-age=31536000; includeSubDomains")
+        );
+        assert_eq!(
+            header(&response, header::REFERRER_POLICY),
+            Some("strict-origin-when-cross-origin")
+        );
+
+        let nonce = csp_nonce(&response);
+        assert!(nonce.len() >= 22);
+        let html = text(response).await;
+        assert!(html.contains(&format!("<script nonce=\"{nonce}\">")));
+        assert!(html.contains(&format!("<style nonce=\"{nonce}\">")));
+        assert!(html.contains("Hello, &lt;script&gt;alert(1)&lt;&#x2f;script&gt;"));
+
+        // 요청마다 다른 nonce
+        let other = send(&app, "/").await;
+        assert_ne!(csp_nonce(&other), nonce);
+    }
+
+    #[tokio::test]
+    async fn other_content_types_get_a_locked_down_policy() {
+        let app = app(SecurityHeaders::default());
+
+        for (uri, content_type) in [
+            ("/api/status", "application/json"),
+            ("/static/app.css", "text/css"),
+        ] {
+            let response = send(&app, uri).await;
+            assert_eq!(header(&response, header::CONTENT_TYPE), Some(content_type));
+            assert_eq!(
+                header(&response, header::CONTENT_SECURITY_POLICY),
+                Some("default-src 'none'; frame-ancestors 'none'"),
+                "{uri}"
+            );
+            assert_eq!(header(&response, header::X_FRAME_OPTIONS), None, "{uri}");
+            assert_eq!(
+                header(&response, header::X_CONTENT_TYPE_OPTIONS),
+                Some("nosniff"),
+                "{uri}"
+            );
+            assert!(header(&response, header::STRICT_TRANSPORT_SECURITY).is_some());
+        }
+
+        // 없는 경로(404)에도 적용
+        let response = send(&app, "/missing").await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            header(&response, header::X_CONTENT_TYPE_OPTIONS),
+            Some("nosniff")
+        );
+    }
+
+    #[tokio::test]
+    async fn headers_follow_the_configuration_and_handlers_can_override_them() {
+        let config = SecurityHeaders {
+            hsts: Some(security::Hsts {
+                max_age: std::time::Duration::from_secs(60),
+                include_subdomains: false,
+                preload: true,
+            }),
+            frame_options: Some("SAMEORIGIN"),
+            ..Default::default()
+        };
+        let router = Router::new()
+            .route("/", get(|| async { Html("<p>hi</p>") }))
+            .route(
+                "/private",
+                get(|| async {
+                    (
+                        [(header::REFERRER_POLICY, "no-referrer")],
+                        Html("<p>hi</p>"),
+                    )
+                }),
+            )
+            .layer(middleware::from_fn_with_state(
+                Arc::new(config),
+                security::security_headers,
+            ));
+
+        let response = send(&router, "/").await;
+        assert_eq!(
+            header(&response, header::STRICT_TRANSPORT_SECURITY),
+            Some("max-age=60; preload")
+        );
+        assert_eq!(
+            header(&response, header::X_FRAME_OPTIONS),
+            Some("SAMEORIGIN")
+        );
+
+        let response = send(&router, "/private").await;
+        assert_eq!(
+            header(&response, header::REFERRER_POLICY),
+            Some("no-referrer")
+        );
+
+        let config = SecurityHeaders {
+            hsts: None,
+            frame_options: None,
+            ..Default::default()
+        };
+        let app = app(config);
+        let response = send(&app, "/").await;
+        assert_eq!(header(&response, header::STRICT_TRANSPORT_SECURITY), None);
+        assert_eq!(header(&response, header::X_FRAME_OPTIONS), None);
+        assert!(header(&response, header::CONTENT_SECURITY_POLICY).is_some());
+    }
+}
+
+// 🧪 테스트 방법
+//
+// cargo run -p example-security-headers
+//
+// # HTML: nonce가 들어간 CSP + X-Frame-Options (요청마다 nonce가 바뀜)
+// curl -i http://127.0.0.1:3000/
+//
+// # JSON/CSS: 아무것도 불러오지 못하는 CSP, X-Frame-Options 없음
+// curl -i http://127.0.0.1:3000/api/status
+// curl -i http://127.0.0.1:3000/static/app.css
+//
+// # 브라우저에서 http://127.0.0.1:3000/?name=<script>alert(1)</script>
+// #   → 이름은 escape되어 글자로 표시, nonce 없는 인라인 스크립트는 콘솔에 CSP 위반으로 기록
@@ -0,0 +1,162 @@
+//! 🛡️ 보안 헤더 미들웨어
+//!
+//! | 헤더                        | HTML 응답                       | 그 밖의 응답 (JSON, CSS ...)        |
+//! |-----------------------------|---------------------------------|-------------------------------------|
+//! | `Content-Security-Policy`   | `html_csp` (요청마다 새 nonce)  | `other_csp` (아무것도 불러오지 못함) |
+//! | `X-Frame-Options`           | `frame_options`                 | -                                   |
+//! | `Strict-Transport-Security` | `hsts`                          | `hsts`                              |
+//! | `X-Content-Type-Options`    | `nosniff`                       | `nosniff`                           |
+//! | `Referrer-Policy`           | `referrer_policy`               | `referrer_policy`                   |
+//!
+//! • nonce는 요청 확장(extension)에 `CspNonce`로 넣어 두고, 핸들러가 꺼내서 템플릿에 전달
+//!   → `<script nonce="{{ csp_nonce }}">`만 실행되고 주입된 인라인 스크립트는 막힘
+//! • 핸들러가 이미 넣은 헤더는 덮어쓰지 않음 (라우트별로 다른 정책이 필요할 때)
+
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::{header, request::Parts, HeaderValue, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use rand::{distributions::Alphanumeric, Rng};
+use std::{sync::Arc, time::Duration};
+
+/// CSP 정책 안의 이 자리를 요청마다 만든 nonce로 바꿈
+pub const NONCE_PLACEHOLDER: &str = "{nonce}";
+
+#[derive(Debug, Clone)]
+pub struct SecurityHeaders {
+    /// HTML 응답의 CSP (`{nonce}`를 포함하면 요청마다 새 값으로 바뀜)
+    pub html_csp: String,
+    /// HTML이 아닌 응답의 CSP (브라우저가 JSON 등을 문서로 열었을 때를 대비)
+    pub other_csp: String,
+    /// HTTPS로만 접속하도록 브라우저에 기억시킴 (HTTP 응답에 있으면 브라우저가 무시)
+    pub hsts: Option<Hsts>,
+    /// `DENY` 또는 `SAMEORIGIN`
+    pub frame_options: Option<&'static str>,
+    pub referrer_policy: &'static str,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Hsts {
+    pub max_age: Duration,
+    pub include_subdomains: bool,
+    /// 브라우저에 미리 포함되는 목록(hstspreload.org)에 등록할 때만 사용
+    pub preload: bool,
+}
+
+impl Default for SecurityHeaders {
+    fn default() -> Self {
+        Self {
+            html_csp: "default-src 'self'; script-src 'nonce-{nonce}' 'strict-dynamic'; \
+                style-src 'self' 'nonce-{nonce}'; object-src 'none'; base-uri 'none'; \
+                form-action 'self'; frame-ancestors 'none'"
+                .to_owned(),
+            other_csp: "default-src 'none'; frame-ancestors 'none'".to_owned(),
+            hsts: Some(Hsts {
+                max_age: Duration::from_secs(365 * 24 * 60 * 60),
+                include_subdomains: true,
+                preload: false,
+            }),
+            frame_options: Some("DENY"),
+            referrer_policy: "strict-origin-when-cross-origin",
+        }
+    }
+}
+
+impl Hsts {
+    fn header_value(&self) -> String {
+        let mut value = format!("max-age={}", self.max_age.as_secs());
+        if self.include_subdomains {
+            value.push_str("; includeSubDomains");
+        }
+        if self.preload {
+            value.push_str("; preload");
+        }
+        value
+    }
+}
+
+/// 이번 요청의 CSP nonce (템플릿의 `nonce` 속성에 그대로 사용)
+#[derive(Debug, Clone)]
+pub struct CspNonce(pub String);
+
+impl CspNonce {
+    fn generate() -> Self {
+        // 128비트 이상의 난수, 영숫자는 CSP nonce(base64 문자)에 그대로 쓸 수 있음
+        let nonce = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(24)
+            .map(char::from)
+            .collect();
+        Self(nonce)
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for CspNonce {
+    type Rejection = (StatusCode, &'static str);
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts.extensions.get::<Self>().cloned().ok_or((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "security_headers middleware is not installed",
+        ))
+    }
+}
+
+/// `middleware::from_fn_with_state(Arc<SecurityHeaders>, security_headers)`로 등록
+pub async fn security_headers(
+    State(config): State<Arc<SecurityHeaders>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let nonce = CspNonce::generate();
+    request.extensions_mut().insert(nonce.clone());
+
+    let mut response = next.run(request).await;
+
+    let is_html = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/html"));
+    let headers = response.headers_mut();
+
+    let csp = if is_html {
+        config.html_csp.replace(NONCE_PLACEHOLDER, &nonce.0)
+    } else {
+        config.other_csp.clone()
+    };
+    let mut suite = vec![
+        (header::CONTENT_SECURITY_POLICY, header_value(csp)),
+        (
+            header::X_CONTENT_TYPE_OPTIONS,
+            HeaderValue::from_static("nosniff"),
+        ),
+        (
+            header::REFERRER_POLICY,
+            HeaderValue::from_static(config.referrer_policy),
+        ),
+    ];
+    if let Some(hsts) = &config.hsts {
+        suite.push((
+            header::STRICT_TRANSPORT_SECURITY,
+            header_value(hsts.header_value()),
+        ));
+    }
+    // 다른 사이트의 iframe에 넣는 공격(clickjacking)은 문서에만 해당
+    if let (true, Some(frame_options)) = (is_html, config.frame_options) {
+        suite.push((
+            header::X_FRAME_OPTIONS,
+            HeaderValue::from_static(frame_options),
+        ));
+    }
+    for (name, value) in suite {
+        headers.entry(name).or_insert(value);
+    }
+    response
+}
+
+fn header_value(value: String) -> HeaderValue {
+    HeaderValue::try_from(value).expect("security header values are ASCII")
+}
@@ -0,0 +1,223 @@
+//! 🛰️ PROXY protocol (v1/v2) 예제: 로드 밸런서 뒤에서 진짜 클라이언트 IP 얻기
+//!
+//! ```not_rust
+//! cargo run -p example-proxy-protocol
+//! ```
+//!
+//! • HAProxy, AWS NLB 같은 L4 로드 밸런서 뒤에서는 TCP peer 주소가 로드 밸런서 주소가 됨
+//!   → 로드 밸런서가 연결 맨 앞에 보내는 PROXY protocol 헤더에 원래 주소가 있음
+//! • 5-05_serve-with-hyper의 accept 루프를 바탕으로, 헤더를 먼저 읽고 나머지 스트림을 hyper에 넘김
+//!   → 읽은 주소로 `into_make_service_with_connect_info`를 호출 → 핸들러는 평소처럼 `ConnectInfo<SocketAddr>`
+//! • 신뢰하는 로드 밸런서(`TRUSTED_PROXIES`)에서 온 연결만 헤더를 읽음 (이때는 헤더 필수)
+//!   → 그 밖의 연결은 헤더를 읽지 않으므로 클라이언트가 직접 보낸 가짜 헤더는 HTTP 파싱 에러 (400)
+//! • 헤더가 늦게 오면 `HEADER_TIMEOUT` 후 연결을 닫음 (연결만 열어 두는 공격 방지)
+
+mod proxy_protocol;
+
+use axum::{
+    extract::{ConnectInfo, Request},
+    routing::get,
+    Router,
+};
+use hyper::body::Incoming;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server;
+use std::{
+    convert::Infallible,
+    env,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::net::TcpListener;
+use tower::{Service, ServiceExt};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+/// PROXY 헤더를 기다리는 최대 시간
+const HEADER_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    // 예: TRUSTED_PROXIES=10.0.0.5,10.0.0.6 (기본값은 로컬 테스트용 loopback)
+    let trusted_proxies = env::var("TRUSTED_PROXIES")
+        .unwrap_or_else(|_| "127.0.0.1,::1".to_owned())
+        .split(',')
+        .map(|ip| {
+            ip.trim()
+                .parse()
+                .expect("TRUSTED_PROXIES must be IP addresses")
+        })
+        .collect();
+
+    let listener = TcpListener::bind("127.0.0.1:3000").await.unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    serve(listener, app(), trusted_proxies).await;
+}
+
+fn app() -> Router {
+    Router::new().route(
+        "/",
+        get(
+            |ConnectInfo(client): ConnectInfo<SocketAddr>| async move {
+                format!("Hello {client}\n")
+            },
+        ),
+    )
+}
+
+async fn serve(listener: TcpListener, app: Router, trusted_proxies: Vec<IpAddr>) {
+    let make_service = app.into_make_service_with_connect_info::<SocketAddr>();
+    let trusted_proxies: Arc<[IpAddr]> = trusted_proxies.into();
+
+    loop {
+        let (mut socket, peer) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(err) => {
+                tracing::warn!("failed to accept connection: {err:#}");
+                continue;
+            }
+        };
+        let mut make_service = make_service.clone();
+        let trusted_proxies = trusted_proxies.clone();
+
+        // 헤더 읽기도 연결 task 안에서 → 느린 연결 하나가 accept 루프를 막지 않음
+        tokio::spawn(async move {
+            let client = if trusted_proxies.contains(&peer.ip()) {
+                let header =
+                    tokio::time::timeout(HEADER_TIMEOUT, proxy_protocol::read_header(&mut socket))
+                        .await;
+                match header {
+                    Ok(Ok(source)) => source.unwrap_or(peer),
+                    Ok(Err(err)) => {
+                        tracing::warn!(%peer, "invalid PROXY protocol header: {err}");
+                        return;
+                    }
+                    Err(_) => {
+                        tracing::warn!(%peer, "timed out waiting for PROXY protocol header");
+                        return;
+                    }
+                }
+            } else {
+                peer
+            };
+            tracing::debug!(%peer, %client, "accepted connection");
+
+            // We don't need to call `poll_ready` because `IntoMakeServiceWithConnectInfo` is always
+            // ready.
+            let tower_service = unwrap_infallible(make_service.call(client).await);
+            let hyper_service = hyper::service::service_fn(move |request: Request<Incoming>| {
+                tower_service.clone().oneshot(request)
+            });
+
+            // 헤더 뒤의 바이트는 그대로 남아 있으므로 소켓을 그대로 hyper에 넘김
+            if let Err(err) = server::conn::auto::Builder::new(TokioExecutor::new())
+                .serve_connection_with_upgrades(TokioIo::new(socket), hyper_service)
+                .await
+            {
+                tracing::debug!("failed to serve connection: {err:#}");
+            }
+        });
+    }
+}
+
+fn unwrap_infallible<T>(result: Result<T, Infallible>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(err) => match err {},
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+
+    const REQUEST: &[u8] = b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+
+    async fn spawn_server(trusted_proxies: Vec<IpAddr>) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, app(), trusted_proxies));
+        addr
+    }
+
+    /// 연결 맨 앞에 `prefix`를 보내고 응답 전체를 문자열로 받음
+    async fn send(addr: SocketAddr, prefix: &[u8]) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(&[prefix, REQUEST].concat()).await.unwrap();
+        let mut response = String::new();
+        let _ = stream.read_to_string(&mut response).await;
+        response
+    }
+
+    #[tokio::test]
+    async fn trusted_proxies_expose_the_client_address() {
+        let addr = spawn_server(vec!["127.0.0.1".parse().unwrap()]).await;
+
+        let response = send(addr, b"PROXY TCP4 203.0.113.7 127.0.0.1 56324 3000\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+        assert!(
+            response.ends_with("Hello 203.0.113.7:56324\n"),
+            "{response}"
+        );
+
+        let header = proxy_protocol::v2_header(
+            "[2001:db8::7]:4000".parse().unwrap(),
+            "[2001:db8::1]:443".parse().unwrap(),
+        );
+        let response = send(addr, &header).await;
+        assert!(
+            response.ends_with("Hello [2001:db8::7]:4000\n"),
+            "{response}"
+        );
+
+        // UNKNOWN → 로드 밸런서 자신의 주소
+        let response = send(addr, b"PROXY UNKNOWN\r\n").await;
+        assert!(response.contains("Hello 127.0.0.1:"), "{response}");
+
+        // 헤더 없는 연결은 응답 없이 닫힘
+        let response = send(addr, b"").await;
+        assert_eq!(response, "");
+    }
+
+    #[tokio::test]
+    async fn untrusted_peers_cannot_spoof_the_client_address() {
+        let addr = spawn_server(Vec::new()).await;
+
+        let response = send(addr, b"").await;
+        assert!(response.contains("Hello 127.0.0.1:"), "{response}");
+
+        let response = send(addr, b"PROXY TCP4 203.0.113.7 127.0.0.1 56324 3000\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+        assert!(!response.contains("203.0.113.7"), "{response}");
+    }
+}
+
+// 🧪 테스트 방법
+//
+// cargo run -p example-proxy-protocol
+//
+// # curl이 PROXY v1 헤더를 붙여 줌 (curl 8.2 이상은 --haproxy-clientip로 주소 지정 가능)
+// curl --haproxy-protocol http://127.0.0.1:3000
+// # → Hello 127.0.0.1:xxxxx (curl이 보낸 헤더의 주소)
+//
+// curl --haproxy-protocol --haproxy-clientip 203.0.113.7 http://127.0.0.1:3000
+// # → Hello 203.0.113.7:xxxxx
+//
+// # 직접 헤더 쓰기
+// printf 'PROXY TCP4 198.51.100.2 127.0.0.1 4000 3000\r\nGET / HTTP/1.1\r\nHost: x\r\nConnection: close\r\n\r\n' | nc 127.0.0.1 3000
+// # → Hello 198.51.100.2:4000
+//
+// # 헤더 없이 요청 → loopback은 신뢰하는 로드 밸런서로 설정되어 있으므로 연결이 닫힘
+// curl http://127.0.0.1:3000
+//
+// # HAProxy 설정 예: server app 127.0.0.1:3000 send-proxy-v2
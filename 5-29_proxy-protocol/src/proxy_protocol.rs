@@ -0,0 +1,249 @@
+//! 📨 PROXY protocol 헤더 파서 (v1 텍스트, v2 바이너리)
+//!
+//! 명세: https://www.haproxy.org/download/2.9/doc/proxy-protocol.txt
+//!
+//! • 로드 밸런서가 TCP 연결을 열자마자 원래 클라이언트 주소를 헤더로 먼저 보냄
+//!   → 헤더 뒤부터가 실제 HTTP 바이트이므로 헤더보다 한 바이트도 더 읽으면 안 됨
+//!   → v2는 길이가 헤더에 있고, v1은 `\r\n`까지 한 바이트씩 읽음 (최대 107바이트)
+//! • 결과가 `None`이면 `LOCAL`(v2) / `UNKNOWN`(v1) → 로드 밸런서 자신의 연결(헬스 체크 등)
+//!   → 호출하는 쪽에서 TCP peer 주소를 그대로 사용
+
+use std::{
+    io,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr},
+};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+const V2_SIGNATURE: [u8; 12] = *b"\r\n\r\n\0\r\nQUIT\n";
+/// `PROXY UNKNOWN\r\n`(15바이트)보다 짧으므로 v1/v2 구분용으로 먼저 읽어도 안전
+const PREFIX_LEN: usize = V2_SIGNATURE.len();
+/// v1 헤더 최대 길이 (`\r\n` 포함)
+const V1_MAX_LEN: usize = 107;
+
+/// 연결 맨 앞의 PROXY protocol 헤더를 읽고 원래 클라이언트 주소를 돌려줌
+pub async fn read_header<R>(stream: &mut R) -> io::Result<Option<SocketAddr>>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut prefix = [0; PREFIX_LEN];
+    stream.read_exact(&mut prefix).await?;
+
+    if prefix == V2_SIGNATURE {
+        read_v2(stream).await
+    } else if prefix.starts_with(b"PROXY ") {
+        read_v1(stream, &prefix).await
+    } else {
+        Err(invalid("missing PROXY protocol header"))
+    }
+}
+
+async fn read_v1<R>(stream: &mut R, prefix: &[u8]) -> io::Result<Option<SocketAddr>>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut line = prefix.to_vec();
+    while !line.ends_with(b"\r\n") {
+        if line.len() == V1_MAX_LEN {
+            return Err(invalid("PROXY v1 header is too long"));
+        }
+        line.push(stream.read_u8().await?);
+    }
+    let line = std::str::from_utf8(&line[..line.len() - 2])
+        .map_err(|_| invalid("PROXY v1 header is not ASCII"))?;
+    parse_v1(line)
+}
+
+/// `PROXY TCP4 <src> <dst> <src port> <dst port>` (`\r\n` 제외)
+fn parse_v1(line: &str) -> io::Result<Option<SocketAddr>> {
+    let mut fields = line.split(' ');
+    let (Some("PROXY"), Some(protocol)) = (fields.next(), fields.next()) else {
+        return Err(invalid("malformed PROXY v1 header"));
+    };
+    if protocol == "UNKNOWN" {
+        // 나머지 필드는 무시해야 함 (명세)
+        return Ok(None);
+    }
+
+    let fields: Vec<&str> = fields.collect();
+    let [source, _destination, source_port, _destination_port] = fields[..] else {
+        return Err(invalid("malformed PROXY v1 header"));
+    };
+    let port: u16 = source_port
+        .parse()
+        .map_err(|_| invalid("invalid PROXY v1 port"))?;
+    let ip = match protocol {
+        "TCP4" => source.parse::<Ipv4Addr>().map(Into::into),
+        "TCP6" => source.parse::<Ipv6Addr>().map(Into::into),
+        _ => return Err(invalid("unsupported PROXY v1 protocol")),
+    }
+    .map_err(|_| invalid("invalid PROXY v1 address"))?;
+    Ok(Some(SocketAddr::new(ip, port)))
+}
+
+async fn read_v2<R>(stream: &mut R) -> io::Result<Option<SocketAddr>>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut fixed = [0; 4];
+    stream.read_exact(&mut fixed).await?;
+    let [version_command, family, len_hi, len_lo] = fixed;
+    // 주소 뒤의 TLV(ALPN, SSL 정보 등)까지 읽어서 버림
+    let mut payload = vec![0; u16::from_be_bytes([len_hi, len_lo]).into()];
+    stream.read_exact(&mut payload).await?;
+    parse_v2(version_command, family, &payload)
+}
+
+fn parse_v2(version_command: u8, family: u8, payload: &[u8]) -> io::Result<Option<SocketAddr>> {
+    if version_command >> 4 != 2 {
+        return Err(invalid("unsupported PROXY protocol version"));
+    }
+    match version_command & 0x0f {
+        // LOCAL: 주소 블록은 무시
+        0x0 => return Ok(None),
+        0x1 => {}
+        _ => return Err(invalid("unsupported PROXY v2 command")),
+    }
+
+    match family {
+        // TCP over IPv4: src(4) dst(4) src port(2) dst port(2)
+        0x11 => {
+            let block: &[u8; 12] = address_block(payload)?;
+            let ip = Ipv4Addr::from(<[u8; 4]>::try_from(&block[..4]).unwrap());
+            let port = u16::from_be_bytes([block[8], block[9]]);
+            Ok(Some(SocketAddr::new(ip.into(), port)))
+        }
+        // TCP over IPv6: src(16) dst(16) src port(2) dst port(2)
+        0x21 => {
+            let block: &[u8; 36] = address_block(payload)?;
+            let ip = Ipv6Addr::from(<[u8; 16]>::try_from(&block[..16]).unwrap());
+            let port = u16::from_be_bytes([block[32], block[33]]);
+            Ok(Some(SocketAddr::new(ip.into(), port)))
+        }
+        // UNSPEC, UDP, UNIX 소켓 → HTTP 서버에서 쓸 주소가 없음
+        _ => Ok(None),
+    }
+}
+
+fn address_block<const N: usize>(payload: &[u8]) -> io::Result<&[u8; N]> {
+    payload
+        .get(..N)
+        .and_then(|block| block.try_into().ok())
+        .ok_or_else(|| invalid("PROXY v2 address block is too short"))
+}
+
+fn invalid(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+pub(crate) fn v2_header(source: SocketAddr, destination: SocketAddr) -> Vec<u8> {
+    let mut header = V2_SIGNATURE.to_vec();
+    let (family, mut block) = match (source, destination) {
+        (SocketAddr::V4(src), SocketAddr::V4(dst)) => {
+            (0x11, [src.ip().octets(), dst.ip().octets()].concat())
+        }
+        (SocketAddr::V6(src), SocketAddr::V6(dst)) => {
+            (0x21, [src.ip().octets(), dst.ip().octets()].concat())
+        }
+        _ => unreachable!("source and destination must use the same family"),
+    };
+    block.extend(source.port().to_be_bytes());
+    block.extend(destination.port().to_be_bytes());
+    header.extend([0x21, family]);
+    header.extend((block.len() as u16).to_be_bytes());
+    header.extend(block);
+    header
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 헤더를 읽고 남은 바이트 (HTTP 요청이 그대로 남아 있어야 함)
+    async fn read(input: &[u8]) -> (io::Result<Option<SocketAddr>>, Vec<u8>) {
+        let mut stream = input;
+        let result = read_header(&mut stream).await;
+        (result, stream.to_vec())
+    }
+
+    fn addr(addr: &str) -> Option<SocketAddr> {
+        Some(addr.parse().unwrap())
+    }
+
+    #[tokio::test]
+    async fn parses_v1_headers_without_consuming_the_request() {
+        let (result, rest) =
+            read(b"PROXY TCP4 203.0.113.7 10.0.0.1 56324 443\r\nGET / HTTP/1.1\r\n").await;
+        assert_eq!(result.unwrap(), addr("203.0.113.7:56324"));
+        assert_eq!(rest, b"GET / HTTP/1.1\r\n");
+
+        let (result, _) = read(b"PROXY TCP6 2001:db8::7 2001:db8::1 4000 443\r\n").await;
+        assert_eq!(result.unwrap(), addr("[2001:db8::7]:4000"));
+
+        let (result, rest) = read(b"PROXY UNKNOWN ffff::1 ffff::2 1 2\r\nGET").await;
+        assert_eq!(result.unwrap(), None);
+        assert_eq!(rest, b"GET");
+    }
+
+    #[tokio::test]
+    async fn parses_v2_headers_and_skips_tlvs() {
+        let mut input = v2_header(
+            "203.0.113.7:56324".parse().unwrap(),
+            "10.0.0.1:443".parse().unwrap(),
+        );
+        input.extend(b"GET");
+        let (result, rest) = read(&input).await;
+        assert_eq!(result.unwrap(), addr("203.0.113.7:56324"));
+        assert_eq!(rest, b"GET");
+
+        let input = v2_header(
+            "[2001:db8::7]:4000".parse().unwrap(),
+            "[2001:db8::1]:443".parse().unwrap(),
+        );
+        assert_eq!(read(&input).await.0.unwrap(), addr("[2001:db8::7]:4000"));
+
+        // TLV(타입 0x01 = ALPN "h2")가 붙은 헤더: 길이를 늘리고 뒤에 추가
+        let mut input = v2_header(
+            "198.51.100.2:1234".parse().unwrap(),
+            "10.0.0.1:443".parse().unwrap(),
+        );
+        input[15] += 5;
+        input.extend([0x01, 0x00, 0x02, b'h', b'2']);
+        input.extend(b"GET");
+        let (result, rest) = read(&input).await;
+        assert_eq!(result.unwrap(), addr("198.51.100.2:1234"));
+        assert_eq!(rest, b"GET");
+
+        // LOCAL 명령 (헬스 체크): 주소 없음
+        let mut input = V2_SIGNATURE.to_vec();
+        input.extend([0x20, 0x00, 0x00, 0x00]);
+        input.extend(b"GET");
+        let (result, rest) = read(&input).await;
+        assert_eq!(result.unwrap(), None);
+        assert_eq!(rest, b"GET");
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_headers() {
+        let long_line = format!("PROXY TCP4 {}\r\n", "1".repeat(100));
+        let mut truncated_v2 = V2_SIGNATURE.to_vec();
+        truncated_v2.extend([0x21, 0x11, 0x00, 0x04, 1, 2, 3, 4]);
+        let mut bad_version = V2_SIGNATURE.to_vec();
+        bad_version.extend([0x11, 0x11, 0x00, 0x00]);
+
+        for input in [
+            &b"GET / HTTP/1.1\r\nHost: x\r\n\r\n"[..],
+            b"PROXY TCP4 203.0.113.7 10.0.0.1 56324\r\n",
+            b"PROXY TCP4 2001:db8::7 10.0.0.1 56324 443\r\n",
+            b"PROXY TCP4 203.0.113.7 10.0.0.1 99999 443\r\n",
+            b"PROXY UDP4 203.0.113.7 10.0.0.1 56324 443\r\n",
+            b"PROXY TCP4 203.0.113.7",
+            long_line.as_bytes(),
+            &truncated_v2,
+            &bad_version,
+        ] {
+            let (result, _) = read(input).await;
+            assert!(result.is_err(), "{:?}", String::from_utf8_lossy(input));
+        }
+    }
+}
@@ -0,0 +1,376 @@
+//! 🐌 요청 크기 제한 + slow-loris 방어 예제
+//!
+//! ```not_rust
+//! cargo run -p example-request-limits
+//! ```
+//!
+//! • 5-05_serve-with-hyper의 accept 루프를 바탕으로 연결 단위 제한은 hyper 설정, 요청 단위 제한은 tower 레이어로 처리
+//! • 연결만 하고 아무것도 보내지 않는 클라이언트: HTTP 버전 판별용 앞부분을 `header_read_timeout` 안에 받지 못하면 닫음
+//!   → `auto::Builder`의 버전 판별 단계에는 타이머가 없어서 직접 읽음 (`read_preface`)
+//! • 헤더를 천천히 보내는 클라이언트: HTTP/1 `header_read_timeout` → 시간 안에 헤더가 끝나지 않으면 연결을 닫음
+//! • 연결을 잔뜩 여는 클라이언트: 동시 연결 수를 `Semaphore`로 제한 (넘치면 accept하지 않고 대기)
+//! • 연결 하나로 스트림을 잔뜩 여는 클라이언트: HTTP/2 `max_concurrent_streams`
+//! • 큰 본문: `RequestBodyLimitLayer` → `Content-Length`가 크면 본문을 읽기 전에 413
+//!   → chunked 본문도 한도를 넘는 순간 413
+//! • 본문을 천천히 보내거나 처리가 오래 걸리는 요청: `TimeoutLayer` → 요청 전체에 시간 제한, 408
+//!   → 본문은 핸들러 future 안에서 읽으므로 본문 업로드 시간도 포함됨
+
+use axum::{
+    body::Bytes,
+    extract::Request,
+    http::StatusCode,
+    routing::{get, post},
+    Router,
+};
+use hyper::body::Incoming;
+use hyper_util::rt::{TokioExecutor, TokioIo, TokioTimer};
+use hyper_util::server;
+use std::{io, sync::Arc, time::Duration};
+use tokio::{
+    io::AsyncReadExt,
+    net::{TcpListener, TcpStream},
+    sync::Semaphore,
+};
+use tower::Service;
+use tower_http::{limit::RequestBodyLimitLayer, timeout::TimeoutLayer};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[derive(Debug, Clone, Copy)]
+struct Limits {
+    /// 연결 후(또는 keep-alive 중 다음 요청의) 헤더를 다 받을 때까지 기다리는 시간 (HTTP/1)
+    header_read_timeout: Duration,
+    /// 본문 수신 + 핸들러 처리까지 요청 하나에 허용하는 시간
+    request_timeout: Duration,
+    /// 요청 본문 최대 크기 (바이트)
+    max_body: usize,
+    /// HTTP/2 연결 하나에서 동시에 열 수 있는 스트림 수
+    max_concurrent_streams: u32,
+    /// 동시에 처리하는 최대 연결 수
+    max_connections: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            header_read_timeout: Duration::from_secs(10),
+            request_timeout: Duration::from_secs(30),
+            max_body: 64 * 1024,
+            max_concurrent_streams: 32,
+            max_connections: 1024,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let limits = Limits::default();
+    let listener = TcpListener::bind("127.0.0.1:3000").await.unwrap();
+    tracing::debug!(
+        "listening on {} with {limits:?}",
+        listener.local_addr().unwrap()
+    );
+    serve(listener, app(limits), limits).await;
+}
+
+fn app(limits: Limits) -> Router {
+    Router::new()
+        .route("/", get(|| async { "Hello!\n" }))
+        .route(
+            "/upload",
+            post(|body: Bytes| async move { format!("received {} bytes\n", body.len()) }),
+        )
+        // 처리가 오래 걸리는 핸들러 → 408
+        .route(
+            "/slow",
+            get(|| async {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                "done\n"
+            }),
+        )
+        // 앞쪽이 바깥 레이어 (`TimeoutLayer`는 안쪽 응답 본문이 `Default`여야 해서 안쪽에 둠)
+        .layer((
+            RequestBodyLimitLayer::new(limits.max_body),
+            TimeoutLayer::with_status_code(StatusCode::REQUEST_TIMEOUT, limits.request_timeout),
+        ))
+}
+
+async fn serve(listener: TcpListener, app: Router, limits: Limits) {
+    let mut builder = server::conn::auto::Builder::new(TokioExecutor::new());
+    // 타임아웃에는 timer가 필요함
+    builder
+        .http1()
+        .timer(TokioTimer::new())
+        .header_read_timeout(limits.header_read_timeout);
+    builder
+        .http2()
+        .timer(TokioTimer::new())
+        .max_concurrent_streams(limits.max_concurrent_streams)
+        // 응답 없는 연결 정리 (PING에 `keep_alive_timeout` 안에 답하지 않으면 닫음)
+        .keep_alive_interval(Duration::from_secs(20));
+    let builder = Arc::new(builder);
+    let connections = Arc::new(Semaphore::new(limits.max_connections));
+
+    loop {
+        // 허가를 먼저 받음 → 한도에 닿으면 새 연결은 커널의 accept 큐에서 대기
+        let permit = connections.clone().acquire_owned().await.unwrap();
+        let (mut socket, remote_addr) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(err) => {
+                tracing::warn!("failed to accept connection: {err:#}");
+                continue;
+            }
+        };
+        let tower_service = app.clone();
+        let builder = builder.clone();
+
+        tokio::spawn(async move {
+            let _permit = permit;
+            let preface =
+                match tokio::time::timeout(limits.header_read_timeout, read_preface(&mut socket))
+                    .await
+                {
+                    Ok(Ok(preface)) => preface,
+                    Ok(Err(err)) => {
+                        tracing::debug!(%remote_addr, "connection closed: {err:#}");
+                        return;
+                    }
+                    Err(_) => {
+                        tracing::debug!(%remote_addr, "connection closed: no request in time");
+                        return;
+                    }
+                };
+            // 이미 읽은 앞부분을 다시 붙여서 hyper에 넘김 (hyper가 같은 바이트로 버전을 판별)
+            let (read, write) = socket.into_split();
+            let io = tokio::io::join(io::Cursor::new(preface).chain(read), write);
+
+            let hyper_service = hyper::service::service_fn(move |request: Request<Incoming>| {
+                tower_service.clone().call(request)
+            });
+
+            // 헤더 타임아웃으로 끊긴 연결도 여기서 에러로 끝남
+            if let Err(err) = builder
+                .serve_connection_with_upgrades(TokioIo::new(io), hyper_service)
+                .await
+            {
+                tracing::debug!(%remote_addr, "connection closed: {err:#}");
+            }
+        });
+    }
+}
+
+const H2_PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+
+/// HTTP/2 preface인지 알 수 있을 때까지 읽은 앞부분
+///
+/// preface와 달라지는 순간(HTTP/1이면 대개 첫 바이트) 또는 preface 전체를 받으면 끝
+async fn read_preface(socket: &mut TcpStream) -> io::Result<Vec<u8>> {
+    let mut buf = [0; H2_PREFACE.len()];
+    let mut len = 0;
+    while len < buf.len() && buf[..len] == H2_PREFACE[..len] {
+        match socket.read(&mut buf[len..]).await? {
+            0 => return Err(io::ErrorKind::UnexpectedEof.into()),
+            n => len += n,
+        }
+    }
+    Ok(buf[..len].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{net::SocketAddr, time::Instant};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    };
+
+    const LIMITS: Limits = Limits {
+        header_read_timeout: Duration::from_millis(200),
+        request_timeout: Duration::from_millis(300),
+        max_body: 16,
+        max_concurrent_streams: 4,
+        max_connections: 8,
+    };
+
+    async fn spawn_server() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, app(LIMITS), LIMITS));
+        addr
+    }
+
+    /// 연결이 닫힐 때까지 받은 응답 (테스트가 멈추지 않도록 5초 제한)
+    async fn read_response(stream: &mut TcpStream) -> String {
+        let mut response = Vec::new();
+        tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response))
+            .await
+            .expect("server did not close the connection")
+            .unwrap();
+        String::from_utf8(response).unwrap()
+    }
+
+    #[tokio::test]
+    async fn well_behaved_requests_succeed() {
+        let addr = spawn_server().await;
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"POST /upload HTTP/1.1\r\nHost: x\r\nContent-Length: 5\r\nConnection: close\r\n\r\nhello")
+            .await
+            .unwrap();
+        let response = read_response(&mut stream).await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+        assert!(response.ends_with("received 5 bytes\n"), "{response}");
+    }
+
+    #[tokio::test]
+    async fn slow_headers_are_cut_off() {
+        let addr = spawn_server().await;
+
+        // 헤더를 끝내지 않고 한 줄씩 천천히 보내는 slow-loris 클라이언트
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let started = Instant::now();
+        stream.write_all(b"GET / HTTP/1.1\r\n").await.unwrap();
+        for _ in 0..3 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            // 서버가 이미 닫았으면 쓰기가 실패할 수 있음
+            let _ = stream.write_all(b"X-Slow: 1\r\n").await;
+        }
+
+        let response = read_response(&mut stream).await;
+        assert!(!response.contains("200 OK"), "{response}");
+        let elapsed = started.elapsed();
+        assert!(elapsed >= LIMITS.header_read_timeout, "{elapsed:?}");
+        assert!(elapsed < Duration::from_secs(2), "{elapsed:?}");
+    }
+
+    #[tokio::test]
+    async fn silent_connections_are_cut_off() {
+        let addr = spawn_server().await;
+
+        // 연결만 하고 아무것도 보내지 않음
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let started = Instant::now();
+        assert_eq!(read_response(&mut stream).await, "");
+        let elapsed = started.elapsed();
+        assert!(elapsed >= LIMITS.header_read_timeout, "{elapsed:?}");
+        assert!(elapsed < Duration::from_secs(2), "{elapsed:?}");
+
+        // HTTP/2 preface를 중간까지만 보내도 마찬가지
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(&H2_PREFACE[..10]).await.unwrap();
+        assert_eq!(read_response(&mut stream).await, "");
+    }
+
+    #[tokio::test]
+    async fn large_bodies_are_rejected_before_they_are_read() {
+        let addr = spawn_server().await;
+
+        // 본문은 보내지 않음 → Content-Length만 보고 바로 413
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"POST /upload HTTP/1.1\r\nHost: x\r\nContent-Length: 1048576\r\n\r\n")
+            .await
+            .unwrap();
+        let response = read_response(&mut stream).await;
+        assert!(
+            response.starts_with("HTTP/1.1 413 Payload Too Large"),
+            "{response}"
+        );
+
+        // chunked는 읽다가 한도를 넘으면 413
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"POST /upload HTTP/1.1\r\nHost: x\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n20\r\n0123456789abcdef0123456789abcdef\r\n0\r\n\r\n")
+            .await
+            .unwrap();
+        let response = read_response(&mut stream).await;
+        assert!(response.starts_with("HTTP/1.1 413"), "{response}");
+    }
+
+    #[tokio::test]
+    async fn slow_bodies_and_slow_handlers_time_out() {
+        let addr = spawn_server().await;
+
+        // 본문 10바이트 중 2바이트만 보내고 멈춤
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let started = Instant::now();
+        stream
+            .write_all(b"POST /upload HTTP/1.1\r\nHost: x\r\nContent-Length: 10\r\n\r\nhe")
+            .await
+            .unwrap();
+        let response = read_response(&mut stream).await;
+        assert!(
+            response.starts_with("HTTP/1.1 408 Request Timeout"),
+            "{response}"
+        );
+        assert!(started.elapsed() >= LIMITS.request_timeout);
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /slow HTTP/1.1\r\nHost: x\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let response = read_response(&mut stream).await;
+        assert!(response.starts_with("HTTP/1.1 408"), "{response}");
+    }
+
+    #[tokio::test]
+    async fn http2_connections_advertise_the_stream_cap() {
+        let addr = spawn_server().await;
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let (client, connection) = h2::client::handshake(stream).await.unwrap();
+        tokio::spawn(connection);
+
+        let mut client = client.ready().await.unwrap();
+        let request = axum::http::Request::get("http://localhost/")
+            .body(())
+            .unwrap();
+        let (response, _) = client.send_request(request, true).unwrap();
+        assert_eq!(response.await.unwrap().status(), StatusCode::OK);
+
+        // 서버 SETTINGS의 MAX_CONCURRENT_STREAMS
+        assert_eq!(
+            client.current_max_send_streams(),
+            LIMITS.max_concurrent_streams as usize
+        );
+    }
+}
+
+// 🧪 테스트 방법
+//
+// cargo run -p example-request-limits
+//
+// # 정상 요청
+// curl -d 'hello' http://127.0.0.1:3000/upload
+// # → received 5 bytes
+//
+// # 64KiB를 넘는 본문 → 413
+// head -c 100000 /dev/zero | curl -i --data-binary @- http://127.0.0.1:3000/upload
+//
+// # 연결만 하고 아무것도 보내지 않음 → 10초 뒤 연결이 닫힘
+// nc 127.0.0.1 3000
+//
+// # 헤더를 끝내지 않는 slow-loris → 10초 뒤 연결이 닫힘
+// (printf 'GET / HTTP/1.1\r\n'; sleep 15) | nc 127.0.0.1 3000
+//
+// # 본문을 다 보내지 않음 → 30초 뒤 408
+// (printf 'POST /upload HTTP/1.1\r\nHost: x\r\nContent-Length: 10\r\n\r\nhe'; sleep 35) | nc 127.0.0.1 3000
+//
+// # HTTP/2 (prior knowledge)
+// curl --http2-prior-knowledge http://127.0.0.1:3000/
+
+// 💡 실무 팁
+//
+//  • 로드 밸런서/리버스 프록시가 앞에 있으면 그쪽에도 같은 종류의 제한이 있음 → 더 작은 쪽이 먼저 적용
+//  • `max_connections`는 파일 디스크립터 한도(`ulimit -n`)보다 충분히 작게
+//  • 라우트마다 본문 한도가 다르면 `route_layer`로 `RequestBodyLimitLayer`를 따로 적용 (업로드 라우트만 크게)
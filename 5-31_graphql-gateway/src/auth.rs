@@ -0,0 +1,32 @@
+//! 🔑 하위 서비스의 인증 (게이트웨이는 검증하지 않고 헤더를 전달만 함)
+//!
+//! • 각 서비스가 `Authorization: Bearer <token>`을 직접 확인
+//!   → 실제로는 서비스들이 같은 키로 JWT를 검증하는 식 (4-01 jwt 예제)
+//! • 토큰이 없거나 모르는 토큰이면 resolver가 `unauthorized` 에러
+
+use async_graphql::{Context, ID};
+use axum::http::{header::AUTHORIZATION, HeaderMap};
+
+/// 데모용 토큰 → 사용자 id
+const DEMO_TOKENS: [(&str, &str); 2] = [("alice-token", "1"), ("bob-token", "2")];
+
+/// 요청을 보낸 사용자 (인증된 경우에만 schema data에 들어감)
+#[derive(Debug, Clone)]
+pub struct Viewer(pub ID);
+
+pub fn viewer(headers: &HeaderMap) -> Option<Viewer> {
+    let token = headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")?;
+    DEMO_TOKENS
+        .iter()
+        .find(|(known, _)| *known == token)
+        .map(|(_, id)| Viewer(ID::from(*id)))
+}
+
+pub fn require<'a>(ctx: &Context<'a>) -> async_graphql::Result<&'a Viewer> {
+    ctx.data_opt::<Viewer>()
+        .ok_or_else(|| "unauthorized".into())
+}
@@ -0,0 +1,294 @@
+//! 🌐 게이트웨이: 두 서비스의 스키마를 하나로 합쳐서 제공
+//!
+//! ```graphql
+//! type User { id: ID!, name: String!, email: String!, todos: [Todo!]! }   # todos ← todos 서비스
+//! type Todo { id: ID!, title: String!, completed: Boolean!, owner: User } # owner ← users 서비스
+//! type Query { me: User!, users: [User!]!, todos: [Todo!]! }
+//! ```
+//!
+//! • 루트 필드는 해당 서비스로 쿼리를 보내고, 서비스 사이를 잇는 필드(`Todo.owner`, `User.todos`)는
+//!   다른 서비스에서 엔티티를 조회해 붙임
+//!   → `DataLoader`로 모아서 보내므로 todo가 N개여도 users 서비스 요청은 한 번 (N+1 방지)
+//! • 클라이언트의 `Authorization` 헤더를 모든 하위 요청에 그대로 전달 (검증은 각 서비스가)
+//! • 하위 서비스의 GraphQL 에러는 해당 필드의 에러로 클라이언트에 전달
+//! • 하위 쿼리는 각 타입의 스칼라 필드를 모두 요청 (간단한 대신 클라이언트가 고른 필드만 보내지는 않음)
+
+use async_graphql::{
+    dataloader::{DataLoader, Loader},
+    http::GraphiQLSource,
+    ComplexObject, Context, EmptyMutation, EmptySubscription, Object, Schema, SimpleObject, ID,
+};
+use axum::{
+    extract::State,
+    http::{
+        header::{HeaderValue, AUTHORIZATION},
+        HeaderMap,
+    },
+    response::Html,
+    routing::get,
+    Json, Router,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::json;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+const USER_FIELDS: &str = "id name email";
+const TODO_FIELDS: &str = "id title completed ownerId";
+
+/// 하위 서비스의 GraphQL 엔드포인트
+#[derive(Debug, Clone)]
+pub struct Upstreams {
+    pub users: String,
+    pub todos: String,
+}
+
+#[derive(Clone)]
+struct Gateway {
+    schema: Schema<Query, EmptyMutation, EmptySubscription>,
+    http: reqwest::Client,
+    upstreams: Arc<Upstreams>,
+}
+
+pub fn app(upstreams: Upstreams) -> Router {
+    let gateway = Gateway {
+        schema: Schema::new(Query, EmptyMutation, EmptySubscription),
+        http: reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap(),
+        upstreams: Arc::new(upstreams),
+    };
+    Router::new()
+        .route("/graphql", get(graphiql).post(graphql))
+        .with_state(gateway)
+}
+
+// 🧭 GET /graphql → 브라우저에서 쿼리를 써볼 수 있는 GraphiQL
+async fn graphiql() -> Html<String> {
+    Html(GraphiQLSource::build().endpoint("/graphql").finish())
+}
+
+// 📮 POST /graphql
+async fn graphql(
+    State(gateway): State<Gateway>,
+    headers: HeaderMap,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    // 인증 헤더만 전달 (Host, Cookie 등 나머지 헤더는 하위 서비스와 관계없음)
+    let downstream = Downstream {
+        http: gateway.http,
+        upstreams: gateway.upstreams,
+        authorization: headers.get(AUTHORIZATION).cloned(),
+    };
+
+    // 로더는 요청마다 새로 만듦: 캐시가 다른 사용자(다른 토큰)의 요청과 섞이지 않도록
+    let request = request
+        .data(DataLoader::new(
+            UserLoader(downstream.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            TodosByOwnerLoader(downstream.clone()),
+            tokio::spawn,
+        ))
+        .data(downstream);
+    Json(gateway.schema.execute(request).await)
+}
+
+// 📡 하위 서비스 호출 (요청 하나 동안 사용)
+#[derive(Clone)]
+struct Downstream {
+    http: reqwest::Client,
+    upstreams: Arc<Upstreams>,
+    authorization: Option<HeaderValue>,
+}
+
+#[derive(Deserialize)]
+struct DownstreamResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<DownstreamError>,
+}
+
+#[derive(Deserialize)]
+struct DownstreamError {
+    message: String,
+}
+
+impl Downstream {
+    async fn query<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Result<T> {
+        let mut request = self
+            .http
+            .post(url)
+            .json(&json!({ "query": query, "variables": variables }));
+        if let Some(authorization) = &self.authorization {
+            request = request.header(AUTHORIZATION, authorization.clone());
+        }
+
+        let response: DownstreamResponse<T> =
+            request.send().await?.error_for_status()?.json().await?;
+        if let Some(error) = response.errors.into_iter().next() {
+            return Err(error.message.into());
+        }
+        response
+            .data
+            .ok_or_else(|| "empty response from upstream".into())
+    }
+}
+
+// 🧩 합쳐진 스키마의 타입 (하위 서비스 응답을 그대로 역직렬화)
+
+#[derive(Clone, SimpleObject, Deserialize)]
+#[graphql(complex)]
+struct User {
+    id: ID,
+    name: String,
+    email: String,
+}
+
+#[ComplexObject]
+impl User {
+    /// todos 서비스에서 가져옴
+    async fn todos(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Todo>> {
+        let todos = ctx
+            .data_unchecked::<DataLoader<TodosByOwnerLoader>>()
+            .load_one(self.id.clone())
+            .await?;
+        Ok(todos.unwrap_or_default())
+    }
+}
+
+#[derive(Clone, SimpleObject, Deserialize)]
+#[graphql(complex)]
+#[serde(rename_all = "camelCase")]
+struct Todo {
+    id: ID,
+    title: String,
+    completed: bool,
+    /// 클라이언트에게는 `owner`로 노출
+    #[graphql(skip)]
+    owner_id: ID,
+}
+
+#[ComplexObject]
+impl Todo {
+    /// users 서비스에서 가져옴 (사용자가 삭제되었으면 null)
+    async fn owner(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<User>> {
+        ctx.data_unchecked::<DataLoader<UserLoader>>()
+            .load_one(self.owner_id.clone())
+            .await
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn me(&self, ctx: &Context<'_>) -> async_graphql::Result<User> {
+        #[derive(Deserialize)]
+        struct Data {
+            me: User,
+        }
+        let downstream = ctx.data_unchecked::<Downstream>();
+        let query = format!("{{ me {{ {USER_FIELDS} }} }}");
+        let data: Data = downstream
+            .query(&downstream.upstreams.users, &query, json!({}))
+            .await?;
+        Ok(data.me)
+    }
+
+    async fn users(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<User>> {
+        #[derive(Deserialize)]
+        struct Data {
+            users: Vec<User>,
+        }
+        let downstream = ctx.data_unchecked::<Downstream>();
+        let query = format!("{{ users {{ {USER_FIELDS} }} }}");
+        let data: Data = downstream
+            .query(&downstream.upstreams.users, &query, json!({}))
+            .await?;
+        Ok(data.users)
+    }
+
+    async fn todos(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Todo>> {
+        #[derive(Deserialize)]
+        struct Data {
+            todos: Vec<Todo>,
+        }
+        let downstream = ctx.data_unchecked::<Downstream>();
+        let query = format!("{{ todos {{ {TODO_FIELDS} }} }}");
+        let data: Data = downstream
+            .query(&downstream.upstreams.todos, &query, json!({}))
+            .await?;
+        Ok(data.todos)
+    }
+}
+
+// 🔗 엔티티 조회: 같은 요청 안에서 1ms 동안 모인 id를 한 번에 보냄
+
+struct UserLoader(Downstream);
+
+impl Loader<ID> for UserLoader {
+    type Value = User;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, ids: &[ID]) -> Result<HashMap<ID, User>, Self::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Data {
+            users_by_ids: Vec<User>,
+        }
+        tracing::debug!(count = ids.len(), "resolving users");
+        let query = format!("query($ids: [ID!]!) {{ usersByIds(ids: $ids) {{ {USER_FIELDS} }} }}");
+        let data: Data = self
+            .0
+            .query(&self.0.upstreams.users, &query, json!({ "ids": ids }))
+            .await?;
+        Ok(data
+            .users_by_ids
+            .into_iter()
+            .map(|user| (user.id.clone(), user))
+            .collect())
+    }
+}
+
+struct TodosByOwnerLoader(Downstream);
+
+impl Loader<ID> for TodosByOwnerLoader {
+    type Value = Vec<Todo>;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, owner_ids: &[ID]) -> Result<HashMap<ID, Vec<Todo>>, Self::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Data {
+            todos_by_owners: Vec<Todo>,
+        }
+        tracing::debug!(count = owner_ids.len(), "resolving todos by owner");
+        let query = format!(
+            "query($ownerIds: [ID!]!) {{ todosByOwners(ownerIds: $ownerIds) {{ {TODO_FIELDS} }} }}"
+        );
+        let data: Data = self
+            .0
+            .query(
+                &self.0.upstreams.todos,
+                &query,
+                json!({ "ownerIds": owner_ids }),
+            )
+            .await?;
+
+        let mut by_owner: HashMap<ID, Vec<Todo>> = HashMap::new();
+        for todo in data.todos_by_owners {
+            by_owner
+                .entry(todo.owner_id.clone())
+                .or_default()
+                .push(todo);
+        }
+        Ok(by_owner)
+    }
+}
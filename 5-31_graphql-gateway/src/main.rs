@@ -0,0 +1,178 @@
+//! GraphQL 게이트웨이(스키마 합치기) 예제
+//!
+//! 작은 GraphQL 서비스 두 개를 하나의 엔드포인트 뒤에 묶음
+//!
+//! 🧭 구성 (한 프로세스 안에서 세 서버를 모두 실행)
+//!  [클라이언트] → POST localhost:3000/graphql  (게이트웨이, `gateway.rs`)
+//!                   ├→ localhost:4001/graphql  (users 서비스, `users.rs`)
+//!                   └→ localhost:4002/graphql  (todos 서비스, `todos.rs`)
+//!
+//! • 클라이언트는 한 번의 쿼리로 두 서비스의 데이터를 함께 받음
+//!   → `{ todos { title owner { name } } }`: todo는 todos 서비스, owner는 users 서비스에서
+//! • 서비스 사이의 엔티티 조회는 `DataLoader`로 모아서 한 번에
+//! • `Authorization` 헤더는 게이트웨이를 거쳐 각 서비스로 전달되고, 인증은 서비스가 직접 (`auth.rs`)
+//!
+//! 📌 여기서는 게이트웨이가 합쳐진 스키마를 코드로 직접 정의함 (schema stitching)
+//!   → 서비스가 많아지면 Apollo Federation 방식(`@key`, `_entities`)과 전용 라우터를 고려
+
+mod auth;
+mod gateway;
+mod todos;
+mod users;
+
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    // 하위 서비스 (보통은 각자 다른 프로세스/서버)
+    for (addr, app) in [
+        ("127.0.0.1:4001", users::app()),
+        ("127.0.0.1:4002", todos::app()),
+    ] {
+        let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+        tracing::debug!("service listening on {}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    }
+
+    let upstreams = gateway::Upstreams {
+        users: "http://127.0.0.1:4001/graphql".to_owned(),
+        todos: "http://127.0.0.1:4002/graphql".to_owned(),
+    };
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("gateway listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, gateway::app(upstreams))
+        .await
+        .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header, Request},
+        Router,
+    };
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use tower::{util::MapRequestLayer, ServiceExt};
+
+    // 서비스를 임의의 포트에 띄우고 GraphQL URL 반환
+    async fn spawn(app: Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{addr}/graphql")
+    }
+
+    // 게이트웨이 + users 서비스가 받은 요청 수
+    async fn gateway() -> (Router, Arc<AtomicUsize>) {
+        let users_requests = Arc::new(AtomicUsize::new(0));
+        let counter = users_requests.clone();
+        let users = users::app().layer(MapRequestLayer::new(move |request: Request<Body>| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            request
+        }));
+
+        let upstreams = gateway::Upstreams {
+            users: spawn(users).await,
+            todos: spawn(todos::app()).await,
+        };
+        (gateway::app(upstreams), users_requests)
+    }
+
+    async fn execute(app: &Router, query: &str, token: Option<&str>) -> Value {
+        let mut request =
+            Request::post("/graphql").header(header::CONTENT_TYPE, "application/json");
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        let response = app
+            .clone()
+            .oneshot(
+                request
+                    .body(Body::from(json!({ "query": query }).to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn owners_are_resolved_from_the_users_service_in_one_batch() {
+        let (app, users_requests) = gateway().await;
+
+        let response = execute(
+            &app,
+            "{ todos { title owner { name } } }",
+            Some("alice-token"),
+        )
+        .await;
+        assert!(response.get("errors").is_none(), "{response}");
+        assert_eq!(
+            response["data"]["todos"][2],
+            json!({ "title": "Review the schema", "owner": { "name": "Bob" } })
+        );
+        assert_eq!(
+            response["data"]["todos"][3]["owner"]["name"],
+            json!("Carol")
+        );
+        // todo 4개의 owner를 한 번의 usersByIds로
+        assert_eq!(users_requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn viewer_comes_from_the_propagated_token() {
+        let (app, _) = gateway().await;
+
+        let response = execute(&app, "{ me { name todos { title } } }", Some("bob-token")).await;
+        assert_eq!(
+            response["data"],
+            json!({ "me": { "name": "Bob", "todos": [{ "title": "Review the schema" }] } })
+        );
+    }
+
+    #[tokio::test]
+    async fn upstream_errors_are_passed_through() {
+        let (app, _) = gateway().await;
+
+        let response = execute(&app, "{ todos { title } }", None).await;
+        assert_eq!(response["data"], Value::Null);
+        assert_eq!(response["errors"][0]["message"], "unauthorized");
+        assert_eq!(response["errors"][0]["path"], json!(["todos"]));
+    }
+}
+
+// 🧪 테스트 방법
+//
+// ✅ 두 서비스의 데이터를 한 번에 (todos 서비스 + users 서비스)
+// curl -s localhost:3000/graphql -H 'content-type: application/json' \
+//   -H 'authorization: Bearer alice-token' \
+//   -d '{"query":"{ todos { title completed owner { name email } } }"}'
+//
+// ✅ 토큰의 사용자와 그 사용자의 todo
+// curl -s localhost:3000/graphql -H 'content-type: application/json' \
+//   -H 'authorization: Bearer bob-token' \
+//   -d '{"query":"{ me { name todos { title } } }"}'
+//
+// ❌ 토큰 없이 → 서비스의 "unauthorized" 에러가 그대로 전달
+// curl -s localhost:3000/graphql -H 'content-type: application/json' -d '{"query":"{ users { name } }"}'
+//
+// 🧭 브라우저에서 http://localhost:3000/graphql 을 열면 GraphiQL
+// (Headers 탭에 {"Authorization": "Bearer alice-token"})
@@ -0,0 +1,82 @@
+//! ✅ todos 서비스 (4002번 포트)
+//!
+//! ```graphql
+//! type Todo { id: ID!, title: String!, completed: Boolean!, ownerId: ID! }
+//! type Query { todos: [Todo!]!, todosByOwners(ownerIds: [ID!]!): [Todo!]! }
+//! ```
+//!
+//! 사용자 정보는 모르고 `ownerId`만 가지고 있음 (이름 등은 게이트웨이가 users 서비스에서 가져옴)
+
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Schema, SimpleObject, ID};
+use axum::{extract::State, http::HeaderMap, routing::post, Json, Router};
+
+use crate::auth;
+
+const TODOS: [(&str, &str, bool, &str); 4] = [
+    ("1", "Write the gateway", true, "1"),
+    ("2", "Batch entity lookups", false, "1"),
+    ("3", "Review the schema", false, "2"),
+    ("4", "Propagate auth headers", true, "3"),
+];
+
+#[derive(SimpleObject)]
+struct Todo {
+    id: ID,
+    title: String,
+    completed: bool,
+    owner_id: ID,
+}
+
+fn todos() -> impl Iterator<Item = Todo> {
+    TODOS
+        .into_iter()
+        .map(|(id, title, completed, owner_id)| Todo {
+            id: id.into(),
+            title: title.to_owned(),
+            completed,
+            owner_id: owner_id.into(),
+        })
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn todos(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Todo>> {
+        auth::require(ctx)?;
+        Ok(todos().collect())
+    }
+
+    /// 여러 사용자의 todo를 한 번에 (게이트웨이의 `User.todos`용)
+    async fn todos_by_owners(
+        &self,
+        ctx: &Context<'_>,
+        owner_ids: Vec<ID>,
+    ) -> async_graphql::Result<Vec<Todo>> {
+        auth::require(ctx)?;
+        Ok(todos()
+            .filter(|todo| owner_ids.contains(&todo.owner_id))
+            .collect())
+    }
+}
+
+type TodosSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+pub fn app() -> Router {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    Router::new()
+        .route("/graphql", post(graphql))
+        .with_state(schema)
+}
+
+async fn graphql(
+    State(schema): State<TodosSchema>,
+    headers: HeaderMap,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    let mut request = request;
+    if let Some(viewer) = auth::viewer(&headers) {
+        request = request.data(viewer);
+    }
+    Json(schema.execute(request).await)
+}
@@ -0,0 +1,82 @@
+//! 👤 users 서비스 (4001번 포트)
+//!
+//! ```graphql
+//! type User { id: ID!, name: String!, email: String! }
+//! type Query { me: User!, users: [User!]!, usersByIds(ids: [ID!]!): [User!]! }
+//! ```
+//!
+//! `usersByIds`는 게이트웨이가 다른 서비스의 데이터(todo의 `ownerId`)를 사용자로 바꿀 때 쓰는 엔티티 조회
+
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Schema, SimpleObject, ID};
+use axum::{extract::State, http::HeaderMap, routing::post, Json, Router};
+
+use crate::auth;
+
+const USERS: [(&str, &str, &str); 3] = [
+    ("1", "Alice", "alice@example.com"),
+    ("2", "Bob", "bob@example.com"),
+    ("3", "Carol", "carol@example.com"),
+];
+
+#[derive(SimpleObject)]
+struct User {
+    id: ID,
+    name: String,
+    email: String,
+}
+
+fn users() -> impl Iterator<Item = User> {
+    USERS.into_iter().map(|(id, name, email)| User {
+        id: id.into(),
+        name: name.to_owned(),
+        email: email.to_owned(),
+    })
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn me(&self, ctx: &Context<'_>) -> async_graphql::Result<User> {
+        let viewer = auth::require(ctx)?;
+        users()
+            .find(|user| user.id == viewer.0)
+            .ok_or_else(|| "user not found".into())
+    }
+
+    async fn users(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<User>> {
+        auth::require(ctx)?;
+        Ok(users().collect())
+    }
+
+    /// 없는 id는 결과에서 빠짐
+    async fn users_by_ids(
+        &self,
+        ctx: &Context<'_>,
+        ids: Vec<ID>,
+    ) -> async_graphql::Result<Vec<User>> {
+        auth::require(ctx)?;
+        Ok(users().filter(|user| ids.contains(&user.id)).collect())
+    }
+}
+
+type UsersSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+pub fn app() -> Router {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    Router::new()
+        .route("/graphql", post(graphql))
+        .with_state(schema)
+}
+
+async fn graphql(
+    State(schema): State<UsersSchema>,
+    headers: HeaderMap,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    let mut request = request;
+    if let Some(viewer) = auth::viewer(&headers) {
+        request = request.data(viewer);
+    }
+    Json(schema.execute(request).await)
+}
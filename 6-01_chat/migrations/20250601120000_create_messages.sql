@@ -0,0 +1,22 @@
+-- 채팅 메시지 (sent_at: Unix time, 밀리초)
+CREATE TABLE messages (
+    id INTEGER PRIMARY KEY,
+    room TEXT NOT NULL,
+    username TEXT NOT NULL,
+    body TEXT NOT NULL,
+    sent_at INTEGER NOT NULL
+);
+
+CREATE INDEX messages_room_sent_at ON messages (room, sent_at);
+
+-- 본문 전문 검색용 FTS5 인덱스 (본문은 messages 테이블에만 저장하는 external content 방식)
+CREATE VIRTUAL TABLE messages_fts USING fts5(body, content = 'messages', content_rowid = 'id');
+
+-- messages가 바뀌면 인덱스도 함께 갱신
+CREATE TRIGGER messages_after_insert AFTER INSERT ON messages BEGIN
+    INSERT INTO messages_fts (rowid, body) VALUES (new.id, new.body);
+END;
+
+CREATE TRIGGER messages_after_delete AFTER DELETE ON messages BEGIN
+    INSERT INTO messages_fts (messages_fts, rowid, body) VALUES ('delete', old.id, old.body);
+END;
@@ -0,0 +1,232 @@
+//! 여러 서버 인스턴스가 하나의 채팅방을 공유하도록 메시지를 외부 pub/sub으로 중계하는 백플레인
+//!
+//! 구조:
+//! • 핸들러는 로컬 broadcast 채널 대신 `Backplane::publish`로 메시지를 보냄
+//! • 백플레인이 메시지를 pub/sub 채널로 발행
+//! • 구독 task가 pub/sub 채널의 메시지(자기 자신이 보낸 것 포함)를 로컬 broadcast 채널로 전달
+//!
+//! 구현체:
+//! • `InMemoryBus` — 프로세스 내부 pub/sub (Redis 없이 두 인스턴스를 테스트할 때 사용)
+//! • `redis::connect` — Redis pub/sub (`--features redis`)
+
+use tokio::sync::{broadcast, mpsc};
+
+/// Name of the pub/sub channel every instance publishes to and subscribes on.
+/// 모든 인스턴스가 공유하는 pub/sub 채널 이름
+#[cfg_attr(not(feature = "redis"), allow(dead_code))]
+pub const CHANNEL: &str = "example-chat";
+
+/// 백플레인으로 메시지를 발행하는 핸들
+///
+/// 실제 발행은 백그라운드 task가 담당하므로 `publish`는 블로킹되지 않음
+#[derive(Clone)]
+pub struct Backplane {
+    outbound: mpsc::UnboundedSender<String>,
+}
+
+impl Backplane {
+    /// Publish a message to every instance sharing this backplane.
+    /// 발행 task가 종료된 경우 메시지를 돌려줌
+    pub fn publish(&self, msg: String) -> Result<(), String> {
+        self.outbound.send(msg).map_err(|err| err.0)
+    }
+}
+
+/// ✅ 프로세스 내부 pub/sub
+///
+/// Redis 채널 하나를 흉내 냄. 같은 `InMemoryBus`에 붙은 인스턴스끼리 메시지를 공유함
+#[derive(Clone)]
+#[cfg_attr(not(test), allow(dead_code))]
+pub struct InMemoryBus {
+    tx: broadcast::Sender<String>,
+}
+
+#[cfg_attr(not(test), allow(dead_code))]
+impl InMemoryBus {
+    pub fn new() -> Self {
+        let (tx, _rx) = broadcast::channel(100);
+        Self { tx }
+    }
+
+    /// Attach an instance's local broadcast channel to the bus.
+    /// 발행 task와 구독 task를 띄우고 발행용 핸들을 반환
+    pub fn attach(&self, local: broadcast::Sender<String>) -> Backplane {
+        let (outbound, mut rx) = mpsc::unbounded_channel::<String>();
+
+        // 발행: 핸들로 들어온 메시지를 버스로 전달
+        let bus = self.tx.clone();
+        tokio::spawn(async move {
+            while let Some(msg) = rx.recv().await {
+                let _ = bus.send(msg);
+            }
+        });
+
+        // 구독: 버스의 메시지를 로컬 broadcast 채널로 전달
+        let mut subscriber = self.tx.subscribe();
+        tokio::spawn(async move {
+            loop {
+                match subscriber.recv().await {
+                    Ok(msg) => {
+                        let _ = local.send(msg);
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!("backplane subscriber lagged, skipped {skipped} messages");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+
+        Backplane { outbound }
+    }
+}
+
+/// ✅ Redis pub/sub 백플레인
+///
+/// 발행은 9-02_tokio-redis와 같은 bb8 커넥션 풀을 사용하고,
+/// 구독은 SUBSCRIBE 전용 연결이 필요하므로 별도의 pub/sub 연결을 사용함
+#[cfg(feature = "redis")]
+pub mod redis {
+    use super::{Backplane, CHANNEL};
+    use bb8_redis::{bb8, RedisConnectionManager};
+    use futures::StreamExt;
+    use redis::AsyncCommands;
+    use std::time::Duration;
+    use tokio::sync::{broadcast, mpsc};
+
+    type ConnectionPool = bb8::Pool<RedisConnectionManager>;
+
+    /// 발행 실패 시 재시도 횟수 (초과하면 메시지를 버림)
+    const MAX_PUBLISH_ATTEMPTS: u32 = 5;
+
+    /// Connect to Redis and bridge `local` to the shared channel.
+    pub async fn connect(
+        url: &str,
+        local: broadcast::Sender<String>,
+    ) -> Result<Backplane, redis::RedisError> {
+        let manager = RedisConnectionManager::new(url)?;
+        let pool = bb8::Pool::builder().build(manager).await?;
+        let client = redis::Client::open(url)?;
+
+        let (outbound, rx) = mpsc::unbounded_channel();
+        tokio::spawn(publish_loop(pool, rx));
+        tokio::spawn(subscribe_loop(client, local));
+
+        Ok(Backplane { outbound })
+    }
+
+    /// 📤 발행 루프 — 실패하면 backoff 후 재시도
+    async fn publish_loop(pool: ConnectionPool, mut rx: mpsc::UnboundedReceiver<String>) {
+        while let Some(msg) = rx.recv().await {
+            let mut backoff = Backoff::default();
+
+            for attempt in 1..=MAX_PUBLISH_ATTEMPTS {
+                match publish(&pool, &msg).await {
+                    Ok(()) => break,
+                    Err(err) if attempt < MAX_PUBLISH_ATTEMPTS => {
+                        let delay = backoff.next_delay();
+                        tracing::warn!("failed to publish to redis ({err}), retrying in {delay:?}");
+                        tokio::time::sleep(delay).await;
+                    }
+                    Err(err) => {
+                        tracing::error!("dropping message, failed to publish to redis: {err}")
+                    }
+                }
+            }
+        }
+    }
+
+    async fn publish(pool: &ConnectionPool, msg: &str) -> Result<(), String> {
+        let mut conn = pool.get().await.map_err(|err| err.to_string())?;
+        conn.publish::<_, _, ()>(CHANNEL, msg)
+            .await
+            .map_err(|err| err.to_string())
+    }
+
+    /// 📥 구독 루프 — 연결이 끊기면 backoff 후 다시 연결하고 재구독
+    async fn subscribe_loop(client: redis::Client, local: broadcast::Sender<String>) {
+        let mut backoff = Backoff::default();
+
+        loop {
+            match client.get_async_pubsub().await {
+                Ok(mut pubsub) => match pubsub.subscribe(CHANNEL).await {
+                    Ok(()) => {
+                        tracing::debug!("subscribed to redis channel {CHANNEL}");
+                        backoff.reset();
+
+                        let mut messages = pubsub.on_message();
+                        while let Some(msg) = messages.next().await {
+                            match msg.get_payload::<String>() {
+                                Ok(payload) => {
+                                    let _ = local.send(payload);
+                                }
+                                Err(err) => tracing::warn!("invalid redis payload: {err}"),
+                            }
+                        }
+
+                        tracing::warn!("redis subscription closed");
+                    }
+                    Err(err) => tracing::warn!("failed to subscribe to redis: {err}"),
+                },
+                Err(err) => tracing::warn!("failed to connect to redis: {err}"),
+            }
+
+            let delay = backoff.next_delay();
+            tracing::debug!("reconnecting to redis in {delay:?}");
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// ⏳ 지수 backoff — 실패할 때마다 대기 시간을 두 배로 늘리되 `max`를 넘지 않음
+    #[derive(Debug)]
+    struct Backoff {
+        base: Duration,
+        max: Duration,
+        current: Duration,
+    }
+
+    impl Backoff {
+        fn new(base: Duration, max: Duration) -> Self {
+            Self {
+                base,
+                max,
+                current: base,
+            }
+        }
+
+        fn next_delay(&mut self) -> Duration {
+            let delay = self.current;
+            self.current = (self.current * 2).min(self.max);
+            delay
+        }
+
+        fn reset(&mut self) {
+            self.current = self.base;
+        }
+    }
+
+    impl Default for Backoff {
+        fn default() -> Self {
+            Self::new(Duration::from_millis(100), Duration::from_secs(10))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn backoff_doubles_up_to_max_and_resets() {
+            let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(5));
+
+            assert_eq!(backoff.next_delay(), Duration::from_secs(1));
+            assert_eq!(backoff.next_delay(), Duration::from_secs(2));
+            assert_eq!(backoff.next_delay(), Duration::from_secs(4));
+            assert_eq!(backoff.next_delay(), Duration::from_secs(5));
+            assert_eq!(backoff.next_delay(), Duration::from_secs(5));
+
+            backoff.reset();
+            assert_eq!(backoff.next_delay(), Duration::from_secs(1));
+        }
+    }
+}
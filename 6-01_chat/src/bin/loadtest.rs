@@ -0,0 +1,300 @@
+//! 채팅 서버 부하(soak) 테스트 클라이언트
+//!
+//! 클라이언트 N개가 동시에 접속해 정해진 속도로 메시지를 보내고,
+//! 모든 클라이언트가 브로드캐스트를 받기까지 걸린 시간(end-to-end 지연)을 측정해 리포트를 출력
+//!
+//! ```not_rust
+//! cargo run -p example-chat                                   # 서버
+//! cargo run -p example-chat --release --features loadtest --bin loadtest
+//! CLIENTS=200 RATE=5 DURATION=30 cargo run -p example-chat --release --features loadtest --bin loadtest
+//! ```
+//!
+//! 환경 변수
+//! - `URL`: 접속 주소 (기본 `ws://127.0.0.1:3000/websocket`)
+//! - `CLIENTS`: 동시 접속 수 (기본 50)
+//! - `RATE`: 클라이언트 하나가 초당 보내는 메시지 수 (기본 1)
+//! - `DURATION`: 메시지를 보내는 시간, 초 (기본 10)
+//!
+//! 보낸 시각은 메시지 본문에 넣고, 받는 쪽에서 같은 프로세스의 `Instant` 기준으로 차이를 계산
+//! (서버가 한 메시지를 모든 클라이언트에게 보내므로 기대 수신 수 = 보낸 수 × 클라이언트 수)
+
+use futures::{future::join_all, SinkExt, StreamExt};
+use std::{
+    env,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+// 부하 테스트 메시지 표시 (입장/퇴장 알림 등 다른 메시지와 구분)
+const MARKER: &str = "lt";
+// 보내기를 멈춘 뒤 아직 도착하지 않은 메시지를 기다리는 시간
+const DRAIN: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone)]
+struct Config {
+    url: String,
+    clients: usize,
+    rate: f64,
+    duration: Duration,
+}
+
+impl Config {
+    fn from_env() -> Result<Self, String> {
+        Ok(Self {
+            url: env::var("URL").unwrap_or_else(|_| "ws://127.0.0.1:3000/websocket".to_owned()),
+            clients: parse_env("CLIENTS", 50)?,
+            rate: parse_env("RATE", 1.0)?,
+            duration: Duration::from_secs_f64(parse_env("DURATION", 10.0)?),
+        })
+    }
+}
+
+fn parse_env<T: std::str::FromStr>(name: &str, default: T) -> Result<T, String> {
+    match env::var(name) {
+        Ok(value) => value
+            .parse()
+            .map_err(|_| format!("invalid value for {name}: {value}")),
+        Err(_) => Ok(default),
+    }
+}
+
+/// 클라이언트 하나의 결과
+#[derive(Debug, Default)]
+struct ClientStats {
+    sent: u64,
+    latencies: Vec<Duration>,
+    /// 서버가 먼저 연결을 끊음 (느린 클라이언트는 broadcast 채널에서 밀려나면 끊김)
+    disconnected: bool,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let config = match Config::from_env() {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if config.clients == 0 || config.rate <= 0.0 {
+        eprintln!("CLIENTS and RATE must be greater than zero");
+        return ExitCode::FAILURE;
+    }
+
+    println!(
+        "🚀 {} clients × {} msg/s for {:?} → {}",
+        config.clients, config.rate, config.duration, config.url
+    );
+
+    // 실행마다 다른 닉네임 (서버는 중복 닉네임을 거절함)
+    let run_id = std::process::id();
+    let start = Instant::now();
+
+    // 1. 모두 접속한 뒤에 보내기 시작 (접속 중에 보낸 메시지는 늦게 붙은 클라이언트가 못 받음)
+    let connecting = (0..config.clients).map(|i| {
+        let url = config.url.clone();
+        async move {
+            let (mut socket, _) = connect_async(url).await?;
+            socket
+                .send(Message::text(format!("loadtest-{run_id}-{i}")))
+                .await?;
+            Ok::<_, tokio_tungstenite::tungstenite::Error>(socket)
+        }
+    });
+    let mut sockets = Vec::with_capacity(config.clients);
+    for (i, result) in join_all(connecting).await.into_iter().enumerate() {
+        match result {
+            Ok(socket) => sockets.push(socket),
+            Err(err) => {
+                eprintln!("client {i} failed to connect: {err}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    let connect_time = start.elapsed();
+    println!("✅ connected in {connect_time:?}");
+
+    // 2. 클라이언트마다 보내는 task + 받는 task
+    let clock = Instant::now();
+    let tasks: Vec<JoinHandle<ClientStats>> = sockets
+        .into_iter()
+        .enumerate()
+        .map(|(id, socket)| {
+            let (mut sink, mut stream) = socket.split();
+            let config = config.clone();
+
+            let sender = tokio::spawn(async move {
+                let mut interval =
+                    tokio::time::interval(Duration::from_secs_f64(1.0 / config.rate));
+                // 밀린 tick을 몰아서 보내지 않음 (설정한 속도 유지)
+                interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                let mut sent = 0;
+                while clock.elapsed() < config.duration {
+                    interval.tick().await;
+                    let text = encode(id, sent, clock.elapsed());
+                    if sink.send(Message::text(text)).await.is_err() {
+                        break;
+                    }
+                    sent += 1;
+                }
+                (sent, sink)
+            });
+
+            tokio::spawn(async move {
+                let mut stats = ClientStats::default();
+                let deadline = tokio::time::Instant::now() + config.duration + DRAIN;
+                loop {
+                    match tokio::time::timeout_at(deadline, stream.next()).await {
+                        // 마감 시간까지 기다림
+                        Err(_) => break,
+                        Ok(Some(Ok(Message::Text(text)))) => {
+                            if let Some(sent_at) = decode(text.as_str()) {
+                                stats
+                                    .latencies
+                                    .push(clock.elapsed().saturating_sub(sent_at));
+                            }
+                        }
+                        Ok(Some(Ok(_))) => {}
+                        Ok(Some(Err(_)) | None) => {
+                            stats.disconnected = true;
+                            break;
+                        }
+                    }
+                }
+
+                if let Ok((sent, mut sink)) = sender.await {
+                    stats.sent = sent;
+                    let _ = sink.close().await;
+                }
+                stats
+            })
+        })
+        .collect();
+
+    // 3. 결과 모으기
+    let mut total_sent = 0;
+    let mut disconnected = 0;
+    let mut latencies = Vec::new();
+    for stats in join_all(tasks).await.into_iter().flatten() {
+        total_sent += stats.sent;
+        disconnected += usize::from(stats.disconnected);
+        latencies.extend(stats.latencies);
+    }
+
+    let report = Report::new(config.clients, total_sent, latencies, disconnected);
+    report.print(config.duration);
+
+    if report.received < report.expected || report.disconnected > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+// 메시지 형식: `lt:<client>:<seq>:<보낸 시각(µs)>`
+// 서버는 `<닉네임>: <본문>` 형태로 브로드캐스트함
+fn encode(client: usize, seq: u64, sent_at: Duration) -> String {
+    format!("{MARKER}:{client}:{seq}:{}", sent_at.as_micros())
+}
+
+fn decode(broadcast: &str) -> Option<Duration> {
+    let (_, body) = broadcast.split_once(": ")?;
+    let mut parts = body.split(':');
+    if parts.next()? != MARKER {
+        return None;
+    }
+    let micros = parts.nth(2)?.parse().ok()?;
+    Some(Duration::from_micros(micros))
+}
+
+/// 📊 리포트
+struct Report {
+    clients: usize,
+    sent: u64,
+    expected: u64,
+    received: u64,
+    disconnected: usize,
+    latencies: Vec<Duration>,
+}
+
+impl Report {
+    fn new(clients: usize, sent: u64, mut latencies: Vec<Duration>, disconnected: usize) -> Self {
+        latencies.sort_unstable();
+        Self {
+            clients,
+            sent,
+            expected: sent * clients as u64,
+            received: latencies.len() as u64,
+            disconnected,
+            latencies,
+        }
+    }
+
+    // 처리량은 보내는 시간 기준 (마지막 DRAIN 대기 시간은 제외)
+    fn print(&self, duration: Duration) {
+        let loss = if self.expected == 0 {
+            0.0
+        } else {
+            100.0 * (self.expected.saturating_sub(self.received)) as f64 / self.expected as f64
+        };
+
+        println!();
+        println!("📊 report ({duration:?} of sending)");
+        println!("  clients        {}", self.clients);
+        println!("  disconnected   {}", self.disconnected);
+        println!("  sent           {}", self.sent);
+        println!(
+            "  received       {} / {} expected ({loss:.2}% lost)",
+            self.received, self.expected
+        );
+        println!(
+            "  throughput     {:.0} deliveries/s",
+            self.received as f64 / duration.as_secs_f64()
+        );
+        if self.latencies.is_empty() {
+            return;
+        }
+        println!("  latency");
+        for (label, p) in [
+            ("p50", 0.50),
+            ("p90", 0.90),
+            ("p99", 0.99),
+            ("p99.9", 0.999),
+        ] {
+            println!("    {label:<6}     {:?}", percentile(&self.latencies, p));
+        }
+        println!("    max        {:?}", self.latencies.last().unwrap());
+    }
+}
+
+/// 정렬된 목록의 백분위수 (nearest-rank 방식)
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_round_trip() {
+        let text = format!("alice: {}", encode(3, 7, Duration::from_micros(1_234)));
+        assert_eq!(decode(&text), Some(Duration::from_micros(1_234)));
+
+        assert_eq!(decode("alice joined."), None);
+        assert_eq!(decode("alice: hello: world"), None);
+    }
+
+    #[test]
+    fn nearest_rank_percentile() {
+        let sorted: Vec<Duration> = (1..=100).map(Duration::from_millis).collect();
+
+        assert_eq!(percentile(&sorted, 0.50), Duration::from_millis(50));
+        assert_eq!(percentile(&sorted, 0.99), Duration::from_millis(99));
+        assert_eq!(percentile(&sorted, 0.0), Duration::from_millis(1));
+        assert_eq!(percentile(&sorted[..1], 0.99), Duration::from_millis(1));
+    }
+}
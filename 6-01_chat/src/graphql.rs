@@ -0,0 +1,550 @@
+//! 🔗 GraphQL 브리지: 같은 채팅방을 GraphQL 클라이언트에도 공개
+//!
+//! • `POST /graphql`: `mutation { sendMessage(username: "..", text: "..") }`
+//!   → 웹소켓 채팅과 똑같이 저장하고 `broadcast::Sender`로 전파
+//! • `GET /graphql/ws`: [graphql-ws] 프로토콜(`graphql-transport-ws`)의
+//!   `subscription { messageAdded }` → 채팅방에 전파되는 모든 줄을 `next`로 전달
+//!
+//! 그래서 HTTP mutation으로 보낸 메시지가 chat.html 사용자에게 보이고, chat.html에서 보낸
+//! 메시지가 GraphQL 구독자에게 보임 (Apollo Client, urql 등의 graphql-ws 링크로 접속 가능)
+//!
+//! 스키마가 필드 두 개뿐이라 GraphQL 엔진 없이 작은 파서로 처리함
+//! (루트 필드 하나, 인자는 문자열/숫자/불리언/변수만 지원)
+//! 실제 스키마가 필요하면 async-graphql 같은 엔진의 실행 결과를 같은 프로토콜로 보내면 됨
+//!
+//! ```graphql
+//! type Mutation { sendMessage(username: String!, text: String!): String! }
+//! type Subscription { messageAdded: String! }
+//! ```
+//!
+//! [graphql-ws]: https://github.com/enisdenjo/graphql-ws/blob/master/PROTOCOL.md
+
+use crate::{AppState, ROOM};
+use axum::{
+    extract::{
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::IntoResponse,
+    Json,
+};
+use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::{
+    sync::{broadcast::error::RecvError, mpsc},
+    task::JoinHandle,
+};
+
+pub const PROTOCOL: &str = "graphql-transport-ws";
+// 연결 후 이 시간 안에 `connection_init`이 없으면 닫음
+const INIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// GraphQL 요청 (HTTP 본문, 웹소켓 `subscribe`의 `payload` 공통)
+#[derive(Debug, Deserialize)]
+pub struct GraphQlRequest {
+    query: String,
+    #[serde(default)]
+    variables: Option<Map<String, Value>>,
+}
+
+// --- 📮 HTTP
+
+pub async fn http_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<GraphQlRequest>,
+) -> Json<Value> {
+    let result = match parse_request(&request) {
+        Ok(operation) if operation.kind == OperationKind::Subscription => {
+            error_result("subscriptions are only available over WebSocket at /graphql/ws")
+        }
+        Ok(operation) => execute(&state, &operation).await,
+        Err(message) => error_result(&message),
+    };
+    Json(result)
+}
+
+/// 구독 외의 연산 실행 (`data` 또는 `errors`를 담은 결과)
+async fn execute(state: &AppState, operation: &Operation) -> Value {
+    match (operation.kind, operation.field.as_str()) {
+        (OperationKind::Mutation, "sendMessage") => {
+            let (Some(username), Some(text)) =
+                (operation.string("username"), operation.string("text"))
+            else {
+                return error_result("sendMessage requires `username` and `text` strings");
+            };
+            // 웹소켓 채팅과 같은 순서: 저장 → 전파
+            if let Err(err) = state.store.insert(ROOM, username, text).await {
+                tracing::error!("failed to store message: {err}");
+            }
+            let line = format!("{username}: {text}");
+            state.broadcast(line.clone());
+            json!({ "data": { operation.response_key(): line } })
+        }
+        (kind, field) => error_result(&format!("unknown {} field `{field}`", kind.as_str())),
+    }
+}
+
+fn error_result(message: &str) -> Value {
+    json!({ "errors": [{ "message": message }] })
+}
+
+// --- 📡 WebSocket (graphql-transport-ws)
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    ConnectionInit,
+    Ping,
+    Pong,
+    Subscribe { id: String, payload: GraphQlRequest },
+    Complete { id: String },
+}
+
+pub async fn ws_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<AppState>>,
+) -> impl IntoResponse {
+    ws.protocols([PROTOCOL])
+        .on_upgrade(|socket| websocket(socket, state))
+}
+
+async fn websocket(socket: WebSocket, state: Arc<AppState>) {
+    let (mut sink, mut stream) = socket.split();
+
+    // 구독 task들이 보내는 메시지를 한 곳에서 순서대로 씀
+    let (out, mut outgoing) = mpsc::unbounded_channel::<Message>();
+    let writer = tokio::spawn(async move {
+        while let Some(message) = outgoing.recv().await {
+            let closing = matches!(message, Message::Close(_));
+            if sink.send(message).await.is_err() || closing {
+                break;
+            }
+        }
+    });
+
+    let mut session = Session {
+        state,
+        out,
+        acknowledged: false,
+        subscriptions: HashMap::new(),
+    };
+
+    let init = tokio::time::timeout(INIT_TIMEOUT, stream.next()).await;
+    let mut next = match init {
+        Ok(message) => message,
+        Err(_) => {
+            session.close(4408, "Connection initialisation timeout");
+            None
+        }
+    };
+
+    while let Some(Ok(message)) = next {
+        let text = match message {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => {
+                next = stream.next().await;
+                continue;
+            }
+        };
+        if let Err((code, reason)) = session.handle(text.as_str()) {
+            session.close(code, &reason);
+            break;
+        }
+        next = stream.next().await;
+    }
+
+    // 연결이 끝나면 남은 구독 정리
+    for (_, task) in session.subscriptions.drain() {
+        task.abort();
+    }
+    drop(session);
+    let _ = writer.await;
+}
+
+struct Session {
+    state: Arc<AppState>,
+    out: mpsc::UnboundedSender<Message>,
+    acknowledged: bool,
+    subscriptions: HashMap<String, JoinHandle<()>>,
+}
+
+impl Session {
+    fn send(&self, message: Value) {
+        let _ = self.out.send(Message::text(message.to_string()));
+    }
+
+    fn close(&self, code: u16, reason: &str) {
+        let _ = self.out.send(Message::Close(Some(CloseFrame {
+            code,
+            reason: reason.into(),
+        })));
+    }
+
+    /// 클라이언트 메시지 하나 처리 (`Err`이면 그 코드로 연결을 닫음)
+    fn handle(&mut self, text: &str) -> Result<(), (u16, String)> {
+        let message: ClientMessage = serde_json::from_str(text).map_err(|err| {
+            // 닫기 사유는 123바이트까지라 자세한 내용은 로그로만 남김
+            tracing::debug!("invalid graphql-ws message: {err}");
+            (4400, "Invalid message received".to_owned())
+        })?;
+
+        match message {
+            ClientMessage::ConnectionInit if self.acknowledged => {
+                return Err((4429, "Too many initialisation requests".to_owned()));
+            }
+            ClientMessage::ConnectionInit => {
+                self.acknowledged = true;
+                self.send(json!({ "type": "connection_ack" }));
+            }
+            ClientMessage::Ping => self.send(json!({ "type": "pong" })),
+            ClientMessage::Pong => {}
+            ClientMessage::Subscribe { .. } if !self.acknowledged => {
+                return Err((4401, "Unauthorized".to_owned()));
+            }
+            ClientMessage::Subscribe { id, payload } => {
+                // 끝난 구독의 ID는 다시 쓸 수 있음
+                self.subscriptions.retain(|_, task| !task.is_finished());
+                if self.subscriptions.contains_key(&id) {
+                    return Err((4409, format!("Subscriber for {id} already exists")));
+                }
+                let task = self.start(id.clone(), payload);
+                self.subscriptions.insert(id, task);
+            }
+            ClientMessage::Complete { id } => {
+                if let Some(task) = self.subscriptions.remove(&id) {
+                    task.abort();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 연산 하나를 실행하는 task (구독은 클라이언트가 `complete`를 보낼 때까지 계속)
+    fn start(&self, id: String, request: GraphQlRequest) -> JoinHandle<()> {
+        let state = self.state.clone();
+        // task가 시작되기 전에 구독해야 `subscribe` 직후 전파된 메시지도 놓치지 않음
+        let mut rx = state.tx.subscribe();
+        let out = self.out.clone();
+        let send = move |message: Value| out.send(Message::text(message.to_string())).is_ok();
+
+        tokio::spawn(async move {
+            let operation = match parse_request(&request) {
+                Ok(operation) => operation,
+                Err(message) => {
+                    send(json!({ "id": id, "type": "error", "payload": [{ "message": message }] }));
+                    return;
+                }
+            };
+
+            match (operation.kind, operation.field.as_str()) {
+                (OperationKind::Subscription, "messageAdded") => {
+                    let key = operation.response_key();
+                    loop {
+                        match rx.recv().await {
+                            Ok(line) => {
+                                let payload = json!({ "data": { key.as_str(): line } });
+                                if !send(json!({ "id": id, "type": "next", "payload": payload })) {
+                                    return;
+                                }
+                            }
+                            // 느린 구독자는 밀린 메시지를 건너뛰고 계속
+                            Err(RecvError::Lagged(skipped)) => {
+                                tracing::warn!(id, skipped, "graphql subscriber lagged");
+                            }
+                            Err(RecvError::Closed) => break,
+                        }
+                    }
+                }
+                (OperationKind::Subscription, field) => {
+                    let message = format!("unknown subscription field `{field}`");
+                    send(json!({ "id": id, "type": "error", "payload": [{ "message": message }] }));
+                    return;
+                }
+                // 웹소켓으로 보낸 mutation은 결과 하나를 보내고 끝남
+                _ => {
+                    let payload = execute(&state, &operation).await;
+                    send(json!({ "id": id, "type": "next", "payload": payload }));
+                }
+            }
+            send(json!({ "id": id, "type": "complete" }));
+        })
+    }
+}
+
+// --- 🧩 작은 GraphQL 파서 (루트 필드 하나짜리 연산)
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OperationKind {
+    Query,
+    Mutation,
+    Subscription,
+}
+
+impl OperationKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            OperationKind::Query => "query",
+            OperationKind::Mutation => "mutation",
+            OperationKind::Subscription => "subscription",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Operation {
+    kind: OperationKind,
+    alias: Option<String>,
+    field: String,
+    /// 변수는 이미 값으로 바뀐 상태
+    args: HashMap<String, Value>,
+}
+
+impl Operation {
+    /// 응답에서 쓸 키 (`latest: messageAdded` 처럼 별칭이 있으면 별칭)
+    fn response_key(&self) -> String {
+        self.alias.clone().unwrap_or_else(|| self.field.clone())
+    }
+
+    fn string(&self, name: &str) -> Option<&str> {
+        self.args.get(name).and_then(Value::as_str)
+    }
+}
+
+fn parse_request(request: &GraphQlRequest) -> Result<Operation, String> {
+    let empty = Map::new();
+    parse(&request.query, request.variables.as_ref().unwrap_or(&empty))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Name(String),
+    Punct(char),
+    Value(Value),
+}
+
+fn parse(query: &str, variables: &Map<String, Value>) -> Result<Operation, String> {
+    let mut tokens = tokenize(query)?.into_iter().peekable();
+
+    // `mutation Name($text: String!) {` 또는 `{` (query 생략형)
+    let kind = match tokens.peek() {
+        Some(Token::Punct('{')) => OperationKind::Query,
+        Some(Token::Name(keyword)) => {
+            let kind = match keyword.as_str() {
+                "query" => OperationKind::Query,
+                "mutation" => OperationKind::Mutation,
+                "subscription" => OperationKind::Subscription,
+                other => return Err(format!("unsupported definition `{other}`")),
+            };
+            tokens.next();
+            if let Some(Token::Name(_)) = tokens.peek() {
+                tokens.next(); // 연산 이름
+            }
+            if tokens.peek() == Some(&Token::Punct('(')) {
+                skip_group(&mut tokens, '(', ')')?; // 변수 선언은 값만 쓰므로 건너뜀
+            }
+            kind
+        }
+        other => return Err(format!("unexpected {other:?}")),
+    };
+    expect(&mut tokens, '{')?;
+
+    // 루트 필드 (별칭 허용)
+    let Some(Token::Name(mut field)) = tokens.next() else {
+        return Err("expected a field".to_owned());
+    };
+    let mut alias = None;
+    if tokens.peek() == Some(&Token::Punct(':')) {
+        tokens.next();
+        let Some(Token::Name(name)) = tokens.next() else {
+            return Err("expected a field after alias".to_owned());
+        };
+        alias = Some(std::mem::replace(&mut field, name));
+    }
+
+    // 인자: `(name: value, ...)`
+    let mut args = HashMap::new();
+    if tokens.peek() == Some(&Token::Punct('(')) {
+        tokens.next();
+        loop {
+            match tokens.next() {
+                Some(Token::Punct(')')) => break,
+                Some(Token::Name(name)) => {
+                    expect(&mut tokens, ':')?;
+                    let value = match tokens.next() {
+                        Some(Token::Value(value)) => value,
+                        Some(Token::Punct('$')) => match tokens.next() {
+                            Some(Token::Name(var)) => {
+                                variables.get(&var).cloned().unwrap_or(Value::Null)
+                            }
+                            other => {
+                                return Err(format!("expected a variable name, found {other:?}"))
+                            }
+                        },
+                        Some(Token::Name(word)) => match word.as_str() {
+                            "true" => Value::Bool(true),
+                            "false" => Value::Bool(false),
+                            "null" => Value::Null,
+                            _ => return Err(format!("unsupported argument value `{word}`")),
+                        },
+                        other => return Err(format!("unsupported argument value {other:?}")),
+                    };
+                    args.insert(name, value);
+                }
+                other => return Err(format!("unexpected {other:?} in arguments")),
+            }
+        }
+    }
+
+    // 스칼라 필드라 하위 선택은 무시
+    if tokens.peek() == Some(&Token::Punct('{')) {
+        skip_group(&mut tokens, '{', '}')?;
+    }
+    match tokens.next() {
+        Some(Token::Punct('}')) => {}
+        Some(Token::Name(_)) => return Err("only one root field is supported".to_owned()),
+        other => return Err(format!("expected `}}`, found {other:?}")),
+    }
+    if let Some(token) = tokens.next() {
+        return Err(format!("only one operation is supported, found {token:?}"));
+    }
+
+    Ok(Operation {
+        kind,
+        alias,
+        field,
+        args,
+    })
+}
+
+fn expect(tokens: &mut impl Iterator<Item = Token>, punct: char) -> Result<(), String> {
+    match tokens.next() {
+        Some(Token::Punct(c)) if c == punct => Ok(()),
+        other => Err(format!("expected `{punct}`, found {other:?}")),
+    }
+}
+
+// 여는 괄호부터 짝이 맞는 닫는 괄호까지 건너뜀
+fn skip_group(
+    tokens: &mut impl Iterator<Item = Token>,
+    open: char,
+    close: char,
+) -> Result<(), String> {
+    let mut depth = 0;
+    for token in tokens.by_ref() {
+        match token {
+            Token::Punct(c) if c == open => depth += 1,
+            Token::Punct(c) if c == close => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(());
+                }
+            }
+            _ => {}
+        }
+    }
+    Err(format!("unclosed `{open}`"))
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            // 쉼표는 GraphQL에서 공백과 같음
+            c if c.is_whitespace() || c == ',' => {
+                chars.next();
+            }
+            '#' => while chars.next_if(|&c| c != '\n').is_some() {},
+            '{' | '}' | '(' | ')' | ':' | '$' | '!' | '[' | ']' | '=' | '@' => {
+                tokens.push(Token::Punct(c));
+                chars.next();
+            }
+            '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => value.push('\n'),
+                            Some('t') => value.push('\t'),
+                            Some(c @ ('"' | '\\' | '/')) => value.push(c),
+                            other => return Err(format!("unsupported escape {other:?}")),
+                        },
+                        Some(c) => value.push(c),
+                        None => return Err("unterminated string".to_owned()),
+                    }
+                }
+                tokens.push(Token::Value(Value::String(value)));
+            }
+            c if c == '-' || c.is_ascii_digit() => {
+                let mut number = String::new();
+                while let Some(c) =
+                    chars.next_if(|c| c.is_ascii_alphanumeric() || "-+.".contains(*c))
+                {
+                    number.push(c);
+                }
+                let value = serde_json::from_str::<serde_json::Number>(&number)
+                    .map_err(|_| format!("invalid number `{number}`"))?;
+                tokens.push(Token::Value(Value::Number(value)));
+            }
+            c if c == '_' || c.is_ascii_alphabetic() => {
+                let mut name = String::new();
+                while let Some(c) = chars.next_if(|c| *c == '_' || c.is_ascii_alphanumeric()) {
+                    name.push(c);
+                }
+                tokens.push(Token::Name(name));
+            }
+            other => return Err(format!("unexpected character `{other}`")),
+        }
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn parses_operations_with_arguments_and_variables() {
+        let operation = parse(
+            r#"mutation Send($text: String!) {
+                sent: sendMessage(username: "alice", text: $text)  # 결과는 문자열
+            }"#,
+            &vars(json!({ "text": "hi \"there\"" })),
+        )
+        .unwrap();
+        assert_eq!(operation.kind, OperationKind::Mutation);
+        assert_eq!(operation.response_key(), "sent");
+        assert_eq!(operation.field, "sendMessage");
+        assert_eq!(operation.string("username"), Some("alice"));
+        assert_eq!(operation.string("text"), Some("hi \"there\""));
+
+        let operation = parse("subscription OnMessage { messageAdded }", &Map::new()).unwrap();
+        assert_eq!(operation.kind, OperationKind::Subscription);
+        assert_eq!(operation.response_key(), "messageAdded");
+
+        let operation = parse("{ __typename }", &Map::new()).unwrap();
+        assert_eq!(operation.kind, OperationKind::Query);
+    }
+
+    #[test]
+    fn rejects_what_the_bridge_does_not_support() {
+        let err = |query: &str| parse(query, &Map::new()).unwrap_err();
+        assert_eq!(
+            err("subscription { messageAdded userJoined }"),
+            "only one root field is supported"
+        );
+        assert!(err("fragment F on Message { id }").contains("unsupported definition"));
+        assert!(err(r#"mutation { sendMessage(text: "oops) }"#).contains("unterminated"));
+        assert!(err("query { a } query { b }").contains("only one operation"));
+    }
+}
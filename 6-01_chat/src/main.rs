@@ -6,25 +6,58 @@
 //! ```not_rust
 //! cargo run -p example-chat
 //! ```
+//!
+//! 여러 인스턴스가 하나의 채팅방을 공유하도록 Redis pub/sub 백플레인을 켜려면
+//!
+//! ```not_rust
+//! REDIS_URL=redis://localhost PORT=3000 cargo run -p example-chat --features redis
+//! REDIS_URL=redis://localhost PORT=3001 cargo run -p example-chat --features redis
+//! ```
+//!
+//! 메시지는 SQLite(`DATABASE_URL`, 기본값 `sqlite://chat.db`)에 저장됨
+//! • 새로 접속한 클라이언트는 DB에서 읽은 최근 메시지를 먼저 받음
+//! • `GET /rooms/{room}/messages?before=<ts>&q=<term>`: 이전 메시지 조회와 본문 검색 (FTS5)
+//!
+//! 채팅방은 아직 하나(`lobby`)뿐이지만 저장소와 조회 API는 방 단위로 구성
+//!
+//! 같은 채팅방을 GraphQL로도 사용할 수 있음 (`graphql` 모듈)
+//! • `POST /graphql`: `mutation { sendMessage(username, text) }`
+//! • `GET /graphql/ws`: graphql-ws 프로토콜의 `subscription { messageAdded }`
+
+mod backplane;
+mod graphql;
+mod store;
 
 use axum::{
     extract::{
         ws::{Message, Utf8Bytes, WebSocket, WebSocketUpgrade},
-        State,
+        Path, Query, State,
     },
+    http::StatusCode,
     response::{Html, IntoResponse},
-    routing::get,
-    Router,
+    routing::{get, post},
+    Json, Router,
 };
+use backplane::Backplane;
 use futures::{sink::SinkExt, stream::StreamExt};
+use serde::Deserialize;
 use std::{
     collections::HashSet,
     sync::{Arc, Mutex},
 };
+use store::{HistoryQuery, Store, StoredMessage};
 use tokio::sync::broadcast;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-/// ✅ 1. 상태 공유 구조체 정의
+// 웹소켓으로 접속한 사용자가 들어가는 채팅방
+const ROOM: &str = "lobby";
+// 접속 직후 보내 주는 최근 메시지 수
+const HISTORY_ON_JOIN: u32 = 50;
+// `GET /rooms/{room}/messages`의 `limit` 기본값과 최댓값
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 200;
+
+// ✅ 1. 상태 공유 구조체 정의
 
 // Our shared state
 struct AppState {
@@ -35,6 +68,43 @@ struct AppState {
     // Channel used to send messages to all connected clients.
     // 메시지를 모든 클라이언트에게 브로드캐스트하는 채널
     tx: broadcast::Sender<String>,
+
+    // Optional backplane that mirrors broadcasts to other server instances.
+    // 설정되어 있으면 메시지를 백플레인으로 발행하고, 백플레인이 다시 `tx`로 전달
+    backplane: Option<Backplane>,
+
+    // 메시지 기록 (SQLite)
+    store: Store,
+}
+
+impl AppState {
+    fn new(store: Store) -> Self {
+        // broadcast::channel은 하나가 메시지를 보내면 구독자 모두에게 전달
+        let (tx, _rx) = broadcast::channel(100);
+
+        Self {
+            user_set: Mutex::new(HashSet::new()),
+            tx,
+            backplane: None,
+            store,
+        }
+    }
+
+    /// 모든 클라이언트(백플레인이 있으면 모든 인스턴스의 클라이언트)에게 메시지 전송
+    fn broadcast(&self, msg: String) {
+        match &self.backplane {
+            Some(backplane) => {
+                // 발행 task가 죽었다면 최소한 이 인스턴스의 클라이언트에게는 전달
+                if let Err(msg) = backplane.publish(msg) {
+                    tracing::warn!("backplane unavailable, delivering locally");
+                    let _ = self.tx.send(msg);
+                }
+            }
+            None => {
+                let _ = self.tx.send(msg);
+            }
+        }
+    }
 }
 
 #[tokio::main]
@@ -49,24 +119,86 @@ async fn main() {
 
     // ✅ 2. main 함수 - 서버 및 상태 초기화
 
+    let database_url =
+        std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite://chat.db".to_owned());
+    tracing::debug!("opening message store at {database_url}");
+    let store = Store::connect(&database_url).await.unwrap();
+
     // Set up application state for use with with_state().
-    let user_set = Mutex::new(HashSet::new());
-    // broadcast::channel은 하나가 메시지를 보내면 구독자 모두에게 전달
-    let (tx, _rx) = broadcast::channel(100);
+    let app_state = AppState::new(store);
+
+    // Redis 백플레인 연결 (연결이 끊겨도 백그라운드에서 재연결을 시도함)
+    #[cfg(feature = "redis")]
+    let app_state = {
+        let url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://localhost".to_owned());
+        tracing::debug!("connecting to redis backplane at {url}");
+        let backplane = backplane::redis::connect(&url, app_state.tx.clone())
+            .await
+            .unwrap();
+        AppState {
+            backplane: Some(backplane),
+            ..app_state
+        }
+    };
+
+    // 같은 머신에서 여러 인스턴스를 띄울 수 있도록 포트를 환경 변수로 받음
+    let port = std::env::var("PORT").unwrap_or_else(|_| "3000".to_owned());
+    let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{port}"))
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
 
     // Arc: AppState를 여러 task 간 공유 가능하게 함
-    let app_state = Arc::new(AppState { user_set, tx });
+    axum::serve(listener, app(Arc::new(app_state)))
+        .await
+        .unwrap();
+}
 
-    let app = Router::new()
+fn app(state: Arc<AppState>) -> Router {
+    Router::new()
         .route("/", get(index))
         .route("/websocket", get(websocket_handler))
-        .with_state(app_state);
+        .route("/rooms/{room}/messages", get(room_messages))
+        .route("/graphql", post(graphql::http_handler))
+        .route("/graphql/ws", get(graphql::ws_handler))
+        .with_state(state)
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryParams {
+    before: Option<i64>,
+    q: Option<String>,
+    limit: Option<u32>,
+}
 
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+/// 📜 저장된 메시지 조회 (오래된 순, 이전 페이지는 첫 메시지의 `sent_at`을 `before`로)
+async fn room_messages(
+    State(state): State<Arc<AppState>>,
+    Path(room): Path<String>,
+    Query(params): Query<HistoryParams>,
+) -> Result<Json<Vec<StoredMessage>>, (StatusCode, String)> {
+    let query = HistoryQuery {
+        before: params.before,
+        // `?q=`처럼 비어 있으면 검색하지 않음
+        search: params.q.as_deref().filter(|q| !q.trim().is_empty()),
+        limit: params
+            .limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE),
+    };
+
+    state
+        .store
+        .history(&room, &query)
         .await
-        .unwrap();
-    tracing::debug!("listening on {}", listener.local_addr().unwrap());
-    axum::serve(listener, app).await.unwrap();
+        .map(Json)
+        .map_err(|err| {
+            tracing::error!("failed to load messages: {err}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to load messages".to_owned(),
+            )
+        })
 }
 
 /// ✅ 3. WebSocket 연결 핸들러
@@ -78,7 +210,7 @@ async fn websocket_handler(
     ws.on_upgrade(|socket| websocket(socket, state))
 }
 
-/// ✅ 4. 각 사용자의 WebSocket 처리
+// ✅ 4. 각 사용자의 WebSocket 처리
 
 // This function deals with a single websocket connection, i.e., a single
 // connected client / user, for which we will spawn two independent tasks (for
@@ -119,11 +251,33 @@ async fn websocket(stream: WebSocket, state: Arc<AppState>) {
     // display it to our client.
     let mut rx = state.tx.subscribe();
 
+    // 📜 최근 메시지를 DB에서 읽어 먼저 전송
+    // 구독 후에 읽으므로 그 사이에 저장된 메시지는 두 번 보일 수 있지만 빠지지는 않음
+    let recent = HistoryQuery {
+        limit: HISTORY_ON_JOIN,
+        ..Default::default()
+    };
+    match state.store.history(ROOM, &recent).await {
+        Ok(messages) => {
+            for message in messages {
+                if sender
+                    .send(Message::text(message.to_chat_line()))
+                    .await
+                    .is_err()
+                {
+                    state.user_set.lock().unwrap().remove(&username);
+                    return;
+                }
+            }
+        }
+        Err(err) => tracing::error!("failed to load history: {err}"),
+    }
+
     // Now send the "joined" message to all subscribers.
     // 사용자 입장을 브로드캐스트로 알림
     let msg = format!("{username} joined.");
     tracing::debug!("{msg}");
-    let _ = state.tx.send(msg);
+    state.broadcast(msg);
 
     //📡 메시지 송수신 Task 분리
 
@@ -140,16 +294,20 @@ async fn websocket(stream: WebSocket, state: Arc<AppState>) {
     });
 
     // Clone things we want to pass (move) to the receiving task.
-    let tx = state.tx.clone();
+    let recv_state = state.clone();
     let name = username.clone();
 
     // Spawn a task that takes messages from the websocket, prepends the user
     // name, and sends them to all broadcast subscribers.
-    // 클라이언트로부터 수신한 메시지를 브로드캐스트로 전달
+    // 클라이언트로부터 수신한 메시지를 저장한 뒤 브로드캐스트로 전달
     let mut recv_task = tokio::spawn(async move {
         while let Some(Ok(Message::Text(text))) = receiver.next().await {
+            // 저장에 실패해도 실시간 전달은 계속함
+            if let Err(err) = recv_state.store.insert(ROOM, &name, text.as_str()).await {
+                tracing::error!("failed to store message: {err}");
+            }
             // Add username before message.
-            let _ = tx.send(format!("{name}: {text}"));
+            recv_state.broadcast(format!("{name}: {text}"));
         }
     });
 
@@ -164,7 +322,7 @@ async fn websocket(stream: WebSocket, state: Arc<AppState>) {
     // 사용자 퇴장을 브로드캐스트로 알림
     let msg = format!("{username} left.");
     tracing::debug!("{msg}");
-    let _ = state.tx.send(msg);
+    state.broadcast(msg);
 
     // Remove username from map so new clients can take it again.
     // 닉네임은 다시 사용 가능하도록 user_set에서 제거
@@ -181,7 +339,7 @@ fn check_username(state: &AppState, string: &mut String, name: &str) {
     }
 }
 
-///✅ 5. HTML 렌더링
+//✅ 5. HTML 렌더링
 
 // Include utf-8 file at **compile** time.
 async fn index() -> Html<&'static str> {
@@ -189,12 +347,398 @@ async fn index() -> Html<&'static str> {
     Html(std::include_str!("../chat.html"))
 }
 
+// --- 🧪 테스트 모듈
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backplane::InMemoryBus;
+    use std::{
+        future::IntoFuture,
+        net::{Ipv4Addr, SocketAddr},
+    };
+    use tokio::net::TcpStream;
+    use tokio_tungstenite::{tungstenite, MaybeTlsStream, WebSocketStream};
+
+    type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+    // 같은 버스에 붙은 인스턴스 하나를 임의의 포트로 실행
+    async fn spawn_instance(bus: &InMemoryBus, store: Store) -> SocketAddr {
+        let mut state = AppState::new(store);
+        state.backplane = Some(bus.attach(state.tx.clone()));
+        serve(Arc::new(state)).await
+    }
+
+    async fn serve(state: Arc<AppState>) -> SocketAddr {
+        let listener = tokio::net::TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)))
+            .await
+            .unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(axum::serve(listener, app(state)).into_future());
+        addr
+    }
+
+    async fn join(addr: SocketAddr, username: &str) -> Client {
+        let (mut socket, _response) =
+            tokio_tungstenite::connect_async(format!("ws://{addr}/websocket"))
+                .await
+                .unwrap();
+        socket
+            .send(tungstenite::Message::text(username))
+            .await
+            .unwrap();
+        socket
+    }
+
+    async fn next_text(socket: &mut Client) -> String {
+        match socket.next().await.unwrap().unwrap() {
+            tungstenite::Message::Text(msg) => msg.to_string(),
+            other => panic!("expected a text message but got {other:?}"),
+        }
+    }
+
+    // Two in-process instances sharing one bus behave like two servers behind Redis.
+    // Redis 없이 두 인스턴스가 같은 채팅방을 공유하는지 확인
+    #[tokio::test]
+    async fn messages_are_shared_between_instances() {
+        let bus = InMemoryBus::new();
+        let store = Store::in_memory().await;
+        let first = spawn_instance(&bus, store.clone()).await;
+        let second = spawn_instance(&bus, store).await;
+
+        let mut alice = join(first, "alice").await;
+        assert_eq!(next_text(&mut alice).await, "alice joined.");
+
+        let mut bob = join(second, "bob").await;
+        assert_eq!(next_text(&mut bob).await, "bob joined.");
+        // 다른 인스턴스에 접속한 사용자의 입장 메시지도 전달됨
+        assert_eq!(next_text(&mut alice).await, "bob joined.");
+
+        alice
+            .send(tungstenite::Message::text("hello from the first instance"))
+            .await
+            .unwrap();
+        assert_eq!(
+            next_text(&mut bob).await,
+            "alice: hello from the first instance"
+        );
+        assert_eq!(
+            next_text(&mut alice).await,
+            "alice: hello from the first instance"
+        );
+    }
+
+    // 백플레인이 없으면 기존처럼 로컬 broadcast 채널만 사용
+    #[tokio::test]
+    async fn broadcast_without_backplane_is_local() {
+        let state = AppState::new(Store::in_memory().await);
+        let mut rx = state.tx.subscribe();
+
+        state.broadcast("hi".to_owned());
+
+        assert_eq!(rx.recv().await.unwrap(), "hi");
+    }
+
+    // 나중에 들어온 사용자는 DB에 저장된 이전 메시지를 먼저 받음
+    #[tokio::test]
+    async fn history_is_replayed_from_the_store_on_join() {
+        let store = Store::in_memory().await;
+        let addr = spawn_instance(&InMemoryBus::new(), store.clone()).await;
+
+        let mut alice = join(addr, "alice").await;
+        assert_eq!(next_text(&mut alice).await, "alice joined.");
+        alice
+            .send(tungstenite::Message::text("anyone here?"))
+            .await
+            .unwrap();
+        assert_eq!(next_text(&mut alice).await, "alice: anyone here?");
+
+        let mut bob = join(addr, "bob").await;
+        assert_eq!(next_text(&mut bob).await, "alice: anyone here?");
+        assert_eq!(next_text(&mut bob).await, "bob joined.");
+
+        // 입장/퇴장 알림은 저장하지 않음
+        let stored = store
+            .history(
+                ROOM,
+                &HistoryQuery {
+                    limit: 10,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].username, "alice");
+    }
+
+    #[tokio::test]
+    async fn room_messages_endpoint_searches_history() {
+        use http_body_util::BodyExt;
+        use tower::ServiceExt;
+
+        let store = Store::in_memory().await;
+        store.insert(ROOM, "alice", "ship it").await.unwrap();
+        store.insert(ROOM, "bob", "not yet").await.unwrap();
+        let app = app(Arc::new(AppState::new(store)));
+
+        let get = |uri: &'static str| {
+            let app = app.clone();
+            async move {
+                let request = axum::http::Request::get(uri)
+                    .body(axum::body::Body::empty())
+                    .unwrap();
+                let response = app.oneshot(request).await.unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                let bytes = response.into_body().collect().await.unwrap().to_bytes();
+                serde_json::from_slice::<serde_json::Value>(&bytes).unwrap()
+            }
+        };
+
+        let all = get("/rooms/lobby/messages").await;
+        assert_eq!(all.as_array().unwrap().len(), 2);
+        assert_eq!(all[0]["body"], "ship it");
+
+        let found = get("/rooms/lobby/messages?q=yet").await;
+        assert_eq!(found.as_array().unwrap().len(), 1);
+        assert_eq!(found[0]["username"], "bob");
+
+        let empty_search = get("/rooms/lobby/messages?q=&limit=1").await;
+        assert_eq!(empty_search[0]["body"], "not yet");
+
+        assert_eq!(get("/rooms/other/messages").await, serde_json::json!([]));
+    }
+
+    mod graphql {
+        use super::*;
+        use http_body_util::BodyExt;
+        use serde_json::{json, Value};
+        use tokio_tungstenite::tungstenite::{
+            client::IntoClientRequest, protocol::frame::coding::CloseCode,
+        };
+        use tower::ServiceExt;
+
+        // graphql-transport-ws 서브프로토콜로 접속
+        async fn connect(addr: SocketAddr) -> Client {
+            let mut request = format!("ws://{addr}/graphql/ws")
+                .into_client_request()
+                .unwrap();
+            request.headers_mut().insert(
+                "sec-websocket-protocol",
+                crate::graphql::PROTOCOL.parse().unwrap(),
+            );
+            let (socket, response) = tokio_tungstenite::connect_async(request).await.unwrap();
+            assert_eq!(
+                response.headers()["sec-websocket-protocol"],
+                crate::graphql::PROTOCOL
+            );
+            socket
+        }
+
+        async fn send(socket: &mut Client, message: Value) {
+            socket
+                .send(tungstenite::Message::text(message.to_string()))
+                .await
+                .unwrap();
+        }
+
+        async fn next_json(socket: &mut Client) -> Value {
+            serde_json::from_str(&next_text(socket).await).unwrap()
+        }
+
+        // 초기화 후 `messageAdded` 구독
+        async fn subscribe(addr: SocketAddr) -> Client {
+            let mut socket = connect(addr).await;
+            send(&mut socket, json!({ "type": "connection_init" })).await;
+            assert_eq!(
+                next_json(&mut socket).await,
+                json!({ "type": "connection_ack" })
+            );
+            send(
+                &mut socket,
+                json!({
+                    "type": "subscribe",
+                    "id": "1",
+                    "payload": { "query": "subscription { latest: messageAdded }" },
+                }),
+            )
+            .await;
+            // pong이 오면 `subscribe`가 처리된 것
+            send(&mut socket, json!({ "type": "ping" })).await;
+            assert_eq!(next_json(&mut socket).await, json!({ "type": "pong" }));
+            socket
+        }
+
+        async fn post_graphql(app: Router, body: Value) -> Value {
+            let request = axum::http::Request::post("/graphql")
+                .header("content-type", "application/json")
+                .body(axum::body::Body::from(body.to_string()))
+                .unwrap();
+            let response = app.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = response.into_body().collect().await.unwrap().to_bytes();
+            serde_json::from_slice(&bytes).unwrap()
+        }
+
+        // HTTP mutation → graphql-ws 구독자와 일반 채팅 클라이언트 모두에게 전달
+        #[tokio::test]
+        async fn http_mutation_reaches_subscribers_and_chat_clients() {
+            let store = Store::in_memory().await;
+            let state = Arc::new(AppState::new(store.clone()));
+            let addr = serve(state.clone()).await;
+
+            let mut subscriber = subscribe(addr).await;
+            let mut alice = join(addr, "alice").await;
+            assert_eq!(next_text(&mut alice).await, "alice joined.");
+            assert_eq!(
+                next_json(&mut subscriber).await["payload"],
+                json!({ "data": { "latest": "alice joined." } })
+            );
+
+            let result = post_graphql(
+                app(state),
+                json!({
+                    "query": "mutation Send($text: String!) { sendMessage(username: \"bot\", text: $text) }",
+                    "variables": { "text": "deploy finished" },
+                }),
+            )
+            .await;
+            assert_eq!(
+                result,
+                json!({ "data": { "sendMessage": "bot: deploy finished" } })
+            );
+
+            assert_eq!(
+                next_json(&mut subscriber).await,
+                json!({
+                    "id": "1",
+                    "type": "next",
+                    "payload": { "data": { "latest": "bot: deploy finished" } },
+                })
+            );
+            assert_eq!(next_text(&mut alice).await, "bot: deploy finished");
+
+            // 웹소켓 채팅 메시지도 구독자에게 전달
+            alice
+                .send(tungstenite::Message::text("nice"))
+                .await
+                .unwrap();
+            assert_eq!(
+                next_json(&mut subscriber).await["payload"]["data"]["latest"],
+                "alice: nice"
+            );
+            assert_eq!(next_text(&mut alice).await, "alice: nice");
+
+            // mutation으로 보낸 메시지도 기록에 남음
+            let stored = store
+                .history(
+                    ROOM,
+                    &HistoryQuery {
+                        limit: 10,
+                        ..Default::default()
+                    },
+                )
+                .await
+                .unwrap();
+            assert_eq!(stored[0].username, "bot");
+
+            // 구독 종료 후에는 더 이상 받지 않음 (pong이 오면 종료가 처리된 것)
+            send(&mut subscriber, json!({ "type": "complete", "id": "1" })).await;
+            send(&mut subscriber, json!({ "type": "ping" })).await;
+            assert_eq!(next_json(&mut subscriber).await, json!({ "type": "pong" }));
+            alice
+                .send(tungstenite::Message::text("anyone?"))
+                .await
+                .unwrap();
+            assert_eq!(next_text(&mut alice).await, "alice: anyone?");
+            send(&mut subscriber, json!({ "type": "ping" })).await;
+            assert_eq!(next_json(&mut subscriber).await, json!({ "type": "pong" }));
+        }
+
+        #[tokio::test]
+        async fn http_endpoint_reports_errors() {
+            let app = app(Arc::new(AppState::new(Store::in_memory().await)));
+
+            let result = post_graphql(
+                app.clone(),
+                json!({ "query": "subscription { messageAdded }" }),
+            )
+            .await;
+            assert!(result["errors"][0]["message"]
+                .as_str()
+                .unwrap()
+                .contains("/graphql/ws"));
+
+            let result =
+                post_graphql(app, json!({ "query": "mutation { sendMessage(text: 1) }" })).await;
+            assert!(result["data"].is_null());
+            assert!(result["errors"][0]["message"]
+                .as_str()
+                .unwrap()
+                .contains("sendMessage"));
+        }
+
+        async fn close_code(socket: &mut Client) -> CloseCode {
+            loop {
+                match socket.next().await.unwrap().unwrap() {
+                    tungstenite::Message::Close(Some(frame)) => return frame.code,
+                    tungstenite::Message::Close(None) => panic!("closed without a code"),
+                    _ => {}
+                }
+            }
+        }
+
+        // 프로토콜 위반은 graphql-ws에서 정한 코드로 연결을 닫음
+        #[tokio::test]
+        async fn protocol_violations_close_the_connection() {
+            let addr = serve(Arc::new(AppState::new(Store::in_memory().await))).await;
+            let subscribe = json!({
+                "type": "subscribe",
+                "id": "1",
+                "payload": { "query": "subscription { messageAdded }" },
+            });
+
+            let mut socket = connect(addr).await;
+            send(&mut socket, subscribe.clone()).await;
+            assert_eq!(close_code(&mut socket).await, CloseCode::from(4401));
+
+            let mut socket = connect(addr).await;
+            send(&mut socket, json!({ "type": "connection_init" })).await;
+            send(&mut socket, json!({ "type": "connection_init" })).await;
+            assert_eq!(close_code(&mut socket).await, CloseCode::from(4429));
+
+            let mut socket = connect(addr).await;
+            send(&mut socket, json!({ "type": "connection_init" })).await;
+            send(&mut socket, subscribe.clone()).await;
+            send(&mut socket, subscribe).await;
+            assert_eq!(close_code(&mut socket).await, CloseCode::from(4409));
+
+            let mut socket = connect(addr).await;
+            send(&mut socket, json!({ "type": "shout" })).await;
+            assert_eq!(close_code(&mut socket).await, CloseCode::from(4400));
+        }
+    }
+}
+
 // ⸻
 
 // 🧪 테스트 방법
 // 	1.	브라우저에서 localhost:3000 접속
 // 	2.	여러 탭에서 접속 후 닉네임 입력 → 채팅 메시지 입력
 // 	3.	서버 로그에도 “joined”, “left” 로그 출력 확인
+// 	4.	Redis 백플레인: redis-server 실행 후 PORT=3000, PORT=3001로 두 인스턴스를
+// 		`--features redis`로 띄우고 각각 다른 탭에서 접속 → 같은 채팅방 공유 확인
+// 	5.	cargo test -p example-chat (Redis 없이 두 인스턴스를 프로세스 내부에서 테스트)
+// 	6.	새 탭으로 접속하면 이전 메시지가 먼저 보임, 기록 조회와 검색은
+// 		curl 'localhost:3000/rooms/lobby/messages?q=hello&limit=20'
+// 	7.	GraphQL: 채팅 탭을 열어 둔 채로
+// 		curl localhost:3000/graphql -H 'content-type: application/json' \
+// 		  -d '{"query":"mutation { sendMessage(username: \"bot\", text: \"hi\") }"}'
+// 		→ 채팅 탭에 "bot: hi" 표시
+// 		구독: websocat -H 'Sec-WebSocket-Protocol: graphql-transport-ws' ws://localhost:3000/graphql/ws
+// 		  {"type":"connection_init"}
+// 		  {"type":"subscribe","id":"1","payload":{"query":"subscription { messageAdded }"}}
+// 		→ 채팅 탭에서 보낸 메시지가 "next"로 도착
 
 // ⸻
 
@@ -208,6 +752,10 @@ async fn index() -> Html<&'static str> {
 //       │   ├── 입장/퇴장 알림
 //       │   ├── 수신 메시지 → broadcast 채널 전파
 //       │   └── 전파 메시지 → 각 사용자에게 전달
+// GraphQL 클라이언트
+//  ├── POST /graphql (sendMessage) → 같은 broadcast 채널로 전파
+//  └── WebSocket(ws://localhost:3000/graphql/ws, graphql-transport-ws)
+//       └── messageAdded 구독 → broadcast 채널의 모든 메시지를 "next"로 전달
 
 // ⸻
 
@@ -0,0 +1,220 @@
+//! 💾 채팅 메시지 저장소 (SQLite + FTS5 전문 검색)
+//!
+//! • 사용자가 보낸 메시지만 저장 (입장/퇴장 알림은 저장하지 않음)
+//! • 새로 접속한 클라이언트에게 보낼 최근 메시지와 `GET /rooms/{room}/messages` 조회에 사용
+//! • Redis 백플레인으로 여러 인스턴스를 띄울 때는 같은 DB 파일을 가리키게 하면 기록도 공유됨
+//!   (메시지는 클라이언트에게서 받은 인스턴스만 저장하므로 중복 저장되지 않음)
+
+use serde::Serialize;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions};
+use std::{
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, sqlx::FromRow)]
+pub struct StoredMessage {
+    pub id: i64,
+    pub room: String,
+    pub username: String,
+    pub body: String,
+    /// Unix time (밀리초)
+    pub sent_at: i64,
+}
+
+impl StoredMessage {
+    /// 실시간 메시지와 같은 `이름: 내용` 형식 (chat.html이 그대로 출력)
+    pub fn to_chat_line(&self) -> String {
+        format!("{}: {}", self.username, self.body)
+    }
+}
+
+/// 조회 조건
+#[derive(Debug, Default)]
+pub struct HistoryQuery<'a> {
+    /// 이 시각(밀리초)보다 이전 메시지만 → 응답의 첫 메시지 `sent_at`을 넘기면 이전 페이지
+    pub before: Option<i64>,
+    /// 본문 검색어 (입력한 문자열 그대로 구문 검색)
+    pub search: Option<&'a str>,
+    pub limit: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Store {
+    pool: SqlitePool,
+}
+
+impl Store {
+    /// DB 파일이 없으면 만들고 마이그레이션 실행
+    pub async fn connect(url: &str) -> Result<Self, sqlx::Error> {
+        let options = SqliteConnectOptions::from_str(url)?
+            .create_if_missing(true)
+            // 읽기(기록 조회)와 쓰기(메시지 저장)가 서로 막지 않도록
+            .journal_mode(SqliteJournalMode::Wal);
+        Self::with_pool(SqlitePoolOptions::new().connect_with(options).await?).await
+    }
+
+    /// 테스트용 메모리 DB
+    ///
+    /// 메모리 DB는 연결마다 따로 생기므로 연결 하나만 계속 유지
+    #[cfg(test)]
+    pub async fn in_memory() -> Self {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        Self::with_pool(pool).await.unwrap()
+    }
+
+    async fn with_pool(pool: SqlitePool) -> Result<Self, sqlx::Error> {
+        sqlx::migrate!().run(&pool).await?;
+        Ok(Self { pool })
+    }
+
+    pub async fn insert(
+        &self,
+        room: &str,
+        username: &str,
+        body: &str,
+    ) -> Result<StoredMessage, sqlx::Error> {
+        sqlx::query_as(
+            "INSERT INTO messages (room, username, body, sent_at) VALUES (?, ?, ?, ?)
+             RETURNING id, room, username, body, sent_at",
+        )
+        .bind(room)
+        .bind(username)
+        .bind(body)
+        .bind(unix_millis())
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// 조건에 맞는 메시지 중 최신 `limit`개를 오래된 순으로 반환
+    pub async fn history(
+        &self,
+        room: &str,
+        query: &HistoryQuery<'_>,
+    ) -> Result<Vec<StoredMessage>, sqlx::Error> {
+        let before = query.before.unwrap_or(i64::MAX);
+
+        let mut messages: Vec<StoredMessage> = match query.search {
+            Some(search) => {
+                sqlx::query_as(
+                    "SELECT m.id, m.room, m.username, m.body, m.sent_at
+                     FROM messages m JOIN messages_fts ON messages_fts.rowid = m.id
+                     WHERE messages_fts MATCH ? AND m.room = ? AND m.sent_at < ?
+                     ORDER BY m.sent_at DESC, m.id DESC LIMIT ?",
+                )
+                .bind(fts_phrase(search))
+                .bind(room)
+                .bind(before)
+                .bind(query.limit)
+                .fetch_all(&self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query_as(
+                    "SELECT id, room, username, body, sent_at FROM messages
+                     WHERE room = ? AND sent_at < ?
+                     ORDER BY sent_at DESC, id DESC LIMIT ?",
+                )
+                .bind(room)
+                .bind(before)
+                .bind(query.limit)
+                .fetch_all(&self.pool)
+                .await?
+            }
+        };
+
+        messages.reverse();
+        Ok(messages)
+    }
+}
+
+// FTS5 문법(AND, OR, *, " 등)을 그대로 받으면 잘못된 입력에서 쿼리가 실패하므로
+// 전체를 하나의 구문(phrase)으로 감쌈
+fn fts_phrase(search: &str) -> String {
+    format!("\"{}\"", search.replace('"', "\"\""))
+}
+
+fn unix_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bodies(messages: &[StoredMessage]) -> Vec<&str> {
+        messages.iter().map(|m| m.body.as_str()).collect()
+    }
+
+    fn query(limit: u32) -> HistoryQuery<'static> {
+        HistoryQuery {
+            limit,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn history_is_paged_backwards_in_chronological_order() {
+        let store = Store::in_memory().await;
+        for body in ["one", "two", "three"] {
+            store.insert("lobby", "alice", body).await.unwrap();
+            // sent_at이 겹치지 않도록
+            tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+        }
+        store.insert("other", "bob", "elsewhere").await.unwrap();
+
+        let page = store.history("lobby", &query(2)).await.unwrap();
+        assert_eq!(bodies(&page), ["two", "three"]);
+
+        let before = HistoryQuery {
+            before: Some(page[0].sent_at),
+            ..query(2)
+        };
+        let page = store.history("lobby", &before).await.unwrap();
+        assert_eq!(bodies(&page), ["one"]);
+    }
+
+    #[tokio::test]
+    async fn full_text_search_matches_words_within_a_room() {
+        let store = Store::in_memory().await;
+        store
+            .insert("lobby", "alice", "Deploying the new release")
+            .await
+            .unwrap();
+        store.insert("lobby", "bob", "lunch?").await.unwrap();
+        store
+            .insert("ops", "carol", "release is out")
+            .await
+            .unwrap();
+
+        let search = |search| HistoryQuery {
+            search: Some(search),
+            ..query(10)
+        };
+        // 대소문자 구분 없이 단어 단위로 일치
+        let found = store.history("lobby", &search("RELEASE")).await.unwrap();
+        assert_eq!(bodies(&found), ["Deploying the new release"]);
+        assert_eq!(found[0].username, "alice");
+
+        assert!(store
+            .history("lobby", &search("rel"))
+            .await
+            .unwrap()
+            .is_empty());
+        // FTS5 문법 문자가 있어도 에러가 아니라 일반 검색어로 처리
+        assert!(store
+            .history("lobby", &search("\"AND (*"))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -1,5 +1,27 @@
-var eventSource = new EventSource('sse');
+if (window.EventSource) {
+    var eventSource = new EventSource('sse');
 
-eventSource.onmessage = function(event) {
-    console.log('Message from server ', event.data);
+    // 연결이 끊기면 EventSource가 자동으로 재연결하면서 Last-Event-ID(= 마지막 event.lastEventId)를 보냄
+    // → 서버가 놓친 이벤트부터 다시 보내므로 ID가 빠짐없이 이어짐
+    eventSource.onmessage = function(event) {
+        console.log('Message from server ', event.lastEventId, event.data);
+    }
+} else {
+    // SSE를 지원하지 않는 브라우저는 같은 이벤트 피드를 long-polling으로 받음
+    var since = 0;
+
+    var poll = function() {
+        fetch('poll?since=' + since)
+            .then(function(response) { return response.json(); })
+            .then(function(body) {
+                body.events.forEach(function(event) {
+                    console.log('Message from server ', event.data);
+                });
+                since = body.next;
+                poll();
+            })
+            .catch(function() { setTimeout(poll, 1000); });
+    }
+
+    poll();
 }
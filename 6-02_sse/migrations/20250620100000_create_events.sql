@@ -0,0 +1,5 @@
+-- 발행된 SSE 이벤트 (ID는 단조 증가, 재연결한 클라이언트에게 다시 보낼 때 사용)
+CREATE TABLE events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    data TEXT NOT NULL
+);
@@ -0,0 +1,282 @@
+//! 연결된 SSE 클라이언트마다 bounded `mpsc` 채널을 하나씩 할당하고,
+//! 서버 쪽에서 모든 클라이언트에게 이벤트를 fan-out 하는 관리자
+//!
+//! • 느린 클라이언트: 버퍼가 가득 차면 `try_send`가 실패 → 해당 클라이언트를 끊음 (backpressure)
+//! • 연결 종료 감지: 응답 스트림(`ClientStream`)이 drop 되면 관리 목록에서 제거
+//! • 재연결: 놓친 이벤트(`replay`)를 먼저 보내고 실시간 이벤트로 넘어감
+
+use crate::event_log::FeedEvent;
+use axum::response::sse::Event;
+use futures::Stream;
+use serde::Serialize;
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::Infallible,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+    time::Instant,
+};
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio_stream::wrappers::ReceiverStream;
+
+pub struct ClientManager {
+    // 클라이언트별 채널 버퍼 크기
+    buffer: usize,
+    next_id: AtomicU64,
+    clients: Mutex<Clients>,
+}
+
+#[derive(Default)]
+struct Clients {
+    connected: HashMap<u64, Client>,
+    // 버퍼가 가득 차서 끊긴 클라이언트 수
+    dropped_slow: u64,
+    // 스스로 연결을 끊은 클라이언트 수
+    disconnected: u64,
+}
+
+struct Client {
+    tx: mpsc::Sender<FeedEvent>,
+    user_agent: String,
+    connected_at: Instant,
+    events_sent: u64,
+}
+
+/// `GET /clients` 응답
+#[derive(Debug, Serialize)]
+pub struct ClientStats {
+    pub connected: usize,
+    pub dropped_slow: u64,
+    pub disconnected: u64,
+    pub clients: Vec<ClientInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClientInfo {
+    pub id: u64,
+    pub user_agent: String,
+    pub connected_secs: u64,
+    pub events_sent: u64,
+    // 아직 클라이언트로 전송되지 않고 버퍼에 남아 있는 이벤트 수
+    pub queued: usize,
+}
+
+impl ClientManager {
+    pub fn new(buffer: usize) -> Self {
+        Self {
+            buffer,
+            next_id: AtomicU64::new(1),
+            clients: Mutex::new(Clients::default()),
+        }
+    }
+
+    /// 새 클라이언트를 등록하고, 응답으로 돌려줄 이벤트 스트림을 반환
+    pub fn connect(self: &Arc<Self>, user_agent: String) -> ClientStream {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = mpsc::channel(self.buffer);
+
+        tracing::debug!(id, %user_agent, "client connected");
+        self.clients.lock().unwrap().connected.insert(
+            id,
+            Client {
+                tx,
+                user_agent,
+                connected_at: Instant::now(),
+                events_sent: 0,
+            },
+        );
+
+        ClientStream {
+            id,
+            missed: VecDeque::new(),
+            replayed_up_to: 0,
+            rx: ReceiverStream::new(rx),
+            manager: self.clone(),
+        }
+    }
+
+    /// Send `event` to every connected client without waiting on any of them.
+    /// 버퍼가 가득 찬 클라이언트는 기다리지 않고 바로 끊음
+    pub fn broadcast(&self, event: &FeedEvent) {
+        let mut clients = self.clients.lock().unwrap();
+        let mut slow = Vec::new();
+
+        for (id, client) in clients.connected.iter_mut() {
+            match client.tx.try_send(event.clone()) {
+                Ok(()) => client.events_sent += 1,
+                Err(TrySendError::Full(_)) => slow.push(*id),
+                // 수신 측이 이미 사라짐 → `ClientStream::drop`에서 정리됨
+                Err(TrySendError::Closed(_)) => {}
+            }
+        }
+
+        for id in slow {
+            // Sender를 drop 하면 남은 이벤트를 모두 보낸 뒤 스트림이 끝나고 응답이 종료됨
+            clients.connected.remove(&id);
+            clients.dropped_slow += 1;
+            tracing::warn!(id, "dropping slow client, buffer is full");
+        }
+    }
+
+    pub fn stats(&self) -> ClientStats {
+        let clients = self.clients.lock().unwrap();
+
+        let mut infos: Vec<_> = clients
+            .connected
+            .iter()
+            .map(|(id, client)| ClientInfo {
+                id: *id,
+                user_agent: client.user_agent.clone(),
+                connected_secs: client.connected_at.elapsed().as_secs(),
+                events_sent: client.events_sent,
+                queued: self.buffer - client.tx.capacity(),
+            })
+            .collect();
+        infos.sort_by_key(|info| info.id);
+
+        ClientStats {
+            connected: infos.len(),
+            dropped_slow: clients.dropped_slow,
+            disconnected: clients.disconnected,
+            clients: infos,
+        }
+    }
+
+    fn disconnect(&self, id: u64) {
+        let mut clients = self.clients.lock().unwrap();
+
+        // 느린 클라이언트로 이미 제거되었다면 다시 세지 않음
+        if clients.connected.remove(&id).is_some() {
+            clients.disconnected += 1;
+            tracing::debug!(id, "client disconnected");
+        }
+    }
+}
+
+/// 클라이언트 한 명의 SSE 이벤트 스트림
+///
+/// axum은 클라이언트가 연결을 끊으면 응답 body(=이 스트림)를 drop 하므로,
+/// `Drop` 구현으로 연결 종료를 감지할 수 있음
+pub struct ClientStream {
+    id: u64,
+    // 실시간 이벤트보다 먼저 보낼, 연결이 끊긴 동안 놓친 이벤트
+    missed: VecDeque<FeedEvent>,
+    // `missed`의 마지막 ID (실시간 채널에 같은 이벤트가 들어와 있으면 건너뜀)
+    replayed_up_to: u64,
+    rx: ReceiverStream<FeedEvent>,
+    manager: Arc<ClientManager>,
+}
+
+impl ClientStream {
+    /// 놓친 이벤트를 실시간 이벤트보다 먼저 보내도록 설정
+    ///
+    /// `connect`로 실시간 채널을 먼저 연 뒤에 DB를 읽어야 그 사이에 발행된 이벤트를 놓치지 않음
+    /// (양쪽에 모두 들어온 이벤트는 ID로 걸러서 한 번만 보냄)
+    pub fn replay(mut self, missed: Vec<FeedEvent>) -> Self {
+        self.replayed_up_to = missed.last().map_or(0, |event| event.id);
+        self.missed = missed.into();
+        self
+    }
+
+    fn poll_feed_event(&mut self, cx: &mut Context<'_>) -> Poll<Option<FeedEvent>> {
+        if let Some(event) = self.missed.pop_front() {
+            return Poll::Ready(Some(event));
+        }
+        loop {
+            match Pin::new(&mut self.rx).poll_next(cx) {
+                Poll::Ready(Some(event)) if event.id <= self.replayed_up_to => continue,
+                other => return other,
+            }
+        }
+    }
+}
+
+impl Stream for ClientStream {
+    type Item = Result<Event, Infallible>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_feed_event(cx).map(|event| {
+            // id를 함께 보내면 브라우저가 재연결 시 Last-Event-ID 헤더로 돌려줌
+            event.map(|event| Ok(Event::default().id(event.id.to_string()).data(event.data)))
+        })
+    }
+}
+
+impl Drop for ClientStream {
+    fn drop(&mut self) {
+        self.manager.disconnect(self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio_stream::StreamExt as _;
+
+    fn event(id: u64) -> FeedEvent {
+        FeedEvent {
+            id,
+            data: "hi!".to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn slow_client_is_dropped_when_buffer_fills() {
+        let manager = Arc::new(ClientManager::new(2));
+        let mut stream = manager.connect("slow".to_owned());
+
+        // 아무것도 읽지 않은 상태에서 버퍼(2)보다 많이 보냄
+        manager.broadcast(&event(1));
+        manager.broadcast(&event(2));
+        assert_eq!(manager.stats().clients[0].queued, 2);
+        manager.broadcast(&event(3));
+
+        let stats = manager.stats();
+        assert_eq!(stats.connected, 0);
+        assert_eq!(stats.dropped_slow, 1);
+
+        // 이미 버퍼에 있던 이벤트는 전달된 뒤 스트림이 끝남
+        assert!(stream.next().await.is_some());
+        assert!(stream.next().await.is_some());
+        assert!(stream.next().await.is_none());
+
+        drop(stream);
+        assert_eq!(manager.stats().disconnected, 0);
+    }
+
+    #[tokio::test]
+    async fn dropping_the_stream_disconnects_the_client() {
+        let manager = Arc::new(ClientManager::new(2));
+        let stream = manager.connect("browser".to_owned());
+        manager.broadcast(&event(1));
+        assert_eq!(manager.stats().clients[0].events_sent, 1);
+
+        drop(stream);
+
+        let stats = manager.stats();
+        assert_eq!(stats.connected, 0);
+        assert_eq!(stats.disconnected, 1);
+    }
+
+    #[tokio::test]
+    async fn replayed_events_come_first_without_duplicates() {
+        let manager = Arc::new(ClientManager::new(8));
+        let stream = manager.connect("reconnecting".to_owned());
+
+        // DB를 읽는 동안 발행된 3은 실시간 채널과 replay 양쪽에 들어옴
+        manager.broadcast(&event(3));
+        manager.broadcast(&event(4));
+        let mut stream = stream.replay(vec![event(2), event(3)]);
+
+        let mut ids = Vec::new();
+        for _ in 0..3 {
+            let event = std::future::poll_fn(|cx| stream.poll_feed_event(cx)).await;
+            ids.push(event.unwrap().id);
+        }
+        assert_eq!(ids, [2, 3, 4]);
+    }
+}
@@ -0,0 +1,252 @@
+//! SSE와 long-polling이 함께 사용하는 이벤트 피드
+//!
+//! • 모든 이벤트는 SQLite `events` 테이블에 저장되고, 1부터 단조 증가하는 ID를 가짐
+//!   (`AUTOINCREMENT`라서 오래된 이벤트를 지워도 ID를 다시 쓰지 않음)
+//! • 재연결한 SSE 클라이언트는 `Last-Event-ID` 이후 이벤트를 DB에서 다시 받음 (`replay`)
+//!   서버를 재시작해도 이어서 받을 수 있음
+//! • 최근 이벤트는 고정 크기 ring buffer(`VecDeque`)에도 보관 → long-polling은 DB를 읽지 않음
+//! • `watch` 채널로 마지막 ID를 알려서, 기다리는 long-poll 요청을 깨움
+
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions},
+    SqlitePool,
+};
+use std::{collections::VecDeque, str::FromStr, sync::Mutex, time::Duration};
+use tokio::sync::watch;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FeedEvent {
+    pub id: u64,
+    pub data: String,
+}
+
+pub struct EventLog {
+    pool: SqlitePool,
+    capacity: usize,
+    // DB에 보관할 최대 이벤트 수 (이보다 오래된 이벤트는 재연결해도 받을 수 없음)
+    retention: u64,
+    events: Mutex<VecDeque<FeedEvent>>,
+    // 저장 순서 = ID 순서 = 알림 순서가 되도록 발행은 한 번에 하나씩
+    publish: tokio::sync::Mutex<()>,
+    // 마지막으로 추가된 이벤트 ID (아직 없으면 0)
+    last_id: watch::Sender<u64>,
+}
+
+impl EventLog {
+    /// DB 파일이 없으면 만들고 마이그레이션 실행
+    pub async fn connect(url: &str, capacity: usize, retention: u64) -> Result<Self, sqlx::Error> {
+        let options = SqliteConnectOptions::from_str(url)?
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal);
+        let pool = SqlitePoolOptions::new().connect_with(options).await?;
+        Self::with_pool(pool, capacity, retention).await
+    }
+
+    /// 테스트용 메모리 DB (연결마다 DB가 따로 생기므로 연결 하나만 유지)
+    #[cfg(test)]
+    pub async fn in_memory(capacity: usize) -> Self {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        Self::with_pool(pool, capacity, u64::MAX).await.unwrap()
+    }
+
+    /// 저장된 이벤트 중 최근 `capacity`개로 ring buffer를 채우고 시작
+    pub async fn with_pool(
+        pool: SqlitePool,
+        capacity: usize,
+        retention: u64,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::migrate!().run(&pool).await?;
+
+        let mut recent: Vec<FeedEvent> =
+            sqlx::query_as("SELECT id, data FROM events ORDER BY id DESC LIMIT ?")
+                .bind(capacity as i64)
+                .fetch_all(&pool)
+                .await?;
+        recent.reverse();
+        // 지워진 이벤트가 있어도 ID는 이어서 매겨짐 (AUTOINCREMENT 기록 기준)
+        let (last_id,): (i64,) = sqlx::query_as(
+            "SELECT COALESCE(MAX(seq), 0) FROM sqlite_sequence WHERE name = 'events'",
+        )
+        .fetch_one(&pool)
+        .await?;
+
+        Ok(Self {
+            pool,
+            capacity,
+            retention,
+            events: Mutex::new(recent.into()),
+            publish: tokio::sync::Mutex::new(()),
+            last_id: watch::Sender::new(last_id as u64),
+        })
+    }
+
+    /// 새 이벤트를 DB에 저장하고(ID는 DB가 매김) ring buffer에도 추가
+    ///
+    /// 구독자에게 알리기 전에 저장이 끝나므로, 알림을 받은 이벤트는 항상 `replay`로도 읽을 수 있음
+    pub async fn push(&self, data: impl Into<String>) -> Result<FeedEvent, sqlx::Error> {
+        let _publish = self.publish.lock().await;
+
+        let event: FeedEvent =
+            sqlx::query_as("INSERT INTO events (data) VALUES (?) RETURNING id, data")
+                .bind(data.into())
+                .fetch_one(&self.pool)
+                .await?;
+
+        // 보관 개수를 넘은 오래된 이벤트 정리
+        if let Some(oldest_kept) = (event.id + 1).checked_sub(self.retention) {
+            sqlx::query("DELETE FROM events WHERE id < ?")
+                .bind(oldest_kept as i64)
+                .execute(&self.pool)
+                .await?;
+        }
+
+        let mut events = self.events.lock().unwrap();
+        if events.len() == self.capacity {
+            events.pop_front();
+        }
+        events.push_back(event.clone());
+
+        // 락을 잡은 상태에서 알려야 ID 순서와 저장 순서가 항상 일치함
+        self.last_id.send_replace(event.id);
+        Ok(event)
+    }
+
+    pub fn last_id(&self) -> u64 {
+        *self.last_id.borrow()
+    }
+
+    /// `after` 이후 DB에 남아 있는 이벤트 (오래된 순, 최대 `limit`개)
+    pub async fn replay(&self, after: u64, limit: u32) -> Result<Vec<FeedEvent>, sqlx::Error> {
+        sqlx::query_as("SELECT id, data FROM events WHERE id > ? ORDER BY id LIMIT ?")
+            .bind(after as i64)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// `since` 이후의 이벤트 (ring buffer에서 밀려난 이벤트는 포함되지 않음)
+    pub fn since(&self, since: u64) -> Vec<FeedEvent> {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|event| event.id > since)
+            .cloned()
+            .collect()
+    }
+
+    /// Wait until there is at least one event newer than `since`, or until `timeout` elapses.
+    /// 시간 초과 시 빈 목록을 반환
+    pub async fn wait_since(&self, since: u64, timeout: Duration) -> Vec<FeedEvent> {
+        let mut last_id = self.last_id.subscribe();
+
+        // `Sender`를 self가 들고 있으므로 wait_for는 에러를 반환하지 않음
+        let wait = last_id.wait_for(|last_id| *last_id > since);
+        let arrived = tokio::time::timeout(timeout, wait).await.is_ok();
+
+        if arrived {
+            self.since(since)
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn ids(events: &[FeedEvent]) -> Vec<u64> {
+        events.iter().map(|event| event.id).collect()
+    }
+
+    #[tokio::test]
+    async fn ids_increase_and_old_events_are_evicted() {
+        let log = EventLog::in_memory(2).await;
+        assert_eq!(log.push("a").await.unwrap().id, 1);
+        assert_eq!(log.push("b").await.unwrap().id, 2);
+        assert_eq!(log.push("c").await.unwrap().id, 3);
+
+        assert_eq!(ids(&log.since(0)), [2, 3]);
+        assert_eq!(log.since(2)[0].data, "c");
+        assert!(log.since(3).is_empty());
+
+        // ring buffer에서 밀려난 이벤트도 DB에서는 다시 읽을 수 있음
+        assert_eq!(ids(&log.replay(0, 100).await.unwrap()), [1, 2, 3]);
+        assert_eq!(ids(&log.replay(1, 1).await.unwrap()), [2]);
+        assert!(log.replay(3, 100).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reopening_continues_ids_and_reloads_recent_events() {
+        let log = EventLog::in_memory(2).await;
+        for data in ["a", "b", "c"] {
+            log.push(data).await.unwrap();
+        }
+
+        // 같은 DB로 다시 시작 (서버 재시작)
+        let log = EventLog::with_pool(log.pool.clone(), 2, u64::MAX)
+            .await
+            .unwrap();
+        assert_eq!(log.last_id(), 3);
+        assert_eq!(ids(&log.since(0)), [2, 3]);
+        assert_eq!(log.push("d").await.unwrap().id, 4);
+    }
+
+    #[tokio::test]
+    async fn events_beyond_retention_are_deleted_but_ids_are_not_reused() {
+        let log = EventLog::in_memory(8).await;
+        let log = EventLog::with_pool(log.pool.clone(), 8, 2).await.unwrap();
+        for data in ["a", "b", "c"] {
+            log.push(data).await.unwrap();
+        }
+        assert_eq!(ids(&log.replay(0, 100).await.unwrap()), [2, 3]);
+
+        sqlx::query("DELETE FROM events")
+            .execute(&log.pool)
+            .await
+            .unwrap();
+        let log = EventLog::with_pool(log.pool.clone(), 8, 2).await.unwrap();
+        assert_eq!(log.last_id(), 3);
+        assert_eq!(log.push("d").await.unwrap().id, 4);
+    }
+
+    #[tokio::test]
+    async fn wait_since_returns_when_a_new_event_arrives() {
+        let log = Arc::new(EventLog::in_memory(8).await);
+        log.push("old").await.unwrap();
+
+        let waiter = tokio::spawn({
+            let log = log.clone();
+            async move { log.wait_since(1, Duration::from_secs(30)).await }
+        });
+        log.push("new").await.unwrap();
+
+        let events = waiter.await.unwrap();
+        assert_eq!(
+            events,
+            [FeedEvent {
+                id: 2,
+                data: "new".to_owned()
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn wait_since_times_out_without_new_events() {
+        let log = EventLog::in_memory(8).await;
+        log.push("old").await.unwrap();
+
+        // DB 작업이 끝난 뒤에 시간을 멈춤 (풀의 타임아웃에 영향을 주지 않도록)
+        tokio::time::pause();
+        assert!(log.wait_since(1, Duration::from_secs(30)).await.is_empty());
+    }
+}
@@ -6,25 +6,38 @@
 //! ```
 //! 그다음 브라우저에서 http://localhost:3000 그리고 /sse 접속
 //! 콘솔 로그에서 hi!, 그리고 브라우저 화면에서 keep-alive-text 메시지 수신 확인
+//! 연결된 클라이언트 현황은 http://localhost:3000/clients 에서 확인
+//! SSE를 지원하지 않는 클라이언트는 같은 이벤트를 long-polling으로 받을 수 있음
+//! (`curl 'http://localhost:3000/poll?since=0'`)
+//!
+//! 이벤트는 SQLite(`DATABASE_URL`, 기본값 `sqlite://sse.db`)에 저장됨
+//! → 연결이 끊겼다가 `Last-Event-ID`와 함께 다시 접속하면 놓친 이벤트부터 받음 (서버 재시작 후에도)
 //!
 //! Test with
 //! ```not_rust
 //! cargo test -p example-sse
 //! ```
 
+mod client_manager;
+mod event_log;
+
 use axum::{
-    response::sse::{Event, Sse}, // Sse → Server Sent Events 형식의 응답
-    routing::get,                // Event → 클라이언트로 보낼 단일 SSE 메시지 단위
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::sse::Sse, // Sse → Server Sent Events 형식의 응답
+    routing::get,
+    Json,
     Router,
 };
 use axum_extra::TypedHeader; // TypedHeader → User-Agent 같은 HTTP 헤더 파싱
-use futures::stream::{self, Stream};
-use std::{convert::Infallible, path::PathBuf, time::Duration};
-use tokio_stream::StreamExt as _;
+use client_manager::{ClientManager, ClientStats, ClientStream};
+use event_log::{EventLog, FeedEvent};
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, sync::Arc, time::Duration};
 use tower_http::{services::ServeDir, trace::TraceLayer}; // ServeDir → / 경로에 정적 HTML/JS 파일 제공
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-/// ✅ main 함수
+// ✅ main 함수
 
 #[tokio::main]
 async fn main() {
@@ -37,8 +50,19 @@ async fn main() {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
+    let database_url =
+        std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite://sse.db".to_owned());
+    tracing::debug!("opening event log at {database_url}");
+    let events = EventLog::connect(&database_url, EVENT_LOG_CAPACITY, EVENT_RETENTION)
+        .await
+        .unwrap();
+
+    // 공유 상태 생성 후 1초마다 이벤트를 만들어 모든 클라이언트에게 fan-out
+    let state = AppState::new(events);
+    tokio::spawn(produce_events(state.clone()));
+
     // 애플리케이션 정의 및 실행
-    let app = app();
+    let app = app(state);
 
     // run it
     let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
@@ -50,8 +74,58 @@ async fn main() {
     axum::serve(listener, app).await.unwrap();
 }
 
+/// 클라이언트별 채널에 쌓아둘 수 있는 이벤트 수 (가득 차면 느린 클라이언트로 보고 끊음)
+const CLIENT_BUFFER: usize = 16;
+
+/// long-polling 클라이언트를 위해 보관하는 최근 이벤트 수
+const EVENT_LOG_CAPACITY: usize = 100;
+
+/// DB에 보관하는 이벤트 수 (이보다 오래 끊겨 있던 클라이언트는 남아 있는 이벤트부터 받음)
+const EVENT_RETENTION: u64 = 10_000;
+
+/// 재연결 시 한 번에 다시 보내는 최대 이벤트 수
+const MAX_REPLAY: u32 = 1_000;
+
+/// 새 이벤트가 없을 때 /poll 요청을 붙잡아 두는 최대 시간
+const LONG_POLL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// ✅ 공유 상태 – SSE 클라이언트 관리자와 이벤트 로그 (재연결, long-polling)
+#[derive(Clone)]
+struct AppState {
+    clients: Arc<ClientManager>,
+    events: Arc<EventLog>,
+}
+
+impl AppState {
+    fn new(events: EventLog) -> Self {
+        Self {
+            clients: Arc::new(ClientManager::new(CLIENT_BUFFER)),
+            events: Arc::new(events),
+        }
+    }
+
+    /// 이벤트에 ID를 붙여 로그에 저장하고(재연결, long-polling), SSE 클라이언트에게 fan-out
+    async fn publish(&self, data: &str) -> Result<(), sqlx::Error> {
+        let event = self.events.push(data).await?;
+        self.clients.broadcast(&event);
+        Ok(())
+    }
+}
+
+/// ✅ produce_events – 서버 쪽 이벤트 생산자
+/// 1초마다 "hi!" 이벤트를 만들어 연결된 모든 클라이언트에게 fan-out
+async fn produce_events(state: AppState) {
+    let mut interval = tokio::time::interval(Duration::from_secs(1));
+    loop {
+        interval.tick().await;
+        if let Err(err) = state.publish("hi!").await {
+            tracing::error!("failed to publish event: {err}");
+        }
+    }
+}
+
 /// ✅ app() – 라우터 및 정적 파일 설정
-fn app() -> Router {
+fn app(state: AppState) -> Router {
     // 정적 파일은 assets/ 디렉토리에서 읽어옴
     let assets_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("assets");
     let static_files_service = ServeDir::new(assets_dir).append_index_html_on_directories(true);
@@ -60,59 +134,127 @@ fn app() -> Router {
     Router::new()
         .fallback_service(static_files_service) // / → index.html 서빙
         .route("/sse", get(sse_handler)) // /sse → SSE 응답 핸들러로 연결
+        .route("/clients", get(clients_handler)) // /clients → 연결 통계
+        .route("/poll", get(poll_handler)) // /poll → SSE 미지원 클라이언트용 long-polling
         .layer(TraceLayer::new_for_http()) // 요청 트레이싱 미들웨어
+        .with_state(state)
 }
 
 /// ✅ sse_handler – SSE 이벤트 핸들러
 /// 반환 타입은 Sse<Stream<...>> → SSE 방식으로 스트리밍 응답 전송
+/// `Last-Event-ID` 헤더가 있으면 그 이후 이벤트를 먼저 보낸 뒤 실시간 이벤트로 이어감
 async fn sse_handler(
+    State(state): State<AppState>,
     TypedHeader(user_agent): TypedHeader<headers::UserAgent>,
-) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    headers: HeaderMap,
+) -> Result<Sse<ClientStream>, StatusCode> {
     // 클라이언트의 User-Agent를 로그로 출력
     println!("`{}` connected", user_agent.as_str());
 
-    // `Stream` 은 1초마다 이벤트를 반복함.
-    // futures::stream::repeat_with()를 통해 Event("hi!")를 1초마다 전송
-    let stream = stream::repeat_with(|| Event::default().data("hi!"))
-        .map(Ok) // Result<Event, Infallible> 형식으로 변환
-        .throttle(Duration::from_secs(1)); // throttle()은 전송 간격 조절
+    // 이 클라이언트 전용 채널을 할당받음
+    // 이벤트는 produce_events()가 채널로 밀어넣고, 연결이 끊기면 스트림이 drop 되면서 정리됨
+    let mut stream = state.clients.connect(user_agent.as_str().to_owned());
+
+    // 브라우저의 EventSource는 재연결할 때 마지막으로 받은 `id`를 이 헤더로 보냄
+    // 채널을 먼저 연 뒤에 DB를 읽어야 그 사이에 발행된 이벤트를 놓치지 않음
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok());
+    if let Some(last_event_id) = last_event_id {
+        let missed = state
+            .events
+            .replay(last_event_id, MAX_REPLAY)
+            .await
+            .map_err(|err| {
+                tracing::error!("failed to read event log: {err}");
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        tracing::debug!(last_event_id, missed = missed.len(), "resuming client");
+        stream = stream.replay(missed);
+    }
 
     // SSE 연결 유지(Connection: keep-alive)를 위해 1초 간격의 "keep-alive-text"를 보냄
-    Sse::new(stream).keep_alive(
+    Ok(Sse::new(stream).keep_alive(
         axum::response::sse::KeepAlive::new()
             .interval(Duration::from_secs(1))
             .text("keep-alive-text"),
-    )
+    ))
+}
+
+/// ✅ clients_handler – 연결된 클라이언트 통계
+async fn clients_handler(State(state): State<AppState>) -> Json<ClientStats> {
+    Json(state.clients.stats())
+}
+
+#[derive(Debug, Deserialize)]
+struct PollParams {
+    // 마지막으로 받은 이벤트 ID (없으면 지금 이후의 이벤트만 기다림)
+    since: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PollResponse {
+    events: Vec<FeedEvent>,
+    // 다음 요청에 `since`로 넘길 ID
+    next: u64,
+}
+
+/// ✅ poll_handler – long-polling
+/// `since` 이후 이벤트가 이미 있으면 바로 응답하고, 없으면 최대 30초 동안 새 이벤트를 기다림
+/// 시간 초과 시 빈 목록으로 응답하므로 클라이언트는 같은 `since`로 다시 요청하면 됨
+async fn poll_handler(
+    State(state): State<AppState>,
+    Query(params): Query<PollParams>,
+) -> Json<PollResponse> {
+    let since = params.since.unwrap_or_else(|| state.events.last_id());
+    let events = state.events.wait_since(since, LONG_POLL_TIMEOUT).await;
+    let next = events.last().map_or(since, |event| event.id);
+
+    Json(PollResponse { events, next })
 }
 
 #[cfg(test)]
 mod tests {
     use eventsource_stream::Eventsource;
+    use std::future::IntoFuture;
     use tokio::net::TcpListener;
+    use tokio_stream::StreamExt as _;
 
     use super::*;
 
+    // A helper function that spawns our application in the background
+    async fn spawn_app(host: impl Into<String>) -> String {
+        let host = host.into();
+        // Bind to localhost at the port 0, which will let the OS assign an available port to us
+        let listener = TcpListener::bind(format!("{}:0", host)).await.unwrap();
+        // Retrieve the port assigned to us by the OS
+        let port = listener.local_addr().unwrap().port();
+        let state = AppState::new(EventLog::in_memory(EVENT_LOG_CAPACITY).await);
+        tokio::spawn(produce_events(state.clone()));
+        tokio::spawn(async {
+            axum::serve(listener, app(state)).await.unwrap();
+        });
+
+        // Returns address (e.g. http://127.0.0.1{random_port})
+        format!("http://{}:{}", host, port)
+    }
+
+    // 이벤트 생산자 없이 서버만 띄움 (테스트가 직접 `publish`)
+    async fn spawn_quiet_app() -> (String, AppState) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let state = AppState::new(EventLog::in_memory(EVENT_LOG_CAPACITY).await);
+        tokio::spawn(axum::serve(listener, app(state.clone())).into_future());
+        (format!("http://{addr}"), state)
+    }
+
     /// ✅ integration_test – SSE 테스트 (옵션)
     ///    임시 서버를 띄워 /sse 엔드포인트로 요청을 보내고 "hi!" 메시지를 수신하는지 검증
     ///    eventsource_stream을 이용하여 SSE 응답 스트림을 처리
     ///    첫 메시지가 "hi!"인지 확인
     #[tokio::test]
     async fn integration_test() {
-        // A helper function that spawns our application in the background
-        async fn spawn_app(host: impl Into<String>) -> String {
-            let host = host.into();
-            // Bind to localhost at the port 0, which will let the OS assign an available port to us
-            let listener = TcpListener::bind(format!("{}:0", host)).await.unwrap();
-            // Retrieve the port assigned to us by the OS
-            let port = listener.local_addr().unwrap().port();
-            tokio::spawn(async {
-                axum::serve(listener, app()).await.unwrap();
-            });
-
-            // Returns address (e.g. http://127.0.0.1{random_port})
-            format!("http://{}:{}", host, port)
-        }
-
         let listening_url = spawn_app("127.0.0.1").await;
 
         let mut event_stream = reqwest::Client::new()
@@ -144,4 +286,133 @@ mod tests {
 
         assert!(event_data[0] == "hi!");
     }
+
+    /// ✅ clients_test – /clients 통계 확인
+    ///    SSE 연결 중에는 클라이언트 목록에 나타나고, 연결을 끊으면 disconnected로 집계되는지 검증
+    #[tokio::test]
+    async fn clients_test() {
+        let listening_url = spawn_app("127.0.0.1").await;
+        let client = reqwest::Client::new();
+
+        let mut event_stream = client
+            .get(format!("{}/sse", listening_url))
+            .header("User-Agent", "clients_test")
+            .send()
+            .await
+            .unwrap()
+            .bytes_stream()
+            .eventsource();
+        // 첫 이벤트를 받을 때까지 기다려 연결이 등록되었음을 보장
+        event_stream.next().await.unwrap().unwrap();
+
+        let stats: serde_json::Value = client
+            .get(format!("{}/clients", listening_url))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(stats["connected"], 1);
+        assert_eq!(stats["clients"][0]["user_agent"], "clients_test");
+
+        // 연결을 끊으면 서버가 다음 이벤트를 보내기 전에 스트림이 drop 되어 정리됨
+        drop(event_stream);
+        let mut stats = serde_json::Value::Null;
+        for _ in 0..50 {
+            stats = client
+                .get(format!("{}/clients", listening_url))
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            if stats["connected"] == 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert_eq!(stats["connected"], 0);
+        assert_eq!(stats["disconnected"], 1);
+    }
+
+    /// ✅ poll_test – long-polling
+    ///    SSE와 같은 이벤트 피드를 /poll로 받고, `next`로 이어서 요청하면 ID가 계속 증가하는지 검증
+    #[tokio::test]
+    async fn poll_test() {
+        let listening_url = spawn_app("127.0.0.1").await;
+        let client = reqwest::Client::new();
+
+        let first: PollResponse = client
+            .get(format!("{}/poll?since=0", listening_url))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert!(!first.events.is_empty());
+        assert_eq!(first.events[0].data, "hi!");
+        assert_eq!(first.next, first.events.last().unwrap().id);
+
+        // 다음 이벤트가 생길 때까지 요청이 붙잡혀 있다가 응답됨
+        let second: PollResponse = client
+            .get(format!("{}/poll?since={}", listening_url, first.next))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert!(second.events.iter().all(|event| event.id > first.next));
+        assert!(second.next > first.next);
+    }
+
+    /// ✅ resume_test – Last-Event-ID로 재연결
+    ///    끊겨 있는 동안 발행된 이벤트를 먼저 받고, 이어서 실시간 이벤트를 받는지 검증
+    #[tokio::test]
+    async fn resume_test() {
+        let (listening_url, state) = spawn_quiet_app().await;
+        let client = reqwest::Client::new();
+        let connect = |last_event_id: Option<&str>| {
+            let mut request = client
+                .get(format!("{listening_url}/sse"))
+                .header("User-Agent", "resume_test");
+            if let Some(last_event_id) = last_event_id {
+                request = request.header("Last-Event-ID", last_event_id);
+            }
+            async move { request.send().await.unwrap().bytes_stream().eventsource() }
+        };
+
+        state.publish("before").await.unwrap();
+
+        // 처음 접속하면 실시간 이벤트만 받음
+        let mut events = connect(None).await;
+        state.publish("first").await.unwrap();
+        let event = events.next().await.unwrap().unwrap();
+        assert_eq!((event.id.as_str(), event.data.as_str()), ("2", "first"));
+        drop(events);
+
+        // 끊겨 있는 동안 발행된 이벤트
+        state.publish("missed 1").await.unwrap();
+        state.publish("missed 2").await.unwrap();
+
+        let mut events = connect(Some("2")).await;
+        state.publish("live").await.unwrap();
+
+        let mut received = Vec::new();
+        for _ in 0..3 {
+            let event = events.next().await.unwrap().unwrap();
+            received.push((event.id, event.data));
+        }
+        assert_eq!(
+            received,
+            [
+                ("3".to_owned(), "missed 1".to_owned()),
+                ("4".to_owned(), "missed 2".to_owned()),
+                ("5".to_owned(), "live".to_owned()),
+            ]
+        );
+    }
 }
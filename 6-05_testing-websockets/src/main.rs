@@ -1,4 +1,5 @@
 //! WebSocket 서버 기능에 대해 단위 테스트(Unit Test) 와 통합 테스트(Integration Test) 를 적용해보는 예제.
+//! 하트비트(Ping/Pong)와 idle timeout 처리를 `tokio::time::pause()`로 테스트하는 방법도 포함.
 //!
 //! ```not_rust
 //! cargo test -p example-testing-websockets
@@ -14,8 +15,10 @@ use axum::{
     Router,
 };
 use futures::{Sink, SinkExt, Stream, StreamExt};
+use std::time::Duration;
+use tokio::time::{Instant, MissedTickBehavior};
 
-/// 🔷 main() 함수 — 서버 실행
+// 🔷 main() 함수 — 서버 실행
 
 #[tokio::main]
 async fn main() {
@@ -26,7 +29,7 @@ async fn main() {
     axum::serve(listener, app()).await.unwrap();
 }
 
-/// 🔷 app() — 라우터 구성
+// 🔷 app() — 라우터 구성
 
 fn app() -> Router {
     // WebSocket routes can generally be tested in two ways:
@@ -39,6 +42,7 @@ fn app() -> Router {
     Router::new()
         .route("/integration-testable", get(integration_testable_handler))
         .route("/unit-testable", get(unit_testable_handler))
+        .route("/heartbeat", get(heartbeat_handler))
 }
 
 // A WebSocket handler that echos any message it receives.
@@ -97,7 +101,83 @@ where
     }
 }
 
-/// --- 🧪 테스트 모듈
+// 🔷 하트비트(heartbeat) — Ping/Pong으로 죽은 연결 감지
+
+// Settings for the heartbeat: a `Ping` is sent every `interval` and the client is dropped once it
+// has missed `max_missed_pongs` of them in a row.
+// 응답 없는 클라이언트는 대략 interval * (max_missed_pongs + 1) 후에 연결이 끊김
+#[derive(Debug, Clone, Copy)]
+struct Heartbeat {
+    interval: Duration,
+    max_missed_pongs: u32,
+}
+
+impl Default for Heartbeat {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(30),
+            max_missed_pongs: 2,
+        }
+    }
+}
+
+// Same split as the unit testable handler so the heartbeat can be tested with mocked streams.
+async fn heartbeat_handler(ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(|socket| {
+        let (write, read) = socket.split();
+        heartbeat_handle_socket(write, read, Heartbeat::default())
+    })
+}
+
+// Echoes text messages like the other handlers, but also pings the client periodically and closes
+// the connection once too many pongs were missed.
+// select!로 "메시지 수신"과 "하트비트 타이머"를 동시에 기다림
+async fn heartbeat_handle_socket<W, R>(mut write: W, mut read: R, heartbeat: Heartbeat)
+where
+    W: Sink<Message> + Unpin,
+    R: Stream<Item = Result<Message, axum::Error>> + Unpin,
+{
+    // `interval`의 첫 tick은 즉시 발생하므로 한 주기 뒤부터 시작
+    let mut ticker =
+        tokio::time::interval_at(Instant::now() + heartbeat.interval, heartbeat.interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut missed_pongs = 0;
+
+    loop {
+        tokio::select! {
+            msg = read.next() => match msg {
+                Some(Ok(Message::Text(msg))) => {
+                    if write
+                        .send(Message::Text(format!("You said: {msg}").into()))
+                        .await
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+                // 클라이언트가 살아 있음 → 카운터 초기화
+                Some(Ok(Message::Pong(_))) => missed_pongs = 0,
+                Some(Ok(_)) => {}
+                // 스트림 종료 또는 에러 → 연결 종료
+                Some(Err(_)) | None => break,
+            },
+            _ = ticker.tick() => {
+                if missed_pongs >= heartbeat.max_missed_pongs {
+                    // 너무 많은 Pong을 놓침 → Close 프레임을 보내고 연결 종료
+                    let _ = write.send(Message::Close(None)).await;
+                    break;
+                }
+
+                missed_pongs += 1;
+                if write.send(Message::Ping(Default::default())).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+// --- 🧪 테스트 모듈
 
 #[cfg(test)]
 mod tests {
@@ -162,4 +242,76 @@ mod tests {
 
         assert_eq!(msg.as_str(), "You said: foo");
     }
+
+    // --- 🔷 하트비트 테스트 (heartbeat)
+
+    // `tokio::time::pause()` freezes the clock; whenever the runtime has nothing else to do it
+    // jumps straight to the next timer, so these tests don't actually wait for the intervals.
+    // 시간을 멈춘 상태에서 테스트하므로 30초 간격이어도 즉시 끝남
+    #[tokio::test]
+    async fn heartbeat_drops_client_that_misses_pongs() {
+        tokio::time::pause();
+
+        let (socket_write, mut test_rx) = futures::channel::mpsc::channel(1024);
+        // The sender is kept alive (but never used) so the socket only closes because of the
+        // heartbeat.
+        let (_test_tx, socket_read) = futures::channel::mpsc::channel(1024);
+
+        let heartbeat = Heartbeat {
+            interval: Duration::from_secs(30),
+            max_missed_pongs: 2,
+        };
+        let start = Instant::now();
+        let handle = tokio::spawn(heartbeat_handle_socket(
+            socket_write,
+            socket_read,
+            heartbeat,
+        ));
+
+        // Pong에 응답하지 않으면 Ping 2번 후 Close
+        for _ in 0..heartbeat.max_missed_pongs {
+            assert!(matches!(test_rx.next().await, Some(Message::Ping(_))));
+        }
+        assert!(matches!(test_rx.next().await, Some(Message::Close(None))));
+
+        handle.await.unwrap();
+        // 타이머 해상도(1ms) 때문에 정확히 90초가 아닐 수 있음
+        let elapsed = start.elapsed();
+        assert!(elapsed >= heartbeat.interval * 3);
+        assert!(elapsed < heartbeat.interval * 3 + Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn heartbeat_keeps_client_that_answers_pings() {
+        tokio::time::pause();
+
+        let (socket_write, mut test_rx) = futures::channel::mpsc::channel(1024);
+        let (mut test_tx, socket_read) = futures::channel::mpsc::channel(1024);
+
+        let handle = tokio::spawn(heartbeat_handle_socket(
+            socket_write,
+            socket_read,
+            Heartbeat::default(),
+        ));
+
+        // max_missed_pongs보다 훨씬 많은 Ping에 응답해도 연결이 유지되어야 함
+        for _ in 0..10 {
+            match test_rx.next().await.unwrap() {
+                Message::Ping(payload) => test_tx.send(Ok(Message::Pong(payload))).await.unwrap(),
+                other => panic!("expected a ping but got {other:?}"),
+            }
+        }
+
+        // Echo still works while the heartbeat is running.
+        test_tx.send(Ok(Message::Text("foo".into()))).await.unwrap();
+        let msg = match test_rx.next().await.unwrap() {
+            Message::Text(msg) => msg,
+            other => panic!("expected a text message but got {other:?}"),
+        };
+        assert_eq!(msg.as_str(), "You said: foo");
+
+        // 클라이언트가 읽기 스트림을 닫으면 핸들러도 종료
+        drop(test_tx);
+        handle.await.unwrap();
+    }
 }
@@ -0,0 +1,336 @@
+//! 📬 사용자별 알림함 (`NotificationHub`)
+//!
+//! • 사용자마다 최근 알림 목록 + `broadcast` 채널 하나
+//!   같은 사용자가 여러 기기(WebSocket 탭, SSE 탭, polling 앱)로 붙어도 모두 같은 이벤트를 받음
+//! • 읽음 처리도 이벤트(`HubEvent::Read`)로 전파 → 한 기기에서 읽으면 다른 기기의 배지도 갱신
+//! • `subscribe`는 안 읽은 알림과 실시간 채널을 같은 락 안에서 가져오므로 빠지거나 겹치는 알림이 없음
+
+use futures::{stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Notification {
+    pub id: u64,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// Unix time (밀리초)
+    pub created_at: u64,
+    pub read: bool,
+}
+
+/// `POST /notify/{user}` 본문
+#[derive(Debug, Deserialize)]
+pub struct NewNotification {
+    pub title: String,
+    pub body: Option<String>,
+}
+
+/// 실시간 채널(WebSocket, SSE)로 나가는 이벤트
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HubEvent {
+    Notification(Notification),
+    /// 읽음 처리된 알림과 남은 안 읽은 알림 수
+    Read {
+        ids: Vec<u64>,
+        unread_count: usize,
+    },
+}
+
+pub struct NotificationHub {
+    // 사용자마다 보관하는 최대 알림 수 (넘으면 가장 오래된 것부터 버림)
+    capacity: usize,
+    next_id: AtomicU64,
+    inboxes: Mutex<HashMap<String, Inbox>>,
+}
+
+struct Inbox {
+    notifications: BTreeMap<u64, Notification>,
+    tx: broadcast::Sender<HubEvent>,
+}
+
+// 실시간 구독자 한 명이 밀릴 수 있는 최대 이벤트 수
+const CHANNEL_CAPACITY: usize = 64;
+
+impl Inbox {
+    fn new() -> Self {
+        Self {
+            notifications: BTreeMap::new(),
+            tx: broadcast::channel(CHANNEL_CAPACITY).0,
+        }
+    }
+
+    fn unread(&self) -> impl Iterator<Item = &Notification> {
+        self.notifications.values().filter(|n| !n.read)
+    }
+}
+
+impl NotificationHub {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            next_id: AtomicU64::new(1),
+            inboxes: Mutex::default(),
+        }
+    }
+
+    /// 알림을 알림함에 넣고 실시간 구독자에게 전달
+    pub fn notify(&self, user: &str, new: NewNotification) -> Notification {
+        let notification = Notification {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            title: new.title,
+            body: new.body,
+            created_at: unix_millis(),
+            read: false,
+        };
+
+        let mut inboxes = self.inboxes.lock().unwrap();
+        let inbox = inboxes.entry(user.to_owned()).or_insert_with(Inbox::new);
+        inbox
+            .notifications
+            .insert(notification.id, notification.clone());
+        if inbox.notifications.len() > self.capacity {
+            inbox.notifications.pop_first();
+        }
+        // 구독자가 없으면 실패하지만 알림함에는 남아 있음
+        let _ = inbox.tx.send(HubEvent::Notification(notification.clone()));
+
+        notification
+    }
+
+    /// 보관 중인 알림 (오래된 순)
+    pub fn list(&self, user: &str, unread_only: bool) -> Vec<Notification> {
+        let inboxes = self.inboxes.lock().unwrap();
+        let Some(inbox) = inboxes.get(user) else {
+            return Vec::new();
+        };
+        inbox
+            .notifications
+            .values()
+            .filter(|n| !unread_only || !n.read)
+            .cloned()
+            .collect()
+    }
+
+    /// `since`보다 새 알림 (오래된 순)
+    pub fn since(&self, user: &str, since: u64) -> Vec<Notification> {
+        let inboxes = self.inboxes.lock().unwrap();
+        let Some(inbox) = inboxes.get(user) else {
+            return Vec::new();
+        };
+        inbox
+            .notifications
+            .range(since + 1..)
+            .map(|(_, n)| n.clone())
+            .collect()
+    }
+
+    pub fn unread_count(&self, user: &str) -> usize {
+        let inboxes = self.inboxes.lock().unwrap();
+        inboxes.get(user).map_or(0, |inbox| inbox.unread().count())
+    }
+
+    /// 읽음 처리 (`ids`가 `None`이면 전부)
+    ///
+    /// 새로 읽음 처리된 알림 ID를 반환 (보관 중이 아닌 ID는 무시)
+    pub fn mark_read(&self, user: &str, ids: Option<&[u64]>) -> Vec<u64> {
+        let mut inboxes = self.inboxes.lock().unwrap();
+        let Some(inbox) = inboxes.get_mut(user) else {
+            return Vec::new();
+        };
+
+        let mut marked = Vec::new();
+        for notification in inbox.notifications.values_mut() {
+            let selected = ids.is_none_or(|ids| ids.contains(&notification.id));
+            if selected && !notification.read {
+                notification.read = true;
+                marked.push(notification.id);
+            }
+        }
+
+        if !marked.is_empty() {
+            let unread_count = inbox.unread().count();
+            let _ = inbox.tx.send(HubEvent::Read {
+                ids: marked.clone(),
+                unread_count,
+            });
+        }
+        marked
+    }
+
+    /// 보관 중인 알림인지 (확인 응답에서 404를 구분할 때)
+    pub fn contains(&self, user: &str, id: u64) -> bool {
+        let inboxes = self.inboxes.lock().unwrap();
+        inboxes
+            .get(user)
+            .is_some_and(|inbox| inbox.notifications.contains_key(&id))
+    }
+
+    /// 안 읽은 알림을 먼저 보내고, 이어서 실시간 이벤트를 보내는 스트림
+    ///
+    /// 구독자가 너무 밀리면(`Lagged`) 스트림이 끝남 → 클라이언트가 다시 연결하면 안 읽은 알림부터 다시 받음
+    pub fn subscribe(&self, user: &str) -> impl Stream<Item = HubEvent> + Send + 'static {
+        let (unread, rx) = {
+            let mut inboxes = self.inboxes.lock().unwrap();
+            let inbox = inboxes.entry(user.to_owned()).or_insert_with(Inbox::new);
+            let unread: Vec<_> = inbox
+                .unread()
+                .cloned()
+                .map(HubEvent::Notification)
+                .collect();
+            (unread, inbox.tx.subscribe())
+        };
+
+        let live = tokio_stream::StreamExt::map_while(BroadcastStream::new(rx), Result::ok);
+        stream::iter(unread).chain(live)
+    }
+
+    /// `since`보다 새 알림이 생길 때까지 최대 `timeout` 동안 기다림 (long-polling)
+    pub async fn wait_since(&self, user: &str, since: u64, timeout: Duration) -> Vec<Notification> {
+        let mut rx = {
+            let mut inboxes = self.inboxes.lock().unwrap();
+            let inbox = inboxes.entry(user.to_owned()).or_insert_with(Inbox::new);
+            if inbox.notifications.range(since + 1..).next().is_some() {
+                drop(inboxes);
+                return self.since(user, since);
+            }
+            inbox.tx.subscribe()
+        };
+
+        let wait = async {
+            loop {
+                match rx.recv().await {
+                    Ok(HubEvent::Notification(_)) | Err(broadcast::error::RecvError::Lagged(_)) => {
+                        break
+                    }
+                    Ok(HubEvent::Read { .. }) => {}
+                    Err(broadcast::error::RecvError::Closed) => return,
+                }
+            }
+        };
+        let _ = tokio::time::timeout(timeout, wait).await;
+        self.since(user, since)
+    }
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new(title: &str) -> NewNotification {
+        NewNotification {
+            title: title.to_owned(),
+            body: None,
+        }
+    }
+
+    #[test]
+    fn inboxes_are_per_user_and_capped() {
+        let hub = NotificationHub::new(2);
+        let first = hub.notify("alice", new("a"));
+        hub.notify("alice", new("b"));
+        hub.notify("alice", new("c"));
+        hub.notify("bob", new("d"));
+
+        let titles: Vec<_> = hub
+            .list("alice", false)
+            .into_iter()
+            .map(|n| n.title)
+            .collect();
+        assert_eq!(titles, ["b", "c"]);
+        assert!(!hub.contains("alice", first.id));
+        assert_eq!(hub.unread_count("bob"), 1);
+        assert!(hub.list("carol", false).is_empty());
+    }
+
+    #[test]
+    fn marking_read_only_reports_newly_read_notifications() {
+        let hub = NotificationHub::new(10);
+        let a = hub.notify("alice", new("a"));
+        let b = hub.notify("alice", new("b"));
+
+        assert_eq!(hub.mark_read("alice", Some(&[a.id, 999])), [a.id]);
+        assert_eq!(hub.mark_read("alice", Some(&[a.id])), Vec::<u64>::new());
+        assert_eq!(hub.unread_count("alice"), 1);
+        assert_eq!(hub.list("alice", true)[0].id, b.id);
+
+        assert_eq!(hub.mark_read("alice", None), [b.id]);
+        assert_eq!(hub.unread_count("alice"), 0);
+        assert_eq!(hub.mark_read("nobody", None), Vec::<u64>::new());
+    }
+
+    #[tokio::test]
+    async fn subscribers_get_unread_backlog_then_live_events() {
+        let hub = NotificationHub::new(10);
+        let read = hub.notify("alice", new("already read"));
+        hub.mark_read("alice", Some(&[read.id]));
+        let unread = hub.notify("alice", new("unread"));
+
+        let mut events = Box::pin(hub.subscribe("alice"));
+        let live = hub.notify("alice", new("live"));
+        hub.mark_read("alice", None);
+
+        assert_eq!(
+            events.next().await,
+            Some(HubEvent::Notification(unread.clone()))
+        );
+        assert_eq!(
+            events.next().await,
+            Some(HubEvent::Notification(live.clone()))
+        );
+        assert_eq!(
+            events.next().await,
+            Some(HubEvent::Read {
+                ids: vec![unread.id, live.id],
+                unread_count: 0
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn wait_since_returns_new_notifications_or_times_out() {
+        let hub = std::sync::Arc::new(NotificationHub::new(10));
+        let old = hub.notify("alice", new("old"));
+
+        // 이미 있으면 바로 반환
+        assert_eq!(
+            hub.wait_since("alice", 0, Duration::from_secs(30)).await,
+            std::slice::from_ref(&old)
+        );
+
+        let waiter = tokio::spawn({
+            let hub = hub.clone();
+            async move {
+                hub.wait_since("alice", old.id, Duration::from_secs(30))
+                    .await
+            }
+        });
+        tokio::task::yield_now().await;
+        let fresh = hub.notify("alice", new("fresh"));
+        assert_eq!(waiter.await.unwrap(), std::slice::from_ref(&fresh));
+
+        tokio::time::pause();
+        assert!(hub
+            .wait_since("alice", fresh.id, Duration::from_secs(30))
+            .await
+            .is_empty());
+    }
+}
@@ -0,0 +1,533 @@
+//! 🔔 알림 센터: 하나의 사용자별 알림 스트림을 WebSocket, SSE, long-polling 중 편한 방식으로 받기
+//!
+//! ```not_rust
+//! cargo run -p example-notifications
+//! ```
+//!
+//! • `POST /notify/{user}`: 알림 보내기 (`{"title": "...", "body": "..."}`)
+//! • `GET /ws?user=`: WebSocket (서버 → 이벤트 JSON, 클라이언트 → `{"type":"ack","ids":[..]}`)
+//! • `GET /sse?user=`: Server-Sent Events (`event: notification` / `event: read`)
+//! • `GET /poll?user=&since=`: long-polling (`since`보다 새 알림이 생길 때까지 최대 30초 대기)
+//! • `GET /notifications?user=&unread_only=`: 알림 목록과 안 읽은 알림 수
+//! • `POST /notifications/{id}/ack?user=`, `POST /notifications/ack-all?user=`: 읽음 처리
+//!
+//! 세 방식 모두 같은 `NotificationHub`를 구독하므로 어느 쪽으로 받든 내용이 같고,
+//! 어디서 읽음 처리를 하든 연결된 모든 기기에 `read` 이벤트가 전달됨
+//!
+//! 예제라서 사용자는 `user` 쿼리로 받음 (실제 서비스에서는 인증 정보에서 꺼내야 함, 4-01_jwt 참고)
+
+mod hub;
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Path, Query, State,
+    },
+    http::StatusCode,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse,
+    },
+    routing::{get, post},
+    Json, Router,
+};
+use example_common_errors::ApiError;
+use futures::{SinkExt, Stream, StreamExt};
+use hub::{HubEvent, NewNotification, Notification, NotificationHub};
+use serde::{Deserialize, Serialize};
+use std::{convert::Infallible, sync::Arc, time::Duration};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+// 사용자마다 보관하는 최대 알림 수
+const INBOX_CAPACITY: usize = 100;
+// 새 알림이 없을 때 /poll 요청을 붙잡아 두는 최대 시간
+const LONG_POLL_TIMEOUT: Duration = Duration::from_secs(30);
+
+type AppState = Arc<NotificationHub>;
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let state = Arc::new(NotificationHub::new(INBOX_CAPACITY));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(state)).await.unwrap();
+}
+
+fn app(state: AppState) -> Router {
+    Router::new()
+        .route("/notify/{user}", post(notify))
+        .route("/notifications", get(list))
+        .route("/notifications/{id}/ack", post(ack))
+        .route("/notifications/ack-all", post(ack_all))
+        .route("/ws", get(ws_handler))
+        .route("/sse", get(sse_handler))
+        .route("/poll", get(poll_handler))
+        .with_state(state)
+}
+
+#[derive(Debug, Deserialize)]
+struct UserParams {
+    user: String,
+}
+
+// --- ✉️ 보내기와 읽음 처리
+
+async fn notify(
+    State(hub): State<AppState>,
+    Path(user): Path<String>,
+    Json(new): Json<NewNotification>,
+) -> Result<(StatusCode, Json<Notification>), ApiError> {
+    if new.title.trim().is_empty() {
+        return Err(ApiError::Validation("title must not be empty".to_owned()));
+    }
+    let notification = hub.notify(&user, new);
+    tracing::debug!(%user, id = notification.id, "notification queued");
+    Ok((StatusCode::CREATED, Json(notification)))
+}
+
+#[derive(Debug, Deserialize)]
+struct ListParams {
+    user: String,
+    #[serde(default)]
+    unread_only: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Inbox {
+    notifications: Vec<Notification>,
+    unread_count: usize,
+}
+
+async fn list(State(hub): State<AppState>, Query(params): Query<ListParams>) -> Json<Inbox> {
+    Json(Inbox {
+        notifications: hub.list(&params.user, params.unread_only),
+        unread_count: hub.unread_count(&params.user),
+    })
+}
+
+/// 알림 하나 읽음 처리 (이미 읽은 알림이어도 204)
+async fn ack(
+    State(hub): State<AppState>,
+    Path(id): Path<u64>,
+    Query(UserParams { user }): Query<UserParams>,
+) -> Result<StatusCode, ApiError> {
+    if !hub.contains(&user, id) {
+        return Err(ApiError::NotFound(format!("notification {id} not found")));
+    }
+    hub.mark_read(&user, Some(&[id]));
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Acknowledged {
+    ids: Vec<u64>,
+    unread_count: usize,
+}
+
+async fn ack_all(
+    State(hub): State<AppState>,
+    Query(UserParams { user }): Query<UserParams>,
+) -> Json<Acknowledged> {
+    let ids = hub.mark_read(&user, None);
+    Json(Acknowledged {
+        ids,
+        unread_count: hub.unread_count(&user),
+    })
+}
+
+// --- 📡 받기: WebSocket
+
+/// 클라이언트 → 서버 메시지
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    Ack { ids: Vec<u64> },
+    AckAll,
+}
+
+async fn ws_handler(
+    ws: WebSocketUpgrade,
+    State(hub): State<AppState>,
+    Query(UserParams { user }): Query<UserParams>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| websocket(socket, hub, user))
+}
+
+async fn websocket(socket: WebSocket, hub: AppState, user: String) {
+    let (mut sender, mut receiver) = socket.split();
+
+    // 허브 이벤트 → 클라이언트
+    let mut events = Box::pin(hub.subscribe(&user));
+    let mut send_task = tokio::spawn(async move {
+        while let Some(event) = events.next().await {
+            let text = serde_json::to_string(&event).expect("hub events serialize");
+            if sender.send(Message::text(text)).await.is_err() {
+                return;
+            }
+        }
+        // 너무 밀려서 구독이 끊김 → 연결을 닫으면 클라이언트가 다시 연결해 안 읽은 알림부터 받음
+        let _ = sender.send(Message::Close(None)).await;
+    });
+
+    // 클라이언트 → 읽음 처리
+    let mut recv_task = tokio::spawn(async move {
+        while let Some(Ok(message)) = receiver.next().await {
+            let Message::Text(text) = message else {
+                continue;
+            };
+            match serde_json::from_str::<ClientMessage>(&text) {
+                Ok(ClientMessage::Ack { ids }) => {
+                    hub.mark_read(&user, Some(&ids));
+                }
+                Ok(ClientMessage::AckAll) => {
+                    hub.mark_read(&user, None);
+                }
+                Err(err) => tracing::debug!(%user, "ignoring websocket message: {err}"),
+            }
+        }
+    });
+
+    // 한쪽이 끝나면 다른 쪽도 정리
+    tokio::select! {
+        _ = &mut send_task => recv_task.abort(),
+        _ = &mut recv_task => send_task.abort(),
+    }
+}
+
+// --- 📡 받기: SSE
+
+async fn sse_handler(
+    State(hub): State<AppState>,
+    Query(UserParams { user }): Query<UserParams>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    // 스트림이 끝나면(구독자가 너무 밀림) 응답이 끝나고, EventSource가 자동으로 다시 연결함
+    let events = hub.subscribe(&user).map(|event| {
+        let sse_event = match &event {
+            HubEvent::Notification(notification) => Event::default()
+                .event("notification")
+                .id(notification.id.to_string()),
+            HubEvent::Read { .. } => Event::default().event("read"),
+        };
+        Ok(sse_event.json_data(&event).expect("hub events serialize"))
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+// --- 📡 받기: long-polling
+
+#[derive(Debug, Deserialize)]
+struct PollParams {
+    user: String,
+    // 마지막으로 받은 알림 ID (없으면 지금의 안 읽은 알림을 바로 응답)
+    since: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PollResponse {
+    notifications: Vec<Notification>,
+    unread_count: usize,
+    // 다음 요청에 `since`로 넘길 ID
+    next: u64,
+}
+
+async fn poll_handler(
+    State(hub): State<AppState>,
+    Query(params): Query<PollParams>,
+) -> Json<PollResponse> {
+    let (notifications, next) = match params.since {
+        Some(since) => {
+            let notifications = hub.wait_since(&params.user, since, LONG_POLL_TIMEOUT).await;
+            let next = notifications.last().map_or(since, |n| n.id);
+            (notifications, next)
+        }
+        // 처음 요청: 안 읽은 알림을 바로 주고, 다음에는 보관 중인 가장 최근 알림 이후부터
+        None => {
+            let all = hub.since(&params.user, 0);
+            let next = all.last().map_or(0, |n| n.id);
+            (all.into_iter().filter(|n| !n.read).collect(), next)
+        }
+    };
+
+    Json(PollResponse {
+        notifications,
+        unread_count: hub.unread_count(&params.user),
+        next,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use http_body_util::BodyExt;
+    use std::{
+        future::IntoFuture,
+        net::{Ipv4Addr, SocketAddr},
+    };
+    use tokio::net::TcpStream;
+    use tokio_tungstenite::{tungstenite, MaybeTlsStream, WebSocketStream};
+    use tower::ServiceExt;
+
+    type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+    async fn serve(state: AppState) -> SocketAddr {
+        let listener = tokio::net::TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)))
+            .await
+            .unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(axum::serve(listener, app(state)).into_future());
+        addr
+    }
+
+    async fn connect_ws(addr: SocketAddr, user: &str) -> Client {
+        let (socket, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/ws?user={user}"))
+            .await
+            .unwrap();
+        socket
+    }
+
+    async fn next_ws_event(socket: &mut Client) -> HubEvent {
+        match socket.next().await.unwrap().unwrap() {
+            tungstenite::Message::Text(text) => serde_json::from_str(&text).unwrap(),
+            other => panic!("expected a text message but got {other:?}"),
+        }
+    }
+
+    async fn request(
+        state: &AppState,
+        method: &str,
+        uri: &str,
+        body: &str,
+    ) -> axum::response::Response {
+        let request = axum::http::Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_owned()))
+            .unwrap();
+        app(state.clone()).oneshot(request).await.unwrap()
+    }
+
+    async fn json<T: serde::de::DeserializeOwned>(response: axum::response::Response) -> T {
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    // SSE 응답 본문에서 빈 줄로 끝나는 이벤트 하나를 읽음
+    async fn next_sse_event(body: &mut Body) -> String {
+        let mut buffer = String::new();
+        while !buffer.ends_with("\n\n") {
+            let frame = body.frame().await.unwrap().unwrap();
+            if let Ok(data) = frame.into_data() {
+                buffer.push_str(std::str::from_utf8(&data).unwrap());
+            }
+        }
+        buffer
+    }
+
+    #[tokio::test]
+    async fn every_transport_receives_the_same_notification() {
+        let state = Arc::new(NotificationHub::new(INBOX_CAPACITY));
+        let addr = serve(state.clone()).await;
+
+        let mut ws = connect_ws(addr, "alice").await;
+        let mut sse = request(&state, "GET", "/sse?user=alice", "")
+            .await
+            .into_body();
+        let poll = tokio::spawn({
+            let state = state.clone();
+            async move {
+                json::<PollResponse>(request(&state, "GET", "/poll?user=alice&since=0", "").await)
+                    .await
+            }
+        });
+        // 다른 사용자의 알림은 받지 않음
+        let mut bob = connect_ws(addr, "bob").await;
+
+        let response = request(
+            &state,
+            "POST",
+            "/notify/alice",
+            r#"{"title":"Build passed","body":"main #42"}"#,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let sent: Notification = json(response).await;
+        assert!(!sent.read);
+
+        assert_eq!(
+            next_ws_event(&mut ws).await,
+            HubEvent::Notification(sent.clone())
+        );
+
+        let event = next_sse_event(&mut sse).await;
+        assert!(event.starts_with("event: notification\n"), "{event}");
+        assert!(event.contains(&format!("id: {}\n", sent.id)), "{event}");
+        assert!(event.contains(r#""title":"Build passed""#), "{event}");
+
+        let polled = poll.await.unwrap();
+        assert_eq!(polled.notifications, std::slice::from_ref(&sent));
+        assert_eq!((polled.unread_count, polled.next), (1, sent.id));
+
+        request(&state, "POST", "/notify/bob", r#"{"title":"for bob"}"#).await;
+        match next_ws_event(&mut bob).await {
+            HubEvent::Notification(n) => assert_eq!(n.title, "for bob"),
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn acknowledging_on_one_device_updates_the_others() {
+        let state = Arc::new(NotificationHub::new(INBOX_CAPACITY));
+        let addr = serve(state.clone()).await;
+        let first = state.notify(
+            "alice",
+            NewNotification {
+                title: "first".to_owned(),
+                body: None,
+            },
+        );
+        let second = state.notify(
+            "alice",
+            NewNotification {
+                title: "second".to_owned(),
+                body: None,
+            },
+        );
+
+        // 나중에 연결해도 안 읽은 알림부터 받음
+        let mut phone = connect_ws(addr, "alice").await;
+        let mut laptop = request(&state, "GET", "/sse?user=alice", "")
+            .await
+            .into_body();
+        for expected in [&first, &second] {
+            assert_eq!(
+                next_ws_event(&mut phone).await,
+                HubEvent::Notification(expected.clone())
+            );
+            assert!(next_sse_event(&mut laptop)
+                .await
+                .contains(&format!("id: {}\n", expected.id)));
+        }
+
+        // 휴대폰(WebSocket)에서 읽음 → 노트북(SSE)에 read 이벤트
+        phone
+            .send(tungstenite::Message::text(format!(
+                r#"{{"type":"ack","ids":[{}]}}"#,
+                first.id
+            )))
+            .await
+            .unwrap();
+        let event = next_sse_event(&mut laptop).await;
+        assert!(event.starts_with("event: read\n"), "{event}");
+        assert!(
+            event.contains(&format!(r#""ids":[{}],"unread_count":1"#, first.id)),
+            "{event}"
+        );
+        assert_eq!(
+            next_ws_event(&mut phone).await,
+            HubEvent::Read {
+                ids: vec![first.id],
+                unread_count: 1
+            }
+        );
+
+        // HTTP로 하나 읽음 처리
+        let response = request(
+            &state,
+            "POST",
+            &format!("/notifications/{}/ack?user=alice", second.id),
+            "",
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            next_ws_event(&mut phone).await,
+            HubEvent::Read {
+                ids: vec![second.id],
+                unread_count: 0
+            }
+        );
+
+        let inbox: Inbox =
+            json(request(&state, "GET", "/notifications?user=alice", "").await).await;
+        assert_eq!(inbox.unread_count, 0);
+        assert!(inbox.notifications.iter().all(|n| n.read));
+
+        // 이미 모두 읽었으면 ack-all은 아무것도 바꾸지 않음
+        let acked: Acknowledged =
+            json(request(&state, "POST", "/notifications/ack-all?user=alice", "").await).await;
+        assert!(acked.ids.is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_notifications_and_empty_titles() {
+        let state = Arc::new(NotificationHub::new(INBOX_CAPACITY));
+        let sent = state.notify(
+            "alice",
+            NewNotification {
+                title: "mine".to_owned(),
+                body: None,
+            },
+        );
+
+        // 다른 사용자의 알림은 읽음 처리할 수 없음
+        let response = request(
+            &state,
+            "POST",
+            &format!("/notifications/{}/ack?user=bob", sent.id),
+            "",
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(state.unread_count("alice"), 1);
+
+        let response = request(&state, "POST", "/notify/alice", r#"{"title":"  "}"#).await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        // 첫 poll은 기다리지 않고 현재의 안 읽은 알림을 돌려줌
+        let polled: PollResponse = json(request(&state, "GET", "/poll?user=alice", "").await).await;
+        assert_eq!(polled.notifications, std::slice::from_ref(&sent));
+        assert_eq!(polled.next, sent.id);
+    }
+}
+
+// ⸻
+
+// 🧪 테스트 방법
+// 	1.	cargo run -p example-notifications
+// 	2.	받는 쪽 (터미널 여러 개)
+// 		curl -N 'localhost:3000/sse?user=alice'
+// 		websocat 'ws://localhost:3000/ws?user=alice'
+// 		curl 'localhost:3000/poll?user=alice&since=0'   (새 알림이 올 때까지 대기)
+// 	3.	보내기
+// 		curl -X POST localhost:3000/notify/alice -H 'content-type: application/json' \
+// 		  -d '{"title":"Build passed","body":"main #42"}'
+// 		→ 세 터미널 모두에 같은 알림
+// 	4.	읽음 처리
+// 		websocat 창에 {"type":"ack_all"} 입력 또는
+// 		curl -X POST 'localhost:3000/notifications/1/ack?user=alice'
+// 		→ SSE 터미널에 event: read
+// 	5.	curl 'localhost:3000/notifications?user=alice&unread_only=true'
+
+// ⸻
+
+// ✅ 요약 흐름
+// POST /notify/{user}
+//  └── NotificationHub (사용자별 알림함 + broadcast 채널)
+//       ├── /ws   → JSON 텍스트 메시지 (ack도 같은 연결로)
+//       ├── /sse  → event: notification / read
+//       └── /poll → since 이후 알림 (없으면 최대 30초 대기)
+// 읽음 처리 (ws ack, POST .../ack, ack-all) → read 이벤트로 모든 연결에 전파
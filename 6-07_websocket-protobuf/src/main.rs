@@ -0,0 +1,337 @@
+//! WebSocket으로 protobuf 메시지를 주고받는 예제 (바이너리 프로토콜)
+//!
+//! 다른 WebSocket 예제는 모두 텍스트(JSON)를 쓰지만, 메시지가 많거나 작을 때는
+//! 스키마가 있는 바이너리 형식이 더 작고 빠름
+//!
+//! • 프레임 형식과 메시지 타입: `proto.rs` (스키마: `proto/messages.proto`)
+//! • `dispatch`: `Command` enum을 match해서 명령별로 응답을 만듦
+//! • 스키마가 맞지 않으면 연결을 끊지 않고 `Error` 메시지로 응답
+//!   → 읽을 수 없는 프레임, 다른 버전, 모르는 명령, 텍스트 프레임
+//! • 핸들러는 6-05처럼 `Sink`/`Stream`으로 분리해서 채널로 단위 테스트
+//!
+//! ```not_rust
+//! cargo run -p example-websocket-protobuf
+//! ```
+
+mod proto;
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket},
+        WebSocketUpgrade,
+    },
+    response::Response,
+    routing::get,
+    Router,
+};
+use futures::{Sink, SinkExt, Stream, StreamExt};
+use proto::{
+    decode_frame, encode_frame, ClientMessage, Command, ErrorCode, Pong, Reply, ServerMessage, Sum,
+    PROTOCOL_VERSION,
+};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+/// 프레임 하나의 최대 크기
+const MAX_FRAME_BYTES: usize = 64 * 1024;
+
+/// 프레임 하나에 담을 수 있는 최대 메시지 수
+const MAX_MESSAGES_PER_FRAME: usize = 64;
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app()).await.unwrap();
+}
+
+fn app() -> Router {
+    Router::new().route("/ws", get(ws_handler))
+}
+
+async fn ws_handler(ws: WebSocketUpgrade) -> Response {
+    ws.max_message_size(MAX_FRAME_BYTES)
+        .on_upgrade(|socket: WebSocket| {
+            let (write, read) = socket.split();
+            handle_socket(write, read)
+        })
+}
+
+// 받은 프레임 하나에 대한 응답을 프레임 하나로 묶어서 보냄
+async fn handle_socket<W, R>(mut write: W, mut read: R)
+where
+    W: Sink<Message> + Unpin,
+    R: Stream<Item = Result<Message, axum::Error>> + Unpin,
+{
+    while let Some(Ok(msg)) = read.next().await {
+        let replies = match msg {
+            Message::Binary(frame) => handle_frame(&frame),
+            Message::Text(_) => vec![ServerMessage::error(
+                0,
+                ErrorCode::TextFrame,
+                "send binary frames with length-prefixed protobuf messages",
+            )],
+            Message::Close(_) => break,
+            // Ping/Pong은 axum이 처리
+            Message::Ping(_) | Message::Pong(_) => continue,
+        };
+
+        if write
+            .send(Message::Binary(encode_frame(&replies)))
+            .await
+            .is_err()
+        {
+            break;
+        }
+    }
+}
+
+fn handle_frame(frame: &[u8]) -> Vec<ServerMessage> {
+    match decode_frame::<ClientMessage>(frame) {
+        Ok(messages) if messages.len() > MAX_MESSAGES_PER_FRAME => vec![ServerMessage::error(
+            0,
+            ErrorCode::TooManyMessages,
+            format!("at most {MAX_MESSAGES_PER_FRAME} messages per frame"),
+        )],
+        Ok(messages) => messages.into_iter().map(dispatch).collect(),
+        // 어느 요청인지 알 수 없으므로 request_id = 0
+        Err(err) => {
+            tracing::debug!(%err, "undecodable frame");
+            vec![ServerMessage::error(0, ErrorCode::Decode, err.to_string())]
+        }
+    }
+}
+
+// 🧭 명령별 처리
+fn dispatch(msg: ClientMessage) -> ServerMessage {
+    let request_id = msg.request_id;
+    if msg.version != PROTOCOL_VERSION {
+        return ServerMessage::error(
+            request_id,
+            ErrorCode::UnsupportedVersion,
+            format!(
+                "protocol version {} is not supported (expected {PROTOCOL_VERSION})",
+                msg.version
+            ),
+        );
+    }
+
+    let reply = match msg.command {
+        Some(Command::Ping(ping)) => Reply::Pong(Pong {
+            sent_at_ms: ping.sent_at_ms,
+        }),
+        Some(Command::Echo(echo)) => Reply::Echo(echo),
+        Some(Command::Add(add)) => {
+            match add
+                .values
+                .iter()
+                .try_fold(0i64, |sum, value| sum.checked_add(*value))
+            {
+                Some(value) => Reply::Sum(Sum { value }),
+                None => {
+                    return ServerMessage::error(request_id, ErrorCode::Overflow, "sum overflows")
+                }
+            }
+        }
+        // 모르는 oneof 번호는 prost가 건너뛰므로 None으로 들어옴
+        None => {
+            return ServerMessage::error(
+                request_id,
+                ErrorCode::UnknownCommand,
+                "unknown or missing command",
+            )
+        }
+    };
+
+    ServerMessage {
+        request_id,
+        reply: Some(reply),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::channel::mpsc;
+    use proto::{Add, Echo, Ping};
+
+    // 6-05와 같은 방식: futures 채널이 Sink/Stream을 구현하므로 소켓 대신 사용
+    struct TestClient {
+        tx: mpsc::Sender<Result<Message, axum::Error>>,
+        rx: mpsc::Receiver<Message>,
+    }
+
+    impl TestClient {
+        fn connect() -> Self {
+            let (socket_write, rx) = mpsc::channel(16);
+            let (tx, socket_read) = mpsc::channel(16);
+            tokio::spawn(handle_socket(socket_write, socket_read));
+            Self { tx, rx }
+        }
+
+        async fn send_raw(&mut self, msg: Message) -> Vec<ServerMessage> {
+            self.tx.send(Ok(msg)).await.unwrap();
+            match self.rx.next().await.unwrap() {
+                Message::Binary(frame) => decode_frame(&frame).unwrap(),
+                other => panic!("expected a binary frame but got {other:?}"),
+            }
+        }
+
+        async fn send(&mut self, messages: &[ClientMessage]) -> Vec<ServerMessage> {
+            self.send_raw(Message::Binary(encode_frame(messages))).await
+        }
+    }
+
+    fn request(request_id: u64, command: Command) -> ClientMessage {
+        ClientMessage {
+            version: PROTOCOL_VERSION,
+            request_id,
+            command: Some(command),
+        }
+    }
+
+    fn error_code(reply: &ServerMessage) -> ErrorCode {
+        match &reply.reply {
+            Some(Reply::Error(err)) => err.code(),
+            other => panic!("expected an error but got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn commands_in_one_frame_are_answered_in_one_frame() {
+        let mut client = TestClient::connect();
+
+        let replies = client
+            .send(&[
+                request(1, Command::Ping(Ping { sent_at_ms: 42 })),
+                request(2, Command::Echo(Echo { text: "hi".into() })),
+                request(
+                    3,
+                    Command::Add(Add {
+                        values: vec![40, 5, -3],
+                    }),
+                ),
+            ])
+            .await;
+
+        assert_eq!(
+            replies,
+            [
+                ServerMessage {
+                    request_id: 1,
+                    reply: Some(Reply::Pong(Pong { sent_at_ms: 42 })),
+                },
+                ServerMessage {
+                    request_id: 2,
+                    reply: Some(Reply::Echo(Echo { text: "hi".into() })),
+                },
+                ServerMessage {
+                    request_id: 3,
+                    reply: Some(Reply::Sum(Sum { value: 42 })),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn schema_mismatches_get_error_frames_and_keep_the_connection() {
+        let mut client = TestClient::connect();
+
+        // 읽을 수 없는 바이트
+        let replies = client
+            .send_raw(Message::Binary(vec![0xff, 0xff, 0xff].into()))
+            .await;
+        assert_eq!(error_code(&replies[0]), ErrorCode::Decode);
+
+        // 다른 버전
+        let mut old = request(7, Command::Ping(Ping::default()));
+        old.version = 0;
+        let replies = client.send(&[old]).await;
+        assert_eq!(replies[0].request_id, 7);
+        assert_eq!(error_code(&replies[0]), ErrorCode::UnsupportedVersion);
+
+        // 텍스트 프레임
+        let replies = client.send_raw(Message::Text("ping".into())).await;
+        assert_eq!(error_code(&replies[0]), ErrorCode::TextFrame);
+
+        let replies = client
+            .send(&[request(
+                8,
+                Command::Add(Add {
+                    values: vec![i64::MAX, 1],
+                }),
+            )])
+            .await;
+        assert_eq!(error_code(&replies[0]), ErrorCode::Overflow);
+
+        // 에러 뒤에도 정상 명령은 그대로 처리
+        let replies = client
+            .send(&[request(9, Command::Echo(Echo { text: "ok".into() }))])
+            .await;
+        assert_eq!(
+            replies[0].reply,
+            Some(Reply::Echo(Echo { text: "ok".into() }))
+        );
+    }
+
+    // 새 명령(필드 번호 13)이 추가된 스키마를 쓰는 클라이언트
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct NewerClientMessage {
+        #[prost(uint32, tag = "1")]
+        version: u32,
+        #[prost(uint64, tag = "2")]
+        request_id: u64,
+        #[prost(message, optional, tag = "13")]
+        subscribe: Option<Echo>,
+    }
+
+    #[tokio::test]
+    async fn unknown_commands_from_newer_clients_are_reported() {
+        let mut client = TestClient::connect();
+        let newer = NewerClientMessage {
+            version: PROTOCOL_VERSION,
+            request_id: 5,
+            subscribe: Some(Echo {
+                text: "news".into(),
+            }),
+        };
+
+        let replies = client
+            .send_raw(Message::Binary(encode_frame(&[newer])))
+            .await;
+        assert_eq!(replies[0].request_id, 5);
+        assert_eq!(error_code(&replies[0]), ErrorCode::UnknownCommand);
+    }
+
+    #[tokio::test]
+    async fn frames_with_too_many_messages_are_rejected() {
+        let mut client = TestClient::connect();
+        let messages: Vec<_> = (0..=MAX_MESSAGES_PER_FRAME as u64)
+            .map(|id| request(id, Command::Ping(Ping::default())))
+            .collect();
+
+        let replies = client.send(&messages).await;
+        assert_eq!(replies.len(), 1);
+        assert_eq!(error_code(&replies[0]), ErrorCode::TooManyMessages);
+    }
+}
+
+// 🧪 테스트 방법
+//
+// cargo test -p example-websocket-protobuf
+//
+// 브라우저/다른 언어 클라이언트는 proto/messages.proto로 코드를 생성해서 사용
+// (예: protobuf.js의 `encodeDelimited` / `decodeDelimited`가 같은 길이 접두 형식)
+//
+// websocat으로 직접 확인 (ping, request_id = 1, sent_at_ms = 42):
+// printf '\x08\x08\x01\x10\x01\x52\x02\x08\x2a' | websocat --binary ws://127.0.0.1:3000/ws | xxd
+// → 06 10 01 52 02 08 2a  (길이 6, request_id 1, pong { sent_at_ms: 42 })
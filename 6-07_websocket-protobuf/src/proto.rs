@@ -0,0 +1,183 @@
+//! 📦 protobuf 메시지와 프레임 코덱
+//!
+//! • 타입은 `proto/messages.proto`와 같은 내용을 `prost` derive로 직접 작성
+//!   → 보통은 `prost-build`로 생성하지만, 예제를 `protoc` 없이 빌드할 수 있도록 손으로 옮김
+//! • WebSocket 바이너리 프레임 하나 = 길이(varint) + 메시지가 1개 이상 이어진 것
+//!   → 작은 메시지 여러 개를 프레임 하나로 묶어 보낼 수 있음
+
+use bytes::{Bytes, BytesMut};
+use prost::{DecodeError, Message};
+
+/// 이 서버가 이해하는 스키마 버전
+pub const PROTOCOL_VERSION: u32 = 1;
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ClientMessage {
+    #[prost(uint32, tag = "1")]
+    pub version: u32,
+    /// 응답에 그대로 돌려줌 (클라이언트가 요청과 응답을 짝지을 때 사용)
+    #[prost(uint64, tag = "2")]
+    pub request_id: u64,
+    /// 모르는 번호의 명령이면 `None` (새 스키마를 쓰는 클라이언트)
+    #[prost(oneof = "Command", tags = "10, 11, 12")]
+    pub command: Option<Command>,
+}
+
+/// 서버가 처리하는 명령 (`dispatch`에서 match)
+#[derive(Clone, PartialEq, prost::Oneof)]
+pub enum Command {
+    #[prost(message, tag = "10")]
+    Ping(Ping),
+    #[prost(message, tag = "11")]
+    Echo(Echo),
+    #[prost(message, tag = "12")]
+    Add(Add),
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ServerMessage {
+    #[prost(uint64, tag = "2")]
+    pub request_id: u64,
+    #[prost(oneof = "Reply", tags = "10, 11, 12, 15")]
+    pub reply: Option<Reply>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+pub enum Reply {
+    #[prost(message, tag = "10")]
+    Pong(Pong),
+    #[prost(message, tag = "11")]
+    Echo(Echo),
+    #[prost(message, tag = "12")]
+    Sum(Sum),
+    #[prost(message, tag = "15")]
+    Error(Error),
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Ping {
+    #[prost(uint64, tag = "1")]
+    pub sent_at_ms: u64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Pong {
+    #[prost(uint64, tag = "1")]
+    pub sent_at_ms: u64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Echo {
+    #[prost(string, tag = "1")]
+    pub text: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Add {
+    #[prost(sint64, repeated, tag = "1")]
+    pub values: Vec<i64>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Sum {
+    #[prost(sint64, tag = "1")]
+    pub value: i64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum ErrorCode {
+    Unspecified = 0,
+    /// 프레임을 protobuf로 읽을 수 없음
+    Decode = 1,
+    UnsupportedVersion = 2,
+    /// 서버가 모르는 명령 (클라이언트 스키마가 더 새로움)
+    UnknownCommand = 3,
+    /// 텍스트 프레임은 받지 않음
+    TextFrame = 4,
+    TooManyMessages = 5,
+    Overflow = 6,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Error {
+    #[prost(enumeration = "ErrorCode", tag = "1")]
+    pub code: i32,
+    #[prost(string, tag = "2")]
+    pub message: String,
+}
+
+impl ServerMessage {
+    pub fn error(request_id: u64, code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            request_id,
+            reply: Some(Reply::Error(Error {
+                code: code as i32,
+                message: message.into(),
+            })),
+        }
+    }
+}
+
+/// 메시지들을 길이를 붙여 이어 붙임
+pub fn encode_frame<M: Message>(messages: &[M]) -> Bytes {
+    let mut buf = BytesMut::new();
+    for message in messages {
+        // BytesMut는 필요한 만큼 늘어나므로 실패하지 않음
+        message
+            .encode_length_delimited(&mut buf)
+            .expect("BytesMut has enough capacity");
+    }
+    buf.freeze()
+}
+
+/// 프레임 안의 메시지를 모두 읽음 (하나라도 깨져 있으면 에러)
+pub fn decode_frame<M: Message + Default>(mut frame: &[u8]) -> Result<Vec<M>, DecodeError> {
+    let mut messages = Vec::new();
+    while !frame.is_empty() {
+        messages.push(M::decode_length_delimited(&mut frame)?);
+    }
+    Ok(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_carry_several_length_prefixed_messages() {
+        let messages = [
+            ClientMessage {
+                version: PROTOCOL_VERSION,
+                request_id: 1,
+                command: Some(Command::Echo(Echo { text: "hi".into() })),
+            },
+            ClientMessage {
+                version: PROTOCOL_VERSION,
+                request_id: 2,
+                command: Some(Command::Add(Add {
+                    values: vec![-1, 2, 3],
+                })),
+            },
+        ];
+
+        let frame = encode_frame(&messages);
+        // 첫 바이트는 첫 메시지의 길이
+        assert_eq!(frame[0] as usize, messages[0].encoded_len());
+        assert_eq!(decode_frame::<ClientMessage>(&frame).unwrap(), messages);
+        assert!(decode_frame::<ClientMessage>(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn truncated_frames_are_rejected() {
+        let frame = encode_frame(&[ClientMessage {
+            version: PROTOCOL_VERSION,
+            request_id: 1,
+            command: Some(Command::Echo(Echo {
+                text: "hello".into(),
+            })),
+        }]);
+
+        assert!(decode_frame::<ClientMessage>(&frame[..frame.len() - 1]).is_err());
+    }
+}
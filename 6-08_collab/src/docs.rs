@@ -0,0 +1,335 @@
+//! 📝 공유 문서 저장소 (`DocumentHub`)
+//!
+//! • 문서 하나 = automerge 문서 하나 (루트의 `"text"` 키에 Text 객체)
+//!   → 여러 클라이언트가 동시에 고쳐도 CRDT라서 도착 순서와 상관없이 모두 같은 결과로 합쳐짐
+//! • 서버도 동기화 상대(peer) 중 하나: 연결마다 `sync::State`를 두고 automerge sync 프로토콜로 주고받음
+//!   → 늦게 들어온 클라이언트도 같은 프로토콜로 빠진 변경을 모두 받음 (따로 "초기 상태" 메시지가 필요 없음)
+//! • 문서가 바뀌면 `watch` 채널로 알림 → 각 연결이 자기 상대에게 보낼 메시지를 새로 만듦
+//! • 바뀐 문서만 주기적으로 `{dir}/{name}.automerge`에 스냅샷 (`save_dirty`)
+
+use automerge::{
+    sync::{self, SyncDoc},
+    transaction::{CommitOptions, Transactable},
+    ActorId, AutoCommit, ObjId, ObjType, ReadDoc, Value, ROOT,
+};
+use axum::BoxError;
+use std::{
+    collections::HashMap,
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{sync::watch, task::JoinHandle};
+
+/// 문서 본문이 들어 있는 루트 키
+pub const TEXT_KEY: &str = "text";
+
+/// 빈 문서 (Text 객체만 있음)
+///
+/// 첫 변경을 고정된 actor와 시각으로 만들기 때문에 어디서 만들든 같은 변경(같은 hash)이 됨
+/// → 클라이언트가 서버와 동기화하기 전에 먼저 편집을 시작해도 `"text"` 객체가 충돌하지 않음
+pub fn new_document() -> AutoCommit {
+    let mut doc = AutoCommit::new().with_actor(ActorId::from([0u8; 16]));
+    doc.put_object(ROOT, TEXT_KEY, ObjType::Text)
+        .expect("the root is a map");
+    doc.commit_with(CommitOptions::default().with_time(0));
+    doc.set_actor(ActorId::random());
+    doc
+}
+
+/// 문서 이름은 파일 이름으로도 쓰므로 영문, 숫자, `-`, `_`만 허용
+pub fn is_valid_name(name: &str) -> bool {
+    (1..=64).contains(&name.len())
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+}
+
+pub struct Room {
+    doc: Mutex<Doc>,
+    changed: watch::Sender<()>,
+}
+
+struct Doc {
+    inner: AutoCommit,
+    text: ObjId,
+    // 마지막 스냅샷 이후 바뀌었는지
+    dirty: bool,
+}
+
+impl Doc {
+    fn new(inner: AutoCommit, dirty: bool) -> io::Result<Self> {
+        let text = match inner.get(ROOT, TEXT_KEY) {
+            Ok(Some((Value::Object(ObjType::Text), id))) => id,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "snapshot has no text object",
+                ))
+            }
+        };
+        Ok(Self { inner, text, dirty })
+    }
+}
+
+impl Room {
+    fn new(doc: Doc) -> Self {
+        Self {
+            doc: Mutex::new(doc),
+            changed: watch::Sender::new(()),
+        }
+    }
+
+    pub fn text(&self) -> String {
+        let doc = self.doc.lock().unwrap();
+        doc.inner.text(&doc.text).expect("text object exists")
+    }
+
+    /// 현재 heads (hex) — 클라이언트가 자기 문서와 같은 상태인지 비교할 때
+    pub fn heads(&self) -> Vec<String> {
+        let mut doc = self.doc.lock().unwrap();
+        doc.inner
+            .get_heads()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    /// 지금 연결된 클라이언트 수
+    pub fn connections(&self) -> usize {
+        self.changed.receiver_count()
+    }
+
+    /// 다른 연결이 문서를 바꿀 때마다 깨어나는 수신기
+    pub fn subscribe(&self) -> watch::Receiver<()> {
+        self.changed.subscribe()
+    }
+
+    /// 상대에게 보낼 sync 메시지 (상대가 이미 최신이거나 응답을 기다리는 중이면 `None`)
+    pub fn generate_sync_message(&self, peer: &mut sync::State) -> Option<Vec<u8>> {
+        let mut doc = self.doc.lock().unwrap();
+        let message = doc.inner.sync().generate_sync_message(peer);
+        message.map(sync::Message::encode)
+    }
+
+    /// 상대가 보낸 sync 메시지를 적용하고, 문서가 바뀌었으면 다른 연결에 알림
+    pub fn receive_sync_message(
+        &self,
+        peer: &mut sync::State,
+        bytes: &[u8],
+    ) -> Result<(), BoxError> {
+        let message = sync::Message::decode(bytes)?;
+
+        let mut doc = self.doc.lock().unwrap();
+        let before = doc.inner.get_heads();
+        doc.inner.sync().receive_sync_message(peer, message)?;
+        if doc.inner.get_heads() != before {
+            doc.dirty = true;
+            self.changed.send_replace(());
+        }
+        Ok(())
+    }
+
+    /// 바뀐 게 있으면 전체 문서를 저장한 바이트 (dirty 표시는 지움)
+    fn take_snapshot(&self) -> Option<Vec<u8>> {
+        let mut doc = self.doc.lock().unwrap();
+        if !doc.dirty {
+            return None;
+        }
+        doc.dirty = false;
+        Some(doc.inner.save())
+    }
+
+    fn mark_dirty(&self) {
+        self.doc.lock().unwrap().dirty = true;
+    }
+}
+
+pub struct DocumentHub {
+    dir: PathBuf,
+    rooms: Mutex<HashMap<String, Arc<Room>>>,
+}
+
+impl DocumentHub {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            rooms: Mutex::default(),
+        }
+    }
+
+    /// 문서를 열고, 메모리에도 스냅샷에도 없으면 빈 문서를 만듦 (WebSocket 연결)
+    pub async fn open(&self, name: &str) -> io::Result<Arc<Room>> {
+        Ok(self.load(name, true).await?.expect("created when missing"))
+    }
+
+    /// 이미 있는 문서만 (HTTP 조회)
+    pub async fn get(&self, name: &str) -> io::Result<Option<Arc<Room>>> {
+        self.load(name, false).await
+    }
+
+    async fn load(&self, name: &str, create: bool) -> io::Result<Option<Arc<Room>>> {
+        if let Some(room) = self.rooms.lock().unwrap().get(name) {
+            return Ok(Some(room.clone()));
+        }
+
+        let doc = match tokio::fs::read(self.path(name)).await {
+            Ok(bytes) => {
+                let inner = AutoCommit::load(&bytes)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+                Doc::new(inner, false)?
+            }
+            // 아직 저장된 적 없는 새 문서도 스냅샷에 남김
+            Err(err) if err.kind() == io::ErrorKind::NotFound && create => {
+                Doc::new(new_document(), true)?
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        // 파일을 읽는 동안 다른 연결이 먼저 열었으면 그쪽을 사용
+        let mut rooms = self.rooms.lock().unwrap();
+        let room = rooms
+            .entry(name.to_owned())
+            .or_insert_with(|| Arc::new(Room::new(doc)));
+        Ok(Some(room.clone()))
+    }
+
+    /// 마지막 스냅샷 이후 바뀐 문서를 모두 저장하고, 저장한 문서 수를 반환
+    pub async fn save_dirty(&self) -> io::Result<usize> {
+        let rooms: Vec<_> = self
+            .rooms
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, room)| (name.clone(), room.clone()))
+            .collect();
+
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let mut saved = 0;
+        for (name, room) in rooms {
+            let Some(bytes) = room.take_snapshot() else {
+                continue;
+            };
+            if let Err(err) = write_atomically(&self.path(&name), &bytes).await {
+                // 다음 주기에 다시 시도
+                room.mark_dirty();
+                return Err(err);
+            }
+            saved += 1;
+        }
+        Ok(saved)
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.automerge"))
+    }
+}
+
+// 쓰다가 죽어도 이전 스냅샷이 깨지지 않도록 임시 파일에 쓰고 rename
+async fn write_atomically(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let tmp = path.with_extension("automerge.tmp");
+    tokio::fs::write(&tmp, bytes).await?;
+    tokio::fs::rename(&tmp, path).await
+}
+
+/// `every`마다 바뀐 문서를 스냅샷으로 저장하는 백그라운드 태스크
+pub fn spawn_snapshots(hub: Arc<DocumentHub>, every: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            match hub.save_dirty().await {
+                Ok(0) => {}
+                Ok(saved) => tracing::debug!(saved, "saved document snapshots"),
+                Err(err) => tracing::warn!(%err, "failed to save document snapshots"),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 클라이언트 문서와 방을 더 보낼 메시지가 없을 때까지 동기화
+    fn sync_with(room: &Room, client: &mut AutoCommit) {
+        let mut client_state = sync::State::new();
+        let mut server_state = sync::State::new();
+        loop {
+            let to_server = client.sync().generate_sync_message(&mut client_state);
+            let to_client = room.generate_sync_message(&mut server_state);
+            if to_server.is_none() && to_client.is_none() {
+                return;
+            }
+            if let Some(message) = to_server {
+                room.receive_sync_message(&mut server_state, &message.encode())
+                    .unwrap();
+            }
+            if let Some(bytes) = to_client {
+                let message = sync::Message::decode(&bytes).unwrap();
+                client
+                    .sync()
+                    .receive_sync_message(&mut client_state, message)
+                    .unwrap();
+            }
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("example-collab-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn new_documents_share_the_same_first_change() {
+        let mut a = new_document();
+        let mut b = new_document();
+        assert_eq!(a.get_heads(), b.get_heads());
+
+        // 동기화 전에 따로 편집해도 같은 Text 객체에 합쳐짐
+        let (_, text) = a.get(ROOT, TEXT_KEY).unwrap().unwrap();
+        a.splice_text(&text, 0, 0, "hello").unwrap();
+        b.splice_text(&text, 0, 0, "!").unwrap();
+        a.merge(&mut b).unwrap();
+        let merged = a.text(&text).unwrap();
+        assert!(merged == "hello!" || merged == "!hello", "{merged}");
+    }
+
+    #[tokio::test]
+    async fn snapshots_are_reloaded_by_a_new_hub() {
+        let dir = temp_dir("reload");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let hub = DocumentHub::new(&dir);
+        assert!(hub.get("notes").await.unwrap().is_none());
+
+        let room = hub.open("notes").await.unwrap();
+        let mut client = new_document();
+        let (_, text) = client.get(ROOT, TEXT_KEY).unwrap().unwrap();
+        client.splice_text(&text, 0, 0, "persist me").unwrap();
+        sync_with(&room, &mut client);
+        assert_eq!(room.text(), "persist me");
+
+        assert_eq!(hub.save_dirty().await.unwrap(), 1);
+        // 바뀐 게 없으면 다시 쓰지 않음
+        assert_eq!(hub.save_dirty().await.unwrap(), 0);
+
+        let restarted = DocumentHub::new(&dir);
+        let reloaded = restarted.get("notes").await.unwrap().unwrap();
+        assert_eq!(reloaded.text(), "persist me");
+        assert_eq!(reloaded.heads(), room.heads());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn document_names_are_safe_file_names() {
+        assert!(is_valid_name("meeting-notes_2024"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("../etc/passwd"));
+        assert!(!is_valid_name("a b"));
+        assert!(!is_valid_name(&"x".repeat(65)));
+    }
+}
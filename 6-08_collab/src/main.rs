@@ -0,0 +1,348 @@
+//! 🤝 여러 사람이 같은 텍스트 문서를 동시에 편집하는 예제 (CRDT, automerge)
+//!
+//! ```not_rust
+//! cargo run -p example-collab
+//! DATA_DIR=collab-data SNAPSHOT_INTERVAL_SECS=5 cargo run -p example-collab
+//! ```
+//!
+//! • `GET /docs/{name}/ws`: WebSocket으로 automerge sync 메시지(바이너리)를 주고받음
+//!   → 연결하자마자 서버가 첫 메시지를 보내고, 이후 양쪽이 보낼 게 없을 때까지 주고받음
+//!   → 늦게 들어온 클라이언트도 이 과정에서 빠진 변경을 모두 받음
+//! • `GET /docs/{name}`: 현재 문서 내용 (`text`, `heads`, 연결 수)
+//! • 바뀐 문서는 `SNAPSHOT_INTERVAL_SECS`마다, 그리고 서버가 끝날 때 `DATA_DIR`에 저장
+//!
+//! 6-01_chat처럼 서버가 메시지를 그대로 중계하지 않고, 서버도 문서 사본을 가진 peer로 참여함
+//! (그래서 스냅샷과 HTTP 조회를 서버가 직접 할 수 있음)
+
+mod docs;
+
+use automerge::sync;
+use axum::{
+    extract::{
+        ws::{Message, WebSocketUpgrade},
+        Path, State,
+    },
+    response::Response,
+    routing::get,
+    Json, Router,
+};
+use docs::{DocumentHub, Room};
+use example_common_errors::ApiError;
+use futures::{Sink, SinkExt, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{sync::Arc, time::Duration};
+use tokio::signal;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+type AppState = Arc<DocumentHub>;
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let dir = std::env::var("DATA_DIR").unwrap_or_else(|_| "collab-data".to_owned());
+    let interval = std::env::var("SNAPSHOT_INTERVAL_SECS")
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .map_or(Duration::from_secs(5), Duration::from_secs);
+
+    let hub = Arc::new(DocumentHub::new(dir));
+    let snapshots = docs::spawn_snapshots(hub.clone(), interval);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(hub.clone()))
+        .with_graceful_shutdown(shutdown_signal())
+        .await
+        .unwrap();
+
+    // 마지막 주기 이후의 변경도 남김
+    snapshots.abort();
+    match hub.save_dirty().await {
+        Ok(saved) => tracing::info!(saved, "saved document snapshots before exiting"),
+        Err(err) => tracing::error!(%err, "failed to save document snapshots"),
+    }
+}
+
+fn app(hub: AppState) -> Router {
+    Router::new()
+        .route("/docs/{name}", get(get_document))
+        .route("/docs/{name}/ws", get(ws_handler))
+        .with_state(hub)
+}
+
+fn validate_name(name: &str) -> Result<(), ApiError> {
+    if docs::is_valid_name(name) {
+        Ok(())
+    } else {
+        Err(ApiError::Validation(
+            "document names are 1-64 characters of [A-Za-z0-9_-]".to_owned(),
+        ))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DocumentView {
+    name: String,
+    text: String,
+    heads: Vec<String>,
+    connections: usize,
+}
+
+async fn get_document(
+    State(hub): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<DocumentView>, ApiError> {
+    validate_name(&name)?;
+    let room = hub
+        .get(&name)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or_else(|| ApiError::NotFound(format!("document {name} not found")))?;
+
+    Ok(Json(DocumentView {
+        text: room.text(),
+        heads: room.heads(),
+        connections: room.connections(),
+        name,
+    }))
+}
+
+async fn ws_handler(
+    State(hub): State<AppState>,
+    Path(name): Path<String>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    validate_name(&name)?;
+    let room = hub.open(&name).await.map_err(ApiError::internal)?;
+
+    Ok(ws.on_upgrade(move |socket| {
+        let (write, read) = socket.split();
+        handle_socket(room, write, read)
+    }))
+}
+
+// 🔄 연결 하나 = sync 상대 하나
+//
+// 보낼 메시지가 있으면 보내고, 클라이언트 메시지나 다른 연결의 변경을 기다리는 것을 반복
+async fn handle_socket<W, R>(room: Arc<Room>, mut write: W, mut read: R)
+where
+    W: Sink<Message> + Unpin,
+    R: Stream<Item = Result<Message, axum::Error>> + Unpin,
+{
+    let mut peer = sync::State::new();
+    let mut changed = room.subscribe();
+
+    loop {
+        if let Some(message) = room.generate_sync_message(&mut peer) {
+            if write.send(Message::Binary(message.into())).await.is_err() {
+                break;
+            }
+        }
+
+        tokio::select! {
+            msg = read.next() => match msg {
+                Some(Ok(Message::Binary(bytes))) => {
+                    if let Err(err) = room.receive_sync_message(&mut peer, &bytes) {
+                        tracing::debug!(%err, "invalid sync message, closing the connection");
+                        break;
+                    }
+                }
+                // 텍스트 프레임은 이 프로토콜에 없음, Ping/Pong은 axum이 처리
+                Some(Ok(Message::Text(_) | Message::Ping(_) | Message::Pong(_))) => {}
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+            },
+            // 다른 연결이 문서를 바꿈 → 다음 반복에서 이 상대에게 보낼 메시지를 만듦
+            _ = changed.changed() => {}
+        }
+    }
+}
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("failed to install signal handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use automerge::{sync::SyncDoc, transaction::Transactable, AutoCommit, ReadDoc, ROOT};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use futures::channel::mpsc;
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    // 6-05와 같은 방식: 소켓 대신 futures 채널로 handle_socket을 실행
+    struct TestClient {
+        doc: AutoCommit,
+        peer: sync::State,
+        tx: mpsc::Sender<Result<Message, axum::Error>>,
+        rx: mpsc::Receiver<Message>,
+    }
+
+    impl TestClient {
+        /// 빈 문서로 시작해서 서버와 한 번 동기화
+        async fn connect(hub: &DocumentHub, name: &str) -> Self {
+            let room = hub.open(name).await.unwrap();
+            let (socket_write, rx) = mpsc::channel(64);
+            let (tx, socket_read) = mpsc::channel(64);
+            tokio::spawn(handle_socket(room, socket_write, socket_read));
+
+            let mut client = Self {
+                doc: AutoCommit::new(),
+                peer: sync::State::new(),
+                tx,
+                rx,
+            };
+            client.sync().await;
+            client
+        }
+
+        /// 양쪽 모두 보낼 메시지가 없을 때까지 주고받음
+        ///
+        /// 테스트는 시간을 멈춰 두므로(`tokio::time::pause`) 서버가 더 보낼 게 없으면 바로 타임아웃
+        async fn sync(&mut self) {
+            loop {
+                if let Some(message) = self.doc.sync().generate_sync_message(&mut self.peer) {
+                    let frame = Message::Binary(message.encode().into());
+                    self.tx.send(Ok(frame)).await.unwrap();
+                }
+
+                match tokio::time::timeout(Duration::from_secs(1), self.rx.next()).await {
+                    Ok(Some(Message::Binary(bytes))) => {
+                        let message = sync::Message::decode(&bytes).unwrap();
+                        self.doc
+                            .sync()
+                            .receive_sync_message(&mut self.peer, message)
+                            .unwrap();
+                    }
+                    Ok(other) => panic!("expected a sync message but got {other:?}"),
+                    Err(_) => return,
+                }
+            }
+        }
+
+        fn splice(&mut self, pos: usize, del: isize, text: &str) {
+            let (_, obj) = self.doc.get(ROOT, docs::TEXT_KEY).unwrap().unwrap();
+            self.doc.splice_text(&obj, pos, del, text).unwrap();
+        }
+
+        fn text(&self) -> String {
+            let (_, obj) = self.doc.get(ROOT, docs::TEXT_KEY).unwrap().unwrap();
+            self.doc.text(&obj).unwrap()
+        }
+    }
+
+    fn hub(name: &str) -> AppState {
+        let dir =
+            std::env::temp_dir().join(format!("example-collab-{}-{name}", std::process::id()));
+        Arc::new(DocumentHub::new(dir))
+    }
+
+    async fn get_json(hub: &AppState, uri: &str) -> (StatusCode, serde_json::Value) {
+        let response = app(hub.clone())
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn concurrent_edits_converge_on_every_client() {
+        tokio::time::pause();
+        let hub = hub("converge");
+        let mut alice = TestClient::connect(&hub, "notes").await;
+        let mut bob = TestClient::connect(&hub, "notes").await;
+
+        alice.splice(0, 0, "Hello");
+        alice.sync().await;
+        bob.sync().await;
+        assert_eq!(bob.text(), "Hello");
+
+        // 서로의 변경을 보기 전에 동시에 편집
+        alice.splice(0, 0, "Oh, ");
+        bob.splice(5, 0, "!");
+        alice.sync().await;
+        bob.sync().await;
+        alice.sync().await;
+
+        assert_eq!(alice.text(), "Oh, Hello!");
+        assert_eq!(bob.text(), "Oh, Hello!");
+
+        let (status, body) = get_json(&hub, "/docs/notes").await;
+        assert_eq!(status, StatusCode::OK);
+        let view: DocumentView = serde_json::from_value(body).unwrap();
+        assert_eq!(view.text, "Oh, Hello!");
+        assert_eq!(view.connections, 2);
+    }
+
+    #[tokio::test]
+    async fn late_joiners_receive_the_whole_document() {
+        tokio::time::pause();
+        let hub = hub("late-joiner");
+        let mut alice = TestClient::connect(&hub, "draft").await;
+        alice.splice(0, 0, "first line\n");
+        alice.sync().await;
+        alice.splice(11, 0, "second line\n");
+        alice.sync().await;
+        // alice가 나간 뒤에도 문서는 서버에 남아 있음
+        drop(alice);
+
+        let carol = TestClient::connect(&hub, "draft").await;
+        assert_eq!(carol.text(), "first line\nsecond line\n");
+    }
+
+    #[tokio::test]
+    async fn unknown_and_invalid_documents_are_rejected() {
+        let hub = hub("rejected");
+
+        let (status, _) = get_json(&hub, "/docs/missing").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _) = get_json(&hub, "/docs/not%20a%20name").await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
+
+// 🧪 테스트 방법
+//
+// cargo test -p example-collab
+//
+// 실제 클라이언트는 automerge(JS: @automerge/automerge)로 같은 문서 구조를 만들고
+// `Automerge.generateSyncMessage` / `receiveSyncMessage`를 WebSocket 바이너리 프레임으로 주고받으면 됨
+//
+// 문서 조회:
+// curl http://127.0.0.1:3000/docs/notes
+// → {"name":"notes","text":"...","heads":["..."],"connections":2}
@@ -0,0 +1,350 @@
+//! 🎮 게임 월드와 고정 틱 루프
+//!
+//! • `Game::run`: tokio 태스크 하나가 `TICK_RATE`(60Hz)로 월드를 진행
+//!   → dt는 항상 같은 값 (실제 경과 시간이 아니라 틱 수로 시뮬레이션)
+//!   → 틱이 밀려도 몰아서 따라잡지 않음 (`MissedTickBehavior::Skip`)
+//! • 클라이언트 입력은 위치를 바로 바꾸지 않고 "현재 이동 방향"만 기록 → 다음 틱에 반영
+//!   → 입력을 아무리 많이 보내도 이동 속도는 서버가 정함
+//! • `BROADCAST_EVERY` 틱마다(20Hz) 지난 전송 이후 바뀐 플레이어만 모아 diff로 방송
+//!   → JSON 직렬화는 한 번만 하고 모든 연결이 같은 `Utf8Bytes`를 공유
+//! • diff 계산과 방송, 새 구독자의 스냅샷은 모두 월드 락 안에서 → 스냅샷과 diff 사이에 빠지는 틱이 없음
+
+use axum::extract::ws::Utf8Bytes;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::sync::broadcast;
+
+/// 초당 시뮬레이션 틱 수
+pub const TICK_RATE: u32 = 60;
+/// 몇 틱마다 diff를 보낼지 (60Hz / 3 = 20Hz)
+pub const BROADCAST_EVERY: u64 = 3;
+/// 맵은 (0, 0) ~ (ARENA_SIZE, ARENA_SIZE)
+pub const ARENA_SIZE: f32 = 100.0;
+/// 초당 이동 거리
+pub const SPEED: f32 = 20.0;
+pub const MAX_PLAYERS: usize = 32;
+
+// 연결 하나가 밀릴 수 있는 최대 diff 수 (넘으면 스냅샷으로 다시 맞춤)
+const CHANNEL_CAPACITY: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerState {
+    pub id: u32,
+    pub name: String,
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub tick: u64,
+    pub players: Vec<PlayerState>,
+}
+
+/// 지난 방송 이후 바뀐 것만
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diff {
+    pub tick: u64,
+    /// 새로 들어왔거나 움직인 플레이어
+    pub updated: Vec<PlayerState>,
+    /// 나간 플레이어 ID
+    pub removed: Vec<u32>,
+}
+
+/// 서버 → 클라이언트 메시지
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// 접속 직후 한 번: 내 ID와 전체 상태
+    Welcome {
+        id: u32,
+        state: Snapshot,
+    },
+    /// 너무 밀려서 diff를 놓쳤을 때 전체 상태로 다시 맞춤
+    Snapshot(Snapshot),
+    Diff(Diff),
+    Error {
+        message: String,
+    },
+}
+
+/// 클라이언트 입력: 이동 방향 (각 축 -1, 0, 1)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+pub struct Input {
+    pub dx: i8,
+    pub dy: i8,
+}
+
+impl Input {
+    pub fn validate(&self) -> Result<(), String> {
+        if (-1..=1).contains(&self.dx) && (-1..=1).contains(&self.dy) {
+            Ok(())
+        } else {
+            Err(format!(
+                "dx and dy must be -1, 0 or 1 (got {}, {})",
+                self.dx, self.dy
+            ))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GameFull;
+
+struct Player {
+    state: PlayerState,
+    input: Input,
+}
+
+#[derive(Default)]
+struct World {
+    tick: u64,
+    next_id: u32,
+    players: BTreeMap<u32, Player>,
+    // 마지막으로 방송한 상태 (diff 기준)
+    last_sent: BTreeMap<u32, PlayerState>,
+}
+
+impl World {
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            tick: self.tick,
+            players: self.players.values().map(|p| p.state.clone()).collect(),
+        }
+    }
+}
+
+pub struct Game {
+    world: Mutex<World>,
+    updates: broadcast::Sender<Utf8Bytes>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self {
+            world: Mutex::default(),
+            updates: broadcast::channel(CHANNEL_CAPACITY).0,
+        }
+    }
+}
+
+impl Game {
+    pub fn is_full(&self) -> bool {
+        self.world.lock().unwrap().players.len() >= MAX_PLAYERS
+    }
+
+    /// 맵 가운데에 새 플레이어를 만들고, 현재 상태와 diff 구독을 함께 반환
+    pub fn join(
+        &self,
+        name: String,
+    ) -> Result<(u32, Snapshot, broadcast::Receiver<Utf8Bytes>), GameFull> {
+        let mut world = self.world.lock().unwrap();
+        if world.players.len() >= MAX_PLAYERS {
+            return Err(GameFull);
+        }
+        world.next_id += 1;
+        let id = world.next_id;
+        let state = PlayerState {
+            id,
+            name,
+            x: ARENA_SIZE / 2.0,
+            y: ARENA_SIZE / 2.0,
+        };
+        world.players.insert(
+            id,
+            Player {
+                state,
+                input: Input::default(),
+            },
+        );
+        Ok((id, world.snapshot(), self.updates.subscribe()))
+    }
+
+    pub fn leave(&self, id: u32) {
+        self.world.lock().unwrap().players.remove(&id);
+    }
+
+    /// 다음 틱부터 적용할 이동 방향
+    pub fn set_input(&self, id: u32, input: Input) {
+        if let Some(player) = self.world.lock().unwrap().players.get_mut(&id) {
+            player.input = input;
+        }
+    }
+
+    /// 관전자용 전체 상태 (`GET /state`)
+    pub fn snapshot(&self) -> Snapshot {
+        self.world.lock().unwrap().snapshot()
+    }
+
+    /// 밀린 구독을 최신으로 바꾸고 그 시점의 전체 상태를 반환
+    pub fn resync(&self, updates: &mut broadcast::Receiver<Utf8Bytes>) -> Snapshot {
+        let world = self.world.lock().unwrap();
+        *updates = self.updates.subscribe();
+        world.snapshot()
+    }
+
+    /// 틱 하나 진행 (`dt`초)
+    pub fn step(&self, dt: f32) {
+        let mut world = self.world.lock().unwrap();
+        world.tick += 1;
+        for player in world.players.values_mut() {
+            let Input { dx, dy } = player.input;
+            if dx == 0 && dy == 0 {
+                continue;
+            }
+            // 대각선이 더 빠르지 않도록 정규화
+            let len = f32::from(dx).hypot(f32::from(dy));
+            let distance = SPEED * dt / len;
+            let state = &mut player.state;
+            state.x = (state.x + f32::from(dx) * distance).clamp(0.0, ARENA_SIZE);
+            state.y = (state.y + f32::from(dy) * distance).clamp(0.0, ARENA_SIZE);
+        }
+    }
+
+    /// 지난 방송 이후 바뀐 것을 모아 방송 (바뀐 게 없으면 보내지 않음)
+    pub fn broadcast_diff(&self) -> Option<Diff> {
+        let mut world = self.world.lock().unwrap();
+        let World {
+            tick,
+            players,
+            last_sent,
+            ..
+        } = &mut *world;
+
+        let updated: Vec<_> = players
+            .values()
+            .filter(|p| last_sent.get(&p.state.id) != Some(&p.state))
+            .map(|p| p.state.clone())
+            .collect();
+        let removed: Vec<_> = last_sent
+            .keys()
+            .filter(|id| !players.contains_key(id))
+            .copied()
+            .collect();
+        if updated.is_empty() && removed.is_empty() {
+            return None;
+        }
+
+        for id in &removed {
+            last_sent.remove(id);
+        }
+        for state in &updated {
+            last_sent.insert(state.id, state.clone());
+        }
+
+        let diff = Diff {
+            tick: *tick,
+            updated,
+            removed,
+        };
+        let text =
+            serde_json::to_string(&ServerMessage::Diff(diff.clone())).expect("diffs serialize");
+        // 접속한 클라이언트가 없으면 실패하지만 상관없음
+        let _ = self.updates.send(text.into());
+        Some(diff)
+    }
+
+    /// 🕹️ 고정 틱 루프 (서버가 끝날 때까지 실행)
+    pub async fn run(self: Arc<Self>) {
+        let period = Duration::from_secs(1) / TICK_RATE;
+        let dt = period.as_secs_f32();
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            interval.tick().await;
+            self.step(dt);
+            let tick = self.world.lock().unwrap().tick;
+            if tick.is_multiple_of(BROADCAST_EVERY) {
+                self.broadcast_diff();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(game: &Game, id: u32) -> (f32, f32) {
+        let snapshot = game.snapshot();
+        let player = snapshot.players.iter().find(|p| p.id == id).unwrap();
+        (player.x, player.y)
+    }
+
+    #[test]
+    fn players_move_at_a_fixed_speed_and_stay_in_the_arena() {
+        let game = Game::default();
+        let (id, _, _rx) = game.join("alice".to_owned()).unwrap();
+
+        game.set_input(id, Input { dx: 1, dy: 0 });
+        for _ in 0..TICK_RATE {
+            game.step(1.0 / TICK_RATE as f32);
+        }
+        let (x, y) = position(&game, id);
+        assert!((x - (ARENA_SIZE / 2.0 + SPEED)).abs() < 0.01, "{x}");
+        assert_eq!(y, ARENA_SIZE / 2.0);
+
+        // 대각선도 같은 속도
+        game.set_input(id, Input { dx: -1, dy: -1 });
+        game.step(1.0);
+        let (x2, y2) = position(&game, id);
+        assert!(((x - x2).hypot(y - y2) - SPEED).abs() < 0.01);
+
+        // 오래 움직여도 맵 밖으로 나가지 않음
+        game.step(100.0);
+        assert_eq!(position(&game, id), (0.0, 0.0));
+        assert_eq!(game.snapshot().tick, TICK_RATE as u64 + 2);
+    }
+
+    #[tokio::test]
+    async fn diffs_only_carry_changes_since_the_last_broadcast() {
+        let game = Game::default();
+        let (alice, _, mut rx) = game.join("alice".to_owned()).unwrap();
+        let (bob, _, _) = game.join("bob".to_owned()).unwrap();
+
+        // 처음에는 두 명 모두 새로 들어옴
+        let diff = game.broadcast_diff().unwrap();
+        assert_eq!(diff.updated.len(), 2);
+        let text = rx.recv().await.unwrap();
+        assert_eq!(
+            serde_json::from_str::<ServerMessage>(&text).unwrap(),
+            ServerMessage::Diff(diff)
+        );
+
+        // 아무도 움직이지 않으면 보내지 않음
+        game.step(0.1);
+        assert_eq!(game.broadcast_diff(), None);
+
+        game.set_input(alice, Input { dx: 0, dy: 1 });
+        game.leave(bob);
+        game.step(0.1);
+        let diff = game.broadcast_diff().unwrap();
+        assert_eq!(
+            diff.updated.iter().map(|p| p.id).collect::<Vec<_>>(),
+            [alice]
+        );
+        assert_eq!(diff.removed, [bob]);
+    }
+
+    #[test]
+    fn inputs_outside_the_allowed_range_are_rejected() {
+        assert!(Input { dx: 1, dy: -1 }.validate().is_ok());
+        assert!(Input { dx: 2, dy: 0 }.validate().is_err());
+        assert!(Input { dx: 0, dy: -128 }.validate().is_err());
+    }
+
+    #[test]
+    fn the_game_refuses_players_when_full() {
+        let game = Game::default();
+        for i in 0..MAX_PLAYERS {
+            game.join(format!("p{i}")).unwrap();
+        }
+        assert!(game.is_full());
+        assert!(game.join("late".to_owned()).is_err());
+    }
+}
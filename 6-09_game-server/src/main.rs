@@ -0,0 +1,374 @@
+//! 🕹️ 게임 서버: 시뮬레이션 루프와 axum을 함께 쓰는 예제
+//!
+//! ```not_rust
+//! cargo run -p example-game-server
+//! ```
+//!
+//! • 시뮬레이션: 별도 tokio 태스크가 60Hz로 월드를 진행 (`game.rs`)
+//! • `GET /ws?name=`: 플레이어로 접속
+//!   → 서버 → `welcome`(내 ID + 전체 상태), 이후 20Hz로 `diff`
+//!   → 클라이언트 → `{"type":"input","dx":1,"dy":0}` (이동 방향)
+//! • `GET /state`: 관전자용 전체 상태 (접속하지 않고 폴링)
+//!
+//! 입력 검증과 rate limit은 연결마다 따로 함
+//! • 범위를 벗어난 입력, 읽을 수 없는 메시지 → `error` 메시지로 응답하고 무시
+//! • 초당 `INPUTS_PER_SEC`개(버스트 `INPUT_BURST`)를 넘으면 버림
+//!   → 입력은 방향만 바꾸므로 버려도 다음 입력에서 바로 회복됨
+
+mod game;
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocketUpgrade},
+        Query, State,
+    },
+    response::Response,
+    routing::get,
+    Json, Router,
+};
+use example_common_errors::ApiError;
+use futures::{Sink, SinkExt, Stream, StreamExt};
+use game::{Game, Input, ServerMessage, Snapshot};
+use serde::Deserialize;
+use std::sync::Arc;
+use tokio::{sync::broadcast::error::RecvError, time::Instant};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+/// 연결 하나가 1초에 보낼 수 있는 입력 수
+const INPUTS_PER_SEC: f64 = 30.0;
+/// 한 번에 몰아서 보낼 수 있는 입력 수
+const INPUT_BURST: f64 = 10.0;
+
+type AppState = Arc<Game>;
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let game = Arc::new(Game::default());
+    tokio::spawn(game.clone().run());
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(game)).await.unwrap();
+}
+
+fn app(game: AppState) -> Router {
+    Router::new()
+        .route("/ws", get(ws_handler))
+        .route("/state", get(state))
+        .with_state(game)
+}
+
+async fn state(State(game): State<AppState>) -> Json<Snapshot> {
+    Json(game.snapshot())
+}
+
+#[derive(Debug, Deserialize)]
+struct JoinParams {
+    name: String,
+}
+
+async fn ws_handler(
+    State(game): State<AppState>,
+    Query(JoinParams { name }): Query<JoinParams>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    let name = name.trim().to_owned();
+    if name.is_empty() || name.chars().count() > 16 {
+        return Err(ApiError::Validation(
+            "name must be 1-16 characters".to_owned(),
+        ));
+    }
+    // 업그레이드 전에 한 번 확인 (그 사이에 찰 수도 있으므로 join에서 다시 확인)
+    if game.is_full() {
+        return Err(ApiError::Conflict("the game is full".to_owned()));
+    }
+
+    Ok(ws.on_upgrade(move |socket| {
+        let (write, read) = socket.split();
+        handle_socket(game, name, write, read)
+    }))
+}
+
+/// 클라이언트 → 서버 메시지
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    Input(Input),
+}
+
+// 토큰 버킷: 초당 `rate`개씩 채워지고 최대 `burst`개까지 모임
+struct RateLimiter {
+    rate: f64,
+    burst: f64,
+    tokens: f64,
+    last: Instant,
+}
+
+impl RateLimiter {
+    fn new(rate: f64, burst: f64) -> Self {
+        Self {
+            rate,
+            burst,
+            tokens: burst,
+            last: Instant::now(),
+        }
+    }
+
+    fn try_acquire(&mut self) -> bool {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+        self.last = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+async fn send<W>(write: &mut W, message: &ServerMessage) -> Result<(), W::Error>
+where
+    W: Sink<Message> + Unpin,
+{
+    let text = serde_json::to_string(message).expect("server messages serialize");
+    write.send(Message::text(text)).await
+}
+
+async fn handle_socket<W, R>(game: AppState, name: String, mut write: W, mut read: R)
+where
+    W: Sink<Message> + Unpin,
+    R: Stream<Item = Result<Message, axum::Error>> + Unpin,
+{
+    let Ok((id, state, mut updates)) = game.join(name) else {
+        let full = ServerMessage::Error {
+            message: "the game is full".to_owned(),
+        };
+        let _ = send(&mut write, &full).await;
+        let _ = write.send(Message::Close(None)).await;
+        return;
+    };
+    tracing::debug!(id, "player joined");
+
+    let mut limiter = RateLimiter::new(INPUTS_PER_SEC, INPUT_BURST);
+    if send(&mut write, &ServerMessage::Welcome { id, state })
+        .await
+        .is_ok()
+    {
+        loop {
+            let result = tokio::select! {
+                update = updates.recv() => match update {
+                    // 이미 직렬화된 diff를 그대로 보냄
+                    Ok(text) => write.send(Message::Text(text)).await,
+                    Err(RecvError::Lagged(missed)) => {
+                        tracing::debug!(id, missed, "client lagged, sending a snapshot");
+                        let snapshot = game.resync(&mut updates);
+                        send(&mut write, &ServerMessage::Snapshot(snapshot)).await
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                msg = read.next() => match msg {
+                    Some(Ok(Message::Text(text))) => {
+                        match handle_input(&game, id, &mut limiter, &text) {
+                            Ok(()) => Ok(()),
+                            Err(message) => {
+                                send(&mut write, &ServerMessage::Error { message }).await
+                            }
+                        }
+                    }
+                    Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                    Some(Ok(_)) => Ok(()),
+                },
+            };
+            if result.is_err() {
+                break;
+            }
+        }
+    }
+
+    game.leave(id);
+    tracing::debug!(id, "player left");
+}
+
+fn handle_input(game: &Game, id: u32, limiter: &mut RateLimiter, text: &str) -> Result<(), String> {
+    if !limiter.try_acquire() {
+        return Err("too many inputs, slow down".to_owned());
+    }
+    let ClientMessage::Input(input) =
+        serde_json::from_str(text).map_err(|err| format!("invalid message: {err}"))?;
+    input.validate()?;
+    game.set_input(id, input);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use futures::channel::mpsc;
+    use game::Diff;
+    use http_body_util::BodyExt;
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    // 6-05와 같은 방식: 소켓 대신 futures 채널로 handle_socket을 실행
+    struct TestClient {
+        tx: mpsc::Sender<Result<Message, axum::Error>>,
+        rx: mpsc::Receiver<Message>,
+    }
+
+    impl TestClient {
+        fn connect(game: &AppState, name: &str) -> Self {
+            let (socket_write, rx) = mpsc::channel(64);
+            let (tx, socket_read) = mpsc::channel(64);
+            tokio::spawn(handle_socket(
+                game.clone(),
+                name.to_owned(),
+                socket_write,
+                socket_read,
+            ));
+            Self { tx, rx }
+        }
+
+        async fn send(&mut self, text: &str) {
+            self.tx.send(Ok(Message::text(text))).await.unwrap();
+        }
+
+        async fn recv(&mut self) -> ServerMessage {
+            match self.rx.next().await.unwrap() {
+                Message::Text(text) => serde_json::from_str(&text).unwrap(),
+                other => panic!("expected a text message but got {other:?}"),
+            }
+        }
+
+        /// 다음 diff (에러 메시지는 실패)
+        async fn next_diff(&mut self) -> Diff {
+            match self.recv().await {
+                ServerMessage::Diff(diff) => diff,
+                other => panic!("expected a diff but got {other:?}"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn players_receive_diffs_from_the_game_loop() {
+        tokio::time::pause();
+        let game = Arc::new(Game::default());
+        tokio::spawn(game.clone().run());
+
+        let mut alice = TestClient::connect(&game, "alice");
+        let ServerMessage::Welcome { id, state } = alice.recv().await else {
+            panic!("expected a welcome message");
+        };
+        assert!(state
+            .players
+            .iter()
+            .any(|p| p.id == id && p.name == "alice"));
+
+        // 들어온 것 자체가 첫 diff
+        let diff = alice.next_diff().await;
+        assert_eq!(diff.updated[0].id, id);
+        assert!(diff.tick.is_multiple_of(game::BROADCAST_EVERY));
+
+        alice.send(r#"{"type":"input","dx":1,"dy":0}"#).await;
+        let diff = alice.next_diff().await;
+        assert!(diff.updated[0].x > game::ARENA_SIZE / 2.0);
+
+        // 관전자는 HTTP로 같은 상태를 봄
+        let response = app(game.clone())
+            .oneshot(Request::get("/state").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let snapshot: Snapshot = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(snapshot.players.len(), 1);
+        assert!(snapshot.tick >= diff.tick);
+
+        // 연결이 끊기면 다음 diff에서 빠짐
+        let mut bob = TestClient::connect(&game, "bob");
+        bob.recv().await;
+        drop(alice);
+        loop {
+            let diff = bob.next_diff().await;
+            if diff.removed.contains(&id) {
+                break;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_and_excessive_inputs_get_errors() {
+        tokio::time::pause();
+        // 틱 루프 없이 입력 처리만 확인
+        let game = Arc::new(Game::default());
+        let mut client = TestClient::connect(&game, "mallory");
+        client.recv().await;
+
+        client.send(r#"{"type":"input","dx":5,"dy":0}"#).await;
+        assert!(matches!(client.recv().await, ServerMessage::Error { .. }));
+        client.send(r#"{"type":"teleport","x":0}"#).await;
+        assert!(matches!(client.recv().await, ServerMessage::Error { .. }));
+
+        // 버스트를 다 쓰면 거절 (위의 두 개도 토큰을 씀)
+        for _ in 2..INPUT_BURST as usize {
+            client.send(r#"{"type":"input","dx":0,"dy":1}"#).await;
+        }
+        client.send(r#"{"type":"input","dx":0,"dy":1}"#).await;
+        match client.recv().await {
+            ServerMessage::Error { message } => assert!(message.contains("slow down")),
+            other => panic!("expected a rate limit error but got {other:?}"),
+        }
+
+        // 시간이 지나면 다시 허용
+        tokio::time::advance(Duration::from_secs(1)).await;
+        client.send(r#"{"type":"input","dx":0,"dy":0}"#).await;
+        client.send(r#"{"type":"input","dx":9,"dy":0}"#).await;
+        match client.recv().await {
+            ServerMessage::Error { message } => assert!(message.contains("dx and dy")),
+            other => panic!("expected a validation error but got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn players_beyond_the_limit_are_turned_away() {
+        let game = Arc::new(Game::default());
+        for i in 0..game::MAX_PLAYERS {
+            game.join(format!("p{i}")).unwrap();
+        }
+        let mut late = TestClient::connect(&game, "late");
+        assert_eq!(
+            late.recv().await,
+            ServerMessage::Error {
+                message: "the game is full".to_owned()
+            }
+        );
+    }
+}
+
+// 🧪 테스트 방법
+//
+// cargo test -p example-game-server
+//
+// websocat으로 접속해서 움직이기:
+// websocat "ws://127.0.0.1:3000/ws?name=alice"
+// {"type":"input","dx":1,"dy":0}
+// → {"type":"diff","tick":..,"updated":[{"id":1,"name":"alice","x":51.0,"y":50.0}],"removed":[]} ...
+//
+// 관전:
+// curl http://127.0.0.1:3000/state
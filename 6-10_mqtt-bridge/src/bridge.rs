@@ -0,0 +1,92 @@
+//! 🔌 MQTT 브로커 ↔ 앱 상태
+//!
+//! 토픽 구조
+//!  • `devices/{id}/telemetry`: 장치 → 서버 (JSON), 브리지가 구독
+//!  • `devices/{id}/commands`: 서버 → 장치 (JSON), `POST /devices/{id}/command`가 발행
+//!
+//! • rumqttc는 `EventLoop::poll`을 계속 호출해야 네트워크 작업(연결, ping, 송수신)이 진행됨
+//!   → 백그라운드 태스크 하나가 poll만 담당하고, 핸들러는 `AsyncClient`로 요청만 넣음
+//! • 연결이 끊기면 poll이 에러를 반환하고, 다음 poll에서 다시 연결
+//!   → clean session이라 재연결하면 구독이 사라지므로 `ConnAck`를 받을 때마다 다시 구독
+
+use rumqttc::{AsyncClient, Event, EventLoop, Packet, Publish, QoS};
+use std::time::Duration;
+
+use crate::devices::{Devices, Telemetry};
+
+pub const TELEMETRY_FILTER: &str = "devices/+/telemetry";
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+pub fn command_topic(device_id: &str) -> String {
+    format!("devices/{device_id}/commands")
+}
+
+/// 토픽에 그대로 넣을 수 있는 id인지 (`/`, `+`, `#`가 들어가면 다른 토픽이 됨)
+pub fn is_valid_device_id(device_id: &str) -> bool {
+    !device_id.is_empty()
+        && device_id.len() <= 64
+        && device_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// `devices/{id}/telemetry` 메시지 → `Telemetry`
+pub fn telemetry(publish: &Publish) -> Result<Telemetry, String> {
+    let device_id = publish
+        .topic
+        .strip_prefix("devices/")
+        .and_then(|rest| rest.strip_suffix("/telemetry"))
+        .filter(|id| is_valid_device_id(id))
+        .ok_or("unexpected topic")?;
+    let payload = serde_json::from_slice(&publish.payload).map_err(|err| err.to_string())?;
+    Ok(Telemetry::now(device_id, payload))
+}
+
+pub async fn run(client: AsyncClient, mut eventloop: EventLoop, devices: Devices) {
+    loop {
+        match eventloop.poll().await {
+            Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                tracing::info!("connected to mqtt broker");
+                // 이 태스크가 poll 중이므로 기다리는 `subscribe`가 아니라 `try_subscribe`
+                if let Err(err) = client.try_subscribe(TELEMETRY_FILTER, QoS::AtMostOnce) {
+                    tracing::error!(%err, "failed to subscribe");
+                }
+            }
+            Ok(Event::Incoming(Packet::Publish(publish))) => match telemetry(&publish) {
+                Ok(telemetry) => devices.record(telemetry),
+                Err(err) => tracing::warn!(topic = %publish.topic, %err, "ignoring message"),
+            },
+            Ok(_) => {}
+            Err(err) => {
+                tracing::warn!(%err, "mqtt connection error, reconnecting");
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn publish(topic: &str, payload: &str) -> Publish {
+        Publish::new(topic, QoS::AtMostOnce, payload)
+    }
+
+    #[test]
+    fn telemetry_topics_and_payloads_are_checked() {
+        let parsed = telemetry(&publish("devices/sensor-1/telemetry", r#"{"temp":21.5}"#)).unwrap();
+        assert_eq!(parsed.device_id, "sensor-1");
+        assert_eq!(parsed.payload["temp"], 21.5);
+
+        assert!(telemetry(&publish("devices/sensor-1/status", "{}")).is_err());
+        assert!(telemetry(&publish("devices//telemetry", "{}")).is_err());
+        assert!(telemetry(&publish("devices/sensor-1/telemetry", "not json")).is_err());
+
+        assert!(is_valid_device_id("sensor_1-a"));
+        for id in ["", "a/b", "a+b", "a#"] {
+            assert!(!is_valid_device_id(id), "{id}");
+        }
+    }
+}
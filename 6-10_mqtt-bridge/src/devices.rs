@@ -0,0 +1,117 @@
+//! 📟 장치별 최근 텔레메트리 버퍼 + 실시간 fan-out
+//!
+//! • 장치마다 최근 `HISTORY_PER_DEVICE`개만 메모리에 보관 (오래된 것부터 버림)
+//! • 새 텔레메트리는 `broadcast` 채널로 SSE 구독자 모두에게 전달
+//!   → 구독자가 느려서 채널이 밀리면 그 구독자만 일부 메시지를 건너뜀 (`Lagged`)
+
+use serde::Serialize;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, RwLock},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::broadcast;
+
+pub const HISTORY_PER_DEVICE: usize = 100;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Telemetry {
+    pub device_id: String,
+    /// 브리지가 받은 시각 (unix ms)
+    pub received_at: u64,
+    /// 장치가 보낸 JSON 그대로
+    pub payload: serde_json::Value,
+}
+
+impl Telemetry {
+    pub fn now(device_id: impl Into<String>, payload: serde_json::Value) -> Self {
+        let received_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        Self {
+            device_id: device_id.into(),
+            received_at,
+            payload,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeviceSummary {
+    pub id: String,
+    pub last_seen: u64,
+    pub buffered: usize,
+}
+
+#[derive(Clone)]
+pub struct Devices {
+    history: Arc<RwLock<HashMap<String, VecDeque<Telemetry>>>>,
+    live: broadcast::Sender<Telemetry>,
+}
+
+impl Devices {
+    /// `live_capacity`: 구독자마다 밀려 있을 수 있는 최대 메시지 수
+    pub fn new(live_capacity: usize) -> Self {
+        Self {
+            history: Default::default(),
+            live: broadcast::channel(live_capacity).0,
+        }
+    }
+
+    pub fn record(&self, telemetry: Telemetry) {
+        {
+            let mut history = self.history.write().unwrap();
+            let buffer = history.entry(telemetry.device_id.clone()).or_default();
+            if buffer.len() == HISTORY_PER_DEVICE {
+                buffer.pop_front();
+            }
+            buffer.push_back(telemetry.clone());
+        }
+        // 구독자가 없으면 에러지만 상관없음
+        let _ = self.live.send(telemetry);
+    }
+
+    pub fn latest(&self, device_id: &str) -> Option<Telemetry> {
+        self.history
+            .read()
+            .unwrap()
+            .get(device_id)
+            .and_then(|buffer| buffer.back().cloned())
+    }
+
+    /// 최근 `limit`개 (오래된 것부터)
+    pub fn history(&self, device_id: &str, limit: usize) -> Option<Vec<Telemetry>> {
+        let history = self.history.read().unwrap();
+        let buffer = history.get(device_id)?;
+        Some(
+            buffer
+                .iter()
+                .skip(buffer.len().saturating_sub(limit))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    pub fn list(&self) -> Vec<DeviceSummary> {
+        let mut devices: Vec<DeviceSummary> = self
+            .history
+            .read()
+            .unwrap()
+            .iter()
+            .filter_map(|(id, buffer)| {
+                Some(DeviceSummary {
+                    id: id.clone(),
+                    last_seen: buffer.back()?.received_at,
+                    buffered: buffer.len(),
+                })
+            })
+            .collect();
+        devices.sort_by(|a, b| a.id.cmp(&b.id));
+        devices
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Telemetry> {
+        self.live.subscribe()
+    }
+}
@@ -0,0 +1,318 @@
+//! MQTT 브리지 예제 (IoT 장치 ↔ HTTP/SSE)
+//!
+//! 🧭 구성
+//!  [장치] --publish devices/{id}/telemetry--> [MQTT 브로커] --> [브리지 태스크] --> 상태(버퍼) --> HTTP / SSE
+//!  [장치] <--subscribe devices/{id}/commands-- [MQTT 브로커] <-- POST /devices/{id}/command
+//!
+//! • `GET /devices`: 텔레메트리를 보낸 장치 목록
+//! • `GET /devices/{id}/latest`: 마지막 텔레메트리
+//! • `GET /devices/{id}/telemetry?limit=20`: 최근 텔레메트리 (장치마다 최대 `HISTORY_PER_DEVICE`개 보관)
+//! • `GET /telemetry/stream[?device={id}]`: 들어오는 텔레메트리를 SSE로 (firehose)
+//! • `POST /devices/{id}/command`: JSON 본문을 `devices/{id}/commands`로 발행 (QoS 1) → 202
+//!   → 브로커와 연결이 끊겨 있으면 클라이언트 큐에 쌓였다가 재연결 후 전송, 큐가 가득 차면 503
+//!
+//! 브로커 주소는 `MQTT_URL` (기본값 `mqtt://127.0.0.1:1883?client_id=axum-mqtt-bridge`)
+//! → 로컬 브로커: `docker run --rm -p 1883:1883 eclipse-mosquitto:2 mosquitto -c /mosquitto-no-auth.conf`
+
+mod bridge;
+mod devices;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
+    routing::{get, post},
+    Json, Router,
+};
+use devices::{DeviceSummary, Devices, Telemetry, HISTORY_PER_DEVICE};
+use rumqttc::{AsyncClient, MqttOptions, QoS};
+use serde::Deserialize;
+use std::{convert::Infallible, time::Duration};
+use tokio_stream::{
+    wrappers::errors::BroadcastStreamRecvError, wrappers::BroadcastStream, Stream, StreamExt,
+};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+/// 클라이언트가 아직 브로커로 보내지 못한 요청을 쌓아둘 수 있는 수
+const MQTT_REQUEST_CAPACITY: usize = 64;
+
+/// SSE 구독자마다 밀려 있을 수 있는 텔레메트리 수
+const LIVE_CAPACITY: usize = 256;
+
+#[derive(Clone)]
+struct AppState {
+    devices: Devices,
+    mqtt: AsyncClient,
+}
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let url = std::env::var("MQTT_URL")
+        .unwrap_or_else(|_| "mqtt://127.0.0.1:1883?client_id=axum-mqtt-bridge".to_owned());
+    let mut options = MqttOptions::parse_url(url).expect("invalid MQTT_URL");
+    options.set_keep_alive(Duration::from_secs(30));
+
+    // 브로커 연결은 백그라운드 태스크가 담당 (브로커가 없어도 HTTP 서버는 뜸)
+    let (mqtt, eventloop) = AsyncClient::new(options, MQTT_REQUEST_CAPACITY);
+    let devices = Devices::new(LIVE_CAPACITY);
+    tokio::spawn(bridge::run(mqtt.clone(), eventloop, devices.clone()));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(AppState { devices, mqtt }))
+        .await
+        .unwrap();
+}
+
+fn app(state: AppState) -> Router {
+    Router::new()
+        .route("/devices", get(list_devices))
+        .route("/devices/{id}/latest", get(latest_telemetry))
+        .route("/devices/{id}/telemetry", get(recent_telemetry))
+        .route("/devices/{id}/command", post(send_command))
+        .route("/telemetry/stream", get(telemetry_stream))
+        .with_state(state)
+}
+
+// 📋 GET /devices
+async fn list_devices(State(state): State<AppState>) -> Json<Vec<DeviceSummary>> {
+    Json(state.devices.list())
+}
+
+// 📍 GET /devices/{id}/latest
+async fn latest_telemetry(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Telemetry>, StatusCode> {
+    state
+        .devices
+        .latest(&id)
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryParams {
+    limit: Option<usize>,
+}
+
+// 🕘 GET /devices/{id}/telemetry?limit=20
+async fn recent_telemetry(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<HistoryParams>,
+) -> Result<Json<Vec<Telemetry>>, StatusCode> {
+    let limit = params.limit.unwrap_or(20).min(HISTORY_PER_DEVICE);
+    state
+        .devices
+        .history(&id, limit)
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+// 📡 GET /telemetry/stream?device=sensor-1
+#[derive(Debug, Deserialize)]
+struct StreamParams {
+    device: Option<String>,
+}
+
+async fn telemetry_stream(
+    State(state): State<AppState>,
+    Query(params): Query<StreamParams>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = BroadcastStream::new(state.devices.subscribe()).filter_map(move |message| {
+        let event = match message {
+            Ok(telemetry)
+                if params
+                    .device
+                    .as_ref()
+                    .is_some_and(|device| *device != telemetry.device_id) =>
+            {
+                return None;
+            }
+            Ok(telemetry) => Event::default()
+                .event("telemetry")
+                .json_data(&telemetry)
+                .ok()?,
+            // 따라오지 못해 건너뛴 메시지 수를 알려줌
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                Event::default().event("lagged").data(skipped.to_string())
+            }
+        };
+        Some(Ok(event))
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+// 📤 POST /devices/{id}/command
+async fn send_command(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(command): Json<serde_json::Value>,
+) -> Result<StatusCode, (StatusCode, &'static str)> {
+    if !bridge::is_valid_device_id(&id) {
+        return Err((StatusCode::BAD_REQUEST, "invalid device id"));
+    }
+
+    // 큐가 가득 차면 기다리지 않고 바로 실패 (브로커가 오래 끊겨 있는 경우)
+    state
+        .mqtt
+        .try_publish(
+            bridge::command_topic(&id),
+            QoS::AtLeastOnce,
+            false,
+            command.to_string(),
+        )
+        .map_err(|err| {
+            tracing::warn!(%err, device = %id, "failed to queue command");
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "mqtt client is not available",
+            )
+        })?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use http_body_util::BodyExt;
+    use rumqttc::Request as MqttRequest;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    // 브로커 없이: 클라이언트가 보낸 요청을 채널에서 직접 확인
+    fn state() -> (AppState, flume::Receiver<MqttRequest>) {
+        let (tx, rx) = flume::bounded(4);
+        let state = AppState {
+            devices: Devices::new(LIVE_CAPACITY),
+            mqtt: AsyncClient::from_senders(tx),
+        };
+        (state, rx)
+    }
+
+    async fn get_json(app: &Router, uri: &str) -> (StatusCode, Value) {
+        let response = app
+            .clone()
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+    }
+
+    #[tokio::test]
+    async fn telemetry_is_buffered_per_device() {
+        let (state, _rx) = state();
+        let app = app(state.clone());
+        for temp in [20, 21, 22] {
+            state
+                .devices
+                .record(Telemetry::now("sensor-1", json!({ "temp": temp })));
+        }
+
+        let (status, latest) = get_json(&app, "/devices/sensor-1/latest").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(latest["payload"], json!({ "temp": 22 }));
+
+        let (_, recent) = get_json(&app, "/devices/sensor-1/telemetry?limit=2").await;
+        let temps: Vec<&Value> = recent
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|telemetry| &telemetry["payload"]["temp"])
+            .collect();
+        assert_eq!(temps, [&json!(21), &json!(22)]);
+
+        let (_, devices) = get_json(&app, "/devices").await;
+        assert_eq!(devices[0]["id"], "sensor-1");
+        assert_eq!(devices[0]["buffered"], 3);
+
+        let (status, _) = get_json(&app, "/devices/unknown/latest").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn commands_are_published_to_the_device_topic() {
+        let (state, rx) = state();
+        let app = app(state);
+
+        let command = |id: &str| {
+            Request::post(format!("/devices/{id}/command"))
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"action":"reboot"}"#))
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(command("sensor-1")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let Ok(MqttRequest::Publish(publish)) = rx.try_recv() else {
+            panic!("expected a publish request");
+        };
+        assert_eq!(publish.topic, "devices/sensor-1/commands");
+        assert_eq!(publish.qos, QoS::AtLeastOnce);
+        assert_eq!(&publish.payload[..], br#"{"action":"reboot"}"#);
+
+        // 와일드카드가 들어간 id는 다른 토픽이 되므로 거부
+        let response = app.oneshot(command("sensor%2B1")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(rx.is_empty());
+    }
+
+    #[tokio::test]
+    async fn firehose_streams_live_telemetry() {
+        let (state, _rx) = state();
+        let response = app(state.clone())
+            .oneshot(
+                Request::get("/telemetry/stream?device=sensor-2")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+        state
+            .devices
+            .record(Telemetry::now("sensor-1", json!({ "temp": 20 })));
+        state
+            .devices
+            .record(Telemetry::now("sensor-2", json!({ "temp": 30 })));
+
+        // 필터에 걸린 sensor-1은 건너뛰고 sensor-2만 옴
+        let mut body = response.into_body();
+        let frame = body.frame().await.unwrap().unwrap();
+        let text = String::from_utf8(frame.into_data().unwrap().to_vec()).unwrap();
+        assert!(text.starts_with("event: telemetry\n"), "{text}");
+        assert!(text.contains(r#""device_id":"sensor-2""#), "{text}");
+        assert!(text.contains(r#""payload":{"temp":30}"#), "{text}");
+    }
+}
+
+// 🧪 테스트 방법 (mosquitto 클라이언트 도구 사용)
+//
+// ✅ 장치처럼 텔레메트리 발행 → 상태 조회
+// mosquitto_pub -t devices/sensor-1/telemetry -m '{"temp":21.5,"humidity":40}'
+// curl localhost:3000/devices/sensor-1/latest
+// curl 'localhost:3000/devices/sensor-1/telemetry?limit=5'
+//
+// ✅ 실시간 스트림 (다른 터미널에서 mosquitto_pub을 반복)
+// curl -N 'localhost:3000/telemetry/stream?device=sensor-1'
+//
+// ✅ 명령 → 장치 토픽으로 발행
+// mosquitto_sub -t 'devices/+/commands' -v
+// curl -i -X POST localhost:3000/devices/sensor-1/command -H 'content-type: application/json' -d '{"action":"reboot"}'
+// → 202, mosquitto_sub에 `devices/sensor-1/commands {"action":"reboot"}`
@@ -0,0 +1,228 @@
+//! 🧩 템플릿 엔진 래퍼
+//!
+//! - 디버그 빌드: `templates/` 디렉터리에서 템플릿을 읽고, 파일이 바뀌면 캐시를 비워
+//!   다음 요청에서 다시 읽음 (서버 재시작 없이 템플릿 수정 확인)
+//! - 릴리스 빌드: `include_str!`로 바이너리에 포함된 템플릿 사용 (배포 시 templates 디렉터리 불필요)
+//!
+//! 두 모드 모두 템플릿 이름은 파일 이름(`home.jinja`)을 그대로 사용하고,
+//! 커스텀 필터와 전역 함수도 같은 `configure`에서 등록함
+
+use axum::{
+    http::StatusCode,
+    response::{Html, IntoResponse, Response},
+};
+use minijinja::{Environment, Error, ErrorKind};
+use serde::Serialize;
+use std::sync::{Arc, RwLock};
+
+/// 템플릿 디렉터리 (실행 위치와 상관없이 찾을 수 있도록 절대 경로 사용)
+#[cfg(debug_assertions)]
+pub const TEMPLATE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/templates");
+
+/// 릴리스 빌드에 포함되는 템플릿 (이름, 내용)
+#[cfg(not(debug_assertions))]
+const EMBEDDED: &[(&str, &str)] = &[
+    ("layout.jinja", include_str!("../templates/layout.jinja")),
+    ("home.jinja", include_str!("../templates/home.jinja")),
+    ("content.jinja", include_str!("../templates/content.jinja")),
+    ("about.jinja", include_str!("../templates/about.jinja")),
+];
+
+/// `url_for("이름")`으로 찾을 수 있는 경로 목록
+const ROUTES: &[(&str, &str)] = &[("home", "/"), ("content", "/content"), ("about", "/about")];
+
+/// 📦 상태에 넣어 공유하는 템플릿 엔진 (Clone 비용은 Arc 복사뿐)
+#[derive(Clone)]
+pub struct TemplateEngine {
+    env: Arc<RwLock<Environment<'static>>>,
+    // 엔진이 살아 있는 동안 감시를 유지하기 위해 보관 (drop되면 감시 중단)
+    #[cfg(debug_assertions)]
+    _watcher: Arc<notify::RecommendedWatcher>,
+}
+
+impl TemplateEngine {
+    /// 디버그 빌드: `TEMPLATE_DIR`에서 읽고 변경 감시
+    #[cfg(debug_assertions)]
+    pub fn new() -> Self {
+        Self::from_dir(TEMPLATE_DIR).expect("failed to watch template directory")
+    }
+
+    /// 릴리스 빌드: 바이너리에 포함된 템플릿 사용
+    #[cfg(not(debug_assertions))]
+    pub fn new() -> Self {
+        let mut env = Environment::new();
+        configure(&mut env);
+        for (name, source) in EMBEDDED {
+            env.add_template(name, source)
+                .unwrap_or_else(|err| panic!("invalid template `{name}`: {err}"));
+        }
+        Self {
+            env: Arc::new(RwLock::new(env)),
+        }
+    }
+
+    /// 🔄 디렉터리에서 템플릿을 읽는 엔진
+    ///
+    /// `path_loader`는 처음 요청된 템플릿을 읽어 Environment에 캐시하므로,
+    /// 파일이 바뀌면 캐시만 비우면 다음 렌더링에서 새 내용을 읽음
+    #[cfg(debug_assertions)]
+    pub fn from_dir(dir: impl AsRef<std::path::Path>) -> notify::Result<Self> {
+        use notify::{RecursiveMode, Watcher};
+
+        let dir = dir.as_ref();
+        let mut env = Environment::new();
+        configure(&mut env);
+        env.set_loader(minijinja::path_loader(dir));
+        let env = Arc::new(RwLock::new(env));
+
+        let watched = Arc::clone(&env);
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                match event {
+                    // 파일을 읽기만 한 이벤트는 무시
+                    Ok(event) if event.kind.is_access() => {}
+                    Ok(event) => {
+                        println!("🔄 templates changed, reloading: {:?}", event.paths);
+                        watched.write().unwrap().clear_templates();
+                    }
+                    Err(err) => eprintln!("template watcher error: {err}"),
+                }
+            })?;
+        watcher.watch(dir, RecursiveMode::Recursive)?;
+
+        Ok(Self {
+            env,
+            _watcher: Arc::new(watcher),
+        })
+    }
+
+    /// 🖼️ 템플릿 렌더링
+    pub fn render(&self, name: &str, ctx: impl Serialize) -> Result<Html<String>, RenderError> {
+        let env = self.env.read().unwrap();
+        let rendered = env.get_template(name)?.render(ctx)?;
+        Ok(Html(rendered))
+    }
+}
+
+/// 🔧 필터와 전역 함수 등록 (디버그/릴리스 공통)
+fn configure(env: &mut Environment<'static>) {
+    env.add_filter("excerpt", excerpt);
+    env.add_function("url_for", url_for);
+}
+
+/// 필터 예제: `{{ text | excerpt(20) }}` → 글자 수 기준으로 자르고 말줄임표 추가
+///
+/// 인자를 생략하면 기본 길이 40
+fn excerpt(value: &str, length: Option<usize>) -> String {
+    let length = length.unwrap_or(40);
+    match value.char_indices().nth(length) {
+        Some((end, _)) => format!("{}…", value[..end].trim_end()),
+        None => value.to_owned(),
+    }
+}
+
+/// 전역 함수 예제: `{{ url_for("about") }}` → "/about"
+///
+/// 없는 이름이면 렌더링 에러 → 링크 오타를 바로 알 수 있음
+fn url_for(name: &str) -> Result<&'static str, Error> {
+    ROUTES
+        .iter()
+        .find(|(route, _)| *route == name)
+        .map(|(_, path)| *path)
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidOperation,
+                format!("unknown route `{name}`"),
+            )
+        })
+}
+
+/// ❌ 렌더링 실패 → 500
+#[derive(Debug)]
+pub struct RenderError(Error);
+
+impl From<Error> for RenderError {
+    fn from(err: Error) -> Self {
+        Self(err)
+    }
+}
+
+impl IntoResponse for RenderError {
+    fn into_response(self) -> Response {
+        eprintln!("template error: {:#}", self.0);
+        // 개발 중에는 에러 내용을 바로 볼 수 있도록 응답에 포함, 릴리스에서는 숨김
+        let message = if cfg!(debug_assertions) {
+            format!("template error: {:#}", self.0)
+        } else {
+            "Internal Server Error".to_owned()
+        };
+        (StatusCode::INTERNAL_SERVER_ERROR, message).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use minijinja::context;
+
+    #[test]
+    fn excerpt_cuts_on_char_boundary() {
+        assert_eq!(excerpt("hello world", Some(5)), "hello…");
+        assert_eq!(excerpt("hello world", Some(6)), "hello…");
+        assert_eq!(excerpt("안녕하세요", Some(2)), "안녕…");
+        assert_eq!(excerpt("short", None), "short");
+    }
+
+    #[test]
+    fn url_for_rejects_unknown_routes() {
+        let engine = TemplateEngine::new();
+        let env = engine.env.read().unwrap();
+
+        let rendered = env
+            .render_str(r#"{{ url_for("about") }}"#, context! {})
+            .unwrap();
+        assert_eq!(rendered, "/about");
+        assert!(env
+            .render_str(r#"{{ url_for("nope") }}"#, context! {})
+            .is_err());
+    }
+
+    #[test]
+    fn all_templates_render() {
+        let engine = TemplateEngine::new();
+
+        for name in ["home.jinja", "content.jinja", "about.jinja"] {
+            let html = engine
+                .render(name, context! { title => "T", entries => ["a"] })
+                .unwrap_or_else(|err| panic!("{name}: {:#}", err.0));
+            assert!(html.0.contains(r#"<a href="/about">"#));
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn changed_template_is_reloaded() {
+        use std::{fs, time::Duration};
+
+        let dir = std::env::temp_dir().join(format!("minijinja-reload-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("page.jinja"), "v1").unwrap();
+
+        let engine = TemplateEngine::from_dir(&dir).unwrap();
+        assert_eq!(engine.render("page.jinja", ()).unwrap().0, "v1");
+
+        fs::write(dir.join("page.jinja"), "v2").unwrap();
+
+        // 파일 변경 이벤트는 비동기로 도착하므로 잠시 기다리며 확인
+        let mut rendered = String::new();
+        for _ in 0..50 {
+            std::thread::sleep(Duration::from_millis(50));
+            rendered = engine.render("page.jinja", ()).unwrap().0;
+            if rendered == "v2" {
+                break;
+            }
+        }
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(rendered, "v2");
+    }
+}
@@ -0,0 +1,155 @@
+//! htmx 요청/응답 헤더 헬퍼
+//!
+//! - `HxRequest`: 요청이 htmx에서 왔는지 확인하는 추출기 → 조각(fragment)과 전체 페이지 중 선택
+//! - `HxTrigger`: 응답을 받은 브라우저에서 이벤트를 발생시키는 `HX-Trigger` 헤더 생성
+//!
+//! 참고: <https://htmx.org/reference/#headers>
+
+use axum::{
+    extract::FromRequestParts,
+    http::{
+        header::{HeaderValue, VARY},
+        request::Parts,
+        HeaderName,
+    },
+    response::{IntoResponseParts, ResponseParts},
+};
+use serde_json::{Map, Value};
+use std::convert::Infallible;
+
+pub const HX_REQUEST: HeaderName = HeaderName::from_static("hx-request");
+pub const HX_BOOSTED: HeaderName = HeaderName::from_static("hx-boosted");
+pub const HX_TRIGGER: HeaderName = HeaderName::from_static("hx-trigger");
+
+/// 🔎 htmx 요청 여부
+///
+/// `hx-boost`로 바뀐 일반 링크/폼 요청도 `HX-Request`를 보내지만 전체 페이지를 기대하므로
+/// 조각을 돌려줄지는 `wants_fragment()`로 판단
+#[derive(Debug, Clone, Copy)]
+pub struct HxRequest {
+    pub request: bool,
+    pub boosted: bool,
+}
+
+impl HxRequest {
+    pub fn wants_fragment(&self) -> bool {
+        self.request && !self.boosted
+    }
+}
+
+impl<S> FromRequestParts<S> for HxRequest
+where
+    S: Send + Sync,
+{
+    // 헤더가 없으면 일반 요청일 뿐이므로 실패하지 않음
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let is_true = |name| parts.headers.get(name).is_some_and(|value| value == "true");
+        Ok(Self {
+            request: is_true(HX_REQUEST),
+            boosted: is_true(HX_BOOSTED),
+        })
+    }
+}
+
+/// 🗂️ 같은 URL이 `HX-Request`에 따라 다른 응답(조각/전체 페이지)을 주므로
+/// 캐시가 둘을 섞지 않도록 `Vary: HX-Request` 추가
+pub struct VaryHxRequest;
+
+impl IntoResponseParts for VaryHxRequest {
+    type Error = Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        res.headers_mut()
+            .append(VARY, HeaderValue::from_static("HX-Request"));
+        Ok(res)
+    }
+}
+
+/// 📣 `HX-Trigger` 응답 헤더
+///
+/// ```ignore
+/// HxTrigger::new().event("todo-added")
+///     .event_with("toast", json!({ "message": "saved" }))
+/// ```
+///
+/// - 상세 정보(detail)가 없는 이벤트만 있으면 `todo-added, todo-updated` 형태
+/// - 하나라도 있으면 `{"todo-added":null,"toast":{"message":"saved"}}` 형태의 JSON
+///
+/// 클라이언트에서는 `hx-trigger="todo-added from:body"`나
+/// `document.body.addEventListener("toast", e => e.detail.message)`로 받음
+#[derive(Debug, Default, Clone)]
+pub struct HxTrigger {
+    events: Vec<(String, Option<Value>)>,
+}
+
+impl HxTrigger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn event(mut self, name: impl Into<String>) -> Self {
+        self.events.push((name.into(), None));
+        self
+    }
+
+    pub fn event_with(mut self, name: impl Into<String>, detail: Value) -> Self {
+        self.events.push((name.into(), Some(detail)));
+        self
+    }
+
+    fn header_value(&self) -> String {
+        if self.events.iter().all(|(_, detail)| detail.is_none()) {
+            let names: Vec<&str> = self.events.iter().map(|(name, _)| name.as_str()).collect();
+            return names.join(", ");
+        }
+
+        let object: Map<String, Value> = self
+            .events
+            .iter()
+            .map(|(name, detail)| (name.clone(), detail.clone().unwrap_or(Value::Null)))
+            .collect();
+        Value::Object(object).to_string()
+    }
+}
+
+impl IntoResponseParts for HxTrigger {
+    type Error = Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        if self.events.is_empty() {
+            return Ok(res);
+        }
+        // 이벤트 이름과 JSON은 사용자 입력이 아니므로 헤더 값으로 항상 유효하다고 가정
+        // (serde_json은 non-ASCII를 그대로 출력하므로 detail에는 ASCII 문자열 사용)
+        let value = HeaderValue::from_str(&self.header_value())
+            .expect("HX-Trigger value must be a valid header value");
+        res.headers_mut().insert(HX_TRIGGER, value);
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn plain_events_are_comma_separated() {
+        let trigger = HxTrigger::new().event("todo-added").event("todo-updated");
+        assert_eq!(trigger.header_value(), "todo-added, todo-updated");
+    }
+
+    #[test]
+    fn events_with_detail_are_json() {
+        let trigger = HxTrigger::new()
+            .event("todo-added")
+            .event_with("toast", json!({ "message": "saved" }));
+        let value: Value = serde_json::from_str(&trigger.header_value()).unwrap();
+        assert_eq!(
+            value,
+            json!({ "todo-added": null, "toast": { "message": "saved" } })
+        );
+    }
+}
@@ -0,0 +1,499 @@
+//! htmx + MiniJinja로 서버에서 HTML 조각(fragment)을 렌더링하는 예제입니다.
+//!
+//! 같은 핸들러가 `HX-Request` 헤더를 보고
+//! - htmx 요청이면 바뀐 부분의 HTML 조각만,
+//! - 일반 요청이면 전체 페이지(또는 리다이렉트)를 응답합니다.
+//!
+//! - `GET /`, `GET /todos`: 목록 (htmx 요청이면 `<ul>` 조각만)
+//! - `POST /todos`: 추가 → 새 `<li>` + 남은 개수(out-of-band swap)
+//! - `GET /todos/{id}`: 보기 모드 `<li>` (편집 취소)
+//! - `GET /todos/{id}/edit`: 편집 모드 `<li>`
+//! - `PUT /todos/{id}`: 제목 수정 → 보기 모드 `<li>`
+//! - `POST /todos/{id}/toggle`: 완료 토글 → `<li>` + 남은 개수
+//! - `DELETE /todos/{id}`: 삭제 → 빈 응답(`<li>` 제거) + 남은 개수
+//!
+//! 변경 응답에는 `HX-Trigger` 헤더로 `todo-added` 같은 이벤트를 함께 보냅니다.
+//!
+//! ```not_rust
+//! cargo run -p example-htmx
+//! ```
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{Html, IntoResponse, Redirect, Response},
+    routing::{get, post},
+    Form, Router,
+};
+use minijinja::{context, Environment};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+mod htmx;
+
+use htmx::{HxRequest, HxTrigger, VaryHxRequest};
+
+// 🏁 main()
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(AppState::new())).await.unwrap();
+}
+
+// 📦 앱 상태: 템플릿 환경 + 메모리 Todo 저장소
+#[derive(Clone)]
+struct AppState {
+    templates: Arc<Environment<'static>>,
+    todos: Arc<RwLock<Todos>>,
+}
+
+impl AppState {
+    fn new() -> Self {
+        Self {
+            templates: Arc::new(templates()),
+            todos: Default::default(),
+        }
+    }
+}
+
+// 🧩 템플릿 등록
+// 이름이 `.html`로 끝나면 MiniJinja가 자동으로 HTML escape 적용 (사용자 입력을 그대로 출력하므로 중요)
+fn templates() -> Environment<'static> {
+    let mut env = Environment::new();
+    for (name, source) in [
+        ("layout.html", include_str!("../templates/layout.html")),
+        ("index.html", include_str!("../templates/index.html")),
+        ("list.html", include_str!("../templates/list.html")),
+        ("todo.html", include_str!("../templates/todo.html")),
+        (
+            "todo_edit.html",
+            include_str!("../templates/todo_edit.html"),
+        ),
+        ("count.html", include_str!("../templates/count.html")),
+    ] {
+        env.add_template(name, source).unwrap();
+    }
+    env
+}
+
+fn app(state: AppState) -> Router {
+    Router::new()
+        .route("/", get(todos_index))
+        .route("/todos", get(todos_index).post(todos_create))
+        .route(
+            "/todos/{id}",
+            get(todos_show).put(todos_update).delete(todos_delete),
+        )
+        .route("/todos/{id}/edit", get(todos_edit))
+        .route("/todos/{id}/toggle", post(todos_toggle))
+        .with_state(state)
+}
+
+// 📝 Todo 저장소
+
+#[derive(Debug, Clone, Serialize)]
+struct Todo {
+    id: u64,
+    title: String,
+    completed: bool,
+}
+
+#[derive(Debug, Default)]
+struct Todos {
+    next_id: u64,
+    items: BTreeMap<u64, Todo>,
+}
+
+impl Todos {
+    fn remaining(&self) -> usize {
+        self.items.values().filter(|todo| !todo.completed).count()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TodoForm {
+    title: String,
+}
+
+// 🚏 핸들러
+
+// 전체 페이지 또는 목록 조각
+async fn todos_index(
+    hx: HxRequest,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let todos = state.todos.read().unwrap();
+    let ctx = context! {
+        todos => todos.items.values().collect::<Vec<_>>(),
+        remaining => todos.remaining(),
+    };
+
+    let template = if hx.wants_fragment() {
+        "list.html"
+    } else {
+        "index.html"
+    };
+    Ok((VaryHxRequest, render(&state, template, ctx)?))
+}
+
+async fn todos_create(
+    hx: HxRequest,
+    State(state): State<AppState>,
+    Form(form): Form<TodoForm>,
+) -> Result<Response, AppError> {
+    let title = form.title.trim();
+    if title.is_empty() {
+        // htmx는 기본적으로 4xx 응답을 화면에 반영하지 않음 (입력창 required로 1차 방어)
+        return Err(AppError::EmptyTitle);
+    }
+
+    let (todo, remaining) = {
+        let mut todos = state.todos.write().unwrap();
+        todos.next_id += 1;
+        let todo = Todo {
+            id: todos.next_id,
+            title: title.to_owned(),
+            completed: false,
+        };
+        todos.items.insert(todo.id, todo.clone());
+        (todo, todos.remaining())
+    };
+
+    // JS 없이 폼을 제출한 경우: redirect-after-post로 목록 페이지 다시 보기
+    if !hx.wants_fragment() {
+        return Ok(Redirect::to("/").into_response());
+    }
+
+    let html = render_with_count(&state, "todo.html", &todo, remaining)?;
+    let trigger = HxTrigger::new().event_with("todo-added", json!({ "id": todo.id }));
+    Ok((trigger, html).into_response())
+}
+
+async fn todos_show(
+    hx: HxRequest,
+    Path(id): Path<u64>,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    if !hx.wants_fragment() {
+        return Ok(Redirect::to("/").into_response());
+    }
+    let todo = find(&state, id)?;
+    Ok((
+        VaryHxRequest,
+        render(&state, "todo.html", context! { todo })?,
+    )
+        .into_response())
+}
+
+async fn todos_edit(
+    hx: HxRequest,
+    Path(id): Path<u64>,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    if !hx.wants_fragment() {
+        return Ok(Redirect::to("/").into_response());
+    }
+    let todo = find(&state, id)?;
+    Ok((
+        VaryHxRequest,
+        render(&state, "todo_edit.html", context! { todo })?,
+    )
+        .into_response())
+}
+
+async fn todos_update(
+    Path(id): Path<u64>,
+    State(state): State<AppState>,
+    Form(form): Form<TodoForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let title = form.title.trim();
+    if title.is_empty() {
+        return Err(AppError::EmptyTitle);
+    }
+
+    let todo = {
+        let mut todos = state.todos.write().unwrap();
+        let todo = todos.items.get_mut(&id).ok_or(AppError::NotFound)?;
+        todo.title = title.to_owned();
+        todo.clone()
+    };
+
+    let html = render(&state, "todo.html", context! { todo })?;
+    Ok((HxTrigger::new().event("todo-updated"), html))
+}
+
+async fn todos_toggle(
+    hx: HxRequest,
+    Path(id): Path<u64>,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    let (todo, remaining) = {
+        let mut todos = state.todos.write().unwrap();
+        let todo = todos.items.get_mut(&id).ok_or(AppError::NotFound)?;
+        todo.completed = !todo.completed;
+        let todo = todo.clone();
+        (todo, todos.remaining())
+    };
+
+    if !hx.wants_fragment() {
+        return Ok(Redirect::to("/").into_response());
+    }
+
+    let html = render_with_count(&state, "todo.html", &todo, remaining)?;
+    Ok((HxTrigger::new().event("todo-updated"), html).into_response())
+}
+
+async fn todos_delete(
+    Path(id): Path<u64>,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let remaining = {
+        let mut todos = state.todos.write().unwrap();
+        todos.items.remove(&id).ok_or(AppError::NotFound)?;
+        todos.remaining()
+    };
+
+    // 대상(<li>)은 빈 내용으로 교체되어 사라지고, 남은 개수만 out-of-band로 갱신
+    // (htmx는 204 응답은 swap하지 않으므로 200 + 빈 본문)
+    let html = render(&state, "count.html", context! { remaining, oob => true })?;
+    Ok((HxTrigger::new().event("todo-deleted"), html))
+}
+
+// 🧰 헬퍼
+
+fn find(state: &AppState, id: u64) -> Result<Todo, AppError> {
+    state
+        .todos
+        .read()
+        .unwrap()
+        .items
+        .get(&id)
+        .cloned()
+        .ok_or(AppError::NotFound)
+}
+
+fn render(state: &AppState, name: &str, ctx: minijinja::Value) -> Result<Html<String>, AppError> {
+    let html = state.templates.get_template(name)?.render(ctx)?;
+    Ok(Html(html))
+}
+
+// 🔀 out-of-band swap: 응답 본문에 `hx-swap-oob`가 붙은 요소를 함께 넣으면
+// htmx가 hx-target과 별개로 페이지에서 같은 id를 가진 요소를 찾아 교체함
+fn render_with_count(
+    state: &AppState,
+    name: &str,
+    todo: &Todo,
+    remaining: usize,
+) -> Result<Html<String>, AppError> {
+    let Html(main) = render(state, name, context! { todo })?;
+    let Html(count) = render(state, "count.html", context! { remaining, oob => true })?;
+    Ok(Html(main + &count))
+}
+
+// ❌ 에러 응답
+
+#[derive(Debug)]
+enum AppError {
+    NotFound,
+    EmptyTitle,
+    Template(minijinja::Error),
+}
+
+impl From<minijinja::Error> for AppError {
+    fn from(err: minijinja::Error) -> Self {
+        Self::Template(err)
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        match self {
+            Self::NotFound => (StatusCode::NOT_FOUND, "todo not found").into_response(),
+            Self::EmptyTitle => {
+                (StatusCode::UNPROCESSABLE_ENTITY, "title must not be empty").into_response()
+            }
+            Self::Template(err) => {
+                tracing::error!("template error: {err:#}");
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+        }
+    }
+}
+
+// 🧪 테스트
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header, Method, Request},
+    };
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    async fn send(
+        app: &Router,
+        method: Method,
+        uri: &str,
+        hx: bool,
+        form: Option<&str>,
+    ) -> (StatusCode, header::HeaderMap, String) {
+        let mut request = Request::builder().method(method).uri(uri);
+        if hx {
+            request = request.header("hx-request", "true");
+        }
+        let body = match form {
+            Some(form) => {
+                request = request.header(header::CONTENT_TYPE, "application/x-www-form-urlencoded");
+                Body::from(form.to_owned())
+            }
+            None => Body::empty(),
+        };
+
+        let response = app
+            .clone()
+            .oneshot(request.body(body).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, headers, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn index_returns_full_page_or_fragment() {
+        let app = app(AppState::new());
+
+        let (status, headers, body) = send(&app, Method::GET, "/todos", false, None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("<!doctype html>"));
+        assert!(body.contains(r#"<ul id="todo-list">"#));
+        assert_eq!(headers[header::VARY], "HX-Request");
+
+        let (_, _, body) = send(&app, Method::GET, "/todos", true, None).await;
+        assert!(!body.contains("<!doctype html>"));
+        assert!(body.trim_start().starts_with(r#"<ul id="todo-list">"#));
+    }
+
+    #[tokio::test]
+    async fn boosted_request_gets_full_page() {
+        let app = app(AppState::new());
+        let request = Request::get("/todos")
+            .header("hx-request", "true")
+            .header("hx-boosted", "true")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert!(String::from_utf8_lossy(&body).contains("<!doctype html>"));
+    }
+
+    #[tokio::test]
+    async fn create_returns_item_with_oob_count_and_trigger() {
+        let app = app(AppState::new());
+
+        let (status, headers, body) = send(
+            &app,
+            Method::POST,
+            "/todos",
+            true,
+            Some("title=%3Cb%3Emilk%3C%2Fb%3E"),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains(r#"<li id="todo-1""#));
+        // 사용자 입력은 escape됨
+        assert!(body.contains("&lt;b&gt;milk&lt;&#x2f;b&gt;"));
+        assert!(body.contains(r#"<span id="todo-count" hx-swap-oob="true">(1 left)</span>"#));
+        assert_eq!(headers["hx-trigger"], r#"{"todo-added":{"id":1}}"#);
+    }
+
+    #[tokio::test]
+    async fn create_without_htmx_redirects() {
+        let app = app(AppState::new());
+
+        let (status, headers, _) =
+            send(&app, Method::POST, "/todos", false, Some("title=milk")).await;
+        assert_eq!(status, StatusCode::SEE_OTHER);
+        assert_eq!(headers[header::LOCATION], "/");
+
+        let (_, _, body) = send(&app, Method::GET, "/", false, None).await;
+        assert!(body.contains("<span>milk</span>"));
+        assert!(body.contains(r#"<span id="todo-count">(1 left)</span>"#));
+    }
+
+    #[tokio::test]
+    async fn empty_title_is_rejected() {
+        let app = app(AppState::new());
+
+        let (status, _, _) = send(&app, Method::POST, "/todos", true, Some("title=++")).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn inline_edit_flow() {
+        let app = app(AppState::new());
+        send(&app, Method::POST, "/todos", true, Some("title=milk")).await;
+
+        let (_, _, body) = send(&app, Method::GET, "/todos/1/edit", true, None).await;
+        assert!(body.contains(r#"<form hx-put="/todos/1">"#));
+        assert!(body.contains(r#"value="milk""#));
+
+        let (status, headers, body) =
+            send(&app, Method::PUT, "/todos/1", true, Some("title=oat+milk")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("<span>oat milk</span>"));
+        assert_eq!(headers["hx-trigger"], "todo-updated");
+
+        // 일반 요청으로 조각 URL에 접근하면 목록 페이지로
+        let (status, _, _) = send(&app, Method::GET, "/todos/1/edit", false, None).await;
+        assert_eq!(status, StatusCode::SEE_OTHER);
+
+        let (status, _, _) = send(&app, Method::GET, "/todos/9/edit", true, None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn toggle_and_delete_update_count_out_of_band() {
+        let app = app(AppState::new());
+        send(&app, Method::POST, "/todos", true, Some("title=a")).await;
+        send(&app, Method::POST, "/todos", true, Some("title=b")).await;
+
+        let (_, _, body) = send(&app, Method::POST, "/todos/1/toggle", true, None).await;
+        assert!(body.contains(r#"class="completed""#));
+        assert!(body.contains(" checked"));
+        assert!(body.contains("(1 left)</span>"));
+
+        let (status, headers, body) = send(&app, Method::DELETE, "/todos/2", true, None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body.trim(),
+            r#"<span id="todo-count" hx-swap-oob="true">(0 left)</span>"#
+        );
+        assert_eq!(headers["hx-trigger"], "todo-deleted");
+
+        let (status, _, _) = send(&app, Method::DELETE, "/todos/2", true, None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,21 @@
+# 영어 (기본 언어) – 모든 메시지가 있어야 함
+page-title = Welcome
+greeting = Hello, { $name }!
+unread-messages =
+    { $count ->
+        [one] You have one unread message.
+       *[other] You have { $count } unread messages.
+    }
+language = Language
+footer = Built with axum and Fluent.
+
+form-title = Sign up
+field-name = Name
+field-age = Age
+submit = Submit
+signup-success = Thanks, { $name }! You are now registered.
+
+error-name-required = Please enter your name.
+error-name-too-long = Name must be at most { $max } characters.
+error-age-invalid = Age must be a whole number.
+error-age-range = Age must be between { $min } and { $max }.
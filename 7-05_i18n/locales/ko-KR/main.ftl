@@ -0,0 +1,20 @@
+# 한국어 – 없는 메시지(footer)는 기본 언어(en-US)로 대체됨
+page-title = 환영합니다
+greeting = 안녕하세요, { $name }님!
+# 한국어는 복수형 구분이 없으므로 [other]만 사용
+unread-messages =
+    { $count ->
+       *[other] 읽지 않은 메시지가 { $count }개 있습니다.
+    }
+language = 언어
+
+form-title = 가입하기
+field-name = 이름
+field-age = 나이
+submit = 제출
+signup-success = { $name }님, 가입이 완료되었습니다!
+
+error-name-required = 이름을 입력하세요.
+error-name-too-long = 이름은 { $max }자 이하로 입력하세요.
+error-age-invalid = 나이는 정수로 입력하세요.
+error-age-range = 나이는 { $min }세에서 { $max }세 사이여야 합니다.
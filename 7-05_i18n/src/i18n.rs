@@ -0,0 +1,288 @@
+//! 🌐 Fluent 메시지 번들 + 언어 협상
+//!
+//! 언어 결정 순서 (앞에서 지원하는 언어를 찾으면 사용)
+//! 1. `?lang=ko-KR` 쿼리 (→ `persist_lang` 미들웨어가 쿠키에 저장)
+//! 2. `lang` 쿠키
+//! 3. `Accept-Language` 헤더 (q 값 순서)
+//! 4. 기본 언어
+//!
+//! 요청한 언어가 `ko`처럼 지역 없이 와도 `ko-KR` 번들과 매칭됨
+
+use axum::{
+    extract::{FromRef, FromRequestParts, Query, Request, State},
+    http::{header::ACCEPT_LANGUAGE, request::Parts, HeaderMap, Uri},
+    middleware::Next,
+    response::Response,
+};
+use axum_extra::extract::cookie::{Cookie, CookieJar, SameSite};
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource};
+use fluent_langneg::{negotiate_languages, NegotiationStrategy};
+use serde::Deserialize;
+use std::{collections::HashMap, convert::Infallible, fs, io, path::Path, sync::Arc};
+use unic_langid::LanguageIdentifier;
+
+pub const LANG_COOKIE: &str = "lang";
+
+/// 📚 언어별 메시지 번들 (시작할 때 한 번 읽고 이후 읽기 전용)
+#[derive(Clone)]
+pub struct Localizer {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    bundles: HashMap<LanguageIdentifier, FluentBundle<FluentResource>>,
+    available: Vec<LanguageIdentifier>,
+    default: LanguageIdentifier,
+}
+
+impl Localizer {
+    /// `dir/<언어>/*.ftl`을 모두 읽어 번들 생성
+    ///
+    /// 문법 오류나 중복 메시지는 요청 처리 중이 아니라 시작할 때 바로 실패하게 함
+    pub fn load(dir: impl AsRef<Path>, default: LanguageIdentifier) -> io::Result<Self> {
+        let mut bundles = HashMap::new();
+
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let lang: LanguageIdentifier = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.parse().ok())
+                .ok_or_else(|| invalid(format!("invalid locale directory {}", path.display())))?;
+
+            let mut bundle = FluentBundle::new_concurrent(vec![lang.clone()]);
+            // 기본값은 변수 앞뒤에 방향 표시 문자(U+2068, U+2069)를 넣음 → 예제에서는 끔
+            bundle.set_use_isolating(false);
+
+            let mut files: Vec<_> = fs::read_dir(&path)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<_, _>>()?;
+            files.sort();
+            for file in files
+                .iter()
+                .filter(|file| file.extension().is_some_and(|ext| ext == "ftl"))
+            {
+                let source = fs::read_to_string(file)?;
+                let resource = FluentResource::try_new(source)
+                    .map_err(|(_, errors)| invalid(format!("{}: {errors:?}", file.display())))?;
+                bundle
+                    .add_resource(resource)
+                    .map_err(|errors| invalid(format!("{}: {errors:?}", file.display())))?;
+            }
+
+            bundles.insert(lang, bundle);
+        }
+
+        if !bundles.contains_key(&default) {
+            return Err(invalid(format!(
+                "missing bundle for default locale {default}"
+            )));
+        }
+
+        let mut available: Vec<_> = bundles.keys().cloned().collect();
+        available.sort_by_key(|lang| lang.to_string());
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                bundles,
+                available,
+                default,
+            }),
+        })
+    }
+
+    pub fn available(&self) -> &[LanguageIdentifier] {
+        &self.inner.available
+    }
+
+    pub fn default_lang(&self) -> &LanguageIdentifier {
+        &self.inner.default
+    }
+
+    /// 요청 언어 목록(선호 순) 중 지원하는 첫 언어
+    pub fn negotiate(&self, requested: &[LanguageIdentifier]) -> Option<LanguageIdentifier> {
+        negotiate_languages(
+            requested,
+            &self.inner.available,
+            None,
+            NegotiationStrategy::Lookup,
+        )
+        .first()
+        .map(|lang| (*lang).clone())
+    }
+
+    /// 🔤 메시지 번역
+    ///
+    /// 해당 언어에 메시지가 없으면 기본 언어, 그래도 없으면 메시지 ID를 그대로 반환
+    pub fn message(
+        &self,
+        lang: &LanguageIdentifier,
+        id: &str,
+        args: Option<&FluentArgs>,
+    ) -> String {
+        for lang in [lang, &self.inner.default] {
+            let Some(bundle) = self.inner.bundles.get(lang) else {
+                continue;
+            };
+            let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) else {
+                continue;
+            };
+
+            let mut errors = Vec::new();
+            let text = bundle.format_pattern(pattern, args, &mut errors);
+            if !errors.is_empty() {
+                tracing::warn!(%lang, id, ?errors, "failed to format message");
+            }
+            return text.into_owned();
+        }
+
+        tracing::warn!(%lang, id, "missing message");
+        id.to_owned()
+    }
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// 🧭 요청 언어 추출기
+///
+/// 지원하지 않는 언어만 요청해도 실패하지 않고 기본 언어를 사용
+#[derive(Clone)]
+pub struct Locale {
+    pub lang: LanguageIdentifier,
+    localizer: Localizer,
+}
+
+impl Locale {
+    pub fn new(lang: LanguageIdentifier, localizer: Localizer) -> Self {
+        Self { lang, localizer }
+    }
+
+    /// 요청 언어로 메시지 번역
+    pub fn t(&self, id: &str, args: &FluentArgs) -> String {
+        self.localizer.message(&self.lang, id, Some(args))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LangQuery {
+    lang: Option<String>,
+}
+
+impl<S> FromRequestParts<S> for Locale
+where
+    Localizer: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let localizer = Localizer::from_ref(state);
+        let lang = resolve(&localizer, &parts.uri, &parts.headers);
+        Ok(Self::new(lang, localizer))
+    }
+}
+
+fn resolve(localizer: &Localizer, uri: &Uri, headers: &HeaderMap) -> LanguageIdentifier {
+    if let Some(lang) = query_lang(localizer, uri) {
+        return lang;
+    }
+
+    let jar = CookieJar::from_headers(headers);
+    let cookie_lang = jar
+        .get(LANG_COOKIE)
+        .and_then(|cookie| cookie.value().parse().ok())
+        .and_then(|lang| localizer.negotiate(&[lang]));
+    if let Some(lang) = cookie_lang {
+        return lang;
+    }
+
+    let accepted = headers
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .map(parse_accept_language)
+        .unwrap_or_default();
+    localizer
+        .negotiate(&accepted)
+        .unwrap_or_else(|| localizer.default_lang().clone())
+}
+
+// `?lang=` 값 중 지원하는 언어 (쿼리 형식이 잘못돼도 요청을 거절하지 않고 무시)
+fn query_lang(localizer: &Localizer, uri: &Uri) -> Option<LanguageIdentifier> {
+    let Query(query) = Query::<LangQuery>::try_from_uri(uri).ok()?;
+    let lang = query.lang?.parse().ok()?;
+    localizer.negotiate(&[lang])
+}
+
+/// `Accept-Language: ko-KR,ko;q=0.9,en;q=0.8,*;q=0.1` → q 값이 높은 순서의 언어 목록
+///
+/// `*`, q=0(거부), 형식이 잘못된 항목은 건너뜀
+pub fn parse_accept_language(header: &str) -> Vec<LanguageIdentifier> {
+    let mut entries: Vec<(LanguageIdentifier, f32)> = header
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split(';');
+            let tag = parts.next()?.trim();
+            let q = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+            if tag == "*" || q <= 0.0 {
+                return None;
+            }
+            Some((tag.parse().ok()?, q))
+        })
+        .collect();
+
+    // 정렬이 안정적이므로 q 값이 같으면 헤더에 적힌 순서 유지
+    entries.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    entries.into_iter().map(|(lang, _)| lang).collect()
+}
+
+/// 🍪 `?lang=`으로 고른 언어를 쿠키에 저장 → 이후 요청은 쿼리 없이도 같은 언어
+pub async fn persist_lang(
+    State(localizer): State<Localizer>,
+    jar: CookieJar,
+    request: Request,
+    next: Next,
+) -> (CookieJar, Response) {
+    let chosen = query_lang(&localizer, request.uri());
+    let response = next.run(request).await;
+
+    let jar = match chosen {
+        Some(lang) => jar.add(
+            Cookie::build((LANG_COOKIE, lang.to_string()))
+                .path("/")
+                .max_age(time::Duration::days(365))
+                .same_site(SameSite::Lax)
+                .build(),
+        ),
+        None => jar,
+    };
+    (jar, response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn langs(values: &[&str]) -> Vec<LanguageIdentifier> {
+        values.iter().map(|value| value.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn accept_language_is_sorted_by_quality() {
+        assert_eq!(
+            parse_accept_language("en;q=0.5, ko-KR, fr;q=0.8, *;q=0.1"),
+            langs(&["ko-KR", "fr", "en"])
+        );
+        assert_eq!(
+            parse_accept_language("de;q=0, ko;q=bad, en"),
+            langs(&["en"])
+        );
+        assert!(parse_accept_language("").is_empty());
+    }
+}
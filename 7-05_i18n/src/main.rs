@@ -0,0 +1,438 @@
+//! Fluent 메시지 번들로 다국어(i18n) 페이지를 렌더링하는 예제입니다.
+//!
+//! - 시작할 때 `locales/<언어>/*.ftl`을 모두 읽어 번들 생성 (오류가 있으면 바로 실패)
+//! - `Locale` 추출기가 `?lang=` → `lang` 쿠키 → `Accept-Language` → 기본 언어(en-US) 순서로 언어 결정
+//! - `?lang=`으로 바꾼 언어는 쿠키에 저장되어 다음 요청에도 유지
+//! - 템플릿에서는 `t("메시지 ID", 변수=값)`, 검증 에러 메시지도 같은 번들에서 번역
+//!
+//! ```not_rust
+//! cargo run -p example-i18n
+//! ```
+//!
+//! ```not_rust
+//! curl -H 'Accept-Language: ko-KR,ko;q=0.9,en;q=0.8' http://127.0.0.1:3000/
+//! curl -i 'http://127.0.0.1:3000/?lang=ko'
+//! curl -H 'Accept-Language: ko' -d 'name=&age=200' http://127.0.0.1:3000/signup
+//! ```
+
+use axum::{
+    extract::{FromRef, Query, State},
+    http::{
+        header::{CONTENT_LANGUAGE, VARY},
+        HeaderValue, StatusCode,
+    },
+    middleware,
+    response::{Html, IntoResponse, Response},
+    routing::{get, post},
+    Form, Router,
+};
+use fluent_bundle::{FluentArgs, FluentValue};
+use minijinja::{context, value::Kwargs, Environment, Value};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, sync::Arc};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+mod i18n;
+
+use i18n::{Locale, Localizer};
+
+const LOCALES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/locales");
+
+// 🏁 main()
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let state = AppState::load().expect("failed to load locales");
+    tracing::debug!("available locales: {:?}", state.localizer.available());
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(state)).await.unwrap();
+}
+
+// 📦 앱 상태 (Locale 추출기는 FromRef로 Localizer를 꺼내 씀)
+#[derive(Clone, FromRef)]
+struct AppState {
+    localizer: Localizer,
+    templates: Arc<Environment<'static>>,
+}
+
+impl AppState {
+    fn load() -> std::io::Result<Self> {
+        let localizer = Localizer::load(LOCALES_DIR, "en-US".parse().unwrap())?;
+        let templates = Arc::new(templates(localizer.clone()));
+        Ok(Self {
+            localizer,
+            templates,
+        })
+    }
+}
+
+fn app(state: AppState) -> Router {
+    Router::new()
+        .route("/", get(index))
+        .route("/signup", post(signup))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            i18n::persist_lang,
+        ))
+        .with_state(state)
+}
+
+// 🧩 템플릿 + 번역 함수 `t`
+// 현재 언어는 렌더링 context의 `lang` 변수에서 읽음 → 요청마다 환경을 새로 만들 필요 없음
+fn templates(localizer: Localizer) -> Environment<'static> {
+    let mut env = Environment::new();
+    env.add_template("index.html", include_str!("../templates/index.html"))
+        .unwrap();
+
+    env.add_function(
+        "t",
+        move |state: &minijinja::State, id: &str, kwargs: Kwargs| {
+            let lang = state
+                .lookup("lang")
+                .and_then(|lang| lang.as_str()?.parse().ok())
+                .unwrap_or_else(|| localizer.default_lang().clone());
+
+            let mut args = FluentArgs::new();
+            for name in kwargs.args() {
+                let value: Value = kwargs.get(name)?;
+                args.set(name.to_owned(), fluent_value(&value));
+            }
+            kwargs.assert_all_used()?;
+
+            Ok::<_, minijinja::Error>(localizer.message(&lang, id, Some(&args)))
+        },
+    );
+    env
+}
+
+// 숫자는 숫자로 넘겨야 Fluent가 복수형(one/other)을 고를 수 있음
+fn fluent_value(value: &Value) -> FluentValue<'static> {
+    match value.as_i64() {
+        Some(number) => FluentValue::from(number),
+        None => FluentValue::from(value.to_string()),
+    }
+}
+
+// 🚏 핸들러
+
+#[derive(Debug, Deserialize)]
+struct IndexQuery {
+    name: Option<String>,
+    unread: Option<u32>,
+}
+
+async fn index(
+    locale: Locale,
+    State(state): State<AppState>,
+    Query(query): Query<IndexQuery>,
+) -> Result<Response, AppError> {
+    let page = Page {
+        name: query.name.unwrap_or_else(|| "Ferris".to_owned()),
+        unread: query.unread.unwrap_or(1),
+        ..Default::default()
+    };
+    render(&state, &locale, StatusCode::OK, page)
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+struct SignupForm {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    age: String,
+}
+
+async fn signup(
+    locale: Locale,
+    State(state): State<AppState>,
+    Form(form): Form<SignupForm>,
+) -> Result<Response, AppError> {
+    let mut page = Page {
+        name: "Ferris".to_owned(),
+        unread: 1,
+        ..Default::default()
+    };
+
+    match validate(&form) {
+        Ok(name) => {
+            let mut args = FluentArgs::new();
+            args.set("name", name);
+            page.success = Some(locale.t("signup-success", &args));
+            render(&state, &locale, StatusCode::OK, page)
+        }
+        Err(errors) => {
+            // 에러 종류는 언어와 무관하게 판단하고, 메시지만 요청 언어로 번역
+            page.errors = errors
+                .into_iter()
+                .map(|(field, error)| (field, error.localize(&locale)))
+                .collect();
+            page.form = form;
+            render(&state, &locale, StatusCode::UNPROCESSABLE_ENTITY, page)
+        }
+    }
+}
+
+// ✅ 검증 (메시지 ID와 인자만 결정)
+
+const NAME_MAX: usize = 20;
+const AGE_RANGE: (u32, u32) = (14, 120);
+
+#[derive(Debug, PartialEq, Eq)]
+enum FieldError {
+    NameRequired,
+    NameTooLong { max: usize },
+    AgeInvalid,
+    AgeRange { min: u32, max: u32 },
+}
+
+impl FieldError {
+    fn localize(&self, locale: &Locale) -> String {
+        let mut args = FluentArgs::new();
+        let id = match *self {
+            Self::NameRequired => "error-name-required",
+            Self::NameTooLong { max } => {
+                args.set("max", max);
+                "error-name-too-long"
+            }
+            Self::AgeInvalid => "error-age-invalid",
+            Self::AgeRange { min, max } => {
+                args.set("min", min);
+                args.set("max", max);
+                "error-age-range"
+            }
+        };
+        locale.t(id, &args)
+    }
+}
+
+fn validate(form: &SignupForm) -> Result<String, BTreeMap<&'static str, FieldError>> {
+    let mut errors = BTreeMap::new();
+
+    let name = form.name.trim();
+    if name.is_empty() {
+        errors.insert("name", FieldError::NameRequired);
+    } else if name.chars().count() > NAME_MAX {
+        errors.insert("name", FieldError::NameTooLong { max: NAME_MAX });
+    }
+
+    let (min, max) = AGE_RANGE;
+    match form.age.trim().parse::<u32>() {
+        Ok(age) if (min..=max).contains(&age) => {}
+        Ok(_) => {
+            errors.insert("age", FieldError::AgeRange { min, max });
+        }
+        Err(_) => {
+            errors.insert("age", FieldError::AgeInvalid);
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(name.to_owned())
+    } else {
+        Err(errors)
+    }
+}
+
+// 🖼️ 렌더링
+
+#[derive(Debug, Default)]
+struct Page {
+    name: String,
+    unread: u32,
+    form: SignupForm,
+    errors: BTreeMap<&'static str, String>,
+    success: Option<String>,
+}
+
+fn render(
+    state: &AppState,
+    locale: &Locale,
+    status: StatusCode,
+    page: Page,
+) -> Result<Response, AppError> {
+    let languages: Vec<String> = state
+        .localizer
+        .available()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let html = state
+        .templates
+        .get_template("index.html")?
+        .render(context! {
+            lang => locale.lang.to_string(),
+            languages,
+            name => page.name,
+            unread => page.unread,
+            form => page.form,
+            errors => page.errors,
+            success => page.success,
+        })?;
+
+    // 응답 언어를 알리고, 언어를 고르는 데 쓴 헤더를 Vary로 표시 (캐시가 언어를 섞지 않도록)
+    let content_language = HeaderValue::from_str(&locale.lang.to_string()).unwrap();
+    Ok((
+        status,
+        [
+            (CONTENT_LANGUAGE, content_language),
+            (VARY, HeaderValue::from_static("Accept-Language, Cookie")),
+        ],
+        Html(html),
+    )
+        .into_response())
+}
+
+// ❌ 에러 응답
+
+#[derive(Debug)]
+struct AppError(minijinja::Error);
+
+impl From<minijinja::Error> for AppError {
+    fn from(err: minijinja::Error) -> Self {
+        Self(err)
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        tracing::error!("template error: {:#}", self.0);
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    }
+}
+
+// 🧪 테스트
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header, Request},
+    };
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    async fn send(request: Request<Body>) -> (StatusCode, header::HeaderMap, String) {
+        let app = app(AppState::load().unwrap());
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, headers, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    fn get(uri: &str, accept_language: Option<&str>) -> Request<Body> {
+        let mut request = Request::get(uri);
+        if let Some(value) = accept_language {
+            request = request.header(header::ACCEPT_LANGUAGE, value);
+        }
+        request.body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn negotiates_from_accept_language() {
+        let (_, headers, body) = send(get("/", Some("fr;q=0.9, ko;q=0.8, en;q=0.5"))).await;
+
+        assert_eq!(headers[header::CONTENT_LANGUAGE], "ko-KR");
+        assert!(body.contains(r#"<html lang="ko-KR">"#));
+        assert!(body.contains("안녕하세요, Ferris님!"));
+        // ko-KR 번들에 없는 메시지는 기본 언어로 대체
+        assert!(body.contains("<footer>Built with axum and Fluent.</footer>"));
+    }
+
+    #[tokio::test]
+    async fn unsupported_language_falls_back_to_default() {
+        let (_, headers, body) = send(get("/", Some("de-DE, fr"))).await;
+        assert_eq!(headers[header::CONTENT_LANGUAGE], "en-US");
+        assert!(body.contains("Hello, Ferris!"));
+
+        let (_, headers, _) = send(get("/", None)).await;
+        assert_eq!(headers[header::CONTENT_LANGUAGE], "en-US");
+    }
+
+    #[tokio::test]
+    async fn query_override_is_persisted_in_cookie() {
+        let (_, headers, body) = send(get("/?lang=ko", Some("en-US"))).await;
+        assert!(body.contains("안녕하세요"));
+        let cookie = headers[header::SET_COOKIE].to_str().unwrap();
+        assert!(cookie.starts_with("lang=ko-KR;"));
+
+        // 쿠키가 Accept-Language보다 우선
+        let request = Request::get("/")
+            .header(header::ACCEPT_LANGUAGE, "en-US")
+            .header(header::COOKIE, "lang=ko-KR")
+            .body(Body::empty())
+            .unwrap();
+        let (_, headers, _) = send(request).await;
+        assert_eq!(headers[header::CONTENT_LANGUAGE], "ko-KR");
+        assert!(!headers.contains_key(header::SET_COOKIE));
+
+        // 지원하지 않는 언어로는 쿠키를 바꾸지 않음
+        let (_, headers, _) = send(get("/?lang=xx", None)).await;
+        assert!(!headers.contains_key(header::SET_COOKIE));
+    }
+
+    #[tokio::test]
+    async fn plural_forms_follow_the_count() {
+        let (_, _, body) = send(get("/?unread=1", Some("en"))).await;
+        assert!(body.contains("You have one unread message."));
+
+        let (_, _, body) = send(get("/?unread=3", Some("en"))).await;
+        assert!(body.contains("You have 3 unread messages."));
+
+        let (_, _, body) = send(get("/?unread=1", Some("ko"))).await;
+        assert!(body.contains("읽지 않은 메시지가 1개 있습니다."));
+    }
+
+    #[tokio::test]
+    async fn validation_errors_are_localized() {
+        let request = Request::post("/signup")
+            .header(header::ACCEPT_LANGUAGE, "ko-KR")
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from("name=&age=200"))
+            .unwrap();
+        let (status, _, body) = send(request).await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(body.contains("이름을 입력하세요."));
+        assert!(body.contains("나이는 14세에서 120세 사이여야 합니다."));
+        assert!(body.contains(r#"value="200""#));
+    }
+
+    #[tokio::test]
+    async fn signup_success_escapes_user_input() {
+        let request = Request::post("/signup?lang=en-US")
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from("name=%3Cb%3EAnn%3C%2Fb%3E&age=30"))
+            .unwrap();
+        let (status, _, body) = send(request).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("Thanks, &lt;b&gt;Ann&lt;&#x2f;b&gt;! You are now registered."));
+    }
+
+    #[test]
+    fn validation_rules() {
+        let form = |name: &str, age: &str| SignupForm {
+            name: name.to_owned(),
+            age: age.to_owned(),
+        };
+
+        assert_eq!(validate(&form(" Ann ", "30")), Ok("Ann".to_owned()));
+        let errors = validate(&form(&"a".repeat(21), "abc")).unwrap_err();
+        assert_eq!(errors["name"], FieldError::NameTooLong { max: 20 });
+        assert_eq!(errors["age"], FieldError::AgeInvalid);
+    }
+}
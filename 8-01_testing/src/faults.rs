@@ -0,0 +1,32 @@
+//! 💥 장애 주입(fault injection) 스위치
+//!
+//! 정상 경로만으로는 만들기 어려운 에러 응답(DB 장애 → 500 등)을 테스트하기 위한 플래그
+//!
+//! - 핸들러는 저장소를 쓰기 전에 `check_db()`를 호출 → 플래그가 켜져 있으면 DB가 죽은 것처럼 실패
+//! - 테스트는 `state.faults`로 플래그를 켜고 응답 상태 코드/본문/로그를 검사
+
+use example_common_errors::ApiError;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[derive(Debug, Default)]
+pub struct Faults {
+    /// 켜면 저장소를 쓰는 모든 요청이 실패
+    pub fail_db: AtomicBool,
+}
+
+impl Faults {
+    pub fn check_db(&self) -> Result<(), ApiError> {
+        if self.fail_db.load(Ordering::Relaxed) {
+            // 실제 DB 드라이버 에러 대신 쓰는 값 (내용은 로그에만 남고 응답에는 숨겨짐)
+            return Err(ApiError::internal(
+                "database unavailable: connection refused (injected fault)",
+            ));
+        }
+        Ok(())
+    }
+
+    #[cfg(test)]
+    pub fn set_fail_db(&self, fail: bool) {
+        self.fail_db.store(fail, Ordering::Relaxed);
+    }
+}
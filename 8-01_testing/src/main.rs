@@ -16,7 +16,16 @@ use axum::{
 use tower_http::trace::TraceLayer; // TraceLayer: 요청 로그 추적용 미들웨어.
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-/// --- 🔧 main()
+mod faults;
+mod users;
+
+// 테스트 전용 도구 (서버 실행 + HTTP 클라이언트 + 픽스처)
+#[cfg(test)]
+mod test_support;
+
+use users::AppState;
+
+// --- 🔧 main()
 
 #[tokio::main] // #[tokio::main] → 서버 실행 엔트리 포인트
 async fn main() {
@@ -47,6 +56,11 @@ async fn main() {
 /// Having a function that produces our app makes it easy to call it from tests
 /// without having to create an HTTP server.
 fn app() -> Router {
+    app_with_state(AppState::default())
+}
+
+// 상태를 밖에서 받으면 테스트가 같은 상태를 들고 픽스처를 넣거나 결과를 검사할 수 있음
+fn app_with_state(state: AppState) -> Router {
     Router::new()
         // / 라우트는 GET 요청에 대해 “Hello, World!” 문자열 반환
         .route("/", get(|| async { "Hello, World!" }))
@@ -62,11 +76,14 @@ fn app() -> Router {
             "/requires-connect-info",
             get(|ConnectInfo(addr): ConnectInfo<SocketAddr>| async move { format!("Hi {addr}") }),
         )
+        // /users: 상태를 사용하는 사용자 API
+        .merge(users::router())
         // 요청 추적용 미들웨어 적용
         .layer(TraceLayer::new_for_http())
+        .with_state(state)
 }
 
-/// --- 🧪 테스트 모듈
+// --- 🧪 테스트 모듈
 
 #[cfg(test)]
 mod tests {
@@ -226,4 +243,256 @@ mod tests {
         let response = app.ready().await.unwrap().call(request).await.unwrap();
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    /// 7. test_app_*(): 공용 테스트 도구(`test_support::TestApp`) 사용
+    // 서버 실행, URL 조합, JSON 파싱을 TestApp이 맡으므로 테스트에는 검증 내용만 남음
+    mod with_test_app {
+        use crate::{
+            test_support::{fixtures, TestApp},
+            users::User,
+        };
+        use reqwest::StatusCode;
+        use serde_json::{json, Value};
+
+        #[tokio::test]
+        async fn seeded_users_are_listed() {
+            let app = TestApp::spawn().await;
+            app.seed(fixtures::users());
+
+            let (status, users): (_, Vec<User>) = app.get_json("/users").await;
+
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(users, fixtures::users());
+        }
+
+        #[tokio::test]
+        async fn created_user_can_be_fetched() {
+            let app = TestApp::spawn().await;
+
+            let (status, created): (_, User) = app
+                .post_json(
+                    "/users",
+                    &json!({ "name": "Carol", "email": "carol@example.com" }),
+                )
+                .await;
+            assert_eq!(status, StatusCode::CREATED);
+            assert_eq!(created.name, "Carol");
+
+            let (status, fetched): (_, User) =
+                app.get_json(&format!("/users/{}", created.id)).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(fetched, created);
+
+            // 서버와 같은 상태를 들고 있으므로 직접 확인도 가능
+            assert_eq!(app.state.get(created.id), Some(created));
+        }
+
+        #[tokio::test]
+        async fn reset_clears_state() {
+            let app = TestApp::spawn().await;
+            app.seed([fixtures::alice()]);
+
+            app.reset();
+
+            let (_, users): (_, Vec<Value>) = app.get_json("/users").await;
+            assert!(users.is_empty());
+            let response = app.get(&format!("/users/{}", fixtures::alice().id)).await;
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+
+        #[tokio::test]
+        async fn each_test_app_has_its_own_state() {
+            let first = TestApp::spawn().await;
+            let second = TestApp::spawn().await;
+            first.seed(fixtures::users());
+
+            let (_, users): (_, Vec<User>) = second.get_json("/users").await;
+            assert!(users.is_empty());
+            assert_ne!(first.addr, second.addr);
+        }
+
+        #[tokio::test]
+        async fn real_server_provides_connect_info() {
+            let app = TestApp::spawn().await;
+
+            let body = app
+                .get("/requires-connect-info")
+                .await
+                .text()
+                .await
+                .unwrap();
+            assert!(body.starts_with("Hi 127.0.0.1:"), "{body}");
+        }
+    }
+
+    /// 8. snapshots(): insta로 응답 전체 모양을 스냅샷과 비교
+    // 필드 하나하나 assert하면 새 필드 추가/이름 변경/HTML 구조 변경을 놓치기 쉬움
+    // 스냅샷은 `src/snapshots/*.snap`에 저장되고, 달라지면 diff와 함께 실패
+    mod snapshots {
+        use crate::{
+            test_support::{fixtures, snapshots, TestApp},
+            users::User,
+        };
+        use serde_json::{json, Value};
+
+        #[tokio::test]
+        async fn created_user_json() {
+            let _guard = snapshots::redacted().bind_to_scope();
+            let app = TestApp::spawn().await;
+
+            let (status, user): (_, Value) = app
+                .post_json(
+                    "/users",
+                    &json!({ "name": "Carol", "email": "carol@example.com" }),
+                )
+                .await;
+
+            assert_eq!(status.as_u16(), 201);
+            insta::assert_json_snapshot!(user);
+        }
+
+        #[tokio::test]
+        async fn user_list_json() {
+            let _guard = snapshots::redacted().bind_to_scope();
+            let app = TestApp::spawn().await;
+            app.seed(fixtures::users());
+
+            let (_, users): (_, Value) = app.get_json("/users").await;
+
+            insta::assert_json_snapshot!(users);
+        }
+
+        #[tokio::test]
+        async fn profile_html() {
+            let _guard = snapshots::redacted().bind_to_scope();
+            let app = TestApp::spawn().await;
+            let (_, user): (_, User) = app
+                .post_json(
+                    "/users",
+                    &json!({ "name": "<Dave>", "email": "dave@example.com" }),
+                )
+                .await;
+
+            let html = app
+                .get(&format!("/users/{}/profile", user.id))
+                .await
+                .text()
+                .await
+                .unwrap();
+
+            insta::assert_snapshot!(html);
+        }
+    }
+
+    /// 9. faults(): 장애 주입 플래그로 에러 경로 테스트
+    // `state.faults`를 켜면 핸들러가 DB 장애처럼 실패 → 500 응답, 에러 본문 형태, 로그를 검사
+    //
+    // `#[traced_test]`는 테스트 함수의 span 안에서 나온 로그만 `logs_contain`으로 보여줌
+    // → 서버를 spawn하면 다른 태스크의 로그라서 보이지 않으므로 `oneshot`으로 같은 태스크에서 실행
+    mod faults {
+        use crate::{app_with_state, test_support::fixtures, users::AppState};
+        use axum::{
+            body::Body,
+            http::{Method, Request, StatusCode},
+            Router,
+        };
+        use http_body_util::BodyExt;
+        use serde_json::{json, Value};
+        use tower::ServiceExt;
+        use tracing_test::traced_test;
+
+        async fn send(app: &Router, method: Method, uri: &str) -> (StatusCode, Vec<u8>) {
+            let request = Request::builder().method(method).uri(uri);
+            let request = if uri == "/users" {
+                request
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        r#"{"name":"Carol","email":"carol@example.com"}"#,
+                    ))
+            } else {
+                request.body(Body::empty())
+            };
+            let response = app.clone().oneshot(request.unwrap()).await.unwrap();
+            let status = response.status();
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            (status, body.to_vec())
+        }
+
+        fn failing_app() -> (AppState, Router) {
+            let state = AppState::default();
+            state.insert(fixtures::alice());
+            state.faults.set_fail_db(true);
+            (state.clone(), app_with_state(state))
+        }
+
+        #[tokio::test]
+        #[traced_test]
+        async fn db_failure_is_a_500_with_a_generic_error_body() {
+            let (_, app) = failing_app();
+
+            let (status, body) = send(&app, Method::GET, "/users").await;
+
+            assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+            let body: Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(
+                body,
+                json!({ "error": { "code": "internal", "message": "Something went wrong" } })
+            );
+
+            // 원인은 응답에는 없고 로그에만 남음
+            assert!(!body.to_string().contains("connection refused"));
+            assert!(logs_contain("internal error"));
+            assert!(logs_contain(
+                "database unavailable: connection refused (injected fault)"
+            ));
+            // TraceLayer도 5xx 응답을 실패로 기록
+            assert!(logs_contain("response failed"));
+        }
+
+        #[tokio::test]
+        async fn every_db_backed_route_fails_and_recovers() {
+            let (state, app) = failing_app();
+            let alice = format!("/users/{}", fixtures::alice().id);
+            let profile = format!("{alice}/profile");
+            let routes = [
+                (Method::GET, "/users"),
+                (Method::POST, "/users"),
+                (Method::GET, alice.as_str()),
+                (Method::GET, profile.as_str()),
+            ];
+
+            for (method, uri) in routes.clone() {
+                let (status, _) = send(&app, method.clone(), uri).await;
+                assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR, "{method} {uri}");
+            }
+            // DB를 쓰지 않는 라우트는 영향 없음
+            assert_eq!(send(&app, Method::GET, "/").await.0, StatusCode::OK);
+            // 실패한 POST는 아무것도 저장하지 않음
+            assert_eq!(state.list(), vec![fixtures::alice()]);
+
+            state.faults.set_fail_db(false);
+            for (method, uri) in routes {
+                let (status, _) = send(&app, method.clone(), uri).await;
+                assert!(status.is_success(), "{method} {uri}: {status}");
+            }
+        }
+
+        #[tokio::test]
+        #[traced_test]
+        async fn client_errors_have_the_same_body_shape_and_are_not_logged_as_errors() {
+            let app = app_with_state(AppState::default());
+            let id = fixtures::bob().id;
+
+            let (status, body) = send(&app, Method::GET, &format!("/users/{id}")).await;
+
+            assert_eq!(status, StatusCode::NOT_FOUND);
+            let body: Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(
+                body,
+                json!({ "error": { "code": "not_found", "message": format!("user {id} not found") } })
+            );
+            assert!(!logs_contain("internal error"));
+            assert!(!logs_contain("response failed"));
+        }
+    }
 }
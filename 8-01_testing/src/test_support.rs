@@ -0,0 +1,190 @@
+//! 🧰 테스트 공용 도구
+//!
+//! 테스트마다 반복되던 서버 실행 / 클라이언트 생성 / 응답 파싱 코드를 한곳에 모음
+//!
+//! ```ignore
+//! let app = TestApp::spawn().await;
+//! app.seed(fixtures::users());
+//!
+//! let (status, users): (_, Vec<User>) = app.get_json("/users").await;
+//! ```
+//!
+//! - `TestApp::spawn()`은 빈 상태로 임의 포트(`127.0.0.1:0`)에 서버를 띄움 → 테스트끼리 상태/포트 공유 없음
+//! - `TestApp`이 drop되면 서버 태스크도 종료
+//! - 한 테스트 안에서 처음 상태로 돌아가야 하면 `reset()`
+
+use reqwest::{Client, Response, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
+use std::net::SocketAddr;
+use tokio::{net::TcpListener, task::JoinHandle};
+
+use crate::{app_with_state, users::AppState};
+
+pub struct TestApp {
+    pub addr: SocketAddr,
+    /// 서버와 같은 상태 (직접 검사하거나 픽스처를 넣을 때 사용)
+    pub state: AppState,
+    client: Client,
+    server: JoinHandle<()>,
+}
+
+impl TestApp {
+    /// 빈 상태로 서버 실행
+    pub async fn spawn() -> Self {
+        Self::spawn_with_state(AppState::default()).await
+    }
+
+    pub async fn spawn_with_state(state: AppState) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // main()과 같이 ConnectInfo를 제공하는 방식으로 실행 (MockConnectInfo 불필요)
+        let app = app_with_state(state.clone());
+        let server = tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Self {
+            addr,
+            state,
+            client: Client::new(),
+            server,
+        }
+    }
+
+    /// 경로 → 전체 URL (`/users` → `http://127.0.0.1:54321/users`)
+    pub fn url(&self, path: &str) -> String {
+        assert!(path.starts_with('/'), "path must start with `/`: {path}");
+        format!("http://{}{path}", self.addr)
+    }
+
+    /// 상태 코드/헤더/본문을 직접 확인하고 싶을 때 쓰는 원본 응답
+    pub async fn get(&self, path: &str) -> Response {
+        self.client.get(self.url(path)).send().await.unwrap()
+    }
+
+    pub async fn get_json<T: DeserializeOwned>(&self, path: &str) -> (StatusCode, T) {
+        json_response(self.get(path).await).await
+    }
+
+    pub async fn post_json<B, T>(&self, path: &str, body: &B) -> (StatusCode, T)
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let response = self
+            .client
+            .post(self.url(path))
+            .json(body)
+            .send()
+            .await
+            .unwrap();
+        json_response(response).await
+    }
+
+    /// 🌱 픽스처 데이터 넣기
+    pub fn seed(&self, users: impl IntoIterator<Item = crate::users::User>) {
+        for user in users {
+            self.state.insert(user);
+        }
+    }
+
+    /// 🧹 상태 초기화
+    pub fn reset(&self) {
+        self.state.clear();
+    }
+}
+
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+// JSON이 아니면 본문을 에러 메시지에 포함해 어떤 응답이었는지 바로 보이게 함
+async fn json_response<T: DeserializeOwned>(response: Response) -> (StatusCode, T) {
+    let status = response.status();
+    let body = response.text().await.unwrap();
+    let value = serde_json::from_str(&body)
+        .unwrap_or_else(|err| panic!("invalid JSON response ({status}): {err}\n{body}"));
+    (status, value)
+}
+
+/// 📋 고정된 테스트 데이터 (ID와 시각이 항상 같아 비교하기 쉬움)
+pub mod fixtures {
+    use crate::users::User;
+    use uuid::Uuid;
+
+    pub fn alice() -> User {
+        User {
+            id: Uuid::from_u128(1),
+            name: "Alice".to_owned(),
+            email: "alice@example.com".to_owned(),
+            created_at: 1_700_000_000,
+        }
+    }
+
+    pub fn bob() -> User {
+        User {
+            id: Uuid::from_u128(2),
+            name: "Bob".to_owned(),
+            email: "bob@example.com".to_owned(),
+            created_at: 1_700_000_100,
+        }
+    }
+
+    pub fn users() -> Vec<User> {
+        vec![alice(), bob()]
+    }
+}
+
+/// 📸 스냅샷 테스트 설정 (insta)
+///
+/// 실행할 때마다 바뀌는 값(UUID, 시각)을 고정된 문자열로 바꿔 스냅샷이 매번 달라지지 않게 함
+///
+/// ```ignore
+/// let _guard = snapshots::redacted().bind_to_scope();
+/// insta::assert_json_snapshot!(value);
+/// ```
+///
+/// 스냅샷이 없거나 달라지면 테스트가 실패하고 `*.snap.new`가 생김 → `cargo insta review`로 확인 후 승인
+pub mod snapshots {
+    use insta::{dynamic_redaction, Settings};
+
+    const UUID_PATTERN: &str = r"[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}";
+    // 2001년 ~ 2286년 사이의 Unix time (초)
+    const TIMESTAMP_PATTERN: &str = r"\b[1-9][0-9]{9}\b";
+
+    pub fn redacted() -> Settings {
+        let mut settings = Settings::clone_current();
+
+        // JSON/YAML 스냅샷: 필드 경로로 지정 (`.**`는 모든 깊이)
+        // 값이 올바른 형식인지는 확인하고 나서 가림 → 형식이 깨지면 스냅샷 대신 assert가 실패
+        settings.add_redaction(
+            ".**.id",
+            dynamic_redaction(|value, _path| {
+                let id = value.as_str().expect("id must be a string");
+                assert!(uuid::Uuid::parse_str(id).is_ok(), "not a UUID: {id}");
+                "[uuid]"
+            }),
+        );
+        settings.add_redaction(
+            ".**.created_at",
+            dynamic_redaction(|value, _path| {
+                assert!(value.as_u64().is_some(), "not a timestamp: {value:?}");
+                "[timestamp]"
+            }),
+        );
+
+        // 문자열 스냅샷(HTML 등): 정규식으로 치환
+        settings.add_filter(UUID_PATTERN, "[uuid]");
+        settings.add_filter(TIMESTAMP_PATTERN, "[timestamp]");
+
+        settings
+    }
+}
@@ -0,0 +1,153 @@
+//! 테스트 예제에서 사용할 간단한 사용자 API (메모리 저장소)
+//!
+//! - `GET /users`: 목록 (가입 시각 → 이름 순)
+//! - `POST /users`: 생성 (201 Created)
+//! - `GET /users/{id}`: 조회 (없으면 404)
+//! - `GET /users/{id}/profile`: HTML 프로필 페이지
+//!
+//! 에러는 `ApiError`(common-errors)의 JSON 형태, 저장소를 쓰기 전에 `faults.check_db()`로 장애 주입 확인
+
+use crate::faults::Faults;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Html,
+    routing::get,
+    Json, Router,
+};
+use example_common_errors::ApiError;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use uuid::Uuid;
+
+/// 📦 앱 상태 (테스트에서 픽스처를 넣거나 비울 수 있도록 메서드 제공)
+#[derive(Clone, Default)]
+pub struct AppState {
+    users: Arc<RwLock<HashMap<Uuid, User>>>,
+    /// 테스트에서 켜는 장애 플래그 (기본값은 모두 꺼짐)
+    pub faults: Arc<Faults>,
+}
+
+impl AppState {
+    pub fn insert(&self, user: User) {
+        self.users.write().unwrap().insert(user.id, user);
+    }
+
+    pub fn list(&self) -> Vec<User> {
+        let mut users: Vec<User> = self.users.read().unwrap().values().cloned().collect();
+        users.sort_by(|a, b| {
+            a.created_at
+                .cmp(&b.created_at)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        users
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<User> {
+        self.users.read().unwrap().get(&id).cloned()
+    }
+
+    // 테스트에서 상태를 초기화할 때만 사용
+    #[cfg(test)]
+    pub fn clear(&self) {
+        self.users.write().unwrap().clear();
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct User {
+    pub id: Uuid,
+    pub name: String,
+    pub email: String,
+    /// 가입 시각 (Unix time, 초)
+    pub created_at: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateUser {
+    name: String,
+    email: String,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/users", get(list_users).post(create_user))
+        .route("/users/{id}", get(get_user))
+        .route("/users/{id}/profile", get(user_profile))
+}
+
+async fn list_users(State(state): State<AppState>) -> Result<Json<Vec<User>>, ApiError> {
+    state.faults.check_db()?;
+    Ok(Json(state.list()))
+}
+
+async fn create_user(
+    State(state): State<AppState>,
+    Json(input): Json<CreateUser>,
+) -> Result<(StatusCode, Json<User>), ApiError> {
+    state.faults.check_db()?;
+    let user = User {
+        id: Uuid::new_v4(),
+        name: input.name,
+        email: input.email,
+        created_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+    };
+    state.insert(user.clone());
+    Ok((StatusCode::CREATED, Json(user)))
+}
+
+async fn get_user(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<User>, ApiError> {
+    find_user(&state, id).map(Json)
+}
+
+async fn user_profile(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Html<String>, ApiError> {
+    let user = find_user(&state, id)?;
+    Ok(Html(format!(
+        r#"<!doctype html>
+<html>
+  <head><title>{name}</title></head>
+  <body>
+    <h1>{name}</h1>
+    <dl>
+      <dt>ID</dt><dd>{id}</dd>
+      <dt>Email</dt><dd><a href="mailto:{email}">{email}</a></dd>
+      <dt>Joined</dt><dd><time>{created_at}</time></dd>
+    </dl>
+  </body>
+</html>
+"#,
+        name = escape(&user.name),
+        id = user.id,
+        email = escape(&user.email),
+        created_at = user.created_at,
+    )))
+}
+
+fn find_user(state: &AppState, id: Uuid) -> Result<User, ApiError> {
+    state.faults.check_db()?;
+    state
+        .get(id)
+        .ok_or_else(|| ApiError::NotFound(format!("user {id} not found")))
+}
+
+// 사용자 입력을 HTML에 넣기 전에 escape
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
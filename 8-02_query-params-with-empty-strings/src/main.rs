@@ -6,12 +6,17 @@
 //! ```
 //! 브라우저나 Postman 에서 GET으로..
 //! http://localhost:3000/?foo=&bar=bar
+//!
+//! 재사용 가능한 헬퍼는 `query_ext` 모듈에 있음 (쉼표 구분 목록, yes/no bool 등)
+//! http://localhost:3000/search?ids=1,2,3&tags=rust,axum&active=yes
+
+mod query_ext;
 
 use axum::{extract::Query, routing::get, Router}; // Query: Axum에서 쿼리 파라미터 추출용 추출기
-use serde::{de, Deserialize, Deserializer}; // serde 관련 항목은 구조체 필드의 커스텀 디시리얼라이저 작성에 필요
-use std::{fmt, str::FromStr};
+use query_ext::{comma_separated, empty_string_as_none, flexible_bool, CommaSeparated};
+use serde::Deserialize; // 구조체 필드에 커스텀 디시리얼라이저(query_ext)를 지정할 때 필요
 
-/// --- 🎯 메인 함수
+// --- 🎯 메인 함수
 
 #[tokio::main]
 async fn main() {
@@ -28,7 +33,9 @@ async fn main() {
 /// 🧭 라우터 구성
 fn app() -> Router {
     // / 경로에서 GET 요청 처리 → handler() 호출
-    Router::new().route("/", get(handler))
+    Router::new()
+        .route("/", get(handler))
+        .route("/search", get(search)) // query_ext 헬퍼 사용 예
 }
 
 /// 📦 요청 핸들러
@@ -36,7 +43,11 @@ async fn handler(Query(params): Query<Params>) -> String {
     format!("{params:?}")
 }
 
-/// --- 📐 구조체 정의 및 커스텀 디시리얼라이저 적용
+async fn search(Query(params): Query<SearchParams>) -> String {
+    format!("{params:?}")
+}
+
+// --- 📐 구조체 정의 및 커스텀 디시리얼라이저 적용
 
 /// See the tests below for which combinations of `foo` and `bar` result in
 /// which deserializations.
@@ -53,29 +64,28 @@ struct Params {
     bar: Option<String>, // bar는 일반적인 Option<String>으로 처리 (”“는 Some(””))로 유지
 }
 
-/// 🧰 커스텀 디시리얼라이저 함수
-/// Serde deserialization decorator to map empty Strings to None,
-fn empty_string_as_none<'de, D, T>(de: D) -> Result<Option<T>, D::Error>
-where
-    D: Deserializer<'de>,
-    T: FromStr,
-    T::Err: fmt::Display,
-{
-    // foo=&bar=bar → foo: None, bar: Some("bar")
-    // foo=1&bar=bar → foo: Some(1), bar: Some("bar")
-    // foo= → 빈 문자열 → None 처리됨
-    let opt = Option::<String>::deserialize(de)?;
-    match opt.as_deref() {
-        None | Some("") => Ok(None),
-        Some(s) => FromStr::from_str(s).map_err(de::Error::custom).map(Some),
-    }
+/// 🧰 query_ext 헬퍼를 조합한 파라미터
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct SearchParams {
+    #[serde(default, deserialize_with = "comma_separated")]
+    ids: Vec<i32>, // ?ids=1,2,3 → [1, 2, 3]
+    #[serde(default)]
+    tags: CommaSeparated<String>, // 래퍼 타입이라 deserialize_with 없이 사용
+    #[serde(default, deserialize_with = "flexible_bool")]
+    active: bool, // yes/no, 1/0, true/false, on/off
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    limit: Option<u32>,
 }
 
 /// ✅ 테스트 모듈
 #[cfg(test)]
 mod tests {
     use super::*;
-    use axum::{body::Body, http::Request};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
     use http_body_util::BodyExt;
     use tower::ServiceExt;
 
@@ -124,20 +134,159 @@ mod tests {
         );
     }
 
+    /// query_ext 헬퍼 조합 결과 검증
+    #[tokio::test]
+    async fn test_search() {
+        assert_eq!(
+            send_search("ids=1,2,3&tags=rust,axum&active=yes&limit=10").await,
+            (
+                StatusCode::OK,
+                r#"SearchParams { ids: [1, 2, 3], tags: CommaSeparated(["rust", "axum"]), active: true, limit: Some(10) }"#.to_owned()
+            ),
+        );
+
+        // 공백과 빈 항목은 무시
+        assert_eq!(
+            send_search("ids=1,%202,,3").await,
+            (
+                StatusCode::OK,
+                r#"SearchParams { ids: [1, 2, 3], tags: CommaSeparated([]), active: false, limit: None }"#.to_owned()
+            ),
+        );
+
+        // 빈 값 / 파라미터 없음 → 빈 목록, false, None
+        assert_eq!(
+            send_search("ids=&tags=&limit=").await,
+            (
+                StatusCode::OK,
+                r#"SearchParams { ids: [], tags: CommaSeparated([]), active: false, limit: None }"#
+                    .to_owned()
+            ),
+        );
+        assert_eq!(
+            send_search("").await,
+            (
+                StatusCode::OK,
+                r#"SearchParams { ids: [], tags: CommaSeparated([]), active: false, limit: None }"#
+                    .to_owned()
+            ),
+        );
+
+        // 숫자가 아닌 항목 → 400
+        let (status, body) = send_search("ids=1,two,3").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.contains("invalid list item `two`"), "{body}");
+    }
+
+    /// bool 표기 조합
+    #[tokio::test]
+    async fn test_flexible_bool() {
+        for (value, expected) in [
+            ("yes", true),
+            ("YES", true),
+            ("1", true),
+            ("true", true),
+            ("on", true),
+            ("no", false),
+            ("0", false),
+            ("false", false),
+            ("Off", false),
+        ] {
+            let (status, body) = send_search(&format!("active={value}")).await;
+            assert_eq!(status, StatusCode::OK, "{value}");
+            assert!(
+                body.contains(&format!("active: {expected}")),
+                "{value}: {body}"
+            );
+        }
+
+        let (status, _) = send_search("active=maybe").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    /// 🎲 임의의 쿼리 문자열 → 패닉/5xx 없이 200 또는 Query 리젝션(400, text/plain)
+    mod fuzz {
+        use super::*;
+        use proptest::prelude::*;
+
+        // 알려진 키/아무 키 + 빈 값, 퍼센트 인코딩(잘못된 것 포함), 쉼표, 공백 등을 섞은 쿼리
+        const QUERY: &str =
+            "((foo|bar|ids|tags|active|limit|[a-z]{1,3})(=([a-zA-Z0-9,+-]|%[0-9A-Fa-f]{2}|%)*)?&?){0,6}";
+
+        fn block_on<F: std::future::Future>(future: F) -> F::Output {
+            tokio::runtime::Builder::new_current_thread()
+                .build()
+                .unwrap()
+                .block_on(future)
+        }
+
+        fn assert_well_formed(uri: &str, debug_prefix: &str) -> Result<(), TestCaseError> {
+            prop_assume!(uri.parse::<axum::http::Uri>().is_ok());
+
+            let response =
+                block_on(app().oneshot(Request::get(uri).body(Body::empty()).unwrap())).unwrap();
+            let status = response.status();
+            let content_type = response.headers().get("content-type").cloned();
+            let body = block_on(response.into_body().collect()).unwrap().to_bytes();
+            let body = String::from_utf8(body.to_vec()).unwrap();
+
+            match status {
+                StatusCode::OK => prop_assert!(body.starts_with(debug_prefix), "{body}"),
+                StatusCode::BAD_REQUEST => {
+                    prop_assert_eq!(content_type.unwrap(), "text/plain; charset=utf-8");
+                    prop_assert!(
+                        body.starts_with("Failed to deserialize query string"),
+                        "{body}"
+                    );
+                }
+                status => prop_assert!(false, "unexpected status {status}: {body}"),
+            }
+            Ok(())
+        }
+
+        proptest! {
+            #[test]
+            fn arbitrary_queries(query in QUERY) {
+                assert_well_formed(&format!("/?{query}"), "Params {")?;
+                assert_well_formed(&format!("/search?{query}"), "SearchParams {")?;
+            }
+
+            /// 공백과 빈 항목을 섞은 숫자 목록은 항상 원래 숫자 목록으로
+            #[test]
+            fn comma_separated_ignores_padding(
+                items in prop::collection::vec((any::<i32>(), "(\\+|%20){0,2}", ",{0,2}"), 0..8),
+            ) {
+                let ids: Vec<String> = items
+                    .iter()
+                    .map(|(id, padding, empty)| format!("{padding}{id}{padding}{empty}"))
+                    .collect();
+                let expected: Vec<i32> = items.iter().map(|(id, ..)| *id).collect();
+
+                let (status, body) = block_on(send_search(&format!("ids={}", ids.join(","))));
+                prop_assert_eq!(status, StatusCode::OK);
+                let expected = format!("SearchParams {{ ids: {expected:?},");
+                prop_assert!(body.starts_with(&expected), "{body}");
+            }
+        }
+    }
+
     /// test_something() 에서 호출되는 함수.
     async fn send_request_get_body(query: &str) -> String {
-        let body = app()
-            .oneshot(
-                Request::builder()
-                    .uri(format!("/?{query}"))
-                    .body(Body::empty())
-                    .unwrap(),
-            )
+        send_request(&format!("/?{query}")).await.1
+    }
+
+    async fn send_search(query: &str) -> (StatusCode, String) {
+        send_request(&format!("/search?{query}")).await
+    }
+
+    async fn send_request(uri: &str) -> (StatusCode, String) {
+        let response = app()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
             .await
-            .unwrap()
-            .into_body();
-        let bytes = body.collect().await.unwrap().to_bytes();
-        String::from_utf8(bytes.to_vec()).unwrap()
+            .unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
     }
 }
 
@@ -152,3 +301,12 @@ mod tests {
 // foo=             None       None
 // bar=             None       Some("")
 // (빈 쿼리)          None       None
+//
+// /search (query_ext)
+// 요청 쿼리                 결과
+// ids=1,2,3               ids: [1, 2, 3]
+// ids=1,%202,,3           ids: [1, 2, 3] (공백/빈 항목 무시)
+// ids=1,two               400 Bad Request
+// active=yes / 1 / on     active: true
+// active=no / 0 / off     active: false
+// (파라미터 없음)            ids: [], tags: [], active: false, limit: None
@@ -0,0 +1,119 @@
+//! 쿼리 파라미터용 serde 헬퍼 모음
+//!
+//! - `empty_string_as_none` : `?foo=` 처럼 빈 값이면 None
+//! - `comma_separated` : `?ids=1,2,3` → `vec![1, 2, 3]`
+//! - `flexible_bool` : `yes/no`, `1/0`, `true/false`, `on/off` → bool
+//! - `CommaSeparated<T>` : `deserialize_with` 없이 필드 타입만으로 쉼표 목록을 받는 래퍼
+//!
+//! 모두 `#[serde(default, deserialize_with = "...")]`와 함께 쓰는 것을 가정함
+//! (`default`가 없으면 파라미터가 아예 없을 때 missing field 에러가 남)
+
+use serde::{de, Deserialize, Deserializer};
+use std::{fmt, ops::Deref, str::FromStr};
+
+/// Serde deserialization decorator to map empty Strings to None,
+pub fn empty_string_as_none<'de, D, T>(de: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    // foo=&bar=bar → foo: None, bar: Some("bar")
+    // foo=1&bar=bar → foo: Some(1), bar: Some("bar")
+    // foo= → 빈 문자열 → None 처리됨
+    let opt = Option::<String>::deserialize(de)?;
+    match opt.as_deref() {
+        None | Some("") => Ok(None),
+        Some(s) => FromStr::from_str(s).map_err(de::Error::custom).map(Some),
+    }
+}
+
+/// `1,2,3` → `vec![1, 2, 3]`
+///
+/// 각 항목의 앞뒤 공백은 무시하고, 빈 항목(`1,,2`, `ids=`)은 건너뜀
+pub fn comma_separated<'de, D, T>(de: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let s = String::deserialize(de)?;
+    parse_list(&s).map_err(de::Error::custom)
+}
+
+/// `yes/no`, `1/0`, `true/false`, `on/off` (대소문자 무시) → bool
+pub fn flexible_bool<'de, D>(de: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(de)?;
+    parse_bool(&s).ok_or_else(|| {
+        de::Error::invalid_value(
+            de::Unexpected::Str(&s),
+            &"yes/no, 1/0, true/false or on/off",
+        )
+    })
+}
+
+/// `deserialize_with` 없이 쓰는 쉼표 구분 목록
+///
+/// ```rust,ignore
+/// #[derive(Deserialize)]
+/// struct Params {
+///     #[serde(default)]
+///     tags: CommaSeparated<String>,
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommaSeparated<T>(pub Vec<T>);
+
+// 파라미터가 없을 때 `#[serde(default)]`로 빈 목록을 쓰기 위해 직접 구현 (T: Default 불필요)
+impl<T> Default for CommaSeparated<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<T> Deref for CommaSeparated<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<'de, T> Deserialize<'de> for CommaSeparated<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        comma_separated(de).map(Self)
+    }
+}
+
+fn parse_list<T>(s: &str) -> Result<Vec<T>, String>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    s.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            item.parse()
+                .map_err(|err| format!("invalid list item `{item}`: {err}"))
+        })
+        .collect()
+}
+
+fn parse_bool(s: &str) -> Option<bool> {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "yes" | "1" | "true" | "on" => Some(true),
+        "no" | "0" | "false" | "off" => Some(false),
+        _ => None,
+    }
+}
@@ -0,0 +1,234 @@
+//! 📦 컴파일 시점에 바이너리에 포함한 정적 파일 + 캐시 무효화(cache busting) (포트: 3008)
+//!
+//! • `rust-embed`로 `assets/` 디렉토리를 바이너리에 포함 → 배포할 때 파일을 따로 복사할 필요 없음
+//!   → 디버그 빌드에서는 디스크에서 바로 읽으므로 파일을 고치면 재시작 없이 반영됨
+//! • 파일 내용의 SHA-256 앞 8자리를 이름에 넣은 경로를 만듦 (`script.js` → `script.1a2b3c4d.js`)
+//!   → 내용이 바뀌면 경로도 바뀌므로 브라우저가 1년 동안 캐시해도 안전 (`immutable`)
+//! • 원래 이름으로 요청하면 `no-cache` + `ETag` → 매번 확인하되 바뀌지 않았으면 304
+//! • `GET /static/manifest.json`: 원래 이름 → 해시 경로 (템플릿/프런트엔드 빌드에서 사용)
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{Html, IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use rust_embed::RustEmbed;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    sync::Arc,
+};
+
+#[derive(RustEmbed)]
+#[folder = "assets/"]
+struct Assets;
+
+const PREFIX: &str = "/static";
+const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+const REVALIDATE: &str = "no-cache";
+
+/// 원래 이름 ↔ 해시가 들어간 이름
+struct Manifest {
+    // manifest.json 출력 순서를 고정하려고 BTreeMap
+    hashed_by_name: BTreeMap<String, String>,
+    name_by_hashed: HashMap<String, String>,
+}
+
+impl Manifest {
+    fn build() -> Self {
+        let mut hashed_by_name = BTreeMap::new();
+        let mut name_by_hashed = HashMap::new();
+        for name in Assets::iter() {
+            let file = Assets::get(&name).expect("listed by Assets::iter");
+            let hashed = hashed_name(&name, &hex(&file.metadata.sha256_hash()[..4]));
+            name_by_hashed.insert(hashed.clone(), name.to_string());
+            hashed_by_name.insert(name.to_string(), hashed);
+        }
+        Self {
+            hashed_by_name,
+            name_by_hashed,
+        }
+    }
+
+    /// 템플릿에 넣을 URL (`/static/script.1a2b3c4d.js`)
+    fn url(&self, name: &str) -> Option<String> {
+        let hashed = self.hashed_by_name.get(name)?;
+        Some(format!("{PREFIX}/{hashed}"))
+    }
+}
+
+pub fn embedded_assets() -> Router {
+    Router::new()
+        .route("/", get(index))
+        .route("/static/manifest.json", get(manifest))
+        .route("/static/{*path}", get(asset))
+        .with_state(Arc::new(Manifest::build()))
+}
+
+// 템플릿 대신 format!으로 매니페스트 사용 예시
+async fn index(State(manifest): State<Arc<Manifest>>) -> Html<String> {
+    let script = manifest.url("script.js").unwrap_or_default();
+    Html(format!(
+        "<!doctype html>\n<h1>Embedded assets</h1>\n<script src=\"{script}\"></script>\n"
+    ))
+}
+
+async fn manifest(State(manifest): State<Arc<Manifest>>) -> impl IntoResponse {
+    let urls: BTreeMap<String, String> = manifest
+        .hashed_by_name
+        .iter()
+        .map(|(name, hashed)| (name.clone(), format!("{PREFIX}/{hashed}")))
+        .collect();
+    // 배포할 때마다 바뀌므로 캐시하지 않음
+    ([(header::CACHE_CONTROL, REVALIDATE)], Json(urls))
+}
+
+async fn asset(
+    State(manifest): State<Arc<Manifest>>,
+    Path(path): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    // 해시 경로면 내용이 절대 바뀌지 않음, 원래 이름이면 매번 확인
+    let (name, cache_control) = match manifest.name_by_hashed.get(&path) {
+        Some(name) => (name.as_str(), IMMUTABLE),
+        None => (path.as_str(), REVALIDATE),
+    };
+    let Some(file) = Assets::get(name) else {
+        // 예전 배포의 해시 경로도 여기로 옴 (이전 버전 파일은 바이너리에 없음)
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let etag = format!("\"{}\"", hex(&file.metadata.sha256_hash()));
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.split(',').any(|tag| tag.trim() == etag));
+    let etag = HeaderValue::from_str(&etag).unwrap();
+    let cache_control = HeaderValue::from_static(cache_control);
+    if not_modified {
+        return (
+            StatusCode::NOT_MODIFIED,
+            [(header::ETAG, etag), (header::CACHE_CONTROL, cache_control)],
+        )
+            .into_response();
+    }
+
+    (
+        [
+            (
+                header::CONTENT_TYPE,
+                file.metadata.mimetype().parse().unwrap(),
+            ),
+            (header::ETAG, etag),
+            (header::CACHE_CONTROL, cache_control),
+        ],
+        Body::from(file.data),
+    )
+        .into_response()
+}
+
+/// `css/app.css` + `1a2b3c4d` → `css/app.1a2b3c4d.css`
+fn hashed_name(name: &str, hash: &str) -> String {
+    let file_start = name.rfind('/').map_or(0, |i| i + 1);
+    match name[file_start..].rfind('.') {
+        // `.gitignore`처럼 점으로 시작하는 이름은 확장자가 없는 것으로 봄
+        Some(dot) if dot > 0 => {
+            let (stem, extension) = name.split_at(file_start + dot);
+            format!("{stem}.{hash}{extension}")
+        }
+        _ => format!("{name}.{hash}"),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut out, b| {
+        let _ = write!(out, "{b:02x}");
+        out
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    async fn get(uri: &str, if_none_match: Option<&str>) -> Response {
+        let mut request = Request::builder().uri(uri);
+        if let Some(etag) = if_none_match {
+            request = request.header(header::IF_NONE_MATCH, etag);
+        }
+        embedded_assets()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn text(response: Response) -> String {
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn hash_goes_before_the_extension() {
+        assert_eq!(hashed_name("script.js", "1a2b"), "script.1a2b.js");
+        assert_eq!(
+            hashed_name("css/app.min.css", "1a2b"),
+            "css/app.min.1a2b.css"
+        );
+        assert_eq!(hashed_name("v1.0/LICENSE", "1a2b"), "v1.0/LICENSE.1a2b");
+        assert_eq!(hashed_name(".well-known", "1a2b"), ".well-known.1a2b");
+    }
+
+    #[tokio::test]
+    async fn hashed_paths_are_immutable_and_listed_in_the_manifest() {
+        let response = get("/static/manifest.json", None).await;
+        assert_eq!(response.headers()[header::CACHE_CONTROL], REVALIDATE);
+        let manifest: Value = serde_json::from_str(&text(response).await).unwrap();
+        let url = manifest["script.js"].as_str().unwrap();
+        assert!(url.starts_with("/static/script.") && url.ends_with(".js"));
+        assert_eq!(url.len(), "/static/script.12345678.js".len());
+
+        let page = text(get("/", None).await).await;
+        assert!(page.contains(&format!("<script src=\"{url}\">")));
+
+        let response = get(url, None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CACHE_CONTROL], IMMUTABLE);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/javascript");
+        assert_eq!(
+            text(response).await.trim(),
+            "console.log(\"Hello, World!\");"
+        );
+
+        // 예전 배포의 해시 경로
+        let response = get("/static/script.00000000.js", None).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn original_names_are_revalidated_with_etags() {
+        let response = get("/static/index.html", None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CACHE_CONTROL], REVALIDATE);
+        let etag = response.headers()[header::ETAG]
+            .to_str()
+            .unwrap()
+            .to_owned();
+
+        let response = get("/static/index.html", Some(&format!("\"stale\", {etag}"))).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert!(text(response).await.is_empty());
+
+        let response = get("/static/index.html", Some("\"stale\"")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = get("/static/missing.js", None).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -1,14 +1,19 @@
 //! **정적 파일(Static Files)**을 여러 방식으로 서비스하는 다양한 패턴을 보여주는 예제
 //!
 //! 📦 전체 예제 요약
-//!  •	assets/index.html → "Hi from index.html"
-//!	 •	assets/script.js → console.log("Hello, World!");
-//!	 •	7개의 포트(3001~3006, 3307)에서 각각 다른 라우팅 전략으로 정적 파일 서빙 테스트
+//!  • assets/index.html → "Hi from index.html"
+//!  • assets/script.js → console.log("Hello, World!");
+//!  • 7개의 포트(3001~3006, 3307)에서 각각 다른 라우팅 전략으로 정적 파일 서빙 테스트
+//!  • 포트 3007: `Host` 헤더로 사이트를 고르는 가상 호스트 (`vhost.rs`, `sites/` 디렉토리)
+//!  • 포트 3008: 바이너리에 포함한 파일 + 해시 경로로 캐시 무효화 (`embedded.rs`)
 //!
 //! ```not_rust
 //! cargo run -p example-static-file-server
 //! ```
 
+mod embedded;
+mod vhost;
+
 use axum::{
     extract::Request, handler::HandlerWithoutStateExt, http::StatusCode, routing::get, Router,
 };
@@ -40,6 +45,8 @@ async fn main() {
         serve(two_serve_dirs(), 3005),
         serve(calling_serve_dir_from_a_handler(), 3006),
         serve(using_serve_file_from_a_route(), 3307),
+        serve(vhost::virtual_hosts(), 3007),
+        serve(embedded::embedded_assets(), 3008),
     );
 }
 
@@ -159,3 +166,19 @@ async fn serve(app: Router, port: u16) {
 
 // # route_service 사용
 // curl http://127.0.0.1:3307/foo
+
+// # 가상 호스트 (Host 헤더에 따라 다른 사이트, 없는 파일은 사이트별 404.html)
+// curl -H 'Host: example.com' http://127.0.0.1:3007/
+// curl -H 'Host: blog.example.com' http://127.0.0.1:3007/
+// curl -i -H 'Host: blog.example.com' http://127.0.0.1:3007/없는글
+// curl http://127.0.0.1:3007/          → 기본 사이트 (sites/default)
+//
+// # 특정 사이트의 접근 로그만 보기
+// RUST_LOG='access[{site=blog.example.com}]=info' cargo run -p example-static-file-server
+
+// # 바이너리에 포함된 파일: 매니페스트 → 해시 경로 (immutable 캐시)
+// curl http://127.0.0.1:3008/static/manifest.json
+// curl -i http://127.0.0.1:3008/static/script.<해시>.js
+// # 원래 이름은 ETag로 확인 (두 번째 요청은 304 Not Modified)
+// curl -i http://127.0.0.1:3008/static/script.js
+// curl -i -H 'If-None-Match: "<ETag 값>"' http://127.0.0.1:3008/static/script.js
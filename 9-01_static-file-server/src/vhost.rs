@@ -0,0 +1,197 @@
+//! 🌐 가상 호스트: 하나의 리스너에서 `Host` 헤더에 따라 여러 사이트를 서비스 (포트: 3007)
+//!
+//! ```text
+//! sites/
+//! ├── example.com/        ← example.com, www.example.com
+//! ├── blog.example.com/   ← blog.example.com
+//! └── default/            ← 그 밖의 모든 호스트 (127.0.0.1 등)
+//! ```
+//!
+//! • 사이트마다 `ServeDir` 루트와 404 페이지(`<루트>/404.html`)가 따로 있음
+//! • fallback 핸들러가 `Host`를 보고 사이트별 `Router` 중 하나로 요청을 넘김 (`oneshot`)
+//! • 접근 로그는 `access` 타깃에 `site` 필드와 함께 기록 → 필터로 사이트별로 골라 볼 수 있음
+//!   → `RUST_LOG='access[{site=blog.example.com}]=info'`
+
+use axum::{
+    extract::{Request, State},
+    http::header,
+    middleware::{self, Next},
+    response::Response,
+    Router,
+};
+use std::{collections::HashMap, sync::Arc, time::Instant};
+use tower::ServiceExt;
+use tower_http::services::{ServeDir, ServeFile};
+
+struct Site {
+    /// 로그에 남길 사이트 이름
+    name: &'static str,
+    /// 이 사이트로 보낼 호스트 이름 (포트 제외, 소문자)
+    hosts: &'static [&'static str],
+    root: &'static str,
+}
+
+const SITES: &[Site] = &[
+    Site {
+        name: "example.com",
+        hosts: &["example.com", "www.example.com"],
+        root: "sites/example.com",
+    },
+    Site {
+        name: "blog.example.com",
+        hosts: &["blog.example.com"],
+        root: "sites/blog.example.com",
+    },
+];
+
+const DEFAULT_SITE: Site = Site {
+    name: "default",
+    hosts: &[],
+    root: "sites/default",
+};
+
+#[derive(Clone)]
+struct VirtualHosts {
+    /// 호스트 이름 → 사이트 라우터 (별칭은 같은 라우터를 복제해서 공유)
+    by_host: Arc<HashMap<&'static str, Router>>,
+    default: Router,
+}
+
+pub fn virtual_hosts() -> Router {
+    let mut by_host = HashMap::new();
+    for site in SITES {
+        let router = site_router(site);
+        for host in site.hosts {
+            by_host.insert(*host, router.clone());
+        }
+    }
+    let hosts = VirtualHosts {
+        by_host: Arc::new(by_host),
+        default: site_router(&DEFAULT_SITE),
+    };
+
+    Router::new().fallback(route_by_host).with_state(hosts)
+}
+
+// 경로는 그대로 두고 요청 전체를 사이트 라우터에 넘김
+async fn route_by_host(State(hosts): State<VirtualHosts>, request: Request) -> Response {
+    let router = request_host(&request)
+        .and_then(|host| hosts.by_host.get(host.as_str()))
+        .unwrap_or(&hosts.default)
+        .clone();
+    router
+        .oneshot(request)
+        .await
+        .unwrap_or_else(|err| match err {})
+}
+
+fn site_router(site: &Site) -> Router {
+    // 파일이 없으면 사이트의 404.html을 404 상태 코드로 응답
+    let serve_dir = ServeDir::new(site.root)
+        .not_found_service(ServeFile::new(format!("{}/404.html", site.root)));
+
+    Router::new()
+        .fallback_service(serve_dir)
+        .layer(middleware::from_fn_with_state(site.name, access_log))
+}
+
+/// 포트와 끝의 `.`을 뗀 소문자 호스트 이름
+///
+/// HTTP/1.1은 `Host` 헤더, HTTP/2는 URI의 authority(`:authority`)에 호스트가 있음
+fn request_host(request: &Request) -> Option<String> {
+    let host = request
+        .headers()
+        .get(header::HOST)
+        .and_then(|value| value.to_str().ok())
+        .or_else(|| request.uri().host())?;
+    // IPv6 주소(`[::1]:3007`)는 대괄호까지가 호스트
+    let host = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next()?,
+        None => host.split(':').next()?,
+    };
+    Some(host.trim_end_matches('.').to_ascii_lowercase())
+}
+
+async fn access_log(State(site): State<&'static str>, request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let path = request.uri().path().to_owned();
+    let host = request_host(&request).unwrap_or_default();
+    let started = Instant::now();
+
+    let response = next.run(request).await;
+
+    tracing::info!(
+        target: "access",
+        site,
+        host,
+        %method,
+        path,
+        status = response.status().as_u16(),
+        latency_ms = started.elapsed().as_millis() as u64,
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode};
+    use http_body_util::BodyExt;
+
+    async fn get(host: &str, path: &str) -> (StatusCode, String) {
+        let request = Request::builder()
+            .uri(path)
+            .header(header::HOST, host)
+            .body(Body::empty())
+            .unwrap();
+        let response = virtual_hosts().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn hosts_are_served_from_their_own_roots() {
+        let (status, body) = get("example.com", "/").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("<h1>example.com</h1>"));
+
+        // 별칭, 포트, 대소문자, 끝의 `.`은 무시
+        let (_, body) = get("WWW.Example.com.:3007", "/index.html").await;
+        assert!(body.contains("<h1>example.com</h1>"));
+
+        let (_, body) = get("blog.example.com", "/").await;
+        assert!(body.contains("<h1>blog.example.com</h1>"));
+
+        // 등록되지 않은 호스트는 기본 사이트
+        let (status, body) = get("127.0.0.1:3007", "/").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("No site is configured"));
+        let (_, body) = get("[::1]:3007", "/").await;
+        assert!(body.contains("No site is configured"));
+    }
+
+    #[tokio::test]
+    async fn missing_files_use_the_sites_own_404_page() {
+        let (status, body) = get("example.com", "/missing").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(body.contains("example.com: page not found"));
+
+        let (status, body) = get("blog.example.com", "/missing").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(body.contains("no such post"));
+
+        // 다른 사이트의 파일은 보이지 않음
+        let (status, _) = get("blog.example.com", "/../example.com/index.html").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn host_is_taken_from_the_uri_without_a_host_header() {
+        let request = Request::builder()
+            .uri("https://Blog.Example.com:8443/post")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(request_host(&request).as_deref(), Some("blog.example.com"));
+    }
+}
@@ -0,0 +1,137 @@
+//! cache-aside 패턴에 필요한 도구들
+//!
+//! • `KeyedLocks` — 키별 비동기 락 (singleflight)
+//!   같은 키에 대한 캐시 miss가 동시에 여러 번 발생해도 DB 조회는 한 번만 일어나도록 함
+//!   (cache stampede / thundering herd 방지)
+//! • `ttl_with_jitter` — 만료 시간에 무작위 값을 더해 여러 키가 동시에 만료되지 않도록 함
+
+use rand::Rng;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+#[derive(Default)]
+pub struct KeyedLocks {
+    locks: Mutex<HashMap<String, Arc<AsyncMutex<()>>>>,
+}
+
+impl KeyedLocks {
+    /// Wait until no other task holds the lock for `key`, then take it.
+    /// 반환된 guard가 drop 될 때까지 같은 키로 들어온 다른 요청은 기다림
+    pub async fn lock(&self, key: &str) -> KeyGuard<'_> {
+        // 맵 락은 Arc를 복제하는 동안만 잡고, 실제 대기는 키별 락에서 함
+        let lock = self
+            .locks
+            .lock()
+            .unwrap()
+            .entry(key.to_owned())
+            .or_default()
+            .clone();
+
+        KeyGuard {
+            locks: self,
+            key: key.to_owned(),
+            guard: Some(lock.lock_owned().await),
+        }
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.locks.lock().unwrap().len()
+    }
+}
+
+pub struct KeyGuard<'a> {
+    locks: &'a KeyedLocks,
+    key: String,
+    guard: Option<OwnedMutexGuard<()>>,
+}
+
+impl Drop for KeyGuard<'_> {
+    fn drop(&mut self) {
+        let mut locks = self.locks.locks.lock().unwrap();
+        drop(self.guard.take());
+
+        // 맵만 락을 들고 있다면 기다리는 요청이 없는 것이므로 항목을 제거 (메모리 누수 방지)
+        // 대기자는 맵 락을 잡은 상태에서 Arc를 복제하므로 이 검사와 경쟁하지 않음
+        if locks
+            .get(&self.key)
+            .is_some_and(|lock| Arc::strong_count(lock) == 1)
+        {
+            locks.remove(&self.key);
+        }
+    }
+}
+
+/// `base`에 0 ~ `jitter` 사이의 무작위 시간을 더한 TTL
+pub fn ttl_with_jitter(base: Duration, jitter: Duration) -> Duration {
+    let jitter_ms = rand::thread_rng().gen_range(0..=jitter.as_millis() as u64);
+    base + Duration::from_millis(jitter_ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // 캐시 확인 → 락 → 다시 확인 → 계산 순서를 메모리 캐시로 흉내 내어
+    // 동시에 10번 miss가 나도 계산은 한 번만 일어나는지 확인
+    #[tokio::test]
+    async fn concurrent_misses_compute_once() {
+        let locks = Arc::new(KeyedLocks::default());
+        let cache = Arc::new(Mutex::new(HashMap::<String, u32>::new()));
+        let computed = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..10)
+            .map(|_| {
+                let (locks, cache, computed) = (locks.clone(), cache.clone(), computed.clone());
+                tokio::spawn(async move {
+                    if let Some(value) = cache.lock().unwrap().get("user:1") {
+                        return *value;
+                    }
+
+                    let _guard = locks.lock("user:1").await;
+                    if let Some(value) = cache.lock().unwrap().get("user:1") {
+                        return *value;
+                    }
+
+                    computed.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    cache.lock().unwrap().insert("user:1".to_owned(), 42);
+                    42
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            assert_eq!(task.await.unwrap(), 42);
+        }
+        assert_eq!(computed.load(Ordering::SeqCst), 1);
+        // 모든 guard가 drop 되면 키별 락도 정리됨
+        assert_eq!(locks.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn different_keys_do_not_block_each_other() {
+        let locks = KeyedLocks::default();
+
+        let _first = locks.lock("user:1").await;
+        let second = tokio::time::timeout(Duration::from_secs(1), locks.lock("user:2")).await;
+
+        assert!(second.is_ok());
+    }
+
+    #[test]
+    fn jitter_stays_within_bounds() {
+        let base = Duration::from_secs(60);
+        let jitter = Duration::from_secs(10);
+
+        for _ in 0..100 {
+            let ttl = ttl_with_jitter(base, jitter);
+            assert!(ttl >= base && ttl <= base + jitter);
+        }
+    }
+}
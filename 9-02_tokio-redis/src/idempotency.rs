@@ -0,0 +1,450 @@
+//! `Idempotency-Key` 미들웨어
+//!
+//! 네트워크 오류 등으로 클라이언트가 같은 POST 요청을 다시 보내도 작업이 한 번만 수행되도록 함.
+//!
+//! 1. 요청 본문을 버퍼링해서 fingerprint(메서드 + 경로 + 본문의 SHA-256)를 만듦
+//! 2. 키가 처음이면 `SET NX PX`로 "처리 중" 표시를 남기고 핸들러 실행 (표시는 `LOCK_TTL` 뒤 만료)
+//! 3. 응답(상태 코드, content-type, body)을 fingerprint와 함께 `TTL` 동안 Redis에 저장
+//! 4. 같은 키로 다시 들어온 요청은
+//!    → fingerprint가 같으면 핸들러를 실행하지 않고 저장된 응답을 그대로 재전송
+//!    → fingerprint가 다르면 422 (같은 키를 다른 요청에 재사용 → 엉뚱한 응답을 재전송하지 않음)
+//!    → 아직 처리 중이면 409 Conflict
+//!
+//! • 핸들러가 취소되거나 패닉이 나면 `LockGuard`가 "처리 중" 표시를 지움 (5-27의 `InFlightGuard`와 같은 방식)
+//!   → 프로세스가 죽어서 guard가 실행되지 못해도 `LOCK_TTL`이 지나면 다시 시도할 수 있음
+//! • 저장소 연산은 `KeyStore` trait으로 분리 (Redis 커넥션 풀, 테스트에서는 메모리)
+
+use crate::ConnectionPool;
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{fmt::Write, future::Future, time::Duration};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// 저장된 응답을 보관하는 시간
+const TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// "처리 중" 표시가 남아 있는 최대 시간 (핸들러가 이보다 오래 걸리면 안 됨)
+const LOCK_TTL: Duration = Duration::from_secs(30);
+
+/// fingerprint를 만들기 위해 버퍼링할 요청 본문의 최대 크기
+const MAX_REQUEST_BYTES: usize = 1024 * 1024;
+
+/// 저장할 응답 body의 최대 크기
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// 처리 중인 요청을 나타내는 값의 접두사 (`in-progress:{fingerprint}:{token}`)
+const IN_PROGRESS: &str = "in-progress:";
+
+/// 미들웨어가 쓰는 저장소 연산
+pub trait KeyStore: Clone + Send + Sync + 'static {
+    /// 키가 없을 때만 저장, 저장했으면 true
+    fn set_nx(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: Duration,
+    ) -> impl Future<Output = Result<bool, BoxError>> + Send;
+
+    fn get(&self, key: &str) -> impl Future<Output = Result<Option<String>, BoxError>> + Send;
+
+    fn set(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: Duration,
+    ) -> impl Future<Output = Result<(), BoxError>> + Send;
+
+    /// 값이 `value`일 때만 삭제 (만료 후 다른 요청이 남긴 표시는 지우지 않음)
+    fn del_if_eq(
+        &self,
+        key: &str,
+        value: &str,
+    ) -> impl Future<Output = Result<(), BoxError>> + Send;
+}
+
+impl KeyStore for ConnectionPool {
+    async fn set_nx(&self, key: &str, value: &str, ttl: Duration) -> Result<bool, BoxError> {
+        let mut conn = self.get().await?;
+        // SET key value NX PX ttl → 처음 들어온 요청만 성공
+        let reply: Option<String> = redis::cmd("SET")
+            .arg(key)
+            .arg(value)
+            .arg("NX")
+            .arg("PX")
+            .arg(ttl.as_millis() as u64)
+            .query_async(&mut *conn)
+            .await?;
+        Ok(reply.is_some())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<String>, BoxError> {
+        let mut conn = self.get().await?;
+        Ok(conn.get(key).await?)
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl: Duration) -> Result<(), BoxError> {
+        let mut conn = self.get().await?;
+        conn.pset_ex::<_, _, ()>(key, value, ttl.as_millis() as u64)
+            .await?;
+        Ok(())
+    }
+
+    async fn del_if_eq(&self, key: &str, value: &str) -> Result<(), BoxError> {
+        let mut conn = self.get().await?;
+        // GET과 DEL 사이에 다른 요청이 끼어들지 않도록 스크립트로 한 번에
+        redis::Script::new(
+            "if redis.call('GET', KEYS[1]) == ARGV[1] then return redis.call('DEL', KEYS[1]) end return 0",
+        )
+        .key(key)
+        .arg(value)
+        .invoke_async::<()>(&mut *conn)
+        .await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredResponse {
+    fingerprint: String,
+    status: u16,
+    content_type: Option<String>,
+    body: Vec<u8>,
+}
+
+/// 이미 있는 키에 대한 판단
+#[derive(Debug)]
+enum Existing {
+    InFlight,
+    Mismatch,
+    Replay(StoredResponse),
+}
+
+fn existing(stored: &str, fingerprint: &str) -> serde_json::Result<Existing> {
+    if let Some(lock) = stored.strip_prefix(IN_PROGRESS) {
+        return Ok(match lock.split(':').next() {
+            Some(locked) if locked == fingerprint => Existing::InFlight,
+            _ => Existing::Mismatch,
+        });
+    }
+    let stored: StoredResponse = serde_json::from_str(stored)?;
+    Ok(if stored.fingerprint == fingerprint {
+        Existing::Replay(stored)
+    } else {
+        Existing::Mismatch
+    })
+}
+
+// 핸들러가 응답을 저장하기 전에 drop되면(취소, 패닉, 5xx) "처리 중" 표시를 지움
+struct LockGuard<S: KeyStore> {
+    store: S,
+    key: String,
+    lock: String,
+    armed: bool,
+}
+
+impl<S: KeyStore> Drop for LockGuard<S> {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        // drop은 async가 아니므로 삭제는 별도 태스크에서
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let (store, key, lock) = (
+            self.store.clone(),
+            std::mem::take(&mut self.key),
+            std::mem::take(&mut self.lock),
+        );
+        runtime.spawn(async move {
+            if let Err(err) = store.del_if_eq(&key, &lock).await {
+                tracing::warn!(%key, %err, "failed to release idempotency lock");
+            }
+        });
+    }
+}
+
+/// `route_layer(axum::middleware::from_fn_with_state(pool, idempotency))`로 POST 라우트에 등록
+pub async fn idempotency<S: KeyStore>(
+    State(store): State<S>,
+    req: Request,
+    next: Next,
+) -> Result<Response, (StatusCode, String)> {
+    let Some(key) = req
+        .headers()
+        .get("idempotency-key")
+        .and_then(|value| value.to_str().ok())
+    else {
+        // 헤더가 없으면 일반 요청처럼 처리
+        return Ok(next.run(req).await);
+    };
+
+    // 같은 키라도 다른 엔드포인트의 응답이 재사용되지 않도록 메서드와 경로를 포함
+    let redis_key = format!("idempotency:{}:{}:{key}", req.method(), req.uri().path());
+
+    let (parts, body) = req.into_parts();
+    let Ok(body) = to_bytes(body, MAX_REQUEST_BYTES).await else {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "request body too large".to_owned(),
+        ));
+    };
+    let fingerprint = Sha256::new()
+        .chain_update(parts.method.as_str())
+        .chain_update([0])
+        .chain_update(parts.uri.path())
+        .chain_update([0])
+        .chain_update(&body)
+        .finalize()
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        });
+
+    let lock = format!("{IN_PROGRESS}{fingerprint}:{:016x}", rand::random::<u64>());
+    let acquired = store
+        .set_nx(&redis_key, &lock, LOCK_TTL)
+        .await
+        .map_err(internal_error)?;
+
+    if !acquired {
+        let stored = store.get(&redis_key).await.map_err(internal_error)?;
+        let Some(stored) = stored else {
+            // 그 사이 만료되었거나 삭제됨 → 클라이언트가 다시 시도하면 됨
+            return Err((
+                StatusCode::CONFLICT,
+                "idempotency key expired, please retry".to_owned(),
+            ));
+        };
+        return match existing(&stored, &fingerprint).map_err(internal_error)? {
+            Existing::InFlight => Err((
+                StatusCode::CONFLICT,
+                "a request with this idempotency key is still being processed".to_owned(),
+            )),
+            Existing::Mismatch => Err((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "this idempotency key was already used with a different request".to_owned(),
+            )),
+            Existing::Replay(stored) => {
+                tracing::debug!(%redis_key, "replaying stored response");
+                Ok(replay(stored))
+            }
+        };
+    }
+
+    let mut guard = LockGuard {
+        store: store.clone(),
+        key: redis_key,
+        lock,
+        armed: true,
+    };
+    let res = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+    // 서버 에러는 저장하지 않음 → guard가 표시를 지워 클라이언트가 다시 시도할 수 있게 함
+    if res.status().is_server_error() {
+        return Ok(res);
+    }
+
+    let (parts, body) = res.into_parts();
+    // 저장할 수 없는 응답이면 guard가 표시를 지움
+    let body = to_bytes(body, MAX_BODY_BYTES)
+        .await
+        .map_err(internal_error)?;
+
+    let stored = StoredResponse {
+        fingerprint,
+        status: parts.status.as_u16(),
+        content_type: parts
+            .headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(ToOwned::to_owned),
+        body: body.to_vec(),
+    };
+    let value = serde_json::to_string(&stored).map_err(internal_error)?;
+
+    // 완료된 응답만 TTL(24시간) 동안 보관
+    store
+        .set(&guard.key, &value, TTL)
+        .await
+        .map_err(internal_error)?;
+    guard.armed = false;
+
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+// 저장된 응답으로 새 Response를 만들고, 재전송된 응답임을 헤더로 알림
+fn replay(stored: StoredResponse) -> Response {
+    let status = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    let mut res = (status, stored.body).into_response();
+
+    let headers = res.headers_mut();
+    headers.remove(header::CONTENT_TYPE);
+    if let Some(content_type) = stored
+        .content_type
+        .and_then(|value| HeaderValue::from_str(&value).ok())
+    {
+        headers.insert(header::CONTENT_TYPE, content_type);
+    }
+    headers.insert("idempotent-replayed", HeaderValue::from_static("true"));
+    res
+}
+
+fn internal_error<E>(err: E) -> (StatusCode, String)
+where
+    E: std::fmt::Display,
+{
+    (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware, routing::post, Router};
+    use std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+    };
+    use tower::ServiceExt;
+
+    // Redis 대신 쓰는 메모리 저장소 (TTL은 무시)
+    #[derive(Clone, Default)]
+    struct MemoryStore(Arc<Mutex<HashMap<String, String>>>);
+
+    impl KeyStore for MemoryStore {
+        async fn set_nx(&self, key: &str, value: &str, _ttl: Duration) -> Result<bool, BoxError> {
+            let mut map = self.0.lock().unwrap();
+            if map.contains_key(key) {
+                return Ok(false);
+            }
+            map.insert(key.to_owned(), value.to_owned());
+            Ok(true)
+        }
+
+        async fn get(&self, key: &str) -> Result<Option<String>, BoxError> {
+            Ok(self.0.lock().unwrap().get(key).cloned())
+        }
+
+        async fn set(&self, key: &str, value: &str, _ttl: Duration) -> Result<(), BoxError> {
+            self.0
+                .lock()
+                .unwrap()
+                .insert(key.to_owned(), value.to_owned());
+            Ok(())
+        }
+
+        async fn del_if_eq(&self, key: &str, value: &str) -> Result<(), BoxError> {
+            let mut map = self.0.lock().unwrap();
+            if map.get(key).map(String::as_str) == Some(value) {
+                map.remove(key);
+            }
+            Ok(())
+        }
+    }
+
+    // 호출 횟수를 응답하는 주문 라우트, `hang`이면 첫 호출은 끝나지 않음
+    fn app(store: MemoryStore, hang: bool) -> (Router, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let app = Router::new()
+            .route(
+                "/orders",
+                post(move || async move {
+                    let call = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                    if hang && call == 1 {
+                        std::future::pending::<()>().await;
+                    }
+                    (StatusCode::CREATED, call.to_string())
+                }),
+            )
+            .route_layer(middleware::from_fn_with_state(
+                store,
+                idempotency::<MemoryStore>,
+            ));
+        (app, calls)
+    }
+
+    fn order(key: &str, body: &'static str) -> Request {
+        Request::post("/orders")
+            .header("idempotency-key", key)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn replay_restores_status_content_type_and_body() {
+        let res = replay(StoredResponse {
+            fingerprint: String::new(),
+            status: 201,
+            content_type: Some("application/json".to_owned()),
+            body: br#"{"id":1}"#.to_vec(),
+        });
+
+        assert_eq!(res.status(), StatusCode::CREATED);
+        assert_eq!(res.headers()[header::CONTENT_TYPE], "application/json");
+        assert_eq!(res.headers()["idempotent-replayed"], "true");
+
+        let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], br#"{"id":1}"#);
+    }
+
+    #[tokio::test]
+    async fn cancelled_request_releases_the_key() {
+        let store = MemoryStore::default();
+        let (app, calls) = app(store.clone(), true);
+
+        // 클라이언트가 연결을 끊은 것처럼 응답을 기다리던 future를 drop
+        let cancelled = tokio::time::timeout(
+            Duration::from_millis(50),
+            app.clone().oneshot(order("k", "{}")),
+        )
+        .await;
+        assert!(cancelled.is_err());
+        // guard가 띄운 삭제 태스크가 실행되도록
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(store.0.lock().unwrap().is_empty());
+
+        // 409가 아니라 핸들러가 다시 실행됨
+        let res = app.oneshot(order("k", "{}")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn same_key_with_different_body_is_rejected() {
+        let (app, calls) = app(MemoryStore::default(), false);
+
+        let res = app
+            .clone()
+            .oneshot(order("k", r#"{"item":"book"}"#))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+
+        let res = app
+            .clone()
+            .oneshot(order("k", r#"{"item":"car"}"#))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        // 같은 본문은 저장된 응답을 재전송
+        let res = app.oneshot(order("k", r#"{"item":"book"}"#)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+        assert_eq!(res.headers()["idempotent-replayed"], "true");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
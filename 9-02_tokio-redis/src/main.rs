@@ -6,24 +6,50 @@
 //! • axum 핸들러에서 커넥션 풀을 사용하는 2가지 방법
 //! • 핸들러 내에서 Redis get("foo") 요청 처리
 //! • Redis에 사전 set("foo", "bar") 수행
+//! • cache-aside 패턴: GET /users/{id} → Redis 조회 → miss면 (느린) DB 조회 후 TTL과 함께 캐시
+//!   동시에 여러 요청이 miss 나도 키별 락(singleflight)으로 DB 조회는 한 번만 수행
+//! • 분산 rate limiter 미들웨어: INCR/EXPIRE 기반 sliding window (클라이언트 IP별)
+//!   → 프록시 뒤에서는 `TRUSTED_PROXIES`(CIDR 목록)를 설정하면 X-Forwarded-For의 클라이언트 IP 사용
+//! • Idempotency-Key 미들웨어: POST /orders 재전송 시 저장된 응답을 재사용
+//! • Redis Streams 작업 큐: POST /events → consumer group 워커가 처리, GET /events/lag로 밀린 양 확인
 //!
 //! ```not_rust
 //! cargo run -p example-tokio-redis
 //! ```
 
+mod cache;
+mod idempotency;
+mod rate_limit;
+mod streams;
+
 // Axum 관련 모듈 임포트
 use axum::{
-    extract::{FromRef, FromRequestParts, State},
+    extract::{FromRef, FromRequestParts, Path, State},
     http::{request::Parts, StatusCode},
-    routing::get,
-    Router,
+    middleware,
+    response::IntoResponse,
+    routing::{get, post},
+    Extension, Json, Router,
 };
 
 // Redis 비동기 연결 풀 관련 모듈
 use bb8::{Pool, PooledConnection};
 use bb8_redis::bb8; // bb8::Pool 등의 접근을 위해 필요
 use bb8_redis::RedisConnectionManager;
+use cache::{ttl_with_jitter, KeyedLocks};
+use example_common_client_ip::TrustedProxies;
+use rate_limit::RateLimiter;
 use redis::AsyncCommands; // Redis 명령어 trait
+use serde::{Deserialize, Serialize};
+use std::{
+    env,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 /// 🚀 main() 함수
@@ -39,6 +65,18 @@ async fn main() {
         .init();
 
     // Redis 연결 매니저 생성 및 커넥션 풀 구성
+    // 예: TRUSTED_PROXIES=10.0.0.0/8 (설정하지 않으면 연결한 주소를 그대로 사용)
+    // 프록시가 RFC 7239 헤더를 붙이면 TRUSTED_PROXY_HEADER=forwarded (기본 x-forwarded-for)
+    let trusted_proxies: TrustedProxies = env::var("TRUSTED_PROXIES")
+        .unwrap_or_default()
+        .parse::<TrustedProxies>()
+        .expect("TRUSTED_PROXIES must be a comma-separated list of CIDR ranges")
+        .header(
+            env::var("TRUSTED_PROXY_HEADER")
+                .map(|header| header.parse().unwrap())
+                .unwrap_or_default(),
+        );
+
     tracing::debug!("connecting to redis");
     let manager = RedisConnectionManager::new("redis://localhost").unwrap();
     let pool = bb8::Pool::builder().build(manager).await.unwrap();
@@ -54,6 +92,10 @@ async fn main() {
 
     tracing::debug!("successfully connected to redis and pinged it");
 
+    // 이벤트 스트림의 consumer group을 만들고 워커 시작
+    streams::ensure_group(&pool).await.unwrap();
+    tokio::spawn(streams::Worker::new(pool.clone(), streams::WorkerConfig::default()).run());
+
     // build our application with some routes
     // 라우터 설정: GET, POST 둘 다 지원
     let app = Router::new()
@@ -62,7 +104,29 @@ async fn main() {
             get(using_connection_pool_extractor) // 방식 1: State로 직접 풀 추출
                 .post(using_connection_extractor), // 방식 2: 커스텀 추출기 사용
         )
-        .with_state(pool); // 상태(State)로 Redis 커넥션 풀 제공
+        .route("/users/{id}", get(get_user)) // cache-aside 예제
+        .route("/events", post(publish_event)) // Redis Streams 예제
+        .route("/events/lag", get(events_lag))
+        .route(
+            "/orders",
+            // 이 라우트에만 Idempotency-Key 처리 적용
+            post(create_order).route_layer(middleware::from_fn_with_state(
+                pool.clone(),
+                idempotency::idempotency::<ConnectionPool>,
+            )),
+        )
+        // 모든 라우트에 rate limit 적용: 클라이언트 IP당 10초에 20번
+        .layer(middleware::from_fn_with_state(
+            RateLimiter::new(pool.clone(), 20, Duration::from_secs(10)),
+            rate_limit::rate_limit,
+        ))
+        // rate limiter의 `ClientIp` 추출기가 사용 (미들웨어보다 바깥에 있어야 함)
+        .layer(Extension(trusted_proxies))
+        .with_state(AppState {
+            pool, // 상태(State)로 Redis 커넥션 풀 제공
+            db: Arc::new(SlowDatabase::default()),
+            locks: Arc::new(KeyedLocks::default()),
+        });
 
     // 서버 실행
     let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
@@ -71,13 +135,35 @@ async fn main() {
 
     tracing::debug!("listening on {}", listener.local_addr().unwrap());
 
-    axum::serve(listener, app).await.unwrap();
+    // rate limiter가 클라이언트 IP(ConnectInfo)를 사용할 수 있도록 연결 정보를 함께 전달
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
 }
 
-// 🧪 방식 1: State<ConnectionPool> 추출기
+// 🧩 공유 상태
 
 type ConnectionPool = Pool<RedisConnectionManager>;
 
+#[derive(Clone)]
+struct AppState {
+    pool: ConnectionPool,
+    db: Arc<SlowDatabase>,
+    locks: Arc<KeyedLocks>,
+}
+
+// State<ConnectionPool>과 DatabaseConnection 추출기가 AppState에서도 동작하도록 함
+impl FromRef<AppState> for ConnectionPool {
+    fn from_ref(state: &AppState) -> Self {
+        state.pool.clone()
+    }
+}
+
+// 🧪 방식 1: State<ConnectionPool> 추출기
+
 async fn using_connection_pool_extractor(
     State(pool): State<ConnectionPool>, // 상태에서 풀을 추출
 ) -> Result<String, (StatusCode, String)> {
@@ -118,6 +204,153 @@ async fn using_connection_extractor(
     Ok(result)
 }
 
+// 🗄️ cache-aside 예제
+
+/// 캐시 기본 TTL과, 여러 키가 동시에 만료되지 않도록 더할 최대 jitter
+const USER_CACHE_TTL: Duration = Duration::from_secs(60);
+const USER_CACHE_JITTER: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct User {
+    id: u64,
+    name: String,
+}
+
+// 조회할 때마다 500ms가 걸리는 가짜 데이터베이스
+#[derive(Default)]
+struct SlowDatabase {
+    // 실제로 DB 조회가 일어난 횟수 (stampede 방지 확인용)
+    queries: AtomicUsize,
+}
+
+impl SlowDatabase {
+    async fn find_user(&self, id: u64) -> Option<User> {
+        let queries = self.queries.fetch_add(1, Ordering::Relaxed) + 1;
+        tracing::debug!(id, queries, "querying slow database");
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        // id 1~100만 존재한다고 가정
+        (1..=100).contains(&id).then(|| User {
+            id,
+            name: format!("user-{id}"),
+        })
+    }
+}
+
+async fn get_user(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let key = format!("user:{id}");
+
+    // 1. 캐시 조회 — hit면 바로 응답
+    if let Some(user) = cached_user(&state.pool, &key).await? {
+        return Ok(([("x-cache", "hit")], Json(user)));
+    }
+
+    // 2. miss — 같은 키에 대한 다른 요청이 DB를 조회 중이면 끝날 때까지 기다림
+    //    (기다리는 동안 커넥션을 점유하지 않도록 커넥션은 위에서 반납한 상태)
+    let _guard = state.locks.lock(&key).await;
+
+    // 3. 락을 얻은 뒤 다시 확인 — 앞선 요청이 이미 캐시를 채웠다면 그 값을 사용
+    if let Some(user) = cached_user(&state.pool, &key).await? {
+        return Ok(([("x-cache", "hit")], Json(user)));
+    }
+
+    // 4. DB 조회 후 TTL(+jitter)과 함께 캐시에 저장
+    let user = state
+        .db
+        .find_user(id)
+        .await
+        .ok_or((StatusCode::NOT_FOUND, format!("user {id} not found")))?;
+
+    let ttl = ttl_with_jitter(USER_CACHE_TTL, USER_CACHE_JITTER);
+    let value = serde_json::to_string(&user).map_err(internal_error)?;
+    let mut conn = state.pool.get().await.map_err(internal_error)?;
+    conn.set_ex::<_, _, ()>(&key, value, ttl.as_secs())
+        .await
+        .map_err(internal_error)?;
+
+    Ok(([("x-cache", "miss")], Json(user)))
+}
+
+async fn cached_user(
+    pool: &ConnectionPool,
+    key: &str,
+) -> Result<Option<User>, (StatusCode, String)> {
+    let mut conn = pool.get().await.map_err(internal_error)?;
+    let cached: Option<String> = conn.get(key).await.map_err(internal_error)?;
+
+    match cached {
+        Some(value) => serde_json::from_str(&value).map_err(internal_error),
+        None => Ok(None),
+    }
+}
+
+// 🧾 Idempotency-Key 예제
+
+#[derive(Debug, Deserialize)]
+struct CreateOrder {
+    item: String,
+    quantity: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct Order {
+    id: u64,
+    item: String,
+    quantity: u32,
+}
+
+// 호출될 때마다 새 주문 번호를 발급하므로, 재전송된 요청이 그대로 실행되면 주문이 중복 생성됨
+async fn create_order(
+    DatabaseConnection(mut conn): DatabaseConnection,
+    Json(input): Json<CreateOrder>,
+) -> Result<(StatusCode, Json<Order>), (StatusCode, String)> {
+    let id: u64 = conn
+        .incr("orders:next_id", 1)
+        .await
+        .map_err(internal_error)?;
+    tracing::debug!(id, "created order");
+
+    Ok((
+        StatusCode::CREATED,
+        Json(Order {
+            id,
+            item: input.item,
+            quantity: input.quantity,
+        }),
+    ))
+}
+
+// 📨 Redis Streams 예제
+
+#[derive(Debug, Serialize)]
+struct Published {
+    id: String,
+}
+
+// 워커가 나중에 처리하므로 202 Accepted
+async fn publish_event(
+    State(state): State<AppState>,
+    Json(event): Json<streams::Event>,
+) -> Result<(StatusCode, Json<Published>), (StatusCode, String)> {
+    let id = streams::publish(&state.pool, &event)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?;
+    Ok((StatusCode::ACCEPTED, Json(Published { id })))
+}
+
+async fn events_lag(
+    State(state): State<AppState>,
+) -> Result<Json<streams::Lag>, (StatusCode, String)> {
+    streams::lag(&state.pool)
+        .await
+        .map(Json)
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))
+}
+
 /// 🛠 에러 처리 헬퍼
 /// Utility function for mapping any error into a `500 Internal Server Error`
 /// response.
@@ -144,5 +377,34 @@ where
 // curl -X POST http://localhost:3000/
 // # 결과: bar
 //
+// 4.	cache-aside 확인:
+// curl -i http://localhost:3000/users/1
+// # 처음에는 x-cache: miss (약 500ms), 이후에는 x-cache: hit
+// for i in $(seq 10); do curl -s http://localhost:3000/users/2 & done; wait
+// # 동시에 10번 요청해도 서버 로그의 "querying slow database"는 한 번만 출력
+//
+// 5.	rate limit 확인:
+// for i in $(seq 25); do curl -s -o /dev/null -w "%{http_code}\n" http://localhost:3000/; done
+// # 20번 이후로 429 응답 (x-ratelimit-remaining 헤더로 남은 횟수 확인 가능)
+// TRUSTED_PROXIES=127.0.0.1 cargo run -p example-tokio-redis
+// curl -i -H 'x-forwarded-for: 203.0.113.7' http://localhost:3000/
+// # 로컬 프록시 뒤에 있는 것처럼 203.0.113.7의 한도가 따로 계산됨
+//
+// 6.	Idempotency-Key 확인:
+// curl -i -X POST http://localhost:3000/orders -H 'content-type: application/json' \
+//      -H 'idempotency-key: order-123' -d '{"item":"book","quantity":1}'
+// # 같은 요청을 다시 보내면 같은 주문 번호와 함께 idempotent-replayed: true 헤더가 응답됨
+// # 같은 키로 본문만 바꿔 보내면 ({"item":"pen","quantity":1}) 422
+//
+// 7.	Redis Streams 작업 큐 확인:
+// curl -X POST http://localhost:3000/events -H 'content-type: application/json' \
+//      -d '{"type":"signup","payload":{"user":1}}'
+// # {"id":"1718000000000-0"} → 서버 로그에 "processed event"
+// curl -X POST http://localhost:3000/events -H 'content-type: application/json' -d '{"type":"poison"}'
+// # 처리에 계속 실패 → 30초마다 XAUTOCLAIM으로 다시 시도, 5번 넘으면 events:dead로 이동
+// curl http://localhost:3000/events/lag
+// # {"length":2,"lag":0,"pending":1,"last_delivered_id":"...","dead_letters":0,"consumers":[{"name":"worker-1234","pending":1,"idle_ms":1200}]}
+// redis-cli XRANGE events:dead - +
+//
 // 종료
 // redis-cli shutdown
@@ -0,0 +1,156 @@
+//! Redis 기반 분산 rate limiter 미들웨어 (sliding window)
+//!
+//! 여러 서버 인스턴스가 같은 Redis를 보므로 인스턴스 수와 상관없이 클라이언트별 한도가 지켜짐.
+//!
+//! 윈도우마다 `INCR`/`EXPIRE`로 카운터를 두고, 현재 윈도우 카운트에
+//! 이전 윈도우 카운트를 "아직 겹쳐 있는 비율"만큼 더해 최근 `window` 동안의 요청 수를 추정함.
+//! (고정 윈도우 방식의 경계 시점 burst 문제를 완화)
+//!
+//! 클라이언트는 `ClientIp`(common-client-ip)로 구분 → 신뢰하는 프록시 뒤에서도 프록시가 아닌 실제 클라이언트별 한도
+
+use crate::ConnectionPool;
+use axum::{
+    extract::{Request, State},
+    http::{HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use example_common_client_ip::ClientIp;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Clone)]
+pub struct RateLimiter {
+    pool: ConnectionPool,
+    // `window` 동안 허용하는 최대 요청 수
+    limit: u64,
+    window: Duration,
+}
+
+/// `axum::middleware::from_fn_with_state`로 등록하는 미들웨어
+pub async fn rate_limit(
+    State(limiter): State<RateLimiter>,
+    ClientIp(ip): ClientIp,
+    req: Request,
+    next: Next,
+) -> Response {
+    let client = ip.to_string();
+
+    let estimated = match limiter.hit(&client).await {
+        Ok(estimated) => estimated,
+        Err(err) => {
+            // Redis 장애로 모든 요청을 막지 않도록 fail-open
+            tracing::warn!(%err, "rate limiter unavailable, letting request through");
+            return next.run(req).await;
+        }
+    };
+
+    if estimated > limiter.limit {
+        tracing::debug!(%client, estimated, "rate limit exceeded");
+        let mut res = (StatusCode::TOO_MANY_REQUESTS, "rate limit exceeded").into_response();
+        res.headers_mut().insert(
+            "retry-after",
+            HeaderValue::from(limiter.window.as_secs().max(1)),
+        );
+        limiter.set_headers(&mut res, estimated);
+        return res;
+    }
+
+    let mut res = next.run(req).await;
+    limiter.set_headers(&mut res, estimated);
+    res
+}
+
+impl RateLimiter {
+    /// `window`는 1ms 이상이어야 함 (윈도우 번호를 ms 단위로 나눠서 계산)
+    ///
+    /// # Panics
+    ///
+    /// `window`가 1ms보다 짧으면 panic (설정 오류)
+    pub fn new(pool: ConnectionPool, limit: u64, window: Duration) -> Self {
+        assert!(
+            window >= Duration::from_millis(1),
+            "rate limit window must be at least 1ms"
+        );
+        Self {
+            pool,
+            limit,
+            window,
+        }
+    }
+
+    /// 요청 한 번을 기록하고, 최근 `window` 동안의 (추정) 요청 수를 반환
+    async fn hit(&self, client: &str) -> Result<u64, String> {
+        let window_ms = self.window.as_millis() as u64;
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let current_window = now_ms / window_ms;
+
+        let current_key = format!("ratelimit:{client}:{current_window}");
+        let previous_key = format!("ratelimit:{client}:{}", current_window - 1);
+
+        let mut conn = self.pool.get().await.map_err(|err| err.to_string())?;
+
+        // MULTI/EXEC로 한 번에 실행: 현재 카운터 증가 + 만료 설정 + 이전 카운터 조회
+        // 이전 윈도우 계산에 쓰이므로 카운터는 윈도우 두 개 길이만큼 유지
+        // (ms 단위 PEXPIRE: 초 단위면 1초보다 짧은 윈도우의 TTL이 0이 되어 바로 사라짐)
+        let (current, previous): (u64, Option<u64>) = redis::pipe()
+            .atomic()
+            .incr(&current_key, 1)
+            .pexpire(&current_key, (window_ms * 2) as i64)
+            .ignore()
+            .get(&previous_key)
+            .query_async(&mut *conn)
+            .await
+            .map_err(|err| err.to_string())?;
+
+        let elapsed = (now_ms % window_ms) as f64 / window_ms as f64;
+        Ok(sliding_window_estimate(
+            previous.unwrap_or(0),
+            current,
+            elapsed,
+        ))
+    }
+
+    fn set_headers(&self, res: &mut Response, estimated: u64) {
+        let headers = res.headers_mut();
+        headers.insert("x-ratelimit-limit", HeaderValue::from(self.limit));
+        headers.insert(
+            "x-ratelimit-remaining",
+            HeaderValue::from(self.limit.saturating_sub(estimated)),
+        );
+    }
+}
+
+/// `elapsed`: 현재 윈도우가 진행된 비율 (0.0 ~ 1.0)
+///
+/// 예) 이전 윈도우 10건, 현재 윈도우 4건, 현재 윈도우 30% 진행
+///     → 이전 윈도우가 아직 70% 겹쳐 있으므로 10 * 0.7 + 4 = 11건
+fn sliding_window_estimate(previous: u64, current: u64, elapsed: f64) -> u64 {
+    let overlap = 1.0 - elapsed.clamp(0.0, 1.0);
+    (previous as f64 * overlap).floor() as u64 + current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn previous_window_is_weighted_by_overlap() {
+        assert_eq!(sliding_window_estimate(10, 4, 0.3), 11);
+        // 윈도우 시작 시점에는 이전 윈도우가 전부 반영됨
+        assert_eq!(sliding_window_estimate(10, 1, 0.0), 11);
+        // 윈도우 끝에서는 현재 윈도우만 반영됨
+        assert_eq!(sliding_window_estimate(10, 4, 1.0), 4);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "at least 1ms")]
+    async fn zero_window_is_rejected() {
+        // build_unchecked는 연결하지 않으므로 Redis 없이 만들 수 있음
+        let manager = bb8_redis::RedisConnectionManager::new("redis://localhost").unwrap();
+        let pool = bb8::Pool::builder().build_unchecked(manager);
+        RateLimiter::new(pool, 10, Duration::ZERO);
+    }
+}
@@ -0,0 +1,433 @@
+//! Redis Streams + consumer group 작업 큐
+//!
+//! • `POST /events` → `XADD`로 스트림에 추가 (`MAXLEN ~`으로 오래된 항목은 정리)
+//! • `Worker` → `XREADGROUP`으로 새 항목을 받아 처리하고 `XACK`
+//!   → 처리 중에 죽거나 실패하면 ack되지 않은 채 pending으로 남음
+//!   → `XAUTOCLAIM`으로 `min_idle` 이상 방치된 pending 항목을 가져와 다시 처리 (다른 인스턴스가 남긴 것도)
+//!   → 전달 횟수가 `max_deliveries`를 넘으면 dead letter 스트림(`events:dead`)으로 옮기고 ack
+//! • `GET /events/lag` → `XINFO GROUPS`/`XINFO CONSUMERS`로 밀린 양 보고
+//!   → `lag`(아직 그룹에 전달되지 않은 항목 수)는 Redis 7 이상에서만 제공
+//!
+//! 같은 그룹에 워커를 여러 개 띄우면 항목이 나눠서 전달됨 (at-least-once → 처리는 멱등하게)
+
+use crate::ConnectionPool;
+use redis::{
+    streams::{
+        StreamAutoClaimOptions, StreamAutoClaimReply, StreamId, StreamInfoConsumersReply,
+        StreamMaxlen, StreamPendingCountReply, StreamReadOptions, StreamReadReply,
+    },
+    AsyncCommands, FromRedisValue, RedisResult, Value,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+pub const STREAM: &str = "events";
+pub const GROUP: &str = "event-workers";
+pub const DEAD_LETTER_STREAM: &str = "events:dead";
+
+/// 스트림에 남겨 둘 대략적인 최대 항목 수
+const MAX_LEN: usize = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub payload: serde_json::Value,
+}
+
+impl Event {
+    fn from_entry(entry: &StreamId) -> Result<Self, String> {
+        let field = |name: &str| -> Result<String, String> {
+            let value = entry
+                .map
+                .get(name)
+                .ok_or_else(|| format!("missing field `{name}`"))?;
+            String::from_redis_value(value).map_err(|err| err.to_string())
+        };
+        Ok(Self {
+            kind: field("type")?,
+            payload: serde_json::from_str(&field("payload")?).map_err(|err| err.to_string())?,
+        })
+    }
+}
+
+/// 그룹이 없으면 만듦 (스트림도 없으면 `MKSTREAM`으로 같이 생성)
+///
+/// `$`: 그룹을 만든 뒤에 추가되는 항목부터 전달
+pub async fn ensure_group(pool: &ConnectionPool) -> Result<(), String> {
+    let mut conn = pool.get().await.map_err(|err| err.to_string())?;
+    let created: RedisResult<()> = conn.xgroup_create_mkstream(STREAM, GROUP, "$").await;
+    match created {
+        Ok(()) => Ok(()),
+        // 이미 있음 (다른 인스턴스가 먼저 만듦)
+        Err(err) if err.code() == Some("BUSYGROUP") => Ok(()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// 스트림에 추가하고 항목 ID를 반환
+pub async fn publish(pool: &ConnectionPool, event: &Event) -> Result<String, String> {
+    let payload = serde_json::to_string(&event.payload).map_err(|err| err.to_string())?;
+    let mut conn = pool.get().await.map_err(|err| err.to_string())?;
+    conn.xadd_maxlen(
+        STREAM,
+        StreamMaxlen::Approx(MAX_LEN),
+        "*",
+        &[("type", event.kind.as_str()), ("payload", payload.as_str())],
+    )
+    .await
+    .map_err(|err| err.to_string())
+}
+
+// 데모용 처리: "poison" 타입은 항상 실패 → 재시도 끝에 dead letter로 이동
+async fn process(id: &str, event: &Event) -> Result<(), String> {
+    if event.kind == "poison" {
+        return Err("cannot process poison event".to_owned());
+    }
+    tracing::info!(id, kind = %event.kind, payload = %event.payload, "processed event");
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub struct WorkerConfig {
+    /// 그룹 안에서 이 워커의 이름 (인스턴스마다 달라야 함)
+    pub consumer: String,
+    pub batch: usize,
+    /// `XREADGROUP BLOCK` 시간 (새 항목이 없을 때 기다리는 시간)
+    pub block: Duration,
+    /// 이 시간 이상 ack되지 않은 항목은 죽은 워커가 남긴 것으로 보고 가져옴
+    pub min_idle: Duration,
+    pub max_deliveries: usize,
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        Self {
+            consumer: format!("worker-{}", std::process::id()),
+            batch: 16,
+            block: Duration::from_secs(5),
+            min_idle: Duration::from_secs(30),
+            max_deliveries: 5,
+        }
+    }
+}
+
+pub struct Worker {
+    pool: ConnectionPool,
+    config: WorkerConfig,
+    // XAUTOCLAIM은 스트림을 나눠서 훑음 → 다음에 이어서 볼 위치
+    claim_cursor: String,
+}
+
+impl Worker {
+    pub fn new(pool: ConnectionPool, config: WorkerConfig) -> Self {
+        Self {
+            pool,
+            config,
+            claim_cursor: "0-0".to_owned(),
+        }
+    }
+
+    pub async fn run(mut self) {
+        tracing::debug!(consumer = %self.config.consumer, "stream worker started");
+        // 시작하자마자 한 번 회수 (재시작 전에 처리하던 항목)
+        let mut last_recovery: Option<Instant> = None;
+
+        loop {
+            if last_recovery.is_none_or(|at| at.elapsed() >= self.config.min_idle) {
+                if let Err(err) = self.recover().await {
+                    tracing::warn!(%err, "failed to claim pending events");
+                }
+                last_recovery = Some(Instant::now());
+            }
+
+            if let Err(err) = self.read_new().await {
+                tracing::warn!(%err, "failed to read events, retrying");
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        }
+    }
+
+    /// 새 항목(`>`)을 받아 처리
+    async fn read_new(&mut self) -> Result<(), String> {
+        let options = StreamReadOptions::default()
+            .group(GROUP, &self.config.consumer)
+            .count(self.config.batch)
+            .block(self.config.block.as_millis() as usize);
+        let reply: StreamReadReply = {
+            let mut conn = self.pool.get().await.map_err(|err| err.to_string())?;
+            conn.xread_options(&[STREAM], &[">"], &options)
+                .await
+                .map_err(|err| err.to_string())?
+        };
+
+        for entry in reply.keys.into_iter().flat_map(|key| key.ids) {
+            self.handle(&entry).await?;
+        }
+        Ok(())
+    }
+
+    /// 오래 pending 상태인 항목을 이 워커로 가져와 다시 처리
+    async fn recover(&mut self) -> Result<(), String> {
+        let mut conn = self.pool.get().await.map_err(|err| err.to_string())?;
+        let reply: StreamAutoClaimReply = conn
+            .xautoclaim_options(
+                STREAM,
+                GROUP,
+                &self.config.consumer,
+                self.config.min_idle.as_millis() as u64,
+                &self.claim_cursor,
+                StreamAutoClaimOptions::default().count(self.config.batch),
+            )
+            .await
+            .map_err(|err| err.to_string())?;
+        // "0-0"이 돌아오면 한 바퀴 다 훑은 것 → 다음에는 처음부터
+        self.claim_cursor = reply.next_stream_id;
+
+        if !reply.deleted_ids.is_empty() {
+            // MAXLEN으로 정리돼 내용이 사라진 항목 (XAUTOCLAIM이 pending 목록에서 지움)
+            tracing::warn!(ids = ?reply.deleted_ids, "pending events were trimmed before processing");
+        }
+        let (Some(first), Some(last)) = (reply.claimed.first(), reply.claimed.last()) else {
+            return Ok(());
+        };
+
+        // 전달 횟수 (XAUTOCLAIM도 한 번으로 셈)
+        let pending: StreamPendingCountReply = conn
+            .xpending_consumer_count(
+                STREAM,
+                GROUP,
+                &first.id,
+                &last.id,
+                reply.claimed.len(),
+                &self.config.consumer,
+            )
+            .await
+            .map_err(|err| err.to_string())?;
+        drop(conn);
+        let deliveries: HashMap<_, _> = pending
+            .ids
+            .into_iter()
+            .map(|pending| (pending.id, pending.times_delivered))
+            .collect();
+
+        tracing::info!(count = reply.claimed.len(), "claimed pending events");
+        for entry in &reply.claimed {
+            let delivered = deliveries.get(&entry.id).copied().unwrap_or(1);
+            if delivered > self.config.max_deliveries {
+                self.dead_letter(entry, &format!("delivered {delivered} times"))
+                    .await?;
+            } else {
+                self.handle(entry).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 처리에 성공했을 때만 ack (실패하면 pending으로 남아 나중에 `recover`)
+    async fn handle(&self, entry: &StreamId) -> Result<(), String> {
+        let event = match Event::from_entry(entry) {
+            Ok(event) => event,
+            // 몇 번을 다시 시도해도 읽을 수 없음
+            Err(err) => return self.dead_letter(entry, &err).await,
+        };
+
+        match process(&entry.id, &event).await {
+            Ok(()) => {
+                let mut conn = self.pool.get().await.map_err(|err| err.to_string())?;
+                conn.xack(STREAM, GROUP, &[&entry.id])
+                    .await
+                    .map_err(|err| err.to_string())
+            }
+            Err(err) => {
+                tracing::warn!(id = %entry.id, %err, "event failed, will be retried");
+                Ok(())
+            }
+        }
+    }
+
+    /// 원래 필드에 원래 ID와 이유를 붙여 dead letter 스트림으로 옮기고 ack
+    async fn dead_letter(&self, entry: &StreamId, reason: &str) -> Result<(), String> {
+        tracing::error!(id = %entry.id, reason, "moving event to dead letter stream");
+        let mut fields: Vec<(&str, Vec<u8>)> = entry
+            .map
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((name.as_str(), FromRedisValue::from_redis_value(value).ok()?))
+            })
+            .collect();
+        fields.push(("original_id", entry.id.clone().into_bytes()));
+        fields.push(("reason", reason.as_bytes().to_vec()));
+
+        let mut conn = self.pool.get().await.map_err(|err| err.to_string())?;
+        redis::pipe()
+            .atomic()
+            .xadd_maxlen(
+                DEAD_LETTER_STREAM,
+                StreamMaxlen::Approx(MAX_LEN),
+                "*",
+                &fields,
+            )
+            .ignore()
+            .xack(STREAM, GROUP, &[&entry.id])
+            .ignore()
+            .query_async::<()>(&mut *conn)
+            .await
+            .map_err(|err| err.to_string())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConsumerLag {
+    pub name: String,
+    pub pending: usize,
+    pub idle_ms: usize,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct GroupLag {
+    /// 그룹에 아직 전달되지 않은 항목 수 (Redis 7 미만이거나 계산할 수 없으면 null)
+    pub lag: Option<u64>,
+    /// 전달됐지만 ack되지 않은 항목 수
+    pub pending: u64,
+    pub last_delivered_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Lag {
+    pub length: u64,
+    #[serde(flatten)]
+    pub group: GroupLag,
+    pub dead_letters: u64,
+    pub consumers: Vec<ConsumerLag>,
+}
+
+pub async fn lag(pool: &ConnectionPool) -> Result<Lag, String> {
+    let mut conn = pool.get().await.map_err(|err| err.to_string())?;
+    let (length, dead_letters, groups, consumers): (u64, u64, Value, StreamInfoConsumersReply) =
+        redis::pipe()
+            .xlen(STREAM)
+            .xlen(DEAD_LETTER_STREAM)
+            .xinfo_groups(STREAM)
+            .xinfo_consumers(STREAM, GROUP)
+            .query_async(&mut *conn)
+            .await
+            .map_err(|err| err.to_string())?;
+
+    Ok(Lag {
+        length,
+        group: group_lag(&groups, GROUP)
+            .map_err(|err| err.to_string())?
+            .ok_or_else(|| format!("consumer group `{GROUP}` not found"))?,
+        dead_letters,
+        consumers: consumers
+            .consumers
+            .into_iter()
+            .map(|consumer| ConsumerLag {
+                name: consumer.name,
+                pending: consumer.pending,
+                idle_ms: consumer.idle,
+            })
+            .collect(),
+    })
+}
+
+// `XINFO GROUPS` 응답에서 그룹 하나를 찾음
+// (redis 크레이트의 `StreamInfoGroupsReply`에는 Redis 7에서 추가된 `lag`가 없어서 직접 읽음)
+fn group_lag(groups: &Value, name: &str) -> RedisResult<Option<GroupLag>> {
+    let groups: Vec<HashMap<String, Value>> = FromRedisValue::from_redis_value(groups)?;
+    let Some(group) = groups.into_iter().find(|group| {
+        group
+            .get("name")
+            .and_then(|value| String::from_redis_value(value).ok())
+            .is_some_and(|group_name| group_name == name)
+    }) else {
+        return Ok(None);
+    };
+
+    let get = |field: &str| group.get(field).cloned().unwrap_or(Value::Nil);
+    Ok(Some(GroupLag {
+        lag: FromRedisValue::from_redis_value(&get("lag"))?,
+        pending: FromRedisValue::from_redis_value(&get("pending"))?,
+        last_delivered_id: FromRedisValue::from_redis_value(&get("last-delivered-id"))?,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(value: &str) -> Value {
+        Value::BulkString(value.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn events_are_read_from_stream_entries() {
+        let entry = StreamId {
+            id: "1-0".to_owned(),
+            map: HashMap::from([
+                ("type".to_owned(), bulk("signup")),
+                ("payload".to_owned(), bulk(r#"{"user":1}"#)),
+            ]),
+        };
+        assert_eq!(
+            Event::from_entry(&entry),
+            Ok(Event {
+                kind: "signup".to_owned(),
+                payload: serde_json::json!({ "user": 1 }),
+            })
+        );
+
+        let broken = StreamId {
+            id: "2-0".to_owned(),
+            map: HashMap::from([("type".to_owned(), bulk("signup"))]),
+        };
+        assert_eq!(
+            Event::from_entry(&broken),
+            Err("missing field `payload`".to_owned())
+        );
+    }
+
+    #[test]
+    fn group_lag_is_read_from_xinfo_groups() {
+        // Redis 7의 XINFO GROUPS 응답 (RESP2: 그룹마다 필드/값이 번갈아 나오는 배열)
+        let group = |name: &str, lag: Value| {
+            Value::Array(vec![
+                bulk("name"),
+                bulk(name),
+                bulk("consumers"),
+                Value::Int(2),
+                bulk("pending"),
+                Value::Int(3),
+                bulk("last-delivered-id"),
+                bulk("1700000000000-5"),
+                bulk("entries-read"),
+                Value::Int(10),
+                bulk("lag"),
+                lag,
+            ])
+        };
+        let reply = Value::Array(vec![
+            group("other", Value::Int(0)),
+            group(GROUP, Value::Int(7)),
+        ]);
+
+        assert_eq!(
+            group_lag(&reply, GROUP).unwrap(),
+            Some(GroupLag {
+                lag: Some(7),
+                pending: 3,
+                last_delivered_id: "1700000000000-5".to_owned(),
+            })
+        );
+        assert_eq!(group_lag(&reply, "missing").unwrap(), None);
+
+        // 계산할 수 없으면 nil
+        let reply = Value::Array(vec![group(GROUP, Value::Nil)]);
+        assert_eq!(group_lag(&reply, GROUP).unwrap().unwrap().lag, None);
+    }
+}
@@ -0,0 +1,92 @@
+// history 모드 라우팅: 주소는 `/notes/1`처럼 바뀌지만 페이지는 다시 불러오지 않음
+// (새로 고침하거나 주소를 직접 입력하면 서버가 index.html을 돌려주고 여기서 다시 그림)
+
+const app = document.querySelector("#app");
+
+async function api(path, options) {
+    const response = await fetch(`/api${path}`, options);
+    const body = await response.json();
+    if (!response.ok) {
+        throw new Error(body.error);
+    }
+    return body;
+}
+
+async function listPage() {
+    const notes = await api("/notes");
+    app.replaceChildren();
+
+    const list = document.createElement("ul");
+    for (const note of notes) {
+        const item = document.createElement("li");
+        const link = document.createElement("a");
+        link.href = `/notes/${note.id}`;
+        link.dataset.link = "";
+        link.textContent = note.title;
+        item.append(link);
+        list.append(item);
+    }
+
+    const form = document.createElement("form");
+    const input = document.createElement("input");
+    input.placeholder = "new note";
+    form.append(input);
+    form.onsubmit = async (event) => {
+        event.preventDefault();
+        const note = await api("/notes", {
+            method: "POST",
+            headers: { "Content-Type": "application/json" },
+            body: JSON.stringify({ title: input.value }),
+        });
+        navigate(`/notes/${note.id}`);
+    };
+
+    app.append(list, form);
+}
+
+async function notePage(id) {
+    const note = await api(`/notes/${id}`);
+    const title = document.createElement("h1");
+    title.textContent = note.title;
+    app.replaceChildren(title);
+}
+
+function notFoundPage() {
+    const message = document.createElement("p");
+    message.textContent = `No page at ${location.pathname}`;
+    app.replaceChildren(message);
+}
+
+async function render() {
+    const note = location.pathname.match(/^\/notes\/(\d+)$/);
+    try {
+        if (location.pathname === "/") {
+            await listPage();
+        } else if (note) {
+            await notePage(note[1]);
+        } else {
+            notFoundPage();
+        }
+    } catch (err) {
+        const message = document.createElement("p");
+        message.className = "error";
+        message.textContent = err.message;
+        app.replaceChildren(message);
+    }
+}
+
+function navigate(path) {
+    history.pushState(null, "", path);
+    render();
+}
+
+// `data-link`가 붙은 링크는 페이지를 다시 불러오지 않고 라우팅
+document.addEventListener("click", (event) => {
+    const link = event.target.closest("a[data-link]");
+    if (link) {
+        event.preventDefault();
+        navigate(link.getAttribute("href"));
+    }
+});
+window.addEventListener("popstate", render);
+render();
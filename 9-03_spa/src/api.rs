@@ -0,0 +1,90 @@
+//! 🗒️ `/api/*` JSON 라우트
+//!
+//! • 핸들러의 에러는 JSON (`{"error": "..."}`) → 프런트엔드가 응답 형식 하나만 처리하면 됨
+//!   → 추출기 거부(잘못된 JSON 본문 등)까지 JSON으로 바꾸려면 2-03_customize-extractor-error 참고
+//! • 없는 `/api` 경로는 index.html이 아니라 JSON 404 (SPA fallback보다 먼저 처리)
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::{Arc, RwLock};
+
+#[derive(Clone, Default)]
+pub struct AppState {
+    notes: Arc<RwLock<Vec<Note>>>,
+}
+
+#[derive(Clone, Serialize)]
+struct Note {
+    id: u64,
+    title: String,
+}
+
+#[derive(Deserialize)]
+struct CreateNote {
+    title: String,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/notes", get(list_notes).post(create_note))
+        .route("/notes/{id}", get(get_note))
+        .fallback(|| async { ApiError::NotFound })
+}
+
+async fn list_notes(State(state): State<AppState>) -> Json<Vec<Note>> {
+    Json(state.notes.read().unwrap().clone())
+}
+
+async fn create_note(
+    State(state): State<AppState>,
+    Json(input): Json<CreateNote>,
+) -> Result<(StatusCode, Json<Note>), ApiError> {
+    let title = input.title.trim();
+    if title.is_empty() {
+        return Err(ApiError::InvalidTitle);
+    }
+    let mut notes = state.notes.write().unwrap();
+    let note = Note {
+        id: notes.len() as u64 + 1,
+        title: title.to_owned(),
+    };
+    notes.push(note.clone());
+    Ok((StatusCode::CREATED, Json(note)))
+}
+
+async fn get_note(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+) -> Result<Json<Note>, ApiError> {
+    state
+        .notes
+        .read()
+        .unwrap()
+        .iter()
+        .find(|note| note.id == id)
+        .cloned()
+        .map(Json)
+        .ok_or(ApiError::NotFound)
+}
+
+enum ApiError {
+    NotFound,
+    InvalidTitle,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            Self::NotFound => (StatusCode::NOT_FOUND, "not found"),
+            Self::InvalidTitle => (StatusCode::UNPROCESSABLE_ENTITY, "title must not be empty"),
+        };
+        (status, Json(json!({ "error": message }))).into_response()
+    }
+}
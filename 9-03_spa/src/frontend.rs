@@ -0,0 +1,88 @@
+//! 🖥️ 바이너리에 포함한 프런트엔드 (`frontend/` 디렉토리) + history 모드 fallback
+//!
+//! | 요청                    | 응답                                              |
+//! |-------------------------|---------------------------------------------------|
+//! | `GET /app.js` 등        | 포함된 파일                                       |
+//! | `GET /notes/1` 등       | index.html (클라이언트 라우터가 화면을 그림)      |
+//! | `GET /missing.js`       | 404 (확장자가 있는 경로는 파일 요청으로 봄)       |
+//! | `POST /notes/1` 등      | 405 (fallback은 GET/HEAD만)                       |
+//!
+//! • 보안 헤더(CSP 등)는 HTML 응답에만 붙임 → JS/CSS/JSON 응답에는 의미가 없음
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use rust_embed::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "frontend/"]
+struct Frontend;
+
+// 인라인 스크립트와 외부 출처를 모두 막음 (모든 스크립트/스타일은 같은 출처의 파일로)
+const CONTENT_SECURITY_POLICY: &str = "default-src 'self'; script-src 'self'; \
+    style-src 'self'; img-src 'self' data:; connect-src 'self'; \
+    object-src 'none'; base-uri 'self'; form-action 'self'; frame-ancestors 'none'";
+
+/// `/api`를 제외한 모든 GET 요청
+pub async fn serve(request: Request) -> Response {
+    let path = request.uri().path().trim_start_matches('/');
+    if let Some(response) = file(path) {
+        return response;
+    }
+    // 클라이언트 라우터의 경로(`/notes/1`)에는 점이 없음
+    let last_segment = path.rsplit('/').next().unwrap_or_default();
+    if last_segment.contains('.') {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    file("index.html").expect("frontend/index.html is embedded")
+}
+
+fn file(path: &str) -> Option<Response> {
+    let path = if path.is_empty() { "index.html" } else { path };
+    let file = Frontend::get(path)?;
+    let cache_control = if path == "index.html" {
+        // 배포하면 바로 새 버전을 받도록 매번 확인
+        "no-cache"
+    } else {
+        "public, max-age=3600"
+    };
+    Some(
+        (
+            [
+                (header::CONTENT_TYPE, file.metadata.mimetype()),
+                (header::CACHE_CONTROL, cache_control),
+            ],
+            Body::from(file.data),
+        )
+            .into_response(),
+    )
+}
+
+/// HTML 응답에만 보안 헤더 추가 (`map_response` 미들웨어)
+pub async fn security_headers(mut response: Response) -> Response {
+    let is_html = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/html"));
+    if !is_html {
+        return response;
+    }
+    let headers = response.headers_mut();
+    for (name, value) in [
+        (header::CONTENT_SECURITY_POLICY, CONTENT_SECURITY_POLICY),
+        (header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+        (header::X_FRAME_OPTIONS, "DENY"),
+        (header::REFERRER_POLICY, "no-referrer"),
+        (
+            HeaderName::from_static("permissions-policy"),
+            "camera=(), microphone=(), geolocation=()",
+        ),
+    ] {
+        headers.insert(name, HeaderValue::from_static(value));
+    }
+    response
+}
@@ -0,0 +1,159 @@
+//! 🧩 SPA + API를 바이너리 하나로 배포하는 예제
+//!
+//! ```not_rust
+//! cargo run -p example-spa
+//! ```
+//!
+//! • `/api/*`: 상태를 쓰는 JSON 라우트 (`api.rs`), 없는 경로도 JSON 404
+//! • 나머지: `frontend/`를 바이너리에 포함해서 서비스, 파일이 아니면 index.html (`frontend.rs`)
+//!   → 브라우저에서 `/notes/1`을 새로 고침해도 클라이언트 라우터가 같은 화면을 그림
+//! • CSP 등 보안 헤더는 HTML 응답에만 추가
+
+mod api;
+mod frontend;
+
+use axum::{middleware, routing::get, Router};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app(api::AppState::default()))
+        .await
+        .unwrap();
+}
+
+fn app(state: api::AppState) -> Router {
+    Router::new()
+        // `/api`의 fallback이 먼저 적용되므로 없는 API 경로가 index.html로 가지 않음
+        .nest("/api", api::router())
+        // GET/HEAD가 아닌 요청은 405
+        .fallback(get(frontend::serve))
+        .layer(middleware::map_response(frontend::security_headers))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header, Request, Response, StatusCode},
+    };
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    async fn send(app: &Router, method: &str, uri: &str, body: Option<Value>) -> Response<Body> {
+        let request = Request::builder().method(method).uri(uri);
+        let request = match body {
+            Some(body) => request
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => request.body(Body::empty()),
+        }
+        .unwrap();
+        app.clone().oneshot(request).await.unwrap()
+    }
+
+    async fn json(response: Response<Body>) -> Value {
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    async fn text(response: Response<Body>) -> String {
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn client_routes_fall_back_to_index_html() {
+        let app = app(Default::default());
+
+        for uri in ["/", "/notes/1", "/some/deep/link?tab=2"] {
+            let response = send(&app, "GET", uri, None).await;
+            assert_eq!(response.status(), StatusCode::OK, "{uri}");
+            assert_eq!(
+                response.headers()[header::CONTENT_TYPE],
+                "text/html",
+                "{uri}"
+            );
+            assert!(response
+                .headers()
+                .contains_key(header::CONTENT_SECURITY_POLICY));
+            assert!(text(response).await.contains("<main id=\"app\">"));
+        }
+
+        // 파일은 그대로, 보안 헤더 없음
+        let response = send(&app, "GET", "/app.js", None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/javascript");
+        assert!(!response
+            .headers()
+            .contains_key(header::CONTENT_SECURITY_POLICY));
+
+        // 없는 파일은 index.html이 아니라 404
+        let response = send(&app, "GET", "/missing.js", None).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = send(&app, "POST", "/notes/1", None).await;
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    #[tokio::test]
+    async fn api_routes_answer_with_json_even_when_missing() {
+        let app = app(Default::default());
+
+        let response = send(&app, "POST", "/api/notes", Some(json!({ "title": "hi" }))).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert!(!response
+            .headers()
+            .contains_key(header::CONTENT_SECURITY_POLICY));
+        assert_eq!(json(response).await, json!({ "id": 1, "title": "hi" }));
+
+        let response = send(&app, "GET", "/api/notes/1", None).await;
+        assert_eq!(json(response).await["title"], "hi");
+
+        for uri in [
+            "/api",
+            "/api/missing",
+            "/api/notes/1/comments",
+            "/api/notes/2",
+        ] {
+            let response = send(&app, "GET", uri, None).await;
+            assert_eq!(response.status(), StatusCode::NOT_FOUND, "{uri}");
+            assert_eq!(json(response).await, json!({ "error": "not found" }));
+        }
+
+        let response = send(&app, "POST", "/api/notes", Some(json!({ "title": " " }))).await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
+
+// 🧪 테스트 방법
+//
+// cargo run -p example-spa
+//
+// # 브라우저에서 http://127.0.0.1:3000 → 노트를 추가하면 주소가 /notes/1로 바뀜
+// #   → 새로 고침해도 같은 화면 (서버가 index.html을 돌려줌)
+//
+// # history 모드 fallback + 보안 헤더
+// curl -i http://127.0.0.1:3000/notes/1
+//
+// # API와 JSON 404
+// curl -X POST -H 'Content-Type: application/json' -d '{"title":"hello"}' http://127.0.0.1:3000/api/notes
+// curl -i http://127.0.0.1:3000/api/does-not-exist
+//
+// # 없는 정적 파일은 index.html이 아니라 404
+// curl -i http://127.0.0.1:3000/missing.js
@@ -0,0 +1,431 @@
+//! 예제들이 함께 쓰는 클라이언트 IP 추출기
+//!
+//! 리버스 프록시/로드 밸런서 뒤에서는 `ConnectInfo`가 프록시 주소가 됨.
+//! `ClientIp`는 프록시가 붙인 `X-Forwarded-For` 또는 `Forwarded`(RFC 7239)에서 원래 주소를 찾음.
+//!
+//! ```toml
+//! example-common-client-ip = { path = "../common-client-ip" }
+//! ```
+//!
+//! ```rust,ignore
+//! let trusted: TrustedProxies = "10.0.0.0/8, 127.0.0.1".parse().unwrap();
+//! let app = Router::new()
+//!     .route("/", get(|ClientIp(ip): ClientIp| async move { ip.to_string() }))
+//!     .layer(Extension(trusted));
+//! axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await
+//! ```
+//!
+//! • TCP peer가 신뢰하는 프록시(CIDR 목록)일 때만 헤더를 봄
+//!   → 그렇지 않으면 헤더는 클라이언트가 마음대로 넣은 값이므로 무시하고 peer 주소 사용
+//! • 헤더 목록을 오른쪽(가장 가까운 프록시가 붙인 값)부터 보며 신뢰하지 않는 첫 주소가 클라이언트
+//!   → 클라이언트가 미리 넣어 둔 왼쪽 값은 사용되지 않음
+//!   → `unknown`, `_hidden` 같이 IP가 아닌 값을 만나면 거기서 멈추고 직전 주소 사용
+//! • 어느 헤더를 볼지는 설정으로 하나만 고름 (기본 `X-Forwarded-For`, `.header(ForwardedHeader::Forwarded)`)
+//!   → 요청에 어떤 헤더가 있는지로 고르면, XFF만 덧붙이는 프록시 뒤에서 클라이언트가 넣은
+//!     `Forwarded: for=...`가 그대로 통과해 주소를 마음대로 정할 수 있음
+//! • `TrustedProxies` extension이 없으면 아무 프록시도 신뢰하지 않음 (= `ConnectInfo`와 같음)
+
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::{header, request::Parts, HeaderMap, HeaderName},
+};
+use std::{
+    fmt,
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+    sync::Arc,
+};
+
+const X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
+
+/// 요청을 보낸 클라이언트의 IP 주소
+///
+/// `into_make_service_with_connect_info::<SocketAddr>()`로 서버를 실행해야 함.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub IpAddr);
+
+impl<S: Send + Sync> FromRequestParts<S> for ClientIp {
+    type Rejection = <ConnectInfo<SocketAddr> as FromRequestParts<S>>::Rejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let ConnectInfo(peer) = ConnectInfo::<SocketAddr>::from_request_parts(parts, state).await?;
+        let ip = match parts.extensions.get::<TrustedProxies>() {
+            Some(trusted) => trusted.client_ip(peer.ip(), &parts.headers),
+            None => peer.ip().to_canonical(),
+        };
+        Ok(Self(ip))
+    }
+}
+
+/// 신뢰하는 프록시 주소 목록, `Extension`으로 라우터에 추가
+///
+/// `"10.0.0.0/8, 192.168.1.10, ::1"`처럼 쉼표로 구분한 문자열에서 만듦 (빈 문자열은 빈 목록)
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    cidrs: Arc<[Cidr]>,
+    header: ForwardedHeader,
+}
+
+/// 프록시가 클라이언트 주소를 붙이는 헤더 (프록시 설정에 맞춰 하나만)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ForwardedHeader {
+    #[default]
+    XForwardedFor,
+    /// RFC 7239 `Forwarded: for=...`
+    Forwarded,
+}
+
+impl FromStr for ForwardedHeader {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("x-forwarded-for") {
+            Ok(Self::XForwardedFor)
+        } else if s.eq_ignore_ascii_case("forwarded") {
+            Ok(Self::Forwarded)
+        } else {
+            Err(format!(
+                "unknown forwarded header {s:?} (expected x-forwarded-for or forwarded)"
+            ))
+        }
+    }
+}
+
+impl TrustedProxies {
+    /// 주소를 읽을 헤더 (다른 헤더는 클라이언트가 넣었을 수 있으므로 무시)
+    pub fn header(mut self, header: ForwardedHeader) -> Self {
+        self.header = header;
+        self
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        self.cidrs.iter().any(|cidr| cidr.contains(ip))
+    }
+
+    /// TCP peer 주소와 요청 헤더로 클라이언트 주소를 결정
+    pub fn client_ip(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        let mut client = peer.to_canonical();
+        if !self.contains(client) {
+            return client;
+        }
+        for hop in forwarded_chain(headers, self.header).into_iter().rev() {
+            let Some(ip) = hop else { break };
+            client = ip.to_canonical();
+            if !self.contains(client) {
+                break;
+            }
+        }
+        client
+    }
+}
+
+impl FromStr for TrustedProxies {
+    type Err = InvalidCidr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|cidr| !cidr.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .map(|cidrs| Self {
+                cidrs: cidrs.into(),
+                header: ForwardedHeader::default(),
+            })
+    }
+}
+
+/// `10.0.0.0/8`, `2001:db8::/32` 같은 주소 범위 (`/` 없으면 주소 하나)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cidr {
+    network: IpAddr,
+    prefix: u8,
+}
+
+impl Cidr {
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.network, ip.to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                u32::from(ip) & v4_mask(self.prefix) == u32::from(network)
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                u128::from(ip) & v6_mask(self.prefix) == u128::from(network)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for Cidr {
+    type Err = InvalidCidr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidCidr(s.to_owned());
+        let (ip, prefix) = match s.split_once('/') {
+            Some((ip, prefix)) => (ip, Some(prefix.parse::<u8>().map_err(|_| invalid())?)),
+            None => (s, None),
+        };
+        // 네트워크 주소의 호스트 비트는 지움 (10.1.2.3/8 → 10.0.0.0/8)
+        let (network, prefix) = match ip.parse::<IpAddr>().map_err(|_| invalid())? {
+            IpAddr::V4(ip) => {
+                let prefix = prefix.unwrap_or(32);
+                if prefix > 32 {
+                    return Err(invalid());
+                }
+                (
+                    IpAddr::from((u32::from(ip) & v4_mask(prefix)).to_be_bytes()),
+                    prefix,
+                )
+            }
+            IpAddr::V6(ip) => {
+                let prefix = prefix.unwrap_or(128);
+                if prefix > 128 {
+                    return Err(invalid());
+                }
+                (
+                    IpAddr::from((u128::from(ip) & v6_mask(prefix)).to_be_bytes()),
+                    prefix,
+                )
+            }
+        };
+        Ok(Self { network, prefix })
+    }
+}
+
+fn v4_mask(prefix: u8) -> u32 {
+    u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0)
+}
+
+fn v6_mask(prefix: u8) -> u128 {
+    u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidCidr(String);
+
+impl fmt::Display for InvalidCidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid IP address or CIDR range: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidCidr {}
+
+/// 프록시들이 붙인 주소 목록 (왼쪽이 클라이언트 쪽), IP가 아닌 값은 `None`
+///
+/// 같은 헤더가 여러 줄이면 순서대로 이어 붙인 것과 같음
+fn forwarded_chain(headers: &HeaderMap, header: ForwardedHeader) -> Vec<Option<IpAddr>> {
+    let (name, is_forwarded) = match header {
+        ForwardedHeader::XForwardedFor => (X_FORWARDED_FOR, false),
+        ForwardedHeader::Forwarded => (header::FORWARDED, true),
+    };
+
+    let mut chain = Vec::new();
+    for value in headers.get_all(name) {
+        let Ok(value) = value.to_str() else {
+            chain.push(None);
+            continue;
+        };
+        for element in value.split(',') {
+            let node = if is_forwarded {
+                // for=192.0.2.60;proto=http;by=203.0.113.43
+                element.split(';').find_map(|pair| {
+                    let (key, value) = pair.trim().split_once('=')?;
+                    key.eq_ignore_ascii_case("for").then_some(value)
+                })
+            } else {
+                Some(element)
+            };
+            chain.push(node.and_then(parse_node));
+        }
+    }
+    chain
+}
+
+/// `192.0.2.60`, `192.0.2.60:4711`, `"[2001:db8::17]:4711"`, `2001:db8::17`
+fn parse_node(node: &str) -> Option<IpAddr> {
+    let node = node.trim().trim_matches('"');
+    if let Some(rest) = node.strip_prefix('[') {
+        let (ip, _port) = rest.split_once(']')?;
+        return ip.parse().ok();
+    }
+    node.parse()
+        .or_else(|_| node.parse::<SocketAddr>().map(|addr| addr.ip()))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::Request, http::HeaderValue};
+
+    fn ip(ip: &str) -> IpAddr {
+        ip.parse().unwrap()
+    }
+
+    fn headers(pairs: &[(&str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(
+                HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                HeaderValue::from_str(value).unwrap(),
+            );
+        }
+        headers
+    }
+
+    #[test]
+    fn cidr_ranges_match_addresses() {
+        let trusted: TrustedProxies = "10.0.0.0/8, 192.168.1.10,2001:db8::/32, 172.16.5.4/12"
+            .parse()
+            .unwrap();
+        for inside in [
+            "10.0.0.1",
+            "10.255.255.255",
+            "192.168.1.10",
+            "2001:db8::1",
+            "172.31.0.1",
+            // IPv4-mapped IPv6 (듀얼 스택 소켓)
+            "::ffff:10.1.2.3",
+        ] {
+            assert!(trusted.contains(ip(inside)), "{inside}");
+        }
+        for outside in [
+            "11.0.0.1",
+            "192.168.1.11",
+            "2001:db9::1",
+            "172.32.0.1",
+            "::1",
+        ] {
+            assert!(!trusted.contains(ip(outside)), "{outside}");
+        }
+
+        let everything: TrustedProxies = "0.0.0.0/0, ::/0".parse().unwrap();
+        assert!(everything.contains(ip("203.0.113.7")));
+        assert!(everything.contains(ip("2001:db8::1")));
+        assert!(!TrustedProxies::default().contains(ip("127.0.0.1")));
+        assert_eq!("".parse::<TrustedProxies>().unwrap().cidrs.len(), 0);
+
+        for invalid in [
+            "10.0.0.0/33",
+            "::/129",
+            "10.0.0/8",
+            "localhost",
+            "10.0.0.0/x",
+        ] {
+            assert!(invalid.parse::<Cidr>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn headers_are_ignored_unless_the_peer_is_trusted() {
+        let trusted: TrustedProxies = "10.0.0.0/8".parse().unwrap();
+        let spoofed = headers(&[("x-forwarded-for", "1.2.3.4")]);
+
+        assert_eq!(
+            trusted.client_ip(ip("203.0.113.7"), &spoofed),
+            ip("203.0.113.7")
+        );
+        assert_eq!(trusted.client_ip(ip("10.0.0.2"), &spoofed), ip("1.2.3.4"));
+        // 헤더가 없으면 프록시 주소 그대로
+        assert_eq!(
+            trusted.client_ip(ip("10.0.0.2"), &HeaderMap::new()),
+            ip("10.0.0.2")
+        );
+    }
+
+    #[test]
+    fn picks_the_rightmost_untrusted_address() {
+        let trusted: TrustedProxies = "10.0.0.0/8".parse().unwrap();
+        let peer = ip("10.0.0.2");
+
+        // 클라이언트가 미리 넣은 1.2.3.4는 무시, 프록시 두 개(10.x)를 거쳐 온 203.0.113.7
+        let chain = headers(&[("x-forwarded-for", "1.2.3.4, 203.0.113.7, 10.0.0.3")]);
+        assert_eq!(trusted.client_ip(peer, &chain), ip("203.0.113.7"));
+
+        // 헤더가 여러 줄이어도 하나의 목록
+        let chain = headers(&[
+            ("x-forwarded-for", "1.2.3.4"),
+            ("x-forwarded-for", "203.0.113.7 , 10.0.0.3"),
+        ]);
+        assert_eq!(trusted.client_ip(peer, &chain), ip("203.0.113.7"));
+
+        // 모두 신뢰하는 주소면 가장 왼쪽
+        let chain = headers(&[("x-forwarded-for", "10.9.9.9, 10.0.0.3")]);
+        assert_eq!(trusted.client_ip(peer, &chain), ip("10.9.9.9"));
+
+        // IP가 아닌 값에서 멈춤
+        let chain = headers(&[("x-forwarded-for", "1.2.3.4, garbage, 10.0.0.3")]);
+        assert_eq!(trusted.client_ip(peer, &chain), ip("10.0.0.3"));
+
+        // 포트와 IPv6
+        let chain = headers(&[("x-forwarded-for", "203.0.113.7:4711")]);
+        assert_eq!(trusted.client_ip(peer, &chain), ip("203.0.113.7"));
+        let chain = headers(&[("x-forwarded-for", "2001:db8::17")]);
+        assert_eq!(trusted.client_ip(peer, &chain), ip("2001:db8::17"));
+    }
+
+    #[test]
+    fn only_the_configured_header_is_used() {
+        let trusted: TrustedProxies = "10.0.0.0/8".parse().unwrap();
+        let peer = ip("10.0.0.2");
+
+        // XFF만 덧붙이는 프록시 뒤에서 클라이언트가 직접 넣은 Forwarded는 무시
+        let injected = headers(&[
+            ("forwarded", "for=8.8.8.8"),
+            ("x-forwarded-for", "203.0.113.7, 10.0.0.3"),
+        ]);
+        assert_eq!(trusted.client_ip(peer, &injected), ip("203.0.113.7"));
+        let injected = headers(&[("forwarded", "for=8.8.8.8")]);
+        assert_eq!(trusted.client_ip(peer, &injected), peer);
+
+        // Forwarded로 설정하면 반대로 XFF를 무시
+        let trusted = trusted.header(ForwardedHeader::Forwarded);
+        let chain = headers(&[
+            ("x-forwarded-for", "1.2.3.4"),
+            (
+                "forwarded",
+                r#"for=1.2.3.4, For="[2001:db8:cafe::17]:4711";proto=https, for=10.0.0.3;by=10.0.0.2"#,
+            ),
+        ]);
+        assert_eq!(trusted.client_ip(peer, &chain), ip("2001:db8:cafe::17"));
+        let chain = headers(&[("x-forwarded-for", "1.2.3.4")]);
+        assert_eq!(trusted.client_ip(peer, &chain), peer);
+
+        // 숨긴 주소(obfuscated)나 `for`가 없는 요소에서 멈춤
+        let chain = headers(&[("forwarded", "for=1.2.3.4, for=_hidden, for=10.0.0.3")]);
+        assert_eq!(trusted.client_ip(peer, &chain), ip("10.0.0.3"));
+        let chain = headers(&[("forwarded", "for=1.2.3.4, proto=https")]);
+        assert_eq!(trusted.client_ip(peer, &chain), peer);
+    }
+
+    #[tokio::test]
+    async fn extractor_uses_the_trusted_proxies_extension() {
+        async fn extract(trusted: Option<TrustedProxies>) -> IpAddr {
+            let mut request = Request::builder()
+                .header("x-forwarded-for", "203.0.113.7")
+                .body(())
+                .unwrap();
+            request
+                .extensions_mut()
+                .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 50000))));
+            if let Some(trusted) = trusted {
+                request.extensions_mut().insert(trusted);
+            }
+            let (mut parts, ()) = request.into_parts();
+            let ClientIp(ip) = ClientIp::from_request_parts(&mut parts, &()).await.unwrap();
+            ip
+        }
+
+        assert_eq!(extract(None).await, ip("127.0.0.1"));
+        assert_eq!(
+            extract(Some("127.0.0.1".parse().unwrap())).await,
+            ip("203.0.113.7")
+        );
+
+        // ConnectInfo 없이 실행한 서버
+        let (mut parts, ()) = Request::new(()).into_parts();
+        assert!(ClientIp::from_request_parts(&mut parts, &()).await.is_err());
+    }
+}
@@ -0,0 +1,301 @@
+//! 예제들이 함께 쓰는 API 에러 타입
+//!
+//! 여러 예제에 복사되어 있던 `internal_error` 헬퍼 대신 사용.
+//! 핸들러는 `Result<_, ApiError>`를 반환하고, 라이브러리 에러는 `?`로 변환됨.
+//!
+//! 응답은 항상 같은 JSON 형태
+//!
+//! ```json
+//! { "error": { "code": "not_found", "message": "user 1 not found" } }
+//! ```
+//!
+//! | 변형         | 상태 코드 | 메시지                         |
+//! |--------------|-----------|--------------------------------|
+//! | `NotFound`   | 404       | 그대로 노출                    |
+//! | `Conflict`   | 409       | 그대로 노출                    |
+//! | `Validation` | 422       | 그대로 노출                    |
+//! | `Upstream`   | 502       | 고정 문구 (원인은 로그로만)    |
+//! | `Internal`   | 500       | 고정 문구 (원인은 로그로만)    |
+//!
+//! 라이브러리별 `From` 구현은 feature로 켬: `sqlx`, `diesel`, `reqwest`
+//!   → DB 제약 조건 위반은 고정 문구로 변환 (DB 메시지에는 테이블/제약 조건 이름이 들어 있어 로그로만)
+//!
+//! ```toml
+//! example-common-errors = { path = "../common-errors", features = ["sqlx"] }
+//! ```
+//!
+//! tower 미들웨어 에러(timeout, load_shed)는 `handle_middleware_error`로 변환 (`middleware.rs`)
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use std::fmt;
+
+mod middleware;
+
+pub use middleware::handle_middleware_error;
+
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// 🧯 API 에러 분류
+#[derive(Debug)]
+pub enum ApiError {
+    NotFound(String),
+    /// unique 제약 조건 위반, 버전 충돌 등
+    Conflict(String),
+    /// 요청 값이 잘못됨 (형식은 맞지만 처리할 수 없음)
+    Validation(String),
+    /// 외부 서비스(HTTP API 등) 호출 실패
+    Upstream(BoxError),
+    /// 그 밖의 서버 내부 오류
+    Internal(BoxError),
+}
+
+impl ApiError {
+    /// `.map_err(ApiError::internal)?` 형태로 사용 (커넥션 풀 에러 등)
+    pub fn internal(err: impl Into<BoxError>) -> Self {
+        Self::Internal(err.into())
+    }
+
+    pub fn upstream(err: impl Into<BoxError>) -> Self {
+        Self::Upstream(err.into())
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::Upstream(_) => StatusCode::BAD_GATEWAY,
+            Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// 클라이언트가 분기 처리에 쓰는 고정 문자열
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "not_found",
+            Self::Conflict(_) => "conflict",
+            Self::Validation(_) => "validation",
+            Self::Upstream(_) => "upstream",
+            Self::Internal(_) => "internal",
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(message) | Self::Conflict(message) | Self::Validation(message) => {
+                f.write_str(message)
+            }
+            Self::Upstream(err) => write!(f, "upstream error: {err}"),
+            Self::Internal(err) => write!(f, "internal error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+#[derive(Serialize)]
+struct ErrorEnvelope<'a> {
+    error: ErrorBody<'a>,
+}
+
+#[derive(Serialize)]
+struct ErrorBody<'a> {
+    code: &'a str,
+    message: String,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let code = self.code();
+        let message = match self {
+            Self::NotFound(message) | Self::Conflict(message) | Self::Validation(message) => {
+                message
+            }
+            // 내부 에러 내용은 로그로만 남기고 클라이언트에는 숨김
+            Self::Upstream(err) => {
+                tracing::error!(%err, "upstream error");
+                "Upstream service error".to_owned()
+            }
+            Self::Internal(err) => {
+                tracing::error!(%err, "internal error");
+                "Something went wrong".to_owned()
+            }
+        };
+
+        (
+            status,
+            Json(ErrorEnvelope {
+                error: ErrorBody { code, message },
+            }),
+        )
+            .into_response()
+    }
+}
+
+// DB 제약 조건 위반을 클라이언트에 보여 줄 때 쓰는 문구
+#[cfg(any(feature = "sqlx", feature = "diesel"))]
+const UNIQUE_VIOLATION: &str = "resource already exists";
+#[cfg(any(feature = "sqlx", feature = "diesel"))]
+const FOREIGN_KEY_VIOLATION: &str = "referenced resource does not exist";
+
+// 🗄️ sqlx: 행 없음 → 404, unique 위반 → 409, 외래 키 위반 → 422
+#[cfg(feature = "sqlx")]
+impl From<sqlx::Error> for ApiError {
+    fn from(err: sqlx::Error) -> Self {
+        match &err {
+            sqlx::Error::RowNotFound => Self::NotFound("resource not found".to_owned()),
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                tracing::debug!(message = db_err.message(), "unique violation");
+                Self::Conflict(UNIQUE_VIOLATION.to_owned())
+            }
+            sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => {
+                tracing::debug!(message = db_err.message(), "foreign key violation");
+                Self::Validation(FOREIGN_KEY_VIOLATION.to_owned())
+            }
+            _ => Self::internal(err),
+        }
+    }
+}
+
+// 🗄️ diesel: sqlx와 같은 규칙
+#[cfg(feature = "diesel")]
+impl From<diesel::result::Error> for ApiError {
+    fn from(err: diesel::result::Error) -> Self {
+        use diesel::result::{DatabaseErrorKind, Error};
+
+        match &err {
+            Error::NotFound => Self::NotFound("resource not found".to_owned()),
+            Error::DatabaseError(DatabaseErrorKind::UniqueViolation, info) => {
+                tracing::debug!(message = info.message(), "unique violation");
+                Self::Conflict(UNIQUE_VIOLATION.to_owned())
+            }
+            Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, info) => {
+                tracing::debug!(message = info.message(), "foreign key violation");
+                Self::Validation(FOREIGN_KEY_VIOLATION.to_owned())
+            }
+            _ => Self::internal(err),
+        }
+    }
+}
+
+// 🌐 reqwest: 외부 API 호출 실패는 모두 502
+#[cfg(feature = "reqwest")]
+impl From<reqwest::Error> for ApiError {
+    fn from(err: reqwest::Error) -> Self {
+        Self::upstream(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+
+    async fn render(err: ApiError) -> (StatusCode, Value) {
+        let response = err.into_response();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn client_errors_expose_their_message() {
+        for (message, err, status, code) in [
+            (
+                "user 1 not found",
+                ApiError::NotFound as fn(String) -> ApiError,
+                StatusCode::NOT_FOUND,
+                "not_found",
+            ),
+            (
+                "email is already taken",
+                ApiError::Conflict,
+                StatusCode::CONFLICT,
+                "conflict",
+            ),
+            (
+                "quantity must be positive",
+                ApiError::Validation,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "validation",
+            ),
+        ] {
+            assert_eq!(
+                render(err(message.to_owned())).await,
+                (
+                    status,
+                    json!({ "error": { "code": code, "message": message } })
+                )
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn server_errors_hide_their_source() {
+        let (status, body) = render(ApiError::internal("password=hunter2")).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(
+            body,
+            json!({ "error": { "code": "internal", "message": "Something went wrong" } })
+        );
+
+        let (status, body) = render(ApiError::upstream("connection refused")).await;
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+        assert_eq!(body["error"]["code"], "upstream");
+        assert!(!body.to_string().contains("connection refused"));
+    }
+
+    #[cfg(feature = "sqlx")]
+    #[test]
+    fn sqlx_row_not_found_is_404() {
+        assert_eq!(
+            ApiError::from(sqlx::Error::RowNotFound).status(),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            ApiError::from(sqlx::Error::PoolTimedOut).status(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
+    #[cfg(feature = "diesel")]
+    #[test]
+    fn diesel_errors_are_classified() {
+        use diesel::result::{DatabaseErrorKind, Error};
+
+        assert_eq!(
+            ApiError::from(Error::NotFound).status(),
+            StatusCode::NOT_FOUND
+        );
+        // DB 메시지(테이블, 제약 조건 이름)는 응답에 들어가지 않음
+        let unique = ApiError::from(Error::DatabaseError(
+            DatabaseErrorKind::UniqueViolation,
+            Box::new(
+                r#"duplicate key value violates unique constraint "users_email_key""#.to_owned(),
+            ),
+        ));
+        assert_eq!(unique.code(), "conflict");
+        assert_eq!(unique.to_string(), UNIQUE_VIOLATION);
+        let foreign_key = ApiError::from(Error::DatabaseError(
+            DatabaseErrorKind::ForeignKeyViolation,
+            Box::new(
+                r#"insert or update on table "posts" violates foreign key constraint"#.to_owned(),
+            ),
+        ));
+        assert_eq!(foreign_key.code(), "validation");
+        assert_eq!(foreign_key.to_string(), FOREIGN_KEY_VIOLATION);
+        assert_eq!(
+            ApiError::from(Error::RollbackTransaction).code(),
+            "internal"
+        );
+    }
+}
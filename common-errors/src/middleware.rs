@@ -0,0 +1,129 @@
+//! 🧱 `HandleErrorLayer`용 에러 핸들러
+//!
+//! tower 미들웨어(timeout, load_shed 등)의 에러를 2-01_error-handling과 같은 `{"message": ...}` JSON으로 변환
+//!
+//! ```json
+//! { "message": "request timed out", "request_id": "5b0c..." }
+//! ```
+//!
+//! | 에러                           | 상태 코드 | 메시지                                 |
+//! |--------------------------------|-----------|----------------------------------------|
+//! | `timeout::error::Elapsed`      | 408       | request timed out                      |
+//! | `load_shed::error::Overloaded` | 503       | service is overloaded, try again later |
+//! | 그 밖                          | 500       | Something went wrong (원인은 로그로만) |
+//!
+//! `request_id`는 `x-request-id` 헤더 값 (`SetRequestIdLayer`를 바깥에 두면 항상 있음)
+//!
+//! ```rust,ignore
+//! ServiceBuilder::new()
+//!     .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+//!     .layer(PropagateRequestIdLayer::x_request_id())
+//!     .layer(HandleErrorLayer::new(handle_middleware_error))
+//!     .timeout(Duration::from_secs(10))
+//! ```
+
+use crate::BoxError;
+use axum::{
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+#[derive(Serialize)]
+struct ErrorResponse<'a> {
+    message: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<&'a str>,
+}
+
+/// `HandleErrorLayer::new(handle_middleware_error)`
+pub async fn handle_middleware_error(headers: HeaderMap, error: BoxError) -> Response {
+    let request_id = headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok());
+
+    let (status, message) = if error.is::<tower::timeout::error::Elapsed>() {
+        (StatusCode::REQUEST_TIMEOUT, "request timed out")
+    } else if error.is::<tower::load_shed::error::Overloaded>() {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "service is overloaded, try again later",
+        )
+    } else {
+        // 내부 에러 내용은 로그로만 남기고 클라이언트에는 숨김
+        tracing::error!(%error, request_id, "unhandled middleware error");
+        (StatusCode::INTERNAL_SERVER_ERROR, "Something went wrong")
+    };
+
+    (
+        status,
+        Json(ErrorResponse {
+            message,
+            request_id,
+        }),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, error_handling::HandleErrorLayer, http::Request, routing::get, Router};
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use std::time::Duration;
+    use tower::{load_shed::error::Overloaded, ServiceBuilder, ServiceExt};
+
+    async fn render(response: Response) -> (StatusCode, Value) {
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn timeouts_become_408_with_the_request_id() {
+        let app = Router::new()
+            .route(
+                "/slow",
+                get(|| async { tokio::time::sleep(Duration::from_secs(5)).await }),
+            )
+            .layer(
+                ServiceBuilder::new()
+                    .layer(HandleErrorLayer::new(handle_middleware_error))
+                    .timeout(Duration::from_millis(10)),
+            );
+
+        let request = Request::get("/slow")
+            .header(REQUEST_ID_HEADER, "req-1")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(
+            render(response).await,
+            (
+                StatusCode::REQUEST_TIMEOUT,
+                json!({ "message": "request timed out", "request_id": "req-1" })
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn overload_is_503_and_other_errors_are_hidden() {
+        let response = handle_middleware_error(HeaderMap::new(), Box::new(Overloaded::new())).await;
+        assert_eq!(
+            render(response).await,
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                json!({ "message": "service is overloaded, try again later" })
+            )
+        );
+
+        let response = handle_middleware_error(HeaderMap::new(), "password=hunter2".into()).await;
+        let (status, body) = render(response).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body, json!({ "message": "Something went wrong" }));
+    }
+}
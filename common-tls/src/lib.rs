@@ -0,0 +1,201 @@
+//! 예제들이 함께 쓰는 TLS 보조 기능
+//!
+//! 5-10_tls-graceful-shutdown, 5-11_tls-rustls에 복사되어 있던 HTTP → HTTPS 리디렉션 서버를 옮겨 옴.
+//!
+//! ```toml
+//! example-common-tls = { path = "../common-tls" }
+//! ```
+//!
+//! ```rust,ignore
+//! let ports = Ports { http: 7878, https: 3000 };
+//! spawn_http_redirect(ports, shutdown_signal());
+//! ```
+//!
+//! • `Host` 헤더(없으면 요청 URI의 authority)의 호스트에 HTTPS 포트를 붙여 308 Permanent Redirect
+//!   → `X-Forwarded-Host`는 보지 않음 (클라이언트가 마음대로 넣을 수 있음)
+//! • 경로와 쿼리 문자열은 그대로 유지, HTTPS 포트가 443이면 포트 생략
+//! • IPv6 주소(`[::1]:7878`)는 대괄호를 유지
+
+use axum::{
+    extract::Request,
+    handler::HandlerWithoutStateExt,
+    http::{header, uri::Authority, uri::Scheme, StatusCode, Uri},
+    response::{IntoResponse, Redirect, Response},
+};
+use std::{future::Future, net::SocketAddr};
+use tokio::task::JoinHandle;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Ports {
+    /// 리디렉션용 HTTP 포트
+    pub http: u16,
+    /// TLS 처리용 HTTPS 포트
+    pub https: u16,
+}
+
+/// `127.0.0.1:{ports.http}`에서 HTTPS로 리디렉션하는 서버를 백그라운드로 실행
+///
+/// `shutdown`이 끝나면 서버도 멈춤 (멈출 필요가 없으면 `std::future::pending()`)
+pub fn spawn_http_redirect<F>(ports: Ports, shutdown: F) -> JoinHandle<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let redirect = move |request: Request| async move { redirect_response(&request, ports.https) };
+
+    tokio::spawn(async move {
+        let addr = SocketAddr::from(([127, 0, 0, 1], ports.http));
+        let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+        tracing::debug!("redirecting http://{addr} to https (port {})", ports.https);
+
+        axum::serve(listener, redirect.into_make_service())
+            .with_graceful_shutdown(shutdown)
+            .await
+            .unwrap();
+    })
+}
+
+fn redirect_response(request: &Request, https_port: u16) -> Response {
+    let host = request
+        .headers()
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .or_else(|| request.uri().authority().map(Authority::as_str));
+    let Some(host) = host else {
+        return (StatusCode::BAD_REQUEST, "missing Host header").into_response();
+    };
+    match make_https(host, request.uri(), https_port) {
+        Some(uri) => Redirect::permanent(&uri.to_string()).into_response(),
+        None => {
+            tracing::warn!(host, "failed to convert URI to HTTPS");
+            (StatusCode::BAD_REQUEST, "invalid Host header").into_response()
+        }
+    }
+}
+
+/// `host`(포트 포함 가능)와 요청 URI로 HTTPS URI를 만듦, 쓸 수 없는 호스트면 `None`
+fn make_https(host: &str, uri: &Uri, https_port: u16) -> Option<Uri> {
+    let authority: Authority = host.trim().parse().ok()?;
+    // `user@host`는 Host 헤더에 올 수 없는 형식
+    if authority.as_str().contains('@') {
+        return None;
+    }
+    // IPv6 주소는 `[::1]`처럼 대괄호까지 포함
+    let bare_host = authority.host();
+    if bare_host.is_empty() {
+        return None;
+    }
+    let authority = match https_port {
+        443 => bare_host.to_owned(),
+        port => format!("{bare_host}:{port}"),
+    };
+
+    let mut parts = uri.clone().into_parts();
+    parts.scheme = Some(Scheme::HTTPS);
+    parts.authority = Some(authority.parse().ok()?);
+    // 경로가 없으면 "/"로 설정, 쿼리 문자열은 그대로 유지
+    if parts.path_and_query.is_none() {
+        parts.path_and_query = Some("/".parse().unwrap());
+    }
+    Uri::from_parts(parts).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+
+    fn https(host: &str, uri: &str, port: u16) -> Option<String> {
+        make_https(host, &uri.parse().unwrap(), port).map(|uri| uri.to_string())
+    }
+
+    #[test]
+    fn keeps_path_and_query_and_swaps_the_port() {
+        assert_eq!(
+            https("localhost:7878", "/search?q=axum&page=2", 3000).as_deref(),
+            Some("https://localhost:3000/search?q=axum&page=2")
+        );
+        assert_eq!(
+            https("example.com", "/", 443).as_deref(),
+            Some("https://example.com/")
+        );
+        // absolute-form 요청 URI (프록시 형식)
+        assert_eq!(
+            https("example.com:80", "http://example.com:80/a?b", 8443).as_deref(),
+            Some("https://example.com:8443/a?b")
+        );
+    }
+
+    #[test]
+    fn handles_odd_host_values() {
+        // IPv6는 대괄호 유지
+        assert_eq!(
+            https("[::1]:7878", "/", 3000).as_deref(),
+            Some("https://[::1]:3000/")
+        );
+        assert_eq!(
+            https("[2001:db8::1]", "/x", 443).as_deref(),
+            Some("https://[2001:db8::1]/x")
+        );
+        // 대소문자와 끝의 `.`은 그대로 (브라우저가 보낸 대로)
+        assert_eq!(
+            https("Example.COM.:80", "/", 3000).as_deref(),
+            Some("https://Example.COM.:3000/")
+        );
+        assert_eq!(
+            https(" localhost ", "/", 3000).as_deref(),
+            Some("https://localhost:3000/")
+        );
+
+        for host in [
+            "",
+            ":7878",
+            "user@evil.example",
+            "example.com/path",
+            "[::1",
+            "a b",
+        ] {
+            assert_eq!(https(host, "/", 3000), None, "{host:?}");
+        }
+    }
+
+    #[test]
+    fn missing_or_invalid_hosts_are_rejected() {
+        let request = Request::builder()
+            .uri("/?next=1")
+            .header(header::HOST, "localhost:7878")
+            .body(Body::empty())
+            .unwrap();
+        let response = redirect_response(&request, 3000);
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(
+            response.headers()[header::LOCATION],
+            "https://localhost:3000/?next=1"
+        );
+
+        let request = Request::builder().uri("/").body(Body::empty()).unwrap();
+        let response = redirect_response(&request, 3000);
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let request = Request::builder()
+            .uri("/")
+            .header(header::HOST, "user@host")
+            .body(Body::empty())
+            .unwrap();
+        let response = redirect_response(&request, 3000);
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn redirect_server_stops_with_the_shutdown_signal() {
+        let ports = Ports {
+            http: 0,
+            https: 3000,
+        };
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        let server = spawn_http_redirect(ports, async {
+            let _ = rx.await;
+        });
+        tx.send(()).unwrap();
+        server.await.unwrap();
+    }
+}